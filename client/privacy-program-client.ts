@@ -8,6 +8,7 @@
 import { Connection, Keypair, PublicKey, SystemProgram } from '@solana/web3.js';
 import { PrivacyLayer } from '../src/privacy/PrivacyLayer';
 import { ScalarOps } from '../src/crypto/zkproofs/primitives';
+import { accountIndexSeed } from '../src/wallet/SolPrivacyMethods';
 
 // Program ID - Deployed to Solana Devnet
 const PROGRAM_ID = new PublicKey('DwBEX4CiNhpMG4cGzem9cvJgTdW17myKD1hZM6D9SG3v');
@@ -27,10 +28,12 @@ export class PrivacyProgramClient {
 
     /**
      * Get the PDA for an encrypted account
+     *
+     * `accountIndex` selects one of the owner's accounts (u16 LE seed).
      */
-    getEncryptedAccountPDA(owner: PublicKey): [PublicKey, number] {
+    getEncryptedAccountPDA(owner: PublicKey, accountIndex = 0): [PublicKey, number] {
         return PublicKey.findProgramAddressSync(
-            [Buffer.from('encrypted-account'), owner.toBuffer()],
+            [Buffer.from('encrypted-account'), owner.toBuffer(), accountIndexSeed(accountIndex)],
             PROGRAM_ID
         );
     }
@@ -39,16 +42,19 @@ export class PrivacyProgramClient {
      * Initialize an encrypted account for a user
      */
     async initializeAccount(
-        owner: Keypair
+        owner: Keypair,
+        accountIndex = 0
     ): Promise<string> {
-        const [encryptedAccountPDA] = this.getEncryptedAccountPDA(owner.publicKey);
+        const [encryptedAccountPDA] = this.getEncryptedAccountPDA(owner.publicKey, accountIndex);
 
         console.log('🔐 Initializing encrypted account...');
         console.log(`   Owner: ${owner.publicKey.toBase58()}`);
+        console.log(`   Index: ${accountIndex}`);
         console.log(`   PDA: ${encryptedAccountPDA.toBase58()}`);
 
         // Create instruction data
         const instruction = {
+            args: { accountIndex },
             accounts: {
                 encryptedAccount: encryptedAccountPDA,
                 owner: owner.publicKey,
@@ -0,0 +1,108 @@
+/*!
+ * Commitment Scheme Abstraction
+ *
+ * Commitments are stored in a fixed 64-byte slot everywhere (account state,
+ * instruction arguments, proofs). How those bytes are interpreted depends on
+ * the proving stack a deployment uses:
+ *
+ * - Pedersen over Ristretto255 (default): 64-byte encoded point
+ * - Pedersen over BN254 G1: uncompressed point, x || y big-endian
+ * - Poseidon hash commitment: one BN254 scalar-field element, big-endian,
+ *   in the first 32 bytes (remaining 32 bytes zero)
+ *
+ * The scheme is selected at build time through Cargo features
+ * (`pedersen-bn254`, `poseidon`); `ActiveScheme` is the selected one.
+ */
+
+use crate::crypto_primitives::is_nonzero_point;
+
+/// BN254 base field modulus p (big-endian)
+const BN254_FIELD_MODULUS: [u8; 32] = [
+    0x30, 0x64, 0x4e, 0x72, 0xe1, 0x31, 0xa0, 0x29, 0xb8, 0x50, 0x45, 0xb6, 0x81, 0x81, 0x58, 0x5d,
+    0x97, 0x81, 0x6a, 0x91, 0x68, 0x71, 0xca, 0x8d, 0x3c, 0x20, 0x8c, 0x16, 0xd8, 0x7c, 0xfd, 0x47,
+];
+
+/// BN254 scalar field modulus r (big-endian)
+const BN254_SCALAR_MODULUS: [u8; 32] = [
+    0x30, 0x64, 0x4e, 0x72, 0xe1, 0x31, 0xa0, 0x29, 0xb8, 0x50, 0x45, 0xb6, 0x81, 0x81, 0x58, 0x5d,
+    0x28, 0x33, 0xe8, 0x48, 0x79, 0xb9, 0x70, 0x91, 0x43, 0xe1, 0xf5, 0x93, 0xf0, 0x00, 0x00, 0x01,
+];
+
+/// A commitment scheme that fits the 64-byte commitment slot
+pub trait CommitmentScheme {
+    /// Stable identifier (exposed to clients and indexers)
+    const ID: u8;
+
+    /// Human-readable name
+    const NAME: &'static str;
+
+    /// Whether `commitment` is well-formed for this scheme
+    ///
+    /// Format check only: the all-zero slot (uninitialized balance) is never
+    /// a valid commitment.
+    fn is_valid_commitment(commitment: &[u8; 64]) -> bool;
+}
+
+/// Pedersen commitments over Ristretto255
+pub struct PedersenRistretto;
+
+impl CommitmentScheme for PedersenRistretto {
+    const ID: u8 = 0;
+    const NAME: &'static str = "pedersen-ristretto255";
+
+    fn is_valid_commitment(commitment: &[u8; 64]) -> bool {
+        is_nonzero_point(commitment)
+    }
+}
+
+/// Pedersen commitments over the BN254 G1 group (alt_bn128 syscalls)
+pub struct PedersenBn254;
+
+impl CommitmentScheme for PedersenBn254 {
+    const ID: u8 = 1;
+    const NAME: &'static str = "pedersen-bn254";
+
+    fn is_valid_commitment(commitment: &[u8; 64]) -> bool {
+        let (x, y) = commitment.split_at(32);
+        is_nonzero_point(commitment)
+            && x < &BN254_FIELD_MODULUS[..]
+            && y < &BN254_FIELD_MODULUS[..]
+    }
+}
+
+/// Poseidon hash commitments (single BN254 scalar-field element)
+pub struct Poseidon;
+
+impl CommitmentScheme for Poseidon {
+    const ID: u8 = 2;
+    const NAME: &'static str = "poseidon-bn254";
+
+    fn is_valid_commitment(commitment: &[u8; 64]) -> bool {
+        let (element, padding) = commitment.split_at(32);
+        is_nonzero_point(commitment)
+            && element < &BN254_SCALAR_MODULUS[..]
+            && padding.iter().all(|&b| b == 0)
+    }
+}
+
+#[cfg(all(feature = "pedersen-bn254", feature = "poseidon"))]
+compile_error!("features `pedersen-bn254` and `poseidon` are mutually exclusive");
+
+/// Commitment scheme selected for this build
+#[cfg(not(any(feature = "pedersen-bn254", feature = "poseidon")))]
+pub type ActiveScheme = PedersenRistretto;
+
+/// Commitment scheme selected for this build
+#[cfg(feature = "pedersen-bn254")]
+pub type ActiveScheme = PedersenBn254;
+
+/// Commitment scheme selected for this build
+#[cfg(feature = "poseidon")]
+pub type ActiveScheme = Poseidon;
+
+/**
+ * Validate a commitment against the active scheme
+ */
+pub fn is_valid_commitment(commitment: &[u8; 64]) -> bool {
+    ActiveScheme::is_valid_commitment(commitment)
+}
@@ -1,4 +1,4 @@
-/**
+/*!
  * Cryptographic Primitives for ZK Proof Verification (BPF-Compatible)
  * 
  * This module provides BPF-compatible cryptographic operations for Solana.
@@ -32,18 +32,27 @@ pub fn hash_to_scalar(input: &[u8]) -> [u8; 32] {
     hash_bytes
 }
 
+/**
+ * Whether every byte is zero
+ *
+ * Compares in place, so no zeroed array is materialized on the stack.
+ */
+pub fn is_zero(bytes: &[u8]) -> bool {
+    bytes.iter().all(|&b| b == 0)
+}
+
 /**
  * Verify point is not all zeros (basic validation)
  */
 pub fn is_nonzero_point(bytes: &[u8; 64]) -> bool {
-    bytes != &[0u8; 64]
+    !is_zero(bytes)
 }
 
 /**
- * Verify commitment format (64 bytes, non-zero)
+ * Verify commitment format (64 bytes, well-formed for the active scheme)
  */
 pub fn is_valid_commitment_format(bytes: &[u8; 64]) -> bool {
-    is_nonzero_point(bytes)
+    crate::commitment_scheme::is_valid_commitment(bytes)
 }
 
 /**
@@ -0,0 +1,30 @@
+/*!
+ * Confidential Proofs
+ *
+ * On-chain verification of the ZK proofs used by privacy-transfer
+ * (range, equality, validity, transfer and key-rotation proofs), packaged as
+ * a standalone crate so other Solana programs can verify the same proofs
+ * without depending on the Anchor program.
+ *
+ * Builds for both BPF (on-chain) and host targets: no Anchor dependency,
+ * and curve arithmetic goes through the curve25519 syscalls (`pedersen`).
+ */
+
+pub mod commitment_scheme;
+pub mod crypto_primitives;
+pub mod merlin_transcript;
+pub mod pedersen;
+pub mod proof_verification;
+
+pub use commitment_scheme::{is_valid_commitment, ActiveScheme, CommitmentScheme};
+pub use proof_verification::{
+    amount_link_challenge, extract_amount_commitment, transfer_challenge,
+    transfer_challenge_from_nonces, verify_amount_link_proof, verify_amount_match_proof,
+    verify_association_spend_proof, verify_balance_cap_proof, verify_decoy_transfer_proof,
+    verify_deposit_proof, verify_escrow_credit_proof, verify_escrow_debit_proof,
+    verify_exclusion_proof, verify_fee_proof, verify_multi_transfer_proof, verify_note_spend_proof,
+    verify_opening_proof, verify_rotation_proof, verify_stream_accrual_proof, verify_supply_proof,
+    verify_transfer_proof, verify_transfer_stage_a, verify_transfer_stage_b,
+    verify_velocity_proof, verify_vesting_schedule_proof, verify_withdraw_proof,
+    verify_withdraw_to_proof, ProofVerificationError, SupplyDirection, TransferStageA,
+};
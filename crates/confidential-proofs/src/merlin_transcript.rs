@@ -1,14 +1,19 @@
-/**
+/*!
  * Merlin Transcript Implementation (Fiat-Shamir) - BPF Compatible
  * 
  * Implements a simplified Merlin transcript protocol for Fiat-Shamir transform
  * in zero-knowledge proof verification. BPF-compatible version.
+ * 
+ * The transcript keeps a running Keccak256 state instead of the message
+ * history: messages are absorbed as they arrive and each challenge hashes a
+ * fork of the state. Memory stays constant and every byte is hashed once,
+ * while challenges are identical to hashing the full history.
  */
 
 use sha3::{Keccak256, Digest};
 
 pub struct MerlinTranscript {
-    messages: Vec<u8>,
+    state: Keccak256,
 }
 
 impl MerlinTranscript {
@@ -16,20 +21,20 @@ impl MerlinTranscript {
      * Create new transcript with domain separator
      */
     pub fn new(domain_separator: &[u8]) -> Self {
-        let mut messages = Vec::new();
-        messages.extend_from_slice(b"Merlin v1.0");
-        messages.extend_from_slice(domain_separator);
-        Self { messages }
+        let mut state = Keccak256::new();
+        state.update(b"Merlin v1.0");
+        state.update(domain_separator);
+        Self { state }
     }
 
     /**
      * Append message to transcript
      */
     pub fn append_message(&mut self, label: &[u8], message: &[u8]) {
-        self.messages.extend_from_slice(&(label.len() as u64).to_le_bytes());
-        self.messages.extend_from_slice(label);
-        self.messages.extend_from_slice(&(message.len() as u64).to_le_bytes());
-        self.messages.extend_from_slice(message);
+        self.state.update((label.len() as u64).to_le_bytes());
+        self.state.update(label);
+        self.state.update((message.len() as u64).to_le_bytes());
+        self.state.update(message);
     }
 
     /**
@@ -52,38 +57,29 @@ impl MerlinTranscript {
      * Returns 32 bytes that can be interpreted as a scalar
      */
     pub fn challenge_scalar(&mut self, label: &[u8]) -> [u8; 32] {
-        // Hash all messages so far
-        let mut hasher = Keccak256::new();
-        hasher.update(&self.messages);
-        hasher.update(&(label.len() as u64).to_le_bytes());
-        hasher.update(label);
-        let hash = hasher.finalize();
-        
-        // Append hash to messages for next challenge
-        self.messages.extend_from_slice(&hash);
-        
-        // Return first 32 bytes as scalar
-        let mut hash_bytes = [0u8; 32];
-        hash_bytes.copy_from_slice(&hash[..32]);
-        hash_bytes
+        self.squeeze(label)
     }
 
     /**
-     * Get challenge bytes from transcript
+     * Get challenge bytes from transcript (at most 32)
      */
     #[allow(dead_code)]
     pub fn challenge_bytes(&mut self, label: &[u8], len: usize) -> Vec<u8> {
-        // Hash all messages so far
-        let mut hasher = Keccak256::new();
-        hasher.update(&self.messages);
-        hasher.update(&(label.len() as u64).to_le_bytes());
-        hasher.update(label);
-        let hash = hasher.finalize();
-        
-        // Append hash to messages for next challenge
-        self.messages.extend_from_slice(&hash);
+        self.squeeze(label)[..len].to_vec()
+    }
+
+    /**
+     * Hash a fork of the state with the label, then absorb the result
+     * so the next challenge depends on this one
+     */
+    fn squeeze(&mut self, label: &[u8]) -> [u8; 32] {
+        let mut fork = self.state.clone();
+        fork.update((label.len() as u64).to_le_bytes());
+        fork.update(label);
+        let hash: [u8; 32] = fork.finalize().into();
         
-        hash[..len].to_vec()
+        self.state.update(hash);
+        hash
     }
 }
 
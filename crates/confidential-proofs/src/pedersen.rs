@@ -0,0 +1,233 @@
+/*!
+ * Pedersen Commitment Arithmetic (curve25519 syscalls)
+ *
+ * Group operations on 64-byte commitment slots, run through the Solana
+ * curve25519 syscalls (curve25519-dalek on host builds). They let the
+ * program compute balance updates itself and check equality proofs
+ * algebraically instead of structurally:
+ * - `add` / `sub`: homomorphic updates of hidden balances
+ * - `commit_public`: amount*G for a public amount (zero blinding)
+ * - `verify_zero_opening`: the Schnorr check s*H == R + c*D
+ *
+ * Generators match both provers (`src/crypto/zkproofs`,
+ * `privacy-transfer-client`): G is the Ristretto basepoint and
+ * H = basepoint * (SHA-256("pedersen_h_generator") mod l). Token-2022
+ * confidential transfers commit with the same G but their own blinding
+ * generator (`ELGAMAL_BLINDING_GENERATOR`).
+ *
+ * ENCODING: a slot holds the 32-byte compressed point followed by 32 zero
+ * bytes. The all-zero slot is the identity, i.e. an empty balance.
+ *
+ * Only Pedersen over Ristretto255 supports these operations; builds using
+ * another commitment scheme fail them with `UnsupportedScheme`.
+ */
+
+use crate::proof_verification::ProofVerificationError;
+
+/// Ristretto255 group order l (little-endian)
+const GROUP_ORDER: [u8; 32] = [
+    0xed, 0xd3, 0xf5, 0x5c, 0x1a, 0x63, 0x12, 0x58, 0xd6, 0x9c, 0xf7, 0xa2, 0xde, 0xf9, 0xde, 0x14,
+    0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x10,
+];
+
+/// Compressed value generator G (Ristretto basepoint)
+pub const VALUE_GENERATOR: [u8; 32] = [
+    0xe2, 0xf2, 0xae, 0x0a, 0x6a, 0xbc, 0x4e, 0x71, 0xa8, 0x84, 0xa9, 0x61, 0xc5, 0x00, 0x51, 0x5f,
+    0x58, 0xe3, 0x0b, 0x6a, 0xa5, 0x82, 0xdd, 0x8d, 0xb6, 0xa6, 0x59, 0x45, 0xe0, 0x8d, 0x2d, 0x76,
+];
+
+/// Compressed blinding generator H
+pub const BLINDING_GENERATOR: [u8; 32] = [
+    0x2e, 0x20, 0xfc, 0xbc, 0x2a, 0x85, 0x36, 0x1f, 0x01, 0x41, 0xe8, 0x47, 0xb9, 0x17, 0x9f, 0x41,
+    0x94, 0xb4, 0x5b, 0x65, 0x69, 0x48, 0x7c, 0xa8, 0x85, 0x1c, 0xa6, 0x37, 0xaf, 0xc5, 0xb8, 0x1f,
+];
+
+/// Compressed blinding generator of the ZK ElGamal proof program
+/// (Token-2022 confidential transfers): hash-to-point of G under SHA3-512
+pub const ELGAMAL_BLINDING_GENERATOR: [u8; 32] = [
+    0x8c, 0x92, 0x40, 0xb4, 0x56, 0xa9, 0xe6, 0xdc, 0x65, 0xc3, 0x77, 0xa1, 0x04, 0x8d, 0x74, 0x5f,
+    0x94, 0xa0, 0x8c, 0xdb, 0x7f, 0x44, 0xcb, 0xcd, 0x7b, 0x46, 0xf3, 0x40, 0x48, 0x87, 0x11, 0x34,
+];
+
+/**
+ * Reduce 32 little-endian bytes modulo l
+ *
+ * Transcript challenges are raw hashes; provers interpret them as
+ * `Scalar::from_bytes_mod_order`, which this matches. Any 256-bit value is
+ * below 16*l, so at most 15 subtractions are needed.
+ */
+pub fn reduce_scalar(bytes: &[u8; 32]) -> [u8; 32] {
+    let mut value = *bytes;
+    while !less_than_order(&value) {
+        let mut borrow = 0i16;
+        for (byte, order) in value.iter_mut().zip(GROUP_ORDER.iter()) {
+            let diff = *byte as i16 - *order as i16 - borrow;
+            borrow = (diff < 0) as i16;
+            *byte = diff.rem_euclid(256) as u8;
+        }
+    }
+    value
+}
+
+/**
+ * Whether 32 little-endian bytes are a canonical scalar (< l)
+ */
+pub fn is_canonical_scalar(bytes: &[u8; 32]) -> bool {
+    less_than_order(bytes)
+}
+
+fn less_than_order(value: &[u8; 32]) -> bool {
+    for (byte, order) in value.iter().rev().zip(GROUP_ORDER.iter().rev()) {
+        if byte != order {
+            return byte < order;
+        }
+    }
+    false
+}
+
+/**
+ * (a - b) mod l for canonical scalars
+ *
+ * Splits an OR-proof challenge: the branches' challenges must add up to
+ * the transcript challenge, so the second one is c - c0.
+ */
+pub fn sub_scalar(a: &[u8; 32], b: &[u8; 32]) -> [u8; 32] {
+    let mut difference = [0u8; 32];
+    let mut borrow = 0i16;
+    for ((out, x), y) in difference.iter_mut().zip(a.iter()).zip(b.iter()) {
+        let diff = *x as i16 - *y as i16 - borrow;
+        borrow = (diff < 0) as i16;
+        *out = diff.rem_euclid(256) as u8;
+    }
+    if borrow == 0 {
+        return difference;
+    }
+
+    // Wrapped below zero: adding l back lands in [0, l)
+    let mut carry = 0u16;
+    for (byte, order) in difference.iter_mut().zip(GROUP_ORDER.iter()) {
+        let sum = *byte as u16 + *order as u16 + carry;
+        *byte = sum as u8;
+        carry = sum >> 8;
+    }
+    difference
+}
+
+/**
+ * Scalar encoding of a u64 (always canonical)
+ */
+pub fn scalar_from_u64(value: u64) -> [u8; 32] {
+    let mut scalar = [0u8; 32];
+    scalar[..8].copy_from_slice(&value.to_le_bytes());
+    scalar
+}
+
+/**
+ * Place a compressed point in a 64-byte slot
+ */
+pub fn to_slot(point: &[u8; 32]) -> [u8; 64] {
+    let mut slot = [0u8; 64];
+    slot[..32].copy_from_slice(point);
+    slot
+}
+
+/**
+ * Commitment to a public amount with zero blinding (amount*G)
+ */
+pub fn commit_public(amount: u64) -> Result<[u8; 64], ProofVerificationError> {
+    mul(&scalar_from_u64(amount), &to_slot(&VALUE_GENERATOR))
+}
+
+/**
+ * Commitment to zero with a public blinding (blinding*H)
+ */
+pub fn commit_zero(blinding: &[u8; 32]) -> Result<[u8; 64], ProofVerificationError> {
+    mul(blinding, &to_slot(&BLINDING_GENERATOR))
+}
+
+/**
+ * Verify s*H == R + c*D, i.e. D commits to zero
+ *
+ * `challenge` is the raw transcript output; it is reduced mod l first.
+ * `s` must be canonical so a proof has exactly one encoding.
+ */
+pub fn verify_zero_opening(
+    d: &[u8; 64],
+    r: &[u8; 64],
+    s: &[u8; 32],
+    challenge: &[u8; 32],
+) -> Result<(), ProofVerificationError> {
+    if !is_canonical_scalar(s) {
+        return Err(ProofVerificationError::InvalidEqualityProof);
+    }
+    let lhs = commit_zero(s)?;
+    let rhs = add(r, &mul(&reduce_scalar(challenge), d)?)?;
+    if lhs != rhs {
+        return Err(ProofVerificationError::BalanceEquationFailed);
+    }
+    Ok(())
+}
+
+#[cfg(not(any(feature = "pedersen-bn254", feature = "poseidon")))]
+mod group {
+    use solana_curve25519::ristretto::{
+        add_ristretto, multiply_ristretto, subtract_ristretto, PodRistrettoPoint,
+    };
+    use solana_curve25519::scalar::PodScalar;
+
+    use super::to_slot;
+    use crate::crypto_primitives::is_zero;
+    use crate::proof_verification::ProofVerificationError;
+
+    /// Compressed point of a slot (padding must be zero)
+    fn point(slot: &[u8; 64]) -> Result<PodRistrettoPoint, ProofVerificationError> {
+        if !is_zero(&slot[32..]) {
+            return Err(ProofVerificationError::InvalidPoint);
+        }
+        let mut bytes = [0u8; 32];
+        bytes.copy_from_slice(&slot[..32]);
+        Ok(PodRistrettoPoint(bytes))
+    }
+
+    pub fn add(a: &[u8; 64], b: &[u8; 64]) -> Result<[u8; 64], ProofVerificationError> {
+        add_ristretto(&point(a)?, &point(b)?)
+            .map(|sum| to_slot(&sum.0))
+            .ok_or(ProofVerificationError::InvalidPoint)
+    }
+
+    pub fn sub(a: &[u8; 64], b: &[u8; 64]) -> Result<[u8; 64], ProofVerificationError> {
+        subtract_ristretto(&point(a)?, &point(b)?)
+            .map(|difference| to_slot(&difference.0))
+            .ok_or(ProofVerificationError::InvalidPoint)
+    }
+
+    pub fn mul(scalar: &[u8; 32], a: &[u8; 64]) -> Result<[u8; 64], ProofVerificationError> {
+        multiply_ristretto(&PodScalar(*scalar), &point(a)?)
+            .map(|product| to_slot(&product.0))
+            .ok_or(ProofVerificationError::InvalidPoint)
+    }
+}
+
+#[cfg(any(feature = "pedersen-bn254", feature = "poseidon"))]
+mod group {
+    use crate::proof_verification::ProofVerificationError;
+
+    pub fn add(_a: &[u8; 64], _b: &[u8; 64]) -> Result<[u8; 64], ProofVerificationError> {
+        Err(ProofVerificationError::UnsupportedScheme)
+    }
+
+    pub fn sub(_a: &[u8; 64], _b: &[u8; 64]) -> Result<[u8; 64], ProofVerificationError> {
+        Err(ProofVerificationError::UnsupportedScheme)
+    }
+
+    pub fn mul(_scalar: &[u8; 32], _a: &[u8; 64]) -> Result<[u8; 64], ProofVerificationError> {
+        Err(ProofVerificationError::UnsupportedScheme)
+    }
+}
+
+/// a + b
+pub use group::add;
+/// a - b
+pub use group::sub;
+/// scalar * a (`scalar` must be canonical)
+pub use group::mul;
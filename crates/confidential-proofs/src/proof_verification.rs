@@ -0,0 +1,2609 @@
+/*!
+ * On-Chain ZK Proof Verification Module (BPF-Compatible)
+ * 
+ * This module provides BPF-compatible proof verification for Solana.
+ * 
+ * IMPORTANT LIMITATION: Due to Solana's 4KB stack limit, full elliptic curve
+ * operations (curve25519-dalek) cannot be used on-chain. This implementation:
+ * 
+ * 1. Performs basic validation (format, size, non-zero checks)
+ * 2. Validates proof structure and commitments
+ * 3. Verifies commitment format and consistency
+ * 
+ * Full cryptographic verification (elliptic curve operations, scalar arithmetic)
+ * should be performed off-chain or using a compute-efficient approach.
+ * 
+ * STATUS: BPF-compatible basic validation with structure for full verification
+ * 
+ * STACK FOOTPRINT: SBF limits each call frame to 4KB, so the verification
+ * path keeps frames small to leave room for the curve syscalls:
+ * - Parsed proofs are borrowed views into the proof data (no copies)
+ * - Zero checks compare in place (`is_zero`) instead of building zeroed
+ *   arrays to compare against
+ * - Parsing, each range/equality/validity check and the transcript run in
+ *   their own `#[inline(never)]` frames rather than being merged into the
+ *   caller, so composite verifiers only hold the views and their inputs
+ */
+
+use std::result::Result;
+use crate::crypto_primitives::{is_nonzero_point, is_valid_commitment_format, is_zero, constant_time_eq};
+use crate::merlin_transcript::{MerlinTranscript, rangeproof_domain_sep};
+use crate::pedersen;
+use privacy_transfer_types::proof_constants;
+pub use privacy_transfer_types::{
+    BulletproofRangeProof, Commitment, EqualityProof, TransferProof, ValidityProof,
+};
+
+/// Error codes for proof verification
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProofVerificationError {
+    DeserializationFailed,
+    InvalidRangeProof,
+    InvalidEqualityProof,
+    InvalidValidityProof,
+    BalanceEquationFailed,
+    CommitmentMismatch,
+    InvalidPoint,
+    InvalidProofStructure,
+    InvalidCommitment, // Added for commitment validation
+    UnsupportedScheme, // Group arithmetic needs Pedersen over Ristretto255
+}
+
+impl From<&str> for ProofVerificationError {
+    fn from(_: &str) -> Self {
+        ProofVerificationError::InvalidPoint
+    }
+}
+
+/// Supply adjustment proof (confidential mint / burn)
+#[derive(Debug, Clone)]
+pub struct SupplyProof<'a> {
+    /// Range proof for the minted/burned amount
+    pub amount_range_proof: BulletproofRangeProof<'a>,
+    /// Range proof for the account's balance after the adjustment
+    pub balance_after_range_proof: BulletproofRangeProof<'a>,
+    /// Equality proof linking old, amount and new commitments
+    pub equality_proof: EqualityProof<'a>,
+}
+
+/// Public-amount balance update proof (deposit / withdrawal)
+#[derive(Debug, Clone)]
+pub struct PublicAmountProof<'a> {
+    /// Range proof for the account's balance after the update
+    pub balance_after_range_proof: BulletproofRangeProof<'a>,
+    /// Proof that C_new - C_old -/+ amount*G opens to zero (knowledge of blinding)
+    pub equality_proof: EqualityProof<'a>,
+}
+
+/// Shielded note spend proof (Groth16-shaped: A in G1, B in G2, C in G1)
+///
+/// Proves knowledge of a note in the tree under `root` whose nullifier is
+/// `nullifier`, without revealing which leaf it is.
+#[derive(Debug, Clone)]
+pub struct NoteSpendProof<'a> {
+    pub a: &'a [u8; 64],
+    pub b: &'a [u8; 128],
+    pub c: &'a [u8; 64],
+}
+
+/// Transfer to one real recipient hidden among decoy outputs
+#[derive(Debug, Clone)]
+pub struct DecoyTransferProof<'a> {
+    /// Range proof for amount
+    pub amount_range_proof: BulletproofRangeProof<'a>,
+    /// Range proof for sender's new balance
+    pub sender_after_range_proof: BulletproofRangeProof<'a>,
+    /// Equality proof for the sender balance equation
+    pub sender_equality_proof: EqualityProof<'a>,
+    /// One OR-proof per output: delta opens to 0 (decoy) or to the amount
+    pub output_proofs: Vec<OutputProof<'a>>,
+    /// Proof that the output deltas minus the amount open to zero
+    pub sum_proof: EqualityProof<'a>,
+}
+
+/// OR-proof that an output delta commits to zero or to the amount
+///
+/// The branch challenges add up to the transcript challenge; the amount
+/// branch's challenge is implied (c - zero_challenge).
+#[derive(Debug, Clone)]
+pub struct OutputProof<'a> {
+    pub zero_challenge: &'a [u8; 32],
+    pub zero_branch: EqualityProof<'a>,
+    pub amount_branch: EqualityProof<'a>,
+}
+
+/// Per-epoch velocity limit proof for an encrypted outgoing-transfer total
+#[derive(Debug, Clone)]
+pub struct VelocityProof<'a> {
+    /// Range proof that limit*G - total_new opens to a value in [0, 2^n)
+    pub headroom_range_proof: BulletproofRangeProof<'a>,
+}
+
+/// One sender to N recipients, with hidden individual amounts
+#[derive(Debug, Clone)]
+pub struct MultiTransferProof<'a> {
+    /// Range proof for sender's new balance
+    pub sender_after_range_proof: BulletproofRangeProof<'a>,
+    /// Equality proof for sender_old - sender_new = sum of amounts
+    pub sender_equality_proof: EqualityProof<'a>,
+    /// One leg per recipient, in recipient order
+    pub legs: Vec<TransferLeg<'a>>,
+}
+
+/// Per-recipient part of a multi-recipient transfer proof
+#[derive(Debug, Clone)]
+pub struct TransferLeg<'a> {
+    /// Commitment to this recipient's amount
+    pub amount_commitment: &'a [u8; 64],
+    /// Range proof for this recipient's amount
+    pub amount_range_proof: BulletproofRangeProof<'a>,
+    /// Equality proof for recipient_new - recipient_old = amount
+    pub recipient_equality_proof: EqualityProof<'a>,
+}
+
+/// Debit of a hidden amount from a balance into an escrow
+#[derive(Debug, Clone)]
+pub struct EscrowDebitProof<'a> {
+    /// Range proof for the escrowed amount
+    pub amount_range_proof: BulletproofRangeProof<'a>,
+    /// Range proof for the owner's new balance
+    pub owner_after_range_proof: BulletproofRangeProof<'a>,
+    /// Equality proof for owner_old - owner_new = amount
+    pub owner_equality_proof: EqualityProof<'a>,
+}
+
+/// Hidden protocol fee on a confidential transfer
+#[derive(Debug, Clone)]
+pub struct FeeProof<'a> {
+    /// Range proof that the remainder commitment V opens to a value in [0, 2^14)
+    pub remainder_range_proof: BulletproofRangeProof<'a>,
+    /// Proof that bps*C_amount - 10_000*C_fee - V opens to zero
+    pub remainder_equality_proof: EqualityProof<'a>,
+}
+
+/// Proof that a Token-2022 amount commitment and a program commitment hide
+/// the same value
+#[derive(Debug, Clone)]
+pub struct AmountLinkProof<'a> {
+    /// Nonce commitment under the ElGamal blinding generator
+    pub a_token: &'a [u8; 64],
+    /// Nonce commitment under the program's blinding generator
+    pub a_amount: &'a [u8; 64],
+    /// Response for the shared value
+    pub s_value: &'a [u8; 32],
+    /// Response for the Token-2022 blinding
+    pub s_token: &'a [u8; 32],
+    /// Response for the program blinding
+    pub s_amount: &'a [u8; 32],
+}
+
+/// Direction of a supply adjustment (selects the transcript domain)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SupplyDirection {
+    /// new = old + amount
+    Mint,
+    /// new = old - amount
+    Burn,
+}
+
+/**
+ * Extract amount commitment from proof data (without full deserialization)
+ * 
+ * SECURITY: This function extracts only the amount commitment (first 64 bytes)
+ * to avoid full deserialization overhead. Used for parameter validation.
+ * 
+ * @param proof_data - Serialized proof data
+ * @returns Amount commitment (64 bytes)
+ */
+pub fn extract_amount_commitment(proof_data: &[u8]) -> Result<[u8; 64], ProofVerificationError> {
+    // Validate minimum size
+    if proof_data.len() < 64 {
+        return Err(ProofVerificationError::DeserializationFailed);
+    }
+    
+    // Extract first 64 bytes as amount commitment
+    let commitment = read_ref::<64>(proof_data, &mut 0)?;
+    
+    // SECURITY: Validate commitment is not all zeros
+    if is_zero(commitment) {
+        return Err(ProofVerificationError::InvalidCommitment);
+    }
+    
+    Ok(*commitment)
+}
+
+/**
+ * Deserialize proof data from bytes
+ * 
+ * PROOF DATA FORMAT:
+ * [amount_range_proof][sender_after_range_proof][recipient_after_range_proof][validity_proof]
+ * 
+ * Each range proof structure:
+ * - commitment: 64 bytes
+ * - A: 64 bytes
+ * - S: 64 bytes
+ * - T1: 64 bytes
+ * - T2: 64 bytes
+ * - taux: 32 bytes
+ * - mu: 32 bytes
+ * - t: 32 bytes
+ * - n: 1 byte
+ * 
+ * Each range proof: 449 bytes
+ * Validity proof: 192 bytes
+ * Total: 1539 bytes
+ * 
+ * The result is a borrowed view: fields reference `proof_data` directly,
+ * so parsing neither copies nor allocates.
+ */
+#[inline(never)]
+pub fn deserialize_proof_data(proof_data: &[u8]) -> Result<TransferProof<'_>, ProofVerificationError> {
+    // Validate minimum size (must have at least basic structure)
+    if proof_data.len() < proof_constants::MIN_PROOF_DATA_SIZE {
+        return Err(ProofVerificationError::DeserializationFailed);
+    }
+    
+    // Validate proof data is not too large (DoS protection)
+    if proof_data.len() > proof_constants::MAX_PROOF_DATA_SIZE {
+        return Err(ProofVerificationError::DeserializationFailed);
+    }
+
+    // SECURITY: Reject invalid proof data
+    // Check for common invalid patterns that indicate malformed proofs
+    if proof_data.len() < 512 {
+        // Minimum size for a valid proof structure
+        return Err(ProofVerificationError::DeserializationFailed);
+    }
+
+    // SECURITY: Reject all-zero proof data (common invalid pattern)
+    if is_zero(&proof_data[..256]) {
+        return Err(ProofVerificationError::DeserializationFailed);
+    }
+
+    // Parse proof data structure (borrowed: every field points into proof_data)
+    let mut offset = 0;
+    
+    // Parse amount range proof
+    let amount_range_proof = read_range_proof(proof_data, &mut offset)?;
+
+    // Parse sender_after range proof (same structure)
+    let sender_after_range_proof = read_range_proof(proof_data, &mut offset)?;
+
+    // Parse recipient_after range proof (same structure)
+    let recipient_after_range_proof = read_range_proof(proof_data, &mut offset)?;
+
+    // Parse validity proof (equality proofs)
+    // SECURITY: read_equality_proof rejects all-zero R or s
+    let sender_equality_proof = read_equality_proof(proof_data, &mut offset)?;
+    let recipient_equality_proof = read_equality_proof(proof_data, &mut offset)?;
+
+    let validity_proof = ValidityProof {
+        sender_equality_proof,
+        recipient_equality_proof,
+    };
+
+    Ok(TransferProof {
+        amount_range_proof,
+        sender_after_range_proof,
+        recipient_after_range_proof,
+        validity_proof,
+    })
+}
+
+/**
+ * Borrow a fixed-size array at `offset`, advancing it
+ * 
+ * Bounds are checked without overflow; nothing is copied.
+ */
+fn read_ref<'a, const N: usize>(data: &'a [u8], offset: &mut usize) -> Result<&'a [u8; N], ProofVerificationError> {
+    let end = offset.checked_add(N).ok_or(ProofVerificationError::DeserializationFailed)?;
+    let bytes = data
+        .get(*offset..end)
+        .ok_or(ProofVerificationError::DeserializationFailed)?;
+    *offset = end;
+    bytes.try_into().map_err(|_| ProofVerificationError::DeserializationFailed)
+}
+
+/**
+ * Read an equality proof (R: 64, s: 32) at `offset`
+ */
+fn read_equality_proof<'a>(data: &'a [u8], offset: &mut usize) -> Result<EqualityProof<'a>, ProofVerificationError> {
+    let r = read_ref::<64>(data, offset)?;
+    let s = read_ref::<32>(data, offset)?;
+    if is_zero(r) || is_zero(s) {
+        return Err(ProofVerificationError::InvalidEqualityProof);
+    }
+    Ok(EqualityProof { r, s })
+}
+
+/**
+ * Read one serialized range proof at `offset`, advancing it
+ * 
+ * Layout: commitment, A, S, T1, T2 (64 bytes each), taux, mu, t
+ * (32 bytes each), n (1 byte, defaults to 64 at the end of the data).
+ * The inner product proof is not transmitted (not verified on-chain).
+ */
+#[inline(never)]
+fn read_range_proof<'a>(data: &'a [u8], offset: &mut usize) -> Result<BulletproofRangeProof<'a>, ProofVerificationError> {
+    let commitment = read_ref::<64>(data, offset)?;
+    let a = read_ref::<64>(data, offset)?;
+    let s = read_ref::<64>(data, offset)?;
+    let t1 = read_ref::<64>(data, offset)?;
+    let t2 = read_ref::<64>(data, offset)?;
+    let taux = read_ref::<32>(data, offset)?;
+    let mu = read_ref::<32>(data, offset)?;
+    let t = read_ref::<32>(data, offset)?;
+    
+    // SECURITY: Validate parsed data is not all zeros (reject dummy proofs)
+    if is_zero(commitment)
+        || is_zero(a)
+        || is_zero(s)
+        || is_zero(taux)
+        || is_zero(mu)
+        || is_zero(t) {
+        return Err(ProofVerificationError::InvalidRangeProof);
+    }
+
+    // Read n (range size) - default to 64 if not present
+    let n = data.get(*offset).copied().unwrap_or(64u8);
+    *offset += 1;
+
+    Ok(BulletproofRangeProof {
+        commitment,
+        a,
+        s,
+        t1,
+        t2,
+        taux,
+        mu,
+        t,
+        n,
+    })
+}
+
+/**
+ * Verify a Bulletproof range proof (BPF-compatible enhanced validation)
+ * 
+ * VERIFICATION STEPS (on-chain):
+ * 1. Validate commitment format (non-zero, 64 bytes)
+ * 2. Validate proof structure (all commitments non-zero)
+ * 3. Validate commitment matches proof.commitment
+ * 4. Basic transcript validation
+ * 5. Enhanced structural validation (NEW)
+ * 
+ * SECURITY IMPROVEMENTS:
+ * - Validates all proof components are distinct (prevents dummy proofs)
+ * - Checks proof structure consistency
+ * - Validates scalar ranges
+ * - Rejects obviously invalid proof patterns
+ * 
+ * NOTE: Full cryptographic verification (elliptic curve operations,
+ * scalar arithmetic, multi-scalar multiplication) is NOT performed
+ * on-chain due to Solana's 4KB stack limit. Full verification should
+ * be done off-chain or using a compute-efficient approach.
+ * 
+ * This enhanced validation provides stronger security guarantees while
+ * remaining BPF-compatible.
+ */
+#[inline(never)]
+pub fn verify_range_proof(
+    proof: &BulletproofRangeProof,
+    commitment: &[u8; 64],
+) -> Result<(), ProofVerificationError> {
+    // Validate commitment format
+    if !is_valid_commitment_format(commitment) {
+        return Err(ProofVerificationError::InvalidRangeProof);
+    }
+    
+    // Verify commitment matches proof.commitment
+    if !constant_time_eq(proof.commitment, commitment) {
+        return Err(ProofVerificationError::CommitmentMismatch);
+    }
+    
+    // Validate all proof commitments are non-zero
+    if !is_nonzero_point(proof.a)
+        || !is_nonzero_point(proof.s)
+        || !is_nonzero_point(proof.t1)
+        || !is_nonzero_point(proof.t2)
+    {
+        return Err(ProofVerificationError::InvalidRangeProof);
+    }
+    
+    // Validate scalars are non-zero (basic check)
+    if is_zero(proof.taux) || is_zero(proof.mu) || is_zero(proof.t) {
+        return Err(ProofVerificationError::InvalidRangeProof);
+    }
+    
+    // Basic transcript validation (structure only)
+    let domain_sep = rangeproof_domain_sep(proof.n, 1);
+    let mut transcript = MerlinTranscript::new(&domain_sep);
+    transcript.append_point(b"V", proof.commitment);
+    transcript.append_point(b"A", proof.a);
+    transcript.append_point(b"S", proof.s);
+    
+    // Get challenges (for structure validation)
+    let _y = transcript.challenge_scalar(b"y");
+    let _z = transcript.challenge_scalar(b"z");
+    
+    transcript.append_point(b"T1", proof.t1);
+    transcript.append_point(b"T2", proof.t2);
+    
+    let _x = transcript.challenge_scalar(b"x");
+    
+    // SECURITY: Enhanced validation to reject obviously invalid proofs
+    // While full cryptographic verification is not performed on-chain due to
+    // Solana's 4KB stack limit, we perform strict structural validation to
+    // reject invalid proof data.
+    
+    // Validate that proof components are not identical (would indicate dummy data)
+    if constant_time_eq(proof.a, proof.s) 
+        || constant_time_eq(proof.t1, proof.t2)
+        || constant_time_eq(proof.taux, proof.mu) {
+        return Err(ProofVerificationError::InvalidRangeProof);
+    }
+    
+    // SECURITY: Validate proof components are not all zeros (additional check)
+    if is_zero(proof.taux) || is_zero(proof.mu) || is_zero(proof.t) {
+        return Err(ProofVerificationError::InvalidRangeProof);
+    }
+    
+    // SECURITY: Validate commitment is not equal to other proof components
+    // (prevents reuse of commitments as proof components)
+    if constant_time_eq(commitment, proof.a)
+        || constant_time_eq(commitment, proof.s)
+        || constant_time_eq(commitment, proof.t1)
+        || constant_time_eq(commitment, proof.t2) {
+        return Err(ProofVerificationError::InvalidRangeProof);
+    }
+    
+    // SECURITY: Validate range size is reasonable (prevent DoS)
+    if proof.n == 0 || proof.n > 64 {
+        return Err(ProofVerificationError::InvalidRangeProof);
+    }
+    
+    // NOTE: Full cryptographic verification (elliptic curve operations,
+    // scalar arithmetic, multi-scalar multiplication) is NOT performed
+    // on-chain due to Solana's 4KB stack limit.
+    // 
+    // Full verification should be done off-chain or using a compute-efficient
+    // approach. This on-chain implementation performs strict structural validation
+    // to reject invalid proofs while maintaining BPF compatibility.
+    //
+    // SECURITY MODEL:
+    // - On-chain: Structural validation (this function)
+    // - Off-chain: Full cryptographic verification (required)
+    // - Hybrid: Both validations must pass for transaction acceptance
+    
+    Ok(())
+}
+
+/**
+ * Verify equality proof (BPF-compatible basic validation)
+ * 
+ * VERIFICATION STEPS (on-chain):
+ * 1. Validate commitment format (non-zero, 64 bytes)
+ * 2. Validate proof structure
+ * 
+ * NOTE: Full cryptographic verification (elliptic curve operations)
+ * is NOT performed on-chain due to Solana's 4KB stack limit.
+ */
+#[inline(never)]
+pub fn verify_equality_proof(
+    proof: &EqualityProof,
+    commitment1: &[u8; 64],
+    commitment2: &[u8; 64],
+) -> Result<(), ProofVerificationError> {
+    // Validate commitments are not all zeros
+    if !is_nonzero_point(commitment1) || !is_nonzero_point(commitment2) {
+        return Err(ProofVerificationError::InvalidEqualityProof);
+    }
+    
+    // Validate proof structure
+    if !is_nonzero_point(proof.r) || is_zero(proof.s) {
+        return Err(ProofVerificationError::InvalidEqualityProof);
+    }
+    
+    // SECURITY: Additional validation to reject obviously invalid proofs
+    // Reject if R and s are identical (would indicate dummy data)
+    let r_first_32 = &proof.r[..32];
+    if constant_time_eq(r_first_32, proof.s) {
+        return Err(ProofVerificationError::InvalidEqualityProof);
+    }
+    
+    // NOTE: Full cryptographic verification (R + s*G == commitment1 - commitment2)
+    // is NOT performed on-chain due to Solana's 4KB stack limit.
+    // Full verification should be done off-chain.
+    // This implementation performs strict structural validation to reject invalid proofs.
+    
+    Ok(())
+}
+
+/**
+ * Verify validity proof (curve25519 syscalls)
+ * 
+ * VERIFICATION STEPS (on-chain):
+ * 1. Validate commitments are non-zero (the recipient's old balance may be
+ *    the identity: a new account starts empty)
+ * 2. Sender: check s*H == R + c*(C_sender_old - C_sender_new - C_amount)
+ * 3. Recipient: check s*H == R + c*(C_recipient_new - C_recipient_old - C_amount)
+ * 
+ * `challenge` is the transfer challenge (see transfer_challenge). Both
+ * differences commit to zero exactly when the amount leaves the sender and
+ * reaches the recipient.
+ */
+#[inline(never)]
+pub fn verify_validity_proof(
+    proof: &ValidityProof,
+    sender_old_commitment: &[u8; 64],
+    amount_commitment: &[u8; 64],
+    sender_new_commitment: &[u8; 64],
+    recipient_old_commitment: &[u8; 64],
+    recipient_new_commitment: &[u8; 64],
+    challenge: &[u8; 32],
+) -> Result<(), ProofVerificationError> {
+    // Validate commitments are not all zeros
+    if !is_nonzero_point(sender_old_commitment)
+        || !is_nonzero_point(amount_commitment)
+        || !is_nonzero_point(sender_new_commitment)
+        || !is_nonzero_point(recipient_new_commitment)
+    {
+        return Err(ProofVerificationError::InvalidValidityProof);
+    }
+    
+    // Sender loses exactly the amount
+    let sender_d = pedersen::sub(
+        &pedersen::sub(sender_old_commitment, sender_new_commitment)?,
+        amount_commitment,
+    )?;
+    pedersen::verify_zero_opening(
+        &sender_d,
+        proof.sender_equality_proof.r,
+        proof.sender_equality_proof.s,
+        challenge,
+    )?;
+    
+    // Recipient gains exactly the amount
+    let recipient_d = pedersen::sub(
+        &pedersen::sub(recipient_new_commitment, recipient_old_commitment)?,
+        amount_commitment,
+    )?;
+    pedersen::verify_zero_opening(
+        &recipient_d,
+        proof.recipient_equality_proof.r,
+        proof.recipient_equality_proof.s,
+        challenge,
+    )?;
+    
+    Ok(())
+}
+
+/**
+ * Verify complete transfer proof (BPF-compatible)
+ * 
+ * VERIFICATION STEPS:
+ * 1. Deserialize proof data
+ * 2. Verify amount range proof (basic validation)
+ * 3. Verify sender_after range proof (basic validation)
+ * 4. Verify recipient_after range proof (basic validation)
+ * 5. Verify commitments match
+ * 6. Derive the challenge over all five commitments (see transfer_challenge)
+ * 7. Verify the validity proof against it (see verify_validity_proof)
+ */
+pub fn verify_transfer_proof(
+    proof_data: &[u8],
+    amount_commitment: &[u8; 64],
+    sender_after_commitment: &[u8; 64],
+    sender_old_commitment: &[u8; 64],
+    recipient_old_commitment: &[u8; 64],
+    recipient_new_commitment: &[u8; 64],
+) -> Result<(), ProofVerificationError> {
+    // Deserialize proof data
+    let proof = deserialize_proof_data(proof_data)?;
+
+    // Verify amount range proof (basic validation)
+    verify_range_proof(&proof.amount_range_proof, amount_commitment)?;
+
+    // Verify sender_after range proof (basic validation)
+    verify_range_proof(&proof.sender_after_range_proof, sender_after_commitment)?;
+
+    // Verify recipient_after range proof (basic validation)
+    verify_range_proof(&proof.recipient_after_range_proof, recipient_new_commitment)?;
+
+    // Verify commitments match
+    if !constant_time_eq(proof.amount_range_proof.commitment, amount_commitment) {
+        return Err(ProofVerificationError::CommitmentMismatch);
+    }
+    if !constant_time_eq(proof.sender_after_range_proof.commitment, sender_after_commitment) {
+        return Err(ProofVerificationError::CommitmentMismatch);
+    }
+    if !constant_time_eq(proof.recipient_after_range_proof.commitment, recipient_new_commitment) {
+        return Err(ProofVerificationError::CommitmentMismatch);
+    }
+
+    let c = transfer_challenge(
+        amount_commitment,
+        sender_old_commitment,
+        sender_after_commitment,
+        recipient_old_commitment,
+        recipient_new_commitment,
+        &proof.validity_proof,
+    );
+
+    // Both balance updates, checked algebraically
+    verify_validity_proof(
+        &proof.validity_proof,
+        sender_old_commitment,
+        amount_commitment,
+        sender_after_commitment,
+        recipient_old_commitment,
+        recipient_new_commitment,
+        &c,
+    )?;
+
+    Ok(())
+}
+
+/**
+ * Fiat-Shamir challenge of a transfer proof
+ * 
+ * Every commitment of the transfer (including both pre-transfer balances)
+ * is absorbed before the challenge, so a proof is tied to the exact state
+ * it was built against. Provers and off-chain verifiers must derive `c`
+ * the same way.
+ * 
+ * TRANSCRIPT LAYOUT (domain "transfer"):
+ * 1. C_amount         - amount commitment
+ * 2. C_sender_old     - sender balance before
+ * 3. C_sender_new     - sender balance after
+ * 4. C_recipient_old  - recipient balance before
+ * 5. C_recipient_new  - recipient balance after
+ * 6. R_sender         - sender equality proof R
+ * 7. R_recipient      - recipient equality proof R
+ * 
+ * The challenge is squeezed with label "c". Points are appended as labeled
+ * 64-byte messages (see MerlinTranscript).
+ */
+#[inline(never)]
+pub fn transfer_challenge(
+    amount_commitment: &[u8; 64],
+    sender_old_commitment: &[u8; 64],
+    sender_new_commitment: &[u8; 64],
+    recipient_old_commitment: &[u8; 64],
+    recipient_new_commitment: &[u8; 64],
+    validity_proof: &ValidityProof,
+) -> [u8; 32] {
+    transfer_challenge_from_nonces(
+        amount_commitment,
+        sender_old_commitment,
+        sender_new_commitment,
+        recipient_old_commitment,
+        recipient_new_commitment,
+        validity_proof.sender_equality_proof.r,
+        validity_proof.recipient_equality_proof.r,
+    )
+}
+
+/**
+ * transfer_challenge from the two equality-proof nonce commitments
+ * 
+ * Provers derive the challenge before the responses `s` exist.
+ */
+pub fn transfer_challenge_from_nonces(
+    amount_commitment: &[u8; 64],
+    sender_old_commitment: &[u8; 64],
+    sender_new_commitment: &[u8; 64],
+    recipient_old_commitment: &[u8; 64],
+    recipient_new_commitment: &[u8; 64],
+    r_sender: &[u8; 64],
+    r_recipient: &[u8; 64],
+) -> [u8; 32] {
+    let mut transcript = MerlinTranscript::new(b"transfer");
+    transcript.append_point(b"C_amount", amount_commitment);
+    transcript.append_point(b"C_sender_old", sender_old_commitment);
+    transcript.append_point(b"C_sender_new", sender_new_commitment);
+    transcript.append_point(b"C_recipient_old", recipient_old_commitment);
+    transcript.append_point(b"C_recipient_new", recipient_new_commitment);
+    transcript.append_point(b"R_sender", r_sender);
+    transcript.append_point(b"R_recipient", r_recipient);
+    transcript.challenge_scalar(b"c")
+}
+
+/**
+ * Result of stage A of a two-stage transfer verification
+ * 
+ * Small enough to carry between transactions in an account.
+ */
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TransferStageA {
+    /// Hash of the proof data and all five commitments
+    pub statement_hash: [u8; 32],
+    /// Fiat-Shamir challenge of the transfer (see transfer_challenge)
+    pub challenge: [u8; 32],
+}
+
+/**
+ * Verify a transfer proof in two stages: stage A (BPF-compatible)
+ * 
+ * Stage A takes the range proofs and the transcript of
+ * verify_transfer_proof so each stage fits in one transaction's compute
+ * budget:
+ * 1. Deserialize proof data
+ * 2. Verify the three range proofs
+ * 3. Derive the transfer challenge
+ * 4. Hash the statement so stage B can only finish this exact transfer
+ * 
+ * verify_transfer_stage_b completes the check. Together they accept
+ * exactly what verify_transfer_proof accepts.
+ */
+pub fn verify_transfer_stage_a(
+    proof_data: &[u8],
+    amount_commitment: &[u8; 64],
+    sender_after_commitment: &[u8; 64],
+    sender_old_commitment: &[u8; 64],
+    recipient_old_commitment: &[u8; 64],
+    recipient_new_commitment: &[u8; 64],
+) -> Result<TransferStageA, ProofVerificationError> {
+    let proof = deserialize_proof_data(proof_data)?;
+
+    verify_range_proof(&proof.amount_range_proof, amount_commitment)?;
+    verify_range_proof(&proof.sender_after_range_proof, sender_after_commitment)?;
+    verify_range_proof(&proof.recipient_after_range_proof, recipient_new_commitment)?;
+
+    let challenge = transfer_challenge(
+        amount_commitment,
+        sender_old_commitment,
+        sender_after_commitment,
+        recipient_old_commitment,
+        recipient_new_commitment,
+        &proof.validity_proof,
+    );
+
+    Ok(TransferStageA {
+        statement_hash: transfer_statement_hash(
+            proof_data,
+            amount_commitment,
+            sender_after_commitment,
+            sender_old_commitment,
+            recipient_old_commitment,
+            recipient_new_commitment,
+        ),
+        challenge,
+    })
+}
+
+/**
+ * Verify a transfer proof in two stages: stage B (BPF-compatible)
+ * 
+ * VERIFICATION STEPS:
+ * 1. Check the statement is the one stage A ran on
+ * 2. Verify commitments match
+ * 3. Verify the validity proof against the stage A challenge
+ */
+pub fn verify_transfer_stage_b(
+    proof_data: &[u8],
+    amount_commitment: &[u8; 64],
+    sender_after_commitment: &[u8; 64],
+    sender_old_commitment: &[u8; 64],
+    recipient_old_commitment: &[u8; 64],
+    recipient_new_commitment: &[u8; 64],
+    stage_a: &TransferStageA,
+) -> Result<(), ProofVerificationError> {
+    let statement_hash = transfer_statement_hash(
+        proof_data,
+        amount_commitment,
+        sender_after_commitment,
+        sender_old_commitment,
+        recipient_old_commitment,
+        recipient_new_commitment,
+    );
+    if !constant_time_eq(&statement_hash, &stage_a.statement_hash) || is_zero(&stage_a.challenge) {
+        return Err(ProofVerificationError::CommitmentMismatch);
+    }
+
+    let proof = deserialize_proof_data(proof_data)?;
+
+    if !constant_time_eq(proof.amount_range_proof.commitment, amount_commitment)
+        || !constant_time_eq(proof.sender_after_range_proof.commitment, sender_after_commitment)
+        || !constant_time_eq(proof.recipient_after_range_proof.commitment, recipient_new_commitment)
+    {
+        return Err(ProofVerificationError::CommitmentMismatch);
+    }
+
+    verify_validity_proof(
+        &proof.validity_proof,
+        sender_old_commitment,
+        amount_commitment,
+        sender_after_commitment,
+        recipient_old_commitment,
+        recipient_new_commitment,
+        &stage_a.challenge,
+    )?;
+
+    Ok(())
+}
+
+/**
+ * Hash binding a transfer proof to its five commitments
+ */
+fn transfer_statement_hash(
+    proof_data: &[u8],
+    amount_commitment: &[u8; 64],
+    sender_after_commitment: &[u8; 64],
+    sender_old_commitment: &[u8; 64],
+    recipient_old_commitment: &[u8; 64],
+    recipient_new_commitment: &[u8; 64],
+) -> [u8; 32] {
+    let mut transcript = MerlinTranscript::new(b"transfer-statement");
+    transcript.append_point(b"C_amount", amount_commitment);
+    transcript.append_point(b"C_sender_old", sender_old_commitment);
+    transcript.append_point(b"C_sender_new", sender_after_commitment);
+    transcript.append_point(b"C_recipient_old", recipient_old_commitment);
+    transcript.append_point(b"C_recipient_new", recipient_new_commitment);
+    transcript.append_message(b"proof", proof_data);
+    transcript.challenge_scalar(b"statement")
+}
+
+/**
+ * Deserialize a standalone equality proof
+ * 
+ * PROOF DATA FORMAT:
+ * - R: 64 bytes
+ * - s: 32 bytes
+ */
+pub fn deserialize_equality_proof(proof_data: &[u8]) -> Result<EqualityProof<'_>, ProofVerificationError> {
+    if proof_data.len() != proof_constants::EQUALITY_PROOF_SIZE {
+        return Err(ProofVerificationError::DeserializationFailed);
+    }
+    
+    let mut offset = 0;
+    let r = read_ref::<64>(proof_data, &mut offset)?;
+    let s = read_ref::<32>(proof_data, &mut offset)?;
+    
+    Ok(EqualityProof { r, s })
+}
+
+/**
+ * Verify key rotation proof (BPF-compatible)
+ * 
+ * Proves that old_commitment and new_commitment open to the same hidden
+ * balance under different blinding factors, i.e. old - new = h^(r_old - r_new).
+ * 
+ * VERIFICATION STEPS (on-chain):
+ * 1. Deserialize equality proof
+ * 2. Reject rotations that do not change the commitment
+ * 3. Bind both commitments and R into the transcript
+ * 4. Check s*H == R + c*(C_old - C_new) with the curve25519 syscalls
+ */
+pub fn verify_rotation_proof(
+    proof_data: &[u8],
+    old_commitment: &[u8; 64],
+    new_commitment: &[u8; 64],
+) -> Result<(), ProofVerificationError> {
+    let proof = deserialize_equality_proof(proof_data)?;
+    
+    // SECURITY: A rotation must actually re-randomize the commitment
+    if constant_time_eq(old_commitment, new_commitment) {
+        return Err(ProofVerificationError::InvalidEqualityProof);
+    }
+    
+    verify_equality_proof(&proof, old_commitment, new_commitment)?;
+    
+    let mut transcript = MerlinTranscript::new(b"key-rotation");
+    transcript.append_point(b"C_old", old_commitment);
+    transcript.append_point(b"C_new", new_commitment);
+    transcript.append_point(b"R", proof.r);
+    let c = transcript.challenge_scalar(b"c");
+    
+    // D = C_old - C_new must commit to zero (same value, new blinding)
+    let d = pedersen::sub(old_commitment, new_commitment)?;
+    pedersen::verify_zero_opening(&d, proof.r, proof.s, &c)
+}
+
+/**
+ * Deserialize a supply adjustment proof
+ * 
+ * PROOF DATA FORMAT:
+ * [amount_range_proof][balance_after_range_proof][equality_proof (R: 64, s: 32)]
+ */
+pub fn deserialize_supply_proof(proof_data: &[u8]) -> Result<SupplyProof<'_>, ProofVerificationError> {
+    if proof_data.len() > proof_constants::MAX_PROOF_DATA_SIZE {
+        return Err(ProofVerificationError::DeserializationFailed);
+    }
+    
+    let mut offset = 0;
+    let amount_range_proof = read_range_proof(proof_data, &mut offset)?;
+    let balance_after_range_proof = read_range_proof(proof_data, &mut offset)?;
+    
+    let equality_proof = read_equality_proof(proof_data, &mut offset)?;
+    
+    Ok(SupplyProof {
+        amount_range_proof,
+        balance_after_range_proof,
+        equality_proof,
+    })
+}
+
+/**
+ * Verify a supply adjustment proof (BPF-compatible)
+ * 
+ * Proves new = old + amount (mint) or new = old - amount (burn), with both
+ * the amount and the resulting balance in range. An empty (all-zero) old
+ * commitment is only valid for a mint into a fresh balance, in which case
+ * the new commitment must be the amount commitment itself.
+ * 
+ * VERIFICATION STEPS (on-chain):
+ * 1. Deserialize proof
+ * 2. Verify amount and balance-after range proofs (basic validation)
+ * 3. Bind all commitments into the transcript
+ * 4. Check s*H == R + c*D with the curve25519 syscalls, where
+ *    D = C_new - C_old - C_amount (mint) or C_old - C_new - C_amount (burn)
+ * 
+ * NOTE: The range proofs themselves are still only validated structurally.
+ */
+pub fn verify_supply_proof(
+    proof_data: &[u8],
+    direction: SupplyDirection,
+    amount_commitment: &[u8; 64],
+    old_commitment: &[u8; 64],
+    new_commitment: &[u8; 64],
+) -> Result<(), ProofVerificationError> {
+    let proof = deserialize_supply_proof(proof_data)?;
+    
+    verify_range_proof(&proof.amount_range_proof, amount_commitment)?;
+    verify_range_proof(&proof.balance_after_range_proof, new_commitment)?;
+    
+    let domain: &[u8] = match direction {
+        SupplyDirection::Mint => b"confidential-mint",
+        SupplyDirection::Burn => b"confidential-burn",
+    };
+    let mut transcript = MerlinTranscript::new(domain);
+    transcript.append_point(b"C_old", old_commitment);
+    transcript.append_point(b"C_amount", amount_commitment);
+    transcript.append_point(b"C_new", new_commitment);
+    transcript.append_point(b"R", proof.equality_proof.r);
+    let c = transcript.challenge_scalar(b"c");
+    
+    if is_zero(old_commitment) {
+        // Fresh balance: only a mint, and the balance is exactly the amount
+        if direction != SupplyDirection::Mint
+            || !constant_time_eq(new_commitment, amount_commitment)
+        {
+            return Err(ProofVerificationError::CommitmentMismatch);
+        }
+        return Ok(());
+    }
+    
+    // SECURITY: A non-zero adjustment must change the commitment
+    if constant_time_eq(old_commitment, new_commitment) {
+        return Err(ProofVerificationError::InvalidEqualityProof);
+    }
+    
+    verify_equality_proof(&proof.equality_proof, old_commitment, new_commitment)?;
+    
+    // new = old + amount (mint) or new = old - amount (burn)
+    let change = match direction {
+        SupplyDirection::Mint => pedersen::sub(new_commitment, old_commitment)?,
+        SupplyDirection::Burn => pedersen::sub(old_commitment, new_commitment)?,
+    };
+    let d = pedersen::sub(&change, amount_commitment)?;
+    pedersen::verify_zero_opening(&d, proof.equality_proof.r, proof.equality_proof.s, &c)
+}
+
+/**
+ * Deserialize a public-amount (deposit / withdrawal) proof
+ * 
+ * PROOF DATA FORMAT:
+ * [balance_after_range_proof][equality_proof (R: 64, s: 32)]
+ */
+pub fn deserialize_public_amount_proof(proof_data: &[u8]) -> Result<PublicAmountProof<'_>, ProofVerificationError> {
+    if proof_data.len() > proof_constants::MAX_PROOF_DATA_SIZE {
+        return Err(ProofVerificationError::DeserializationFailed);
+    }
+    
+    let mut offset = 0;
+    let balance_after_range_proof = read_range_proof(proof_data, &mut offset)?;
+    
+    let equality_proof = read_equality_proof(proof_data, &mut offset)?;
+    
+    Ok(PublicAmountProof {
+        balance_after_range_proof,
+        equality_proof,
+    })
+}
+
+/**
+ * Verify a deposit correctness proof (BPF-compatible)
+ * 
+ * Proves new = old + amount*G + r*H for the public `amount`, i.e. the
+ * depositor knows the blinding difference and did not inflate the hidden
+ * balance. An all-zero old commitment denotes an empty balance.
+ * 
+ * VERIFICATION STEPS (on-chain):
+ * 1. Deserialize proof
+ * 2. Verify the balance-after range proof commits to `new_commitment`
+ * 3. Bind both commitments, the amount and R into the transcript
+ * 4. Check s*H == R + c*(C_new - C_old - amount*G) with the curve25519 syscalls
+ */
+pub fn verify_deposit_proof(
+    proof_data: &[u8],
+    amount: u64,
+    old_commitment: &[u8; 64],
+    new_commitment: &[u8; 64],
+) -> Result<(), ProofVerificationError> {
+    let proof = deserialize_public_amount_proof(proof_data)?;
+    
+    verify_range_proof(&proof.balance_after_range_proof, new_commitment)?;
+    
+    // SECURITY: A non-zero deposit must change the commitment
+    if amount == 0 || constant_time_eq(old_commitment, new_commitment) {
+        return Err(ProofVerificationError::CommitmentMismatch);
+    }
+    
+    let mut transcript = MerlinTranscript::new(b"deposit");
+    transcript.append_point(b"C_old", old_commitment);
+    transcript.append_point(b"C_new", new_commitment);
+    transcript.append_message(b"amount", &amount.to_le_bytes());
+    transcript.append_point(b"R", proof.equality_proof.r);
+    let c = transcript.challenge_scalar(b"c");
+    
+    // D = C_new - C_old - amount*G must commit to zero (an all-zero C_old is
+    // the identity, so first deposits need no special case)
+    let d = pedersen::sub(
+        &pedersen::sub(new_commitment, old_commitment)?,
+        &pedersen::commit_public(amount)?,
+    )?;
+    pedersen::verify_zero_opening(&d, proof.equality_proof.r, proof.equality_proof.s, &c)
+}
+
+/**
+ * Verify a withdrawal consistency proof (BPF-compatible)
+ * 
+ * Proves old = new + amount*G + r*H for the public `amount`, so the
+ * commitment cannot drift from the payout. The remainder range proof on
+ * `new_commitment` is only validated structurally; callers bound the
+ * payout by a plaintext balance (escrow, pool total).
+ * 
+ * VERIFICATION STEPS (on-chain):
+ * 1. Deserialize proof
+ * 2. Verify the remainder range proof commits to `new_commitment`
+ * 3. Bind both commitments, the amount and R into the transcript
+ * 4. Check s*H == R + c*(C_old - C_new - amount*G) with the curve25519 syscalls
+ */
+pub fn verify_withdraw_proof(
+    proof_data: &[u8],
+    amount: u64,
+    old_commitment: &[u8; 64],
+    new_commitment: &[u8; 64],
+) -> Result<(), ProofVerificationError> {
+    verify_withdraw_proof_inner(proof_data, amount, old_commitment, new_commitment, None)
+}
+
+/**
+ * Verify a withdrawal consistency proof bound to a payout destination (BPF-compatible)
+ * 
+ * Same statement as verify_withdraw_proof (including the structural
+ * remainder range proof on `new_commitment`), but the destination pubkey is
+ * absorbed into the transcript before the challenge, so a proof made for
+ * one destination fails for any other (front-runners cannot redirect it).
+ */
+pub fn verify_withdraw_to_proof(
+    proof_data: &[u8],
+    amount: u64,
+    old_commitment: &[u8; 64],
+    new_commitment: &[u8; 64],
+    destination: &[u8; 32],
+) -> Result<(), ProofVerificationError> {
+    verify_withdraw_proof_inner(proof_data, amount, old_commitment, new_commitment, Some(destination))
+}
+
+fn verify_withdraw_proof_inner(
+    proof_data: &[u8],
+    amount: u64,
+    old_commitment: &[u8; 64],
+    new_commitment: &[u8; 64],
+    destination: Option<&[u8; 32]>,
+) -> Result<(), ProofVerificationError> {
+    let proof = deserialize_public_amount_proof(proof_data)?;
+    
+    verify_range_proof(&proof.balance_after_range_proof, new_commitment)?;
+    
+    // SECURITY: A non-zero withdrawal must change the commitment
+    if amount == 0 || constant_time_eq(old_commitment, new_commitment) {
+        return Err(ProofVerificationError::CommitmentMismatch);
+    }
+    
+    // SECURITY: Nothing can be withdrawn from an empty (all-zero) balance
+    if is_zero(old_commitment) {
+        return Err(ProofVerificationError::InvalidCommitment);
+    }
+    
+    let mut transcript = MerlinTranscript::new(b"withdraw");
+    transcript.append_point(b"C_old", old_commitment);
+    transcript.append_point(b"C_new", new_commitment);
+    transcript.append_message(b"amount", &amount.to_le_bytes());
+    if let Some(destination) = destination {
+        transcript.append_message(b"destination", destination);
+    }
+    transcript.append_point(b"R", proof.equality_proof.r);
+    let c = transcript.challenge_scalar(b"c");
+    
+    // D = C_old - C_new - amount*G must commit to zero
+    let d = pedersen::sub(
+        &pedersen::sub(old_commitment, new_commitment)?,
+        &pedersen::commit_public(amount)?,
+    )?;
+    pedersen::verify_zero_opening(&d, proof.equality_proof.r, proof.equality_proof.s, &c)
+}
+
+/**
+ * Deserialize a note spend proof
+ * 
+ * PROOF DATA FORMAT:
+ * [A (64 bytes)][B (128 bytes)][C (64 bytes)]
+ */
+pub fn deserialize_note_spend_proof(proof_data: &[u8]) -> Result<NoteSpendProof<'_>, ProofVerificationError> {
+    if proof_data.len() != proof_constants::NOTE_SPEND_PROOF_SIZE {
+        return Err(ProofVerificationError::DeserializationFailed);
+    }
+    
+    let mut offset = 0;
+    let a = read_ref::<64>(proof_data, &mut offset)?;
+    let b = read_ref::<128>(proof_data, &mut offset)?;
+    let c = read_ref::<64>(proof_data, &mut offset)?;
+    
+    Ok(NoteSpendProof { a, b, c })
+}
+
+/**
+ * Verify a shielded note spend proof (BPF-compatible)
+ * 
+ * Public inputs: Merkle root, nullifier, amount and recipient. Binding the
+ * recipient prevents a front-runner from redirecting a pending spend.
+ * 
+ * VERIFICATION STEPS (on-chain):
+ * 1. Deserialize proof
+ * 2. Validate proof elements are non-zero and distinct
+ * 3. Bind all public inputs into the transcript
+ * 
+ * NOTE: This is the structural pre-check only. The pairing check against
+ * the circuit's verifying key runs in the program (`groth16::verify`),
+ * which owns the stored key and the alt_bn128 syscalls.
+ */
+pub fn verify_note_spend_proof(
+    proof_data: &[u8],
+    root: &[u8; 32],
+    nullifier: &[u8; 32],
+    amount: u64,
+    recipient: &[u8; 32],
+) -> Result<(), ProofVerificationError> {
+    let proof = deserialize_note_spend_proof(proof_data)?;
+    
+    if is_zero(nullifier) || is_zero(root) {
+        return Err(ProofVerificationError::InvalidProofStructure);
+    }
+    
+    if !is_nonzero_point(proof.a)
+        || !is_nonzero_point(proof.c)
+        || proof.b.iter().all(|&byte| byte == 0)
+    {
+        return Err(ProofVerificationError::InvalidProofStructure);
+    }
+    
+    // SECURITY: Reject obviously dummy proofs (A == C)
+    if constant_time_eq(proof.a, proof.c) {
+        return Err(ProofVerificationError::InvalidProofStructure);
+    }
+    
+    // Transcript binding (structure only)
+    let mut transcript = MerlinTranscript::new(b"note-spend");
+    transcript.append_message(b"root", root);
+    transcript.append_message(b"nullifier", nullifier);
+    transcript.append_message(b"amount", &amount.to_le_bytes());
+    transcript.append_message(b"recipient", recipient);
+    transcript.append_point(b"A", proof.a);
+    transcript.append_point(b"C", proof.c);
+    let _c = transcript.challenge_scalar(b"c");
+    
+    Ok(())
+}
+
+/**
+ * Verify a note spend proof with association-set membership (BPF-compatible)
+ * 
+ * Same statement as `verify_note_spend_proof`, plus: the spent note's leaf is
+ * also a member of the association set committed to by `association_root`
+ * (privacy-pools style). The spender proves the funds come from an approved
+ * set of deposits without revealing which one.
+ * 
+ * PROOF DATA FORMAT: identical to a note spend proof
+ * 
+ * NOTE: Structural pre-check only; the program runs the pairing check
+ * against the association circuit's stored verifying key.
+ */
+pub fn verify_association_spend_proof(
+    proof_data: &[u8],
+    root: &[u8; 32],
+    association_root: &[u8; 32],
+    nullifier: &[u8; 32],
+    amount: u64,
+    recipient: &[u8; 32],
+) -> Result<(), ProofVerificationError> {
+    let proof = deserialize_note_spend_proof(proof_data)?;
+    
+    if is_zero(nullifier) || is_zero(root) || is_zero(association_root) {
+        return Err(ProofVerificationError::InvalidProofStructure);
+    }
+    
+    if !is_nonzero_point(proof.a)
+        || !is_nonzero_point(proof.c)
+        || proof.b.iter().all(|&byte| byte == 0)
+    {
+        return Err(ProofVerificationError::InvalidProofStructure);
+    }
+    
+    // SECURITY: Reject obviously dummy proofs (A == C)
+    if constant_time_eq(proof.a, proof.c) {
+        return Err(ProofVerificationError::InvalidProofStructure);
+    }
+    
+    // Transcript binding (structure only)
+    let mut transcript = MerlinTranscript::new(b"association-spend");
+    transcript.append_message(b"root", root);
+    transcript.append_message(b"association_root", association_root);
+    transcript.append_message(b"nullifier", nullifier);
+    transcript.append_message(b"amount", &amount.to_le_bytes());
+    transcript.append_message(b"recipient", recipient);
+    transcript.append_point(b"A", proof.a);
+    transcript.append_point(b"C", proof.c);
+    let _c = transcript.challenge_scalar(b"c");
+    
+    Ok(())
+}
+
+/**
+ * Deserialize a decoy transfer proof with `outputs` output proofs
+ * 
+ * PROOF DATA FORMAT:
+ * [amount_range_proof][sender_after_range_proof][sender_equality_proof (96)]
+ * [output_proof (224)] * outputs [sum_proof (96)]
+ * 
+ * OUTPUT PROOF FORMAT:
+ * [zero_challenge (32)][zero_branch (R: 64, s: 32)][amount_branch (R: 64, s: 32)]
+ */
+pub fn deserialize_decoy_transfer_proof(
+    proof_data: &[u8],
+    outputs: usize,
+) -> Result<DecoyTransferProof<'_>, ProofVerificationError> {
+    if proof_data.len() > proof_constants::MAX_PROOF_DATA_SIZE {
+        return Err(ProofVerificationError::DeserializationFailed);
+    }
+    
+    let mut offset = 0;
+    let amount_range_proof = read_range_proof(proof_data, &mut offset)?;
+    let sender_after_range_proof = read_range_proof(proof_data, &mut offset)?;
+    let sender_equality_proof = read_equality_proof(proof_data, &mut offset)?;
+    
+    let mut output_proofs = Vec::with_capacity(outputs);
+    for _ in 0..outputs {
+        let zero_challenge = read_ref::<32>(proof_data, &mut offset)?;
+        let zero_branch = read_equality_proof(proof_data, &mut offset)?;
+        let amount_branch = read_equality_proof(proof_data, &mut offset)?;
+        output_proofs.push(OutputProof { zero_challenge, zero_branch, amount_branch });
+    }
+    let sum_proof = read_equality_proof(proof_data, &mut offset)?;
+    
+    if offset != proof_data.len() {
+        return Err(ProofVerificationError::DeserializationFailed);
+    }
+    
+    Ok(DecoyTransferProof {
+        amount_range_proof,
+        sender_after_range_proof,
+        sender_equality_proof,
+        output_proofs,
+        sum_proof,
+    })
+}
+
+/**
+ * Verify a transfer whose real recipient is hidden among decoys (BPF-compatible)
+ * 
+ * `outputs` holds (old, delta) of every touched recipient account, real and
+ * decoy alike; the caller applies new = old + delta itself. Each delta
+ * carries an OR-proof that it commits to zero (a decoy re-randomization) or
+ * to the amount, and the deltas together must add up to exactly one
+ * amount, so observers cannot tell which account received the funds and
+ * the sender cannot move other accounts' balances by anything else.
+ * 
+ * VERIFICATION STEPS (on-chain):
+ * 1. Deserialize proof
+ * 2. Verify amount and sender-after range proofs (basic validation)
+ * 3. Validate the sender equality proof
+ * 4. Bind all commitments, deltas and nonces into the transcript
+ * 5. Check every OR-proof branch with the curve25519 syscalls:
+ *    s0*H == R0 + c0*D and s1*H == R1 + (c - c0)*(D - C_amount)
+ * 6. Check sum(D) - C_amount commits to zero
+ * 
+ * NOTE: The range proofs and the sender equality proof are still only
+ * validated structurally.
+ */
+pub fn verify_decoy_transfer_proof(
+    proof_data: &[u8],
+    amount_commitment: &[u8; 64],
+    sender_old_commitment: &[u8; 64],
+    sender_after_commitment: &[u8; 64],
+    outputs: &[([u8; 64], [u8; 64])],
+) -> Result<(), ProofVerificationError> {
+    let proof = deserialize_decoy_transfer_proof(proof_data, outputs.len())?;
+    
+    verify_range_proof(&proof.amount_range_proof, amount_commitment)?;
+    verify_range_proof(&proof.sender_after_range_proof, sender_after_commitment)?;
+    verify_equality_proof(&proof.sender_equality_proof, sender_old_commitment, sender_after_commitment)?;
+    
+    let mut transcript = MerlinTranscript::new(b"decoy-transfer");
+    transcript.append_point(b"C_amount", amount_commitment);
+    transcript.append_point(b"C_sender_old", sender_old_commitment);
+    transcript.append_point(b"C_sender_new", sender_after_commitment);
+    for ((old, delta), output_proof) in outputs.iter().zip(&proof.output_proofs) {
+        transcript.append_point(b"C_out_old", old);
+        transcript.append_point(b"D_out", delta);
+        transcript.append_point(b"R_zero", output_proof.zero_branch.r);
+        transcript.append_point(b"R_amount", output_proof.amount_branch.r);
+    }
+    transcript.append_point(b"R_sum", proof.sum_proof.r);
+    let c = pedersen::reduce_scalar(&transcript.challenge_scalar(b"c"));
+    
+    let mut delta_sum = [0u8; 64];
+    for ((_, delta), output_proof) in outputs.iter().zip(&proof.output_proofs) {
+        // SECURITY: Every output (decoy or not) must be re-randomized
+        if is_zero(delta) {
+            return Err(ProofVerificationError::CommitmentMismatch);
+        }
+        if !pedersen::is_canonical_scalar(output_proof.zero_challenge) {
+            return Err(ProofVerificationError::InvalidEqualityProof);
+        }
+        let amount_challenge = pedersen::sub_scalar(&c, output_proof.zero_challenge);
+        
+        // D commits to zero, or D - C_amount does (one branch is simulated)
+        pedersen::verify_zero_opening(
+            delta,
+            output_proof.zero_branch.r,
+            output_proof.zero_branch.s,
+            output_proof.zero_challenge,
+        )?;
+        pedersen::verify_zero_opening(
+            &pedersen::sub(delta, amount_commitment)?,
+            output_proof.amount_branch.r,
+            output_proof.amount_branch.s,
+            &amount_challenge,
+        )?;
+        delta_sum = pedersen::add(&delta_sum, delta)?;
+    }
+    
+    // Exactly one output received the amount
+    pedersen::verify_zero_opening(
+        &pedersen::sub(&delta_sum, amount_commitment)?,
+        proof.sum_proof.r,
+        proof.sum_proof.s,
+        &c,
+    )
+}
+
+/**
+ * Deserialize a velocity limit proof
+ * 
+ * PROOF DATA FORMAT:
+ * [headroom_range_proof]
+ */
+pub fn deserialize_velocity_proof(proof_data: &[u8]) -> Result<VelocityProof<'_>, ProofVerificationError> {
+    if proof_data.len() > proof_constants::MAX_PROOF_DATA_SIZE {
+        return Err(ProofVerificationError::DeserializationFailed);
+    }
+    
+    let mut offset = 0;
+    let headroom_range_proof = read_range_proof(proof_data, &mut offset)?;
+    
+    if offset != proof_data.len() {
+        return Err(ProofVerificationError::DeserializationFailed);
+    }
+    
+    Ok(VelocityProof {
+        headroom_range_proof,
+    })
+}
+
+/**
+ * Verify a per-epoch velocity limit proof (BPF-compatible)
+ * 
+ * Checks total_new = total_old + amount and limit - total_new >= 0 without
+ * revealing the amount or the running total. An all-zero `total_old` (the
+ * identity) starts a new epoch, so the new total is then the amount
+ * commitment itself.
+ * 
+ * VERIFICATION STEPS (on-chain):
+ * 1. Deserialize proof
+ * 2. Compute total_old + C_amount with the curve25519 syscalls and require
+ *    it to equal `total_new`
+ * 3. Derive the headroom limit*G - total_new and verify the range proof
+ *    is over that point
+ * 
+ * NOTE: The range proof itself is still only validated structurally.
+ */
+pub fn verify_velocity_proof(
+    proof_data: &[u8],
+    amount_commitment: &[u8; 64],
+    total_old: &[u8; 64],
+    total_new: &[u8; 64],
+    limit: u64,
+) -> Result<(), ProofVerificationError> {
+    let proof = deserialize_velocity_proof(proof_data)?;
+    
+    if limit == 0 || !is_valid_commitment_format(total_new) {
+        return Err(ProofVerificationError::InvalidCommitment);
+    }
+    
+    // SECURITY: The new total is exactly the old total plus the amount
+    let expected_total = pedersen::add(total_old, amount_commitment)?;
+    if !constant_time_eq(total_new, &expected_total) {
+        return Err(ProofVerificationError::CommitmentMismatch);
+    }
+    
+    // SECURITY: The range proof must be over limit*G - total_new itself
+    let headroom = pedersen::sub(&pedersen::commit_public(limit)?, total_new)?;
+    verify_range_proof(&proof.headroom_range_proof, &headroom)
+}
+
+/**
+ * Verify a commitment opening proof (BPF-compatible)
+ * 
+ * Proves `commitment` = amount*G + r*H for the disclosed `amount` and a
+ * blinding factor r known to the prover, without revealing r.
+ * 
+ * PROOF DATA FORMAT:
+ * [equality_proof (96)]
+ * 
+ * Checks s*H == R + c*(C - amount*G) with the curve25519 syscalls.
+ */
+pub fn verify_opening_proof(
+    proof_data: &[u8],
+    commitment: &[u8; 64],
+    amount: u64,
+) -> Result<(), ProofVerificationError> {
+    if proof_data.len() != proof_constants::EQUALITY_PROOF_SIZE {
+        return Err(ProofVerificationError::DeserializationFailed);
+    }
+    if !is_valid_commitment_format(commitment) {
+        return Err(ProofVerificationError::InvalidCommitment);
+    }
+    
+    let mut offset = 0;
+    let proof = read_equality_proof(proof_data, &mut offset)?;
+    
+    // SECURITY: Reject dummy proofs (R reusing the commitment or s its half)
+    if constant_time_eq(proof.r, commitment) || constant_time_eq(&proof.r[..32], proof.s) {
+        return Err(ProofVerificationError::InvalidEqualityProof);
+    }
+    
+    let mut transcript = MerlinTranscript::new(b"opening");
+    transcript.append_point(b"C", commitment);
+    transcript.append_message(b"amount", &amount.to_le_bytes());
+    transcript.append_point(b"R", proof.r);
+    let c = transcript.challenge_scalar(b"c");
+    
+    // D = C - amount*G must commit to zero
+    let d = pedersen::sub(commitment, &pedersen::commit_public(amount)?)?;
+    pedersen::verify_zero_opening(&d, proof.r, proof.s, &c)
+}
+
+/**
+ * Verify a deny-list exclusion proof (BPF-compatible)
+ * 
+ * Proves `subject` is NOT a leaf of the sorted deny-list tree under
+ * `deny_list_root` (two adjacent leaves bracket it), without revealing the
+ * bracketing leaves or their position.
+ * 
+ * PROOF DATA FORMAT: Groth16-shaped, identical to a note spend proof
+ * 
+ * NOTE: Structural pre-check only; the program runs the pairing check
+ * against the exclusion circuit's stored verifying key.
+ */
+pub fn verify_exclusion_proof(
+    proof_data: &[u8],
+    deny_list_root: &[u8; 32],
+    subject: &[u8; 32],
+) -> Result<(), ProofVerificationError> {
+    let proof = deserialize_note_spend_proof(proof_data)?;
+    
+    if is_zero(deny_list_root) {
+        return Err(ProofVerificationError::InvalidProofStructure);
+    }
+    
+    if !is_nonzero_point(proof.a)
+        || !is_nonzero_point(proof.c)
+        || proof.b.iter().all(|&byte| byte == 0)
+    {
+        return Err(ProofVerificationError::InvalidProofStructure);
+    }
+    
+    // SECURITY: Reject obviously dummy proofs (A == C)
+    if constant_time_eq(proof.a, proof.c) {
+        return Err(ProofVerificationError::InvalidProofStructure);
+    }
+    
+    // Transcript binding (structure only)
+    let mut transcript = MerlinTranscript::new(b"deny-list-exclusion");
+    transcript.append_message(b"deny_list_root", deny_list_root);
+    transcript.append_message(b"subject", subject);
+    transcript.append_point(b"A", proof.a);
+    transcript.append_point(b"C", proof.c);
+    let _c = transcript.challenge_scalar(b"c");
+    
+    Ok(())
+}
+
+/**
+ * Verify a balance cap proof (BPF-compatible)
+ * 
+ * Proves cap - balance >= 0 for the balance committed in `new_commitment`,
+ * via a range proof on the headroom commitment cap*G - C_new. The headroom
+ * is derived on-chain with the curve25519 syscalls, so the range proof
+ * cannot be over some other prover-chosen commitment.
+ * 
+ * PROOF DATA FORMAT:
+ * [headroom_range_proof]
+ * 
+ * NOTE: The range proof itself is still only validated structurally.
+ */
+pub fn verify_balance_cap_proof(
+    proof_data: &[u8],
+    new_commitment: &[u8; 64],
+    cap: u64,
+) -> Result<(), ProofVerificationError> {
+    if proof_data.len() > proof_constants::MAX_PROOF_DATA_SIZE {
+        return Err(ProofVerificationError::DeserializationFailed);
+    }
+    if cap == 0 || !is_valid_commitment_format(new_commitment) {
+        return Err(ProofVerificationError::InvalidCommitment);
+    }
+    
+    let mut offset = 0;
+    let headroom_range_proof = read_range_proof(proof_data, &mut offset)?;
+    if offset != proof_data.len() {
+        return Err(ProofVerificationError::DeserializationFailed);
+    }
+    
+    // SECURITY: The range proof must be over cap*G - C_new itself
+    let headroom = pedersen::sub(&pedersen::commit_public(cap)?, new_commitment)?;
+    verify_range_proof(&headroom_range_proof, &headroom)
+}
+
+/**
+ * Deserialize a multi-recipient transfer proof with `recipients` legs
+ * 
+ * PROOF DATA FORMAT:
+ * [sender_after_range_proof][sender_equality_proof (96)]
+ * [amount_commitment (64)][amount_range_proof][recipient_equality_proof (96)] * recipients
+ */
+pub fn deserialize_multi_transfer_proof(
+    proof_data: &[u8],
+    recipients: usize,
+) -> Result<MultiTransferProof<'_>, ProofVerificationError> {
+    if proof_data.len() > proof_constants::MAX_PROOF_DATA_SIZE {
+        return Err(ProofVerificationError::DeserializationFailed);
+    }
+    
+    let mut offset = 0;
+    let sender_after_range_proof = read_range_proof(proof_data, &mut offset)?;
+    let sender_equality_proof = read_equality_proof(proof_data, &mut offset)?;
+    
+    let mut legs = Vec::with_capacity(recipients);
+    for _ in 0..recipients {
+        let amount_commitment = read_ref::<64>(proof_data, &mut offset)?;
+        let amount_range_proof = read_range_proof(proof_data, &mut offset)?;
+        let recipient_equality_proof = read_equality_proof(proof_data, &mut offset)?;
+        legs.push(TransferLeg {
+            amount_commitment,
+            amount_range_proof,
+            recipient_equality_proof,
+        });
+    }
+    
+    if offset != proof_data.len() {
+        return Err(ProofVerificationError::DeserializationFailed);
+    }
+    
+    Ok(MultiTransferProof {
+        sender_after_range_proof,
+        sender_equality_proof,
+        legs,
+    })
+}
+
+/**
+ * Verify a multi-recipient (1 -> N) transfer proof (BPF-compatible)
+ * 
+ * `recipients` holds (old, new) commitments of every recipient. Each amount
+ * is range-proven and credited to its recipient, and the amounts sum to the
+ * sender's debit: sender_old - sender_new = sum(amount_i).
+ * 
+ * VERIFICATION STEPS (on-chain):
+ * 1. Deserialize proof (one leg per recipient)
+ * 2. Verify the sender's balance range proof and each leg's amount range proof
+ * 3. Bind every commitment and every R into one transcript
+ * 4. Sum the amount commitments and check
+ *    s*H == R + c*(C_sender_old - C_sender_new - sum(C_amount_i))
+ * 5. Check each recipient's s*H == R + c*(C_out_new - C_out_old - C_amount)
+ *    (an empty recipient balance must instead become C_amount itself)
+ * 
+ * NOTE: The range proofs themselves are still only validated structurally.
+ */
+pub fn verify_multi_transfer_proof<'a>(
+    proof_data: &'a [u8],
+    sender_old_commitment: &[u8; 64],
+    sender_after_commitment: &[u8; 64],
+    recipients: &[([u8; 64], [u8; 64])],
+) -> Result<MultiTransferProof<'a>, ProofVerificationError> {
+    let proof = deserialize_multi_transfer_proof(proof_data, recipients.len())?;
+    
+    verify_range_proof(&proof.sender_after_range_proof, sender_after_commitment)?;
+    
+    let mut transcript = MerlinTranscript::new(b"multi-transfer");
+    transcript.append_point(b"C_sender_old", sender_old_commitment);
+    transcript.append_point(b"C_sender_new", sender_after_commitment);
+    transcript.append_point(b"R_sender", proof.sender_equality_proof.r);
+    
+    // Identity: the sum of no amounts
+    let mut total = [0u8; 64];
+    for ((old, new), leg) in recipients.iter().zip(&proof.legs) {
+        verify_range_proof(&leg.amount_range_proof, leg.amount_commitment)?;
+        
+        // SECURITY: Every recipient must actually be credited
+        if constant_time_eq(old, new) || !is_nonzero_point(new) {
+            return Err(ProofVerificationError::CommitmentMismatch);
+        }
+        // Empty recipient balance: the new balance is the amount itself
+        if is_zero(old) && !constant_time_eq(new, leg.amount_commitment) {
+            return Err(ProofVerificationError::CommitmentMismatch);
+        }
+        
+        transcript.append_point(b"C_amount", leg.amount_commitment);
+        transcript.append_point(b"C_out_old", old);
+        transcript.append_point(b"C_out_new", new);
+        transcript.append_point(b"R_out", leg.recipient_equality_proof.r);
+        total = pedersen::add(&total, leg.amount_commitment)?;
+    }
+    let c = transcript.challenge_scalar(b"c");
+    
+    // Sender pays exactly the sum of the legs
+    let sender_d = pedersen::sub(
+        &pedersen::sub(sender_old_commitment, sender_after_commitment)?,
+        &total,
+    )?;
+    pedersen::verify_zero_opening(
+        &sender_d,
+        proof.sender_equality_proof.r,
+        proof.sender_equality_proof.s,
+        &c,
+    )?;
+    
+    // Each non-empty recipient gains exactly its leg's amount
+    for ((old, new), leg) in recipients.iter().zip(&proof.legs) {
+        if is_zero(old) {
+            continue;
+        }
+        let d = pedersen::sub(&pedersen::sub(new, old)?, leg.amount_commitment)?;
+        pedersen::verify_zero_opening(
+            &d,
+            leg.recipient_equality_proof.r,
+            leg.recipient_equality_proof.s,
+            &c,
+        )?;
+    }
+    
+    Ok(proof)
+}
+
+/**
+ * Deserialize an escrow debit proof
+ * 
+ * PROOF DATA FORMAT:
+ * [amount_range_proof][owner_after_range_proof][owner_equality_proof (96)]
+ * 
+ * The amount commitment leads the proof, so extract_amount_commitment works.
+ */
+pub fn deserialize_escrow_debit_proof(proof_data: &[u8]) -> Result<EscrowDebitProof<'_>, ProofVerificationError> {
+    if proof_data.len() > proof_constants::MAX_PROOF_DATA_SIZE {
+        return Err(ProofVerificationError::DeserializationFailed);
+    }
+    
+    let mut offset = 0;
+    let amount_range_proof = read_range_proof(proof_data, &mut offset)?;
+    let owner_after_range_proof = read_range_proof(proof_data, &mut offset)?;
+    let owner_equality_proof = read_equality_proof(proof_data, &mut offset)?;
+    
+    if offset != proof_data.len() {
+        return Err(ProofVerificationError::DeserializationFailed);
+    }
+    
+    Ok(EscrowDebitProof {
+        amount_range_proof,
+        owner_after_range_proof,
+        owner_equality_proof,
+    })
+}
+
+/**
+ * Verify that a hidden amount moves from a balance into an escrow (BPF-compatible)
+ * 
+ * Proves owner_old - owner_new = amount with both the amount and the new
+ * balance in range. The escrow holds `amount_commitment` until it is
+ * credited with verify_escrow_credit_proof.
+ * 
+ * VERIFICATION STEPS (on-chain):
+ * 1. Deserialize proof
+ * 2. Verify amount and owner-after range proofs (basic validation)
+ * 3. Bind all commitments and R into the transcript
+ * 4. Check s*H == R + c*(C_old - C_new - C_amount) with the curve25519 syscalls
+ * 
+ * NOTE: The range proofs themselves are still only validated structurally.
+ */
+pub fn verify_escrow_debit_proof(
+    proof_data: &[u8],
+    amount_commitment: &[u8; 64],
+    owner_old_commitment: &[u8; 64],
+    owner_after_commitment: &[u8; 64],
+) -> Result<(), ProofVerificationError> {
+    let proof = deserialize_escrow_debit_proof(proof_data)?;
+    
+    verify_range_proof(&proof.amount_range_proof, amount_commitment)?;
+    verify_range_proof(&proof.owner_after_range_proof, owner_after_commitment)?;
+    verify_equality_proof(&proof.owner_equality_proof, owner_old_commitment, owner_after_commitment)?;
+    
+    let mut transcript = MerlinTranscript::new(b"escrow-debit");
+    transcript.append_point(b"C_amount", amount_commitment);
+    transcript.append_point(b"C_old", owner_old_commitment);
+    transcript.append_point(b"C_new", owner_after_commitment);
+    transcript.append_point(b"R", proof.owner_equality_proof.r);
+    let c = transcript.challenge_scalar(b"c");
+    
+    // D = C_old - C_new - C_amount must commit to zero
+    let d = pedersen::sub(
+        &pedersen::sub(owner_old_commitment, owner_after_commitment)?,
+        amount_commitment,
+    )?;
+    pedersen::verify_zero_opening(&d, proof.owner_equality_proof.r, proof.owner_equality_proof.s, &c)
+}
+
+/**
+ * Verify that an escrowed amount is credited to a balance (BPF-compatible)
+ * 
+ * Proves balance_new = balance_old + amount. An all-zero `balance_old` is an
+ * empty account, in which case the new balance must be the amount
+ * commitment itself.
+ * 
+ * PROOF DATA FORMAT:
+ * [equality_proof (96)]
+ * 
+ * For a non-empty balance, checks s*H == R + c*(C_new - C_old - C_amount)
+ * with the curve25519 syscalls; `c` comes from the "escrow-credit"
+ * transcript over C_amount, C_old, C_new and R.
+ */
+pub fn verify_escrow_credit_proof(
+    proof_data: &[u8],
+    amount_commitment: &[u8; 64],
+    balance_old: &[u8; 64],
+    balance_new: &[u8; 64],
+) -> Result<(), ProofVerificationError> {
+    let proof = deserialize_equality_proof(proof_data)?;
+    
+    if !is_valid_commitment_format(amount_commitment) || !is_valid_commitment_format(balance_new) {
+        return Err(ProofVerificationError::InvalidCommitment);
+    }
+    
+    if is_zero(balance_old) {
+        // Empty balance: new balance = 0 + amount
+        if !constant_time_eq(balance_new, amount_commitment) {
+            return Err(ProofVerificationError::CommitmentMismatch);
+        }
+        return Ok(());
+    }
+    
+    // SECURITY: The balance must actually change
+    if constant_time_eq(balance_old, balance_new) {
+        return Err(ProofVerificationError::CommitmentMismatch);
+    }
+    verify_equality_proof(&proof, balance_old, balance_new)?;
+    
+    let mut transcript = MerlinTranscript::new(b"escrow-credit");
+    transcript.append_point(b"C_amount", amount_commitment);
+    transcript.append_point(b"C_old", balance_old);
+    transcript.append_point(b"C_new", balance_new);
+    transcript.append_point(b"R", proof.r);
+    let c = transcript.challenge_scalar(b"c");
+    
+    // D = C_new - C_old - C_amount must commit to zero
+    let d = pedersen::sub(&pedersen::sub(balance_new, balance_old)?, amount_commitment)?;
+    pedersen::verify_zero_opening(&d, proof.r, proof.s, &c)
+}
+
+/**
+ * Verify that two commitments hide the same amount (BPF-compatible)
+ * 
+ * Proves `committed` - `amount_commitment` opens to zero under H, i.e. both
+ * commit to one amount with independent blinding factors.
+ * 
+ * PROOF DATA FORMAT:
+ * [equality_proof (96)]
+ * 
+ * VERIFICATION STEPS (on-chain):
+ * 1. Deserialize proof and validate both commitments
+ * 2. Bind both commitments and R into the transcript
+ * 3. Check s*H == R + c*(C_committed - C_amount) with the curve25519 syscalls
+ */
+pub fn verify_amount_match_proof(
+    proof_data: &[u8],
+    committed: &[u8; 64],
+    amount_commitment: &[u8; 64],
+) -> Result<(), ProofVerificationError> {
+    let proof = deserialize_equality_proof(proof_data)?;
+    
+    verify_equality_proof(&proof, committed, amount_commitment)?;
+    
+    let mut transcript = MerlinTranscript::new(b"amount-match");
+    transcript.append_point(b"C_committed", committed);
+    transcript.append_point(b"C_amount", amount_commitment);
+    transcript.append_point(b"R", proof.r);
+    let c = transcript.challenge_scalar(b"c");
+    
+    // D = C_committed - C_amount must commit to zero
+    let d = pedersen::sub(committed, amount_commitment)?;
+    pedersen::verify_zero_opening(&d, proof.r, proof.s, &c)
+}
+
+/**
+ * Verify that a claim equals a hidden rate times public elapsed time (BPF-compatible)
+ * 
+ * Proves claim = elapsed * rate, i.e. C_claim - elapsed*C_rate opens to zero
+ * under H, so a stream recipient takes exactly what accrued without the rate
+ * or the amount being revealed.
+ * 
+ * PROOF DATA FORMAT:
+ * [equality_proof (96)]
+ * 
+ * elapsed*C_rate is derived with the curve25519 syscalls and
+ * s*H == R + c*(C_claim - elapsed*C_rate) is checked against the
+ * "stream-accrual" transcript challenge.
+ */
+pub fn verify_stream_accrual_proof(
+    proof_data: &[u8],
+    rate_commitment: &[u8; 64],
+    claim_commitment: &[u8; 64],
+    elapsed: u64,
+) -> Result<(), ProofVerificationError> {
+    let proof = deserialize_equality_proof(proof_data)?;
+    
+    if elapsed == 0 {
+        return Err(ProofVerificationError::CommitmentMismatch);
+    }
+    
+    verify_equality_proof(&proof, claim_commitment, rate_commitment)?;
+    
+    let mut transcript = MerlinTranscript::new(b"stream-accrual");
+    transcript.append_point(b"C_rate", rate_commitment);
+    transcript.append_point(b"C_claim", claim_commitment);
+    transcript.append_message(b"elapsed", &elapsed.to_le_bytes());
+    transcript.append_point(b"R", proof.r);
+    let c = transcript.challenge_scalar(b"c");
+    
+    // D = C_claim - elapsed*C_rate must commit to zero
+    let accrued = pedersen::mul(&pedersen::scalar_from_u64(elapsed), rate_commitment)?;
+    let d = pedersen::sub(claim_commitment, &accrued)?;
+    pedersen::verify_zero_opening(&d, proof.r, proof.s, &c)
+}
+
+/**
+ * Verify that a vesting claim stays within the schedule (BPF-compatible)
+ * 
+ * With linear vesting of a hidden `total` over `duration` seconds, at most
+ * total * elapsed / duration may have left the grant, i.e.
+ * duration * remaining_new - (duration - elapsed) * total >= 0. The proof is
+ * a range proof on that headroom, so neither the total nor the claim is
+ * revealed.
+ * 
+ * PROOF DATA FORMAT:
+ * [headroom_range_proof]
+ * 
+ * The headroom commitment duration*C_remaining - (duration - elapsed)*C_total
+ * is derived with the curve25519 syscalls and the range proof must be over
+ * that point.
+ * 
+ * NOTE: The range proof itself is still only validated structurally.
+ */
+pub fn verify_vesting_schedule_proof(
+    proof_data: &[u8],
+    total_commitment: &[u8; 64],
+    remaining_new: &[u8; 64],
+    elapsed: u64,
+    duration: u64,
+) -> Result<(), ProofVerificationError> {
+    if proof_data.len() > proof_constants::MAX_PROOF_DATA_SIZE {
+        return Err(ProofVerificationError::DeserializationFailed);
+    }
+    
+    let mut offset = 0;
+    let headroom_range_proof = read_range_proof(proof_data, &mut offset)?;
+    if offset != proof_data.len() {
+        return Err(ProofVerificationError::DeserializationFailed);
+    }
+    
+    if duration == 0 || elapsed > duration || !is_valid_commitment_format(remaining_new) {
+        return Err(ProofVerificationError::InvalidCommitment);
+    }
+    
+    // SECURITY: The range proof must be over the headroom itself
+    let covered = pedersen::mul(&pedersen::scalar_from_u64(duration), remaining_new)?;
+    let unvested = pedersen::mul(&pedersen::scalar_from_u64(duration - elapsed), total_commitment)?;
+    let headroom = pedersen::sub(&covered, &unvested)?;
+    verify_range_proof(&headroom_range_proof, &headroom)
+}
+
+/**
+ * Deserialize a confidential fee proof
+ * 
+ * PROOF DATA FORMAT:
+ * [remainder_range_proof][remainder_equality_proof (96)]
+ */
+pub fn deserialize_fee_proof(proof_data: &[u8]) -> Result<FeeProof<'_>, ProofVerificationError> {
+    if proof_data.len() > proof_constants::MAX_PROOF_DATA_SIZE {
+        return Err(ProofVerificationError::DeserializationFailed);
+    }
+    
+    let mut offset = 0;
+    let remainder_range_proof = read_range_proof(proof_data, &mut offset)?;
+    let remainder_equality_proof = read_equality_proof(proof_data, &mut offset)?;
+    
+    if offset != proof_data.len() {
+        return Err(ProofVerificationError::DeserializationFailed);
+    }
+    
+    Ok(FeeProof {
+        remainder_range_proof,
+        remainder_equality_proof,
+    })
+}
+
+/**
+ * Verify a hidden fee on a confidential transfer (BPF-compatible)
+ * 
+ * Proves fee = floor(amount * bps / 10_000) up to rounding: the remainder
+ * bps*amount - 10_000*fee is committed (V) and range-proven to 14 bits, so
+ * the fee can be at most one unit below the exact rate. The caller credits
+ * the fee commitment to the treasury itself.
+ * 
+ * The sender's side is covered by the transfer proof, which the caller
+ * checks against sender_old - fee, so its sender equality proof opens
+ * sender_old - fee - sender_new - amount. Both sender commitments are
+ * bound here so the two proofs cannot be mixed across transfers.
+ * 
+ * VERIFICATION STEPS (on-chain):
+ * 1. Deserialize proof
+ * 2. Validate the remainder range proof (at most 14 bits)
+ * 3. Bind the commitments, the rate, V and R into the transcript
+ * 4. Derive bps*C_amount - 10_000*C_fee with the curve25519 syscalls and
+ *    check s*H == R + c*(bps*C_amount - 10_000*C_fee - V)
+ * 
+ * NOTE: The range proof itself is still only validated structurally.
+ */
+pub fn verify_fee_proof(
+    proof_data: &[u8],
+    amount_commitment: &[u8; 64],
+    fee_commitment: &[u8; 64],
+    fee_bps: u16,
+    sender_old_commitment: &[u8; 64],
+    sender_new_commitment: &[u8; 64],
+) -> Result<(), ProofVerificationError> {
+    let proof = deserialize_fee_proof(proof_data)?;
+    
+    if fee_bps == 0 || fee_bps as u64 > proof_constants::BPS_DENOMINATOR {
+        return Err(ProofVerificationError::InvalidProofStructure);
+    }
+    if !is_valid_commitment_format(fee_commitment) {
+        return Err(ProofVerificationError::InvalidCommitment);
+    }
+    
+    // SECURITY: The fee must be its own commitment, not the amount's
+    if constant_time_eq(fee_commitment, amount_commitment) {
+        return Err(ProofVerificationError::CommitmentMismatch);
+    }
+    
+    // Rounding remainder: a fresh commitment in [0, 2^14)
+    let remainder = proof.remainder_range_proof.commitment;
+    verify_range_proof(&proof.remainder_range_proof, remainder)?;
+    if proof.remainder_range_proof.n > proof_constants::FEE_REMAINDER_BITS
+        || constant_time_eq(remainder, amount_commitment)
+        || constant_time_eq(remainder, fee_commitment)
+    {
+        return Err(ProofVerificationError::InvalidRangeProof);
+    }
+    
+    let mut transcript = MerlinTranscript::new(b"transfer-fee");
+    transcript.append_point(b"C_amount", amount_commitment);
+    transcript.append_point(b"C_fee", fee_commitment);
+    transcript.append_message(b"bps", &fee_bps.to_le_bytes());
+    transcript.append_point(b"C_sender_old", sender_old_commitment);
+    transcript.append_point(b"C_sender_new", sender_new_commitment);
+    transcript.append_point(b"V_remainder", remainder);
+    transcript.append_point(b"R", proof.remainder_equality_proof.r);
+    let c = transcript.challenge_scalar(b"c");
+    
+    // D = bps*C_amount - 10_000*C_fee - V must commit to zero
+    let scaled_amount = pedersen::mul(&pedersen::scalar_from_u64(fee_bps as u64), amount_commitment)?;
+    let scaled_fee = pedersen::mul(
+        &pedersen::scalar_from_u64(proof_constants::BPS_DENOMINATOR),
+        fee_commitment,
+    )?;
+    let d = pedersen::sub(&pedersen::sub(&scaled_amount, &scaled_fee)?, remainder)?;
+    pedersen::verify_zero_opening(
+        &d,
+        proof.remainder_equality_proof.r,
+        proof.remainder_equality_proof.s,
+        &c,
+    )
+}
+
+/**
+ * Deserialize an amount link proof
+ * 
+ * PROOF DATA FORMAT:
+ * [A_token (64)][A_amount (64)][s_value (32)][s_token (32)][s_amount (32)]
+ */
+pub fn deserialize_amount_link_proof(
+    proof_data: &[u8],
+) -> Result<AmountLinkProof<'_>, ProofVerificationError> {
+    let mut offset = 0;
+    let a_token = read_ref::<64>(proof_data, &mut offset)?;
+    let a_amount = read_ref::<64>(proof_data, &mut offset)?;
+    let s_value = read_ref::<32>(proof_data, &mut offset)?;
+    let s_token = read_ref::<32>(proof_data, &mut offset)?;
+    let s_amount = read_ref::<32>(proof_data, &mut offset)?;
+    
+    if offset != proof_data.len() {
+        return Err(ProofVerificationError::DeserializationFailed);
+    }
+    
+    Ok(AmountLinkProof {
+        a_token,
+        a_amount,
+        s_value,
+        s_token,
+        s_amount,
+    })
+}
+
+/**
+ * Challenge of an amount link proof from its nonce commitments
+ * 
+ * Provers derive the challenge before the responses exist.
+ */
+pub fn amount_link_challenge(
+    token_commitment: &[u8; 64],
+    amount_commitment: &[u8; 64],
+    a_token: &[u8; 64],
+    a_amount: &[u8; 64],
+) -> [u8; 32] {
+    let mut transcript = MerlinTranscript::new(b"amount-link");
+    transcript.append_point(b"C_token", token_commitment);
+    transcript.append_point(b"C_amount", amount_commitment);
+    transcript.append_point(b"A_token", a_token);
+    transcript.append_point(b"A_amount", a_amount);
+    transcript.challenge_scalar(b"c")
+}
+
+/**
+ * Verify that a Token-2022 amount commitment and a program commitment hide
+ * the same value (BPF-compatible)
+ * 
+ * `token_commitment` = v*G + r_t*H_elgamal comes from a confidential
+ * transfer's proof context; `amount_commitment` = v*G + r_a*H is the
+ * program's commitment to the same amount. Sigma protocol for knowledge of
+ * (v, r_t, r_a), checked with the curve25519 syscalls:
+ * 
+ *   s_v*G + s_t*H_elgamal == A_token + c*C_token
+ *   s_v*G + s_a*H         == A_amount + c*C_amount
+ * 
+ * The shared response s_v forces both commitments to the same v.
+ */
+pub fn verify_amount_link_proof(
+    proof_data: &[u8],
+    token_commitment: &[u8; 64],
+    amount_commitment: &[u8; 64],
+) -> Result<(), ProofVerificationError> {
+    let proof = deserialize_amount_link_proof(proof_data)?;
+    
+    for scalar in [proof.s_value, proof.s_token, proof.s_amount] {
+        if !pedersen::is_canonical_scalar(scalar) {
+            return Err(ProofVerificationError::InvalidEqualityProof);
+        }
+    }
+    if is_zero(proof.a_token) || is_zero(proof.a_amount) {
+        return Err(ProofVerificationError::InvalidEqualityProof);
+    }
+    
+    let c = pedersen::reduce_scalar(&amount_link_challenge(
+        token_commitment,
+        amount_commitment,
+        proof.a_token,
+        proof.a_amount,
+    ));
+    
+    let value_term = pedersen::mul(proof.s_value, &pedersen::to_slot(&pedersen::VALUE_GENERATOR))?;
+    let token_blinding = pedersen::to_slot(&pedersen::ELGAMAL_BLINDING_GENERATOR);
+    
+    let token_lhs = pedersen::add(&value_term, &pedersen::mul(proof.s_token, &token_blinding)?)?;
+    let token_rhs = pedersen::add(proof.a_token, &pedersen::mul(&c, token_commitment)?)?;
+    let amount_lhs = pedersen::add(&value_term, &pedersen::commit_zero(proof.s_amount)?)?;
+    let amount_rhs = pedersen::add(proof.a_amount, &pedersen::mul(&c, amount_commitment)?)?;
+    
+    if token_lhs != token_rhs || amount_lhs != amount_rhs {
+        return Err(ProofVerificationError::BalanceEquationFailed);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use privacy_transfer_client::{
+        prove_amount_link, prove_amount_match, prove_balance_cap, prove_burn,
+        prove_decoy_transfer, prove_deposit, prove_escrow_credit, prove_escrow_debit, prove_fee,
+        prove_mint, prove_multi_transfer, prove_opening, prove_range, prove_rotation,
+        prove_stream_accrual, prove_transfer, prove_velocity, prove_vesting_schedule,
+        prove_withdraw, BulletproofGens, Opening, PedersenGens, ProverError,
+    };
+    use rand_core::OsRng;
+
+    fn gens() -> (PedersenGens, BulletproofGens) {
+        (PedersenGens::default(), BulletproofGens::default())
+    }
+
+    /// Fresh commitment to `value` (same value, unrelated blinding)
+    fn fresh(pc: &PedersenGens, value: u64) -> [u8; 64] {
+        Opening::random(value, &mut OsRng).to_slot(pc)
+    }
+
+    /// Flip the low bit of the trailing response scalar
+    fn tamper(proof_data: &[u8]) -> Vec<u8> {
+        let mut tampered = proof_data.to_vec();
+        let len = tampered.len();
+        tampered[len - 32] ^= 1;
+        tampered
+    }
+
+    /// Groth16-shaped bytes that pass the structural checks
+    fn groth16_bytes(pc: &PedersenGens) -> Vec<u8> {
+        [fresh(pc, 1).as_slice(), &[7u8; 128], &fresh(pc, 2)].concat()
+    }
+
+    #[test]
+    fn range_proof_is_bound_to_its_commitment() {
+        let (pc, bp) = gens();
+        let opening = Opening::random(42, &mut OsRng);
+        let bytes = prove_range(&pc, &bp, &opening, 64, &mut OsRng).unwrap();
+
+        let proof = read_range_proof(&bytes, &mut 0).unwrap();
+        assert!(verify_range_proof(&proof, &opening.to_slot(&pc)).is_ok());
+        assert!(verify_range_proof(&proof, &fresh(&pc, 42)).is_err());
+        // n may be omitted (defaults to 64); anything shorter is malformed
+        assert!(read_range_proof(&bytes[..bytes.len() - 1], &mut 0).is_ok());
+        assert!(read_range_proof(&bytes[..bytes.len() - 2], &mut 0).is_err());
+    }
+
+    #[test]
+    fn equality_proof_structure() {
+        let (pc, _) = gens();
+        let opening = Opening::random(5, &mut OsRng);
+        let bytes = prove_opening(&pc, &opening, &mut OsRng);
+        let proof = deserialize_equality_proof(&bytes).unwrap();
+
+        assert!(verify_equality_proof(&proof, &opening.to_slot(&pc), &fresh(&pc, 5)).is_ok());
+        assert!(verify_equality_proof(&proof, &[0u8; 64], &fresh(&pc, 5)).is_err());
+        let zero = deserialize_equality_proof(&[0u8; 96]).unwrap();
+        assert!(verify_equality_proof(&zero, &opening.to_slot(&pc), &fresh(&pc, 5)).is_err());
+        assert!(deserialize_equality_proof(&bytes[..95]).is_err());
+    }
+
+    #[test]
+    fn transfer_proof_checks_both_balances() {
+        let (pc, bp) = gens();
+        let sender_old = Opening::random(10_000, &mut OsRng);
+        for recipient_old in [Opening::random(250, &mut OsRng), Opening::empty()] {
+            let proven = prove_transfer(&pc, &bp, &sender_old, &recipient_old, 1_200, &mut OsRng).unwrap();
+            let verify = |proof_data: &[u8], sender_old: &[u8; 64], recipient_old: &[u8; 64]| {
+                verify_transfer_proof(
+                    proof_data,
+                    &proven.amount_commitment,
+                    &proven.sender_new_commitment,
+                    sender_old,
+                    recipient_old,
+                    &proven.recipient_new_commitment,
+                )
+            };
+            let sender = sender_old.to_slot(&pc);
+            let recipient = recipient_old.to_slot(&pc);
+
+            assert!(verify(&proven.proof_data, &sender, &recipient).is_ok());
+            // Same values, other blindings: only the algebra catches these
+            assert!(verify(&proven.proof_data, &fresh(&pc, 10_000), &recipient).is_err());
+            assert!(verify(&proven.proof_data, &sender, &fresh(&pc, 250)).is_err());
+            assert!(verify(&tamper(&proven.proof_data), &sender, &recipient).is_err());
+        }
+    }
+
+    #[test]
+    fn staged_transfer_verification() {
+        let (pc, bp) = gens();
+        let sender_old = Opening::random(500, &mut OsRng);
+        let recipient_old = Opening::random(7, &mut OsRng);
+        let proven = prove_transfer(&pc, &bp, &sender_old, &recipient_old, 100, &mut OsRng).unwrap();
+        let sender = sender_old.to_slot(&pc);
+        let recipient = recipient_old.to_slot(&pc);
+        let stage_b = |sender_old: &[u8; 64], stage_a: &TransferStageA| {
+            verify_transfer_stage_b(
+                &proven.proof_data,
+                &proven.amount_commitment,
+                &proven.sender_new_commitment,
+                sender_old,
+                &recipient,
+                &proven.recipient_new_commitment,
+                stage_a,
+            )
+        };
+
+        let stage_a = verify_transfer_stage_a(
+            &proven.proof_data,
+            &proven.amount_commitment,
+            &proven.sender_new_commitment,
+            &sender,
+            &recipient,
+            &proven.recipient_new_commitment,
+        )
+        .unwrap();
+        assert!(stage_b(&sender, &stage_a).is_ok());
+        assert!(stage_b(&fresh(&pc, 500), &stage_a).is_err());
+    }
+
+    #[test]
+    fn rotation_keeps_the_value() {
+        let (pc, _) = gens();
+        let old = Opening::random(900, &mut OsRng);
+        let proven = prove_rotation(&pc, &old, &mut OsRng);
+        let old_commitment = old.to_slot(&pc);
+
+        assert!(verify_rotation_proof(&proven.proof_data, &old_commitment, &proven.new_commitment).is_ok());
+        assert!(verify_rotation_proof(&proven.proof_data, &old_commitment, &fresh(&pc, 900)).is_err());
+        assert!(verify_rotation_proof(&proven.proof_data, &old_commitment, &old_commitment).is_err());
+    }
+
+    #[test]
+    fn supply_proofs_follow_their_direction() {
+        let (pc, bp) = gens();
+        let old = Opening::random(1_000, &mut OsRng);
+        let old_commitment = old.to_slot(&pc);
+        let verify = |proof_data: &[u8], direction, amount: &[u8; 64], old: &[u8; 64], new: &[u8; 64]| {
+            verify_supply_proof(proof_data, direction, amount, old, new)
+        };
+
+        let mint = prove_mint(&pc, &bp, &old, 300, &mut OsRng).unwrap();
+        assert_eq!(mint.new_opening.value, 1_300);
+        assert!(verify(&mint.proof_data, SupplyDirection::Mint, &mint.amount_commitment, &old_commitment, &mint.new_commitment).is_ok());
+        assert!(verify(&mint.proof_data, SupplyDirection::Burn, &mint.amount_commitment, &old_commitment, &mint.new_commitment).is_err());
+        assert!(verify(&mint.proof_data, SupplyDirection::Mint, &mint.amount_commitment, &fresh(&pc, 1_000), &mint.new_commitment).is_err());
+
+        let burn = prove_burn(&pc, &bp, &old, 300, &mut OsRng).unwrap();
+        assert!(verify(&burn.proof_data, SupplyDirection::Burn, &burn.amount_commitment, &old_commitment, &burn.new_commitment).is_ok());
+        assert!(verify(&tamper(&burn.proof_data), SupplyDirection::Burn, &burn.amount_commitment, &old_commitment, &burn.new_commitment).is_err());
+
+        // Fresh balance: the amount commitment becomes the balance
+        let fresh_mint = prove_mint(&pc, &bp, &Opening::empty(), 50, &mut OsRng).unwrap();
+        assert_eq!(fresh_mint.new_commitment, fresh_mint.amount_commitment);
+        assert!(verify(&fresh_mint.proof_data, SupplyDirection::Mint, &fresh_mint.amount_commitment, &[0u8; 64], &fresh_mint.new_commitment).is_ok());
+        assert!(verify(&fresh_mint.proof_data, SupplyDirection::Burn, &fresh_mint.amount_commitment, &[0u8; 64], &fresh_mint.new_commitment).is_err());
+    }
+
+    #[test]
+    fn deposit_proof_binds_the_amount() {
+        let (pc, bp) = gens();
+        for old in [Opening::random(40, &mut OsRng), Opening::empty()] {
+            let proven = prove_deposit(&pc, &bp, &old, 60, &mut OsRng).unwrap();
+            let old_commitment = old.to_slot(&pc);
+
+            assert!(verify_deposit_proof(&proven.proof_data, 60, &old_commitment, &proven.new_commitment).is_ok());
+            assert!(verify_deposit_proof(&proven.proof_data, 61, &old_commitment, &proven.new_commitment).is_err());
+            assert!(verify_deposit_proof(&tamper(&proven.proof_data), 60, &old_commitment, &proven.new_commitment).is_err());
+        }
+    }
+
+    #[test]
+    fn withdraw_proof_binds_amount_and_destination() {
+        let (pc, bp) = gens();
+        let old = Opening::random(1_000, &mut OsRng);
+        let old_commitment = old.to_slot(&pc);
+
+        let proven = prove_withdraw(&pc, &bp, &old, 400, None, &mut OsRng).unwrap();
+        assert!(verify_withdraw_proof(&proven.proof_data, 400, &old_commitment, &proven.new_commitment).is_ok());
+        assert!(verify_withdraw_proof(&proven.proof_data, 399, &old_commitment, &proven.new_commitment).is_err());
+        assert!(verify_withdraw_proof(&proven.proof_data, 400, &fresh(&pc, 1_000), &proven.new_commitment).is_err());
+        assert!(verify_withdraw_proof(&proven.proof_data, 400, &[0u8; 64], &proven.new_commitment).is_err());
+
+        let destination = [3u8; 32];
+        let bound = prove_withdraw(&pc, &bp, &old, 400, Some(&destination), &mut OsRng).unwrap();
+        assert!(verify_withdraw_to_proof(&bound.proof_data, 400, &old_commitment, &bound.new_commitment, &destination).is_ok());
+        assert!(verify_withdraw_to_proof(&bound.proof_data, 400, &old_commitment, &bound.new_commitment, &[4u8; 32]).is_err());
+        assert!(verify_withdraw_proof(&bound.proof_data, 400, &old_commitment, &bound.new_commitment).is_err());
+
+        assert_eq!(
+            prove_withdraw(&pc, &bp, &old, 1_001, None, &mut OsRng).unwrap_err(),
+            ProverError::InsufficientBalance
+        );
+    }
+
+    #[test]
+    fn note_proofs_structure() {
+        let (pc, _) = gens();
+        let proof = groth16_bytes(&pc);
+        let (root, nullifier, recipient) = ([1u8; 32], [2u8; 32], [3u8; 32]);
+
+        assert!(verify_note_spend_proof(&proof, &root, &nullifier, 10, &recipient).is_ok());
+        assert!(verify_note_spend_proof(&proof, &[0u8; 32], &nullifier, 10, &recipient).is_err());
+        assert!(verify_note_spend_proof(&proof[..255], &root, &nullifier, 10, &recipient).is_err());
+
+        // A == C is a dummy proof
+        let mut dummy = proof.clone();
+        dummy.copy_within(..64, 192);
+        assert!(verify_note_spend_proof(&dummy, &root, &nullifier, 10, &recipient).is_err());
+
+        assert!(verify_association_spend_proof(&proof, &root, &[5u8; 32], &nullifier, 10, &recipient).is_ok());
+        assert!(verify_association_spend_proof(&proof, &root, &[0u8; 32], &nullifier, 10, &recipient).is_err());
+
+        assert!(verify_exclusion_proof(&proof, &[6u8; 32], &recipient).is_ok());
+        assert!(verify_exclusion_proof(&proof, &[0u8; 32], &recipient).is_err());
+        assert!(verify_exclusion_proof(&dummy, &[6u8; 32], &recipient).is_err());
+    }
+
+    #[test]
+    fn decoy_transfer_hides_one_real_output() {
+        let (pc, bp) = gens();
+        let sender_old = Opening::random(5_000, &mut OsRng);
+        let outputs_old = [fresh(&pc, 10), fresh(&pc, 20), fresh(&pc, 30)];
+        let proven = prove_decoy_transfer(&pc, &bp, &sender_old, 700, &outputs_old, 1, &mut OsRng).unwrap();
+        assert_eq!(proven.delta_openings[1].value, 700);
+        assert_eq!(proven.delta_openings[0].value, 0);
+
+        let sender = sender_old.to_slot(&pc);
+        let outputs: Vec<_> = outputs_old.iter().copied().zip(proven.deltas.iter().copied()).collect();
+        let verify = |proof_data: &[u8], outputs: &[([u8; 64], [u8; 64])]| {
+            verify_decoy_transfer_proof(
+                proof_data,
+                &proven.amount_commitment,
+                &sender,
+                &proven.sender_new_commitment,
+                outputs,
+            )
+        };
+
+        assert!(verify(&proven.proof_data, &outputs).is_ok());
+        assert!(verify(&tamper(&proven.proof_data), &outputs).is_err());
+
+        // A delta moved to another output no longer matches its OR-proof
+        let mut swapped = outputs.clone();
+        swapped.swap(0, 1);
+        assert!(verify(&proven.proof_data, &swapped).is_err());
+
+        // A decoy delta cannot credit anything
+        let mut inflated = outputs.clone();
+        inflated[0].1 = fresh(&pc, 700);
+        assert!(verify(&proven.proof_data, &inflated).is_err());
+    }
+
+    #[test]
+    fn velocity_proof_tracks_the_epoch_total() {
+        let (pc, bp) = gens();
+        let amount = Opening::random(200, &mut OsRng);
+        let amount_commitment = amount.to_slot(&pc);
+        for total_old in [Opening::random(300, &mut OsRng), Opening::empty()] {
+            let proven = prove_velocity(&pc, &bp, &total_old, &amount, 1_000, &mut OsRng).unwrap();
+            let old = total_old.to_slot(&pc);
+
+            assert!(verify_velocity_proof(&proven.proof_data, &amount_commitment, &old, &proven.total_new_commitment, 1_000).is_ok());
+            assert!(verify_velocity_proof(&proven.proof_data, &amount_commitment, &old, &proven.total_new_commitment, 600).is_err());
+            assert!(verify_velocity_proof(&proven.proof_data, &amount_commitment, &old, &fresh(&pc, total_old.value + 200), 1_000).is_err());
+        }
+        assert_eq!(
+            prove_velocity(&pc, &bp, &Opening::random(900, &mut OsRng), &amount, 1_000, &mut OsRng).unwrap_err(),
+            ProverError::ValueOutOfRange
+        );
+    }
+
+    #[test]
+    fn opening_proof_discloses_the_amount() {
+        let (pc, _) = gens();
+        let opening = Opening::random(123, &mut OsRng);
+        let proof = prove_opening(&pc, &opening, &mut OsRng);
+        let commitment = opening.to_slot(&pc);
+
+        assert!(verify_opening_proof(&proof, &commitment, 123).is_ok());
+        assert!(verify_opening_proof(&proof, &commitment, 124).is_err());
+        assert!(verify_opening_proof(&proof, &fresh(&pc, 123), 123).is_err());
+        assert!(verify_opening_proof(&proof[..95], &commitment, 123).is_err());
+    }
+
+    #[test]
+    fn balance_cap_proof_is_over_the_derived_headroom() {
+        let (pc, bp) = gens();
+        let balance = Opening::random(500, &mut OsRng);
+        let proof = prove_balance_cap(&pc, &bp, &balance, 1_000, &mut OsRng).unwrap();
+        let commitment = balance.to_slot(&pc);
+
+        assert!(verify_balance_cap_proof(&proof, &commitment, 1_000).is_ok());
+        assert!(verify_balance_cap_proof(&proof, &commitment, 999).is_err());
+        assert!(verify_balance_cap_proof(&proof, &fresh(&pc, 500), 1_000).is_err());
+        assert_eq!(
+            prove_balance_cap(&pc, &bp, &balance, 400, &mut OsRng).unwrap_err(),
+            ProverError::ValueOutOfRange
+        );
+    }
+
+    #[test]
+    fn multi_transfer_debits_the_sum_of_legs() {
+        let (pc, bp) = gens();
+        let sender_old = Opening::random(10_000, &mut OsRng);
+        let legs = [(Opening::random(100, &mut OsRng), 1_000), (Opening::empty(), 2_000)];
+        let proven = prove_multi_transfer(&pc, &bp, &sender_old, &legs, &mut OsRng).unwrap();
+        assert_eq!(proven.sender_new_opening.value, 7_000);
+        assert_eq!(proven.recipient_new_openings[0].value, 1_100);
+
+        let sender = sender_old.to_slot(&pc);
+        let recipients: Vec<_> = legs
+            .iter()
+            .map(|(old, _)| old.to_slot(&pc))
+            .zip(proven.recipient_new_commitments.iter().copied())
+            .collect();
+        let verify = |proof_data: &[u8], sender_old: &[u8; 64], recipients: &[([u8; 64], [u8; 64])]| {
+            verify_multi_transfer_proof(proof_data, sender_old, &proven.sender_new_commitment, recipients).map(|_| ())
+        };
+
+        assert!(verify(&proven.proof_data, &sender, &recipients).is_ok());
+        assert!(verify(&proven.proof_data, &fresh(&pc, 10_000), &recipients).is_err());
+        // Sender response (the last leg credits an empty balance, so its
+        // equality proof is never checked)
+        let sender_response = proof_constants::RANGE_PROOF_SIZE + 64;
+        let mut tampered = proven.proof_data.clone();
+        tampered[sender_response] ^= 1;
+        assert!(verify(&tampered, &sender, &recipients).is_err());
+
+        let mut other_old = recipients.clone();
+        other_old[0].0 = fresh(&pc, 100);
+        assert!(verify(&proven.proof_data, &sender, &other_old).is_err());
+
+        let mut dropped = recipients.clone();
+        dropped.pop();
+        assert!(verify(&proven.proof_data, &sender, &dropped).is_err());
+    }
+
+    #[test]
+    fn escrow_debit_and_credit() {
+        let (pc, bp) = gens();
+        let owner = Opening::random(800, &mut OsRng);
+        let amount = Opening::random(300, &mut OsRng);
+        let owner_commitment = owner.to_slot(&pc);
+
+        let debit = prove_escrow_debit(&pc, &bp, &owner, &amount, &mut OsRng).unwrap();
+        assert_eq!(extract_amount_commitment(&debit.proof_data).unwrap(), debit.amount_commitment);
+        assert!(verify_escrow_debit_proof(&debit.proof_data, &debit.amount_commitment, &owner_commitment, &debit.new_commitment).is_ok());
+        assert!(verify_escrow_debit_proof(&debit.proof_data, &debit.amount_commitment, &fresh(&pc, 800), &debit.new_commitment).is_err());
+        assert!(verify_escrow_debit_proof(&tamper(&debit.proof_data), &debit.amount_commitment, &owner_commitment, &debit.new_commitment).is_err());
+
+        let recipient = Opening::random(50, &mut OsRng);
+        let recipient_commitment = recipient.to_slot(&pc);
+        let amount_commitment = amount.to_slot(&pc);
+        let credit = prove_escrow_credit(&pc, &recipient, &amount, &mut OsRng).unwrap();
+        assert!(verify_escrow_credit_proof(&credit.proof_data, &amount_commitment, &recipient_commitment, &credit.new_commitment).is_ok());
+        assert!(verify_escrow_credit_proof(&credit.proof_data, &fresh(&pc, 300), &recipient_commitment, &credit.new_commitment).is_err());
+        assert!(verify_escrow_credit_proof(&tamper(&credit.proof_data), &amount_commitment, &recipient_commitment, &credit.new_commitment).is_err());
+
+        // Empty balance: the amount commitment becomes the balance
+        let first = prove_escrow_credit(&pc, &Opening::empty(), &amount, &mut OsRng).unwrap();
+        assert!(verify_escrow_credit_proof(&first.proof_data, &amount_commitment, &[0u8; 64], &first.new_commitment).is_ok());
+        assert!(verify_escrow_credit_proof(&first.proof_data, &amount_commitment, &[0u8; 64], &fresh(&pc, 300)).is_err());
+    }
+
+    #[test]
+    fn amount_match_requires_equal_values() {
+        let (pc, _) = gens();
+        let committed = Opening::random(77, &mut OsRng);
+        let amount = Opening::random(77, &mut OsRng);
+        let proof = prove_amount_match(&pc, &committed, &amount, &mut OsRng);
+        let committed_commitment = committed.to_slot(&pc);
+
+        assert!(verify_amount_match_proof(&proof, &committed_commitment, &amount.to_slot(&pc)).is_ok());
+        assert!(verify_amount_match_proof(&proof, &committed_commitment, &fresh(&pc, 78)).is_err());
+        assert!(verify_amount_match_proof(&tamper(&proof), &committed_commitment, &amount.to_slot(&pc)).is_err());
+    }
+
+    #[test]
+    fn stream_accrual_is_rate_times_elapsed() {
+        let (pc, _) = gens();
+        let rate = Opening::random(15, &mut OsRng);
+        let rate_commitment = rate.to_slot(&pc);
+        let proven = prove_stream_accrual(&pc, &rate, 40, &mut OsRng).unwrap();
+        assert_eq!(proven.claim_opening.value, 600);
+
+        assert!(verify_stream_accrual_proof(&proven.proof, &rate_commitment, &proven.claim_commitment, 40).is_ok());
+        assert!(verify_stream_accrual_proof(&proven.proof, &rate_commitment, &proven.claim_commitment, 41).is_err());
+        assert!(verify_stream_accrual_proof(&proven.proof, &rate_commitment, &proven.claim_commitment, 0).is_err());
+        assert!(verify_stream_accrual_proof(&proven.proof, &rate_commitment, &fresh(&pc, 600), 40).is_err());
+    }
+
+    #[test]
+    fn vesting_claims_stay_within_the_schedule() {
+        let (pc, bp) = gens();
+        let total = Opening::random(1_000, &mut OsRng);
+        let remaining = Opening::random(700, &mut OsRng);
+        let total_commitment = total.to_slot(&pc);
+        let remaining_commitment = remaining.to_slot(&pc);
+        let proof = prove_vesting_schedule(&pc, &bp, &total, &remaining, 40, 100, &mut OsRng).unwrap();
+
+        assert!(verify_vesting_schedule_proof(&proof, &total_commitment, &remaining_commitment, 40, 100).is_ok());
+        assert!(verify_vesting_schedule_proof(&proof, &total_commitment, &remaining_commitment, 30, 100).is_err());
+        assert!(verify_vesting_schedule_proof(&proof, &total_commitment, &remaining_commitment, 101, 100).is_err());
+        assert!(verify_vesting_schedule_proof(&proof, &fresh(&pc, 1_000), &remaining_commitment, 40, 100).is_err());
+
+        // 500 left after 40% of the time is more than vested
+        let overdrawn = Opening::random(500, &mut OsRng);
+        assert_eq!(
+            prove_vesting_schedule(&pc, &bp, &total, &overdrawn, 40, 100, &mut OsRng).unwrap_err(),
+            ProverError::ValueOutOfRange
+        );
+    }
+
+    #[test]
+    fn fee_proof_binds_rate_and_fee() {
+        let (pc, bp) = gens();
+        let amount = Opening::random(12_345, &mut OsRng);
+        let amount_commitment = amount.to_slot(&pc);
+        let (sender_old, sender_new) = (fresh(&pc, 50_000), fresh(&pc, 37_618));
+        let proven = prove_fee(&pc, &bp, &amount, 30, &sender_old, &sender_new, &mut OsRng).unwrap();
+        assert_eq!(proven.fee_opening.value, 37);
+        let verify = |proof_data: &[u8], fee: &[u8; 64], bps: u16, sender_new: &[u8; 64]| {
+            verify_fee_proof(proof_data, &amount_commitment, fee, bps, &sender_old, sender_new)
+        };
+
+        assert!(verify(&proven.proof_data, &proven.fee_commitment, 30, &sender_new).is_ok());
+        assert!(verify(&proven.proof_data, &proven.fee_commitment, 31, &sender_new).is_err());
+        assert!(verify(&proven.proof_data, &fresh(&pc, 36), 30, &sender_new).is_err());
+        assert!(verify(&proven.proof_data, &proven.fee_commitment, 30, &fresh(&pc, 37_618)).is_err());
+        assert!(verify(&proven.proof_data, &amount_commitment, 30, &sender_new).is_err());
+    }
+
+    #[test]
+    fn amount_link_ties_token_and_program_commitments() {
+        let (pc, _) = gens();
+        let token = Opening::random(64_000, &mut OsRng);
+        let amount = Opening::random(64_000, &mut OsRng);
+        let token_commitment = pedersen::add(
+            &pedersen::commit_public(token.value).unwrap(),
+            &pedersen::mul(
+                token.blinding.as_bytes(),
+                &pedersen::to_slot(&pedersen::ELGAMAL_BLINDING_GENERATOR),
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        let proof = prove_amount_link(&pc, &token, &amount, &mut OsRng);
+
+        assert!(verify_amount_link_proof(&proof, &token_commitment, &amount.to_slot(&pc)).is_ok());
+        assert!(verify_amount_link_proof(&proof, &token_commitment, &fresh(&pc, 64_001)).is_err());
+        assert!(verify_amount_link_proof(&proof, &amount.to_slot(&pc), &amount.to_slot(&pc)).is_err());
+        assert!(verify_amount_link_proof(&proof[..223], &token_commitment, &amount.to_slot(&pc)).is_err());
+    }
+}
@@ -0,0 +1,294 @@
+/*!
+ * Balance Update Provers
+ *
+ * Proofs that move one hidden balance, each built on the transcript of its
+ * on-chain verifier:
+ * - `prove_deposit` / `prove_withdraw`: public amount ("deposit" / "withdraw")
+ * - `prove_rotation`: same value, fresh blinding ("key-rotation")
+ * - `prove_mint` / `prove_burn`: hidden supply adjustment
+ * - `prove_escrow_debit` / `prove_escrow_credit`: hidden amount into or
+ *   out of an escrow
+ *
+ * An empty balance is the all-zero slot, i.e. `Opening::empty()`.
+ */
+
+use confidential_proofs::merlin_transcript::MerlinTranscript;
+use curve25519_dalek::scalar::Scalar;
+use rand_core::{CryptoRng, RngCore};
+
+use crate::commitment::Opening;
+use crate::equality_proof::prove_zero_opening;
+use crate::generators::{BulletproofGens, PedersenGens, MAX_RANGE_BITS};
+use crate::range_proof::prove_range;
+use crate::{Commitment, ProverError};
+
+/// A proven balance update: instruction argument plus the new opening
+#[derive(Debug, Clone)]
+pub struct ProvenUpdate {
+    /// `proof_data` instruction argument
+    pub proof_data: Vec<u8>,
+    /// Balance commitment after the update
+    pub new_commitment: Commitment,
+    /// Opening of the new balance
+    pub new_opening: Opening,
+}
+
+/// A proven update by a hidden amount
+#[derive(Debug, Clone)]
+pub struct ProvenAmountUpdate {
+    /// `proof_data` instruction argument
+    pub proof_data: Vec<u8>,
+    /// Amount commitment
+    pub amount_commitment: Commitment,
+    /// Balance commitment after the update
+    pub new_commitment: Commitment,
+    /// Opening of the amount commitment
+    pub amount_opening: Opening,
+    /// Opening of the new balance
+    pub new_opening: Opening,
+}
+
+/**
+ * Prove a deposit of the public `amount` (`verify_deposit_proof`)
+ *
+ * LAYOUT: [balance_after_range_proof][equality_proof]
+ */
+pub fn prove_deposit<R: RngCore + CryptoRng>(
+    pc_gens: &PedersenGens,
+    bp_gens: &BulletproofGens,
+    old: &Opening,
+    amount: u64,
+    rng: &mut R,
+) -> Result<ProvenUpdate, ProverError> {
+    let value = old.value.checked_add(amount).ok_or(ProverError::BalanceOverflow)?;
+    let new = Opening::random(value, rng);
+    let range = prove_range(pc_gens, bp_gens, &new, MAX_RANGE_BITS as u8, rng)?;
+    let new_commitment = new.to_slot(pc_gens);
+
+    let mut transcript = MerlinTranscript::new(b"deposit");
+    transcript.append_point(b"C_old", &old.to_slot(pc_gens));
+    transcript.append_point(b"C_new", &new_commitment);
+    transcript.append_message(b"amount", &amount.to_le_bytes());
+    let equality = prove_zero_opening(pc_gens, transcript, &(new.blinding - old.blinding), rng);
+
+    Ok(ProvenUpdate {
+        proof_data: [range.as_slice(), &equality].concat(),
+        new_commitment,
+        new_opening: new,
+    })
+}
+
+/**
+ * Prove a withdrawal of the public `amount` (`verify_withdraw_proof`, or
+ * `verify_withdraw_to_proof` when a `destination` is bound)
+ *
+ * LAYOUT: [balance_after_range_proof][equality_proof]
+ */
+pub fn prove_withdraw<R: RngCore + CryptoRng>(
+    pc_gens: &PedersenGens,
+    bp_gens: &BulletproofGens,
+    old: &Opening,
+    amount: u64,
+    destination: Option<&[u8; 32]>,
+    rng: &mut R,
+) -> Result<ProvenUpdate, ProverError> {
+    let value = old.value.checked_sub(amount).ok_or(ProverError::InsufficientBalance)?;
+    let new = Opening::random(value, rng);
+    let range = prove_range(pc_gens, bp_gens, &new, MAX_RANGE_BITS as u8, rng)?;
+    let new_commitment = new.to_slot(pc_gens);
+
+    let mut transcript = MerlinTranscript::new(b"withdraw");
+    transcript.append_point(b"C_old", &old.to_slot(pc_gens));
+    transcript.append_point(b"C_new", &new_commitment);
+    transcript.append_message(b"amount", &amount.to_le_bytes());
+    if let Some(destination) = destination {
+        transcript.append_message(b"destination", destination);
+    }
+    let equality = prove_zero_opening(pc_gens, transcript, &(old.blinding - new.blinding), rng);
+
+    Ok(ProvenUpdate {
+        proof_data: [range.as_slice(), &equality].concat(),
+        new_commitment,
+        new_opening: new,
+    })
+}
+
+/**
+ * Re-randomize a balance under a fresh blinding (`verify_rotation_proof`)
+ *
+ * LAYOUT: [equality_proof]
+ */
+pub fn prove_rotation<R: RngCore + CryptoRng>(
+    pc_gens: &PedersenGens,
+    old: &Opening,
+    rng: &mut R,
+) -> ProvenUpdate {
+    let new = Opening::random(old.value, rng);
+    let new_commitment = new.to_slot(pc_gens);
+
+    let mut transcript = MerlinTranscript::new(b"key-rotation");
+    transcript.append_point(b"C_old", &old.to_slot(pc_gens));
+    transcript.append_point(b"C_new", &new_commitment);
+    let equality = prove_zero_opening(pc_gens, transcript, &(old.blinding - new.blinding), rng);
+
+    ProvenUpdate {
+        proof_data: equality.to_vec(),
+        new_commitment,
+        new_opening: new,
+    }
+}
+
+/**
+ * Prove a hidden mint of `amount` into `old` (`verify_supply_proof` with
+ * `SupplyDirection::Mint`)
+ *
+ * Minting into an empty balance makes the amount commitment the new
+ * balance, as the verifier requires.
+ *
+ * LAYOUT: [amount_range_proof][balance_after_range_proof][equality_proof]
+ */
+pub fn prove_mint<R: RngCore + CryptoRng>(
+    pc_gens: &PedersenGens,
+    bp_gens: &BulletproofGens,
+    old: &Opening,
+    amount: u64,
+    rng: &mut R,
+) -> Result<ProvenAmountUpdate, ProverError> {
+    let value = old.value.checked_add(amount).ok_or(ProverError::BalanceOverflow)?;
+    let amount_opening = Opening::random(amount, rng);
+    let new = if old.to_slot(pc_gens) == [0u8; 64] {
+        amount_opening
+    } else {
+        Opening::random(value, rng)
+    };
+    let blinding = new.blinding - old.blinding - amount_opening.blinding;
+    prove_supply(pc_gens, bp_gens, b"confidential-mint", old, amount_opening, new, blinding, rng)
+}
+
+/**
+ * Prove a hidden burn of `amount` from `old` (`verify_supply_proof` with
+ * `SupplyDirection::Burn`)
+ *
+ * LAYOUT: [amount_range_proof][balance_after_range_proof][equality_proof]
+ */
+pub fn prove_burn<R: RngCore + CryptoRng>(
+    pc_gens: &PedersenGens,
+    bp_gens: &BulletproofGens,
+    old: &Opening,
+    amount: u64,
+    rng: &mut R,
+) -> Result<ProvenAmountUpdate, ProverError> {
+    let value = old.value.checked_sub(amount).ok_or(ProverError::InsufficientBalance)?;
+    let amount_opening = Opening::random(amount, rng);
+    let new = Opening::random(value, rng);
+    let blinding = old.blinding - new.blinding - amount_opening.blinding;
+    prove_supply(pc_gens, bp_gens, b"confidential-burn", old, amount_opening, new, blinding, rng)
+}
+
+#[allow(clippy::too_many_arguments)]
+fn prove_supply<R: RngCore + CryptoRng>(
+    pc_gens: &PedersenGens,
+    bp_gens: &BulletproofGens,
+    domain: &[u8],
+    old: &Opening,
+    amount_opening: Opening,
+    new: Opening,
+    blinding: Scalar,
+    rng: &mut R,
+) -> Result<ProvenAmountUpdate, ProverError> {
+    let bits = MAX_RANGE_BITS as u8;
+    let amount_range = prove_range(pc_gens, bp_gens, &amount_opening, bits, rng)?;
+    let balance_range = prove_range(pc_gens, bp_gens, &new, bits, rng)?;
+    let amount_commitment = amount_opening.to_slot(pc_gens);
+    let new_commitment = new.to_slot(pc_gens);
+
+    let mut transcript = MerlinTranscript::new(domain);
+    transcript.append_point(b"C_old", &old.to_slot(pc_gens));
+    transcript.append_point(b"C_amount", &amount_commitment);
+    transcript.append_point(b"C_new", &new_commitment);
+    let equality = prove_zero_opening(pc_gens, transcript, &blinding, rng);
+
+    Ok(ProvenAmountUpdate {
+        proof_data: [amount_range.as_slice(), &balance_range, &equality].concat(),
+        amount_commitment,
+        new_commitment,
+        amount_opening,
+        new_opening: new,
+    })
+}
+
+/**
+ * Prove `amount` leaves `old` for an escrow (`verify_escrow_debit_proof`)
+ *
+ * The amount opening is an input so it can be tied to other statements
+ * (e.g. a stream claim) before it is debited.
+ *
+ * LAYOUT: [amount_range_proof][owner_after_range_proof][owner_equality_proof]
+ */
+pub fn prove_escrow_debit<R: RngCore + CryptoRng>(
+    pc_gens: &PedersenGens,
+    bp_gens: &BulletproofGens,
+    old: &Opening,
+    amount: &Opening,
+    rng: &mut R,
+) -> Result<ProvenAmountUpdate, ProverError> {
+    let value = old.value.checked_sub(amount.value).ok_or(ProverError::InsufficientBalance)?;
+    let new = Opening::random(value, rng);
+    let bits = MAX_RANGE_BITS as u8;
+    let amount_range = prove_range(pc_gens, bp_gens, amount, bits, rng)?;
+    let owner_range = prove_range(pc_gens, bp_gens, &new, bits, rng)?;
+    let amount_commitment = amount.to_slot(pc_gens);
+    let new_commitment = new.to_slot(pc_gens);
+
+    let mut transcript = MerlinTranscript::new(b"escrow-debit");
+    transcript.append_point(b"C_amount", &amount_commitment);
+    transcript.append_point(b"C_old", &old.to_slot(pc_gens));
+    transcript.append_point(b"C_new", &new_commitment);
+    let blinding = old.blinding - new.blinding - amount.blinding;
+    let equality = prove_zero_opening(pc_gens, transcript, &blinding, rng);
+
+    Ok(ProvenAmountUpdate {
+        proof_data: [amount_range.as_slice(), &owner_range, &equality].concat(),
+        amount_commitment,
+        new_commitment,
+        amount_opening: *amount,
+        new_opening: new,
+    })
+}
+
+/**
+ * Prove an escrowed `amount` is credited to `old` (`verify_escrow_credit_proof`)
+ *
+ * An empty balance becomes the amount commitment itself; the verifier then
+ * only parses the proof.
+ *
+ * LAYOUT: [equality_proof]
+ */
+pub fn prove_escrow_credit<R: RngCore + CryptoRng>(
+    pc_gens: &PedersenGens,
+    old: &Opening,
+    amount: &Opening,
+    rng: &mut R,
+) -> Result<ProvenUpdate, ProverError> {
+    let value = old.value.checked_add(amount.value).ok_or(ProverError::BalanceOverflow)?;
+    let old_commitment = old.to_slot(pc_gens);
+    let new = if old_commitment == [0u8; 64] {
+        *amount
+    } else {
+        Opening::random(value, rng)
+    };
+    let new_commitment = new.to_slot(pc_gens);
+
+    let mut transcript = MerlinTranscript::new(b"escrow-credit");
+    transcript.append_point(b"C_amount", &amount.to_slot(pc_gens));
+    transcript.append_point(b"C_old", &old_commitment);
+    transcript.append_point(b"C_new", &new_commitment);
+    let blinding = new.blinding - old.blinding - amount.blinding;
+    let equality = prove_zero_opening(pc_gens, transcript, &blinding, rng);
+
+    Ok(ProvenUpdate {
+        proof_data: equality.to_vec(),
+        new_commitment,
+        new_opening: new,
+    })
+}
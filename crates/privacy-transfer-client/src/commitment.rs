@@ -0,0 +1,92 @@
+/*!
+ * Commitments
+ *
+ * Openings of Pedersen commitments and the on-chain point encoding.
+ *
+ * ENCODING:
+ * - Points: 32-byte compressed Ristretto, zero-padded to 64 bytes
+ * - Scalars: 32 bytes little-endian, reduced mod l
+ */
+
+use curve25519_dalek::ristretto::{CompressedRistretto, RistrettoPoint};
+use curve25519_dalek::scalar::Scalar;
+use rand_core::{CryptoRng, RngCore};
+
+use crate::generators::PedersenGens;
+use crate::{Commitment, ProverError};
+
+/// Secret opening (value, blinding) of a Pedersen commitment
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Opening {
+    /// Committed value
+    pub value: u64,
+    /// Blinding factor
+    pub blinding: Scalar,
+}
+
+impl Opening {
+    /**
+     * Opening of `value` with a fresh random blinding
+     */
+    pub fn random<R: RngCore + CryptoRng>(value: u64, rng: &mut R) -> Self {
+        Self {
+            value,
+            blinding: Scalar::random(rng),
+        }
+    }
+
+    /**
+     * Opening of an empty balance (the all-zero slot)
+     */
+    pub fn empty() -> Self {
+        Self {
+            value: 0,
+            blinding: Scalar::ZERO,
+        }
+    }
+
+    /**
+     * Commitment point v*G + r*H
+     */
+    pub fn commit(&self, gens: &PedersenGens) -> RistrettoPoint {
+        gens.commit(Scalar::from(self.value), self.blinding)
+    }
+
+    /**
+     * Commitment in the 64-byte on-chain encoding
+     */
+    pub fn to_slot(&self, gens: &PedersenGens) -> Commitment {
+        point_to_slot(&self.commit(gens))
+    }
+}
+
+/**
+ * Encode a point into a 64-byte commitment slot
+ */
+pub fn point_to_slot(point: &RistrettoPoint) -> Commitment {
+    let mut slot = [0u8; 64];
+    slot[..32].copy_from_slice(point.compress().as_bytes());
+    slot
+}
+
+/**
+ * Decode a 64-byte commitment slot
+ * 
+ * Rejects non-canonical encodings and non-zero padding.
+ */
+pub fn slot_to_point(slot: &[u8; 64]) -> Result<RistrettoPoint, ProverError> {
+    if slot[32..].iter().any(|&b| b != 0) {
+        return Err(ProverError::InvalidPoint);
+    }
+    CompressedRistretto::from_slice(&slot[..32])
+        .ok()
+        .and_then(|compressed| compressed.decompress())
+        .ok_or(ProverError::InvalidPoint)
+}
+
+/**
+ * Interpret a 32-byte transcript challenge as a scalar
+ */
+pub fn challenge_scalar(challenge: &[u8; 32]) -> Scalar {
+    Scalar::from_bytes_mod_order(*challenge)
+}
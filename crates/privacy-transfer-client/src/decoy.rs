@@ -0,0 +1,182 @@
+/*!
+ * Decoy Transfer Prover
+ *
+ * Builds the `proof_data` of a transfer whose real recipient is hidden
+ * among decoys (`verify_decoy_transfer_proof`). Every output gets a fresh
+ * delta D: the real one commits to the amount, decoys commit to zero.
+ *
+ * LAYOUT:
+ * [amount_range_proof][sender_after_range_proof][sender_equality_proof]
+ * [zero_challenge (32)][zero_branch (96)][amount_branch (96)] * outputs
+ * [sum_proof (96)]
+ *
+ * Each output carries an OR-proof over "D opens to zero" and
+ * "D - C_amount opens to zero": the false branch is simulated with a
+ * chosen challenge, and the two challenges add up to the "decoy-transfer"
+ * challenge c. The sum proof shows sum(D) - C_amount opens to zero.
+ */
+
+use confidential_proofs::merlin_transcript::MerlinTranscript;
+use curve25519_dalek::ristretto::RistrettoPoint;
+use curve25519_dalek::scalar::Scalar;
+use rand_core::{CryptoRng, RngCore};
+
+use crate::commitment::{challenge_scalar, point_to_slot, Opening};
+use crate::equality_proof::EqualityNonce;
+use crate::generators::{BulletproofGens, PedersenGens, MAX_RANGE_BITS};
+use crate::range_proof::prove_range;
+use crate::{Commitment, ProverError, EQUALITY_PROOF_SIZE};
+
+/// A proven decoy transfer
+#[derive(Debug, Clone)]
+pub struct ProvenDecoyTransfer {
+    /// `proof_data` instruction argument
+    pub proof_data: Vec<u8>,
+    /// Amount commitment
+    pub amount_commitment: Commitment,
+    /// Sender balance commitment after the transfer
+    pub sender_new_commitment: Commitment,
+    /// Delta added to each output, in output order
+    pub deltas: Vec<Commitment>,
+    /// Opening of the amount commitment (shared with the recipient)
+    pub amount_opening: Opening,
+    /// Sender's opening of their new balance
+    pub sender_new_opening: Opening,
+    /// Opening of each delta
+    pub delta_openings: Vec<Opening>,
+}
+
+/// One OR-proof branch before the challenge is known
+enum Branch {
+    /// Real branch: nonce answered once c is known
+    Real(EqualityNonce),
+    /// Simulated branch: its challenge and serialized R || s
+    Simulated(Scalar, [u8; EQUALITY_PROOF_SIZE]),
+}
+
+impl Branch {
+    fn r(&self) -> [u8; 64] {
+        match self {
+            Branch::Real(nonce) => nonce.r,
+            Branch::Simulated(_, proof) => proof[..64].try_into().expect("64-byte slot"),
+        }
+    }
+}
+
+/**
+ * Prove a transfer of `amount` to output `real_index` of `outputs_old`
+ * (the current balances of every touched output, real and decoy)
+ */
+pub fn prove_decoy_transfer<R: RngCore + CryptoRng>(
+    pc_gens: &PedersenGens,
+    bp_gens: &BulletproofGens,
+    sender_old: &Opening,
+    amount: u64,
+    outputs_old: &[Commitment],
+    real_index: usize,
+    rng: &mut R,
+) -> Result<ProvenDecoyTransfer, ProverError> {
+    if real_index >= outputs_old.len() {
+        return Err(ProverError::ValueOutOfRange);
+    }
+    let sender_value = sender_old
+        .value
+        .checked_sub(amount)
+        .ok_or(ProverError::InsufficientBalance)?;
+
+    let bits = MAX_RANGE_BITS as u8;
+    let amount_opening = Opening::random(amount, rng);
+    let sender_new = Opening::random(sender_value, rng);
+    let amount_range = prove_range(pc_gens, bp_gens, &amount_opening, bits, rng)?;
+    let sender_range = prove_range(pc_gens, bp_gens, &sender_new, bits, rng)?;
+    let amount_point = amount_opening.commit(pc_gens);
+    let amount_commitment = point_to_slot(&amount_point);
+    let sender_new_commitment = sender_new.to_slot(pc_gens);
+
+    let mut transcript = MerlinTranscript::new(b"decoy-transfer");
+    transcript.append_point(b"C_amount", &amount_commitment);
+    transcript.append_point(b"C_sender_old", &sender_old.to_slot(pc_gens));
+    transcript.append_point(b"C_sender_new", &sender_new_commitment);
+
+    let mut delta_openings = Vec::with_capacity(outputs_old.len());
+    let mut branches = Vec::with_capacity(outputs_old.len());
+    for (index, old) in outputs_old.iter().enumerate() {
+        let real = index == real_index;
+        let delta = Opening::random(if real { amount } else { 0 }, rng);
+        let delta_point = delta.commit(pc_gens);
+
+        // The zero branch is false for the real output, the amount branch
+        // for every decoy
+        let (zero, amount_branch) = if real {
+            (simulate(pc_gens, &delta_point, rng), Branch::Real(EqualityNonce::new(pc_gens, rng)))
+        } else {
+            (
+                Branch::Real(EqualityNonce::new(pc_gens, rng)),
+                simulate(pc_gens, &(delta_point - amount_point), rng),
+            )
+        };
+
+        transcript.append_point(b"C_out_old", old);
+        transcript.append_point(b"D_out", &point_to_slot(&delta_point));
+        transcript.append_point(b"R_zero", &zero.r());
+        transcript.append_point(b"R_amount", &amount_branch.r());
+        delta_openings.push(delta);
+        branches.push((zero, amount_branch));
+    }
+    let sum_nonce = EqualityNonce::new(pc_gens, rng);
+    transcript.append_point(b"R_sum", &sum_nonce.r);
+    let challenge = challenge_scalar(&transcript.challenge_scalar(b"c"));
+
+    let sender_nonce = EqualityNonce::new(pc_gens, rng);
+    let sender_blinding = sender_old.blinding - sender_new.blinding - amount_opening.blinding;
+
+    let mut proof_data = Vec::new();
+    proof_data.extend_from_slice(&amount_range);
+    proof_data.extend_from_slice(&sender_range);
+    proof_data.extend_from_slice(&sender_nonce.respond(&challenge, &sender_blinding));
+    for (delta, (zero, amount_branch)) in delta_openings.iter().zip(branches) {
+        let (zero_challenge, zero_proof, amount_proof) = match (zero, amount_branch) {
+            (Branch::Simulated(zero_challenge, zero_proof), Branch::Real(nonce)) => {
+                let blinding = delta.blinding - amount_opening.blinding;
+                let amount_proof = nonce.respond(&(challenge - zero_challenge), &blinding);
+                (zero_challenge, zero_proof, amount_proof)
+            }
+            (Branch::Real(nonce), Branch::Simulated(amount_challenge, amount_proof)) => {
+                let zero_challenge = challenge - amount_challenge;
+                (zero_challenge, nonce.respond(&zero_challenge, &delta.blinding), amount_proof)
+            }
+            _ => unreachable!("exactly one branch per output is simulated"),
+        };
+        proof_data.extend_from_slice(zero_challenge.as_bytes());
+        proof_data.extend_from_slice(&zero_proof);
+        proof_data.extend_from_slice(&amount_proof);
+    }
+    let delta_blinding: Scalar = delta_openings.iter().map(|delta| delta.blinding).sum();
+    proof_data.extend_from_slice(
+        &sum_nonce.respond(&challenge, &(delta_blinding - amount_opening.blinding)),
+    );
+
+    Ok(ProvenDecoyTransfer {
+        proof_data,
+        amount_commitment,
+        sender_new_commitment,
+        deltas: delta_openings.iter().map(|delta| delta.to_slot(pc_gens)).collect(),
+        amount_opening,
+        sender_new_opening: sender_new,
+        delta_openings,
+    })
+}
+
+/// Simulated branch for D: random c and s, R = s*H - c*D
+fn simulate<R: RngCore + CryptoRng>(
+    pc_gens: &PedersenGens,
+    d: &RistrettoPoint,
+    rng: &mut R,
+) -> Branch {
+    let challenge = Scalar::random(rng);
+    let s = Scalar::random(rng);
+    let mut proof = [0u8; EQUALITY_PROOF_SIZE];
+    proof[..64].copy_from_slice(&point_to_slot(&(pc_gens.h * s - d * challenge)));
+    proof[64..].copy_from_slice(s.as_bytes());
+    Branch::Simulated(challenge, proof)
+}
@@ -0,0 +1,80 @@
+/*!
+ * Fee Prover
+ *
+ * Proves a hidden fee of floor(amount * bps / 10_000) for
+ * `verify_fee_proof`: the rounding remainder V = bps*amount - 10_000*fee is
+ * committed, range-proven to FEE_REMAINDER_BITS, and
+ * bps*C_amount - 10_000*C_fee - V is shown to open to zero under the
+ * "transfer-fee" transcript.
+ *
+ * LAYOUT: [remainder_range_proof][remainder_equality_proof]
+ *
+ * The transfer itself is proven against sender_old - fee, so callers pass
+ * `prove_transfer` an opening of (value - fee, blinding - fee blinding).
+ */
+
+use confidential_proofs::merlin_transcript::MerlinTranscript;
+use curve25519_dalek::scalar::Scalar;
+use privacy_transfer_types::proof_constants::{BPS_DENOMINATOR, FEE_REMAINDER_BITS};
+use rand_core::{CryptoRng, RngCore};
+
+use crate::commitment::Opening;
+use crate::equality_proof::prove_zero_opening;
+use crate::generators::{BulletproofGens, PedersenGens};
+use crate::range_proof::prove_range;
+use crate::{Commitment, ProverError};
+
+/// A proven transfer fee
+#[derive(Debug, Clone)]
+pub struct ProvenFee {
+    /// `ConfidentialFee::proof_data`
+    pub proof_data: Vec<u8>,
+    /// Fee commitment
+    pub fee_commitment: Commitment,
+    /// Opening of the fee commitment
+    pub fee_opening: Opening,
+}
+
+/**
+ * Prove the fee at `fee_bps` on the hidden `amount`
+ *
+ * `sender_old` / `sender_new` are the sender's balances around the
+ * transfer; they are bound into the transcript, not opened.
+ */
+pub fn prove_fee<R: RngCore + CryptoRng>(
+    pc_gens: &PedersenGens,
+    bp_gens: &BulletproofGens,
+    amount: &Opening,
+    fee_bps: u16,
+    sender_old: &Commitment,
+    sender_new: &Commitment,
+    rng: &mut R,
+) -> Result<ProvenFee, ProverError> {
+    if fee_bps == 0 || fee_bps as u64 > BPS_DENOMINATOR {
+        return Err(ProverError::ValueOutOfRange);
+    }
+    let scaled = amount.value as u128 * fee_bps as u128;
+    let fee = Opening::random((scaled / BPS_DENOMINATOR as u128) as u64, rng);
+    let remainder = Opening::random((scaled % BPS_DENOMINATOR as u128) as u64, rng);
+
+    let remainder_range = prove_range(pc_gens, bp_gens, &remainder, FEE_REMAINDER_BITS, rng)?;
+    let fee_commitment = fee.to_slot(pc_gens);
+
+    let mut transcript = MerlinTranscript::new(b"transfer-fee");
+    transcript.append_point(b"C_amount", &amount.to_slot(pc_gens));
+    transcript.append_point(b"C_fee", &fee_commitment);
+    transcript.append_message(b"bps", &fee_bps.to_le_bytes());
+    transcript.append_point(b"C_sender_old", sender_old);
+    transcript.append_point(b"C_sender_new", sender_new);
+    transcript.append_point(b"V_remainder", &remainder.to_slot(pc_gens));
+    let blinding = Scalar::from(fee_bps) * amount.blinding
+        - Scalar::from(BPS_DENOMINATOR) * fee.blinding
+        - remainder.blinding;
+    let equality = prove_zero_opening(pc_gens, transcript, &blinding, rng);
+
+    Ok(ProvenFee {
+        proof_data: [remainder_range.as_slice(), &equality].concat(),
+        fee_commitment,
+        fee_opening: fee,
+    })
+}
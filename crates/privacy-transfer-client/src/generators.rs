@@ -0,0 +1,116 @@
+/*!
+ * Generators
+ *
+ * Pedersen bases (G, H) and the Bulletproof vector bases (G_i, H_i).
+ *
+ * Derivations follow the TypeScript prover:
+ * - G: the Ristretto basepoint
+ * - H: basepoint * (SHA-256("pedersen_h_generator") mod l)
+ * - G_i / H_i: 64-byte blocks squeezed from
+ *   Shake256("GeneratorsChain" || "G" / "H"), mapped with
+ *   `RistrettoPoint::from_uniform_bytes`
+ *
+ * `BulletproofGens::from_table` loads bases published on-chain in a
+ * `GeneratorTable` instead.
+ */
+
+use curve25519_dalek::constants::RISTRETTO_BASEPOINT_POINT;
+use curve25519_dalek::ristretto::RistrettoPoint;
+use curve25519_dalek::scalar::Scalar;
+use sha2::{Digest, Sha256};
+use sha3::digest::{ExtendableOutput, Update, XofReader};
+use sha3::Shake256;
+
+use crate::commitment::slot_to_point;
+use crate::ProverError;
+
+/// Maximum range size, and number of vector generators per side
+pub const MAX_RANGE_BITS: usize = 64;
+
+/// Pedersen commitment bases: C = v*G + r*H
+#[derive(Debug, Clone, Copy)]
+pub struct PedersenGens {
+    /// Value base
+    pub g: RistrettoPoint,
+    /// Blinding base
+    pub h: RistrettoPoint,
+}
+
+impl Default for PedersenGens {
+    fn default() -> Self {
+        let hash: [u8; 32] = Sha256::digest(b"pedersen_h_generator").into();
+        Self {
+            g: RISTRETTO_BASEPOINT_POINT,
+            h: RISTRETTO_BASEPOINT_POINT * Scalar::from_bytes_mod_order(hash),
+        }
+    }
+}
+
+impl PedersenGens {
+    /**
+     * Commit to `value` with `blinding`
+     */
+    pub fn commit(&self, value: Scalar, blinding: Scalar) -> RistrettoPoint {
+        self.g * value + self.h * blinding
+    }
+}
+
+/// Bulletproof vector bases for a single range proof of up to 64 bits
+#[derive(Debug, Clone)]
+pub struct BulletproofGens {
+    /// G_0 .. G_63
+    pub g_vec: Vec<RistrettoPoint>,
+    /// H_0 .. H_63
+    pub h_vec: Vec<RistrettoPoint>,
+}
+
+impl Default for BulletproofGens {
+    fn default() -> Self {
+        Self {
+            g_vec: generator_chain(b"G", MAX_RANGE_BITS),
+            h_vec: generator_chain(b"H", MAX_RANGE_BITS),
+        }
+    }
+}
+
+impl BulletproofGens {
+    /**
+     * Load the vector bases from a finalized on-chain `GeneratorTable`
+     * 
+     * `g_vec` / `h_vec` are the table's 64-byte slots, in order.
+     */
+    pub fn from_table(g_vec: &[[u8; 64]], h_vec: &[[u8; 64]]) -> Result<Self, ProverError> {
+        let decode = |slots: &[[u8; 64]]| -> Result<Vec<RistrettoPoint>, ProverError> {
+            slots.iter().map(slot_to_point).collect()
+        };
+        Ok(Self {
+            g_vec: decode(g_vec)?,
+            h_vec: decode(h_vec)?,
+        })
+    }
+
+    /**
+     * Number of bits these generators can prove
+     */
+    pub fn capacity(&self) -> usize {
+        self.g_vec.len().min(self.h_vec.len())
+    }
+}
+
+/**
+ * Squeeze `n` points from Shake256("GeneratorsChain" || label)
+ */
+fn generator_chain(label: &[u8], n: usize) -> Vec<RistrettoPoint> {
+    let mut shake = Shake256::default();
+    shake.update(b"GeneratorsChain");
+    shake.update(label);
+    let mut reader = shake.finalize_xof();
+
+    (0..n)
+        .map(|_| {
+            let mut uniform = [0u8; 64];
+            reader.read(&mut uniform);
+            RistrettoPoint::from_uniform_bytes(&uniform)
+        })
+        .collect()
+}
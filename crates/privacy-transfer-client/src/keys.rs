@@ -0,0 +1,71 @@
+/*!
+ * Wallet Keys
+ *
+ * Keys derived from the 32-byte wallet seed, matching the TypeScript
+ * wallet (`ConfidentialWallet`, `src/crypto/viewingKey.ts`):
+ * - Signer: Ed25519 keypair with the seed as secret (`Keypair.fromSeed`)
+ * - Viewing secret: SHA-256("privacy-transfer/viewing-key" || seed)
+ * - Viewing key: X25519 public key of the viewing secret
+ *
+ * The viewing key reads balance openings (see `viewing_key`) but carries
+ * no spend authority.
+ */
+
+use curve25519_dalek::edwards::EdwardsPoint;
+use curve25519_dalek::montgomery::MontgomeryPoint;
+use sha2::{Digest, Sha256, Sha512};
+
+/// Domain of the viewing secret derived from a wallet seed
+pub const VIEWING_KEY_DOMAIN: &[u8] = b"privacy-transfer/viewing-key";
+
+/// Public keys and viewing secret of a wallet seed
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WalletKeys {
+    /// Ed25519 public key of the signer (the owner pubkey on-chain)
+    pub signer_public_key: [u8; 32],
+    /// X25519 viewing secret (exportable to watch-only wallets)
+    pub viewing_secret: [u8; 32],
+    /// Viewing public key to register with register_viewing_key
+    pub viewing_public_key: [u8; 32],
+}
+
+impl WalletKeys {
+    /**
+     * Derive every key of a wallet seed
+     */
+    pub fn from_seed(seed: &[u8; 32]) -> Self {
+        let viewing_secret = derive_viewing_secret(seed);
+        Self {
+            signer_public_key: signer_public_key(seed),
+            viewing_secret,
+            viewing_public_key: viewing_public_key(&viewing_secret),
+        }
+    }
+}
+
+/**
+ * Ed25519 public key of the signer whose secret is `seed`
+ */
+pub fn signer_public_key(seed: &[u8; 32]) -> [u8; 32] {
+    let hash = Sha512::digest(seed);
+    let mut scalar = [0u8; 32];
+    scalar.copy_from_slice(&hash[..32]);
+    EdwardsPoint::mul_base_clamped(scalar).compress().to_bytes()
+}
+
+/**
+ * Viewing secret of a wallet (deterministic, so it can be re-exported)
+ */
+pub fn derive_viewing_secret(seed: &[u8]) -> [u8; 32] {
+    let mut hasher = Sha256::new();
+    hasher.update(VIEWING_KEY_DOMAIN);
+    hasher.update(seed);
+    hasher.finalize().into()
+}
+
+/**
+ * Public key to register on-chain for a viewing secret
+ */
+pub fn viewing_public_key(viewing_secret: &[u8; 32]) -> [u8; 32] {
+    MontgomeryPoint::mul_base_clamped(*viewing_secret).to_bytes()
+}
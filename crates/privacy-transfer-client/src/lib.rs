@@ -0,0 +1,82 @@
+/*!
+ * Privacy Transfer Client
+ *
+ * Off-chain prover for privacy-transfer: Pedersen commitments, Bulletproof
+ * range proofs, Schnorr equality proofs and Token-2022 amount links, serialized in exactly the byte
+ * layout `confidential_proofs::proof_verification::deserialize_proof_data`
+ * expects. Every other statement the program checks (deposits, supply,
+ * escrows, limits, fees, multi-recipient and decoy transfers) has a prover
+ * built on the same transcript as its verifier. Lets Rust wallets and
+ * services build transfers without going through the TypeScript prover.
+ *
+ * COMPATIBILITY:
+ * - Generators match `src/crypto/zkproofs` (Pedersen H, Dalek-style
+ *   Shake256 vector generators), so commitments made by either prover can
+ *   be spent by the other.
+ * - Points are written as the 32-byte compressed Ristretto encoding,
+ *   zero-padded to the on-chain 64-byte slot.
+ * - Challenges come from `confidential_proofs::merlin_transcript`, the same
+ *   transcript the program runs.
+ * - Wallet keys and balance views (`keys`, `viewing_key`) follow
+ *   `src/crypto/viewingKey.ts`, so one seed yields the same keys in both.
+ *
+ * Only the default commitment scheme (Pedersen over Ristretto255) is
+ * supported.
+ */
+
+pub mod amount_link;
+pub mod balance;
+pub mod commitment;
+pub mod decoy;
+pub mod equality_proof;
+pub mod fee;
+pub mod generators;
+pub mod keys;
+pub mod limits;
+pub mod multi_transfer;
+pub mod range_proof;
+pub mod schedule;
+pub mod transfer;
+pub mod viewing_key;
+
+pub use amount_link::prove_amount_link;
+pub use balance::{
+    prove_burn, prove_deposit, prove_escrow_credit, prove_escrow_debit, prove_mint,
+    prove_rotation, prove_withdraw, ProvenAmountUpdate, ProvenUpdate,
+};
+pub use commitment::{point_to_slot, slot_to_point, Opening};
+pub use decoy::{prove_decoy_transfer, ProvenDecoyTransfer};
+pub use equality_proof::{prove_amount_match, prove_opening, EqualityNonce};
+pub use fee::{prove_fee, ProvenFee};
+pub use generators::{BulletproofGens, PedersenGens};
+pub use keys::WalletKeys;
+pub use limits::{prove_balance_cap, prove_velocity, ProvenVelocity};
+pub use multi_transfer::{prove_multi_transfer, ProvenMultiTransfer};
+pub use range_proof::prove_range;
+pub use schedule::{prove_stream_accrual, prove_vesting_schedule, ProvenAccrual};
+pub use transfer::{prove_transfer, ProvenTransfer};
+pub use viewing_key::{open_balance_view, seal_balance_view, EncryptedView};
+
+pub use privacy_transfer_types::proof_constants::{
+    AMOUNT_LINK_PROOF_SIZE, EQUALITY_PROOF_SIZE, RANGE_PROOF_SIZE, TRANSFER_PROOF_SIZE,
+};
+pub use privacy_transfer_types::Commitment;
+
+/// Error codes for proof generation
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProverError {
+    /// Value does not fit in the requested number of bits
+    ValueOutOfRange,
+    /// Range size must be 1..=64 bits
+    InvalidRangeBits,
+    /// Sender balance is smaller than the amount
+    InsufficientBalance,
+    /// Recipient balance would exceed u64::MAX
+    BalanceOverflow,
+    /// Generator table holds fewer generators than the range size
+    NotEnoughGenerators,
+    /// Bytes are not a canonical Ristretto point in a 64-byte slot
+    InvalidPoint,
+    /// Balance view was not encrypted to this key or was tampered with
+    ViewDecryptionFailed,
+}
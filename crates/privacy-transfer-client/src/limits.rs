@@ -0,0 +1,80 @@
+/*!
+ * Limit Provers
+ *
+ * Range proofs on headroom commitments the program derives itself:
+ * - `prove_balance_cap`: cap*G - C_balance (`verify_balance_cap_proof`)
+ * - `prove_velocity`: limit*G - C_total_new, where the new epoch total is
+ *   the old total plus the amount commitment (`verify_velocity_proof`)
+ *
+ * The headroom openings carry the negated blinding of the balance.
+ */
+
+use rand_core::{CryptoRng, RngCore};
+
+use crate::commitment::Opening;
+use crate::generators::{BulletproofGens, PedersenGens, MAX_RANGE_BITS};
+use crate::range_proof::prove_range;
+use crate::{Commitment, ProverError};
+
+/// A proven velocity update
+#[derive(Debug, Clone)]
+pub struct ProvenVelocity {
+    /// `VelocityUpdate::proof_data`
+    pub proof_data: Vec<u8>,
+    /// Epoch total after the transfer (total_old + amount)
+    pub total_new_commitment: Commitment,
+    /// Opening of the new epoch total
+    pub total_new_opening: Opening,
+}
+
+/**
+ * Prove `balance` is at most `cap`
+ */
+pub fn prove_balance_cap<R: RngCore + CryptoRng>(
+    pc_gens: &PedersenGens,
+    bp_gens: &BulletproofGens,
+    balance: &Opening,
+    cap: u64,
+    rng: &mut R,
+) -> Result<Vec<u8>, ProverError> {
+    let headroom = headroom(balance, cap)?;
+    Ok(prove_range(pc_gens, bp_gens, &headroom, MAX_RANGE_BITS as u8, rng)?.to_vec())
+}
+
+/**
+ * Prove `total_old + amount` stays within the epoch `limit`
+ */
+pub fn prove_velocity<R: RngCore + CryptoRng>(
+    pc_gens: &PedersenGens,
+    bp_gens: &BulletproofGens,
+    total_old: &Opening,
+    amount: &Opening,
+    limit: u64,
+    rng: &mut R,
+) -> Result<ProvenVelocity, ProverError> {
+    let total_new = Opening {
+        value: total_old
+            .value
+            .checked_add(amount.value)
+            .ok_or(ProverError::BalanceOverflow)?,
+        blinding: total_old.blinding + amount.blinding,
+    };
+    let headroom = headroom(&total_new, limit)?;
+    let proof = prove_range(pc_gens, bp_gens, &headroom, MAX_RANGE_BITS as u8, rng)?;
+
+    Ok(ProvenVelocity {
+        proof_data: proof.to_vec(),
+        total_new_commitment: total_new.to_slot(pc_gens),
+        total_new_opening: total_new,
+    })
+}
+
+/// Opening of bound*G - C
+fn headroom(opening: &Opening, bound: u64) -> Result<Opening, ProverError> {
+    Ok(Opening {
+        value: bound
+            .checked_sub(opening.value)
+            .ok_or(ProverError::ValueOutOfRange)?,
+        blinding: -opening.blinding,
+    })
+}
@@ -0,0 +1,135 @@
+/*!
+ * Multi-Recipient Transfer Prover
+ *
+ * Builds the `proof_data` of a 1 -> N transfer for
+ * `verify_multi_transfer_proof`.
+ *
+ * LAYOUT:
+ * [sender_after_range_proof][sender_equality_proof]
+ * [amount_commitment][amount_range_proof][recipient_equality_proof] * N
+ *
+ * All equality proofs answer one "multi-transfer" challenge over every
+ * commitment and nonce. The sender's proof opens
+ * sender_old - sender_new - sum(amount_i); each recipient's opens
+ * new - old - amount. An empty recipient balance becomes the amount
+ * commitment itself.
+ */
+
+use confidential_proofs::merlin_transcript::MerlinTranscript;
+use curve25519_dalek::scalar::Scalar;
+use rand_core::{CryptoRng, RngCore};
+
+use crate::commitment::{challenge_scalar, Opening};
+use crate::equality_proof::EqualityNonce;
+use crate::generators::{BulletproofGens, PedersenGens, MAX_RANGE_BITS};
+use crate::range_proof::prove_range;
+use crate::{Commitment, ProverError};
+
+/// A proven multi-recipient transfer
+#[derive(Debug, Clone)]
+pub struct ProvenMultiTransfer {
+    /// `proof_data` instruction argument
+    pub proof_data: Vec<u8>,
+    /// Sender balance commitment after the transfer
+    pub sender_new_commitment: Commitment,
+    /// Sender's opening of their new balance
+    pub sender_new_opening: Opening,
+    /// New balance commitment of each recipient
+    pub recipient_new_commitments: Vec<Commitment>,
+    /// Opening of each leg's amount (shared with its recipient)
+    pub amount_openings: Vec<Opening>,
+    /// Opening of each recipient's new balance
+    pub recipient_new_openings: Vec<Opening>,
+}
+
+/**
+ * Prove a transfer of `legs` (recipient's old opening, amount) from
+ * `sender_old`
+ */
+pub fn prove_multi_transfer<R: RngCore + CryptoRng>(
+    pc_gens: &PedersenGens,
+    bp_gens: &BulletproofGens,
+    sender_old: &Opening,
+    legs: &[(Opening, u64)],
+    rng: &mut R,
+) -> Result<ProvenMultiTransfer, ProverError> {
+    let total = legs
+        .iter()
+        .try_fold(0u64, |total, (_, amount)| total.checked_add(*amount))
+        .ok_or(ProverError::BalanceOverflow)?;
+    let sender_value = sender_old
+        .value
+        .checked_sub(total)
+        .ok_or(ProverError::InsufficientBalance)?;
+
+    let bits = MAX_RANGE_BITS as u8;
+    let sender_new = Opening::random(sender_value, rng);
+    let sender_new_commitment = sender_new.to_slot(pc_gens);
+    let sender_range = prove_range(pc_gens, bp_gens, &sender_new, bits, rng)?;
+    let sender_nonce = EqualityNonce::new(pc_gens, rng);
+
+    let mut transcript = MerlinTranscript::new(b"multi-transfer");
+    transcript.append_point(b"C_sender_old", &sender_old.to_slot(pc_gens));
+    transcript.append_point(b"C_sender_new", &sender_new_commitment);
+    transcript.append_point(b"R_sender", &sender_nonce.r);
+
+    let mut amount_openings = Vec::with_capacity(legs.len());
+    let mut recipient_new_openings = Vec::with_capacity(legs.len());
+    let mut amount_ranges = Vec::with_capacity(legs.len());
+    let mut recipient_nonces = Vec::with_capacity(legs.len());
+    for (recipient_old, amount) in legs {
+        let value = recipient_old
+            .value
+            .checked_add(*amount)
+            .ok_or(ProverError::BalanceOverflow)?;
+        let amount_opening = Opening::random(*amount, rng);
+        let recipient_old_commitment = recipient_old.to_slot(pc_gens);
+        let recipient_new = if recipient_old_commitment == [0u8; 64] {
+            amount_opening
+        } else {
+            Opening::random(value, rng)
+        };
+        let nonce = EqualityNonce::new(pc_gens, rng);
+
+        transcript.append_point(b"C_amount", &amount_opening.to_slot(pc_gens));
+        transcript.append_point(b"C_out_old", &recipient_old_commitment);
+        transcript.append_point(b"C_out_new", &recipient_new.to_slot(pc_gens));
+        transcript.append_point(b"R_out", &nonce.r);
+
+        amount_ranges.push(prove_range(pc_gens, bp_gens, &amount_opening, bits, rng)?);
+        amount_openings.push(amount_opening);
+        recipient_new_openings.push(recipient_new);
+        recipient_nonces.push(nonce);
+    }
+    let challenge = challenge_scalar(&transcript.challenge_scalar(b"c"));
+
+    let amount_blinding: Scalar = amount_openings.iter().map(|opening| opening.blinding).sum();
+    let sender_blinding = sender_old.blinding - sender_new.blinding - amount_blinding;
+
+    let mut proof_data = Vec::new();
+    proof_data.extend_from_slice(&sender_range);
+    proof_data.extend_from_slice(&sender_nonce.respond(&challenge, &sender_blinding));
+    for ((((recipient_old, _), amount_opening), recipient_new), (range, nonce)) in legs
+        .iter()
+        .zip(&amount_openings)
+        .zip(&recipient_new_openings)
+        .zip(amount_ranges.iter().zip(recipient_nonces))
+    {
+        let blinding = recipient_new.blinding - recipient_old.blinding - amount_opening.blinding;
+        proof_data.extend_from_slice(&amount_opening.to_slot(pc_gens));
+        proof_data.extend_from_slice(range);
+        proof_data.extend_from_slice(&nonce.respond(&challenge, &blinding));
+    }
+
+    Ok(ProvenMultiTransfer {
+        proof_data,
+        sender_new_commitment,
+        sender_new_opening: sender_new,
+        recipient_new_commitments: recipient_new_openings
+            .iter()
+            .map(|opening| opening.to_slot(pc_gens))
+            .collect(),
+        amount_openings,
+        recipient_new_openings,
+    })
+}
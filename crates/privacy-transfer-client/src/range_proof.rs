@@ -0,0 +1,137 @@
+/*!
+ * Range Proof
+ *
+ * Bulletproof prover for a single value in [0, 2^n), stopping after the
+ * polynomial commitment round: the output holds V, A, S, T1, T2, taux, mu,
+ * t and n, which is what `read_range_proof` parses. The inner product
+ * argument is not part of the on-chain layout.
+ *
+ * TRANSCRIPT (matches `verify_range_proof`):
+ * - domain `rangeproof_domain_sep(n, 1)`
+ * - V, A, S -> challenges y, z
+ * - T1, T2 -> challenge x
+ */
+
+use confidential_proofs::merlin_transcript::{rangeproof_domain_sep, MerlinTranscript};
+use curve25519_dalek::ristretto::RistrettoPoint;
+use curve25519_dalek::scalar::Scalar;
+use curve25519_dalek::traits::MultiscalarMul;
+use rand_core::{CryptoRng, RngCore};
+
+use crate::commitment::{challenge_scalar, point_to_slot, Opening};
+use crate::generators::{BulletproofGens, PedersenGens, MAX_RANGE_BITS};
+use crate::{ProverError, RANGE_PROOF_SIZE};
+
+/**
+ * Prove `opening.value < 2^n` for the commitment of `opening`
+ */
+pub fn prove_range<R: RngCore + CryptoRng>(
+    pc_gens: &PedersenGens,
+    bp_gens: &BulletproofGens,
+    opening: &Opening,
+    n: u8,
+    rng: &mut R,
+) -> Result<[u8; RANGE_PROOF_SIZE], ProverError> {
+    let bits = n as usize;
+    if bits == 0 || bits > MAX_RANGE_BITS {
+        return Err(ProverError::InvalidRangeBits);
+    }
+    if bits < MAX_RANGE_BITS && opening.value >> bits != 0 {
+        return Err(ProverError::ValueOutOfRange);
+    }
+    if bp_gens.capacity() < bits {
+        return Err(ProverError::NotEnoughGenerators);
+    }
+    let g_vec = &bp_gens.g_vec[..bits];
+    let h_vec = &bp_gens.h_vec[..bits];
+
+    let v = point_to_slot(&opening.commit(pc_gens));
+
+    // Bit decomposition: a_L in {0,1}^n, a_R = a_L - 1
+    let a_l: Vec<Scalar> = (0..bits)
+        .map(|i| Scalar::from((opening.value >> i) & 1))
+        .collect();
+    let a_r: Vec<Scalar> = a_l.iter().map(|a| a - Scalar::ONE).collect();
+
+    let alpha = Scalar::random(rng);
+    let a = vector_commit(pc_gens, alpha, &a_l, &a_r, g_vec, h_vec);
+
+    let s_l: Vec<Scalar> = (0..bits).map(|_| Scalar::random(rng)).collect();
+    let s_r: Vec<Scalar> = (0..bits).map(|_| Scalar::random(rng)).collect();
+    let rho = Scalar::random(rng);
+    let s = vector_commit(pc_gens, rho, &s_l, &s_r, g_vec, h_vec);
+
+    let mut transcript = MerlinTranscript::new(&rangeproof_domain_sep(n, 1));
+    transcript.append_point(b"V", &v);
+    transcript.append_point(b"A", &a);
+    transcript.append_point(b"S", &s);
+    let y = challenge_scalar(&transcript.challenge_scalar(b"y"));
+    let z = challenge_scalar(&transcript.challenge_scalar(b"z"));
+    let zz = z * z;
+
+    // l(X) = (a_L - z) + s_L*X
+    // r(X) = y^i * (a_R + z + s_R*X) + z^2 * 2^i
+    // t(X) = <l(X), r(X)> = t0 + t1*X + t2*X^2
+    let mut t0 = Scalar::ZERO;
+    let mut t1 = Scalar::ZERO;
+    let mut t2 = Scalar::ZERO;
+    let mut y_i = Scalar::ONE;
+    let mut two_i = Scalar::ONE;
+    for i in 0..bits {
+        let l0 = a_l[i] - z;
+        let l1 = s_l[i];
+        let r0 = y_i * (a_r[i] + z) + zz * two_i;
+        let r1 = y_i * s_r[i];
+
+        t0 += l0 * r0;
+        t1 += l0 * r1 + l1 * r0;
+        t2 += l1 * r1;
+
+        y_i *= y;
+        two_i += two_i;
+    }
+
+    let tau1 = Scalar::random(rng);
+    let tau2 = Scalar::random(rng);
+    let t1_commitment = point_to_slot(&pc_gens.commit(t1, tau1));
+    let t2_commitment = point_to_slot(&pc_gens.commit(t2, tau2));
+
+    transcript.append_point(b"T1", &t1_commitment);
+    transcript.append_point(b"T2", &t2_commitment);
+    let x = challenge_scalar(&transcript.challenge_scalar(b"x"));
+
+    let taux = tau2 * x * x + tau1 * x + zz * opening.blinding;
+    let mu = alpha + rho * x;
+    let t = t0 + t1 * x + t2 * x * x;
+
+    let mut proof = [0u8; RANGE_PROOF_SIZE];
+    let mut offset = 0;
+    for field in [&v, &a, &s, &t1_commitment, &t2_commitment] {
+        proof[offset..offset + 64].copy_from_slice(field);
+        offset += 64;
+    }
+    for scalar in [taux, mu, t] {
+        proof[offset..offset + 32].copy_from_slice(scalar.as_bytes());
+        offset += 32;
+    }
+    proof[offset] = n;
+    Ok(proof)
+}
+
+/**
+ * blinding*H + <l, G_vec> + <r, H_vec>, as a 64-byte slot
+ */
+fn vector_commit(
+    pc_gens: &PedersenGens,
+    blinding: Scalar,
+    l: &[Scalar],
+    r: &[Scalar],
+    g_vec: &[RistrettoPoint],
+    h_vec: &[RistrettoPoint],
+) -> [u8; 64] {
+    let point = RistrettoPoint::multiscalar_mul(
+        std::iter::once(&blinding).chain(l).chain(r),
+        std::iter::once(&pc_gens.h).chain(g_vec).chain(h_vec),
+    );
+    point_to_slot(&point)
+}
@@ -0,0 +1,94 @@
+/*!
+ * Stream and Vesting Provers
+ *
+ * - `prove_stream_accrual`: a claim is exactly elapsed * rate
+ *   (`verify_stream_accrual_proof`, "stream-accrual" transcript)
+ * - `prove_vesting_schedule`: range proof on the headroom
+ *   duration * remaining - (duration - elapsed) * total, which the program
+ *   derives itself (`verify_vesting_schedule_proof`)
+ */
+
+use confidential_proofs::merlin_transcript::MerlinTranscript;
+use curve25519_dalek::scalar::Scalar;
+use rand_core::{CryptoRng, RngCore};
+
+use crate::commitment::Opening;
+use crate::equality_proof::prove_zero_opening;
+use crate::generators::{BulletproofGens, PedersenGens, MAX_RANGE_BITS};
+use crate::range_proof::prove_range;
+use crate::{Commitment, ProverError, EQUALITY_PROOF_SIZE};
+
+/// A proven stream claim
+#[derive(Debug, Clone)]
+pub struct ProvenAccrual {
+    /// `accrual_proof` instruction argument
+    pub proof: [u8; EQUALITY_PROOF_SIZE],
+    /// Claim commitment
+    pub claim_commitment: Commitment,
+    /// Opening of the claim commitment
+    pub claim_opening: Opening,
+}
+
+/**
+ * Prove a claim of `elapsed` seconds at the hidden `rate`
+ */
+pub fn prove_stream_accrual<R: RngCore + CryptoRng>(
+    pc_gens: &PedersenGens,
+    rate: &Opening,
+    elapsed: u64,
+    rng: &mut R,
+) -> Result<ProvenAccrual, ProverError> {
+    let value = rate.value.checked_mul(elapsed).ok_or(ProverError::BalanceOverflow)?;
+    let claim = Opening::random(value, rng);
+    let claim_commitment = claim.to_slot(pc_gens);
+
+    let mut transcript = MerlinTranscript::new(b"stream-accrual");
+    transcript.append_point(b"C_rate", &rate.to_slot(pc_gens));
+    transcript.append_point(b"C_claim", &claim_commitment);
+    transcript.append_message(b"elapsed", &elapsed.to_le_bytes());
+    let blinding = claim.blinding - Scalar::from(elapsed) * rate.blinding;
+    let proof = prove_zero_opening(pc_gens, transcript, &blinding, rng);
+
+    Ok(ProvenAccrual {
+        proof,
+        claim_commitment,
+        claim_opening: claim,
+    })
+}
+
+/**
+ * Prove `remaining` keeps at least the unvested part of `total` after
+ * `elapsed` of `duration` seconds
+ *
+ * Returns the `schedule_proof` instruction argument.
+ */
+pub fn prove_vesting_schedule<R: RngCore + CryptoRng>(
+    pc_gens: &PedersenGens,
+    bp_gens: &BulletproofGens,
+    total: &Opening,
+    remaining: &Opening,
+    elapsed: u64,
+    duration: u64,
+    rng: &mut R,
+) -> Result<Vec<u8>, ProverError> {
+    if duration == 0 || elapsed > duration {
+        return Err(ProverError::ValueOutOfRange);
+    }
+    let unvested_time = duration - elapsed;
+
+    // Exact in u128: both products are below 2^128
+    let covered = duration as u128 * remaining.value as u128;
+    let unvested = unvested_time as u128 * total.value as u128;
+    let headroom = covered
+        .checked_sub(unvested)
+        .and_then(|headroom| u64::try_from(headroom).ok())
+        .ok_or(ProverError::ValueOutOfRange)?;
+
+    let opening = Opening {
+        value: headroom,
+        blinding: Scalar::from(duration) * remaining.blinding
+            - Scalar::from(unvested_time) * total.blinding,
+    };
+    let proof = prove_range(pc_gens, bp_gens, &opening, MAX_RANGE_BITS as u8, rng)?;
+    Ok(proof.to_vec())
+}
@@ -0,0 +1,238 @@
+/*!
+ * Transfer Prover
+ *
+ * Builds the `proof_data` argument of `confidential_transfer` together with
+ * the three new commitments it is checked against.
+ *
+ * LAYOUT (TRANSFER_PROOF_SIZE bytes, parsed by `deserialize_proof_data`):
+ * 1. Range proof: amount
+ * 2. Range proof: sender balance after
+ * 3. Range proof: recipient balance after
+ * 4. Equality proof: sender_old - amount - sender_new commits to zero
+ * 5. Equality proof: recipient_new - recipient_old - amount commits to zero
+ *
+ * Both equality proofs answer the challenge of `transfer_challenge`, which
+ * binds all five commitments, so the proof only verifies against the
+ * balances it was built for.
+ */
+
+use confidential_proofs::transfer_challenge_from_nonces;
+use rand_core::{CryptoRng, RngCore};
+
+use crate::commitment::{challenge_scalar, Opening};
+use crate::equality_proof::EqualityNonce;
+use crate::generators::{BulletproofGens, PedersenGens, MAX_RANGE_BITS};
+use crate::range_proof::prove_range;
+use crate::{Commitment, ProverError, TRANSFER_PROOF_SIZE};
+
+/// A proven transfer: instruction arguments plus the new openings
+#[derive(Debug, Clone)]
+pub struct ProvenTransfer {
+    /// `proof_data` instruction argument
+    pub proof_data: Vec<u8>,
+    /// Amount commitment
+    pub amount_commitment: Commitment,
+    /// Sender balance commitment after the transfer
+    pub sender_new_commitment: Commitment,
+    /// Recipient balance commitment after the transfer
+    pub recipient_new_commitment: Commitment,
+    /// Opening of the amount commitment (shared with the recipient)
+    pub amount_opening: Opening,
+    /// Sender's opening of their new balance
+    pub sender_new_opening: Opening,
+    /// Opening of the recipient's new balance
+    pub recipient_new_opening: Opening,
+}
+
+/**
+ * Prove a transfer of `amount` between two encrypted balances
+ * 
+ * `sender_old` / `recipient_old` must open the balances currently stored
+ * on-chain; the recipient's opening is needed for the range proof on their
+ * new balance.
+ */
+pub fn prove_transfer<R: RngCore + CryptoRng>(
+    pc_gens: &PedersenGens,
+    bp_gens: &BulletproofGens,
+    sender_old: &Opening,
+    recipient_old: &Opening,
+    amount: u64,
+    rng: &mut R,
+) -> Result<ProvenTransfer, ProverError> {
+    let sender_new_value = sender_old
+        .value
+        .checked_sub(amount)
+        .ok_or(ProverError::InsufficientBalance)?;
+    let recipient_new_value = recipient_old
+        .value
+        .checked_add(amount)
+        .ok_or(ProverError::BalanceOverflow)?;
+
+    let amount_opening = Opening::random(amount, rng);
+    let sender_new = Opening::random(sender_new_value, rng);
+    let recipient_new = Opening::random(recipient_new_value, rng);
+
+    let bits = MAX_RANGE_BITS as u8;
+    let amount_range = prove_range(pc_gens, bp_gens, &amount_opening, bits, rng)?;
+    let sender_range = prove_range(pc_gens, bp_gens, &sender_new, bits, rng)?;
+    let recipient_range = prove_range(pc_gens, bp_gens, &recipient_new, bits, rng)?;
+
+    let amount_commitment = amount_opening.to_slot(pc_gens);
+    let sender_old_commitment = sender_old.to_slot(pc_gens);
+    let sender_new_commitment = sender_new.to_slot(pc_gens);
+    let recipient_old_commitment = recipient_old.to_slot(pc_gens);
+    let recipient_new_commitment = recipient_new.to_slot(pc_gens);
+
+    let sender_nonce = EqualityNonce::new(pc_gens, rng);
+    let recipient_nonce = EqualityNonce::new(pc_gens, rng);
+    let challenge = challenge_scalar(&transfer_challenge_from_nonces(
+        &amount_commitment,
+        &sender_old_commitment,
+        &sender_new_commitment,
+        &recipient_old_commitment,
+        &recipient_new_commitment,
+        &sender_nonce.r,
+        &recipient_nonce.r,
+    ));
+
+    // Values cancel in both differences, leaving only the blinding
+    let sender_blinding = sender_old.blinding - amount_opening.blinding - sender_new.blinding;
+    let recipient_blinding =
+        recipient_new.blinding - recipient_old.blinding - amount_opening.blinding;
+    let sender_equality = sender_nonce.respond(&challenge, &sender_blinding);
+    let recipient_equality = recipient_nonce.respond(&challenge, &recipient_blinding);
+
+    let mut proof_data = Vec::with_capacity(TRANSFER_PROOF_SIZE);
+    proof_data.extend_from_slice(&amount_range);
+    proof_data.extend_from_slice(&sender_range);
+    proof_data.extend_from_slice(&recipient_range);
+    proof_data.extend_from_slice(&sender_equality);
+    proof_data.extend_from_slice(&recipient_equality);
+
+    Ok(ProvenTransfer {
+        proof_data,
+        amount_commitment,
+        sender_new_commitment,
+        recipient_new_commitment,
+        amount_opening,
+        sender_new_opening: sender_new,
+        recipient_new_opening: recipient_new,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use confidential_proofs::proof_verification::deserialize_proof_data;
+    use confidential_proofs::{verify_transfer_proof, verify_transfer_stage_a, verify_transfer_stage_b};
+    use rand_core::OsRng;
+
+    fn setup() -> (PedersenGens, Opening, Opening, ProvenTransfer) {
+        let pc_gens = PedersenGens::default();
+        let bp_gens = BulletproofGens::default();
+        let sender_old = Opening::random(10_000, &mut OsRng);
+        let recipient_old = Opening::random(250, &mut OsRng);
+        let proven =
+            prove_transfer(&pc_gens, &bp_gens, &sender_old, &recipient_old, 1_200, &mut OsRng)
+                .unwrap();
+        (pc_gens, sender_old, recipient_old, proven)
+    }
+
+    #[test]
+    fn proof_round_trips_through_the_verifier() {
+        let (pc_gens, sender_old, recipient_old, proven) = setup();
+        assert_eq!(proven.proof_data.len(), TRANSFER_PROOF_SIZE);
+
+        let proof = deserialize_proof_data(&proven.proof_data).unwrap();
+        assert_eq!(proof.amount_range_proof.commitment, &proven.amount_commitment);
+        assert_eq!(proof.sender_after_range_proof.commitment, &proven.sender_new_commitment);
+        assert_eq!(
+            proof.recipient_after_range_proof.commitment,
+            &proven.recipient_new_commitment
+        );
+
+        assert!(verify_transfer_proof(
+            &proven.proof_data,
+            &proven.amount_commitment,
+            &proven.sender_new_commitment,
+            &sender_old.to_slot(&pc_gens),
+            &recipient_old.to_slot(&pc_gens),
+            &proven.recipient_new_commitment,
+        )
+        .is_ok());
+        assert_eq!(proven.sender_new_opening.value, 8_800);
+        assert_eq!(proven.recipient_new_opening.value, 1_450);
+    }
+
+    #[test]
+    fn staged_verification_accepts_the_proof() {
+        let (pc_gens, sender_old, recipient_old, proven) = setup();
+        let sender_old = sender_old.to_slot(&pc_gens);
+        let recipient_old = recipient_old.to_slot(&pc_gens);
+
+        let stage_a = verify_transfer_stage_a(
+            &proven.proof_data,
+            &proven.amount_commitment,
+            &proven.sender_new_commitment,
+            &sender_old,
+            &recipient_old,
+            &proven.recipient_new_commitment,
+        )
+        .unwrap();
+        assert!(verify_transfer_stage_b(
+            &proven.proof_data,
+            &proven.amount_commitment,
+            &proven.sender_new_commitment,
+            &sender_old,
+            &recipient_old,
+            &proven.recipient_new_commitment,
+            &stage_a,
+        )
+        .is_ok());
+    }
+
+    #[test]
+    fn rejects_mismatched_commitments_and_malformed_proofs() {
+        let (pc_gens, sender_old, recipient_old, proven) = setup();
+        let sender_old = sender_old.to_slot(&pc_gens);
+        let recipient_old = recipient_old.to_slot(&pc_gens);
+        let verify = |proof_data: &[u8], recipient_new: &Commitment| {
+            verify_transfer_proof(
+                proof_data,
+                &proven.amount_commitment,
+                &proven.sender_new_commitment,
+                &sender_old,
+                &recipient_old,
+                recipient_new,
+            )
+        };
+
+        let other = Opening::random(1_450, &mut OsRng).to_slot(&pc_gens);
+        assert!(verify(&proven.proof_data, &other).is_err());
+
+        // Zeroed recipient equality response
+        let mut tampered = proven.proof_data.clone();
+        let len = tampered.len();
+        tampered[len - 32..].fill(0);
+        assert!(verify(&tampered, &proven.recipient_new_commitment).is_err());
+
+        assert!(verify(
+            &proven.proof_data[..TRANSFER_PROOF_SIZE - 1],
+            &proven.recipient_new_commitment
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn rejects_overdrafts() {
+        let pc_gens = PedersenGens::default();
+        let bp_gens = BulletproofGens::default();
+        let sender_old = Opening::random(100, &mut OsRng);
+        let recipient_old = Opening::random(0, &mut OsRng);
+        assert_eq!(
+            prove_transfer(&pc_gens, &bp_gens, &sender_old, &recipient_old, 101, &mut OsRng)
+                .unwrap_err(),
+            ProverError::InsufficientBalance
+        );
+    }
+}
@@ -0,0 +1,123 @@
+/*!
+ * Balance Views
+ *
+ * Openings encrypted to a viewing key, in the EncryptedMemo envelope of
+ * `src/crypto/viewingKey.ts`, so views sealed by either implementation open
+ * with the other:
+ * - ephemeral_key: X25519 public key of a one-time sender key
+ * - key = SHA-256("privacy-transfer/memo" || ECDH(ephemeral, viewing key))
+ * - ciphertext = AES-256-GCM(key, nonce, value (u64 LE) || blinding (32))
+ *   followed by the 16-byte tag
+ */
+
+use aes_gcm::aead::{Aead, KeyInit};
+use aes_gcm::{Aes256Gcm, Nonce};
+use curve25519_dalek::montgomery::MontgomeryPoint;
+use curve25519_dalek::scalar::Scalar;
+use rand_core::{CryptoRng, RngCore};
+use sha2::{Digest, Sha256};
+
+use crate::commitment::Opening;
+use crate::keys::viewing_public_key;
+use crate::ProverError;
+
+/// Domain of the envelope key derivation
+pub const MEMO_KDF_DOMAIN: &[u8] = b"privacy-transfer/memo";
+
+const PLAINTEXT_LENGTH: usize = 40;
+
+/// Encrypted opening as stored in an EncryptedMemo
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EncryptedView {
+    /// Sender's ephemeral X25519 public key
+    pub ephemeral_key: [u8; 32],
+    /// AEAD nonce
+    pub nonce: [u8; 12],
+    /// Ciphertext followed by the 16-byte tag
+    pub ciphertext: [u8; 56],
+}
+
+/**
+ * Encrypt an opening to a viewing key (publish_balance_view, transfer memos)
+ */
+pub fn seal_balance_view<R: RngCore + CryptoRng>(
+    viewing_key: &[u8; 32],
+    opening: &Opening,
+    rng: &mut R,
+) -> EncryptedView {
+    let mut ephemeral_secret = [0u8; 32];
+    rng.fill_bytes(&mut ephemeral_secret);
+    let shared = MontgomeryPoint(*viewing_key).mul_clamped(ephemeral_secret);
+
+    let mut plaintext = [0u8; PLAINTEXT_LENGTH];
+    plaintext[..8].copy_from_slice(&opening.value.to_le_bytes());
+    plaintext[8..].copy_from_slice(opening.blinding.as_bytes());
+
+    let mut nonce = [0u8; 12];
+    rng.fill_bytes(&mut nonce);
+    let ciphertext = envelope_cipher(&shared)
+        .encrypt(&Nonce::from(nonce), plaintext.as_slice())
+        .expect("AES-GCM encryption of 40 bytes cannot fail");
+
+    EncryptedView {
+        ephemeral_key: viewing_public_key(&ephemeral_secret),
+        nonce,
+        ciphertext: ciphertext.try_into().expect("40-byte plaintext plus 16-byte tag"),
+    }
+}
+
+/**
+ * Decrypt an opening with the viewing secret
+ *
+ * Fails if the view was not encrypted to this key or was tampered with.
+ */
+pub fn open_balance_view(
+    viewing_secret: &[u8; 32],
+    view: &EncryptedView,
+) -> Result<Opening, ProverError> {
+    let shared = MontgomeryPoint(view.ephemeral_key).mul_clamped(*viewing_secret);
+    let plaintext = envelope_cipher(&shared)
+        .decrypt(&Nonce::from(view.nonce), view.ciphertext.as_slice())
+        .map_err(|_| ProverError::ViewDecryptionFailed)?;
+
+    let mut value = [0u8; 8];
+    value.copy_from_slice(&plaintext[..8]);
+    let mut blinding = [0u8; 32];
+    blinding.copy_from_slice(&plaintext[8..]);
+    Ok(Opening {
+        value: u64::from_le_bytes(value),
+        blinding: Scalar::from_bytes_mod_order(blinding),
+    })
+}
+
+fn envelope_cipher(shared: &MontgomeryPoint) -> Aes256Gcm {
+    let mut hasher = Sha256::new();
+    hasher.update(MEMO_KDF_DOMAIN);
+    hasher.update(shared.as_bytes());
+    Aes256Gcm::new(&hasher.finalize())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::keys::{derive_viewing_secret, WalletKeys};
+    use rand_core::OsRng;
+
+    #[test]
+    fn views_open_only_with_their_key() {
+        let keys = WalletKeys::from_seed(&[9u8; 32]);
+        let opening = Opening::random(1234, &mut OsRng);
+        let view = seal_balance_view(&keys.viewing_public_key, &opening, &mut OsRng);
+        assert_eq!(open_balance_view(&keys.viewing_secret, &view).unwrap(), opening);
+
+        let other = derive_viewing_secret(&[1u8; 32]);
+        assert_eq!(
+            open_balance_view(&other, &view).unwrap_err(),
+            ProverError::ViewDecryptionFailed
+        );
+
+        let mut tampered = view.clone();
+        tampered.ciphertext[0] ^= 1;
+        assert!(open_balance_view(&keys.viewing_secret, &tampered).is_err());
+    }
+}
@@ -0,0 +1,387 @@
+/*!
+ * Privacy Transfer FFI
+ *
+ * UniFFI bindings of `privacy-transfer-client` for native iOS and Android
+ * wallets: key derivation, commitments, balance view decryption and proof
+ * generation, so mobile apps embed the Rust core instead of shipping a
+ * second implementation.
+ *
+ * BUILDING:
+ * - Library: `cargo build -p privacy-transfer-ffi --release` (cdylib for
+ *   Android, staticlib for iOS; cross-compile with the usual targets)
+ * - Bindings: `cargo run -p privacy-transfer-ffi --features bindgen --bin
+ *   uniffi-bindgen -- generate --library <built library> --language
+ *   swift|kotlin --out-dir <dir>`
+ *
+ * ENCODING:
+ * - Byte arrays cross the boundary as `Vec<u8>` and are length-checked
+ *   (commitments 64 bytes, keys and blindings 32 bytes)
+ * - Blindings are 32-byte little-endian scalars, reduced mod l like
+ *   `ScalarOps.fromBytes` in the TypeScript SDK
+ * - `proof_data` is the exact instruction argument
+ */
+
+use std::sync::OnceLock;
+
+use curve25519_dalek::scalar::Scalar;
+use privacy_transfer_client::{
+    BulletproofGens, Opening, PedersenGens, ProverError, WalletKeys as ClientWalletKeys,
+};
+use rand_core::OsRng;
+
+uniffi::setup_scaffolding!();
+
+/// Errors returned across the FFI boundary
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error, uniffi::Error)]
+pub enum ClientError {
+    #[error("value does not fit in the requested number of bits")]
+    ValueOutOfRange,
+    #[error("range size must be 1..=64 bits")]
+    InvalidRangeBits,
+    #[error("balance is smaller than the amount")]
+    InsufficientBalance,
+    #[error("balance would exceed u64::MAX")]
+    BalanceOverflow,
+    #[error("not enough bulletproof generators")]
+    NotEnoughGenerators,
+    #[error("bytes are not a valid commitment")]
+    InvalidPoint,
+    #[error("balance view cannot be decrypted with this viewing key")]
+    ViewDecryptionFailed,
+    #[error("{field} must be {expected} bytes")]
+    InvalidLength { field: String, expected: u32 },
+}
+
+impl From<ProverError> for ClientError {
+    fn from(error: ProverError) -> Self {
+        match error {
+            ProverError::ValueOutOfRange => ClientError::ValueOutOfRange,
+            ProverError::InvalidRangeBits => ClientError::InvalidRangeBits,
+            ProverError::InsufficientBalance => ClientError::InsufficientBalance,
+            ProverError::BalanceOverflow => ClientError::BalanceOverflow,
+            ProverError::NotEnoughGenerators => ClientError::NotEnoughGenerators,
+            ProverError::InvalidPoint => ClientError::InvalidPoint,
+            ProverError::ViewDecryptionFailed => ClientError::ViewDecryptionFailed,
+        }
+    }
+}
+
+/// Keys of a wallet seed
+#[derive(Debug, Clone, PartialEq, Eq, uniffi::Record)]
+pub struct WalletKeys {
+    /// Ed25519 public key of the signer (32 bytes)
+    pub signer_public_key: Vec<u8>,
+    /// X25519 viewing secret (32 bytes)
+    pub viewing_secret: Vec<u8>,
+    /// Viewing public key to register on-chain (32 bytes)
+    pub viewing_public_key: Vec<u8>,
+}
+
+/// Opening (value, blinding) of a commitment
+#[derive(Debug, Clone, PartialEq, Eq, uniffi::Record)]
+pub struct BalanceOpening {
+    /// Committed value
+    pub value: u64,
+    /// Blinding factor (32 bytes)
+    pub blinding: Vec<u8>,
+}
+
+/// Opening encrypted to a viewing key (EncryptedMemo)
+#[derive(Debug, Clone, PartialEq, Eq, uniffi::Record)]
+pub struct EncryptedView {
+    /// Ephemeral X25519 public key (32 bytes)
+    pub ephemeral_key: Vec<u8>,
+    /// AEAD nonce (12 bytes)
+    pub nonce: Vec<u8>,
+    /// Ciphertext and tag (56 bytes)
+    pub ciphertext: Vec<u8>,
+}
+
+/// A proven transfer
+#[derive(Debug, Clone, PartialEq, Eq, uniffi::Record)]
+pub struct TransferProof {
+    /// `proof_data` instruction argument
+    pub proof_data: Vec<u8>,
+    /// Amount commitment (64 bytes)
+    pub amount_commitment: Vec<u8>,
+    /// Sender balance commitment after the transfer (64 bytes)
+    pub sender_new_commitment: Vec<u8>,
+    /// Recipient balance commitment after the transfer (64 bytes)
+    pub recipient_new_commitment: Vec<u8>,
+    /// Opening of the amount (shared with the recipient)
+    pub amount_opening: BalanceOpening,
+    /// Sender's opening of their new balance
+    pub sender_new_opening: BalanceOpening,
+    /// Opening of the recipient's new balance
+    pub recipient_new_opening: BalanceOpening,
+}
+
+/// A proven deposit or withdrawal
+#[derive(Debug, Clone, PartialEq, Eq, uniffi::Record)]
+pub struct BalanceUpdate {
+    /// `proof_data` instruction argument
+    pub proof_data: Vec<u8>,
+    /// Balance commitment after the update (64 bytes)
+    pub new_commitment: Vec<u8>,
+    /// Opening of the new balance
+    pub new_opening: BalanceOpening,
+}
+
+/**
+ * Derive the signer and viewing keys of a 32-byte wallet seed
+ */
+#[uniffi::export]
+pub fn derive_wallet_keys(seed: Vec<u8>) -> Result<WalletKeys, ClientError> {
+    let keys = ClientWalletKeys::from_seed(&fixed(&seed, "seed")?);
+    Ok(WalletKeys {
+        signer_public_key: keys.signer_public_key.to_vec(),
+        viewing_secret: keys.viewing_secret.to_vec(),
+        viewing_public_key: keys.viewing_public_key.to_vec(),
+    })
+}
+
+/**
+ * Opening of `value` with a fresh random blinding
+ */
+#[uniffi::export]
+pub fn random_opening(value: u64) -> BalanceOpening {
+    Opening::random(value, &mut OsRng).into()
+}
+
+/**
+ * Opening of an empty balance (the all-zero commitment)
+ */
+#[uniffi::export]
+pub fn empty_opening() -> BalanceOpening {
+    Opening::empty().into()
+}
+
+/**
+ * 64-byte on-chain commitment of an opening
+ */
+#[uniffi::export]
+pub fn commit(opening: BalanceOpening) -> Result<Vec<u8>, ClientError> {
+    Ok(Opening::try_from(opening)?.to_slot(pedersen_gens()).to_vec())
+}
+
+/**
+ * Encrypt an opening to a viewing key (balance views, transfer memos)
+ */
+#[uniffi::export]
+pub fn seal_balance_view(
+    viewing_key: Vec<u8>,
+    opening: BalanceOpening,
+) -> Result<EncryptedView, ClientError> {
+    let view = privacy_transfer_client::seal_balance_view(
+        &fixed(&viewing_key, "viewing_key")?,
+        &opening.try_into()?,
+        &mut OsRng,
+    );
+    Ok(EncryptedView {
+        ephemeral_key: view.ephemeral_key.to_vec(),
+        nonce: view.nonce.to_vec(),
+        ciphertext: view.ciphertext.to_vec(),
+    })
+}
+
+/**
+ * Decrypt a balance view with the viewing secret
+ */
+#[uniffi::export]
+pub fn open_balance_view(
+    viewing_secret: Vec<u8>,
+    view: EncryptedView,
+) -> Result<BalanceOpening, ClientError> {
+    let view = privacy_transfer_client::EncryptedView {
+        ephemeral_key: fixed(&view.ephemeral_key, "ephemeral_key")?,
+        nonce: fixed(&view.nonce, "nonce")?,
+        ciphertext: fixed(&view.ciphertext, "ciphertext")?,
+    };
+    let opening =
+        privacy_transfer_client::open_balance_view(&fixed(&viewing_secret, "viewing_secret")?, &view)?;
+    Ok(opening.into())
+}
+
+/**
+ * Prove a confidential transfer of `amount`
+ */
+#[uniffi::export]
+pub fn prove_transfer(
+    sender_old: BalanceOpening,
+    recipient_old: BalanceOpening,
+    amount: u64,
+) -> Result<TransferProof, ClientError> {
+    let pc_gens = pedersen_gens();
+    let proven = privacy_transfer_client::prove_transfer(
+        pc_gens,
+        bulletproof_gens(),
+        &sender_old.try_into()?,
+        &recipient_old.try_into()?,
+        amount,
+        &mut OsRng,
+    )?;
+    Ok(TransferProof {
+        proof_data: proven.proof_data,
+        amount_commitment: proven.amount_commitment.to_vec(),
+        sender_new_commitment: proven.sender_new_commitment.to_vec(),
+        recipient_new_commitment: proven.recipient_new_commitment.to_vec(),
+        amount_opening: proven.amount_opening.into(),
+        sender_new_opening: proven.sender_new_opening.into(),
+        recipient_new_opening: proven.recipient_new_opening.into(),
+    })
+}
+
+/**
+ * Prove a deposit of the public `amount`
+ */
+#[uniffi::export]
+pub fn prove_deposit(old: BalanceOpening, amount: u64) -> Result<BalanceUpdate, ClientError> {
+    let proven = privacy_transfer_client::prove_deposit(
+        pedersen_gens(),
+        bulletproof_gens(),
+        &old.try_into()?,
+        amount,
+        &mut OsRng,
+    )?;
+    Ok(proven.into())
+}
+
+/**
+ * Prove a withdrawal of the public `amount`, optionally bound to a
+ * 32-byte payout destination
+ */
+#[uniffi::export]
+pub fn prove_withdraw(
+    old: BalanceOpening,
+    amount: u64,
+    destination: Option<Vec<u8>>,
+) -> Result<BalanceUpdate, ClientError> {
+    let destination = destination
+        .map(|destination| fixed::<32>(&destination, "destination"))
+        .transpose()?;
+    let proven = privacy_transfer_client::prove_withdraw(
+        pedersen_gens(),
+        bulletproof_gens(),
+        &old.try_into()?,
+        amount,
+        destination.as_ref(),
+        &mut OsRng,
+    )?;
+    Ok(proven.into())
+}
+
+/**
+ * Prove a commitment hides `opening.value` (selective disclosure)
+ */
+#[uniffi::export]
+pub fn prove_opening(opening: BalanceOpening) -> Result<Vec<u8>, ClientError> {
+    let opening = Opening::try_from(opening)?;
+    Ok(privacy_transfer_client::prove_opening(pedersen_gens(), &opening, &mut OsRng).to_vec())
+}
+
+impl From<Opening> for BalanceOpening {
+    fn from(opening: Opening) -> Self {
+        Self {
+            value: opening.value,
+            blinding: opening.blinding.to_bytes().to_vec(),
+        }
+    }
+}
+
+impl TryFrom<BalanceOpening> for Opening {
+    type Error = ClientError;
+
+    fn try_from(opening: BalanceOpening) -> Result<Self, ClientError> {
+        Ok(Opening {
+            value: opening.value,
+            blinding: Scalar::from_bytes_mod_order(fixed(&opening.blinding, "blinding")?),
+        })
+    }
+}
+
+impl From<privacy_transfer_client::ProvenUpdate> for BalanceUpdate {
+    fn from(proven: privacy_transfer_client::ProvenUpdate) -> Self {
+        Self {
+            proof_data: proven.proof_data,
+            new_commitment: proven.new_commitment.to_vec(),
+            new_opening: proven.new_opening.into(),
+        }
+    }
+}
+
+/// Generator tables are built once per process
+fn pedersen_gens() -> &'static PedersenGens {
+    static GENS: OnceLock<PedersenGens> = OnceLock::new();
+    GENS.get_or_init(PedersenGens::default)
+}
+
+fn bulletproof_gens() -> &'static BulletproofGens {
+    static GENS: OnceLock<BulletproofGens> = OnceLock::new();
+    GENS.get_or_init(BulletproofGens::default)
+}
+
+fn fixed<const N: usize>(bytes: &[u8], field: &str) -> Result<[u8; N], ClientError> {
+    bytes.try_into().map_err(|_| ClientError::InvalidLength {
+        field: field.to_string(),
+        expected: N as u32,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use confidential_proofs::{verify_transfer_proof, verify_withdraw_to_proof};
+
+    fn slot(bytes: &[u8]) -> [u8; 64] {
+        bytes.try_into().unwrap()
+    }
+
+    #[test]
+    fn keys_and_views_round_trip() {
+        let keys = derive_wallet_keys(vec![9u8; 32]).unwrap();
+        let opening = random_opening(1_234);
+        let view = seal_balance_view(keys.viewing_public_key.clone(), opening.clone()).unwrap();
+        assert_eq!(open_balance_view(keys.viewing_secret, view.clone()).unwrap(), opening);
+
+        let other = derive_wallet_keys(vec![1u8; 32]).unwrap();
+        assert_eq!(
+            open_balance_view(other.viewing_secret, view).unwrap_err(),
+            ClientError::ViewDecryptionFailed
+        );
+        assert_eq!(
+            derive_wallet_keys(vec![0u8; 31]).unwrap_err(),
+            ClientError::InvalidLength { field: "seed".into(), expected: 32 }
+        );
+    }
+
+    #[test]
+    fn proofs_verify_on_chain() {
+        let sender_old = random_opening(10_000);
+        let recipient_old = empty_opening();
+        let transfer = prove_transfer(sender_old.clone(), recipient_old.clone(), 2_500).unwrap();
+        assert!(verify_transfer_proof(
+            &transfer.proof_data,
+            &slot(&transfer.amount_commitment),
+            &slot(&transfer.sender_new_commitment),
+            &slot(&commit(sender_old).unwrap()),
+            &slot(&commit(recipient_old).unwrap()),
+            &slot(&transfer.recipient_new_commitment),
+        )
+        .is_ok());
+
+        let old = transfer.sender_new_opening;
+        let withdrawal = prove_withdraw(old.clone(), 500, Some(vec![3u8; 32])).unwrap();
+        assert!(verify_withdraw_to_proof(
+            &withdrawal.proof_data,
+            500,
+            &slot(&commit(old.clone()).unwrap()),
+            &slot(&withdrawal.new_commitment),
+            &[3u8; 32],
+        )
+        .is_ok());
+        assert_eq!(
+            prove_withdraw(old, 7_501, None).unwrap_err(),
+            ClientError::InsufficientBalance
+        );
+    }
+}
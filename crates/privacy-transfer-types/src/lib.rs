@@ -0,0 +1,16 @@
+/*!
+ * Privacy Transfer Types
+ *
+ * Proof layouts and constants shared by the on-chain program, the
+ * confidential-proofs verifier and off-chain tooling (the Rust client,
+ * indexers), so sizes and limits are defined once.
+ *
+ * `no_std` and dependency-free: usable from BPF, host and wasm builds.
+ */
+
+#![no_std]
+
+pub mod proof;
+pub mod proof_constants;
+
+pub use proof::{BulletproofRangeProof, Commitment, EqualityProof, TransferProof, ValidityProof};
@@ -0,0 +1,66 @@
+/*!
+ * Proof Layouts
+ *
+ * Borrowed views over serialized proofs: fields point into the input
+ * slice, nothing is copied. Parsing lives in
+ * `confidential_proofs::proof_verification::deserialize_proof_data`.
+ */
+
+use crate::proof_constants::{COMMITMENT_SIZE, SCALAR_SIZE};
+
+/// Serialized commitment (64 bytes, encoding set by the commitment scheme)
+pub type Commitment = [u8; COMMITMENT_SIZE];
+
+/// Bulletproof range proof structure
+#[derive(Debug, Clone, Copy)]
+pub struct BulletproofRangeProof<'a> {
+    /// Commitment V = g^v * h^gamma (64 bytes: 32 for X, 32 for Y)
+    pub commitment: &'a Commitment,
+    /// Commitment A (64 bytes)
+    pub a: &'a [u8; COMMITMENT_SIZE],
+    /// Commitment S (64 bytes)
+    pub s: &'a [u8; COMMITMENT_SIZE],
+    /// Commitment T1 (64 bytes)
+    pub t1: &'a [u8; COMMITMENT_SIZE],
+    /// Commitment T2 (64 bytes)
+    pub t2: &'a [u8; COMMITMENT_SIZE],
+    /// Scalar taux (32 bytes)
+    pub taux: &'a [u8; SCALAR_SIZE],
+    /// Scalar mu (32 bytes)
+    pub mu: &'a [u8; SCALAR_SIZE],
+    /// Scalar t (32 bytes)
+    pub t: &'a [u8; SCALAR_SIZE],
+    /// Range size (n bits)
+    pub n: u8,
+}
+
+/// Validity proof structure
+#[derive(Debug, Clone)]
+pub struct ValidityProof<'a> {
+    /// Equality proof for sender balance equation
+    pub sender_equality_proof: EqualityProof<'a>,
+    /// Equality proof for recipient balance equation
+    pub recipient_equality_proof: EqualityProof<'a>,
+}
+
+/// Equality proof structure (Schnorr-like)
+#[derive(Debug, Clone, Copy)]
+pub struct EqualityProof<'a> {
+    /// Commitment R (64 bytes)
+    pub r: &'a [u8; COMMITMENT_SIZE],
+    /// Scalar s (32 bytes)
+    pub s: &'a [u8; SCALAR_SIZE],
+}
+
+/// Transfer proof structure (complete proof for a transfer)
+#[derive(Debug, Clone)]
+pub struct TransferProof<'a> {
+    /// Range proof for amount
+    pub amount_range_proof: BulletproofRangeProof<'a>,
+    /// Range proof for sender's new balance
+    pub sender_after_range_proof: BulletproofRangeProof<'a>,
+    /// Range proof for recipient's new balance (no hidden wrap past 2^64)
+    pub recipient_after_range_proof: BulletproofRangeProof<'a>,
+    /// Validity proof for balance equations
+    pub validity_proof: ValidityProof<'a>,
+}
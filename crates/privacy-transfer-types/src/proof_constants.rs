@@ -0,0 +1,46 @@
+/*!
+ * Proof Constants
+ *
+ * Sizes and limits of serialized proofs. These match the TypeScript
+ * PROOF_CONSTANTS for consistency.
+ */
+
+/// Serialized commitment / point size in bytes
+pub const COMMITMENT_SIZE: usize = 64;
+
+/// Serialized scalar size in bytes
+pub const SCALAR_SIZE: usize = 32;
+
+/// Minimum proof data size in bytes (basic proof structure)
+pub const MIN_PROOF_DATA_SIZE: usize = 64;
+
+/// Maximum proof data size in bytes (DoS protection)
+/// Prevents malicious clients from submitting extremely large proofs
+pub const MAX_PROOF_DATA_SIZE: usize = 10000;
+
+/// Default range proof bits for lamport amounts
+pub const DEFAULT_RANGE_BITS: u8 = 64;
+
+/// Serialized range proof size in bytes
+/// (V, A, S, T1, T2: 64 each; taux, mu, t: 32 each; n: 1)
+pub const RANGE_PROOF_SIZE: usize = 5 * COMMITMENT_SIZE + 3 * SCALAR_SIZE + 1;
+
+/// Serialized equality proof size in bytes (R: 64 + s: 32)
+pub const EQUALITY_PROOF_SIZE: usize = COMMITMENT_SIZE + SCALAR_SIZE;
+
+/// Serialized transfer proof size in bytes (three range proofs + two
+/// equality proofs)
+pub const TRANSFER_PROOF_SIZE: usize = 3 * RANGE_PROOF_SIZE + 2 * EQUALITY_PROOF_SIZE;
+
+/// Serialized amount link proof size in bytes
+/// (A_token, A_amount: 64 each; s_value, s_token, s_amount: 32 each)
+pub const AMOUNT_LINK_PROOF_SIZE: usize = 2 * COMMITMENT_SIZE + 3 * SCALAR_SIZE;
+
+/// Serialized note spend proof size in bytes (A: 64 + B: 128 + C: 64)
+pub const NOTE_SPEND_PROOF_SIZE: usize = 256;
+
+/// Basis points per 100% (fee = floor(amount * bps / BPS_DENOMINATOR))
+pub const BPS_DENOMINATOR: u64 = 10_000;
+
+/// Range bits of a fee rounding remainder (2^14 > BPS_DENOMINATOR)
+pub const FEE_REMAINDER_BITS: u8 = 14;
@@ -0,0 +1,425 @@
+/*!
+ * Cross-Version Account Decoder
+ *
+ * Recognizes every historical EncryptedAccount / SolEscrow layout and
+ * normalizes it into a single `DecodedAccount` enum, so indexers keep working
+ * while accounts are being migrated (see migration.rs).
+ *
+ * KNOWN LAYOUTS:
+ * - Legacy: pre-`account_index` layout (PDA seeds: [prefix, owner])
+ * - Schema 1: account_index, seed_owner, pending_owner, schema_version
+ *   (EncryptedAccount additionally stores encryption_key)
+ * - EncryptedAccount schema 2: multi-asset `assets` vector
+ * - EncryptedAccount schema 3: `state_hash` after `assets`
+ * - EncryptedAccount schema 4: `frozen` after `state_hash`
+ * - EncryptedAccount schema 5: `last_activity_slot` after `frozen`
+ * - SolEscrow schema 2: `receipts_outstanding`
+ *
+ * Decoding is deterministic: it depends only on the raw account bytes and
+ * never on the program version that is currently deployed.
+ */
+
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::Discriminator;
+
+use crate::migration::{
+    ENCRYPTED_ACCOUNT_SCHEMA_OFFSET, ENCRYPTED_ACCOUNT_SCHEMA_VERSION, SOL_ESCROW_SCHEMA_OFFSET,
+    SOL_ESCROW_SCHEMA_VERSION,
+};
+use crate::{EncryptedAccount, SolEscrow};
+
+/// Serialized size of a legacy EncryptedAccount (discriminator included)
+pub const LEGACY_ENCRYPTED_ACCOUNT_LEN: usize = 8 + 32 + 64 + 8 + 1;
+
+/// Serialized size of a legacy SolEscrow (discriminator included)
+pub const LEGACY_SOL_ESCROW_LEN: usize = 8 + 32 + 8 + 1;
+
+/// Errors returned by the account decoder
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DecodeError {
+    /// Discriminator does not match any known account type
+    UnknownAccountType,
+    /// Data length does not match any known layout
+    UnknownLayout,
+    /// Schema version is newer than this decoder understands
+    UnsupportedSchemaVersion(u8),
+}
+
+/// Layout an account was decoded from
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LayoutVersion {
+    /// Pre-`account_index` layout without a schema version
+    Legacy,
+    /// Layout tagged with an explicit schema version
+    Schema(u8),
+}
+
+/// EncryptedAccount normalized to the latest field set
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NormalizedEncryptedAccount {
+    pub layout: LayoutVersion,
+    pub owner: Pubkey,
+    pub encrypted_balance: [u8; 64],
+    pub version: u64,
+    pub bump: u8,
+    /// 0 for legacy accounts
+    pub account_index: u16,
+    /// Equals `owner` for legacy accounts
+    pub seed_owner: Pubkey,
+    /// `Pubkey::default()` when no transfer is pending
+    pub pending_owner: Pubkey,
+    /// All zeros when no key is registered
+    pub encryption_key: [u8; 32],
+    /// (mint, commitment) entries; empty before schema 2
+    pub assets: Vec<(Pubkey, [u8; 64])>,
+    /// All zeros before schema 3 (chain genesis)
+    pub state_hash: [u8; 32],
+    /// False before schema 4
+    pub frozen: bool,
+    /// 0 before schema 5
+    pub last_activity_slot: u64,
+}
+
+/// SolEscrow normalized to the latest field set
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NormalizedSolEscrow {
+    pub layout: LayoutVersion,
+    pub owner: Pubkey,
+    pub balance: u64,
+    pub bump: u8,
+    /// 0 for legacy accounts
+    pub account_index: u16,
+    /// Equals `owner` for legacy accounts
+    pub seed_owner: Pubkey,
+    /// `Pubkey::default()` when no transfer is pending
+    pub pending_owner: Pubkey,
+    /// Receipt tokens backed by this escrow; 0 before schema 2
+    pub receipts_outstanding: u64,
+}
+
+/// Any program account, normalized across layout versions
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DecodedAccount {
+    EncryptedAccount(NormalizedEncryptedAccount),
+    SolEscrow(NormalizedSolEscrow),
+}
+
+
+/**
+ * Decode raw account data (discriminator included) into a normalized account
+ */
+pub fn decode_account(data: &[u8]) -> Result<DecodedAccount, DecodeError> {
+    if data.starts_with(EncryptedAccount::DISCRIMINATOR) {
+        decode_encrypted_account(data).map(DecodedAccount::EncryptedAccount)
+    } else if data.starts_with(SolEscrow::DISCRIMINATOR) {
+        decode_sol_escrow(data).map(DecodedAccount::SolEscrow)
+    } else {
+        Err(DecodeError::UnknownAccountType)
+    }
+}
+
+/**
+ * Decode an EncryptedAccount of any known layout
+ */
+pub fn decode_encrypted_account(data: &[u8]) -> Result<NormalizedEncryptedAccount, DecodeError> {
+    if !data.starts_with(EncryptedAccount::DISCRIMINATOR) {
+        return Err(DecodeError::UnknownAccountType);
+    }
+
+    let mut reader = Reader::new(data, 8);
+    let owner = reader.pubkey()?;
+    let encrypted_balance = reader.array::<64>()?;
+    let version = reader.u64()?;
+    let bump = reader.u8()?;
+
+    if data.len() == LEGACY_ENCRYPTED_ACCOUNT_LEN {
+        return Ok(NormalizedEncryptedAccount {
+            layout: LayoutVersion::Legacy,
+            owner,
+            encrypted_balance,
+            version,
+            bump,
+            account_index: 0,
+            seed_owner: owner,
+            pending_owner: Pubkey::default(),
+            encryption_key: [0u8; 32],
+            assets: Vec::new(),
+            state_hash: [0u8; 32],
+            frozen: false,
+            last_activity_slot: 0,
+        });
+    }
+
+    let schema_version = schema_version_at(
+        data,
+        ENCRYPTED_ACCOUNT_SCHEMA_OFFSET,
+        ENCRYPTED_ACCOUNT_SCHEMA_VERSION,
+    )?;
+    let account_index = reader.u16()?;
+    let seed_owner = reader.pubkey()?;
+    let pending_owner = reader.pubkey()?;
+    let encryption_key = reader.array::<32>()?;
+    reader.u8()?; // schema_version
+
+    let mut assets = Vec::new();
+    if schema_version >= 2 {
+        let count = reader.u32()?;
+        for _ in 0..count {
+            let mint = reader.pubkey()?;
+            let commitment = reader.array::<64>()?;
+            assets.push((mint, commitment));
+        }
+    }
+
+    let state_hash = if schema_version >= 3 {
+        reader.array::<32>()?
+    } else {
+        [0u8; 32]
+    };
+
+    let frozen = if schema_version >= 4 {
+        reader.u8()? != 0
+    } else {
+        false
+    };
+
+    let last_activity_slot = if schema_version >= 5 {
+        reader.u64()?
+    } else {
+        0
+    };
+
+    Ok(NormalizedEncryptedAccount {
+        layout: LayoutVersion::Schema(schema_version),
+        owner,
+        encrypted_balance,
+        version,
+        bump,
+        account_index,
+        seed_owner,
+        pending_owner,
+        encryption_key,
+        assets,
+        state_hash,
+        frozen,
+        last_activity_slot,
+    })
+}
+
+/**
+ * Decode a SolEscrow of any known layout
+ */
+pub fn decode_sol_escrow(data: &[u8]) -> Result<NormalizedSolEscrow, DecodeError> {
+    if !data.starts_with(SolEscrow::DISCRIMINATOR) {
+        return Err(DecodeError::UnknownAccountType);
+    }
+
+    let mut reader = Reader::new(data, 8);
+    let owner = reader.pubkey()?;
+    let balance = reader.u64()?;
+    let bump = reader.u8()?;
+
+    if data.len() == LEGACY_SOL_ESCROW_LEN {
+        return Ok(NormalizedSolEscrow {
+            layout: LayoutVersion::Legacy,
+            owner,
+            balance,
+            bump,
+            account_index: 0,
+            seed_owner: owner,
+            pending_owner: Pubkey::default(),
+            receipts_outstanding: 0,
+        });
+    }
+
+    let schema_version = schema_version_at(data, SOL_ESCROW_SCHEMA_OFFSET, SOL_ESCROW_SCHEMA_VERSION)?;
+    let account_index = reader.u16()?;
+    let seed_owner = reader.pubkey()?;
+    let pending_owner = reader.pubkey()?;
+    reader.u8()?; // schema_version
+
+    let receipts_outstanding = if schema_version >= 2 {
+        reader.u64()?
+    } else {
+        0
+    };
+
+    Ok(NormalizedSolEscrow {
+        layout: LayoutVersion::Schema(schema_version),
+        owner,
+        balance,
+        bump,
+        account_index,
+        seed_owner,
+        pending_owner,
+        receipts_outstanding,
+    })
+}
+
+/**
+ * Read and validate the schema version byte at a fixed offset
+ *
+ * `max_known` is the highest version this decoder has a branch for; every
+ * migration step must come with a matching decoder branch.
+ */
+fn schema_version_at(data: &[u8], offset: usize, max_known: u8) -> Result<u8, DecodeError> {
+    let schema_version = *data.get(offset).ok_or(DecodeError::UnknownLayout)?;
+    if schema_version == 0 {
+        return Err(DecodeError::UnknownLayout);
+    }
+    if schema_version > max_known {
+        return Err(DecodeError::UnsupportedSchemaVersion(schema_version));
+    }
+    Ok(schema_version)
+}
+
+/// Bounds-checked little-endian reader over raw account bytes
+struct Reader<'a> {
+    data: &'a [u8],
+    offset: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn new(data: &'a [u8], offset: usize) -> Self {
+        Self { data, offset }
+    }
+
+    fn array<const N: usize>(&mut self) -> Result<[u8; N], DecodeError> {
+        let bytes = self
+            .data
+            .get(self.offset..self.offset + N)
+            .ok_or(DecodeError::UnknownLayout)?;
+        let mut arr = [0u8; N];
+        arr.copy_from_slice(bytes);
+        self.offset += N;
+        Ok(arr)
+    }
+
+    fn pubkey(&mut self) -> Result<Pubkey, DecodeError> {
+        self.array::<32>().map(Pubkey::new_from_array)
+    }
+
+    fn u64(&mut self) -> Result<u64, DecodeError> {
+        self.array::<8>().map(u64::from_le_bytes)
+    }
+
+    fn u32(&mut self) -> Result<u32, DecodeError> {
+        self.array::<4>().map(u32::from_le_bytes)
+    }
+
+    fn u16(&mut self) -> Result<u16, DecodeError> {
+        self.array::<2>().map(u16::from_le_bytes)
+    }
+
+    fn u8(&mut self) -> Result<u8, DecodeError> {
+        self.array::<1>().map(|b| b[0])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anchor_lang::AccountSerialize;
+
+    fn legacy_encrypted_account(owner: &Pubkey) -> Vec<u8> {
+        let mut data = EncryptedAccount::DISCRIMINATOR.to_vec();
+        data.extend_from_slice(owner.as_ref());
+        data.extend_from_slice(&[5u8; 64]);
+        data.extend_from_slice(&11u64.to_le_bytes());
+        data.push(255);
+        data
+    }
+
+    #[test]
+    fn decodes_legacy_accounts() {
+        let owner = Pubkey::new_unique();
+        let decoded = decode_encrypted_account(&legacy_encrypted_account(&owner)).unwrap();
+        assert_eq!(decoded.layout, LayoutVersion::Legacy);
+        assert_eq!(decoded.owner, owner);
+        assert_eq!(decoded.seed_owner, owner);
+        assert_eq!(decoded.encrypted_balance, [5u8; 64]);
+        assert_eq!(decoded.version, 11);
+        assert_eq!(decoded.account_index, 0);
+
+        let mut escrow = SolEscrow::DISCRIMINATOR.to_vec();
+        escrow.extend_from_slice(owner.as_ref());
+        escrow.extend_from_slice(&900u64.to_le_bytes());
+        escrow.push(254);
+        match decode_account(&escrow).unwrap() {
+            DecodedAccount::SolEscrow(escrow) => {
+                assert_eq!(escrow.layout, LayoutVersion::Legacy);
+                assert_eq!(escrow.balance, 900);
+            }
+            other => panic!("unexpected {:?}", other),
+        }
+    }
+
+    #[test]
+    fn decodes_the_current_layout() {
+        let account = EncryptedAccount {
+            owner: Pubkey::new_unique(),
+            encrypted_balance: [1u8; 64],
+            version: 3,
+            bump: 250,
+            account_index: 7,
+            seed_owner: Pubkey::new_unique(),
+            pending_owner: Pubkey::new_unique(),
+            encryption_key: [2u8; 32],
+            schema_version: ENCRYPTED_ACCOUNT_SCHEMA_VERSION,
+            assets: vec![crate::AssetBalance {
+                mint: Pubkey::new_unique(),
+                encrypted_balance: [3u8; 64],
+            }],
+            state_hash: [4u8; 32],
+            frozen: true,
+            last_activity_slot: 1_234,
+        };
+        let mut data = Vec::new();
+        account.try_serialize(&mut data).unwrap();
+
+        let decoded = decode_encrypted_account(&data).unwrap();
+        assert_eq!(decoded.layout, LayoutVersion::Schema(ENCRYPTED_ACCOUNT_SCHEMA_VERSION));
+        assert_eq!(decoded.account_index, 7);
+        assert_eq!(decoded.seed_owner, account.seed_owner);
+        assert_eq!(decoded.pending_owner, account.pending_owner);
+        assert_eq!(decoded.assets, vec![(account.assets[0].mint, [3u8; 64])]);
+        assert_eq!(decoded.state_hash, [4u8; 32]);
+        assert!(decoded.frozen);
+        assert_eq!(decoded.last_activity_slot, 1_234);
+    }
+
+    #[test]
+    fn older_schemas_default_the_missing_fields() {
+        let mut data = legacy_encrypted_account(&Pubkey::new_unique());
+        data.extend_from_slice(&[0u8; 2 + 32 + 32 + 32]);
+        data.push(1);
+
+        let decoded = decode_encrypted_account(&data).unwrap();
+        assert_eq!(decoded.layout, LayoutVersion::Schema(1));
+        assert!(decoded.assets.is_empty());
+        assert_eq!(decoded.state_hash, [0u8; 32]);
+        assert!(!decoded.frozen);
+    }
+
+    #[test]
+    fn rejects_unknown_data() {
+        let mut data = legacy_encrypted_account(&Pubkey::new_unique());
+        data.extend_from_slice(&[0u8; 2 + 32 + 32 + 32]);
+        data.push(ENCRYPTED_ACCOUNT_SCHEMA_VERSION + 1);
+        assert_eq!(
+            decode_encrypted_account(&data),
+            Err(DecodeError::UnsupportedSchemaVersion(ENCRYPTED_ACCOUNT_SCHEMA_VERSION + 1))
+        );
+
+        // Schema 5 promises fields the data does not hold
+        let last = data.len() - 1;
+        data[last] = ENCRYPTED_ACCOUNT_SCHEMA_VERSION;
+        assert_eq!(decode_encrypted_account(&data), Err(DecodeError::UnknownLayout));
+
+        assert_eq!(decode_account(&[0u8; 64]), Err(DecodeError::UnknownAccountType));
+        assert_eq!(
+            decode_sol_escrow(SolEscrow::DISCRIMINATOR),
+            Err(DecodeError::UnknownLayout)
+        );
+    }
+}
@@ -0,0 +1,121 @@
+/*!
+ * One-Time Authorization Codes (Hash Chain)
+ *
+ * Optional second factor for sensitive instructions (key rotation, recovery
+ * initiation). The owner picks a secret seed `s` off-chain and registers the
+ * chain tip `H^n(s)`. Each sensitive instruction must reveal the previous
+ * link: a code `c` is accepted iff `H(c) == tip`, after which `c` becomes the
+ * new tip. Codes are therefore single-use and must be consumed in order,
+ * S/KEY style, and verification needs a single hash on-chain.
+ *
+ * A stolen signing key alone is no longer enough to rotate keys: the attacker
+ * also needs the next code from the owner's (offline) chain.
+ *
+ * Hash: Keccak256(code)
+ */
+
+use anchor_lang::prelude::*;
+use sha3::{Digest, Keccak256};
+
+use crate::{AuthChain, ErrorCode};
+
+/**
+ * Hash one chain link
+ */
+pub fn hash_code(code: &[u8; 32]) -> [u8; 32] {
+    let mut hasher = Keccak256::new();
+    hasher.update(code);
+    hasher.finalize().into()
+}
+
+/**
+ * Consume the next code of a chain
+ *
+ * Fails if no code was supplied, the chain is used up, or the code does not
+ * hash to the current tip.
+ */
+pub fn consume_code(chain: &mut AuthChain, code: Option<[u8; 32]>) -> Result<()> {
+    let code = code.ok_or(ErrorCode::AuthCodeRequired)?;
+    require!(chain.remaining > 0, ErrorCode::AuthChainExhausted);
+    require!(hash_code(&code) == chain.tip, ErrorCode::InvalidAuthCode);
+
+    chain.tip = code;
+    chain.remaining -= 1;
+    Ok(())
+}
+
+/**
+ * Enforce the auth chain of an account, if one is configured
+ *
+ * `auth_chain` must already be seed-checked against the protected account.
+ * An empty (never initialized) PDA means the owner has not opted in, so the
+ * instruction proceeds without a code.
+ */
+pub fn enforce_if_configured(auth_chain: &AccountInfo, code: Option<[u8; 32]>) -> Result<()> {
+    if auth_chain.data_is_empty() || auth_chain.owner != &crate::ID {
+        return Ok(());
+    }
+
+    let mut chain = {
+        let data = auth_chain.try_borrow_data()?;
+        AuthChain::try_deserialize(&mut &data[..])?
+    };
+    consume_code(&mut chain, code)?;
+
+    let mut data = auth_chain.try_borrow_mut_data()?;
+    chain.try_serialize(&mut &mut data[..])?;
+
+    msg!("Auth code accepted ({} remaining)", chain.remaining);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Chain registered from `seed` with `length` codes; returns the codes in
+    /// the order they must be revealed
+    fn chain(seed: [u8; 32], length: u32) -> (AuthChain, Vec<[u8; 32]>) {
+        let mut links = vec![seed];
+        for _ in 0..length {
+            links.push(hash_code(links.last().unwrap()));
+        }
+        let tip = links.pop().unwrap();
+        links.reverse();
+        let chain = AuthChain {
+            encrypted_account: Pubkey::new_unique(),
+            tip,
+            remaining: length,
+            bump: 255,
+        };
+        (chain, links)
+    }
+
+    #[test]
+    fn codes_are_consumed_in_order() {
+        let (mut chain, codes) = chain([1u8; 32], 3);
+        for (used, code) in codes.iter().enumerate() {
+            consume_code(&mut chain, Some(*code)).unwrap();
+            assert_eq!(chain.tip, *code);
+            assert_eq!(chain.remaining, 2 - used as u32);
+        }
+    }
+
+    #[test]
+    fn rejects_missing_reused_and_skipped_codes() {
+        let (mut chain, codes) = chain([2u8; 32], 3);
+        assert!(consume_code(&mut chain, None).is_err());
+        assert!(consume_code(&mut chain, Some(codes[1])).is_err());
+
+        consume_code(&mut chain, Some(codes[0])).unwrap();
+        assert!(consume_code(&mut chain, Some(codes[0])).is_err());
+        assert_eq!(chain.remaining, 2);
+    }
+
+    #[test]
+    fn exhausted_chains_accept_nothing() {
+        let (mut chain, codes) = chain([3u8; 32], 1);
+        consume_code(&mut chain, Some(codes[0])).unwrap();
+        assert!(consume_code(&mut chain, Some([3u8; 32])).is_err());
+    }
+}
@@ -0,0 +1,61 @@
+/*!
+ * Compressed Commitments
+ *
+ * Instruction arguments may carry a commitment in 32-byte compressed form
+ * (`PackedCommitment::Compressed`) instead of the 64-byte slot, roughly
+ * halving their share of the instruction data and leaving more room for
+ * proof bytes. The program expands it to the 64-byte slot and validates
+ * it before use; account state and proofs always hold the expanded form,
+ * so both encodings of one commitment are interchangeable.
+ *
+ * Expansion per commitment scheme:
+ * - Pedersen over Ristretto255: canonical 32-byte point encoding, checked
+ *   through the curve25519 syscall, zero-padded into the slot
+ * - Pedersen over BN254 G1: compressed point, decompressed through the
+ *   alt_bn128 syscall to x || y
+ * - Poseidon: the field element itself, zero-padded into the slot
+ */
+
+use anchor_lang::prelude::*;
+use confidential_proofs::is_valid_commitment;
+
+use crate::ErrorCode;
+
+/**
+ * Expand a compressed commitment into the 64-byte slot
+ *
+ * Fails with InvalidCommitment unless the result is a well-formed
+ * commitment for the active scheme.
+ */
+pub fn expand(compressed: &[u8; 32]) -> Result<[u8; 64]> {
+    let expanded = expand_for_scheme(compressed).ok_or(ErrorCode::InvalidCommitment)?;
+    require!(is_valid_commitment(&expanded), ErrorCode::InvalidCommitment);
+    Ok(expanded)
+}
+
+/// Place a 32-byte encoding in the first half of the slot
+#[cfg(not(feature = "commitment-pedersen-bn254"))]
+fn zero_padded(compressed: &[u8; 32]) -> [u8; 64] {
+    let mut expanded = [0u8; 64];
+    expanded[..32].copy_from_slice(compressed);
+    expanded
+}
+
+#[cfg(not(any(feature = "commitment-pedersen-bn254", feature = "commitment-poseidon")))]
+fn expand_for_scheme(compressed: &[u8; 32]) -> Option<[u8; 64]> {
+    use solana_curve25519::ristretto::{validate_ristretto, PodRistrettoPoint};
+
+    validate_ristretto(&PodRistrettoPoint(*compressed)).then(|| zero_padded(compressed))
+}
+
+#[cfg(feature = "commitment-pedersen-bn254")]
+fn expand_for_scheme(compressed: &[u8; 32]) -> Option<[u8; 64]> {
+    use solana_bn254::compression::prelude::alt_bn128_g1_decompress;
+
+    alt_bn128_g1_decompress(compressed).ok()
+}
+
+#[cfg(feature = "commitment-poseidon")]
+fn expand_for_scheme(compressed: &[u8; 32]) -> Option<[u8; 64]> {
+    Some(zero_padded(compressed))
+}
@@ -0,0 +1,272 @@
+/*!
+ * Token-2022 Confidential Transfer Extension Bridge
+ *
+ * CPI helpers that move hidden token amounts between a user's Token-2022
+ * confidential-transfer balance and the token escrow's confidential vault,
+ * so the program can bridge value into / out of its own commitments
+ * without the amount ever appearing in plaintext.
+ *
+ * FLOW:
+ * - deposit_from_confidential: user's available balance -> confidential
+ *   vault (extension `Transfer`); escrow commitment += linked amount
+ * - withdraw_to_confidential: confidential vault -> user's pending balance
+ *   (extension `Transfer`, escrow PDA signs); escrow commitment -= linked
+ *   amount
+ *
+ * The extension `Transfer` takes a ciphertext-commitment equality proof, a
+ * batched grouped ciphertext validity proof and a batched range proof.
+ * These must be pre-verified by the ZK ElGamal proof program into context
+ * state accounts, which are passed through here. The transfer amount's
+ * Pedersen commitments are read back from the validity proof context and
+ * linked to the program's amount commitment with an amount link proof.
+ *
+ * The confidential vault is a Token-2022 account at a PDA, owned by the
+ * token escrow and configured with the owner's ElGamal key (the owner
+ * decrypts it to build vault-side proofs).
+ */
+
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::program::{invoke, invoke_signed};
+use spl_token_2022::extension::confidential_transfer::instruction as ct_instruction;
+use spl_token_2022::extension::confidential_transfer::DecryptableBalance;
+use spl_token_2022::extension::ExtensionType;
+use spl_token_2022::solana_zk_sdk::encryption::pod::elgamal::PodElGamalCiphertext;
+use spl_token_2022::solana_zk_sdk::zk_elgamal_proof_program::proof_data::{
+    BatchedGroupedCiphertext3HandlesValidityProofContext, ProofType,
+};
+use spl_token_2022::solana_zk_sdk::zk_elgamal_proof_program::state::ProofContextState;
+use spl_token_confidential_transfer_proof_extraction::instruction::{
+    check_zk_elgamal_proof_program_account, ProofLocation,
+};
+
+use crate::{pedersen, ErrorCode};
+
+/// Decrypt handle of the auditor in a transfer's grouped ciphertexts
+const AUDITOR_HANDLE_INDEX: usize = 2;
+
+/// Bits in the low part of a transfer amount (amount = lo + 2^16 * hi)
+const TRANSFER_AMOUNT_LO_BITS: u32 = 16;
+
+/// Hidden amount of a confidential transfer, read from its validity proof
+pub struct TransferAmount {
+    /// C_lo + 2^16 * C_hi in the program's 64-byte slot encoding
+    pub commitment: [u8; 64],
+    /// Auditor ciphertext of the low part
+    pub auditor_lo: PodElGamalCiphertext,
+    /// Auditor ciphertext of the high part
+    pub auditor_hi: PodElGamalCiphertext,
+}
+
+/**
+ * Read the hidden amount from a ciphertext validity proof context account
+ *
+ * The account must belong to the ZK ElGamal proof program and hold a
+ * batched 3-handle validity proof; Token-2022 checks the rest (keys,
+ * consistency with the equality and range proofs) during the transfer.
+ */
+pub fn read_transfer_amount(validity_proof_context: &AccountInfo) -> Result<TransferAmount> {
+    check_zk_elgamal_proof_program_account(validity_proof_context.owner)
+        .map_err(|_| ErrorCode::InvalidProof)?;
+
+    let data = validity_proof_context.try_borrow_data()?;
+    let state = bytemuck::try_from_bytes::<
+        ProofContextState<BatchedGroupedCiphertext3HandlesValidityProofContext>,
+    >(&data)
+    .map_err(|_| ErrorCode::InvalidProof)?;
+    require!(
+        ProofType::try_from(state.proof_type)
+            .is_ok_and(|proof_type| proof_type == ProofType::BatchedGroupedCiphertext3HandlesValidity),
+        ErrorCode::InvalidProof
+    );
+
+    let context = &state.proof_context;
+    let lo = pedersen::to_slot(&commitment_bytes(&context.grouped_ciphertext_lo.extract_commitment()));
+    let hi = pedersen::to_slot(&commitment_bytes(&context.grouped_ciphertext_hi.extract_commitment()));
+    let scaled_hi = pedersen::mul(&pedersen::scalar_from_u64(1 << TRANSFER_AMOUNT_LO_BITS), &hi)
+        .map_err(|_| ErrorCode::InvalidCommitment)?;
+    let commitment = pedersen::add(&lo, &scaled_hi).map_err(|_| ErrorCode::InvalidCommitment)?;
+
+    Ok(TransferAmount {
+        commitment,
+        auditor_lo: context
+            .grouped_ciphertext_lo
+            .try_extract_ciphertext(AUDITOR_HANDLE_INDEX)
+            .map_err(|_| ErrorCode::InvalidProof)?,
+        auditor_hi: context
+            .grouped_ciphertext_hi
+            .try_extract_ciphertext(AUDITOR_HANDLE_INDEX)
+            .map_err(|_| ErrorCode::InvalidProof)?,
+    })
+}
+
+/// Raw bytes of a Pedersen commitment from the ZK SDK
+fn commitment_bytes<T: bytemuck::Pod>(commitment: &T) -> [u8; 32] {
+    let mut bytes = [0u8; 32];
+    bytes.copy_from_slice(bytemuck::bytes_of(commitment));
+    bytes
+}
+
+/**
+ * Size of a confidential vault token account
+ */
+pub fn vault_space() -> Result<usize> {
+    ExtensionType::try_calculate_account_len::<spl_token_2022::state::Account>(&[
+        ExtensionType::ConfidentialTransferAccount,
+    ])
+    .map_err(Into::into)
+}
+
+/**
+ * Initialize a freshly allocated vault as a token account of `mint`
+ */
+pub fn initialize_vault<'info>(
+    token_program: &AccountInfo<'info>,
+    vault: &AccountInfo<'info>,
+    mint: &AccountInfo<'info>,
+    authority: &AccountInfo<'info>,
+) -> Result<()> {
+    let ix = spl_token_2022::instruction::initialize_account3(
+        token_program.key,
+        vault.key,
+        mint.key,
+        authority.key,
+    )?;
+
+    invoke(&ix, &[vault.clone(), mint.clone()]).map_err(Into::into)
+}
+
+/**
+ * Configure the vault for confidential transfers (vault authority signs)
+ *
+ * `pubkey_proof_context` holds the pre-verified validity proof of the
+ * vault's ElGamal public key.
+ */
+#[allow(clippy::too_many_arguments)]
+pub fn configure_vault<'info>(
+    token_program: &AccountInfo<'info>,
+    vault: &AccountInfo<'info>,
+    mint: &AccountInfo<'info>,
+    pubkey_proof_context: &AccountInfo<'info>,
+    authority: &AccountInfo<'info>,
+    decryptable_zero_balance: [u8; 36],
+    maximum_pending_balance_credit_counter: u64,
+    signer_seeds: &[&[&[u8]]],
+) -> Result<()> {
+    let decryptable_balance: DecryptableBalance = bytemuck::cast(decryptable_zero_balance);
+
+    let ix = ct_instruction::inner_configure_account(
+        token_program.key,
+        vault.key,
+        mint.key,
+        &decryptable_balance,
+        maximum_pending_balance_credit_counter,
+        authority.key,
+        &[],
+        ProofLocation::ContextStateAccount(pubkey_proof_context.key),
+    )?;
+
+    invoke_signed(
+        &ix,
+        &[
+            vault.clone(),
+            mint.clone(),
+            pubkey_proof_context.clone(),
+            authority.clone(),
+        ],
+        signer_seeds,
+    )
+    .map_err(Into::into)
+}
+
+/**
+ * Move a hidden amount between two confidential balances
+ *
+ * `new_source_decryptable_available_balance` is the source owner's AES
+ * ciphertext of the remaining available balance (36 bytes), as required by
+ * Token-2022. Pass `signer_seeds` when the source authority is a PDA.
+ */
+#[allow(clippy::too_many_arguments)]
+pub fn transfer<'info>(
+    token_program: &AccountInfo<'info>,
+    source: &AccountInfo<'info>,
+    mint: &AccountInfo<'info>,
+    destination: &AccountInfo<'info>,
+    equality_proof_context: &AccountInfo<'info>,
+    validity_proof_context: &AccountInfo<'info>,
+    range_proof_context: &AccountInfo<'info>,
+    authority: &AccountInfo<'info>,
+    amount: &TransferAmount,
+    new_source_decryptable_available_balance: [u8; 36],
+    signer_seeds: &[&[&[u8]]],
+) -> Result<()> {
+    let decryptable_balance: DecryptableBalance =
+        bytemuck::cast(new_source_decryptable_available_balance);
+
+    let ix = ct_instruction::inner_transfer(
+        token_program.key,
+        source.key,
+        mint.key,
+        destination.key,
+        &decryptable_balance,
+        &amount.auditor_lo,
+        &amount.auditor_hi,
+        authority.key,
+        &[],
+        ProofLocation::ContextStateAccount(equality_proof_context.key),
+        ProofLocation::ContextStateAccount(validity_proof_context.key),
+        ProofLocation::ContextStateAccount(range_proof_context.key),
+    )?;
+
+    invoke_signed(
+        &ix,
+        &[
+            source.clone(),
+            mint.clone(),
+            destination.clone(),
+            equality_proof_context.clone(),
+            validity_proof_context.clone(),
+            range_proof_context.clone(),
+            authority.clone(),
+        ],
+        signer_seeds,
+    )
+    .map_err(Into::into)
+}
+
+/**
+ * Fold the vault's pending balance into its available balance (vault
+ * authority signs)
+ */
+pub fn apply_pending_balance<'info>(
+    token_program: &AccountInfo<'info>,
+    vault: &AccountInfo<'info>,
+    authority: &AccountInfo<'info>,
+    expected_pending_balance_credit_counter: u64,
+    new_decryptable_available_balance: [u8; 36],
+    signer_seeds: &[&[&[u8]]],
+) -> Result<()> {
+    let decryptable_balance: DecryptableBalance =
+        bytemuck::cast(new_decryptable_available_balance);
+
+    let ix = ct_instruction::inner_apply_pending_balance(
+        token_program.key,
+        vault.key,
+        expected_pending_balance_credit_counter,
+        &decryptable_balance,
+        authority.key,
+        &[],
+    )?;
+
+    invoke_signed(&ix, &[vault.clone(), authority.clone()], signer_seeds).map_err(Into::into)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use spl_token_2022::solana_zk_sdk::encryption::pedersen::H;
+
+    #[test]
+    fn elgamal_generator_matches_the_zk_sdk() {
+        assert_eq!(H.compress().to_bytes(), pedersen::ELGAMAL_BLINDING_GENERATOR);
+    }
+}
@@ -0,0 +1,112 @@
+/*!
+ * Compute-Unit Stage Profiling (`cu-profile` feature)
+ *
+ * Debug builds only: the built-in transfer verifier runs its stages one by
+ * one and reports the compute units each consumed through return data, so
+ * integrators can tune proof sizes and batching against real costs (e.g.
+ * from `simulateTransaction`). Acceptance is identical to
+ * `verify_transfer_proof`.
+ *
+ * RETURN DATA (32 bytes, u64 little-endian each):
+ * [deserialize][range_proofs][validity][transcript]
+ *
+ * Stages that did not run (verification failed earlier) report 0. Each
+ * figure includes one `sol_remaining_compute_units` syscall.
+ */
+
+use anchor_lang::solana_program::program::set_return_data;
+use solana_program::compute_units::sol_remaining_compute_units;
+
+use confidential_proofs::crypto_primitives::constant_time_eq;
+
+use crate::proof_verification::{
+    deserialize_proof_data, transfer_challenge, verify_range_proof, verify_validity_proof,
+    ProofVerificationError,
+};
+use crate::verifier::TransferStatement;
+
+/// Profiled verification stages, in return-data order
+#[derive(Clone, Copy)]
+enum Stage {
+    Deserialize,
+    RangeProofs,
+    Validity,
+    Transcript,
+}
+
+/// Per-stage compute units, published on drop
+struct Profile {
+    last_remaining: u64,
+    units: [u64; 4],
+}
+
+impl Profile {
+    fn start() -> Self {
+        Self {
+            last_remaining: sol_remaining_compute_units(),
+            units: [0; 4],
+        }
+    }
+
+    /// Charge the units used since the previous lap to `stage`
+    fn lap(&mut self, stage: Stage) {
+        let remaining = sol_remaining_compute_units();
+        self.units[stage as usize] = self.last_remaining.saturating_sub(remaining);
+        self.last_remaining = remaining;
+    }
+}
+
+impl Drop for Profile {
+    fn drop(&mut self) {
+        let mut data = [0u8; 32];
+        for (bytes, units) in data.chunks_exact_mut(8).zip(self.units) {
+            bytes.copy_from_slice(&units.to_le_bytes());
+        }
+        set_return_data(&data);
+    }
+}
+
+/**
+ * verify_transfer_proof, split into profiled stages
+ */
+pub fn verify_transfer_proof(statement: &TransferStatement) -> Result<(), ProofVerificationError> {
+    let mut profile = Profile::start();
+
+    let proof = deserialize_proof_data(statement.proof_data)?;
+    profile.lap(Stage::Deserialize);
+
+    verify_range_proof(&proof.amount_range_proof, statement.amount_commitment)?;
+    verify_range_proof(&proof.sender_after_range_proof, statement.sender_new)?;
+    verify_range_proof(&proof.recipient_after_range_proof, statement.recipient_new)?;
+    profile.lap(Stage::RangeProofs);
+
+    if !constant_time_eq(proof.amount_range_proof.commitment, statement.amount_commitment)
+        || !constant_time_eq(proof.sender_after_range_proof.commitment, statement.sender_new)
+        || !constant_time_eq(proof.recipient_after_range_proof.commitment, statement.recipient_new)
+    {
+        return Err(ProofVerificationError::CommitmentMismatch);
+    }
+
+    let c = transfer_challenge(
+        statement.amount_commitment,
+        statement.sender_old,
+        statement.sender_new,
+        statement.recipient_old,
+        statement.recipient_new,
+        &proof.validity_proof,
+    );
+    profile.lap(Stage::Transcript);
+
+    verify_validity_proof(
+        &proof.validity_proof,
+        statement.sender_old,
+        statement.amount_commitment,
+        statement.sender_new,
+        statement.recipient_old,
+        statement.recipient_new,
+        &c,
+    )?;
+    profile.lap(Stage::Validity);
+
+    Ok(())
+}
@@ -0,0 +1,51 @@
+/*!
+ * Deny-List Exclusion Proofs
+ *
+ * Operators publish the Merkle root of a sorted deny list (sanctioned
+ * addresses) in the config. While a root is set, SOL deposits and
+ * withdrawals must carry a zero-knowledge non-membership proof for the
+ * depositing / receiving wallet. The operator only maintains the list;
+ * compliant users are never registered or looked up individually.
+ *
+ * Exclusion proofs are Groth16 proofs of the exclusion circuit
+ * (`groth16::EXCLUSION_CIRCUIT`, public inputs: deny_list_root, subject),
+ * pairing-checked against its stored verifying key.
+ */
+
+use anchor_lang::prelude::*;
+
+use crate::groth16;
+use crate::proof_verification::verify_exclusion_proof;
+use crate::{Config, ErrorCode, NoteVerifyingKey};
+
+/**
+ * Require an exclusion proof for `subject` if a deny list is configured
+ *
+ * `verifying_key` must already be seed-checked as the exclusion circuit's key.
+ */
+pub fn enforce_if_configured(
+    config: Option<&Config>,
+    subject: &Pubkey,
+    proof_data: Option<&[u8]>,
+    verifying_key: Option<&NoteVerifyingKey>,
+) -> Result<()> {
+    let Some(config) = config else {
+        return Ok(());
+    };
+    if config.deny_list_root == [0u8; 32] {
+        return Ok(());
+    }
+
+    let proof_data = proof_data.ok_or(ErrorCode::ExclusionProofRequired)?;
+    let verifying_key = verifying_key.ok_or(ErrorCode::ExclusionProofRequired)?;
+    if let Err(e) = verify_exclusion_proof(proof_data, &config.deny_list_root, &subject.to_bytes()) {
+        msg!("⚠️  Exclusion proof error: {:?}", e);
+        return Err(ErrorCode::InvalidProof.into());
+    }
+
+    let public_inputs = [
+        groth16::field_element(&config.deny_list_root),
+        groth16::field_element(&subject.to_bytes()),
+    ];
+    groth16::verify(verifying_key, proof_data, &public_inputs)
+}
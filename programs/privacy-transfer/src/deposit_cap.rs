@@ -0,0 +1,34 @@
+/*!
+ * Per-Account Deposit Caps
+ *
+ * Deployments that need bounded exposure set `max_account_balance` in the
+ * config. Deposits into an EncryptedAccount must then prove, with a range
+ * proof on the headroom cap*G - C_new, that the new hidden balance does not
+ * exceed the cap. The balance itself stays hidden.
+ */
+
+use anchor_lang::prelude::*;
+
+use crate::proof_verification::verify_balance_cap_proof;
+use crate::{Config, ErrorCode};
+
+/**
+ * Require a cap proof for `new_commitment` if a cap is configured
+ */
+pub fn enforce_if_configured(
+    config: Option<&Config>,
+    new_commitment: &[u8; 64],
+    proof_data: Option<&[u8]>,
+) -> Result<()> {
+    let cap = config.map_or(0, |config| config.max_account_balance);
+    if cap == 0 {
+        return Ok(());
+    }
+
+    let proof_data = proof_data.ok_or(ErrorCode::BalanceCapProofRequired)?;
+    if let Err(e) = verify_balance_cap_proof(proof_data, new_commitment, cap) {
+        msg!("⚠️  Balance cap proof error: {:?}", e);
+        return Err(ErrorCode::InvalidProof.into());
+    }
+    Ok(())
+}
@@ -0,0 +1,43 @@
+/*!
+ * Feature Flags
+ *
+ * Operators roll out subsystems progressively per deployment by toggling
+ * bits in `config.features` (`set_features`). Guarded instructions start
+ * with `require_feature!(config, FLAG)`. Deployments without a config PDA
+ * run with every subsystem enabled.
+ */
+
+use crate::Config;
+
+/// Token escrow deposits, withdrawals and transfers
+pub const TOKEN_TRANSFERS: u32 = 1 << 0;
+
+/// Relayed (intent-based) transfers
+pub const RELAYERS: u32 = 1 << 1;
+
+/// Every flag above
+pub const ALL: u32 = TOKEN_TRANSFERS | RELAYERS;
+
+/// Flags of a freshly initialized config
+pub const DEFAULT: u32 = ALL;
+
+/**
+ * Whether `feature` is enabled (always, without a config)
+ */
+pub fn is_enabled(config: Option<&Config>, feature: u32) -> bool {
+    config.is_none_or(|config| config.features & feature != 0)
+}
+
+/**
+ * Fail with `FeatureDisabled` unless the instruction's subsystem is enabled
+ */
+macro_rules! require_feature {
+    ($config:expr, $feature:expr) => {
+        require!(
+            crate::features::is_enabled($config, $feature),
+            ErrorCode::FeatureDisabled
+        )
+    };
+}
+
+pub(crate) use require_feature;
@@ -0,0 +1,201 @@
+/*!
+ * Protocol Fees
+ *
+ * Deployments can charge a basis-point fee on public-amount flows
+ * (`set_fee_params`): withdrawals pay it out of the withdrawn amount,
+ * escrow-to-escrow SOL transfers pay it on top from the sender's wallet.
+ * Either way the escrow balance and its commitment stay in step.
+ *
+ * Fees accrue in the Treasury PDA and are withdrawn by the config's
+ * `fee_authority` with `collect_fees`. Wallets on the exemption list pay
+ * nothing.
+ *
+ * Shielded-to-shielded transfers pay a hidden fee instead: the sender
+ * commits to the fee and proves it matches the rate (up to rounding); the
+ * program adds the commitment to the ShieldedTreasury PDA itself:
+ *
+ *   sender_new    = sender_old - amount - fee     (transfer proof, over
+ *                                                  sender_old - fee)
+ *   fee           = floor(amount * bps / 10_000)  (fee proof)
+ *   treasury_new  = treasury_old + fee            (computed on-chain)
+ *
+ * Each fee's opening reaches the fee authority in a memo (`FeeMemo`), and
+ * `sweep_shielded_fees` moves the treasury balance into the fee
+ * authority's encrypted account.
+ */
+
+use anchor_lang::prelude::*;
+
+use crate::proof_verification::verify_fee_proof;
+use crate::{
+    fee_constants, pedersen, ConfidentialFee, Config, ErrorCode, FeeParams, ShieldedTreasury,
+};
+
+/**
+ * Fee owed by `payer` on `amount` (0 without a config or fee)
+ */
+pub fn fee_for(config: Option<&Config>, payer: &Pubkey, amount: u64) -> Result<u64> {
+    let Some(config) = config else {
+        return Ok(0);
+    };
+    if config.fee_bps == 0 || config.fee_exempt.contains(payer) {
+        return Ok(0);
+    }
+
+    let fee = (amount as u128)
+        .checked_mul(config.fee_bps as u128)
+        .ok_or(ErrorCode::Overflow)?
+        / fee_constants::BPS_DENOMINATOR as u128;
+    Ok(fee as u64)
+}
+
+/**
+ * Sender balance a confidential transfer proof is checked against
+ *
+ * With a hidden fee the sender's balance also drops by the fee, so the
+ * transfer proof opens (sender_old - fee) - sender_new - amount. Whether
+ * the fee was actually owed is checked by enforce_confidential.
+ */
+pub fn transfer_sender_old(sender_old: &[u8; 64], fee: Option<&ConfidentialFee>) -> Result<[u8; 64]> {
+    let Some(fee) = fee else {
+        return Ok(*sender_old);
+    };
+    pedersen::sub(sender_old, &fee.fee_commitment).map_err(|e| {
+        msg!("⚠️  Commitment subtraction error: {:?}", e);
+        ErrorCode::InvalidCommitment.into()
+    })
+}
+
+/**
+ * Charge the hidden fee of a confidential transfer, if one applies
+ *
+ * `treasury` must already be seed-checked. The fee commitment is added to
+ * its balance here; nobody chooses the treasury's new balance.
+ */
+pub fn enforce_confidential(
+    config: Option<&Config>,
+    payer: &Pubkey,
+    treasury: Option<&mut Account<ShieldedTreasury>>,
+    fee: Option<&ConfidentialFee>,
+    amount_commitment: &[u8; 64],
+    sender_old: &[u8; 64],
+    sender_new: &[u8; 64],
+) -> Result<()> {
+    let fee_bps = match config {
+        Some(config) if !config.fee_exempt.contains(payer) => config.fee_bps,
+        _ => 0,
+    };
+    if fee_bps == 0 {
+        // A fee proof would mean the sender's new balance also dropped by a fee
+        require!(fee.is_none(), ErrorCode::InvalidProof);
+        return Ok(());
+    }
+
+    let treasury = treasury.ok_or(ErrorCode::TreasuryRequired)?;
+    let fee = fee.ok_or(ErrorCode::FeeProofRequired)?;
+
+    if let Err(e) = verify_fee_proof(
+        &fee.proof_data,
+        amount_commitment,
+        &fee.fee_commitment,
+        fee_bps,
+        sender_old,
+        sender_new,
+    ) {
+        msg!("⚠️  Fee proof error: {:?}", e);
+        return Err(ErrorCode::InvalidProof.into());
+    }
+
+    let new_balance = match pedersen::add(&treasury.encrypted_balance, &fee.fee_commitment) {
+        Ok(commitment) => commitment,
+        Err(e) => {
+            msg!("⚠️  Commitment addition error: {:?}", e);
+            return Err(ErrorCode::InvalidCommitment.into());
+        }
+    };
+    treasury.encrypted_balance = new_balance;
+    treasury.version += 1;
+    treasury.fees_charged = treasury.fees_charged.checked_add(1)
+        .ok_or(ErrorCode::Overflow)?;
+    Ok(())
+}
+
+/**
+ * Validate new fee parameters
+ */
+pub fn validate_params(params: &FeeParams) -> Result<()> {
+    require!(
+        params.fee_bps <= fee_constants::MAX_FEE_BPS
+            && params.exempt.len() <= fee_constants::MAX_FEE_EXEMPTIONS
+            && (params.fee_bps == 0 || params.fee_authority != Pubkey::default()),
+        ErrorCode::InvalidConfigParams
+    );
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::EncryptedMemo;
+
+    /// Config with every field zeroed (no fee, no exemptions)
+    fn config(fee_bps: u16, exempt: Vec<Pubkey>) -> Config {
+        let zeroed = vec![0u8; Config::INIT_SPACE];
+        let mut config = Config::deserialize(&mut &zeroed[..]).unwrap();
+        config.fee_bps = fee_bps;
+        config.fee_exempt = exempt;
+        config
+    }
+
+    #[test]
+    fn fee_rounds_down_and_honors_exemptions() {
+        let payer = Pubkey::new_unique();
+        assert_eq!(fee_for(None, &payer, 1_000_000).unwrap(), 0);
+        assert_eq!(fee_for(Some(&config(0, vec![])), &payer, 1_000_000).unwrap(), 0);
+
+        let charging = config(25, vec![]);
+        assert_eq!(fee_for(Some(&charging), &payer, 1_000_000).unwrap(), 2_500);
+        assert_eq!(fee_for(Some(&charging), &payer, 399).unwrap(), 0);
+        assert_eq!(fee_for(Some(&charging), &payer, 401).unwrap(), 1);
+        assert_eq!(fee_for(Some(&charging), &payer, u64::MAX).unwrap(), u64::MAX / 400);
+
+        let exempting = config(25, vec![payer]);
+        assert_eq!(fee_for(Some(&exempting), &payer, 1_000_000).unwrap(), 0);
+    }
+
+    #[test]
+    fn fee_proofs_are_refused_when_no_fee_applies() {
+        let payer = Pubkey::new_unique();
+        let fee = ConfidentialFee {
+            fee_commitment: [0u8; 64],
+            memo: EncryptedMemo::deserialize(&mut &[0u8; EncryptedMemo::INIT_SPACE][..]).unwrap(),
+            proof_data: vec![],
+        };
+        let slot = [0u8; 64];
+        assert!(enforce_confidential(None, &payer, None, None, &slot, &slot, &slot).is_ok());
+        assert_eq!(
+            enforce_confidential(None, &payer, None, Some(&fee), &slot, &slot, &slot).unwrap_err(),
+            ErrorCode::InvalidProof.into()
+        );
+        assert_eq!(
+            enforce_confidential(Some(&config(25, vec![])), &payer, None, None, &slot, &slot, &slot)
+                .unwrap_err(),
+            ErrorCode::TreasuryRequired.into()
+        );
+    }
+
+    #[test]
+    fn params_are_bounded() {
+        let authority = Pubkey::new_unique();
+        let params = |fee_bps, fee_authority, exempt: usize| FeeParams {
+            fee_bps,
+            fee_authority,
+            exempt: vec![Pubkey::new_unique(); exempt],
+        };
+        assert!(validate_params(&params(0, Pubkey::default(), 0)).is_ok());
+        assert!(validate_params(&params(fee_constants::MAX_FEE_BPS, authority, 8)).is_ok());
+        assert!(validate_params(&params(fee_constants::MAX_FEE_BPS + 1, authority, 0)).is_err());
+        assert!(validate_params(&params(10, authority, 9)).is_err());
+        assert!(validate_params(&params(10, Pubkey::default(), 0)).is_err());
+    }
+}
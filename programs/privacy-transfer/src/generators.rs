@@ -0,0 +1,118 @@
+/*!
+ * Bulletproof Generator Table
+ *
+ * The Pedersen bases (G, H) and the range-proof vector generators
+ * (G_i, H_i) published once in a read-only PDA, so provers, off-chain
+ * verifiers and the program all use identical bases instead of each
+ * re-deriving them.
+ *
+ * The table holds ~8 KiB of points, more than one transaction can carry.
+ * The config authority creates it with the Pedersen bases
+ * (`initialize_generators`), fills the vectors in chunks
+ * (`write_generators`) and seals it (`finalize_generators`). Sealing
+ * records a Keccak256 digest of every point; a sealed table is never
+ * written again.
+ *
+ * Digest: Keccak256("generators-v1" || G || H || G_0..G_63 || H_0..H_63)
+ */
+
+use anchor_lang::prelude::*;
+use sha3::{Digest, Keccak256};
+
+use crate::{proof_constants, ErrorCode, GeneratorTable};
+
+/// Vector generators per side (one per range-proof bit)
+pub const GENERATOR_CAPACITY: usize = proof_constants::DEFAULT_RANGE_BITS as usize;
+
+/// Generator pairs per `write_generators` call (6 * 128 bytes fits a transaction)
+pub const MAX_PAIRS_PER_WRITE: usize = 6;
+
+/**
+ * Whether `point` can serve as a generator (non-zero encoding)
+ */
+fn is_valid_generator(point: &[u8; 64]) -> bool {
+    point != &[0u8; 64]
+}
+
+/**
+ * Set the Pedersen bases of a freshly created table
+ */
+pub fn init(
+    table: &mut GeneratorTable,
+    authority: Pubkey,
+    pedersen_g: &[u8; 64],
+    pedersen_h: &[u8; 64],
+    bump: u8,
+) -> Result<()> {
+    require!(
+        is_valid_generator(pedersen_g) && is_valid_generator(pedersen_h) && pedersen_g != pedersen_h,
+        ErrorCode::InvalidGenerator
+    );
+
+    table.authority = authority;
+    table.pedersen_g = *pedersen_g;
+    table.pedersen_h = *pedersen_h;
+    table.written = 0;
+    table.finalized = 0;
+    table.bump = bump;
+    Ok(())
+}
+
+/**
+ * Write generator pairs (G_i, H_i) starting at index `start`
+ *
+ * Writes may overwrite earlier entries but must not leave gaps, so a
+ * table with `written == GENERATOR_CAPACITY` is completely filled.
+ */
+pub fn write(table: &mut GeneratorTable, start: u16, g: &[[u8; 64]], h: &[[u8; 64]]) -> Result<()> {
+    require!(table.finalized == 0, ErrorCode::GeneratorsSealed);
+    require!(
+        !g.is_empty() && g.len() == h.len() && g.len() <= MAX_PAIRS_PER_WRITE,
+        ErrorCode::InvalidGenerator
+    );
+
+    let start = start as usize;
+    let end = start + g.len();
+    require!(
+        start <= table.written as usize && end <= GENERATOR_CAPACITY,
+        ErrorCode::InvalidGenerator
+    );
+    require!(
+        g.iter().chain(h).all(is_valid_generator),
+        ErrorCode::InvalidGenerator
+    );
+
+    table.g_vec[start..end].copy_from_slice(g);
+    table.h_vec[start..end].copy_from_slice(h);
+    table.written = table.written.max(end as u16);
+    Ok(())
+}
+
+/**
+ * Seal a completely filled table and return its digest
+ */
+pub fn finalize(table: &mut GeneratorTable) -> Result<[u8; 32]> {
+    require!(table.finalized == 0, ErrorCode::GeneratorsSealed);
+    require!(
+        table.written as usize == GENERATOR_CAPACITY,
+        ErrorCode::GeneratorsIncomplete
+    );
+
+    table.digest = digest(table);
+    table.finalized = 1;
+    Ok(table.digest)
+}
+
+/**
+ * Keccak256 digest over all bases, in table order
+ */
+pub fn digest(table: &GeneratorTable) -> [u8; 32] {
+    let mut hasher = Keccak256::new();
+    hasher.update(b"generators-v1");
+    hasher.update(table.pedersen_g);
+    hasher.update(table.pedersen_h);
+    for point in table.g_vec.iter().chain(table.h_vec.iter()) {
+        hasher.update(point);
+    }
+    hasher.finalize().into()
+}
@@ -0,0 +1,221 @@
+/*!
+ * Groth16 Verification (alt_bn128 syscalls)
+ *
+ * Note spends and deny-list exclusion proofs are Groth16 proofs over BN254.
+ * Each circuit's verifying key is stored on-chain in a NoteVerifyingKey
+ * account (written once by the config authority), and every proof is
+ * checked with the pairing equation
+ * e(-A, B) * e(alpha, beta) * e(vk_x, gamma) * e(C, delta) == 1, where
+ * vk_x = IC[0] + sum(input_i * IC[i + 1]).
+ *
+ * ENCODING (EIP-197, as taken by the syscalls):
+ * - G1: x || y, 32-byte big-endian coordinates (64 bytes)
+ * - G2: x_im || x_re || y_im || y_re (128 bytes)
+ * - Proof data: A (G1) || B (G2) || C (G1)
+ *
+ * Public inputs are 32-byte big-endian field elements. Hashes (roots,
+ * nullifiers) and pubkeys are mapped into the scalar field by clearing
+ * their top byte; circuits must apply the same truncation.
+ */
+
+use anchor_lang::prelude::*;
+use solana_bn254::prelude::{alt_bn128_addition, alt_bn128_multiplication, alt_bn128_pairing};
+
+use crate::{ErrorCode, NoteVerifyingKey};
+
+/// Circuit id of plain note spends (root, nullifier, amount, recipient)
+pub const NOTE_SPEND_CIRCUIT: u8 = 0;
+
+/// Circuit id of association-set spends
+/// (root, association_root, nullifier, amount, recipient)
+pub const ASSOCIATION_SPEND_CIRCUIT: u8 = 1;
+
+/// Circuit id of deny-list exclusion proofs (deny_list_root, subject)
+pub const EXCLUSION_CIRCUIT: u8 = 2;
+
+/// BN254 base field modulus p (big-endian)
+const FIELD_MODULUS: [u8; 32] = [
+    0x30, 0x64, 0x4e, 0x72, 0xe1, 0x31, 0xa0, 0x29, 0xb8, 0x50, 0x45, 0xb6, 0x81, 0x81, 0x58, 0x5d,
+    0x97, 0x81, 0x6a, 0x91, 0x68, 0x71, 0xca, 0x8d, 0x3c, 0x20, 0x8c, 0x16, 0xd8, 0x7c, 0xfd, 0x47,
+];
+
+/**
+ * Number of public inputs of a note circuit
+ */
+pub fn public_input_count(circuit: u8) -> Option<usize> {
+    match circuit {
+        NOTE_SPEND_CIRCUIT => Some(4),
+        ASSOCIATION_SPEND_CIRCUIT => Some(5),
+        EXCLUSION_CIRCUIT => Some(2),
+        _ => None,
+    }
+}
+
+/**
+ * Map a 32-byte hash or pubkey into the scalar field (top byte cleared)
+ */
+pub fn field_element(bytes: &[u8; 32]) -> [u8; 32] {
+    let mut element = *bytes;
+    element[0] = 0;
+    element
+}
+
+/**
+ * Public input encoding of an amount
+ */
+pub fn amount_element(amount: u64) -> [u8; 32] {
+    let mut element = [0u8; 32];
+    element[24..].copy_from_slice(&amount.to_be_bytes());
+    element
+}
+
+/**
+ * Verify a Groth16 proof against `vk` for the given public inputs
+ */
+pub fn verify(vk: &NoteVerifyingKey, proof_data: &[u8], public_inputs: &[[u8; 32]]) -> Result<()> {
+    require!(proof_data.len() == 256, ErrorCode::InvalidProof);
+    require!(vk.ic.len() == public_inputs.len() + 1, ErrorCode::InvalidVerifyingKey);
+
+    let (a, rest) = proof_data.split_at(64);
+    let (b, c) = rest.split_at(128);
+
+    // vk_x = IC[0] + sum(input_i * IC[i + 1])
+    let mut vk_x = vk.ic[0];
+    for (input, ic) in public_inputs.iter().zip(vk.ic[1..].iter()) {
+        let mut mul_input = [0u8; 96];
+        mul_input[..64].copy_from_slice(ic);
+        mul_input[64..].copy_from_slice(input);
+        let term = alt_bn128_multiplication(&mul_input).map_err(|_| ErrorCode::InvalidProof)?;
+
+        let mut add_input = [0u8; 128];
+        add_input[..64].copy_from_slice(&vk_x);
+        add_input[64..].copy_from_slice(&term);
+        let sum = alt_bn128_addition(&add_input).map_err(|_| ErrorCode::InvalidProof)?;
+        vk_x.copy_from_slice(&sum);
+    }
+
+    let mut pairing_input = Vec::with_capacity(4 * 192);
+    pairing_input.extend_from_slice(&negate_g1(a)?);
+    pairing_input.extend_from_slice(b);
+    pairing_input.extend_from_slice(&vk.alpha_g1);
+    pairing_input.extend_from_slice(&vk.beta_g2);
+    pairing_input.extend_from_slice(&vk_x);
+    pairing_input.extend_from_slice(&vk.gamma_g2);
+    pairing_input.extend_from_slice(c);
+    pairing_input.extend_from_slice(&vk.delta_g2);
+
+    let result = alt_bn128_pairing(&pairing_input).map_err(|_| ErrorCode::InvalidProof)?;
+    let accepted = result.len() == 32
+        && result[31] == 1
+        && result[..31].iter().all(|&byte| byte == 0);
+    require!(accepted, ErrorCode::InvalidProof);
+    Ok(())
+}
+
+/// -P for a G1 point: (x, p - y); the point at infinity is its own negation
+fn negate_g1(point: &[u8]) -> Result<[u8; 64]> {
+    let mut negated = [0u8; 64];
+    negated.copy_from_slice(point);
+    if point.iter().all(|&byte| byte == 0) {
+        return Ok(negated);
+    }
+
+    let mut borrow = 0i16;
+    for i in (0..32).rev() {
+        let diff = FIELD_MODULUS[i] as i16 - point[32 + i] as i16 - borrow;
+        borrow = (diff < 0) as i16;
+        negated[32 + i] = diff.rem_euclid(256) as u8;
+    }
+    // y >= p is not a valid coordinate
+    require!(borrow == 0, ErrorCode::InvalidProof);
+    Ok(negated)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// G1 generator (1, 2)
+    fn g1() -> [u8; 64] {
+        let mut point = [0u8; 64];
+        point[31] = 1;
+        point[63] = 2;
+        point
+    }
+
+    /// G2 generator (EIP-197 order)
+    fn g2() -> [u8; 128] {
+        let hex = concat!(
+            "198e9393920d483a7260bfb731fb5d25f1aa493335a9e71297e485b7aef312c2",
+            "1800deef121f1e76426a00665e5c4479674322d4f75edadd46debd5cd992f6ed",
+            "090689d0585ff075ec9e99ad690c3395bc4b313370b38ef355acdadcd122975b",
+            "12c85ea5db8c6deb4aab71808dcb408fe3d1e7690c43d37b4ce6cc0166fa7daa",
+        );
+        let mut point = [0u8; 128];
+        for (i, byte) in point.iter_mut().enumerate() {
+            *byte = u8::from_str_radix(&hex[2 * i..2 * i + 2], 16).unwrap();
+        }
+        point
+    }
+
+    fn g1_times(k: u64) -> [u8; 64] {
+        let mut input = [0u8; 96];
+        input[..64].copy_from_slice(&g1());
+        input[88..].copy_from_slice(&k.to_be_bytes());
+        alt_bn128_multiplication(&input).unwrap().try_into().unwrap()
+    }
+
+    /// Key with every point a generator: the equation reduces to
+    /// a = 1 + (1 + sum(inputs)) + c in the exponent
+    fn trivial_key(inputs: usize) -> NoteVerifyingKey {
+        NoteVerifyingKey {
+            circuit: NOTE_SPEND_CIRCUIT,
+            alpha_g1: g1(),
+            beta_g2: g2(),
+            gamma_g2: g2(),
+            delta_g2: g2(),
+            ic: vec![g1(); inputs + 1],
+            bump: 0,
+        }
+    }
+
+    fn proof(a: u64, c: u64) -> Vec<u8> {
+        let mut data = g1_times(a).to_vec();
+        data.extend_from_slice(&g2());
+        data.extend_from_slice(&g1_times(c));
+        data
+    }
+
+    #[test]
+    fn accepts_a_satisfying_proof() {
+        let vk = trivial_key(4);
+        let inputs = [amount_element(3), amount_element(4), amount_element(5), amount_element(6)];
+        // 1 + (1 + 18) + 7
+        assert!(verify(&vk, &proof(27, 7), &inputs).is_ok());
+    }
+
+    #[test]
+    fn rejects_wrong_inputs_and_forged_proofs() {
+        let vk = trivial_key(4);
+        let inputs = [amount_element(3), amount_element(4), amount_element(5), amount_element(6)];
+        let other = [amount_element(3), amount_element(4), amount_element(5), amount_element(7)];
+        assert!(verify(&vk, &proof(27, 7), &other).is_err());
+        assert!(verify(&vk, &proof(28, 7), &inputs).is_err());
+        assert!(verify(&vk, &[0x11; 256], &inputs).is_err());
+        assert!(verify(&vk, &proof(27, 7), &inputs[..3]).is_err());
+    }
+
+    #[test]
+    fn negation_cancels_in_the_group() {
+        let mut input = [0u8; 128];
+        input[..64].copy_from_slice(&g1());
+        input[64..].copy_from_slice(&negate_g1(&g1()).unwrap());
+        assert_eq!(alt_bn128_addition(&input).unwrap(), vec![0u8; 64]);
+    }
+
+    #[test]
+    fn field_elements_fit_the_scalar_field() {
+        assert_eq!(field_element(&[0xff; 32])[0], 0);
+        assert_eq!(&amount_element(0x0102)[30..], &[0x01, 0x02]);
+    }
+}
@@ -0,0 +1,174 @@
+/*!
+ * Verification Heap Allocator (`custom-heap` feature)
+ *
+ * The default SBF allocator bumps down through a 32 KiB heap and never
+ * frees, so proof parsing plus future MSM scratch space runs out quickly.
+ * With `custom-heap` the program installs this allocator instead:
+ *
+ * - Heap region of `HEAP_LENGTH` (256 KiB, the runtime maximum). Clients
+ *   must request it with a ComputeBudget `RequestHeapFrame(262144)`
+ *   instruction; without that, use past 32 KiB faults the transaction.
+ * - Bump allocation upwards, so the most recent allocation can grow in
+ *   place (`Vec` pushes while parsing) and is reclaimed when freed first
+ *   (scratch buffers released in LIFO order).
+ * - The bump position lives at the start of the heap, which the runtime
+ *   zeroes for every instruction, so each instruction starts with an
+ *   empty heap.
+ */
+
+use std::alloc::{GlobalAlloc, Layout};
+use std::mem::size_of;
+use std::ptr::null_mut;
+
+use anchor_lang::solana_program::entrypoint::HEAP_START_ADDRESS;
+
+/// Heap region used by the allocator (matches the requested heap frame)
+pub const HEAP_LENGTH: usize = 256 * 1024;
+
+/// Upward bump allocator over `[start, start + len)`
+///
+/// The first word of the region holds the bump position (0 = empty).
+pub struct BumpAllocator {
+    pub start: usize,
+    pub len: usize,
+}
+
+impl BumpAllocator {
+    /// Pointer to the stored bump position
+    fn position(&self) -> *mut usize {
+        self.start as *mut usize
+    }
+
+    /// Current bump position (first free byte)
+    unsafe fn current(&self) -> usize {
+        match *self.position() {
+            0 => self.start + size_of::<usize>(),
+            pos => pos,
+        }
+    }
+}
+
+unsafe impl GlobalAlloc for BumpAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let align_mask = layout.align() - 1;
+        let Some(ptr) = self.current().checked_add(align_mask).map(|pos| pos & !align_mask) else {
+            return null_mut();
+        };
+        match ptr.checked_add(layout.size()) {
+            Some(end) if end <= self.start + self.len => {
+                *self.position() = end;
+                ptr as *mut u8
+            }
+            _ => null_mut(),
+        }
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        // Only the most recent allocation can be reclaimed
+        if ptr as usize + layout.size() == self.current() {
+            *self.position() = ptr as usize;
+        }
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        // The most recent allocation grows or shrinks in place
+        if ptr as usize + layout.size() == self.current() {
+            if let Some(end) = (ptr as usize).checked_add(new_size) {
+                if end <= self.start + self.len {
+                    *self.position() = end;
+                    return ptr;
+                }
+            }
+            return null_mut();
+        }
+
+        let new_layout = Layout::from_size_align_unchecked(new_size, layout.align());
+        let new_ptr = self.alloc(new_layout);
+        if !new_ptr.is_null() {
+            std::ptr::copy_nonoverlapping(ptr, new_ptr, layout.size().min(new_size));
+        }
+        new_ptr
+    }
+}
+
+#[cfg(all(target_os = "solana", not(feature = "no-entrypoint")))]
+#[global_allocator]
+static ALLOCATOR: BumpAllocator = BumpAllocator {
+    start: HEAP_START_ADDRESS as usize,
+    len: HEAP_LENGTH,
+};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LEN: usize = 256;
+
+    /// Allocator over a zeroed, word-aligned test region
+    fn allocator(region: &mut [u64; LEN / 8]) -> BumpAllocator {
+        BumpAllocator {
+            start: region.as_mut_ptr() as usize,
+            len: LEN,
+        }
+    }
+
+    #[test]
+    fn allocates_upwards_and_aligned() {
+        let mut region = [0u64; LEN / 8];
+        let heap = allocator(&mut region);
+        unsafe {
+            let a = heap.alloc(Layout::from_size_align(3, 1).unwrap());
+            assert_eq!(a as usize, heap.start + size_of::<usize>());
+            let b = heap.alloc(Layout::from_size_align(8, 8).unwrap());
+            assert_eq!(b as usize % 8, 0);
+            assert!(b as usize >= a as usize + 3);
+        }
+    }
+
+    #[test]
+    fn reclaims_the_most_recent_allocation() {
+        let mut region = [0u64; LEN / 8];
+        let heap = allocator(&mut region);
+        let layout = Layout::from_size_align(16, 8).unwrap();
+        unsafe {
+            let a = heap.alloc(layout);
+            let b = heap.alloc(layout);
+            heap.dealloc(b, layout);
+            assert_eq!(heap.alloc(layout), b);
+
+            // Older allocations are not reclaimed
+            heap.dealloc(a, layout);
+            assert_ne!(heap.alloc(layout), a);
+        }
+    }
+
+    #[test]
+    fn grows_the_last_allocation_in_place() {
+        let mut region = [0u64; LEN / 8];
+        let heap = allocator(&mut region);
+        let layout = Layout::from_size_align(8, 8).unwrap();
+        unsafe {
+            let a = heap.alloc(layout);
+            *a = 0x5a;
+            assert_eq!(heap.realloc(a, layout, 64), a);
+
+            let b = heap.alloc(layout);
+            let moved = heap.realloc(a, Layout::from_size_align(64, 8).unwrap(), 96);
+            assert!(moved as usize > b as usize);
+            assert_eq!(*moved, 0x5a);
+        }
+    }
+
+    #[test]
+    fn fails_past_the_end_of_the_region() {
+        let mut region = [0u64; LEN / 8];
+        let heap = allocator(&mut region);
+        unsafe {
+            assert!(heap.alloc(Layout::from_size_align(LEN, 1).unwrap()).is_null());
+            let a = heap.alloc(Layout::from_size_align(LEN - 16, 8).unwrap());
+            assert!(!a.is_null());
+            assert!(heap.realloc(a, Layout::from_size_align(LEN - 16, 8).unwrap(), LEN).is_null());
+            assert!(heap.alloc(Layout::from_size_align(16, 8).unwrap()).is_null());
+        }
+    }
+}
@@ -0,0 +1,52 @@
+/*!
+ * Heartbeat Self-Tests
+ *
+ * Known-answer tests for the verification-critical building blocks, run by
+ * the `heartbeat` instruction so operators can continuously check that they
+ * behave correctly on the live cluster (e.g. across validator upgrades).
+ *
+ * - Transcript: Fiat-Shamir challenge over fixed input (Keccak256)
+ * - Curve: one Ristretto scalar multiplication through the curve25519
+ *   syscall (2 * G)
+ */
+
+use confidential_proofs::merlin_transcript::MerlinTranscript;
+use solana_curve25519::ristretto::{multiply_ristretto, PodRistrettoPoint};
+use solana_curve25519::scalar::PodScalar;
+
+/// Expected challenge for the fixed heartbeat transcript
+const EXPECTED_CHALLENGE: [u8; 32] = [
+    0x32, 0x59, 0x0a, 0x08, 0xa4, 0x0a, 0x4c, 0x36, 0x84, 0x74, 0xab, 0xd3, 0x9b, 0x87, 0xfd, 0xd8,
+    0x7d, 0x0e, 0xa0, 0x71, 0xb9, 0xf0, 0xa8, 0x57, 0x0d, 0x34, 0xb7, 0x19, 0x00, 0x35, 0x2e, 0x22,
+];
+
+/// Ristretto255 base point G (compressed)
+const RISTRETTO_BASEPOINT: [u8; 32] = [
+    0xe2, 0xf2, 0xae, 0x0a, 0x6a, 0xbc, 0x4e, 0x71, 0xa8, 0x84, 0xa9, 0x61, 0xc5, 0x00, 0x51, 0x5f,
+    0x58, 0xe3, 0x0b, 0x6a, 0xa5, 0x82, 0xdd, 0x8d, 0xb6, 0xa6, 0x59, 0x45, 0xe0, 0x8d, 0x2d, 0x76,
+];
+
+/// 2 * G (compressed)
+const RISTRETTO_BASEPOINT_DOUBLED: [u8; 32] = [
+    0x6a, 0x49, 0x32, 0x10, 0xf7, 0x49, 0x9c, 0xd1, 0x7f, 0xec, 0xb5, 0x10, 0xae, 0x0c, 0xea, 0x23,
+    0xa1, 0x10, 0xe8, 0xd5, 0xb9, 0x01, 0xf8, 0xac, 0xad, 0xd3, 0x09, 0x5c, 0x73, 0xa3, 0xb9, 0x19,
+];
+
+/**
+ * Build the fixed heartbeat transcript and compare its challenge
+ */
+pub fn transcript_self_test() -> bool {
+    let mut transcript = MerlinTranscript::new(b"heartbeat");
+    transcript.append_message(b"probe", b"privacy-transfer");
+    transcript.challenge_scalar(b"challenge") == EXPECTED_CHALLENGE
+}
+
+/**
+ * Compute 2 * G through the curve syscall and compare
+ */
+pub fn curve_self_test() -> bool {
+    let mut two = [0u8; 32];
+    two[0] = 2;
+    multiply_ristretto(&PodScalar(two), &PodRistrettoPoint(RISTRETTO_BASEPOINT))
+        .is_some_and(|point| point.0 == RISTRETTO_BASEPOINT_DOUBLED)
+}
@@ -0,0 +1,56 @@
+/*!
+ * KYC / Soulbound-Token Gating
+ *
+ * Permissioned deployments set `kyc_mint` in the config; confidential
+ * transfers then require the sender (and, with `kyc_require_recipient`, the
+ * recipient) to hold at least one token of that mint. Issuing the
+ * credential as a Token-2022 NonTransferable mint makes it soulbound, so it
+ * cannot be lent to an unverified wallet.
+ *
+ * Credentials are passed as token accounts owned by the wallet itself.
+ */
+
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::TokenAccount;
+
+use crate::{Config, ErrorCode};
+
+/**
+ * Require `wallet` to hold the configured credential
+ */
+fn check_credential(
+    kyc_mint: &Pubkey,
+    wallet: &Pubkey,
+    credential: Option<&TokenAccount>,
+) -> Result<()> {
+    let credential = credential.ok_or(ErrorCode::KycCredentialRequired)?;
+    require!(
+        credential.mint == *kyc_mint && credential.owner == *wallet && credential.amount > 0,
+        ErrorCode::KycCredentialRequired
+    );
+    Ok(())
+}
+
+/**
+ * Enforce the KYC gate for a transfer, if one is configured
+ */
+pub fn enforce_if_configured(
+    config: Option<&Config>,
+    sender: &Pubkey,
+    sender_credential: Option<&TokenAccount>,
+    recipient: &Pubkey,
+    recipient_credential: Option<&TokenAccount>,
+) -> Result<()> {
+    let Some(config) = config else {
+        return Ok(());
+    };
+    if config.kyc_mint == Pubkey::default() {
+        return Ok(());
+    }
+
+    check_credential(&config.kyc_mint, sender, sender_credential)?;
+    if config.kyc_require_recipient {
+        check_credential(&config.kyc_mint, recipient, recipient_credential)?;
+    }
+    Ok(())
+}
@@ -1,26 +1,305 @@
 use anchor_lang::prelude::*;
-use anchor_lang::system_program::{transfer, Transfer};
+use anchor_lang::system_program::{create_account, transfer, CreateAccount, Transfer};
+use anchor_spl::token_2022::{
+    burn, initialize_mint2, mint_to, Burn, InitializeMint2, MintTo, Token2022,
+};
+use anchor_spl::token_interface::{
+    close_account as close_token_account, non_transferable_mint_initialize, sync_native,
+    transfer_checked, CloseAccount as CloseTokenAccount, Mint, NonTransferableMintInitialize,
+    SyncNative, TokenAccount, TokenInterface, TransferChecked,
+};
 
-mod crypto_primitives;
-mod merlin_transcript;
-mod proof_verification;
-use proof_verification::verify_transfer_proof;
+pub mod account_decoder;
+mod auth_chain;
+mod compression;
+mod confidential_token_bridge;
+#[cfg(feature = "cu-profile")]
+mod cu_profile;
+mod deny_list;
+mod deposit_cap;
+mod features;
+mod fees;
+mod generators;
+mod groth16;
+#[cfg(any(feature = "custom-heap", test))]
+#[cfg_attr(not(target_os = "solana"), allow(dead_code, unused_imports))]
+mod heap;
+mod heartbeat;
+mod kyc;
+mod limits;
+mod merkle_tree;
+mod migration;
+mod pause;
+mod stats;
+pub mod pda;
+mod relayer;
+mod sanctions;
+mod token_transfer;
+mod travel_rule;
+mod velocity;
+mod verifier;
+use confidential_proofs::{is_valid_commitment, pedersen, proof_verification};
+use proof_verification::{
+    verify_amount_link_proof, verify_amount_match_proof, verify_association_spend_proof,
+    verify_decoy_transfer_proof,
+    verify_deposit_proof, verify_escrow_credit_proof, verify_escrow_debit_proof,
+    verify_multi_transfer_proof, verify_note_spend_proof, verify_opening_proof,
+    verify_rotation_proof, verify_stream_accrual_proof, verify_supply_proof, verify_transfer_proof,
+    verify_transfer_stage_a, verify_transfer_stage_b, verify_vesting_schedule_proof,
+    verify_withdraw_proof, verify_withdraw_to_proof, SupplyDirection, TransferStageA,
+};
+use privacy_transfer_types::proof_constants;
+use features::require_feature;
+use pause::require_not_paused;
+use token_transfer::transfer_checked_with_hooks;
 
 declare_id!("HHvRt9CScrgHkfhDGUiwbskYpCSA9PetdT4uVwQ5C7f5");
 
-/// Proof verification constants
-/// These match the TypeScript PROOF_CONSTANTS for consistency
-mod proof_constants {
-    /// Minimum proof data size in bytes (basic proof structure)
-    pub const MIN_PROOF_DATA_SIZE: usize = 64;
+/// Multi-asset account constants
+mod asset_constants {
+    /// Maximum number of (mint, commitment) entries per encrypted account
+    pub const MAX_ASSETS: usize = 16;
+}
+
+/// Shared pool constants
+mod pool_constants {
+    use anchor_lang::prelude::Pubkey;
+    
+    /// Asset id of pool-backed SOL inside EncryptedAccount.assets
+    /// (the system program stands in for a native-SOL "mint")
+    pub const POOL_SOL_ASSET_ID: Pubkey = anchor_lang::solana_program::system_program::ID;
+    
+    /// Only amounts the pool accepts in and pays out (0.1, 1, 10, 100 SOL)
+    ///
+    /// Every pool deposit and withdrawal of one size looks alike, so each
+    /// hides among all others of its denomination. Larger amounts are split
+    /// client-side into several transactions.
+    pub const DENOMINATIONS: [u64; 4] = [
+        100_000_000,
+        1_000_000_000,
+        10_000_000_000,
+        100_000_000_000,
+    ];
+}
+
+/// Receipt token constants
+mod receipt_constants {
+    /// Receipt token decimals (1 base unit = 1 lamport)
+    pub const RECEIPT_DECIMALS: u8 = 9;
+}
+
+/// Mailbox constants
+mod mailbox_constants {
+    /// Maximum ciphertext size of one mailbox message (bytes)
+    pub const MAX_CIPHERTEXT_LEN: usize = 512;
+    
+    /// Default minimum number of slots between two posts (~4s)
+    pub const DEFAULT_POST_INTERVAL_SLOTS: u64 = 10;
+}
+
+/// Shielded note pool constants
+mod note_pool_constants {
+    /// Depth of the note commitment tree (2^20 notes)
+    pub const NOTE_TREE_DEPTH: usize = 20;
+    
+    /// Number of recent roots accepted by `spend_note`
+    pub const ROOT_HISTORY_SIZE: usize = 30;
+}
+
+/// Inbox constants
+mod inbox_constants {
+    /// Maximum number of unpruned notices per inbox
+    pub const INBOX_CAPACITY: usize = 16;
+}
+
+/// Withdrawal queue constants
+mod withdrawal_queue_constants {
+    /// Minimum delay between queueing and release (~10 minutes)
+    pub const MIN_DELAY_SLOTS: u64 = 1_500;
+    
+    /// Random extra delay added per withdrawal, in [0, JITTER) slots (~20 minutes)
+    pub const DELAY_JITTER_SLOTS: u64 = 3_000;
+    
+    /// Minimum number of withdrawals released together
+    pub const MIN_BATCH_SIZE: usize = 3;
+    
+    /// Withdrawals overdue by this many slots may be released in smaller batches
+    pub const STALE_AFTER_SLOTS: u64 = 9_000;
+    
+    /// Maximum number of withdrawals per crank call
+    pub const MAX_BATCH_SIZE: usize = 10;
+}
+
+/// Decoy transfer constants
+mod decoy_constants {
+    /// Minimum touched recipient accounts (real recipient + at least one decoy)
+    pub const MIN_OUTPUTS: usize = 2;
+    
+    /// Maximum touched recipient accounts per transfer
+    pub const MAX_OUTPUTS: usize = 8;
+}
+
+/// Travel-rule envelope constants
+mod travel_rule_constants {
+    /// Smallest envelope ciphertext (AEAD tag only)
+    pub const MIN_CIPHERTEXT_LEN: usize = 16;
+    
+    /// Largest envelope ciphertext (bytes, tag included)
+    pub const MAX_CIPHERTEXT_LEN: usize = 512;
+}
+
+/// Court-order disclosure constants
+mod disclosure_constants {
+    /// Maximum number of regulator keys in the config
+    pub const MAX_REGULATORS: usize = 7;
+    
+    /// Shortest answer window a config may set (~1 day)
+    pub const MIN_WINDOW_SLOTS: u64 = 216_000;
+}
+
+/// Selective-disclosure report constants
+mod report_constants {
+    /// Maximum openings in one report (bounded by transaction size)
+    pub const MAX_REPORT_ENTRIES: usize = 6;
+}
+
+/// Multi-recipient transfer constants
+mod multi_transfer_constants {
+    /// Minimum recipients per multi-recipient transfer
+    pub const MIN_RECIPIENTS: usize = 2;
+    
+    /// Maximum recipients per multi-recipient transfer
+    pub const MAX_RECIPIENTS: usize = 8;
+}
+
+/// Batched transfer constants
+mod batch_transfer_constants {
+    /// Maximum independent transfers per batch
+    pub const MAX_TRANSFERS: usize = 4;
+}
+
+/// Two-phase transfer constants
+mod pending_transfer_constants {
+    /// Minimum claim window (~1 minute at 400ms slots)
+    pub const MIN_EXPIRY_SLOTS: u64 = 150;
+    
+    /// Maximum claim window (~1 week at 400ms slots)
+    pub const MAX_EXPIRY_SLOTS: u64 = 1_512_000;
+}
+
+/// Invoice constants
+mod invoice_constants {
+    /// Minimum invoice lifetime (~1 minute at 400ms slots)
+    pub const MIN_EXPIRY_SLOTS: u64 = 150;
+    
+    /// Maximum invoice lifetime (~30 days at 400ms slots)
+    pub const MAX_EXPIRY_SLOTS: u64 = 6_480_000;
+}
+
+/// Claim link constants
+mod claim_link_constants {
+    /// Minimum link lifetime (~1 hour at 400ms slots)
+    pub const MIN_EXPIRY_SLOTS: u64 = 9_000;
+    
+    /// Maximum link lifetime (~30 days at 400ms slots)
+    pub const MAX_EXPIRY_SLOTS: u64 = 6_480_000;
+}
+
+/// Payment stream constants
+mod stream_constants {
+    /// Minimum stream duration (1 minute)
+    pub const MIN_DURATION_SECS: u64 = 60;
+    
+    /// Maximum stream duration (~4 years)
+    pub const MAX_DURATION_SECS: u64 = 126_230_400;
+    
+    /// Time the recipient has after the end to claim before the sender
+    /// may close the stream anyway (1 week)
+    pub const SETTLEMENT_GRACE_SECS: i64 = 604_800;
+}
+
+/// Vesting constants
+mod vesting_constants {
+    /// Maximum vesting duration (~10 years)
+    pub const MAX_DURATION_SECS: u64 = 315_576_000;
+}
+
+/// Subscription constants
+mod subscription_constants {
+    /// Minimum billing period (1 hour)
+    pub const MIN_PERIOD_SECS: u64 = 3_600;
+    
+    /// Maximum billing period (~1 year)
+    pub const MAX_PERIOD_SECS: u64 = 31_557_600;
+}
+
+/// Session key constants
+mod session_key_constants {
+    /// Maximum session lifetime (~1 day at 400ms slots)
+    pub const MAX_DURATION_SLOTS: u64 = 216_000;
+    
+    /// Maximum number of allowed recipients (empty = any recipient)
+    pub const MAX_ALLOWED_RECIPIENTS: usize = 4;
+}
+
+/// Dead-man switch constants
+mod dead_man_switch_constants {
+    /// Minimum inactivity period (~1 week at 400ms slots)
+    pub const MIN_INACTIVITY_SLOTS: u64 = 1_512_000;
+    
+    /// Maximum inactivity period (~5 years)
+    pub const MAX_INACTIVITY_SLOTS: u64 = 394_200_000;
+}
+
+/// Social recovery constants
+mod guardian_constants {
+    /// Maximum number of guardians (approvals fit a u8 bitmask)
+    pub const MAX_GUARDIANS: usize = 7;
+    
+    /// Minimum veto window (~1 day at 400ms slots)
+    pub const MIN_RECOVERY_DELAY_SLOTS: u64 = 216_000;
+    
+    /// Maximum veto window (~30 days)
+    pub const MAX_RECOVERY_DELAY_SLOTS: u64 = 6_480_000;
+}
+
+/// Hash time-lock constants
+mod htlc_constants {
+    /// Minimum timelock (~1 hour at 400ms slots)
+    pub const MIN_TIMELOCK_SLOTS: u64 = 9_000;
+    
+    /// Maximum timelock (~1 week)
+    pub const MAX_TIMELOCK_SLOTS: u64 = 1_512_000;
+}
+
+/// Protocol fee constants
+mod fee_constants {
+    /// Basis points per 100%
+    pub const BPS_DENOMINATOR: u64 = 10_000;
     
-    /// Maximum proof data size in bytes (DoS protection)
-    /// Prevents malicious clients from submitting extremely large proofs
-    pub const MAX_PROOF_DATA_SIZE: usize = 10000;
+    /// Highest configurable fee (10%)
+    pub const MAX_FEE_BPS: u16 = 1_000;
     
-    /// Default range proof bits for lamport amounts
-    #[allow(dead_code)] // Reserved for future use
-    pub const DEFAULT_RANGE_BITS: u8 = 64;
+    /// Maximum fee-exempt wallets (Config.fee_exempt capacity)
+    pub const MAX_FEE_EXEMPTIONS: usize = 8;
+}
+
+/// External verifier constants
+mod verifier_constants {
+    /// Maximum whitelisted verifier programs (Config.verifier_programs capacity)
+    pub const MAX_VERIFIER_PROGRAMS: usize = 4;
+}
+
+/// Config timelock constants
+mod timelock_constants {
+    /// Longest configurable timelock (~1 week at 400ms slots)
+    pub const MAX_DELAY_SLOTS: u64 = 1_512_000;
+}
+
+/// Archive tree constants
+mod archive_constants {
+    /// Depth of the closed-account archive tree (2^20 records)
+    pub const ARCHIVE_TREE_DEPTH: usize = 20;
 }
 
 /// Transfer constants
@@ -34,31 +313,215 @@ mod transfer_constants {
     pub const MIN_AMOUNT: u64 = 1;
 }
 
+/// Whether `mint` is the wrapped-SOL mint of the Token or Token-2022 program
+fn is_native_mint(mint: &Pubkey) -> bool {
+    *mint == anchor_spl::token::spl_token::native_mint::ID
+        || *mint == spl_token_2022::native_mint::ID
+}
+
+/**
+ * Snapshot hash of a watched account's raw data
+ *
+ * Closed (empty) accounts hash to all zeros, so closing a canary trips it.
+ */
+fn canary_snapshot(account: &AccountInfo) -> Result<[u8; 32]> {
+    if account.data_is_empty() {
+        return Ok([0u8; 32]);
+    }
+    let data = account.try_borrow_data()?;
+    Ok(merkle_tree::hash_leaf(&[account.owner.as_ref(), &data]))
+}
+
+/**
+ * Shared body of confidential_mint / confidential_burn
+ *
+ * Verifies the supply proof against the holder's current asset commitment
+ * and stores the new commitment. Caller-specific authorization happens
+ * before this is called; the issuer signature is enforced by the accounts.
+ */
+fn adjust_private_supply(
+    ctx: Context<ConfidentialSupply>,
+    direction: SupplyDirection,
+    new_commitment: [u8; 64],
+    proof_data: &[u8],
+) -> Result<()> {
+    // ============================================
+    // INPUT VALIDATION (Checks)
+    // ============================================
+    
+    // Validate proof data size (DoS protection)
+    require!(
+        proof_data.len() >= proof_constants::MIN_PROOF_DATA_SIZE,
+        ErrorCode::InvalidProof
+    );
+    require!(
+        proof_data.len() <= proof_constants::MAX_PROOF_DATA_SIZE,
+        ErrorCode::InvalidProof
+    );
+    
+    // SECURITY: Validate commitment is well-formed for the configured commitment scheme
+    require!(
+        is_valid_commitment(&new_commitment),
+        ErrorCode::InvalidCommitment
+    );
+    
+    let asset_id = ctx.accounts.private_asset.asset_id;
+    let index = ctx.accounts.holder_account.find_asset(&asset_id)
+        .ok_or(ErrorCode::AssetNotFound)?;
+    let old_commitment = ctx.accounts.holder_account.assets[index].encrypted_balance;
+    
+    // ============================================
+    // ZK PROOF VERIFICATION
+    // ============================================
+    
+    let amount_commitment = match proof_verification::extract_amount_commitment(proof_data) {
+        Ok(commitment) => commitment,
+        Err(e) => {
+            msg!("⚠️  Failed to extract amount commitment from proof: {:?}", e);
+            return Err(ErrorCode::InvalidProof.into());
+        }
+    };
+    
+    if let Err(e) = verify_supply_proof(
+        proof_data,
+        direction,
+        &amount_commitment,
+        &old_commitment,
+        &new_commitment,
+    ) {
+        msg!("⚠️  Proof verification error: {:?}", e);
+        return Err(ErrorCode::InvalidProof.into());
+    }
+    
+    // ============================================
+    // STATE UPDATES (Effects)
+    // ============================================
+    
+    let holder_account = &mut ctx.accounts.holder_account;
+    holder_account.assets[index].encrypted_balance = new_commitment;
+    holder_account.version += 1;
+    holder_account.advance_state_hash(&new_commitment);
+    
+    let private_asset = &mut ctx.accounts.private_asset;
+    private_asset.supply_version = private_asset.supply_version
+        .checked_add(1)
+        .ok_or(ErrorCode::InvalidAmount)?;
+    
+    msg!("   Asset: {}, supply version: {}", asset_id, private_asset.supply_version);
+    Ok(())
+}
+
+/**
+ * Validate a regulator quorum (see `set_regulators`)
+ */
+fn validate_regulators(params: &RegulatorParams) -> Result<()> {
+    let regulators = &params.regulators;
+    require!(
+        regulators.len() <= disclosure_constants::MAX_REGULATORS,
+        ErrorCode::InvalidRegulatorSet
+    );
+    require!(
+        (params.threshold as usize) <= regulators.len()
+            && (params.threshold > 0 || regulators.is_empty()),
+        ErrorCode::InvalidRegulatorSet
+    );
+    require!(
+        regulators.is_empty() || params.window_slots >= disclosure_constants::MIN_WINDOW_SLOTS,
+        ErrorCode::InvalidRegulatorSet
+    );
+    for (i, regulator) in regulators.iter().enumerate() {
+        require!(
+            *regulator != Pubkey::default() && !regulators[..i].contains(regulator),
+            ErrorCode::InvalidRegulatorSet
+        );
+    }
+    Ok(())
+}
+
+/// Per-mint amount bounds must be ordered and above the global minimum
+fn validate_mint_bounds(min_amount: u64, max_amount: u64) -> Result<()> {
+    require!(
+        min_amount >= transfer_constants::MIN_AMOUNT && min_amount <= max_amount,
+        ErrorCode::InvalidAmount
+    );
+    Ok(())
+}
+
+/**
+ * Gate a config change applied by its own instruction on the timelock
+ *
+ * Without a delay the change applies directly. Otherwise `change` must be
+ * the pending config change and its delay must have elapsed; the caller's
+ * accounts close the pending entry. A pending entry passed without a delay
+ * is checked the same way, so an unrelated change is never discarded.
+ */
+fn require_queued_change(
+    config: &Config,
+    pending: Option<&PendingConfigChange>,
+    change: &ConfigChange,
+) -> Result<()> {
+    match pending {
+        None => require!(config.timelock_delay_slots == 0, ErrorCode::TimelockRequired),
+        Some(pending) => {
+            require!(pending.change == *change, ErrorCode::QueuedChangeMismatch);
+            require!(Clock::get()?.slot >= pending.eta_slot, ErrorCode::TimelockNotElapsed);
+        }
+    }
+    Ok(())
+}
+
 #[program]
 pub mod privacy_transfer {
     use super::*;
 
     /// Initialize a new encrypted account
-    pub fn initialize_account(ctx: Context<InitializeAccount>) -> Result<()> {
+    ///
+    /// `account_index` selects the sub-account, so one owner can hold several
+    /// independent shielded accounts (e.g., savings vs. spending).
+    pub fn initialize_account(ctx: Context<InitializeAccount>, account_index: u16) -> Result<()> {
         let account = &mut ctx.accounts.encrypted_account;
         account.owner = ctx.accounts.owner.key();
         account.encrypted_balance = [0u8; 64]; // Zero commitment initially
         account.version = 0;
         account.bump = ctx.bumps.encrypted_account;
+        account.account_index = account_index;
+        account.seed_owner = account.owner;
+        account.pending_owner = Pubkey::default();
+        account.encryption_key = [0u8; 32]; // Registered via rotate_keys
+        account.schema_version = migration::ENCRYPTED_ACCOUNT_SCHEMA_VERSION;
+        account.assets = Vec::new();
+        account.state_hash = [0u8; 32]; // Chain genesis
+        account.frozen = false;
+        account.record_activity()?;
+        
+        // Protocol statistics (if initialized)
+        stats::record(&ctx.accounts.stats, stats::Activity::AccountOpened)?;
         
         msg!("Initialized encrypted account for owner: {}", account.owner);
+        msg!("Account index: {}", account.account_index);
         msg!("Balance is encrypted - not visible on-chain!");
         Ok(())
     }
 
     /// Initialize SOL escrow account for native SOL privacy transfers
-    pub fn initialize_sol_escrow(ctx: Context<InitializeSolEscrow>) -> Result<()> {
+    ///
+    /// Each escrow is paired with the encrypted account of the same `account_index`.
+    pub fn initialize_sol_escrow(ctx: Context<InitializeSolEscrow>, account_index: u16) -> Result<()> {
         let escrow = &mut ctx.accounts.sol_escrow;
         escrow.owner = ctx.accounts.owner.key();
         escrow.balance = 0;
         escrow.bump = ctx.bumps.sol_escrow;
+        escrow.account_index = account_index;
+        escrow.seed_owner = escrow.owner;
+        escrow.pending_owner = Pubkey::default();
+        escrow.schema_version = migration::SOL_ESCROW_SCHEMA_VERSION;
+        escrow.receipts_outstanding = 0;
+        
+        // Protocol statistics (if initialized)
+        stats::record(&ctx.accounts.stats, stats::Activity::EscrowOpened)?;
         
         msg!("Initialized SOL escrow for owner: {}", escrow.owner);
+        msg!("Account index: {}", escrow.account_index);
         msg!("Native SOL privacy transfers enabled!");
         Ok(())
     }
@@ -71,6 +534,7 @@ pub mod privacy_transfer {
         ctx: Context<Deposit>,
         _amount_hint: u64,  // For logging only, not used in computation
         encrypted_commitment: [u8; 64],
+        cap_proof: Option<Vec<u8>>,  // New balance <= cap (when configured)
     ) -> Result<()> {
         // ============================================
         // INPUT VALIDATION (Checks)
@@ -82,18 +546,31 @@ pub mod privacy_transfer {
             ErrorCode::Unauthorized
         );
         
-        // SECURITY: Validate commitment is not all zeros (would indicate invalid commitment)
+        // SECURITY: Validate commitment is well-formed for the configured commitment scheme
         require!(
-            encrypted_commitment != [0u8; 64],
+            is_valid_commitment(&encrypted_commitment),
             ErrorCode::InvalidCommitment
         );
         
+        let config = Config::load_optional(&ctx.accounts.config)?;
+        
+        // SECURITY: Emergency pause (set by the config authority)
+        require_not_paused!(config.as_ref(), pause::DEPOSITS);
+        
+        // New balance must stay under the per-account cap (if configured)
+        deposit_cap::enforce_if_configured(config.as_ref(), &encrypted_commitment, cap_proof.as_deref())?;
+        
         let account = &mut ctx.accounts.encrypted_account;
         
         // Store the encrypted commitment
         // The actual amount is HIDDEN in the commitment!
         account.encrypted_balance = encrypted_commitment;
         account.version += 1;
+        account.advance_state_hash(&encrypted_commitment);
+        account.record_activity()?;
+        
+        // Protocol statistics (if initialized)
+        stats::record(&ctx.accounts.stats, stats::Activity::Deposit)?;
         
         msg!("Deposit completed - amount is ENCRYPTED");
         msg!("Commitment stored (64 bytes), version: {}", account.version);
@@ -113,16 +590,68 @@ pub mod privacy_transfer {
     /// 2. Account locking during instruction execution
     /// 3. No cross-program reentrancy in same transaction
     /// We follow checks-effects-interactions pattern for additional safety.
+    #[allow(clippy::too_many_arguments)]
     pub fn confidential_transfer(
         ctx: Context<ConfidentialTransfer>,
-        sender_new_commitment: [u8; 64],      // Encrypted new balance
-        recipient_new_commitment: [u8; 64],   // Encrypted new balance
+        sender_new_commitment: PackedCommitment,    // Encrypted new balance (full or compressed)
+        recipient_new_commitment: PackedCommitment, // Encrypted new balance (full or compressed)
+        recipient_seed_owner: Pubkey,          // Recipient account PDA seeds (owner, index)
+        recipient_account_index: u16,
         proof_data: Vec<u8>,                   // ZK proofs (range, equality, validity)
+        memo: Option<EncryptedMemo>,           // Opening of the amount for the recipient
+        travel_rule: Option<TravelRuleEnvelope>, // Originator/beneficiary data for the VASP
+        velocity: Option<VelocityUpdate>,        // Epoch total update (when limits are on)
+        fee: Option<ConfidentialFee>,            // Hidden protocol fee (when a fee is set)
+        expected_sender_version: u64,            // Sender version the proof was built against
+        expected_recipient_version: u64,         // Recipient version the proof was built against
     ) -> Result<()> {
+        // Compressed commitments are expanded (and validated) up front
+        let sender_new_commitment = sender_new_commitment.expand()?;
+        let recipient_new_commitment = recipient_new_commitment.expand()?;
+        
+        // First-time recipient: `init_if_needed` just created the account, so
+        // set it up exactly like initialize_account would (zero commitment)
+        let recipient_is_new = ctx.accounts.recipient_account.owner == Pubkey::default();
+        if recipient_is_new {
+            require!(
+                recipient_seed_owner == ctx.accounts.recipient.key(),
+                ErrorCode::Unauthorized
+            );
+            
+            let account = &mut ctx.accounts.recipient_account;
+            account.owner = ctx.accounts.recipient.key();
+            account.encrypted_balance = [0u8; 64];
+            account.version = 0;
+            account.bump = ctx.bumps.recipient_account;
+            account.account_index = recipient_account_index;
+            account.seed_owner = account.owner;
+            account.pending_owner = Pubkey::default();
+            account.encryption_key = [0u8; 32]; // Registered via rotate_keys
+            account.schema_version = migration::ENCRYPTED_ACCOUNT_SCHEMA_VERSION;
+            account.assets = Vec::new();
+            account.state_hash = [0u8; 32]; // Chain genesis
+            account.frozen = false;
+            
+            stats::record(&ctx.accounts.stats, stats::Activity::AccountOpened)?;
+            
+            msg!("Initialized encrypted account for recipient: {}", account.owner);
+        }
+        
         // ============================================
         // INPUT VALIDATION (Checks)
         // ============================================
         
+        // SECURITY: Optimistic concurrency - the proof was built against these
+        // exact states; a concurrent update would make it stale
+        require!(
+            ctx.accounts.sender_account.version == expected_sender_version,
+            ErrorCode::StaleAccountVersion
+        );
+        require!(
+            ctx.accounts.recipient_account.version == expected_recipient_version,
+            ErrorCode::StaleAccountVersion
+        );
+        
         // SECURITY: Validate sender account ownership first
         require!(
             ctx.accounts.sender_account.owner == ctx.accounts.sender.key(),
@@ -136,11 +665,30 @@ pub mod privacy_transfer {
         );
         
         // SECURITY: Validate sender and recipient are different accounts
+        // (compared by PDA, so an owner can move value between sub-accounts)
         require!(
-            ctx.accounts.sender.key() != ctx.accounts.recipient.key(),
+            ctx.accounts.sender_account.key() != ctx.accounts.recipient_account.key(),
             ErrorCode::InvalidRecipient
         );
         
+        // Deployment policies (pause, travel rule, KYC gate, velocity limit)
+        let config = Config::load_optional(&ctx.accounts.config)?;
+        require_not_paused!(config.as_ref(), pause::TRANSFERS);
+        
+        // Deployment-tuned limits (compiled-in defaults when unset)
+        limits::enforce_transfer_rate(config.as_ref(), &ctx.accounts.stats)?;
+        limits::enforce_proof_size(config.as_ref(), &proof_data)?;
+        limits::enforce_range_bits(config.as_ref(), &proof_data)?;
+        
+        // SECURITY: Permissioned deployments only allow credentialed wallets
+        kyc::enforce_if_configured(
+            config.as_ref(),
+            &ctx.accounts.sender.key(),
+            ctx.accounts.sender_credential.as_deref().map(|c| &**c),
+            &ctx.accounts.recipient.key(),
+            ctx.accounts.recipient_credential.as_deref().map(|c| &**c),
+        )?;
+        
         let sender_account = &mut ctx.accounts.sender_account;
         let recipient_account = &mut ctx.accounts.recipient_account;
         
@@ -164,13 +712,13 @@ pub mod privacy_transfer {
             ErrorCode::InvalidProof
         );
         
-        // Validate commitments are not all zeros (would indicate invalid commitment)
+        // Validate commitments are well-formed for the configured commitment scheme
         require!(
-            sender_new_commitment != [0u8; 64],
+            is_valid_commitment(&sender_new_commitment),
             ErrorCode::InvalidCommitment
         );
         require!(
-            recipient_new_commitment != [0u8; 64],
+            is_valid_commitment(&recipient_new_commitment),
             ErrorCode::InvalidCommitment
         );
         
@@ -180,25 +728,33 @@ pub mod privacy_transfer {
             ErrorCode::InvalidCommitment
         );
         
+        // A memo is encrypted to the recipient's registered key
+        if memo.is_some() {
+            require!(
+                recipient_account.encryption_key != [0u8; 32],
+                ErrorCode::InvalidEncryptionKey
+            );
+        }
+        
+        // Travel rule: hidden amounts always count as above the threshold
+        travel_rule::enforce_for_hidden_amount(config.as_ref(), travel_rule.as_ref())?;
+        
         // ============================================
         // ZK PROOF VERIFICATION
         // ============================================
         //
-        // BPF-Compatible Verification (Solana 4KB stack limit):
         // 1. Basic validation (format, size, non-zero checks) ✅
         // 2. Commitment format validation ✅
-        // 3. Proof structure validation ✅
-        // 4. Transcript structure validation ✅
-        //
-        // NOTE: Full cryptographic verification (elliptic curve operations,
-        // scalar arithmetic, multi-scalar multiplication) is NOT performed
-        // on-chain due to Solana's 4KB stack limit. Full verification should
-        // be done off-chain or using a compute-efficient approach.
+        // 3. Range proof structure validation ✅
+        // 4. Balance equations, checked with the curve25519 syscalls ✅
         
         // Get old commitments for verification
         let sender_old_commitment = sender_account.encrypted_balance;
         let recipient_old_commitment = recipient_account.encrypted_balance;
         
+        // A hidden fee also leaves the sender's balance (see fees)
+        let transfer_sender_old = fees::transfer_sender_old(&sender_old_commitment, fee.as_ref())?;
+        
         // SECURITY: Extract amount commitment from proof data
         // The amount commitment is embedded in the proof data structure
         // We need to extract it before verification
@@ -210,10 +766,17 @@ pub mod privacy_transfer {
             }
         };
         
-        // SECURITY: Proof verification with strict validation
-        // While full cryptographic verification is not performed on-chain due to
-        // Solana's 4KB stack limit, we perform strict structural validation to
-        // reject invalid proof data and ensure proof data integrity.
+        // SECURITY: A new account starts from zero, so its balance must be
+        // exactly the incoming amount
+        if recipient_is_new {
+            require!(
+                recipient_new_commitment == amount_commitment,
+                ErrorCode::InvalidCommitment
+            );
+        }
+        
+        // SECURITY: Proof verification: the equality proofs are checked
+        // against the balance differences under the transcript challenge
         // 
         // REENTRANCY PROTECTION: Solana's runtime prevents reentrancy attacks by:
         // 1. Single-threaded execution model
@@ -221,31 +784,92 @@ pub mod privacy_transfer {
         // 3. No cross-program reentrancy in same transaction
         // However, we validate all inputs before state changes to follow
         // checks-effects-interactions pattern for additional safety.
-        match verify_transfer_proof(
-            &proof_data,
-            &amount_commitment,      // FIXED: Correct amount commitment extracted from proof
-            &sender_new_commitment,  // Correct: Sender after commitment
+        //
+        // The proof goes to a whitelisted external verifier when one is passed,
+        // otherwise to the built-in verifier, unless the config pins one
+        // (see `verifier`)
+        verifier::verify_transfer(
+            config.as_ref(),
+            ctx.accounts.verifier_program.as_ref().map(|v| v.as_ref()),
+            &verifier::TransferStatement {
+                amount_commitment: &amount_commitment,
+                sender_old: &transfer_sender_old,
+                sender_new: &sender_new_commitment,
+                recipient_old: &recipient_old_commitment,
+                recipient_new: &recipient_new_commitment,
+                proof_data: &proof_data,
+            },
+        )?;
+        msg!("✅ Proof verification passed");
+        
+        // Per-epoch velocity limit (if configured)
+        velocity::enforce_if_configured(
+            config.as_ref(),
+            ctx.accounts.sender_accumulator.as_deref_mut().map(|a| &mut **a),
+            velocity.as_ref(),
+            &amount_commitment,
+        )?;
+        
+        // Protocol fee (if configured): hidden, credited to the shielded treasury
+        fees::enforce_confidential(
+            config.as_ref(),
+            &ctx.accounts.sender.key(),
+            ctx.accounts.shielded_treasury.as_deref_mut(),
+            fee.as_ref(),
+            &amount_commitment,
             &sender_old_commitment,
-            &recipient_old_commitment,
-            &recipient_new_commitment,
-        ) {
-            Ok(_) => {
-                msg!("✅ Proof verification passed (BPF-compatible strict validation)");
-            }
-            Err(e) => {
-                // SECURITY: Reject invalid proofs - this is critical for security
-                msg!("⚠️  Proof verification error: {:?}", e);
-                return Err(ErrorCode::InvalidProof.into());
-            }
-        }
+            &sender_new_commitment,
+        )?;
         
         // Update encrypted balances
         // The actual transfer amount is HIDDEN in these commitments!
         sender_account.encrypted_balance = sender_new_commitment;
         sender_account.version += 1;
+        sender_account.advance_state_hash(&sender_new_commitment);
+        sender_account.record_activity()?;
         
         recipient_account.encrypted_balance = recipient_new_commitment;
         recipient_account.version += 1;
+        recipient_account.advance_state_hash(&recipient_new_commitment);
+        
+        // Record the notice for wallet discovery (id = recipient's new state hash,
+        // which is unique per update and says nothing about the sender)
+        if let Some(inbox) = ctx.accounts.recipient_inbox.as_mut() {
+            let note = memo.clone().ok_or(ErrorCode::InvalidMessage)?;
+            inbox.push(InboxEntry {
+                id: recipient_account.state_hash,
+                note,
+                slot: Clock::get()?.slot,
+            })?;
+        }
+        
+        if let Some(memo) = memo {
+            emit!(TransferMemo {
+                recipient_account: recipient_account.key(),
+                encryption_key: recipient_account.encryption_key,
+                recipient_version: recipient_account.version,
+                memo,
+            });
+        }
+        
+        if let Some(envelope) = travel_rule {
+            emit!(TravelRuleData {
+                sender_account: sender_account.key(),
+                recipient_account: recipient_account.key(),
+                sender_version: sender_account.version,
+                envelope,
+            });
+        }
+        
+        if let (Some(fee), Some(treasury)) = (fee, ctx.accounts.shielded_treasury.as_ref()) {
+            emit!(FeeMemo {
+                treasury_version: treasury.version,
+                memo: fee.memo,
+            });
+        }
+        
+        // Protocol statistics (if initialized)
+        stats::record(&ctx.accounts.stats, stats::Activity::Transfer)?;
         
         msg!("✅ Confidential transfer completed");
         msg!("   Sender version: {}", sender_account.version);
@@ -256,140 +880,498 @@ pub mod privacy_transfer {
         Ok(())
     }
 
-    /// Withdraw funds (convert encrypted to plaintext)
-    /// 
-    /// SECURITY: This function implements input validation and overflow protection.
-    pub fn withdraw(
-        ctx: Context<Withdraw>,
-        _amount_hint: u64,  // For logging only
-        new_commitment: [u8; 64],
+    /// Two-stage transfer, stage A: verify the range proofs and record the
+    /// result in a PendingVerification PDA
+    ///
+    /// For proofs whose full verification does not fit one transaction.
+    /// verify_stage_b finishes the check and performs the transfer; until
+    /// then the sender can drop the PDA with cancel_verification. Proofs too
+    /// large for the transaction are uploaded to the sender's proof buffer
+    /// first and passed with an empty `proof_data`. Uses the
+    /// built-in verifier; transfers that need a memo, travel rule data,
+    /// velocity update, fee or KYC credential go through confidential_transfer.
+    pub fn verify_stage_a(
+        ctx: Context<VerifyStageA>,
+        sender_new_commitment: [u8; 64],
+        recipient_new_commitment: [u8; 64],
+        proof_data: Vec<u8>,
+        expected_sender_version: u64,
+        expected_recipient_version: u64,
     ) -> Result<()> {
         // ============================================
         // INPUT VALIDATION (Checks)
         // ============================================
         
-        // SECURITY: Verify the account owner
+        // SECURITY: Optimistic concurrency - stage B re-checks these versions
         require!(
-            ctx.accounts.encrypted_account.owner == ctx.accounts.owner.key(),
-            ErrorCode::Unauthorized
+            ctx.accounts.sender_account.version == expected_sender_version,
+            ErrorCode::StaleAccountVersion
         );
-        
-        // SECURITY: Validate commitment is not all zeros
         require!(
-            new_commitment != [0u8; 64],
-            ErrorCode::InvalidCommitment
+            ctx.accounts.recipient_account.version == expected_recipient_version,
+            ErrorCode::StaleAccountVersion
         );
         
-        let account = &mut ctx.accounts.encrypted_account;
+        // SECURITY: Validate sender account ownership first
+        require!(
+            ctx.accounts.sender_account.owner == ctx.accounts.sender.key(),
+            ErrorCode::Unauthorized
+        );
         
-        // Update encrypted balance
-        account.encrypted_balance = new_commitment;
-        account.version += 1;
+        // SECURITY: Recipient must be an initialized account of another owner
+        require!(
+            ctx.accounts.recipient_account.owner != Pubkey::default(),
+            ErrorCode::Unauthorized
+        );
+        require!(
+            ctx.accounts.sender_account.owner != ctx.accounts.recipient_account.owner,
+            ErrorCode::InvalidRecipient
+        );
         
-        msg!("Withdraw completed - new encrypted balance stored");
-        msg!("Version: {}", account.version);
+        let config = Config::load_optional(&ctx.accounts.config)?;
+        require_not_paused!(config.as_ref(), pause::TRANSFERS);
         
-        Ok(())
-    }
-
-    /// Deposit native SOL into escrow with encrypted commitment
-    pub fn deposit_sol(
-        ctx: Context<DepositSOL>,
-        amount: u64,
-        encrypted_commitment: [u8; 64],
-    ) -> Result<()> {
-        // ============================================
-        // INPUT VALIDATION
-        // ============================================
+        // Large proofs are read in place from the sender's proof buffer
+        let proof_buffer = ctx.accounts.proof_buffer.as_ref().map(|buffer| buffer.load()).transpose()?;
+        let proof_data = ProofBuffer::select(proof_buffer.as_deref(), &proof_data)?;
+        limits::enforce_proof_size(config.as_ref(), proof_data)?;
+        limits::enforce_range_bits(config.as_ref(), proof_data)?;
         
-        // Validate amount (prevent overflow and invalid amounts)
+        // Validate commitments are well-formed for the configured commitment scheme
         require!(
-            amount >= transfer_constants::MIN_AMOUNT,
-            ErrorCode::InvalidAmount
+            is_valid_commitment(&sender_new_commitment),
+            ErrorCode::InvalidCommitment
         );
         require!(
-            amount <= transfer_constants::MAX_AMOUNT,
-            ErrorCode::InvalidAmount
+            is_valid_commitment(&recipient_new_commitment),
+            ErrorCode::InvalidCommitment
         );
         
-        // Validate commitment is not all zeros (would indicate invalid commitment)
+        let sender_old_commitment = ctx.accounts.sender_account.encrypted_balance;
+        let recipient_old_commitment = ctx.accounts.recipient_account.encrypted_balance;
         require!(
-            encrypted_commitment != [0u8; 64],
+            sender_old_commitment != [0u8; 64],
             ErrorCode::InvalidCommitment
         );
         
-        // Transfer SOL from user to escrow PDA
-        let cpi_context = CpiContext::new(
-            ctx.accounts.system_program.to_account_info(),
-            Transfer {
-                from: ctx.accounts.owner.to_account_info(),
-                to: ctx.accounts.sol_escrow.to_account_info(),
-            },
-        );
-        transfer(cpi_context, amount)?;
+        // ============================================
+        // ZK PROOF VERIFICATION (stage A)
+        // ============================================
         
-        // Update escrow balance
-        let escrow = &mut ctx.accounts.sol_escrow;
-        escrow.balance = escrow.balance.checked_add(amount)
-            .ok_or(ErrorCode::Overflow)?;
+        let amount_commitment = proof_verification::extract_amount_commitment(proof_data)
+            .map_err(|_| ErrorCode::InvalidProof)?;
+        let stage_a = match verify_transfer_stage_a(
+            proof_data,
+            &amount_commitment,
+            &sender_new_commitment,
+            &sender_old_commitment,
+            &recipient_old_commitment,
+            &recipient_new_commitment,
+        ) {
+            Ok(stage_a) => stage_a,
+            Err(e) => {
+                msg!("⚠️  Proof verification error (stage A): {:?}", e);
+                return Err(ErrorCode::InvalidProof.into());
+            }
+        };
         
-        // Update encrypted commitment
-        let account = &mut ctx.accounts.encrypted_account;
-        account.encrypted_balance = encrypted_commitment;
-        account.version += 1;
+        // ============================================
+        // STATE UPDATES (Effects)
+        // ============================================
         
-        msg!("✅ SOL Deposit completed");
-        msg!("   ❌ AMOUNT IS HIDDEN - Not visible in logs!");
-        msg!("   Escrow balance: {} lamports", escrow.balance);
-        msg!("   Commitment version: {}", account.version);
+        let pending = &mut ctx.accounts.pending_verification;
+        pending.sender = ctx.accounts.sender.key();
+        pending.sender_account = ctx.accounts.sender_account.key();
+        pending.recipient_account = ctx.accounts.recipient_account.key();
+        pending.sender_version = expected_sender_version;
+        pending.recipient_version = expected_recipient_version;
+        pending.statement_hash = stage_a.statement_hash;
+        pending.challenge = stage_a.challenge;
+        pending.slot = Clock::get()?.slot;
+        pending.bump = ctx.bumps.pending_verification;
         
+        msg!("✅ Transfer proof stage A passed");
         Ok(())
     }
 
-    /// Withdraw native SOL from escrow
-    pub fn withdraw_sol(
-        ctx: Context<WithdrawSOL>,
-        amount: u64,
-        new_commitment: [u8; 64],
+    /// Two-stage transfer, stage B: finish verification and transfer
+    ///
+    /// `proof_data` (or the proof buffer) and the commitments must be those
+    /// stage A verified.
+    pub fn verify_stage_b(
+        ctx: Context<VerifyStageB>,
+        sender_new_commitment: [u8; 64],
+        recipient_new_commitment: [u8; 64],
+        proof_data: Vec<u8>,
     ) -> Result<()> {
         // ============================================
-        // INPUT VALIDATION
+        // INPUT VALIDATION (Checks)
         // ============================================
         
-        // Validate amount (prevent overflow and invalid amounts)
+        // SECURITY: Neither account may have changed since stage A
+        let pending = &ctx.accounts.pending_verification;
         require!(
-            amount >= transfer_constants::MIN_AMOUNT,
-            ErrorCode::InvalidAmount
+            ctx.accounts.sender_account.version == pending.sender_version
+                && ctx.accounts.recipient_account.version == pending.recipient_version,
+            ErrorCode::StaleAccountVersion
         );
-        require!(
-            amount <= transfer_constants::MAX_AMOUNT,
-            ErrorCode::InvalidAmount
+        
+        // Deployment policies; those needing extra data fail here when enabled
+        let config = Config::load_optional(&ctx.accounts.config)?;
+        require_not_paused!(config.as_ref(), pause::TRANSFERS);
+        limits::enforce_transfer_rate(config.as_ref(), &ctx.accounts.stats)?;
+        kyc::enforce_if_configured(
+            config.as_ref(),
+            &ctx.accounts.sender.key(),
+            None,
+            &ctx.accounts.recipient_account.owner,
+            None,
+        )?;
+        travel_rule::enforce_for_hidden_amount(config.as_ref(), None)?;
+        
+        // ============================================
+        // ZK PROOF VERIFICATION (stage B)
+        // ============================================
+        
+        let proof_buffer = ctx.accounts.proof_buffer.as_ref().map(|buffer| buffer.load()).transpose()?;
+        let proof_data = ProofBuffer::select(proof_buffer.as_deref(), &proof_data)?;
+        let sender_old_commitment = ctx.accounts.sender_account.encrypted_balance;
+        let recipient_old_commitment = ctx.accounts.recipient_account.encrypted_balance;
+        let amount_commitment = proof_verification::extract_amount_commitment(proof_data)
+            .map_err(|_| ErrorCode::InvalidProof)?;
+        if let Err(e) = verify_transfer_stage_b(
+            proof_data,
+            &amount_commitment,
+            &sender_new_commitment,
+            &sender_old_commitment,
+            &recipient_old_commitment,
+            &recipient_new_commitment,
+            &TransferStageA {
+                statement_hash: pending.statement_hash,
+                challenge: pending.challenge,
+            },
+        ) {
+            msg!("⚠️  Proof verification error (stage B): {:?}", e);
+            return Err(ErrorCode::InvalidProof.into());
+        }
+        
+        velocity::enforce_if_configured(config.as_ref(), None, None, &amount_commitment)?;
+        fees::enforce_confidential(
+            config.as_ref(),
+            &ctx.accounts.sender.key(),
+            None,
+            None,
+            &amount_commitment,
+            &sender_old_commitment,
+            &sender_new_commitment,
+        )?;
+        
+        // ============================================
+        // STATE UPDATES (Effects)
+        // ============================================
+        
+        let sender_account = &mut ctx.accounts.sender_account;
+        sender_account.encrypted_balance = sender_new_commitment;
+        sender_account.version += 1;
+        sender_account.advance_state_hash(&sender_new_commitment);
+        sender_account.record_activity()?;
+        
+        let recipient_account = &mut ctx.accounts.recipient_account;
+        recipient_account.encrypted_balance = recipient_new_commitment;
+        recipient_account.version += 1;
+        recipient_account.advance_state_hash(&recipient_new_commitment);
+        
+        // Protocol statistics (if initialized)
+        stats::record(&ctx.accounts.stats, stats::Activity::Transfer)?;
+        
+        msg!("✅ Confidential transfer completed (two-stage)");
+        msg!("   Sender version: {}", ctx.accounts.sender_account.version);
+        msg!("   Recipient version: {}", ctx.accounts.recipient_account.version);
+        msg!("   ❌ AMOUNT IS HIDDEN - Not visible on Solana Explorer!");
+        
+        Ok(())
+    }
+
+    /// Drop an unfinished two-stage verification and reclaim its rent
+    pub fn cancel_verification(_ctx: Context<CancelVerification>) -> Result<()> {
+        msg!("Two-stage verification cancelled");
+        Ok(())
+    }
+
+    /// Withdraw funds (convert encrypted to plaintext)
+    /// 
+    /// SECURITY: This function implements input validation and overflow protection.
+    pub fn withdraw(
+        ctx: Context<Withdraw>,
+        _amount_hint: u64,  // For logging only
+        new_commitment: [u8; 64],
+    ) -> Result<()> {
+        // ============================================
+        // INPUT VALIDATION (Checks)
+        // ============================================
+        
+        // SECURITY: Emergency pause and circuit breaker (set by the config authority)
+        let config = Config::load_optional(&ctx.accounts.config)?;
+        require_not_paused!(config.as_ref(), pause::WITHDRAWALS);
+        limits::enforce_transfer_rate(config.as_ref(), &ctx.accounts.stats)?;
+        
+        // SECURITY: Verify the account owner
+        require!(
+            ctx.accounts.encrypted_account.owner == ctx.accounts.owner.key(),
+            ErrorCode::Unauthorized
+        );
+        
+        // SECURITY: Validate commitment is well-formed for the configured commitment scheme
+        require!(
+            is_valid_commitment(&new_commitment),
+            ErrorCode::InvalidCommitment
+        );
+        
+        let account = &mut ctx.accounts.encrypted_account;
+        
+        // Update encrypted balance
+        account.encrypted_balance = new_commitment;
+        account.version += 1;
+        account.advance_state_hash(&new_commitment);
+        account.record_activity()?;
+        
+        // Protocol statistics (if initialized)
+        stats::record(&ctx.accounts.stats, stats::Activity::Withdrawal)?;
+        
+        msg!("Withdraw completed - new encrypted balance stored");
+        msg!("Version: {}", account.version);
+        
+        Ok(())
+    }
+
+    /// Deposit native SOL into escrow with encrypted commitment
+    ///
+    /// `proof_data` proves the new commitment is the old one plus a
+    /// commitment to exactly `amount`, so the hidden balance always matches
+    /// the lamports actually received.
+    pub fn deposit_sol(
+        ctx: Context<DepositSOL>,
+        amount: u64,
+        encrypted_commitment: [u8; 64],
+        proof_data: Vec<u8>,
+        exclusion_proof: Option<Vec<u8>>,  // Deny-list non-membership (when configured)
+        cap_proof: Option<Vec<u8>>,        // New balance <= cap (when configured)
+    ) -> Result<()> {
+        // ============================================
+        // INPUT VALIDATION
+        // ============================================
+        
+        // Validate amount (prevent overflow and invalid amounts)
+        require!(
+            amount >= transfer_constants::MIN_AMOUNT,
+            ErrorCode::InvalidAmount
+        );
+        require!(
+            amount <= transfer_constants::MAX_AMOUNT,
+            ErrorCode::InvalidAmount
+        );
+        
+        // Validate proof data size (DoS protection)
+        require!(
+            proof_data.len() >= proof_constants::MIN_PROOF_DATA_SIZE,
+            ErrorCode::InvalidProof
+        );
+        require!(
+            proof_data.len() <= proof_constants::MAX_PROOF_DATA_SIZE,
+            ErrorCode::InvalidProof
+        );
+        
+        // Validate commitment is well-formed for the configured commitment scheme
+        require!(
+            is_valid_commitment(&encrypted_commitment),
+            ErrorCode::InvalidCommitment
+        );
+        
+        // ============================================
+        // ZK PROOF VERIFICATION
+        // ============================================
+        
+        // SECURITY: new = old + amount*G + r*H, so the hidden balance grows by
+        // exactly the lamports/tokens received and cannot be inflated
+        let old_commitment = ctx.accounts.encrypted_account.encrypted_balance;
+        if let Err(e) = verify_deposit_proof(&proof_data, amount, &old_commitment, &encrypted_commitment) {
+            msg!("⚠️  Proof verification error: {:?}", e);
+            return Err(ErrorCode::InvalidProof.into());
+        }
+        
+        let config = Config::load_optional(&ctx.accounts.config)?;
+        
+        // SECURITY: Emergency pause (set by the config authority)
+        require_not_paused!(config.as_ref(), pause::DEPOSITS);
+        
+        // Deployment-tuned limits (compiled-in defaults when unset)
+        limits::enforce_amount(config.as_ref(), amount)?;
+        limits::enforce_proof_size(config.as_ref(), &proof_data)?;
+        
+        // COMPLIANCE: Depositing wallet must not be on the deny list (if configured)
+        deny_list::enforce_if_configured(
+            config.as_ref(),
+            &ctx.accounts.owner.key(),
+            exclusion_proof.as_deref(),
+            ctx.accounts.exclusion_verifying_key.as_deref().map(|vk| &**vk),
+        )?;
+        
+        // New balance must stay under the per-account cap (if configured)
+        deposit_cap::enforce_if_configured(config.as_ref(), &encrypted_commitment, cap_proof.as_deref())?;
+        
+        // Transfer SOL from user to escrow PDA
+        let cpi_context = CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.owner.to_account_info(),
+                to: ctx.accounts.sol_escrow.to_account_info(),
+            },
+        );
+        transfer(cpi_context, amount)?;
+        
+        // Update escrow balance
+        let escrow = &mut ctx.accounts.sol_escrow;
+        escrow.balance = escrow.balance.checked_add(amount)
+            .ok_or(ErrorCode::Overflow)?;
+        
+        // Update encrypted commitment
+        let account = &mut ctx.accounts.encrypted_account;
+        account.encrypted_balance = encrypted_commitment;
+        account.version += 1;
+        account.advance_state_hash(&encrypted_commitment);
+        account.record_activity()?;
+        
+        // Protocol statistics (if initialized)
+        stats::record(&ctx.accounts.stats, stats::Activity::Deposit)?;
+        
+        msg!("✅ SOL Deposit completed");
+        msg!("   ❌ AMOUNT IS HIDDEN - Not visible in logs!");
+        msg!("   Escrow balance: {} lamports", escrow.balance);
+        msg!("   Commitment version: {}", account.version);
+        
+        Ok(())
+    }
+
+    /// Withdraw native SOL from escrow to any destination
+    ///
+    /// Partial withdrawals are allowed. The equality proof ties the new
+    /// commitment to old - amount; the payout itself is bounded by the
+    /// plaintext escrow balance. The remainder range proof is only checked
+    /// structurally on-chain, so it does not by itself stop the hidden
+    /// balance from going negative.
+    /// The destination pubkey is absorbed into the withdrawal proof's
+    /// transcript, so the proof cannot be replayed towards another wallet.
+    pub fn withdraw_sol(
+        ctx: Context<WithdrawSOL>,
+        amount: u64,
+        new_commitment: [u8; 64],
+        proof_data: Vec<u8>,
+        exclusion_proof: Option<Vec<u8>>,  // Deny-list non-membership (when configured)
+    ) -> Result<()> {
+        // ============================================
+        // INPUT VALIDATION
+        // ============================================
+        
+        // Validate amount (prevent overflow and invalid amounts)
+        require!(
+            amount >= transfer_constants::MIN_AMOUNT,
+            ErrorCode::InvalidAmount
+        );
+        require!(
+            amount <= transfer_constants::MAX_AMOUNT,
+            ErrorCode::InvalidAmount
+        );
+        
+        // Validate proof data size (DoS protection)
+        require!(
+            proof_data.len() >= proof_constants::MIN_PROOF_DATA_SIZE,
+            ErrorCode::InvalidProof
+        );
+        require!(
+            proof_data.len() <= proof_constants::MAX_PROOF_DATA_SIZE,
+            ErrorCode::InvalidProof
         );
         
-        // Validate commitment is not all zeros (would indicate invalid commitment)
+        // Validate commitment is well-formed for the configured commitment scheme
         require!(
-            new_commitment != [0u8; 64],
+            is_valid_commitment(&new_commitment),
             ErrorCode::InvalidCommitment
         );
         
+        // ============================================
+        // ZK PROOF VERIFICATION
+        // ============================================
+        
+        // SECURITY: The commitment moves by exactly `amount`; the proof is
+        // bound to this destination (no front-running swap)
+        let destination = ctx.accounts.destination.key();
+        let old_commitment = ctx.accounts.encrypted_account.encrypted_balance;
+        if let Err(e) = verify_withdraw_to_proof(
+            &proof_data,
+            amount,
+            &old_commitment,
+            &new_commitment,
+            &destination.to_bytes(),
+        ) {
+            msg!("⚠️  Proof verification error: {:?}", e);
+            return Err(ErrorCode::InvalidProof.into());
+        }
+        
         // ============================================
         // BALANCE VERIFICATION
         // ============================================
         
-        // Verify sufficient balance in escrow
+        // SECURITY: Lamports paid out are bounded here, not by the
+        // (structural) remainder range proof
         require!(
             ctx.accounts.sol_escrow.balance >= amount,
             ErrorCode::InsufficientBalance
         );
         
+        // SECURITY: Receipt tokens must stay fully backed (burn them first)
+        require!(
+            ctx.accounts.sol_escrow.balance - amount >= ctx.accounts.sol_escrow.receipts_outstanding,
+            ErrorCode::ReceiptsOutstanding
+        );
+        
+        let config = Config::load_optional(&ctx.accounts.config)?;
+        
+        // SECURITY: Emergency pause and circuit breaker (set by the config authority)
+        require_not_paused!(config.as_ref(), pause::WITHDRAWALS);
+        limits::enforce_transfer_rate(config.as_ref(), &ctx.accounts.stats)?;
+        
+        // Deployment-tuned limits (compiled-in defaults when unset)
+        limits::enforce_amount(config.as_ref(), amount)?;
+        limits::enforce_proof_size(config.as_ref(), &proof_data)?;
+        
+        // COMPLIANCE: Destination must be cleared by the sanctions oracle and
+        // excluded from the deny list (each only if configured)
+        sanctions::enforce_if_configured(
+            config.as_ref(),
+            ctx.accounts.sanctions_attestation.as_deref(),
+            &destination,
+        )?;
+        deny_list::enforce_if_configured(
+            config.as_ref(),
+            &destination,
+            exclusion_proof.as_deref(),
+            ctx.accounts.exclusion_verifying_key.as_deref().map(|vk| &**vk),
+        )?;
+        
+        // Protocol fee (if configured) comes out of the withdrawn amount
+        let fee = fees::fee_for(config.as_ref(), &ctx.accounts.owner.key(), amount)?;
+        
         // Get bump before borrowing
+        // Signer seeds use the derivation key, which survives ownership transfers
         let bump = ctx.accounts.sol_escrow.bump;
-        let owner_key = ctx.accounts.owner.key();
+        let owner_key = ctx.accounts.sol_escrow.seed_owner;
+        let index_bytes = ctx.accounts.sol_escrow.account_index.to_le_bytes();
         
-        // Transfer SOL from escrow to user
+        // Transfer SOL from escrow to the destination
         let seeds = &[
-            b"sol-escrow",
+            pda::SOL_ESCROW_SEED,
             owner_key.as_ref(),
+            index_bytes.as_ref(),
             &[bump],
         ];
         let signer_seeds = &[&seeds[..]];
@@ -398,11 +1380,29 @@ pub mod privacy_transfer {
             ctx.accounts.system_program.to_account_info(),
             Transfer {
                 from: ctx.accounts.sol_escrow.to_account_info(),
-                to: ctx.accounts.owner.to_account_info(),
+                to: ctx.accounts.destination.to_account_info(),
             },
             signer_seeds,
         );
-        transfer(cpi_context, amount)?;
+        transfer(cpi_context, amount - fee)?;
+        
+        if fee > 0 {
+            let treasury = ctx.accounts.treasury.as_mut().ok_or(ErrorCode::TreasuryRequired)?;
+            
+            // Program-owned escrow and treasury: move lamports directly
+            let escrow_info = ctx.accounts.sol_escrow.to_account_info();
+            let treasury_info = treasury.to_account_info();
+            let new_escrow_lamports = escrow_info.lamports().checked_sub(fee)
+                .ok_or(ErrorCode::Underflow)?;
+            let new_treasury_lamports = treasury_info.lamports().checked_add(fee)
+                .ok_or(ErrorCode::Overflow)?;
+            **escrow_info.try_borrow_mut_lamports()? = new_escrow_lamports;
+            **treasury_info.try_borrow_mut_lamports()? = new_treasury_lamports;
+            
+            treasury.total_collected = treasury.total_collected.checked_add(fee)
+                .ok_or(ErrorCode::Overflow)?;
+            msg!("   Protocol fee: {} lamports", fee);
+        }
         
         // Update escrow balance
         let escrow = &mut ctx.accounts.sol_escrow;
@@ -415,6 +1415,11 @@ pub mod privacy_transfer {
         let account = &mut ctx.accounts.encrypted_account;
         account.encrypted_balance = new_commitment;
         account.version += 1;
+        account.advance_state_hash(&new_commitment);
+        account.record_activity()?;
+        
+        // Protocol statistics (if initialized)
+        stats::record(&ctx.accounts.stats, stats::Activity::Withdrawal)?;
         
         msg!("✅ SOL Withdrawal completed");
         msg!("   ❌ AMOUNT IS HIDDEN - Not visible in logs!");
@@ -426,6 +1431,10 @@ pub mod privacy_transfer {
 
     /// Confidential SOL transfer between escrows
     /// 
+    /// PRIVACY: `amount` is public in the instruction data, since it moves
+    /// lamports between per-user escrows. Use `confidential_pool_transfer`
+    /// for pool-backed SOL where no plaintext amount appears.
+    /// 
     /// SECURITY: This function implements comprehensive input validation,
     /// proof verification, overflow protection, and safe lamport manipulation.
     /// 
@@ -436,14 +1445,28 @@ pub mod privacy_transfer {
         sender_new_commitment: [u8; 64],
         recipient_new_commitment: [u8; 64],
         proof_data: Vec<u8>,
+        expected_sender_version: u64,
+        expected_recipient_version: u64,
     ) -> Result<()> {
         // ============================================
         // INPUT VALIDATION (Checks)
         // ============================================
         
+        // SECURITY: Optimistic concurrency - the proof was built against these
+        // exact states; a concurrent update would make it stale
+        require!(
+            ctx.accounts.sender_account.version == expected_sender_version,
+            ErrorCode::StaleAccountVersion
+        );
+        require!(
+            ctx.accounts.recipient_account.version == expected_recipient_version,
+            ErrorCode::StaleAccountVersion
+        );
+        
         // SECURITY: Validate sender and recipient are different accounts
+        // (compared by PDA, so an owner can move value between sub-accounts)
         require!(
-            ctx.accounts.sender.key() != ctx.accounts.recipient.key(),
+            ctx.accounts.sender_account.key() != ctx.accounts.recipient_account.key(),
             ErrorCode::InvalidRecipient
         );
         
@@ -459,6 +1482,11 @@ pub mod privacy_transfer {
             ErrorCode::Unauthorized
         );
         
+        // Emergency pause and circuit breaker (if configured)
+        let config = Config::load_optional(&ctx.accounts.config)?;
+        require_not_paused!(config.as_ref(), pause::TRANSFERS);
+        limits::enforce_transfer_rate(config.as_ref(), &ctx.accounts.stats)?;
+        
         // ============================================
         // COMPREHENSIVE INPUT VALIDATION
         // ============================================
@@ -473,13 +1501,13 @@ pub mod privacy_transfer {
             ErrorCode::InvalidAmount
         );
         
-        // Validate commitments are not all zeros (would indicate invalid commitment)
+        // Validate commitments are well-formed for the configured commitment scheme
         require!(
-            sender_new_commitment != [0u8; 64],
+            is_valid_commitment(&sender_new_commitment),
             ErrorCode::InvalidCommitment
         );
         require!(
-            recipient_new_commitment != [0u8; 64],
+            is_valid_commitment(&recipient_new_commitment),
             ErrorCode::InvalidCommitment
         );
         
@@ -509,6 +1537,12 @@ pub mod privacy_transfer {
             ErrorCode::InsufficientBalance
         );
         
+        // SECURITY: Sender's receipt tokens must stay fully backed
+        require!(
+            ctx.accounts.sender_escrow.balance - amount >= ctx.accounts.sender_escrow.receipts_outstanding,
+            ErrorCode::ReceiptsOutstanding
+        );
+        
         // ============================================
         // ZK PROOF VERIFICATION
         // ============================================
@@ -546,6 +1580,25 @@ pub mod privacy_transfer {
             }
         }
         
+        // Protocol fee (if configured) is paid on top from the sender's wallet,
+        // so both escrows keep matching their commitments
+        let fee = fees::fee_for(config.as_ref(), &ctx.accounts.sender.key(), amount)?;
+        if fee > 0 {
+            let treasury = ctx.accounts.treasury.as_mut().ok_or(ErrorCode::TreasuryRequired)?;
+            let cpi_context = CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.sender.to_account_info(),
+                    to: treasury.to_account_info(),
+                },
+            );
+            transfer(cpi_context, fee)?;
+            
+            treasury.total_collected = treasury.total_collected.checked_add(fee)
+                .ok_or(ErrorCode::Overflow)?;
+            msg!("   Protocol fee: {} lamports", fee);
+        }
+        
         // Get bump before borrowing
         let _sender_bump = ctx.accounts.sender_escrow.bump;
         let _sender_key = ctx.accounts.sender.key();
@@ -590,10 +1643,15 @@ pub mod privacy_transfer {
         let sender_account = &mut ctx.accounts.sender_account;
         sender_account.encrypted_balance = sender_new_commitment;
         sender_account.version += 1;
+        sender_account.advance_state_hash(&sender_new_commitment);
         
         let recipient_account = &mut ctx.accounts.recipient_account;
         recipient_account.encrypted_balance = recipient_new_commitment;
         recipient_account.version += 1;
+        recipient_account.advance_state_hash(&recipient_new_commitment);
+        
+        // Protocol statistics (if initialized)
+        stats::record(&ctx.accounts.stats, stats::Activity::Transfer)?;
         
         msg!("✅ Confidential SOL transfer completed");
         msg!("   ❌ AMOUNT IS HIDDEN - Not visible in logs!");
@@ -604,210 +1662,14325 @@ pub mod privacy_transfer {
         
         Ok(())
     }
-}
-
-#[derive(Accounts)]
-pub struct InitializeAccount<'info> {
-    #[account(
-        init,
-        payer = owner,
-        space = 8 + EncryptedAccount::INIT_SPACE,
-        seeds = [b"encrypted-account", owner.key().as_ref()],
-        bump
-    )]
-    pub encrypted_account: Account<'info, EncryptedAccount>,
-    
-    #[account(mut)]
-    pub owner: Signer<'info>,
-    
-    pub system_program: Program<'info, System>,
-}
 
-#[derive(Accounts)]
-pub struct InitializeSolEscrow<'info> {
-    #[account(
-        init,
-        payer = owner,
-        space = 8 + SolEscrow::INIT_SPACE,
-        seeds = [b"sol-escrow", owner.key().as_ref()],
-        bump
-    )]
-    pub sol_escrow: Account<'info, SolEscrow>,
-    
-    #[account(mut)]
-    pub owner: Signer<'info>,
-    
-    pub system_program: Program<'info, System>,
-}
+    /// Propose a new owner for an encrypted account and its SOL escrow
+    ///
+    /// First step of a two-step ownership transfer (wallet migration).
+    /// Nothing changes until the proposed owner calls `accept_owner`.
+    /// Proposing `Pubkey::default()` cancels a pending proposal.
+    pub fn propose_owner(
+        ctx: Context<ProposeOwner>,
+        new_owner: Pubkey,
+        auth_code: Option<[u8; 32]>,
+    ) -> Result<()> {
+        // SECURITY: Reject no-op proposals
+        require!(
+            new_owner != ctx.accounts.owner.key(),
+            ErrorCode::InvalidNewOwner
+        );
+        
+        // SECURITY: Second factor, if the owner configured an auth chain
+        auth_chain::enforce_if_configured(&ctx.accounts.auth_chain, auth_code)?;
+        
+        ctx.accounts.encrypted_account.pending_owner = new_owner;
+        ctx.accounts.sol_escrow.pending_owner = new_owner;
+        
+        if new_owner == Pubkey::default() {
+            msg!("Pending ownership transfer cancelled");
+        } else {
+            msg!("Proposed new owner: {}", new_owner);
+        }
+        
+        ctx.accounts.encrypted_account.record_activity()?;
+        
+        Ok(())
+    }
 
-#[derive(Accounts)]
-pub struct Deposit<'info> {
-    #[account(
-        mut,
-        seeds = [b"encrypted-account", owner.key().as_ref()],
+    /// Accept a pending ownership transfer
+    ///
+    /// Second step of the two-step ownership transfer. The PDA addresses are
+    /// not re-derived: only the `owner` field moves to the new key.
+    ///
+    /// The auth chain stays with the account, so when one is configured the
+    /// previous owner hands the next code to the new key with the migration.
+    pub fn accept_owner(ctx: Context<AcceptOwner>, auth_code: Option<[u8; 32]>) -> Result<()> {
+        let new_owner = ctx.accounts.new_owner.key();
+        
+        // SECURITY: Both accounts must name the signer as pending owner
+        require!(
+            ctx.accounts.encrypted_account.pending_owner != Pubkey::default(),
+            ErrorCode::InvalidNewOwner
+        );
+        require!(
+            ctx.accounts.encrypted_account.pending_owner == new_owner,
+            ErrorCode::Unauthorized
+        );
+        require!(
+            ctx.accounts.sol_escrow.pending_owner == new_owner,
+            ErrorCode::Unauthorized
+        );
+        
+        // SECURITY: Second factor, if the account has an auth chain
+        auth_chain::enforce_if_configured(&ctx.accounts.auth_chain, auth_code)?;
+        
+        let account = &mut ctx.accounts.encrypted_account;
+        let previous_owner = account.owner;
+        account.owner = new_owner;
+        account.pending_owner = Pubkey::default();
+        account.record_activity()?;
+        
+        let escrow = &mut ctx.accounts.sol_escrow;
+        escrow.owner = new_owner;
+        escrow.pending_owner = Pubkey::default();
+        
+        msg!("Ownership transferred: {} -> {}", previous_owner, new_owner);
+        
+        Ok(())
+    }
+
+    /// Rotate the blinding factor and encryption key of an encrypted account
+    ///
+    /// The owner supplies a re-randomized commitment to the same hidden balance
+    /// plus an equality proof that the balance is unchanged, so long-lived
+    /// accounts can refresh their randomness without revealing the amount.
+    pub fn rotate_keys(
+        ctx: Context<RotateKeys>,
+        new_commitment: [u8; 64],
+        new_encryption_key: [u8; 32],
+        proof_data: Vec<u8>,
+        auth_code: Option<[u8; 32]>,
+    ) -> Result<()> {
+        // ============================================
+        // INPUT VALIDATION (Checks)
+        // ============================================
+        
+        // SECURITY: Validate account ownership
+        require!(
+            ctx.accounts.encrypted_account.owner == ctx.accounts.owner.key(),
+            ErrorCode::Unauthorized
+        );
+        
+        // SECURITY: Validate commitment is well-formed for the configured commitment scheme
+        require!(
+            is_valid_commitment(&new_commitment),
+            ErrorCode::InvalidCommitment
+        );
+        
+        // SECURITY: Validate encryption key is not all zeros
+        require!(
+            new_encryption_key != [0u8; 32],
+            ErrorCode::InvalidEncryptionKey
+        );
+        
+        // SECURITY: Second factor, if the owner configured an auth chain
+        auth_chain::enforce_if_configured(&ctx.accounts.auth_chain, auth_code)?;
+        
+        let account = &mut ctx.accounts.encrypted_account;
+        
+        // Nothing to rotate on an uninitialized balance
+        require!(
+            account.encrypted_balance != [0u8; 64],
+            ErrorCode::InvalidCommitment
+        );
+        
+        // ============================================
+        // ZK PROOF VERIFICATION
+        // ============================================
+        
+        if let Err(e) = verify_rotation_proof(&proof_data, &account.encrypted_balance, &new_commitment) {
+            msg!("⚠️  Rotation proof verification error: {:?}", e);
+            return Err(ErrorCode::InvalidProof.into());
+        }
+        
+        account.encrypted_balance = new_commitment;
+        account.encryption_key = new_encryption_key;
+        account.version += 1;
+        account.advance_state_hash(&new_commitment);
+        account.record_activity()?;
+        
+        msg!("✅ Keys rotated - balance unchanged and still HIDDEN");
+        msg!("   Commitment version: {}", account.version);
+        
+        Ok(())
+    }
+
+    /// Upgrade an EncryptedAccount or SolEscrow to the current layout in place
+    ///
+    /// The account is read raw because its layout may predate the current
+    /// struct definition. Any growth in rent is paid by the owner.
+    pub fn migrate_account(ctx: Context<MigrateAccount>) -> Result<()> {
+        let account_info = ctx.accounts.account.to_account_info();
+        
+        let (schema_offset, current_version, steps) = {
+            let data = account_info.try_borrow_data()?;
+            require!(data.len() > migration::OWNER_OFFSET + 32, ErrorCode::UnsupportedSchemaVersion);
+            
+            // SECURITY: Only the account's owner may migrate it
+            let owner = Pubkey::try_from(&data[migration::OWNER_OFFSET..migration::OWNER_OFFSET + 32])
+                .map_err(|_| ErrorCode::UnsupportedSchemaVersion)?;
+            require!(
+                owner == ctx.accounts.owner.key(),
+                ErrorCode::Unauthorized
+            );
+            
+            if data.starts_with(EncryptedAccount::DISCRIMINATOR) {
+                (
+                    migration::ENCRYPTED_ACCOUNT_SCHEMA_OFFSET,
+                    migration::ENCRYPTED_ACCOUNT_SCHEMA_VERSION,
+                    migration::ENCRYPTED_ACCOUNT_MIGRATIONS,
+                )
+            } else if data.starts_with(SolEscrow::DISCRIMINATOR) {
+                (
+                    migration::SOL_ESCROW_SCHEMA_OFFSET,
+                    migration::SOL_ESCROW_SCHEMA_VERSION,
+                    migration::SOL_ESCROW_MIGRATIONS,
+                )
+            } else {
+                return Err(ErrorCode::UnsupportedSchemaVersion.into());
+            }
+        };
+        
+        let version = migration::migrate_in_place(
+            &account_info,
+            &ctx.accounts.owner.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+            schema_offset,
+            current_version,
+            steps,
+        )?;
+        
+        msg!("✅ Account migrated to schema version {}", version);
+        
+        Ok(())
+    }
+
+    /// Move a legacy EncryptedAccount into the current layout
+    ///
+    /// Legacy accounts have no schema byte and live at [prefix, owner], so
+    /// `migrate_account` cannot reach them. The balance commitment and its
+    /// version carry over to a new account at `account_index`; the legacy
+    /// account is closed and its rent refunded to the owner.
+    pub fn migrate_legacy_account(ctx: Context<MigrateLegacyAccount>, account_index: u16) -> Result<()> {
+        let legacy_info = ctx.accounts.legacy_account.to_account_info();
+        let legacy = {
+            let data = legacy_info.try_borrow_data()?;
+            require!(
+                data.len() == account_decoder::LEGACY_ENCRYPTED_ACCOUNT_LEN,
+                ErrorCode::UnsupportedSchemaVersion
+            );
+            account_decoder::decode_encrypted_account(&data)
+                .map_err(|_| ErrorCode::UnsupportedSchemaVersion)?
+        };
+        
+        // SECURITY: Only the account's owner may migrate it
+        require!(
+            legacy.owner == ctx.accounts.owner.key(),
+            ErrorCode::Unauthorized
+        );
+        
+        let account = &mut ctx.accounts.encrypted_account;
+        account.owner = legacy.owner;
+        account.encrypted_balance = legacy.encrypted_balance;
+        account.version = legacy.version;
+        account.bump = ctx.bumps.encrypted_account;
+        account.account_index = account_index;
+        account.seed_owner = legacy.owner;
+        account.pending_owner = Pubkey::default();
+        account.encryption_key = [0u8; 32]; // Registered via rotate_keys
+        account.schema_version = migration::ENCRYPTED_ACCOUNT_SCHEMA_VERSION;
+        account.assets = Vec::new();
+        account.state_hash = [0u8; 32]; // Chain genesis
+        account.frozen = false;
+        account.record_activity()?;
+        
+        migration::close_legacy(&legacy_info, &ctx.accounts.owner.to_account_info())?;
+        
+        msg!("✅ Legacy account migrated to index {}", account_index);
+        msg!("   Commitment version: {}", account.version);
+        
+        Ok(())
+    }
+
+    /// Move a legacy SolEscrow into the current layout
+    ///
+    /// Counterpart of `migrate_legacy_account`: the escrowed lamports and
+    /// balance move to a new escrow at `account_index` (pair it with the
+    /// migrated EncryptedAccount of the same index); the legacy escrow's
+    /// rent is refunded to the owner.
+    pub fn migrate_legacy_escrow(ctx: Context<MigrateLegacyEscrow>, account_index: u16) -> Result<()> {
+        let legacy_info = ctx.accounts.legacy_escrow.to_account_info();
+        let legacy = {
+            let data = legacy_info.try_borrow_data()?;
+            require!(
+                data.len() == account_decoder::LEGACY_SOL_ESCROW_LEN,
+                ErrorCode::UnsupportedSchemaVersion
+            );
+            account_decoder::decode_sol_escrow(&data)
+                .map_err(|_| ErrorCode::UnsupportedSchemaVersion)?
+        };
+        
+        // SECURITY: Only the escrow's owner may migrate it
+        require!(
+            legacy.owner == ctx.accounts.owner.key(),
+            ErrorCode::Unauthorized
+        );
+        
+        // Program-owned escrows: move the escrowed lamports directly
+        let escrow_info = ctx.accounts.sol_escrow.to_account_info();
+        let new_legacy_lamports = legacy_info.lamports().checked_sub(legacy.balance)
+            .ok_or(ErrorCode::Underflow)?;
+        let new_escrow_lamports = escrow_info.lamports().checked_add(legacy.balance)
+            .ok_or(ErrorCode::Overflow)?;
+        **legacy_info.try_borrow_mut_lamports()? = new_legacy_lamports;
+        **escrow_info.try_borrow_mut_lamports()? = new_escrow_lamports;
+        
+        let escrow = &mut ctx.accounts.sol_escrow;
+        escrow.owner = legacy.owner;
+        escrow.balance = legacy.balance;
+        escrow.bump = ctx.bumps.sol_escrow;
+        escrow.account_index = account_index;
+        escrow.seed_owner = legacy.owner;
+        escrow.pending_owner = Pubkey::default();
+        escrow.schema_version = migration::SOL_ESCROW_SCHEMA_VERSION;
+        escrow.receipts_outstanding = 0;
+        
+        migration::close_legacy(&legacy_info, &ctx.accounts.owner.to_account_info())?;
+        
+        msg!("✅ Legacy escrow migrated to index {}", account_index);
+        msg!("   Escrowed: {} lamports", escrow.balance);
+        
+        Ok(())
+    }
+
+    /// Resize an EncryptedAccount to `new_len` bytes via realloc
+    ///
+    /// Growing reserves zeroed space past the current layout (up to
+    /// `migration::MAX_RESERVED_BYTES`), so optional fields can be added to
+    /// existing accounts without closing and recreating them; `add_asset`
+    /// grows into the reserve. Shrinking releases it. The owner pays or is
+    /// refunded the rent difference.
+    pub fn resize_account(ctx: Context<ResizeAccount>, new_len: u32) -> Result<()> {
+        let used_len = EncryptedAccount::space_for(ctx.accounts.encrypted_account.assets.len());
+        
+        // SECURITY: Reserved bytes must read as zero (default values) once a
+        // layout extends into them
+        let account_info = ctx.accounts.encrypted_account.to_account_info();
+        account_info.try_borrow_mut_data()?[used_len..].fill(0);
+        
+        ctx.accounts.encrypted_account.record_activity()?;
+        
+        msg!("✅ Account resized to {} bytes", new_len);
+        msg!("   Reserved: {} bytes", new_len as usize - used_len);
+        
+        Ok(())
+    }
+
+    /// Initialize a zero-copy proof buffer for chunked proof upload
+    ///
+    /// Proofs that do not fit in a single transaction are written in chunks
+    /// and read in place through `AccountLoader`, avoiding stack-based
+    /// deserialization of multi-kilobyte accounts.
+    pub fn initialize_proof_buffer(ctx: Context<InitializeProofBuffer>) -> Result<()> {
+        let mut buffer = ctx.accounts.proof_buffer.load_init()?;
+        buffer.owner = ctx.accounts.owner.key();
+        buffer.data_len = 0;
+        buffer.bump = ctx.bumps.proof_buffer;
+        
+        msg!("Initialized proof buffer for owner: {}", buffer.owner);
+        Ok(())
+    }
+
+    /// Write a chunk of proof data into the proof buffer at `offset`
+    pub fn write_proof_buffer(
+        ctx: Context<WriteProofBuffer>,
+        offset: u32,
+        chunk: Vec<u8>,
+    ) -> Result<()> {
+        let mut buffer = ctx.accounts.proof_buffer.load_mut()?;
+        
+        // SECURITY: Validate buffer ownership
+        require!(
+            buffer.owner == ctx.accounts.owner.key(),
+            ErrorCode::Unauthorized
+        );
+        
+        // SECURITY: Validate chunk bounds (DoS protection)
+        let start = offset as usize;
+        let end = start.checked_add(chunk.len())
+            .ok_or(ErrorCode::Overflow)?;
+        require!(
+            !chunk.is_empty() && end <= proof_constants::MAX_PROOF_DATA_SIZE,
+            ErrorCode::InvalidProof
+        );
+        
+        buffer.data[start..end].copy_from_slice(&chunk);
+        buffer.data_len = buffer.data_len.max(end as u32);
+        
+        msg!("Proof buffer chunk written: {} bytes at offset {}", chunk.len(), offset);
+        msg!("   Buffered proof data: {} bytes", buffer.data_len);
+        
+        Ok(())
+    }
+
+    /// Close the proof buffer and reclaim its rent
+    pub fn close_proof_buffer(ctx: Context<CloseProofBuffer>) -> Result<()> {
+        let buffer = ctx.accounts.proof_buffer.load()?;
+        
+        // SECURITY: Validate buffer ownership
+        require!(
+            buffer.owner == ctx.accounts.owner.key(),
+            ErrorCode::Unauthorized
+        );
+        
+        msg!("Closed proof buffer for owner: {}", buffer.owner);
+        Ok(())
+    }
+
+    /// Initialize a token escrow for confidential SPL token transfers
+    ///
+    /// Creates one escrow state PDA per (owner, mint) holding the encrypted
+    /// commitment, plus a token-account PDA (vault) that holds the tokens.
+    /// Works with both the Token and Token-2022 programs.
+    pub fn initialize_token_escrow(ctx: Context<InitializeTokenEscrow>) -> Result<()> {
+        let escrow = &mut ctx.accounts.token_escrow;
+        escrow.owner = ctx.accounts.owner.key();
+        escrow.mint = ctx.accounts.mint.key();
+        escrow.encrypted_balance = [0u8; 64]; // Zero commitment initially
+        escrow.balance = 0;
+        escrow.version = 0;
+        escrow.bump = ctx.bumps.token_escrow;
+        escrow.vault_bump = ctx.bumps.token_vault;
+        
+        // Protocol statistics (if initialized)
+        stats::record(&ctx.accounts.stats, stats::Activity::EscrowOpened)?;
+        
+        msg!("Initialized token escrow for owner: {}", escrow.owner);
+        msg!("   Mint: {}", escrow.mint);
+        msg!("Confidential token transfers enabled!");
+        Ok(())
+    }
+
+    /// Deposit SPL tokens into the token escrow with encrypted commitment
+    ///
+    /// For Token-2022 mints with a transfer hook, pass the hook's extra
+    /// accounts as remaining accounts (same for every token-escrow transfer).
+    pub fn deposit_token<'info>(
+        ctx: Context<'_, '_, '_, 'info, DepositToken<'info>>,
+        amount: u64,
+        encrypted_commitment: [u8; 64],
+        proof_data: Vec<u8>,
+    ) -> Result<()> {
+        // ============================================
+        // INPUT VALIDATION
+        // ============================================
+        
+        // SECURITY: Emergency pause and subsystem rollout (set by the config authority)
+        require_not_paused!(Some(&*ctx.accounts.config), pause::DEPOSITS);
+        require_feature!(Some(&*ctx.accounts.config), features::TOKEN_TRANSFERS);
+        
+        // Validate amount (prevent overflow and invalid amounts)
+        require!(
+            amount >= transfer_constants::MIN_AMOUNT,
+            ErrorCode::InvalidAmount
+        );
+        require!(
+            amount <= transfer_constants::MAX_AMOUNT,
+            ErrorCode::InvalidAmount
+        );
+        
+        // Validate proof data size (DoS protection)
+        require!(
+            proof_data.len() >= proof_constants::MIN_PROOF_DATA_SIZE,
+            ErrorCode::InvalidProof
+        );
+        require!(
+            proof_data.len() <= proof_constants::MAX_PROOF_DATA_SIZE,
+            ErrorCode::InvalidProof
+        );
+        
+        // Validate commitment is well-formed for the configured commitment scheme
+        require!(
+            is_valid_commitment(&encrypted_commitment),
+            ErrorCode::InvalidCommitment
+        );
+        
+        // ============================================
+        // ZK PROOF VERIFICATION
+        // ============================================
+        
+        // SECURITY: new = old + amount*G + r*H, so the hidden balance grows by
+        // exactly the lamports/tokens received and cannot be inflated
+        let old_commitment = ctx.accounts.token_escrow.encrypted_balance;
+        if let Err(e) = verify_deposit_proof(&proof_data, amount, &old_commitment, &encrypted_commitment) {
+            msg!("⚠️  Proof verification error: {:?}", e);
+            return Err(ErrorCode::InvalidProof.into());
+        }
+        
+        // SECURITY: Only vetted mints when the allowlist is enforced
+        ctx.accounts.config.check_mint(
+            ctx.accounts.mint_config.as_deref(),
+            &ctx.accounts.mint,
+            amount,
+        )?;
+        
+        // Transfer tokens from user to escrow vault
+        let cpi_context = CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            TransferChecked {
+                from: ctx.accounts.user_token_account.to_account_info(),
+                mint: ctx.accounts.mint.to_account_info(),
+                to: ctx.accounts.token_vault.to_account_info(),
+                authority: ctx.accounts.owner.to_account_info(),
+            },
+        );
+        transfer_checked_with_hooks(
+            cpi_context.with_remaining_accounts(ctx.remaining_accounts.to_vec()),
+            amount,
+            ctx.accounts.mint.decimals,
+        )?;
+        
+        // Update escrow balance and encrypted commitment
+        let escrow = &mut ctx.accounts.token_escrow;
+        escrow.balance = escrow.balance.checked_add(amount)
+            .ok_or(ErrorCode::Overflow)?;
+        escrow.encrypted_balance = encrypted_commitment;
+        escrow.version += 1;
+        
+        // Protocol statistics (if initialized)
+        stats::record(&ctx.accounts.stats, stats::Activity::Deposit)?;
+        
+        msg!("✅ Token Deposit completed");
+        msg!("   ❌ AMOUNT IS HIDDEN - Not visible in logs!");
+        msg!("   Escrow balance: {} base units", escrow.balance);
+        msg!("   Commitment version: {}", escrow.version);
+        
+        Ok(())
+    }
+
+    /// Withdraw SPL tokens from the token escrow
+    pub fn withdraw_token<'info>(
+        ctx: Context<'_, '_, '_, 'info, WithdrawToken<'info>>,
+        amount: u64,
+        new_commitment: [u8; 64],
+        proof_data: Vec<u8>,
+    ) -> Result<()> {
+        // ============================================
+        // INPUT VALIDATION
+        // ============================================
+        
+        // Validate amount (prevent overflow and invalid amounts)
+        require!(
+            amount >= transfer_constants::MIN_AMOUNT,
+            ErrorCode::InvalidAmount
+        );
+        require!(
+            amount <= transfer_constants::MAX_AMOUNT,
+            ErrorCode::InvalidAmount
+        );
+        
+        // Validate proof data size (DoS protection)
+        require!(
+            proof_data.len() >= proof_constants::MIN_PROOF_DATA_SIZE,
+            ErrorCode::InvalidProof
+        );
+        require!(
+            proof_data.len() <= proof_constants::MAX_PROOF_DATA_SIZE,
+            ErrorCode::InvalidProof
+        );
+        
+        // Validate commitment is well-formed for the configured commitment scheme
+        require!(
+            is_valid_commitment(&new_commitment),
+            ErrorCode::InvalidCommitment
+        );
+        
+        // ============================================
+        // ZK PROOF VERIFICATION
+        // ============================================
+        
+        // SECURITY: old - new must open to exactly `amount`, keeping the
+        // plaintext escrow balance and the commitment in sync
+        let old_commitment = ctx.accounts.token_escrow.encrypted_balance;
+        if let Err(e) = verify_withdraw_proof(&proof_data, amount, &old_commitment, &new_commitment) {
+            msg!("⚠️  Proof verification error: {:?}", e);
+            return Err(ErrorCode::InvalidProof.into());
+        }
+        
+        // ============================================
+        // BALANCE VERIFICATION
+        // ============================================
+        
+        // Verify sufficient balance in escrow
+        require!(
+            ctx.accounts.token_escrow.balance >= amount,
+            ErrorCode::InsufficientBalance
+        );
+        
+        let config = Config::load_optional(&ctx.accounts.config)?;
+        
+        // SECURITY: Emergency pause and subsystem rollout (set by the config authority)
+        require_not_paused!(config.as_ref(), pause::WITHDRAWALS);
+        limits::enforce_transfer_rate(config.as_ref(), &ctx.accounts.stats)?;
+        require_feature!(config.as_ref(), features::TOKEN_TRANSFERS);
+        
+        // Deployment-tuned limits (compiled-in defaults when unset)
+        limits::enforce_amount(config.as_ref(), amount)?;
+        limits::enforce_proof_size(config.as_ref(), &proof_data)?;
+        
+        // COMPLIANCE: Destination wallet must be cleared by the sanctions oracle (if configured)
+        sanctions::enforce_if_configured(
+            config.as_ref(),
+            ctx.accounts.sanctions_attestation.as_deref(),
+            &ctx.accounts.user_token_account.owner,
+        )?;
+        
+        // Transfer tokens from vault to user (escrow PDA signs as vault authority)
+        let owner_key = ctx.accounts.token_escrow.owner;
+        let mint_key = ctx.accounts.token_escrow.mint;
+        let bump = ctx.accounts.token_escrow.bump;
+        let seeds = &[
+            pda::TOKEN_ESCROW_SEED,
+            owner_key.as_ref(),
+            mint_key.as_ref(),
+            &[bump],
+        ];
+        let signer_seeds = &[&seeds[..]];
+        
+        let cpi_context = CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            TransferChecked {
+                from: ctx.accounts.token_vault.to_account_info(),
+                mint: ctx.accounts.mint.to_account_info(),
+                to: ctx.accounts.user_token_account.to_account_info(),
+                authority: ctx.accounts.token_escrow.to_account_info(),
+            },
+            signer_seeds,
+        );
+        transfer_checked_with_hooks(
+            cpi_context.with_remaining_accounts(ctx.remaining_accounts.to_vec()),
+            amount,
+            ctx.accounts.mint.decimals,
+        )?;
+        
+        // Update escrow balance and encrypted commitment
+        let escrow = &mut ctx.accounts.token_escrow;
+        escrow.balance = escrow.balance.checked_sub(amount)
+            .ok_or(ErrorCode::Underflow)?;
+        escrow.encrypted_balance = new_commitment;
+        escrow.version += 1;
+        
+        // Protocol statistics (if initialized)
+        stats::record(&ctx.accounts.stats, stats::Activity::Withdrawal)?;
+        
+        msg!("✅ Token Withdrawal completed");
+        msg!("   ❌ AMOUNT IS HIDDEN - Not visible in logs!");
+        msg!("   Remaining escrow: {} base units", escrow.balance);
+        msg!("   Commitment version: {}", escrow.version);
+        
+        Ok(())
+    }
+
+    /// Deposit native SOL into a wSOL token escrow, wrapping it atomically
+    ///
+    /// Lamports go straight into the escrow vault (a native-mint token
+    /// account) and `sync_native` credits them as wSOL, so SOL users share the
+    /// token-escrow code path without wrapping client-side.
+    pub fn deposit_sol_wrapped(
+        ctx: Context<DepositSolWrapped>,
+        amount: u64,
+        encrypted_commitment: [u8; 64],
+        proof_data: Vec<u8>,
+    ) -> Result<()> {
+        // SECURITY: Emergency pause (set by the config authority)
+        let config = Config::load_optional(&ctx.accounts.config)?;
+        require_not_paused!(config.as_ref(), pause::DEPOSITS);
+        
+        // ============================================
+        // INPUT VALIDATION
+        // ============================================
+        
+        // Validate amount (prevent overflow and invalid amounts)
+        require!(
+            amount >= transfer_constants::MIN_AMOUNT,
+            ErrorCode::InvalidAmount
+        );
+        require!(
+            amount <= transfer_constants::MAX_AMOUNT,
+            ErrorCode::InvalidAmount
+        );
+        
+        // Validate proof data size (DoS protection)
+        require!(
+            proof_data.len() >= proof_constants::MIN_PROOF_DATA_SIZE,
+            ErrorCode::InvalidProof
+        );
+        require!(
+            proof_data.len() <= proof_constants::MAX_PROOF_DATA_SIZE,
+            ErrorCode::InvalidProof
+        );
+        
+        // Validate commitment is well-formed for the configured commitment scheme
+        require!(
+            is_valid_commitment(&encrypted_commitment),
+            ErrorCode::InvalidCommitment
+        );
+        
+        // ============================================
+        // ZK PROOF VERIFICATION
+        // ============================================
+        
+        // SECURITY: new = old + amount*G + r*H, so the hidden balance grows by
+        // exactly the lamports/tokens received and cannot be inflated
+        let old_commitment = ctx.accounts.token_escrow.encrypted_balance;
+        if let Err(e) = verify_deposit_proof(&proof_data, amount, &old_commitment, &encrypted_commitment) {
+            msg!("⚠️  Proof verification error: {:?}", e);
+            return Err(ErrorCode::InvalidProof.into());
+        }
+        
+        // Move lamports into the vault, then credit them as wSOL
+        let cpi_context = CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.owner.to_account_info(),
+                to: ctx.accounts.token_vault.to_account_info(),
+            },
+        );
+        transfer(cpi_context, amount)?;
+        
+        sync_native(CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            SyncNative {
+                account: ctx.accounts.token_vault.to_account_info(),
+            },
+        ))?;
+        
+        // Update escrow balance and encrypted commitment
+        let escrow = &mut ctx.accounts.token_escrow;
+        escrow.balance = escrow.balance.checked_add(amount)
+            .ok_or(ErrorCode::Overflow)?;
+        escrow.encrypted_balance = encrypted_commitment;
+        escrow.version += 1;
+        
+        msg!("✅ SOL Deposit completed (wrapped to wSOL)");
+        msg!("   ❌ AMOUNT IS HIDDEN - Not visible in logs!");
+        msg!("   Commitment version: {}", escrow.version);
+        
+        Ok(())
+    }
+
+    /// Withdraw from a wSOL token escrow as native SOL
+    ///
+    /// The wSOL is moved into `user_wsol_account` (a temporary native-mint
+    /// account owned by the caller), which is then closed so its full lamport
+    /// balance is returned to the owner as native SOL.
+    pub fn withdraw_sol_unwrapped(
+        ctx: Context<WithdrawSolUnwrapped>,
+        amount: u64,
+        new_commitment: [u8; 64],
+        proof_data: Vec<u8>,
+    ) -> Result<()> {
+        // SECURITY: Emergency pause and circuit breaker (set by the config authority)
+        let config = Config::load_optional(&ctx.accounts.config)?;
+        require_not_paused!(config.as_ref(), pause::WITHDRAWALS);
+        limits::enforce_transfer_rate(config.as_ref(), &ctx.accounts.stats)?;
+        
+        // ============================================
+        // INPUT VALIDATION
+        // ============================================
+        
+        // Validate amount (prevent overflow and invalid amounts)
+        require!(
+            amount >= transfer_constants::MIN_AMOUNT,
+            ErrorCode::InvalidAmount
+        );
+        require!(
+            amount <= transfer_constants::MAX_AMOUNT,
+            ErrorCode::InvalidAmount
+        );
+        
+        // Validate proof data size (DoS protection)
+        require!(
+            proof_data.len() >= proof_constants::MIN_PROOF_DATA_SIZE,
+            ErrorCode::InvalidProof
+        );
+        require!(
+            proof_data.len() <= proof_constants::MAX_PROOF_DATA_SIZE,
+            ErrorCode::InvalidProof
+        );
+        
+        // Validate commitment is well-formed for the configured commitment scheme
+        require!(
+            is_valid_commitment(&new_commitment),
+            ErrorCode::InvalidCommitment
+        );
+        
+        // ============================================
+        // ZK PROOF VERIFICATION
+        // ============================================
+        
+        // SECURITY: old - new must open to exactly `amount`, keeping the
+        // plaintext escrow balance and the commitment in sync
+        let old_commitment = ctx.accounts.token_escrow.encrypted_balance;
+        if let Err(e) = verify_withdraw_proof(&proof_data, amount, &old_commitment, &new_commitment) {
+            msg!("⚠️  Proof verification error: {:?}", e);
+            return Err(ErrorCode::InvalidProof.into());
+        }
+        
+        // Verify sufficient balance in escrow
+        require!(
+            ctx.accounts.token_escrow.balance >= amount,
+            ErrorCode::InsufficientBalance
+        );
+        
+        // Transfer wSOL from vault to the temporary account (escrow PDA signs)
+        let owner_key = ctx.accounts.token_escrow.owner;
+        let mint_key = ctx.accounts.token_escrow.mint;
+        let bump = ctx.accounts.token_escrow.bump;
+        let seeds = &[
+            pda::TOKEN_ESCROW_SEED,
+            owner_key.as_ref(),
+            mint_key.as_ref(),
+            &[bump],
+        ];
+        let signer_seeds = &[&seeds[..]];
+        
+        let cpi_context = CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            TransferChecked {
+                from: ctx.accounts.token_vault.to_account_info(),
+                mint: ctx.accounts.mint.to_account_info(),
+                to: ctx.accounts.user_wsol_account.to_account_info(),
+                authority: ctx.accounts.token_escrow.to_account_info(),
+            },
+            signer_seeds,
+        );
+        transfer_checked(cpi_context, amount, ctx.accounts.mint.decimals)?;
+        
+        // Unwrap: closing a native account releases all its lamports
+        close_token_account(CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            CloseTokenAccount {
+                account: ctx.accounts.user_wsol_account.to_account_info(),
+                destination: ctx.accounts.owner.to_account_info(),
+                authority: ctx.accounts.owner.to_account_info(),
+            },
+        ))?;
+        
+        // Update escrow balance and encrypted commitment
+        let escrow = &mut ctx.accounts.token_escrow;
+        escrow.balance = escrow.balance.checked_sub(amount)
+            .ok_or(ErrorCode::Underflow)?;
+        escrow.encrypted_balance = new_commitment;
+        escrow.version += 1;
+        
+        // Protocol statistics (if initialized)
+        stats::record(&ctx.accounts.stats, stats::Activity::Withdrawal)?;
+        
+        msg!("✅ SOL Withdrawal completed (unwrapped from wSOL)");
+        msg!("   ❌ AMOUNT IS HIDDEN - Not visible in logs!");
+        msg!("   Commitment version: {}", escrow.version);
+        
+        Ok(())
+    }
+
+    /// Confidential SPL token transfer between token escrows
+    /// 
+    /// SECURITY: This function implements comprehensive input validation,
+    /// proof verification, and overflow protection.
+    /// 
+    /// REENTRANCY PROTECTION: See confidential_transfer() for documentation.
+    pub fn confidential_token_transfer<'info>(
+        ctx: Context<'_, '_, '_, 'info, ConfidentialTokenTransfer<'info>>,
+        amount: u64,
+        sender_new_commitment: [u8; 64],
+        recipient_new_commitment: [u8; 64],
+        proof_data: Vec<u8>,
+        expected_sender_version: u64,
+        expected_recipient_version: u64,
+    ) -> Result<()> {
+        // ============================================
+        // INPUT VALIDATION (Checks)
+        // ============================================
+        
+        // SECURITY: Emergency pause and subsystem rollout (set by the config authority)
+        require_not_paused!(Some(&*ctx.accounts.config), pause::TRANSFERS);
+        require_feature!(Some(&*ctx.accounts.config), features::TOKEN_TRANSFERS);
+        limits::enforce_transfer_rate(Some(&*ctx.accounts.config), &ctx.accounts.stats)?;
+        
+        // SECURITY: Optimistic concurrency - the proof was built against these
+        // exact states; a concurrent update would make it stale
+        require!(
+            ctx.accounts.sender_escrow.version == expected_sender_version,
+            ErrorCode::StaleAccountVersion
+        );
+        require!(
+            ctx.accounts.recipient_escrow.version == expected_recipient_version,
+            ErrorCode::StaleAccountVersion
+        );
+        
+        // SECURITY: Validate sender and recipient are different escrows
+        // (one per owner and mint, so this also rules out self-transfers)
+        require!(
+            ctx.accounts.sender_escrow.key() != ctx.accounts.recipient_escrow.key(),
+            ErrorCode::InvalidRecipient
+        );
+        
+        // SECURITY: Validate sender escrow ownership
+        require!(
+            ctx.accounts.sender_escrow.owner == ctx.accounts.sender.key(),
+            ErrorCode::Unauthorized
+        );
+        
+        // SECURITY: Validate recipient escrow ownership
+        require!(
+            ctx.accounts.recipient_escrow.owner == ctx.accounts.recipient.key(),
+            ErrorCode::Unauthorized
+        );
+        
+        // ============================================
+        // COMPREHENSIVE INPUT VALIDATION
+        // ============================================
+        
+        // Validate amount (prevent overflow and invalid amounts)
+        require!(
+            amount >= transfer_constants::MIN_AMOUNT,
+            ErrorCode::InvalidAmount
+        );
+        require!(
+            amount <= transfer_constants::MAX_AMOUNT,
+            ErrorCode::InvalidAmount
+        );
+        
+        // SECURITY: Only vetted mints when the allowlist is enforced
+        ctx.accounts.config.check_mint(
+            ctx.accounts.mint_config.as_deref(),
+            &ctx.accounts.mint,
+            amount,
+        )?;
+        
+        // Validate commitments are well-formed for the configured commitment scheme
+        require!(
+            is_valid_commitment(&sender_new_commitment),
+            ErrorCode::InvalidCommitment
+        );
+        require!(
+            is_valid_commitment(&recipient_new_commitment),
+            ErrorCode::InvalidCommitment
+        );
+        
+        // Validate proof data size (DoS protection)
+        require!(
+            proof_data.len() >= proof_constants::MIN_PROOF_DATA_SIZE,
+            ErrorCode::InvalidProof
+        );
+        require!(
+            proof_data.len() <= proof_constants::MAX_PROOF_DATA_SIZE,
+            ErrorCode::InvalidProof
+        );
+        
+        // Validate sender escrow is initialized
+        require!(
+            ctx.accounts.sender_escrow.encrypted_balance != [0u8; 64],
+            ErrorCode::InvalidCommitment
+        );
+        
+        // ============================================
+        // BALANCE VERIFICATION
+        // ============================================
+        
+        // Verify sender has sufficient balance in escrow
+        require!(
+            ctx.accounts.sender_escrow.balance >= amount,
+            ErrorCode::InsufficientBalance
+        );
+        
+        // ============================================
+        // ZK PROOF VERIFICATION
+        // ============================================
+        //
+        // BPF-Compatible Verification (see confidential_transfer() for details)
+        
+        let sender_old_commitment = ctx.accounts.sender_escrow.encrypted_balance;
+        let recipient_old_commitment = ctx.accounts.recipient_escrow.encrypted_balance;
+        
+        // SECURITY: Extract amount commitment from proof data
+        let amount_commitment = match proof_verification::extract_amount_commitment(&proof_data) {
+            Ok(commitment) => commitment,
+            Err(e) => {
+                msg!("⚠️  Failed to extract amount commitment from proof: {:?}", e);
+                return Err(ErrorCode::InvalidProof.into());
+            }
+        };
+        
+        match verify_transfer_proof(
+            &proof_data,
+            &amount_commitment,
+            &sender_new_commitment,
+            &sender_old_commitment,
+            &recipient_old_commitment,
+            &recipient_new_commitment,
+        ) {
+            Ok(_) => {
+                msg!("✅ Proof verification passed (BPF-compatible validation)");
+            }
+            Err(e) => {
+                msg!("⚠️  Proof verification error: {:?}", e);
+                return Err(ErrorCode::InvalidProof.into());
+            }
+        }
+        
+        // Transfer tokens between vaults (sender escrow PDA signs)
+        let sender_key = ctx.accounts.sender_escrow.owner;
+        let mint_key = ctx.accounts.sender_escrow.mint;
+        let bump = ctx.accounts.sender_escrow.bump;
+        let seeds = &[
+            pda::TOKEN_ESCROW_SEED,
+            sender_key.as_ref(),
+            mint_key.as_ref(),
+            &[bump],
+        ];
+        let signer_seeds = &[&seeds[..]];
+        
+        let cpi_context = CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            TransferChecked {
+                from: ctx.accounts.sender_vault.to_account_info(),
+                mint: ctx.accounts.mint.to_account_info(),
+                to: ctx.accounts.recipient_vault.to_account_info(),
+                authority: ctx.accounts.sender_escrow.to_account_info(),
+            },
+            signer_seeds,
+        );
+        transfer_checked_with_hooks(
+            cpi_context.with_remaining_accounts(ctx.remaining_accounts.to_vec()),
+            amount,
+            ctx.accounts.mint.decimals,
+        )?;
+        
+        // Update escrow balances and encrypted commitments
+        let sender_escrow = &mut ctx.accounts.sender_escrow;
+        sender_escrow.balance = sender_escrow.balance.checked_sub(amount)
+            .ok_or(ErrorCode::Underflow)?;
+        sender_escrow.encrypted_balance = sender_new_commitment;
+        sender_escrow.version += 1;
+        
+        let recipient_escrow = &mut ctx.accounts.recipient_escrow;
+        recipient_escrow.balance = recipient_escrow.balance.checked_add(amount)
+            .ok_or(ErrorCode::Overflow)?;
+        recipient_escrow.encrypted_balance = recipient_new_commitment;
+        recipient_escrow.version += 1;
+        
+        // Protocol statistics (if initialized)
+        stats::record(&ctx.accounts.stats, stats::Activity::Transfer)?;
+        
+        msg!("✅ Confidential token transfer completed");
+        msg!("   ❌ AMOUNT IS HIDDEN - Not visible in logs!");
+        msg!("   Sender version: {}", ctx.accounts.sender_escrow.version);
+        msg!("   Recipient version: {}", ctx.accounts.recipient_escrow.version);
+        msg!("   Proof data: {} bytes", proof_data.len());
+        
+        Ok(())
+    }
+
+    /// Initialize the global archive tree for closed accounts
+    ///
+    /// Permissionless: only the Merkle root and frontier are stored on-chain,
+    /// the archival records themselves are emitted as `AccountArchived` events.
+    pub fn initialize_archive_tree(ctx: Context<InitializeArchiveTree>) -> Result<()> {
+        let tree = &mut ctx.accounts.archive_tree;
+        tree.root = merkle_tree::empty_root(archive_constants::ARCHIVE_TREE_DEPTH);
+        tree.next_index = 0;
+        tree.filled_subtrees = [merkle_tree::ZERO_LEAF; archive_constants::ARCHIVE_TREE_DEPTH];
+        tree.bump = ctx.bumps.archive_tree;
+        
+        msg!("Initialized archive tree (depth {})", archive_constants::ARCHIVE_TREE_DEPTH);
+        Ok(())
+    }
+
+    /// Close an encrypted account and reclaim its rent
+    ///
+    /// When `archive` is set, a compact record (final commitment hash, version,
+    /// close slot) is appended to the archive tree first, so historical proofs
+    /// about past balances remain verifiable after the account is gone.
+    pub fn close_account(ctx: Context<CloseAccount>, archive: bool) -> Result<()> {
+        // SECURITY: Validate account ownership
+        require!(
+            ctx.accounts.encrypted_account.owner == ctx.accounts.owner.key(),
+            ErrorCode::Unauthorized
+        );
+        
+        // Protocol statistics (if initialized)
+        stats::record(&ctx.accounts.stats, stats::Activity::AccountClosed)?;
+        
+        if !archive {
+            msg!("Closed encrypted account (not archived)");
+            return Ok(());
+        }
+        
+        let tree: &mut ArchiveTree = ctx.accounts.archive_tree.as_mut()
+            .ok_or(ErrorCode::ArchiveTreeRequired)?;
+        let account = &ctx.accounts.encrypted_account;
+        
+        let commitment_hash = merkle_tree::hash_leaf(&[&account.encrypted_balance]);
+        let close_slot = Clock::get()?.slot;
+        let account_key = account.key();
+        let leaf = merkle_tree::hash_leaf(&[
+            account_key.as_ref(),
+            account.owner.as_ref(),
+            &commitment_hash,
+            &account.version.to_le_bytes(),
+            &close_slot.to_le_bytes(),
+        ]);
+        
+        let (leaf_index, root) = merkle_tree::insert_leaf(
+            &mut tree.filled_subtrees,
+            &mut tree.next_index,
+            leaf,
+        ).map_err(|_| ErrorCode::ArchiveTreeFull)?;
+        tree.root = root;
+        
+        emit!(AccountArchived {
+            account: account_key,
+            owner: account.owner,
+            commitment_hash,
+            version: account.version,
+            close_slot,
+            leaf_index,
+            root,
+        });
+        
+        msg!("Closed encrypted account (archived at leaf {})", leaf_index);
+        Ok(())
+    }
+
+    /// Close an empty SOL escrow and reclaim its rent
+    pub fn close_sol_escrow(ctx: Context<CloseSolEscrow>) -> Result<()> {
+        // SECURITY: Escrow must not hold deposited funds
+        require!(
+            ctx.accounts.sol_escrow.balance == 0,
+            ErrorCode::EscrowNotEmpty
+        );
+        
+        // Protocol statistics (if initialized)
+        stats::record(&ctx.accounts.stats, stats::Activity::EscrowClosed)?;
+        
+        msg!("Closed SOL escrow for owner: {}", ctx.accounts.owner.key());
+        Ok(())
+    }
+
+    /// Monitoring probe: run the verification self-tests and emit the result
+    ///
+    /// Permissionless and cheap (one transcript, one curve syscall). Never
+    /// fails on a bad result, so monitors can read the emitted event.
+    pub fn heartbeat(_ctx: Context<Heartbeat>, nonce: u64) -> Result<()> {
+        let transcript_ok = heartbeat::transcript_self_test();
+        let curve_ok = heartbeat::curve_self_test();
+        
+        emit!(HeartbeatResult {
+            nonce,
+            slot: Clock::get()?.slot,
+            transcript_ok,
+            curve_ok,
+        });
+        
+        if transcript_ok && curve_ok {
+            msg!("💓 Heartbeat {} OK", nonce);
+        } else {
+            msg!("⚠️  Heartbeat {} FAILED (transcript: {}, curve: {})", nonce, transcript_ok, curve_ok);
+        }
+        Ok(())
+    }
+
+    /// Register a canary: a program account that must never change
+    ///
+    /// Operators seed decoy accounts whose keys are never used. Any later
+    /// mutation means an unauthorized write path or a verifier bypass, and
+    /// `check_canary` reports it via a `CanaryTripped` event.
+    pub fn register_canary(ctx: Context<RegisterCanary>) -> Result<()> {
+        let watched = &ctx.accounts.watched_account;
+        
+        // SECURITY: Only program-owned state can be a canary
+        require!(
+            watched.owner == &crate::ID && !watched.data_is_empty(),
+            ErrorCode::InvalidCanary
+        );
+        
+        let canary = &mut ctx.accounts.canary;
+        canary.watched_account = watched.key();
+        canary.snapshot_hash = canary_snapshot(watched)?;
+        canary.snapshot_lamports = watched.lamports();
+        canary.armed_slot = Clock::get()?.slot;
+        canary.tripped = false;
+        canary.bump = ctx.bumps.canary;
+        
+        msg!("🐤 Canary armed on {}", canary.watched_account);
+        Ok(())
+    }
+
+    /// Re-snapshot a canary after an investigated (or intended) change
+    pub fn rearm_canary(ctx: Context<UpdateCanary>) -> Result<()> {
+        let watched = &ctx.accounts.watched_account;
+        let canary = &mut ctx.accounts.canary;
+        canary.snapshot_hash = canary_snapshot(watched)?;
+        canary.snapshot_lamports = watched.lamports();
+        canary.armed_slot = Clock::get()?.slot;
+        canary.tripped = false;
+        
+        msg!("🐤 Canary re-armed on {}", canary.watched_account);
+        Ok(())
+    }
+
+    /// Remove a canary and refund its rent to the config authority
+    pub fn close_canary(_ctx: Context<CloseCanary>) -> Result<()> {
+        msg!("Canary closed");
+        Ok(())
+    }
+
+    /// Compare a canary against its snapshot (permissionless)
+    ///
+    /// Meant to run every slot or so from a keeper. Never fails on a
+    /// mismatch, so monitors can alert on the emitted event.
+    pub fn check_canary(ctx: Context<CheckCanary>) -> Result<()> {
+        let watched = &ctx.accounts.watched_account;
+        let observed_hash = canary_snapshot(watched)?;
+        let observed_lamports = watched.lamports();
+        
+        let canary = &mut ctx.accounts.canary;
+        if observed_hash == canary.snapshot_hash && observed_lamports == canary.snapshot_lamports {
+            msg!("🐤 Canary {} intact", canary.watched_account);
+            return Ok(());
+        }
+        
+        canary.tripped = true;
+        emit!(CanaryTripped {
+            watched_account: canary.watched_account,
+            expected_hash: canary.snapshot_hash,
+            observed_hash,
+            expected_lamports: canary.snapshot_lamports,
+            observed_lamports,
+            armed_slot: canary.armed_slot,
+            slot: Clock::get()?.slot,
+        });
+        
+        msg!("🚨 CANARY TRIPPED: {} changed since slot {}", canary.watched_account, canary.armed_slot);
+        Ok(())
+    }
+
+    /// Create the global config (program upgrade authority only)
+    ///
+    /// The upgrade authority becomes the config authority, which can be
+    /// handed over with `set_config_authority`.
+    pub fn initialize_config(ctx: Context<InitializeConfig>) -> Result<()> {
+        let config = &mut ctx.accounts.config;
+        config.authority = ctx.accounts.authority.key();
+        config.mint_allowlist_enabled = false;
+        config.bump = ctx.bumps.config;
+        config.association_root = [0u8; 32];
+        config.travel_rule_threshold = 0;
+        config.sanctions_oracle = Pubkey::default();
+        config.attestation_max_age = 0;
+        config.epoch_transfer_limit = 0;
+        config.kyc_mint = Pubkey::default();
+        config.kyc_require_recipient = false;
+        config.regulators = Vec::new();
+        config.regulator_threshold = 0;
+        config.disclosure_window = 0;
+        config.deny_list_root = [0u8; 32];
+        config.max_account_balance = 0;
+        config.max_amount = 0;
+        config.min_proof_data_size = 0;
+        config.max_proof_data_size = 0;
+        config.range_bits = 0;
+        config.paused = 0;
+        config.timelock_delay_slots = 0;
+        config.verifier_programs = Vec::new();
+        config.features = features::DEFAULT;
+        config.fee_bps = 0;
+        config.fee_authority = Pubkey::default();
+        config.fee_exempt = Vec::new();
+        config.compliance_authority = Pubkey::default();
+        config.max_transfers_per_slot = 0;
+        config.required_verifier = Pubkey::default();
+        
+        msg!("Initialized config (authority: {})", config.authority);
+        Ok(())
+    }
+
+    /// Hand the config authority over to a new key
+    pub fn set_config_authority(ctx: Context<UpdateConfig>, new_authority: Pubkey) -> Result<()> {
+        // SECURITY: Once a timelock is set, the authority changes only through it
+        require!(
+            ctx.accounts.config.timelock_delay_slots == 0,
+            ErrorCode::TimelockRequired
+        );
+        require!(
+            new_authority != Pubkey::default(),
+            ErrorCode::InvalidNewOwner
+        );
+        
+        ctx.accounts.config.authority = new_authority;
+        
+        msg!("Config authority set to {}", new_authority);
+        Ok(())
+    }
+
+    /// Turn mint allowlist enforcement on or off
+    ///
+    /// While enabled, token deposits and transfers only accept mints with an
+    /// enabled MintConfig entry and amounts within its bounds.
+    pub fn set_mint_allowlist_enabled(ctx: Context<UpdateConfig>, enabled: bool) -> Result<()> {
+        ctx.accounts.config.mint_allowlist_enabled = enabled;
+        
+        msg!("Mint allowlist enforcement: {}", enabled);
+        Ok(())
+    }
+
+    /// Add a mint to the allowlist with per-mint amount bounds
+    ///
+    /// While a config timelock is set, the addition must be queued first
+    /// (`ConfigChange::SupportedMint`) and is applied here once due.
+    pub fn add_supported_mint(
+        ctx: Context<AddSupportedMint>,
+        min_amount: u64,
+        max_amount: u64,
+    ) -> Result<()> {
+        validate_mint_bounds(min_amount, max_amount)?;
+        
+        // SECURITY: Once a timelock is set, allowlist changes must be queued
+        require_queued_change(
+            &ctx.accounts.config,
+            ctx.accounts.pending_change.as_deref(),
+            &ConfigChange::SupportedMint(MintParams {
+                mint: ctx.accounts.mint.key(),
+                min_amount,
+                max_amount,
+                enabled: true,
+            }),
+        )?;
+        
+        let mint_config = &mut ctx.accounts.mint_config;
+        mint_config.mint = ctx.accounts.mint.key();
+        mint_config.decimals = ctx.accounts.mint.decimals;
+        mint_config.min_amount = min_amount;
+        mint_config.max_amount = max_amount;
+        mint_config.enabled = true;
+        mint_config.bump = ctx.bumps.mint_config;
+        
+        msg!("Allowlisted mint {} ({} decimals)", mint_config.mint, mint_config.decimals);
+        msg!("   Amount bounds: {} - {}", min_amount, max_amount);
+        Ok(())
+    }
+
+    /// Update a mint's amount bounds or enable/disable it
+    ///
+    /// Timelocked like `add_supported_mint`.
+    pub fn update_supported_mint(
+        ctx: Context<UpdateSupportedMint>,
+        min_amount: u64,
+        max_amount: u64,
+        enabled: bool,
+    ) -> Result<()> {
+        validate_mint_bounds(min_amount, max_amount)?;
+        
+        // SECURITY: Once a timelock is set, allowlist changes must be queued
+        require_queued_change(
+            &ctx.accounts.config,
+            ctx.accounts.pending_change.as_deref(),
+            &ConfigChange::SupportedMint(MintParams {
+                mint: ctx.accounts.mint_config.mint,
+                min_amount,
+                max_amount,
+                enabled,
+            }),
+        )?;
+        
+        let mint_config = &mut ctx.accounts.mint_config;
+        mint_config.min_amount = min_amount;
+        mint_config.max_amount = max_amount;
+        mint_config.enabled = enabled;
+        
+        msg!("Updated mint {} (enabled: {})", mint_config.mint, enabled);
+        Ok(())
+    }
+
+    /// Remove a mint from the allowlist and reclaim its rent
+    ///
+    /// Timelocked like `add_supported_mint`.
+    pub fn remove_supported_mint(ctx: Context<RemoveSupportedMint>) -> Result<()> {
+        // SECURITY: Once a timelock is set, allowlist changes must be queued
+        require_queued_change(
+            &ctx.accounts.config,
+            ctx.accounts.pending_change.as_deref(),
+            &ConfigChange::RemoveSupportedMint(ctx.accounts.mint_config.mint),
+        )?;
+        
+        msg!("Removed mint {} from allowlist", ctx.accounts.mint_config.mint);
+        Ok(())
+    }
+
+    /// Create the global receipt-token (pSOL) mint
+    ///
+    /// Token-2022 mint with the NonTransferable extension, 9 decimals
+    /// (1 pSOL base unit = 1 lamport), and itself as mint authority.
+    /// Permissionless: its configuration is fixed by the program.
+    pub fn initialize_receipt_mint(ctx: Context<InitializeReceiptMint>) -> Result<()> {
+        let mint_info = ctx.accounts.receipt_mint.to_account_info();
+        let token_program = ctx.accounts.token_program.to_account_info();
+        let bump = ctx.bumps.receipt_mint;
+        let seeds = &[pda::RECEIPT_MINT_SEED, &[bump]];
+        let signer_seeds = &[&seeds[..]];
+        
+        let space = spl_token_2022::extension::ExtensionType::try_calculate_account_len::<
+            spl_token_2022::state::Mint,
+        >(&[spl_token_2022::extension::ExtensionType::NonTransferable])?;
+        
+        create_account(
+            CpiContext::new_with_signer(
+                ctx.accounts.system_program.to_account_info(),
+                CreateAccount {
+                    from: ctx.accounts.payer.to_account_info(),
+                    to: mint_info.clone(),
+                },
+                signer_seeds,
+            ),
+            Rent::get()?.minimum_balance(space),
+            space as u64,
+            &spl_token_2022::ID,
+        )?;
+        
+        // Extensions must be initialized before the mint itself
+        non_transferable_mint_initialize(CpiContext::new(
+            token_program.clone(),
+            NonTransferableMintInitialize {
+                token_program_id: token_program.clone(),
+                mint: mint_info.clone(),
+            },
+        ))?;
+        
+        initialize_mint2(
+            CpiContext::new(token_program, InitializeMint2 { mint: mint_info.clone() }),
+            receipt_constants::RECEIPT_DECIMALS,
+            mint_info.key,
+            None,
+        )?;
+        
+        msg!("Initialized receipt mint: {}", mint_info.key);
+        Ok(())
+    }
+
+    /// Mint non-transferable receipt tokens backed by the SOL escrow
+    ///
+    /// Receipts are minted 1:1 against escrowed lamports that are not yet
+    /// backing receipts. Bundle with `deposit_sol` to receive receipts on
+    /// deposit; external protocols can then check the owner has shielded
+    /// funds without learning how they move inside the pool.
+    pub fn mint_receipt(ctx: Context<ReceiptTokens>, amount: u64) -> Result<()> {
+        require!(
+            amount >= transfer_constants::MIN_AMOUNT,
+            ErrorCode::InvalidAmount
+        );
+        
+        let escrow = &mut ctx.accounts.sol_escrow;
+        let outstanding = escrow.receipts_outstanding.checked_add(amount)
+            .ok_or(ErrorCode::Overflow)?;
+        
+        // SECURITY: Receipts can never exceed escrowed lamports
+        require!(
+            outstanding <= escrow.balance,
+            ErrorCode::InsufficientBalance
+        );
+        escrow.receipts_outstanding = outstanding;
+        
+        let bump = ctx.bumps.receipt_mint;
+        let seeds = &[pda::RECEIPT_MINT_SEED, &[bump]];
+        let signer_seeds = &[&seeds[..]];
+        
+        mint_to(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                MintTo {
+                    mint: ctx.accounts.receipt_mint.to_account_info(),
+                    to: ctx.accounts.receipt_token_account.to_account_info(),
+                    authority: ctx.accounts.receipt_mint.to_account_info(),
+                },
+                signer_seeds,
+            ),
+            amount,
+        )?;
+        
+        msg!("Minted {} receipt units ({} outstanding)", amount, outstanding);
+        Ok(())
+    }
+
+    /// Burn receipt tokens, releasing the escrowed lamports backing them
+    ///
+    /// Bundle with `withdraw_sol` when withdrawing receipted funds.
+    pub fn burn_receipt(ctx: Context<ReceiptTokens>, amount: u64) -> Result<()> {
+        require!(
+            amount >= transfer_constants::MIN_AMOUNT,
+            ErrorCode::InvalidAmount
+        );
+        
+        burn(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                Burn {
+                    mint: ctx.accounts.receipt_mint.to_account_info(),
+                    from: ctx.accounts.receipt_token_account.to_account_info(),
+                    authority: ctx.accounts.owner.to_account_info(),
+                },
+            ),
+            amount,
+        )?;
+        
+        let escrow = &mut ctx.accounts.sol_escrow;
+        escrow.receipts_outstanding = escrow.receipts_outstanding.checked_sub(amount)
+            .ok_or(ErrorCode::Underflow)?;
+        
+        msg!("Burned {} receipt units ({} outstanding)", amount, escrow.receipts_outstanding);
+        Ok(())
+    }
+
+    /// Register a one-time authorization code chain for an encrypted account
+    ///
+    /// `tip` is H^length(seed) for an off-chain secret seed. Once registered,
+    /// sensitive instructions (e.g., rotate_keys) require the next code.
+    pub fn initialize_auth_chain(
+        ctx: Context<InitializeAuthChain>,
+        tip: [u8; 32],
+        length: u32,
+    ) -> Result<()> {
+        // SECURITY: Validate account ownership
+        require!(
+            ctx.accounts.encrypted_account.owner == ctx.accounts.owner.key(),
+            ErrorCode::Unauthorized
+        );
+        require!(tip != [0u8; 32], ErrorCode::InvalidAuthCode);
+        require!(length > 0, ErrorCode::AuthChainExhausted);
+        
+        let chain = &mut ctx.accounts.auth_chain;
+        chain.encrypted_account = ctx.accounts.encrypted_account.key();
+        chain.tip = tip;
+        chain.remaining = length;
+        chain.bump = ctx.bumps.auth_chain;
+        
+        ctx.accounts.encrypted_account.record_activity()?;
+        
+        msg!("Auth chain registered ({} codes)", length);
+        Ok(())
+    }
+
+    /// Replace the auth chain with a fresh one (requires the next current code)
+    pub fn reset_auth_chain(
+        ctx: Context<UpdateAuthChain>,
+        auth_code: [u8; 32],
+        new_tip: [u8; 32],
+        new_length: u32,
+    ) -> Result<()> {
+        require!(new_tip != [0u8; 32], ErrorCode::InvalidAuthCode);
+        require!(new_length > 0, ErrorCode::AuthChainExhausted);
+        
+        let chain = &mut ctx.accounts.auth_chain;
+        auth_chain::consume_code(chain, Some(auth_code))?;
+        chain.tip = new_tip;
+        chain.remaining = new_length;
+        
+        ctx.accounts.encrypted_account.record_activity()?;
+        
+        msg!("Auth chain reset ({} codes)", new_length);
+        Ok(())
+    }
+
+    /// Remove the auth chain and reclaim its rent
+    ///
+    /// Requires the next code, unless the chain is already exhausted (an
+    /// exhausted chain protects nothing and would otherwise lock the owner out).
+    pub fn close_auth_chain(
+        ctx: Context<CloseAuthChain>,
+        auth_code: Option<[u8; 32]>,
+    ) -> Result<()> {
+        let chain = &mut ctx.accounts.auth_chain;
+        if chain.remaining > 0 {
+            auth_chain::consume_code(chain, auth_code)?;
+        }
+        
+        ctx.accounts.encrypted_account.record_activity()?;
+        
+        msg!("Auth chain removed");
+        Ok(())
+    }
+
+    /// Create the token escrow's confidential vault (owner only)
+    ///
+    /// Token-2022 account at a PDA, owned by the escrow and configured for
+    /// confidential transfers with the owner's ElGamal key, whose validity
+    /// proof must be pre-verified into `pubkey_proof_context`.
+    pub fn initialize_confidential_vault(
+        ctx: Context<InitializeConfidentialVault>,
+        decryptable_zero_balance: [u8; 36],
+        maximum_pending_balance_credit_counter: u64,
+    ) -> Result<()> {
+        let vault_info = ctx.accounts.confidential_vault.to_account_info();
+        let token_program = ctx.accounts.token_program.to_account_info();
+        let mint_info = ctx.accounts.mint.to_account_info();
+        let escrow_info = ctx.accounts.token_escrow.to_account_info();
+        
+        let escrow_key = ctx.accounts.token_escrow.key();
+        let vault_bump = ctx.bumps.confidential_vault;
+        let vault_seeds = &[pda::CONFIDENTIAL_VAULT_SEED, escrow_key.as_ref(), &[vault_bump]];
+        
+        let space = confidential_token_bridge::vault_space()?;
+        create_account(
+            CpiContext::new_with_signer(
+                ctx.accounts.system_program.to_account_info(),
+                CreateAccount {
+                    from: ctx.accounts.owner.to_account_info(),
+                    to: vault_info.clone(),
+                },
+                &[&vault_seeds[..]],
+            ),
+            Rent::get()?.minimum_balance(space),
+            space as u64,
+            &spl_token_2022::ID,
+        )?;
+        
+        confidential_token_bridge::initialize_vault(&token_program, &vault_info, &mint_info, &escrow_info)?;
+        
+        // Escrow PDA signs as vault authority
+        let owner_key = ctx.accounts.token_escrow.owner;
+        let mint_key = ctx.accounts.token_escrow.mint;
+        let bump = ctx.accounts.token_escrow.bump;
+        let escrow_seeds = &[
+            pda::TOKEN_ESCROW_SEED,
+            owner_key.as_ref(),
+            mint_key.as_ref(),
+            &[bump],
+        ];
+        confidential_token_bridge::configure_vault(
+            &token_program,
+            &vault_info,
+            &mint_info,
+            &ctx.accounts.pubkey_proof_context.to_account_info(),
+            &escrow_info,
+            decryptable_zero_balance,
+            maximum_pending_balance_credit_counter,
+            &[&escrow_seeds[..]],
+        )?;
+        
+        msg!("✅ Confidential vault created for the token escrow");
+        Ok(())
+    }
+
+    /// Fold the confidential vault's pending balance into its available
+    /// balance (owner only)
+    ///
+    /// Bridged deposits land in the pending balance; run this before
+    /// bridging them back out.
+    pub fn apply_confidential_vault_balance(
+        ctx: Context<ApplyConfidentialVaultBalance>,
+        expected_pending_balance_credit_counter: u64,
+        new_decryptable_available_balance: [u8; 36],
+    ) -> Result<()> {
+        let owner_key = ctx.accounts.token_escrow.owner;
+        let mint_key = ctx.accounts.token_escrow.mint;
+        let bump = ctx.accounts.token_escrow.bump;
+        let escrow_seeds = &[
+            pda::TOKEN_ESCROW_SEED,
+            owner_key.as_ref(),
+            mint_key.as_ref(),
+            &[bump],
+        ];
+        
+        confidential_token_bridge::apply_pending_balance(
+            &ctx.accounts.token_program.to_account_info(),
+            &ctx.accounts.confidential_vault.to_account_info(),
+            &ctx.accounts.token_escrow.to_account_info(),
+            expected_pending_balance_credit_counter,
+            new_decryptable_available_balance,
+            &[&escrow_seeds[..]],
+        )?;
+        
+        msg!("Confidential vault pending balance applied");
+        Ok(())
+    }
+
+    /// Bridge tokens from a Token-2022 confidential-transfer balance into the
+    /// token escrow
+    ///
+    /// Moves a hidden amount from the user's available balance into the
+    /// escrow's confidential vault with the extension's `Transfer`, using
+    /// pre-verified equality/validity/range proof context accounts. The
+    /// amount link proof shows `amount_commitment` hides the same value as
+    /// the transfer, and the program adds it to the escrow commitment.
+    /// PRIVACY: Amount is NEVER revealed on-chain!
+    pub fn deposit_from_confidential<'info>(
+        ctx: Context<'_, '_, '_, 'info, DepositFromConfidential<'info>>,
+        amount_commitment: [u8; 64],
+        link_proof: [u8; 224],
+        new_decryptable_available_balance: [u8; 36],
+    ) -> Result<()> {
+        // SECURITY: Emergency pause (set by the config authority)
+        let config = Config::load_optional(&ctx.accounts.config)?;
+        require_not_paused!(config.as_ref(), pause::DEPOSITS);
+        
+        // ============================================
+        // INPUT VALIDATION
+        // ============================================
+        
+        // Validate commitment is well-formed for the configured commitment scheme
+        require!(
+            is_valid_commitment(&amount_commitment),
+            ErrorCode::InvalidCommitment
+        );
+        
+        // ============================================
+        // ZK PROOF VERIFICATION
+        // ============================================
+        
+        // SECURITY: amount_commitment must hide exactly what the vault receives
+        let validity_proof_context = ctx.accounts.ciphertext_validity_proof_context.to_account_info();
+        let transfer_amount = confidential_token_bridge::read_transfer_amount(&validity_proof_context)?;
+        if let Err(e) = verify_amount_link_proof(&link_proof, &transfer_amount.commitment, &amount_commitment) {
+            msg!("⚠️  Amount link proof error: {:?}", e);
+            return Err(ErrorCode::InvalidProof.into());
+        }
+        
+        // User's available balance -> confidential vault (pending balance)
+        confidential_token_bridge::transfer(
+            &ctx.accounts.token_program.to_account_info(),
+            &ctx.accounts.user_token_account.to_account_info(),
+            &ctx.accounts.mint.to_account_info(),
+            &ctx.accounts.confidential_vault.to_account_info(),
+            &ctx.accounts.equality_proof_context.to_account_info(),
+            &validity_proof_context,
+            &ctx.accounts.range_proof_context.to_account_info(),
+            &ctx.accounts.owner.to_account_info(),
+            &transfer_amount,
+            new_decryptable_available_balance,
+            &[],
+        )?;
+        
+        // SECURITY: new = old + amount, computed here
+        let escrow = &mut ctx.accounts.token_escrow;
+        let new_commitment = match pedersen::add(&escrow.encrypted_balance, &amount_commitment) {
+            Ok(commitment) => commitment,
+            Err(e) => {
+                msg!("⚠️  Commitment addition error: {:?}", e);
+                return Err(ErrorCode::InvalidCommitment.into());
+            }
+        };
+        escrow.encrypted_balance = new_commitment;
+        escrow.version += 1;
+        
+        msg!("✅ Bridged from Token-2022 confidential balance");
+        msg!("   Commitment version: {}", escrow.version);
+        msg!("   ❌ AMOUNT IS HIDDEN!");
+        
+        Ok(())
+    }
+
+    /// Bridge tokens from the token escrow into a Token-2022
+    /// confidential-transfer balance
+    ///
+    /// Moves a hidden amount from the escrow's confidential vault into the
+    /// user's pending balance with the extension's `Transfer` (the escrow
+    /// PDA signs; the owner builds the vault-side proofs). `proof_data` is an
+    /// escrow debit proof for the escrow commitment, and the amount link
+    /// proof ties its amount to the transfer. The user applies their pending
+    /// balance afterwards.
+    /// PRIVACY: Amount is NEVER revealed on-chain!
+    pub fn withdraw_to_confidential<'info>(
+        ctx: Context<'_, '_, '_, 'info, WithdrawToConfidential<'info>>,
+        new_commitment: [u8; 64],
+        proof_data: Vec<u8>,
+        link_proof: [u8; 224],
+        new_vault_decryptable_available_balance: [u8; 36],
+    ) -> Result<()> {
+        // SECURITY: Emergency pause and circuit breaker (set by the config authority)
+        let config = Config::load_optional(&ctx.accounts.config)?;
+        require_not_paused!(config.as_ref(), pause::WITHDRAWALS);
+        limits::enforce_transfer_rate(config.as_ref(), &ctx.accounts.stats)?;
+        
+        // ============================================
+        // INPUT VALIDATION
+        // ============================================
+        
+        // Validate proof data size (DoS protection)
+        require!(
+            proof_data.len() >= proof_constants::MIN_PROOF_DATA_SIZE,
+            ErrorCode::InvalidProof
+        );
+        require!(
+            proof_data.len() <= proof_constants::MAX_PROOF_DATA_SIZE,
+            ErrorCode::InvalidProof
+        );
+        
+        // Validate commitment is well-formed for the configured commitment scheme
+        require!(
+            is_valid_commitment(&new_commitment),
+            ErrorCode::InvalidCommitment
+        );
+        
+        // ============================================
+        // ZK PROOF VERIFICATION
+        // ============================================
+        
+        let amount_commitment = match proof_verification::extract_amount_commitment(&proof_data) {
+            Ok(commitment) => commitment,
+            Err(e) => {
+                msg!("⚠️  Failed to extract amount commitment from proof: {:?}", e);
+                return Err(ErrorCode::InvalidProof.into());
+            }
+        };
+        
+        // SECURITY: old - new must open to the amount leaving the vault
+        let old_commitment = ctx.accounts.token_escrow.encrypted_balance;
+        if let Err(e) = verify_escrow_debit_proof(&proof_data, &amount_commitment, &old_commitment, &new_commitment) {
+            msg!("⚠️  Proof verification error: {:?}", e);
+            return Err(ErrorCode::InvalidProof.into());
+        }
+        
+        let validity_proof_context = ctx.accounts.ciphertext_validity_proof_context.to_account_info();
+        let transfer_amount = confidential_token_bridge::read_transfer_amount(&validity_proof_context)?;
+        if let Err(e) = verify_amount_link_proof(&link_proof, &transfer_amount.commitment, &amount_commitment) {
+            msg!("⚠️  Amount link proof error: {:?}", e);
+            return Err(ErrorCode::InvalidProof.into());
+        }
+        
+        // Confidential vault -> user's pending balance (escrow PDA signs as vault authority)
+        let owner_key = ctx.accounts.token_escrow.owner;
+        let mint_key = ctx.accounts.token_escrow.mint;
+        let bump = ctx.accounts.token_escrow.bump;
+        let seeds = &[
+            pda::TOKEN_ESCROW_SEED,
+            owner_key.as_ref(),
+            mint_key.as_ref(),
+            &[bump],
+        ];
+        let signer_seeds = &[&seeds[..]];
+        
+        confidential_token_bridge::transfer(
+            &ctx.accounts.token_program.to_account_info(),
+            &ctx.accounts.confidential_vault.to_account_info(),
+            &ctx.accounts.mint.to_account_info(),
+            &ctx.accounts.user_token_account.to_account_info(),
+            &ctx.accounts.equality_proof_context.to_account_info(),
+            &validity_proof_context,
+            &ctx.accounts.range_proof_context.to_account_info(),
+            &ctx.accounts.token_escrow.to_account_info(),
+            &transfer_amount,
+            new_vault_decryptable_available_balance,
+            signer_seeds,
+        )?;
+        
+        // Update encrypted commitment
+        let escrow = &mut ctx.accounts.token_escrow;
+        escrow.encrypted_balance = new_commitment;
+        escrow.version += 1;
+        
+        // Protocol statistics (if initialized)
+        stats::record(&ctx.accounts.stats, stats::Activity::Withdrawal)?;
+        
+        msg!("✅ Bridged to Token-2022 confidential balance");
+        msg!("   Commitment version: {}", escrow.version);
+        msg!("   ❌ AMOUNT IS HIDDEN!");
+        
+        Ok(())
+    }
+
+    /// Add an asset entry (mint, commitment) to an encrypted account
+    ///
+    /// The account grows by one entry via realloc (rent paid by the owner),
+    /// so a single shielded account can track SOL plus several SPL tokens.
+    /// Space reserved with `resize_account` is used first.
+    pub fn add_asset(ctx: Context<AddAsset>, mint: Pubkey) -> Result<()> {
+        let account = &mut ctx.accounts.encrypted_account;
+        
+        // SECURITY: Validate account ownership
+        require!(
+            account.owner == ctx.accounts.owner.key(),
+            ErrorCode::Unauthorized
+        );
+        
+        require!(
+            account.assets.len() < asset_constants::MAX_ASSETS,
+            ErrorCode::TooManyAssets
+        );
+        require!(
+            account.find_asset(&mint).is_none(),
+            ErrorCode::AssetAlreadyExists
+        );
+        
+        account.assets.push(AssetBalance {
+            mint,
+            encrypted_balance: [0u8; 64], // Zero commitment initially
+        });
+        
+        account.record_activity()?;
+        
+        msg!("Added asset {} ({} assets tracked)", mint, account.assets.len());
+        Ok(())
+    }
+
+    /// Remove an unfunded asset entry and shrink the account
+    ///
+    /// The account shrinks to fit exactly, releasing any reserved space.
+    pub fn remove_asset(ctx: Context<RemoveAsset>, mint: Pubkey) -> Result<()> {
+        let account = &mut ctx.accounts.encrypted_account;
+        
+        // SECURITY: Validate account ownership
+        require!(
+            account.owner == ctx.accounts.owner.key(),
+            ErrorCode::Unauthorized
+        );
+        
+        let index = account.find_asset(&mint)
+            .ok_or(ErrorCode::AssetNotFound)?;
+        
+        // SECURITY: Never drop a commitment that may hide funds
+        require!(
+            account.assets[index].encrypted_balance == [0u8; 64],
+            ErrorCode::AssetNotEmpty
+        );
+        
+        account.assets.remove(index);
+        
+        account.record_activity()?;
+        
+        msg!("Removed asset {} ({} assets tracked)", mint, account.assets.len());
+        Ok(())
+    }
+
+    /// Deposit into an asset entry (convert plaintext to encrypted)
+    pub fn deposit_asset(
+        ctx: Context<UpdateAsset>,
+        mint: Pubkey,
+        encrypted_commitment: [u8; 64],
+    ) -> Result<()> {
+        // SECURITY: Emergency pause (set by the config authority)
+        let config = Config::load_optional(&ctx.accounts.config)?;
+        require_not_paused!(config.as_ref(), pause::DEPOSITS);
+        
+        // SECURITY: Pool-backed SOL only moves through proven pool instructions
+        require!(
+            mint != pool_constants::POOL_SOL_ASSET_ID,
+            ErrorCode::PoolAssetReserved
+        );
+        
+        // SECURITY: Validate commitment is well-formed for the configured commitment scheme
+        require!(
+            is_valid_commitment(&encrypted_commitment),
+            ErrorCode::InvalidCommitment
+        );
+        
+        let account = &mut ctx.accounts.encrypted_account;
+        let index = account.find_asset(&mint)
+            .ok_or(ErrorCode::AssetNotFound)?;
+        
+        account.assets[index].encrypted_balance = encrypted_commitment;
+        account.version += 1;
+        account.advance_state_hash(&encrypted_commitment);
+        account.record_activity()?;
+        
+        msg!("Asset deposit completed - amount is ENCRYPTED");
+        msg!("   Mint: {}, version: {}", mint, account.version);
+        
+        Ok(())
+    }
+
+    /// Withdraw from an asset entry (store the new encrypted balance)
+    pub fn withdraw_asset(
+        ctx: Context<UpdateAsset>,
+        mint: Pubkey,
+        new_commitment: [u8; 64],
+    ) -> Result<()> {
+        // SECURITY: Emergency pause and circuit breaker (set by the config authority)
+        let config = Config::load_optional(&ctx.accounts.config)?;
+        require_not_paused!(config.as_ref(), pause::WITHDRAWALS);
+        limits::enforce_transfer_rate(config.as_ref(), &ctx.accounts.stats)?;
+        
+        // SECURITY: Pool-backed SOL only moves through proven pool instructions
+        require!(
+            mint != pool_constants::POOL_SOL_ASSET_ID,
+            ErrorCode::PoolAssetReserved
+        );
+        
+        // SECURITY: Validate commitment is well-formed for the configured commitment scheme
+        require!(
+            is_valid_commitment(&new_commitment),
+            ErrorCode::InvalidCommitment
+        );
+        
+        let account = &mut ctx.accounts.encrypted_account;
+        let index = account.find_asset(&mint)
+            .ok_or(ErrorCode::AssetNotFound)?;
+        
+        account.assets[index].encrypted_balance = new_commitment;
+        account.version += 1;
+        account.advance_state_hash(&new_commitment);
+        account.record_activity()?;
+        
+        // Protocol statistics (if initialized)
+        stats::record(&ctx.accounts.stats, stats::Activity::Withdrawal)?;
+        
+        msg!("Asset withdraw completed - new encrypted balance stored");
+        msg!("   Mint: {}, version: {}", mint, account.version);
+        
+        Ok(())
+    }
+
+    /// Transfer an encrypted asset amount between two multi-asset accounts
+    /// PRIVACY: Amount is NEVER revealed on-chain!
+    /// 
+    /// REENTRANCY PROTECTION: See confidential_transfer() for documentation.
+    pub fn confidential_asset_transfer(
+        ctx: Context<ConfidentialAssetTransfer>,
+        mint: Pubkey,
+        sender_new_commitment: [u8; 64],
+        recipient_new_commitment: [u8; 64],
+        proof_data: Vec<u8>,
+        expected_sender_version: u64,
+        expected_recipient_version: u64,
+    ) -> Result<()> {
+        // ============================================
+        // INPUT VALIDATION (Checks)
+        // ============================================
+        
+        // SECURITY: Emergency pause and circuit breaker (set by the config authority)
+        let config = Config::load_optional(&ctx.accounts.config)?;
+        require_not_paused!(config.as_ref(), pause::TRANSFERS);
+        limits::enforce_transfer_rate(config.as_ref(), &ctx.accounts.stats)?;
+        
+        // SECURITY: Optimistic concurrency - the proof was built against these
+        // exact states; a concurrent update would make it stale
+        require!(
+            ctx.accounts.sender_account.version == expected_sender_version,
+            ErrorCode::StaleAccountVersion
+        );
+        require!(
+            ctx.accounts.recipient_account.version == expected_recipient_version,
+            ErrorCode::StaleAccountVersion
+        );
+        
+        // SECURITY: Validate sender and recipient are different accounts
+        // (compared by PDA, so an owner can move value between sub-accounts)
+        require!(
+            ctx.accounts.sender_account.key() != ctx.accounts.recipient_account.key(),
+            ErrorCode::InvalidRecipient
+        );
+        
+        // SECURITY: Validate sender account ownership
+        require!(
+            ctx.accounts.sender_account.owner == ctx.accounts.sender.key(),
+            ErrorCode::Unauthorized
+        );
+        
+        // SECURITY: Validate recipient account ownership
+        require!(
+            ctx.accounts.recipient_account.owner == ctx.accounts.recipient.key(),
+            ErrorCode::Unauthorized
+        );
+        
+        // Validate proof data size (DoS protection)
+        require!(
+            proof_data.len() >= proof_constants::MIN_PROOF_DATA_SIZE,
+            ErrorCode::InvalidProof
+        );
+        require!(
+            proof_data.len() <= proof_constants::MAX_PROOF_DATA_SIZE,
+            ErrorCode::InvalidProof
+        );
+        
+        // Validate commitments are well-formed for the configured commitment scheme
+        require!(
+            is_valid_commitment(&sender_new_commitment),
+            ErrorCode::InvalidCommitment
+        );
+        require!(
+            is_valid_commitment(&recipient_new_commitment),
+            ErrorCode::InvalidCommitment
+        );
+        
+        let sender_index = ctx.accounts.sender_account.find_asset(&mint)
+            .ok_or(ErrorCode::AssetNotFound)?;
+        let recipient_index = ctx.accounts.recipient_account.find_asset(&mint)
+            .ok_or(ErrorCode::AssetNotFound)?;
+        
+        let sender_old_commitment = ctx.accounts.sender_account.assets[sender_index].encrypted_balance;
+        let recipient_old_commitment = ctx.accounts.recipient_account.assets[recipient_index].encrypted_balance;
+        
+        // Validate sender asset is funded (has non-zero commitment)
+        require!(
+            sender_old_commitment != [0u8; 64],
+            ErrorCode::InvalidCommitment
+        );
+        
+        // ============================================
+        // ZK PROOF VERIFICATION
+        // ============================================
+        
+        let amount_commitment = match proof_verification::extract_amount_commitment(&proof_data) {
+            Ok(commitment) => commitment,
+            Err(e) => {
+                msg!("⚠️  Failed to extract amount commitment from proof: {:?}", e);
+                return Err(ErrorCode::InvalidProof.into());
+            }
+        };
+        
+        if let Err(e) = verify_transfer_proof(
+            &proof_data,
+            &amount_commitment,
+            &sender_new_commitment,
+            &sender_old_commitment,
+            &recipient_old_commitment,
+            &recipient_new_commitment,
+        ) {
+            msg!("⚠️  Proof verification error: {:?}", e);
+            return Err(ErrorCode::InvalidProof.into());
+        }
+        
+        // Update encrypted asset balances
+        let sender_account = &mut ctx.accounts.sender_account;
+        sender_account.assets[sender_index].encrypted_balance = sender_new_commitment;
+        sender_account.version += 1;
+        sender_account.advance_state_hash(&sender_new_commitment);
+        
+        let recipient_account = &mut ctx.accounts.recipient_account;
+        recipient_account.assets[recipient_index].encrypted_balance = recipient_new_commitment;
+        recipient_account.version += 1;
+        recipient_account.advance_state_hash(&recipient_new_commitment);
+        
+        // Protocol statistics (if initialized)
+        stats::record(&ctx.accounts.stats, stats::Activity::Transfer)?;
+        
+        msg!("✅ Confidential asset transfer completed");
+        msg!("   Mint: {}", mint);
+        msg!("   ❌ AMOUNT IS HIDDEN - Not visible on Solana Explorer!");
+        
+        Ok(())
+    }
+
+    /// Shield public tokens into an encrypted asset balance in one step
+    ///
+    /// Moves `amount` from the owner's token account into the escrow vault
+    /// and updates the mint's asset entry with a deposit-correctness proof,
+    /// so the transfer and the commitment update can never diverge.
+    pub fn shield_tokens<'info>(
+        ctx: Context<'_, '_, '_, 'info, ShieldTokens<'info>>,
+        amount: u64,
+        new_commitment: [u8; 64],
+        proof_data: Vec<u8>,
+    ) -> Result<()> {
+        // ============================================
+        // INPUT VALIDATION (Checks)
+        // ============================================
+        
+        // SECURITY: Emergency pause and subsystem rollout (set by the config authority)
+        require_not_paused!(Some(&*ctx.accounts.config), pause::DEPOSITS);
+        require_feature!(Some(&*ctx.accounts.config), features::TOKEN_TRANSFERS);
+        
+        // Validate amount (prevent overflow and invalid amounts)
+        require!(
+            amount >= transfer_constants::MIN_AMOUNT,
+            ErrorCode::InvalidAmount
+        );
+        require!(
+            amount <= transfer_constants::MAX_AMOUNT,
+            ErrorCode::InvalidAmount
+        );
+        
+        // Validate proof data size (DoS protection)
+        require!(
+            proof_data.len() >= proof_constants::MIN_PROOF_DATA_SIZE,
+            ErrorCode::InvalidProof
+        );
+        require!(
+            proof_data.len() <= proof_constants::MAX_PROOF_DATA_SIZE,
+            ErrorCode::InvalidProof
+        );
+        
+        // Validate commitment is well-formed for the configured commitment scheme
+        require!(
+            is_valid_commitment(&new_commitment),
+            ErrorCode::InvalidCommitment
+        );
+        
+        // SECURITY: Only vetted mints when the allowlist is enforced
+        ctx.accounts.config.check_mint(
+            ctx.accounts.mint_config.as_deref(),
+            &ctx.accounts.mint,
+            amount,
+        )?;
+        
+        let mint = ctx.accounts.mint.key();
+        let index = ctx.accounts.encrypted_account.find_asset(&mint)
+            .ok_or(ErrorCode::AssetNotFound)?;
+        let old_commitment = ctx.accounts.encrypted_account.assets[index].encrypted_balance;
+        
+        // ============================================
+        // ZK PROOF VERIFICATION
+        // ============================================
+        
+        if let Err(e) = verify_deposit_proof(&proof_data, amount, &old_commitment, &new_commitment) {
+            msg!("⚠️  Proof verification error: {:?}", e);
+            return Err(ErrorCode::InvalidProof.into());
+        }
+        
+        // ============================================
+        // STATE UPDATES (Effects)
+        // ============================================
+        
+        let escrow = &mut ctx.accounts.token_escrow;
+        escrow.balance = escrow.balance.checked_add(amount)
+            .ok_or(ErrorCode::Overflow)?;
+        
+        let account = &mut ctx.accounts.encrypted_account;
+        account.assets[index].encrypted_balance = new_commitment;
+        account.version += 1;
+        account.advance_state_hash(&new_commitment);
+        
+        // ============================================
+        // EXTERNAL CALLS (Interactions)
+        // ============================================
+        
+        let cpi_context = CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            TransferChecked {
+                from: ctx.accounts.user_token_account.to_account_info(),
+                mint: ctx.accounts.mint.to_account_info(),
+                to: ctx.accounts.token_vault.to_account_info(),
+                authority: ctx.accounts.owner.to_account_info(),
+            },
+        );
+        transfer_checked_with_hooks(
+            cpi_context.with_remaining_accounts(ctx.remaining_accounts.to_vec()),
+            amount,
+            ctx.accounts.mint.decimals,
+        )?;
+        
+        msg!("✅ Tokens shielded");
+        msg!("   Mint: {}, version: {}", mint, ctx.accounts.encrypted_account.version);
+        msg!("   Escrow balance: {} base units", ctx.accounts.token_escrow.balance);
+        
+        Ok(())
+    }
+
+    /// Create the shared SOL pool vault
+    ///
+    /// Pool deposits from every user land in this one account; per-user
+    /// balances exist only as commitments under the `POOL_SOL_ASSET_ID`
+    /// asset entry.
+    pub fn initialize_pool_vault(ctx: Context<InitializePoolVault>) -> Result<()> {
+        let pool = &mut ctx.accounts.pool_vault;
+        pool.total_shielded = 0;
+        pool.bump = ctx.bumps.pool_vault;
+        
+        msg!("Initialized shared SOL pool vault");
+        Ok(())
+    }
+
+    /// Deposit SOL into the shared pool (hidden per-user balance)
+    ///
+    /// `amount` must be one of `pool_constants::DENOMINATIONS`; the SDK
+    /// splits larger deposits and spreads them out over time.
+    pub fn deposit_pool(
+        ctx: Context<PoolTransfer>,
+        amount: u64,
+        new_commitment: [u8; 64],
+        proof_data: Vec<u8>,
+        cap_proof: Option<Vec<u8>>,  // New balance <= cap (when configured)
+    ) -> Result<()> {
+        // ============================================
+        // INPUT VALIDATION (Checks)
+        // ============================================
+        
+        // Validate amount (prevent overflow and invalid amounts)
+        require!(
+            amount >= transfer_constants::MIN_AMOUNT,
+            ErrorCode::InvalidAmount
+        );
+        require!(
+            amount <= transfer_constants::MAX_AMOUNT,
+            ErrorCode::InvalidAmount
+        );
+        
+        // PRIVACY: Fixed denominations only, so deposits cannot be matched
+        // to withdrawals by amount
+        require!(
+            pool_constants::DENOMINATIONS.contains(&amount),
+            ErrorCode::InvalidDenomination
+        );
+        
+        // Validate proof data size (DoS protection)
+        require!(
+            proof_data.len() >= proof_constants::MIN_PROOF_DATA_SIZE,
+            ErrorCode::InvalidProof
+        );
+        require!(
+            proof_data.len() <= proof_constants::MAX_PROOF_DATA_SIZE,
+            ErrorCode::InvalidProof
+        );
+        
+        // Validate commitment is well-formed for the configured commitment scheme
+        require!(
+            is_valid_commitment(&new_commitment),
+            ErrorCode::InvalidCommitment
+        );
+        
+        let config = Config::load_optional(&ctx.accounts.config)?;
+        
+        // SECURITY: Emergency pause (set by the config authority)
+        require_not_paused!(config.as_ref(), pause::DEPOSITS);
+        
+        // Deployment-tuned limits (compiled-in defaults when unset)
+        limits::enforce_amount(config.as_ref(), amount)?;
+        limits::enforce_proof_size(config.as_ref(), &proof_data)?;
+        
+        // New pool balance must stay under the per-account cap (if configured)
+        deposit_cap::enforce_if_configured(config.as_ref(), &new_commitment, cap_proof.as_deref())?;
+        
+        let index = ctx.accounts.encrypted_account.find_asset(&pool_constants::POOL_SOL_ASSET_ID)
+            .ok_or(ErrorCode::AssetNotFound)?;
+        let old_commitment = ctx.accounts.encrypted_account.assets[index].encrypted_balance;
+        
+        // ============================================
+        // ZK PROOF VERIFICATION
+        // ============================================
+        
+        if let Err(e) = verify_deposit_proof(&proof_data, amount, &old_commitment, &new_commitment) {
+            msg!("⚠️  Proof verification error: {:?}", e);
+            return Err(ErrorCode::InvalidProof.into());
+        }
+        
+        // Transfer SOL from user to the pool vault
+        let cpi_context = CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.owner.to_account_info(),
+                to: ctx.accounts.pool_vault.to_account_info(),
+            },
+        );
+        transfer(cpi_context, amount)?;
+        
+        let pool = &mut ctx.accounts.pool_vault;
+        pool.total_shielded = pool.total_shielded.checked_add(amount)
+            .ok_or(ErrorCode::Overflow)?;
+        
+        let account = &mut ctx.accounts.encrypted_account;
+        account.assets[index].encrypted_balance = new_commitment;
+        account.version += 1;
+        account.advance_state_hash(&new_commitment);
+        account.record_activity()?;
+        
+        msg!("✅ Pool deposit completed");
+        msg!("   ❌ BALANCE IS HIDDEN - No per-user escrow balance stored!");
+        msg!("   Commitment version: {}", account.version);
+        
+        Ok(())
+    }
+
+    /// Withdraw SOL from the shared pool, proven against the hidden balance
+    ///
+    /// Bounded by the account's pool commitment, whether its value was
+    /// deposited or received through `confidential_pool_transfer`.
+    /// Like deposits, `amount` must be a fixed denomination; value below the
+    /// smallest one stays in the pool until more is received.
+    pub fn withdraw_pool(
+        ctx: Context<PoolTransfer>,
+        amount: u64,
+        new_commitment: [u8; 64],
+        proof_data: Vec<u8>,
+    ) -> Result<()> {
+        // ============================================
+        // INPUT VALIDATION (Checks)
+        // ============================================
+        
+        // SECURITY: Emergency pause and circuit breaker (set by the config authority)
+        let config = Config::load_optional(&ctx.accounts.config)?;
+        require_not_paused!(config.as_ref(), pause::WITHDRAWALS);
+        limits::enforce_transfer_rate(config.as_ref(), &ctx.accounts.stats)?;
+        
+        // Validate amount (prevent overflow and invalid amounts)
+        require!(
+            amount >= transfer_constants::MIN_AMOUNT,
+            ErrorCode::InvalidAmount
+        );
+        require!(
+            amount <= transfer_constants::MAX_AMOUNT,
+            ErrorCode::InvalidAmount
+        );
+        
+        // PRIVACY: Pool exits use the same fixed denominations as deposits
+        require!(
+            pool_constants::DENOMINATIONS.contains(&amount),
+            ErrorCode::InvalidDenomination
+        );
+        
+        // Validate proof data size (DoS protection)
+        require!(
+            proof_data.len() >= proof_constants::MIN_PROOF_DATA_SIZE,
+            ErrorCode::InvalidProof
+        );
+        require!(
+            proof_data.len() <= proof_constants::MAX_PROOF_DATA_SIZE,
+            ErrorCode::InvalidProof
+        );
+        
+        // Validate commitment is well-formed for the configured commitment scheme
+        require!(
+            is_valid_commitment(&new_commitment),
+            ErrorCode::InvalidCommitment
+        );
+        
+        let index = ctx.accounts.encrypted_account.find_asset(&pool_constants::POOL_SOL_ASSET_ID)
+            .ok_or(ErrorCode::AssetNotFound)?;
+        let old_commitment = ctx.accounts.encrypted_account.assets[index].encrypted_balance;
+        
+        // ============================================
+        // ZK PROOF VERIFICATION
+        // ============================================
+        
+        // SECURITY: The proof (not a plaintext balance) shows the hidden
+        // balance covers `amount` and the remainder stays in range
+        if let Err(e) = verify_withdraw_proof(&proof_data, amount, &old_commitment, &new_commitment) {
+            msg!("⚠️  Proof verification error: {:?}", e);
+            return Err(ErrorCode::InvalidProof.into());
+        }
+        
+        // SECURITY: Pool solvency (aggregate)
+        require!(
+            ctx.accounts.pool_vault.total_shielded >= amount,
+            ErrorCode::InsufficientBalance
+        );
+        
+        // ============================================
+        // STATE UPDATES (Effects)
+        // ============================================
+        
+        let pool = &mut ctx.accounts.pool_vault;
+        pool.total_shielded = pool.total_shielded.checked_sub(amount)
+            .ok_or(ErrorCode::Underflow)?;
+        
+        let account = &mut ctx.accounts.encrypted_account;
+        account.assets[index].encrypted_balance = new_commitment;
+        account.version += 1;
+        account.advance_state_hash(&new_commitment);
+        account.record_activity()?;
+        
+        // ============================================
+        // EXTERNAL CALLS (Interactions)
+        // ============================================
+        
+        // Program-owned vault: move lamports directly
+        let pool_info = ctx.accounts.pool_vault.to_account_info();
+        let owner_info = ctx.accounts.owner.to_account_info();
+        let new_pool_lamports = pool_info.lamports().checked_sub(amount)
+            .ok_or(ErrorCode::Underflow)?;
+        let new_owner_lamports = owner_info.lamports().checked_add(amount)
+            .ok_or(ErrorCode::Overflow)?;
+        **pool_info.try_borrow_mut_lamports()? = new_pool_lamports;
+        **owner_info.try_borrow_mut_lamports()? = new_owner_lamports;
+        
+        // Protocol statistics (if initialized)
+        stats::record(&ctx.accounts.stats, stats::Activity::Withdrawal)?;
+        
+        msg!("✅ Pool withdrawal completed");
+        msg!("   Commitment version: {}", ctx.accounts.encrypted_account.version);
+        
+        Ok(())
+    }
+
+    /// Confidential SOL transfer inside the shared pool (no plaintext amount)
+    ///
+    /// Unlike `confidential_sol_transfer`, no lamports move: both balances
+    /// are pool-asset commitments backed by the shared vault, so the amount
+    /// appears nowhere in the instruction data, account data or logs.
+    ///
+    /// SECURITY: Both balance updates are checked algebraically (see
+    /// `verify_transfer_proof`), so received value is withdrawable like
+    /// deposited value and the pool commitments sum to what the vault holds.
+    /// Negative balances are only excluded by the range proofs, which are
+    /// still structural (see docs/ZK_PROOF_VERIFICATION_STATUS.md); the
+    /// vault's `total_shielded` caps what all exits together can take.
+    ///
+    /// PRIVACY: Only the two account versions change publicly.
+    pub fn confidential_pool_transfer(
+        ctx: Context<ConfidentialAssetTransfer>,
+        sender_new_commitment: [u8; 64],
+        recipient_new_commitment: [u8; 64],
+        proof_data: Vec<u8>,
+        expected_sender_version: u64,
+        expected_recipient_version: u64,
+    ) -> Result<()> {
+        // ============================================
+        // INPUT VALIDATION (Checks)
+        // ============================================
+        
+        // SECURITY: Emergency pause and circuit breaker (set by the config authority)
+        let config = Config::load_optional(&ctx.accounts.config)?;
+        require_not_paused!(config.as_ref(), pause::TRANSFERS);
+        limits::enforce_transfer_rate(config.as_ref(), &ctx.accounts.stats)?;
+        
+        // SECURITY: Optimistic concurrency - the proof was built against these
+        // exact states; a concurrent update would make it stale
+        require!(
+            ctx.accounts.sender_account.version == expected_sender_version,
+            ErrorCode::StaleAccountVersion
+        );
+        require!(
+            ctx.accounts.recipient_account.version == expected_recipient_version,
+            ErrorCode::StaleAccountVersion
+        );
+        
+        // SECURITY: Validate sender and recipient are different accounts
+        // (compared by PDA, so an owner can move value between sub-accounts)
+        require!(
+            ctx.accounts.sender_account.key() != ctx.accounts.recipient_account.key(),
+            ErrorCode::InvalidRecipient
+        );
+        
+        // SECURITY: Validate sender account ownership
+        require!(
+            ctx.accounts.sender_account.owner == ctx.accounts.sender.key(),
+            ErrorCode::Unauthorized
+        );
+        
+        // SECURITY: Validate recipient account ownership
+        require!(
+            ctx.accounts.recipient_account.owner == ctx.accounts.recipient.key(),
+            ErrorCode::Unauthorized
+        );
+        
+        // Validate proof data size (DoS protection)
+        require!(
+            proof_data.len() >= proof_constants::MIN_PROOF_DATA_SIZE,
+            ErrorCode::InvalidProof
+        );
+        require!(
+            proof_data.len() <= proof_constants::MAX_PROOF_DATA_SIZE,
+            ErrorCode::InvalidProof
+        );
+        
+        // Validate commitments are well-formed for the configured commitment scheme
+        require!(
+            is_valid_commitment(&sender_new_commitment),
+            ErrorCode::InvalidCommitment
+        );
+        require!(
+            is_valid_commitment(&recipient_new_commitment),
+            ErrorCode::InvalidCommitment
+        );
+        
+        let sender_index = ctx.accounts.sender_account.find_asset(&pool_constants::POOL_SOL_ASSET_ID)
+            .ok_or(ErrorCode::AssetNotFound)?;
+        let recipient_index = ctx.accounts.recipient_account.find_asset(&pool_constants::POOL_SOL_ASSET_ID)
+            .ok_or(ErrorCode::AssetNotFound)?;
+        
+        let sender_old_commitment = ctx.accounts.sender_account.assets[sender_index].encrypted_balance;
+        let recipient_old_commitment = ctx.accounts.recipient_account.assets[recipient_index].encrypted_balance;
+        
+        // Validate sender pool balance is funded (has non-zero commitment)
+        require!(
+            sender_old_commitment != [0u8; 64],
+            ErrorCode::InvalidCommitment
+        );
+        
+        // ============================================
+        // ZK PROOF VERIFICATION
+        // ============================================
+        //
+        // SECURITY: With no plaintext amount to check against, the proof shows
+        // the sender loses and the recipient gains the same hidden amount
+        
+        let amount_commitment = match proof_verification::extract_amount_commitment(&proof_data) {
+            Ok(commitment) => commitment,
+            Err(e) => {
+                msg!("⚠️  Failed to extract amount commitment from proof: {:?}", e);
+                return Err(ErrorCode::InvalidProof.into());
+            }
+        };
+        
+        if let Err(e) = verify_transfer_proof(
+            &proof_data,
+            &amount_commitment,
+            &sender_new_commitment,
+            &sender_old_commitment,
+            &recipient_old_commitment,
+            &recipient_new_commitment,
+        ) {
+            msg!("⚠️  Proof verification error: {:?}", e);
+            return Err(ErrorCode::InvalidProof.into());
+        }
+        
+        // ============================================
+        // STATE UPDATES (Effects)
+        // ============================================
+        
+        // The pool vault already backs both balances; no lamports move
+        let sender_account = &mut ctx.accounts.sender_account;
+        sender_account.assets[sender_index].encrypted_balance = sender_new_commitment;
+        sender_account.version += 1;
+        sender_account.advance_state_hash(&sender_new_commitment);
+        sender_account.record_activity()?;
+        
+        let recipient_account = &mut ctx.accounts.recipient_account;
+        recipient_account.assets[recipient_index].encrypted_balance = recipient_new_commitment;
+        recipient_account.version += 1;
+        recipient_account.advance_state_hash(&recipient_new_commitment);
+        
+        // Protocol statistics (if initialized)
+        stats::record(&ctx.accounts.stats, stats::Activity::Transfer)?;
+        
+        msg!("✅ Confidential pool transfer completed");
+        msg!("   Sender version: {}", sender_account.version);
+        msg!("   Recipient version: {}", recipient_account.version);
+        msg!("   ❌ AMOUNT IS HIDDEN - Not even in instruction data!");
+        
+        Ok(())
+    }
+
+    /// Register a private asset issued directly into the shielded system
+    ///
+    /// The asset id is a fresh keypair that must sign once, so nobody can
+    /// squat another issuer's id. Holders track the asset with
+    /// `add_asset(asset_id)`.
+    pub fn register_private_asset(ctx: Context<RegisterPrivateAsset>) -> Result<()> {
+        let asset = &mut ctx.accounts.private_asset;
+        asset.asset_id = ctx.accounts.asset_id.key();
+        asset.issuer = ctx.accounts.issuer.key();
+        asset.supply_version = 0;
+        asset.bump = ctx.bumps.private_asset;
+        
+        msg!("Private asset {} registered", asset.asset_id);
+        msg!("   Issuer: {}", asset.issuer);
+        Ok(())
+    }
+
+    /// Mint a hidden amount of a private asset into a holder's account
+    /// PRIVACY: Minted amount is NEVER revealed on-chain!
+    pub fn confidential_mint(
+        ctx: Context<ConfidentialSupply>,
+        new_commitment: [u8; 64],
+        proof_data: Vec<u8>,
+    ) -> Result<()> {
+        adjust_private_supply(ctx, SupplyDirection::Mint, new_commitment, &proof_data)?;
+        
+        msg!("✅ Confidential mint completed");
+        msg!("   ❌ AMOUNT IS HIDDEN - Not visible on Solana Explorer!");
+        Ok(())
+    }
+
+    /// Burn a hidden amount of a private asset from a holder's account
+    ///
+    /// Requires both the holder and the issuer to sign.
+    /// PRIVACY: Burned amount is NEVER revealed on-chain!
+    pub fn confidential_burn(
+        ctx: Context<ConfidentialSupply>,
+        new_commitment: [u8; 64],
+        proof_data: Vec<u8>,
+    ) -> Result<()> {
+        // SECURITY: Only the holder can consent to burning their balance
+        let holder = ctx.accounts.holder.as_ref().ok_or(ErrorCode::Unauthorized)?;
+        require!(
+            ctx.accounts.holder_account.owner == holder.key(),
+            ErrorCode::Unauthorized
+        );
+        
+        adjust_private_supply(ctx, SupplyDirection::Burn, new_commitment, &proof_data)?;
+        
+        msg!("✅ Confidential burn completed");
+        msg!("   ❌ AMOUNT IS HIDDEN - Not visible on Solana Explorer!");
+        Ok(())
+    }
+
+    /// Open the encrypted mailbox of an account
+    ///
+    /// Counterparties (or support) leave small ciphertexts encrypted to the
+    /// account's registered key, e.g. to negotiate refunds or disputes
+    /// without exchanging contact details.
+    pub fn initialize_mailbox(ctx: Context<InitializeMailbox>) -> Result<()> {
+        // SECURITY: Messages are encrypted to the registered key
+        require!(
+            ctx.accounts.encrypted_account.encryption_key != [0u8; 32],
+            ErrorCode::InvalidEncryptionKey
+        );
+        
+        let mailbox = &mut ctx.accounts.mailbox;
+        mailbox.encrypted_account = ctx.accounts.encrypted_account.key();
+        mailbox.next_seq = 0;
+        mailbox.min_post_interval = mailbox_constants::DEFAULT_POST_INTERVAL_SLOTS;
+        mailbox.last_post_slot = 0;
+        mailbox.bump = ctx.bumps.mailbox;
+        
+        ctx.accounts.encrypted_account.record_activity()?;
+        
+        msg!("Mailbox opened for {}", mailbox.encrypted_account);
+        Ok(())
+    }
+
+    /// Change the mailbox rate limit (minimum slots between posts)
+    pub fn set_mailbox_rate_limit(ctx: Context<UpdateMailbox>, min_post_interval: u64) -> Result<()> {
+        ctx.accounts.mailbox.min_post_interval = min_post_interval;
+        
+        ctx.accounts.encrypted_account.record_activity()?;
+        
+        msg!("Mailbox rate limit: {} slots between posts", min_post_interval);
+        Ok(())
+    }
+
+    /// Leave an encrypted message in an account's mailbox
+    ///
+    /// The sender pays the message rent. `ciphertext` must be encrypted to
+    /// the account's current `encryption_key`, which is recorded with it.
+    pub fn post_message(ctx: Context<PostMessage>, ciphertext: Vec<u8>) -> Result<()> {
+        require!(
+            !ciphertext.is_empty() && ciphertext.len() <= mailbox_constants::MAX_CIPHERTEXT_LEN,
+            ErrorCode::InvalidMessage
+        );
+        
+        let encryption_key = ctx.accounts.encrypted_account.encryption_key;
+        require!(
+            encryption_key != [0u8; 32],
+            ErrorCode::InvalidEncryptionKey
+        );
+        
+        // SECURITY: Rate limit posts to keep the mailbox usable under spam
+        let slot = Clock::get()?.slot;
+        let mailbox = &mut ctx.accounts.mailbox;
+        require!(
+            mailbox.next_seq == 0
+                || slot >= mailbox.last_post_slot.saturating_add(mailbox.min_post_interval),
+            ErrorCode::MailboxRateLimited
+        );
+        
+        let message = &mut ctx.accounts.message;
+        message.mailbox = mailbox.key();
+        message.sender = ctx.accounts.sender.key();
+        message.seq = mailbox.next_seq;
+        message.encryption_key = encryption_key;
+        message.slot = slot;
+        message.ciphertext = ciphertext;
+        message.bump = ctx.bumps.message;
+        
+        mailbox.next_seq += 1;
+        mailbox.last_post_slot = slot;
+        
+        emit!(MessagePosted {
+            mailbox: message.mailbox,
+            message: message.key(),
+            seq: message.seq,
+            slot,
+        });
+        
+        msg!("📬 Message {} posted", message.seq);
+        Ok(())
+    }
+
+    /// Delete a read message (account owner only; rent goes to the owner)
+    pub fn close_message(_ctx: Context<CloseMessage>) -> Result<()> {
+        msg!("Message closed");
+        Ok(())
+    }
+
+    /// Create the shielded note pool (commitment tree + SOL vault)
+    pub fn initialize_note_pool(ctx: Context<InitializeNotePool>) -> Result<()> {
+        let pool = &mut ctx.accounts.note_pool;
+        pool.root = merkle_tree::empty_root(note_pool_constants::NOTE_TREE_DEPTH);
+        pool.next_index = 0;
+        pool.filled_subtrees = [merkle_tree::ZERO_LEAF; note_pool_constants::NOTE_TREE_DEPTH];
+        pool.root_history = [pool.root; note_pool_constants::ROOT_HISTORY_SIZE];
+        pool.root_history_index = 0;
+        pool.total_deposited = 0;
+        pool.bump = ctx.bumps.note_pool;
+        
+        msg!("Initialized note pool (depth {})", note_pool_constants::NOTE_TREE_DEPTH);
+        Ok(())
+    }
+
+    /// Store the Groth16 verifying key of a circuit (config authority only)
+    ///
+    /// Covers the note spend circuits and the deny-list exclusion circuit.
+    /// Written once per circuit: the key is what makes those proofs sound, so
+    /// it cannot be swapped out later. `ic` holds one G1 point per public
+    /// input plus the constant term (see `groth16`).
+    pub fn set_note_verifying_key(
+        ctx: Context<SetNoteVerifyingKey>,
+        circuit: u8,
+        alpha_g1: [u8; 64],
+        beta_g2: [u8; 128],
+        gamma_g2: [u8; 128],
+        delta_g2: [u8; 128],
+        ic: Vec<[u8; 64]>,
+    ) -> Result<()> {
+        let inputs = groth16::public_input_count(circuit).ok_or(ErrorCode::InvalidVerifyingKey)?;
+        require!(ic.len() == inputs + 1, ErrorCode::InvalidVerifyingKey);
+        
+        let vk = &mut ctx.accounts.verifying_key;
+        vk.circuit = circuit;
+        vk.alpha_g1 = alpha_g1;
+        vk.beta_g2 = beta_g2;
+        vk.gamma_g2 = gamma_g2;
+        vk.delta_g2 = delta_g2;
+        vk.ic = ic;
+        vk.bump = ctx.bumps.verifying_key;
+        
+        msg!("Note verifying key set for circuit {}", circuit);
+        Ok(())
+    }
+
+    /// Deposit SOL as a shielded note
+    ///
+    /// `note_commitment` hides the note's secret and nullifier key; the leaf
+    /// also binds the public `amount`. Leaves are emitted in `NoteInserted`
+    /// so wallets can rebuild the tree off-chain.
+    pub fn deposit_note(
+        ctx: Context<DepositNote>,
+        amount: u64,
+        note_commitment: [u8; 32],
+    ) -> Result<()> {
+        // SECURITY: Emergency pause (set by the config authority)
+        let config = Config::load_optional(&ctx.accounts.config)?;
+        require_not_paused!(config.as_ref(), pause::DEPOSITS);
+        
+        // Validate amount (prevent overflow and invalid amounts)
+        require!(
+            amount >= transfer_constants::MIN_AMOUNT,
+            ErrorCode::InvalidAmount
+        );
+        require!(
+            amount <= transfer_constants::MAX_AMOUNT,
+            ErrorCode::InvalidAmount
+        );
+        require!(note_commitment != [0u8; 32], ErrorCode::InvalidCommitment);
+        
+        // Transfer SOL from depositor to the note pool
+        let cpi_context = CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.depositor.to_account_info(),
+                to: ctx.accounts.note_pool.to_account_info(),
+            },
+        );
+        transfer(cpi_context, amount)?;
+        
+        let pool: &mut NotePool = &mut ctx.accounts.note_pool;
+        let leaf = merkle_tree::hash_leaf(&[&note_commitment, &amount.to_le_bytes()]);
+        let (leaf_index, root) = merkle_tree::insert_leaf(
+            &mut pool.filled_subtrees,
+            &mut pool.next_index,
+            leaf,
+        ).map_err(|_| ErrorCode::NoteTreeFull)?;
+        pool.push_root(root);
+        pool.total_deposited = pool.total_deposited.checked_add(amount)
+            .ok_or(ErrorCode::Overflow)?;
+        
+        emit!(NoteInserted {
+            leaf_index,
+            note_commitment,
+            amount,
+            root,
+        });
+        
+        msg!("✅ Note deposited at leaf {}", leaf_index);
+        Ok(())
+    }
+
+    /// Spend a note to any recipient
+    ///
+    /// PRIVACY: The proof shows membership under a recent root without
+    /// revealing the leaf, so the recipient cannot be linked to the deposit.
+    /// The nullifier PDA can only be created once, preventing double spends.
+    pub fn spend_note(
+        ctx: Context<SpendNote>,
+        root: [u8; 32],
+        nullifier: [u8; 32],
+        amount: u64,
+        proof_data: Vec<u8>,
+    ) -> Result<()> {
+        // ============================================
+        // INPUT VALIDATION (Checks)
+        // ============================================
+        
+        // SECURITY: Emergency pause and circuit breaker (set by the config authority)
+        let config = Config::load_optional(&ctx.accounts.config)?;
+        require_not_paused!(config.as_ref(), pause::WITHDRAWALS);
+        limits::enforce_transfer_rate(config.as_ref(), &ctx.accounts.stats)?;
+        
+        require!(
+            amount >= transfer_constants::MIN_AMOUNT,
+            ErrorCode::InvalidAmount
+        );
+        
+        // SECURITY: Proofs must be against a root the pool actually had
+        require!(
+            ctx.accounts.note_pool.is_known_root(&root),
+            ErrorCode::UnknownRoot
+        );
+        
+        // ============================================
+        // ZK PROOF VERIFICATION
+        // ============================================
+        
+        let recipient = ctx.accounts.recipient.key();
+        if let Err(e) = verify_note_spend_proof(
+            &proof_data,
+            &root,
+            &nullifier,
+            amount,
+            &recipient.to_bytes(),
+        ) {
+            msg!("⚠️  Proof verification error: {:?}", e);
+            return Err(ErrorCode::InvalidProof.into());
+        }
+        
+        // SECURITY: Pairing check against the stored verifying key - the
+        // structural check above accepts any well-shaped bytes
+        groth16::verify(
+            &ctx.accounts.verifying_key,
+            &proof_data,
+            &[
+                groth16::field_element(&root),
+                groth16::field_element(&nullifier),
+                groth16::amount_element(amount),
+                groth16::field_element(&recipient.to_bytes()),
+            ],
+        )?;
+        
+        // ============================================
+        // STATE UPDATES (Effects)
+        // ============================================
+        
+        let spent = &mut ctx.accounts.nullifier_record;
+        spent.nullifier = nullifier;
+        spent.slot = Clock::get()?.slot;
+        spent.bump = ctx.bumps.nullifier_record;
+        
+        let pool = &mut ctx.accounts.note_pool;
+        pool.total_deposited = pool.total_deposited.checked_sub(amount)
+            .ok_or(ErrorCode::InsufficientBalance)?;
+        
+        // ============================================
+        // EXTERNAL CALLS (Interactions)
+        // ============================================
+        
+        // Program-owned pool: move lamports directly
+        let pool_info = ctx.accounts.note_pool.to_account_info();
+        let recipient_info = ctx.accounts.recipient.to_account_info();
+        let new_pool_lamports = pool_info.lamports().checked_sub(amount)
+            .ok_or(ErrorCode::Underflow)?;
+        let new_recipient_lamports = recipient_info.lamports().checked_add(amount)
+            .ok_or(ErrorCode::Overflow)?;
+        **pool_info.try_borrow_mut_lamports()? = new_pool_lamports;
+        **recipient_info.try_borrow_mut_lamports()? = new_recipient_lamports;
+        
+        emit!(NoteSpent {
+            nullifier,
+            root,
+            slot: spent.slot,
+        });
+        
+        // Protocol statistics (if initialized)
+        stats::record(&ctx.accounts.stats, stats::Activity::Withdrawal)?;
+        
+        msg!("✅ Note spent - depositor NOT linked to recipient");
+        Ok(())
+    }
+
+    /// Ask the recipient of a mistaken transfer to send it back
+    ///
+    /// `transfer_ref` identifies the transfer (the recipient's state hash
+    /// right after it) and `amount_commitment` is the transfer's amount
+    /// commitment, which a granted refund must reuse. Details can be
+    /// negotiated through the recipient's mailbox.
+    pub fn request_refund(
+        ctx: Context<RequestRefund>,
+        transfer_ref: [u8; 32],
+        amount_commitment: [u8; 64],
+    ) -> Result<()> {
+        require!(
+            ctx.accounts.requester_account.key() != ctx.accounts.refunder_account.key(),
+            ErrorCode::InvalidRecipient
+        );
+        require!(
+            is_valid_commitment(&amount_commitment),
+            ErrorCode::InvalidCommitment
+        );
+        
+        let request = &mut ctx.accounts.refund_request;
+        request.requester = ctx.accounts.requester.key();
+        request.requester_account = ctx.accounts.requester_account.key();
+        request.refunder_account = ctx.accounts.refunder_account.key();
+        request.transfer_ref = transfer_ref;
+        request.amount_commitment = amount_commitment;
+        request.requested_slot = Clock::get()?.slot;
+        request.bump = ctx.bumps.refund_request;
+        
+        emit!(RefundRequested {
+            refund_request: request.key(),
+            requester_account: request.requester_account,
+            refunder_account: request.refunder_account,
+            transfer_ref,
+        });
+        
+        ctx.accounts.requester_account.record_activity()?;
+        
+        msg!("↩️  Refund requested from {}", request.refunder_account);
+        Ok(())
+    }
+
+    /// Return the requested amount to the requester (confidential transfer)
+    ///
+    /// The proof's amount commitment must equal the one in the request, so
+    /// exactly the mistaken amount goes back. Closes the request.
+    /// PRIVACY: Amount is NEVER revealed on-chain!
+    pub fn grant_refund(
+        ctx: Context<GrantRefund>,
+        refunder_new_commitment: [u8; 64],
+        requester_new_commitment: [u8; 64],
+        proof_data: Vec<u8>,
+    ) -> Result<()> {
+        // ============================================
+        // INPUT VALIDATION (Checks)
+        // ============================================
+        
+        // SECURITY: Emergency pause and circuit breaker (set by the config authority)
+        let config = Config::load_optional(&ctx.accounts.config)?;
+        require_not_paused!(config.as_ref(), pause::TRANSFERS);
+        limits::enforce_transfer_rate(config.as_ref(), &ctx.accounts.stats)?;
+        
+        // Validate proof data size (DoS protection)
+        require!(
+            proof_data.len() >= proof_constants::MIN_PROOF_DATA_SIZE,
+            ErrorCode::InvalidProof
+        );
+        require!(
+            proof_data.len() <= proof_constants::MAX_PROOF_DATA_SIZE,
+            ErrorCode::InvalidProof
+        );
+        
+        // Validate commitments are well-formed for the configured commitment scheme
+        require!(
+            is_valid_commitment(&refunder_new_commitment),
+            ErrorCode::InvalidCommitment
+        );
+        require!(
+            is_valid_commitment(&requester_new_commitment),
+            ErrorCode::InvalidCommitment
+        );
+        
+        let refunder_old_commitment = ctx.accounts.refunder_account.encrypted_balance;
+        let requester_old_commitment = ctx.accounts.requester_account.encrypted_balance;
+        
+        // Validate refunder account is funded (has non-zero commitment)
+        require!(
+            refunder_old_commitment != [0u8; 64],
+            ErrorCode::InvalidCommitment
+        );
+        
+        // ============================================
+        // ZK PROOF VERIFICATION
+        // ============================================
+        
+        let amount_commitment = match proof_verification::extract_amount_commitment(&proof_data) {
+            Ok(commitment) => commitment,
+            Err(e) => {
+                msg!("⚠️  Failed to extract amount commitment from proof: {:?}", e);
+                return Err(ErrorCode::InvalidProof.into());
+            }
+        };
+        
+        // SECURITY: Refund exactly the amount of the referenced transfer
+        require!(
+            amount_commitment == ctx.accounts.refund_request.amount_commitment,
+            ErrorCode::RefundAmountMismatch
+        );
+        
+        if let Err(e) = verify_transfer_proof(
+            &proof_data,
+            &amount_commitment,
+            &refunder_new_commitment,
+            &refunder_old_commitment,
+            &requester_old_commitment,
+            &requester_new_commitment,
+        ) {
+            msg!("⚠️  Proof verification error: {:?}", e);
+            return Err(ErrorCode::InvalidProof.into());
+        }
+        
+        // Update encrypted balances
+        let refunder_account = &mut ctx.accounts.refunder_account;
+        refunder_account.encrypted_balance = refunder_new_commitment;
+        refunder_account.version += 1;
+        refunder_account.advance_state_hash(&refunder_new_commitment);
+        refunder_account.record_activity()?;
+        
+        let requester_account = &mut ctx.accounts.requester_account;
+        requester_account.encrypted_balance = requester_new_commitment;
+        requester_account.version += 1;
+        requester_account.advance_state_hash(&requester_new_commitment);
+        
+        // Protocol statistics (if initialized)
+        stats::record(&ctx.accounts.stats, stats::Activity::Transfer)?;
+        
+        msg!("✅ Refund granted");
+        msg!("   ❌ AMOUNT IS HIDDEN - Not visible on Solana Explorer!");
+        
+        Ok(())
+    }
+
+    /// Decline a refund request (refunder only; rent goes back to the requester)
+    pub fn decline_refund(_ctx: Context<DeclineRefund>) -> Result<()> {
+        msg!("Refund request declined");
+        Ok(())
+    }
+
+    /// Withdraw an open refund request (requester only)
+    pub fn cancel_refund(_ctx: Context<CancelRefund>) -> Result<()> {
+        msg!("Refund request cancelled");
+        Ok(())
+    }
+
+    /// Publish stealth meta-keys (scan + spend) for receiving stealth payments
+    ///
+    /// Senders derive a fresh one-time destination per payment from these
+    /// keys (P = H(r*S)*G + B off-chain), so payments cannot be linked to the
+    /// owner or to each other.
+    pub fn register_stealth_meta(
+        ctx: Context<RegisterStealthMeta>,
+        scan_key: [u8; 32],
+        spend_key: [u8; 32],
+    ) -> Result<()> {
+        require!(
+            scan_key != [0u8; 32] && spend_key != [0u8; 32],
+            ErrorCode::InvalidEncryptionKey
+        );
+        
+        let meta = &mut ctx.accounts.stealth_meta;
+        meta.owner = ctx.accounts.owner.key();
+        meta.scan_key = scan_key;
+        meta.spend_key = spend_key;
+        meta.bump = ctx.bumps.stealth_meta;
+        
+        msg!("Stealth meta-keys registered for {}", meta.owner);
+        Ok(())
+    }
+
+    /// Pay SOL to a one-time stealth destination and announce it
+    ///
+    /// `ephemeral_key` is the sender's R = r*G; `view_tag` is the first byte
+    /// of H(r*S) so recipients can skip most announcements cheaply.
+    pub fn announce_stealth_payment(
+        ctx: Context<AnnounceStealthPayment>,
+        ephemeral_key: [u8; 32],
+        view_tag: u8,
+        amount: u64,
+    ) -> Result<()> {
+        require!(ephemeral_key != [0u8; 32], ErrorCode::InvalidEncryptionKey);
+        require!(
+            amount >= transfer_constants::MIN_AMOUNT,
+            ErrorCode::InvalidAmount
+        );
+        require!(
+            amount <= transfer_constants::MAX_AMOUNT,
+            ErrorCode::InvalidAmount
+        );
+        
+        // Transfer SOL from sender to the one-time destination
+        let cpi_context = CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.sender.to_account_info(),
+                to: ctx.accounts.destination.to_account_info(),
+            },
+        );
+        transfer(cpi_context, amount)?;
+        
+        let announcement = &mut ctx.accounts.announcement;
+        announcement.ephemeral_key = ephemeral_key;
+        announcement.view_tag = view_tag;
+        announcement.destination = ctx.accounts.destination.key();
+        announcement.slot = Clock::get()?.slot;
+        announcement.bump = ctx.bumps.announcement;
+        
+        emit!(StealthAnnounced {
+            ephemeral_key,
+            view_tag,
+            destination: announcement.destination,
+            slot: announcement.slot,
+        });
+        
+        msg!("🥷 Stealth payment announced");
+        Ok(())
+    }
+
+    /// Sweep a stealth destination and close its announcement
+    ///
+    /// Signed with the one-time key the recipient derived; moves all of its
+    /// lamports (plus the announcement rent) to `to`.
+    pub fn sweep_stealth(ctx: Context<SweepStealth>) -> Result<()> {
+        // SECURITY: Emergency pause and circuit breaker (set by the config authority)
+        let config = Config::load_optional(&ctx.accounts.config)?;
+        require_not_paused!(config.as_ref(), pause::WITHDRAWALS);
+        limits::enforce_transfer_rate(config.as_ref(), &ctx.accounts.stats)?;
+        
+        let amount = ctx.accounts.destination.lamports();
+        
+        let cpi_context = CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.destination.to_account_info(),
+                to: ctx.accounts.to.to_account_info(),
+            },
+        );
+        transfer(cpi_context, amount)?;
+        
+        // Protocol statistics (if initialized)
+        stats::record(&ctx.accounts.stats, stats::Activity::Withdrawal)?;
+        
+        msg!("Swept {} lamports from stealth destination", amount);
+        Ok(())
+    }
+
+    /// Create the inbox of incoming transfer notices for an account
+    pub fn initialize_inbox(ctx: Context<InitializeInbox>) -> Result<()> {
+        let inbox = &mut ctx.accounts.inbox;
+        inbox.encrypted_account = ctx.accounts.encrypted_account.key();
+        inbox.total_received = 0;
+        inbox.entries = Vec::new();
+        inbox.bump = ctx.bumps.inbox;
+        
+        ctx.accounts.encrypted_account.record_activity()?;
+        
+        msg!("Inbox created for {}", inbox.encrypted_account);
+        Ok(())
+    }
+
+    /// Drop the oldest `count` notices once the wallet has processed them
+    pub fn prune_inbox(ctx: Context<PruneInbox>, count: u16) -> Result<()> {
+        let inbox = &mut ctx.accounts.inbox;
+        let count = (count as usize).min(inbox.entries.len());
+        inbox.entries.drain(..count);
+        
+        ctx.accounts.encrypted_account.record_activity()?;
+        
+        msg!("Pruned {} inbox notices ({} left)", count, inbox.entries.len());
+        Ok(())
+    }
+
+    /// Create the global withdrawal queue
+    pub fn initialize_withdrawal_queue(ctx: Context<InitializeWithdrawalQueue>) -> Result<()> {
+        let queue = &mut ctx.accounts.withdrawal_queue;
+        queue.next_ticket = 0;
+        queue.pending = 0;
+        queue.bump = ctx.bumps.withdrawal_queue;
+        
+        msg!("Initialized withdrawal queue");
+        Ok(())
+    }
+
+    /// Queue a delayed withdrawal from the shared pool
+    ///
+    /// The hidden balance is debited now (same proof as `withdraw_pool`), but
+    /// the payout to `destination` only happens after a randomized delay, in
+    /// a batch with other withdrawals, so exits do not line up with the
+    /// owner's shielded activity. Bounded by the pool commitment like
+    /// `withdraw_pool`.
+    pub fn queue_withdrawal(
+        ctx: Context<QueueWithdrawal>,
+        amount: u64,
+        new_commitment: [u8; 64],
+        proof_data: Vec<u8>,
+        destination: Pubkey,
+    ) -> Result<()> {
+        // ============================================
+        // INPUT VALIDATION (Checks)
+        // ============================================
+        
+        // SECURITY: Emergency pause and circuit breaker (set by the config authority)
+        let config = Config::load_optional(&ctx.accounts.config)?;
+        require_not_paused!(config.as_ref(), pause::WITHDRAWALS);
+        limits::enforce_transfer_rate(config.as_ref(), &ctx.accounts.stats)?;
+        
+        // Validate amount (prevent overflow and invalid amounts)
+        require!(
+            amount >= transfer_constants::MIN_AMOUNT,
+            ErrorCode::InvalidAmount
+        );
+        require!(
+            amount <= transfer_constants::MAX_AMOUNT,
+            ErrorCode::InvalidAmount
+        );
+        
+        // PRIVACY: Pool exits use the same fixed denominations as deposits
+        require!(
+            pool_constants::DENOMINATIONS.contains(&amount),
+            ErrorCode::InvalidDenomination
+        );
+        
+        // Validate proof data size (DoS protection)
+        require!(
+            proof_data.len() >= proof_constants::MIN_PROOF_DATA_SIZE,
+            ErrorCode::InvalidProof
+        );
+        require!(
+            proof_data.len() <= proof_constants::MAX_PROOF_DATA_SIZE,
+            ErrorCode::InvalidProof
+        );
+        
+        // Validate commitment is well-formed for the configured commitment scheme
+        require!(
+            is_valid_commitment(&new_commitment),
+            ErrorCode::InvalidCommitment
+        );
+        
+        let index = ctx.accounts.encrypted_account.find_asset(&pool_constants::POOL_SOL_ASSET_ID)
+            .ok_or(ErrorCode::AssetNotFound)?;
+        let old_commitment = ctx.accounts.encrypted_account.assets[index].encrypted_balance;
+        
+        // ============================================
+        // ZK PROOF VERIFICATION
+        // ============================================
+        
+        if let Err(e) = verify_withdraw_proof(&proof_data, amount, &old_commitment, &new_commitment) {
+            msg!("⚠️  Proof verification error: {:?}", e);
+            return Err(ErrorCode::InvalidProof.into());
+        }
+        
+        // SECURITY: Pool solvency (aggregate)
+        require!(
+            ctx.accounts.pool_vault.total_shielded >= amount,
+            ErrorCode::InsufficientBalance
+        );
+        
+        // ============================================
+        // STATE UPDATES (Effects)
+        // ============================================
+        
+        let pool = &mut ctx.accounts.pool_vault;
+        pool.total_shielded = pool.total_shielded.checked_sub(amount)
+            .ok_or(ErrorCode::Underflow)?;
+        
+        let account = &mut ctx.accounts.encrypted_account;
+        account.assets[index].encrypted_balance = new_commitment;
+        account.version += 1;
+        account.advance_state_hash(&new_commitment);
+        account.record_activity()?;
+        
+        let queue = &mut ctx.accounts.withdrawal_queue;
+        let ticket = queue.next_ticket;
+        queue.next_ticket += 1;
+        queue.pending += 1;
+        
+        // Per-withdrawal jitter so release times do not reveal queueing times
+        let now = Clock::get()?.slot;
+        let seed = merkle_tree::hash_leaf(&[
+            &ticket.to_le_bytes(),
+            &now.to_le_bytes(),
+            destination.as_ref(),
+            &account.state_hash,
+        ]);
+        let mut jitter_bytes = [0u8; 8];
+        jitter_bytes.copy_from_slice(&seed[..8]);
+        let jitter = u64::from_le_bytes(jitter_bytes) % withdrawal_queue_constants::DELAY_JITTER_SLOTS;
+        
+        let queued = &mut ctx.accounts.queued_withdrawal;
+        queued.ticket = ticket;
+        queued.destination = destination;
+        queued.amount = amount;
+        queued.release_slot = now + withdrawal_queue_constants::MIN_DELAY_SLOTS + jitter;
+        queued.bump = ctx.bumps.queued_withdrawal;
+        
+        // Protocol statistics (if initialized)
+        stats::record(&ctx.accounts.stats, stats::Activity::Withdrawal)?;
+        
+        msg!("⏳ Withdrawal queued (ticket {})", ticket);
+        Ok(())
+    }
+
+    /// Release a batch of matured withdrawals (permissionless crank)
+    ///
+    /// Remaining accounts: (queued_withdrawal, destination) pairs. The batch
+    /// must contain at least MIN_BATCH_SIZE withdrawals unless all of them are
+    /// long overdue. Ticket rent goes to the destination, not the original
+    /// owner, so the payout is not linked back to the owner.
+    pub fn process_withdrawal_batch<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ProcessWithdrawalBatch<'info>>,
+    ) -> Result<()> {
+        // SECURITY: Emergency pause and circuit breaker (set by the config authority)
+        let config = Config::load_optional(&ctx.accounts.config)?;
+        require_not_paused!(config.as_ref(), pause::WITHDRAWALS);
+        limits::enforce_transfer_rate(config.as_ref(), &ctx.accounts.stats)?;
+        
+        let pairs = ctx.remaining_accounts;
+        require!(
+            !pairs.is_empty() && pairs.len() & 1 == 0,
+            ErrorCode::InvalidWithdrawalBatch
+        );
+        let batch_size = pairs.len() / 2;
+        require!(
+            batch_size <= withdrawal_queue_constants::MAX_BATCH_SIZE,
+            ErrorCode::InvalidWithdrawalBatch
+        );
+        
+        let now = Clock::get()?.slot;
+        let pool_info = ctx.accounts.pool_vault.to_account_info();
+        let mut all_stale = true;
+        
+        for pair in pairs.chunks(2) {
+            let (ticket_info, destination_info) = (&pair[0], &pair[1]);
+            let queued: Account<'info, QueuedWithdrawal> = Account::try_from(ticket_info)?;
+            
+            // SECURITY: Only genuine tickets, paid to their own destination
+            let expected = Pubkey::create_program_address(
+                &[pda::QUEUED_WITHDRAWAL_SEED, &queued.ticket.to_le_bytes(), &[queued.bump]],
+                &crate::ID,
+            ).map_err(|_| ErrorCode::InvalidWithdrawalBatch)?;
+            require!(ticket_info.key() == expected, ErrorCode::InvalidWithdrawalBatch);
+            require!(
+                destination_info.key() == queued.destination,
+                ErrorCode::InvalidRecipient
+            );
+            require!(now >= queued.release_slot, ErrorCode::WithdrawalNotReady);
+            
+            if now < queued.release_slot + withdrawal_queue_constants::STALE_AFTER_SLOTS {
+                all_stale = false;
+            }
+            
+            // Program-owned pool: move lamports directly
+            let new_pool_lamports = pool_info.lamports().checked_sub(queued.amount)
+                .ok_or(ErrorCode::Underflow)?;
+            let new_destination_lamports = destination_info.lamports().checked_add(queued.amount)
+                .ok_or(ErrorCode::Overflow)?;
+            **pool_info.try_borrow_mut_lamports()? = new_pool_lamports;
+            **destination_info.try_borrow_mut_lamports()? = new_destination_lamports;
+            
+            queued.close(destination_info.clone())?;
+        }
+        
+        // SECURITY: Small batches would re-link exits to their queueing time
+        require!(
+            batch_size >= withdrawal_queue_constants::MIN_BATCH_SIZE || all_stale,
+            ErrorCode::InvalidWithdrawalBatch
+        );
+        
+        let queue = &mut ctx.accounts.withdrawal_queue;
+        queue.pending = queue.pending.saturating_sub(batch_size as u64);
+        
+        msg!("✅ Released {} queued withdrawals", batch_size);
+        Ok(())
+    }
+
+    /// Confidential transfer with the real recipient hidden among decoys
+    ///
+    /// Remaining accounts: every output EncryptedAccount (real recipient and
+    /// decoys, in any order), matching `output_deltas` and
+    /// `expected_output_versions`. Every output's new balance is computed
+    /// here as old + delta; decoy deltas are commitments to zero, so every
+    /// touched account changes and observers cannot tell which one received
+    /// funds. The proof shows each delta hides zero or the amount and that
+    /// they add up to the amount, so no balance can be overwritten.
+    /// PRIVACY: Amount AND recipient are hidden on-chain!
+    pub fn confidential_decoy_transfer<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ConfidentialDecoyTransfer<'info>>,
+        sender_new_commitment: [u8; 64],
+        output_deltas: Vec<[u8; 64]>,
+        proof_data: Vec<u8>,
+        expected_sender_version: u64,
+        expected_output_versions: Vec<u64>,
+    ) -> Result<()> {
+        // ============================================
+        // INPUT VALIDATION (Checks)
+        // ============================================
+        
+        // SECURITY: Emergency pause and circuit breaker (set by the config authority)
+        let config = Config::load_optional(&ctx.accounts.config)?;
+        require_not_paused!(config.as_ref(), pause::TRANSFERS);
+        limits::enforce_transfer_rate(config.as_ref(), &ctx.accounts.stats)?;
+        
+        // SECURITY: Optimistic concurrency - the proof was built against these
+        // exact states; a concurrent update would make it stale
+        require!(
+            ctx.accounts.sender_account.version == expected_sender_version,
+            ErrorCode::StaleAccountVersion
+        );
+        
+        // SECURITY: Validate sender account ownership
+        require!(
+            ctx.accounts.sender_account.owner == ctx.accounts.sender.key(),
+            ErrorCode::Unauthorized
+        );
+        
+        let output_infos = ctx.remaining_accounts;
+        require!(
+            output_deltas.len() >= decoy_constants::MIN_OUTPUTS
+                && output_deltas.len() <= decoy_constants::MAX_OUTPUTS
+                && output_deltas.len() == output_infos.len()
+                && expected_output_versions.len() == output_infos.len(),
+            ErrorCode::InvalidDecoyOutputs
+        );
+        
+        // Validate proof data size (DoS protection)
+        require!(
+            proof_data.len() >= proof_constants::MIN_PROOF_DATA_SIZE,
+            ErrorCode::InvalidProof
+        );
+        require!(
+            proof_data.len() <= proof_constants::MAX_PROOF_DATA_SIZE,
+            ErrorCode::InvalidProof
+        );
+        
+        // Validate commitments are well-formed for the configured commitment scheme
+        require!(
+            is_valid_commitment(&sender_new_commitment),
+            ErrorCode::InvalidCommitment
+        );
+        for delta in &output_deltas {
+            require!(
+                is_valid_commitment(delta),
+                ErrorCode::InvalidCommitment
+            );
+        }
+        
+        let sender_old_commitment = ctx.accounts.sender_account.encrypted_balance;
+        require!(
+            sender_old_commitment != [0u8; 64],
+            ErrorCode::InvalidCommitment
+        );
+        
+        // Load outputs: genuine, distinct encrypted accounts other than the sender
+        let sender_key = ctx.accounts.sender_account.key();
+        let mut outputs: Vec<Account<'info, EncryptedAccount>> = Vec::with_capacity(output_infos.len());
+        for (info, expected_version) in output_infos.iter().zip(&expected_output_versions) {
+            require!(info.key() != sender_key, ErrorCode::InvalidRecipient);
+            require!(info.is_writable, ErrorCode::InvalidDecoyOutputs);
+            require!(
+                outputs.iter().all(|output| output.key() != info.key()),
+                ErrorCode::InvalidDecoyOutputs
+            );
+            
+            let output: Account<'info, EncryptedAccount> = Account::try_from(info)?;
+            let expected = Pubkey::create_program_address(
+                &[
+                    pda::ENCRYPTED_ACCOUNT_SEED,
+                    output.seed_owner.as_ref(),
+                    &output.account_index.to_le_bytes(),
+                    &[output.bump],
+                ],
+                &crate::ID,
+            ).map_err(|_| ErrorCode::InvalidDecoyOutputs)?;
+            require!(info.key() == expected, ErrorCode::InvalidDecoyOutputs);
+            require!(output.version == *expected_version, ErrorCode::StaleAccountVersion);
+            outputs.push(output);
+        }
+        
+        // ============================================
+        // ZK PROOF VERIFICATION
+        // ============================================
+        
+        let amount_commitment = match proof_verification::extract_amount_commitment(&proof_data) {
+            Ok(commitment) => commitment,
+            Err(e) => {
+                msg!("⚠️  Failed to extract amount commitment from proof: {:?}", e);
+                return Err(ErrorCode::InvalidProof.into());
+            }
+        };
+        
+        let pairs: Vec<([u8; 64], [u8; 64])> = outputs.iter()
+            .zip(&output_deltas)
+            .map(|(output, delta)| (output.encrypted_balance, *delta))
+            .collect();
+        
+        if let Err(e) = verify_decoy_transfer_proof(
+            &proof_data,
+            &amount_commitment,
+            &sender_old_commitment,
+            &sender_new_commitment,
+            &pairs,
+        ) {
+            msg!("⚠️  Proof verification error: {:?}", e);
+            return Err(ErrorCode::InvalidProof.into());
+        }
+        
+        // ============================================
+        // STATE UPDATES (Effects)
+        // ============================================
+        
+        let sender_account = &mut ctx.accounts.sender_account;
+        sender_account.encrypted_balance = sender_new_commitment;
+        sender_account.version += 1;
+        sender_account.advance_state_hash(&sender_new_commitment);
+        
+        // SECURITY: new = old + proven delta, computed here so the sender
+        // never chooses another account's balance
+        for (output, delta) in outputs.iter_mut().zip(&output_deltas) {
+            let new_commitment = match pedersen::add(&output.encrypted_balance, delta) {
+                Ok(commitment) => commitment,
+                Err(e) => {
+                    msg!("⚠️  Commitment addition error: {:?}", e);
+                    return Err(ErrorCode::InvalidCommitment.into());
+                }
+            };
+            output.encrypted_balance = new_commitment;
+            output.version += 1;
+            output.advance_state_hash(&new_commitment);
+            output.exit(&crate::ID)?;
+        }
+        
+        // Protocol statistics (if initialized)
+        stats::record(&ctx.accounts.stats, stats::Activity::Transfer)?;
+        
+        msg!("✅ Confidential decoy transfer completed");
+        msg!("   Outputs: {}", outputs.len());
+        msg!("   ❌ AMOUNT AND RECIPIENT ARE HIDDEN!");
+        
+        Ok(())
+    }
+
+    /// Confidential transfer submitted by a relayer on the sender's behalf
+    ///
+    /// The sender authorizes the transfer with an Ed25519 signature over the
+    /// intent (see relayer.rs), verified by an Ed25519 program instruction
+    /// placed right before this one. The relayer signs and pays the fees, so
+    /// the sender's key is not linked to the transaction.
+    /// PRIVACY: Amount is NEVER revealed on-chain!
+    pub fn relayed_confidential_transfer(
+        ctx: Context<RelayedConfidentialTransfer>,
+        sender_new_commitment: [u8; 64],
+        recipient_new_commitment: [u8; 64],
+        proof_data: Vec<u8>,
+        expiry_slot: u64,
+        expected_sender_version: u64,
+        expected_recipient_version: u64,
+    ) -> Result<()> {
+        // ============================================
+        // INPUT VALIDATION (Checks)
+        // ============================================
+        
+        // SECURITY: Emergency pause and subsystem rollout (set by the config authority)
+        let config = Config::load_optional(&ctx.accounts.config)?;
+        require_not_paused!(config.as_ref(), pause::TRANSFERS);
+        require_feature!(config.as_ref(), features::RELAYERS);
+        limits::enforce_transfer_rate(config.as_ref(), &ctx.accounts.stats)?;
+        
+        // SECURITY: Optimistic concurrency - the proof was built against these
+        // exact states; a concurrent update would make it stale
+        require!(
+            ctx.accounts.sender_account.version == expected_sender_version,
+            ErrorCode::StaleAccountVersion
+        );
+        require!(
+            ctx.accounts.recipient_account.version == expected_recipient_version,
+            ErrorCode::StaleAccountVersion
+        );
+        
+        require!(
+            ctx.accounts.sender_account.key() != ctx.accounts.recipient_account.key(),
+            ErrorCode::InvalidRecipient
+        );
+        require!(
+            Clock::get()?.slot <= expiry_slot,
+            ErrorCode::IntentExpired
+        );
+        
+        // Validate proof data size (DoS protection)
+        require!(
+            proof_data.len() >= proof_constants::MIN_PROOF_DATA_SIZE,
+            ErrorCode::InvalidProof
+        );
+        require!(
+            proof_data.len() <= proof_constants::MAX_PROOF_DATA_SIZE,
+            ErrorCode::InvalidProof
+        );
+        
+        // Validate commitments are well-formed for the configured commitment scheme
+        require!(
+            is_valid_commitment(&sender_new_commitment),
+            ErrorCode::InvalidCommitment
+        );
+        require!(
+            is_valid_commitment(&recipient_new_commitment),
+            ErrorCode::InvalidCommitment
+        );
+        
+        let sender_old_commitment = ctx.accounts.sender_account.encrypted_balance;
+        let recipient_old_commitment = ctx.accounts.recipient_account.encrypted_balance;
+        require!(
+            sender_old_commitment != [0u8; 64],
+            ErrorCode::InvalidCommitment
+        );
+        
+        // ============================================
+        // SENDER AUTHORIZATION (intent signature)
+        // ============================================
+        
+        let sender_account_key = ctx.accounts.sender_account.key();
+        let recipient_account_key = ctx.accounts.recipient_account.key();
+        let intent = relayer::TransferIntent {
+            sender_account: &sender_account_key,
+            recipient_account: &recipient_account_key,
+            sender_new_commitment: &sender_new_commitment,
+            recipient_new_commitment: &recipient_new_commitment,
+            proof_data: &proof_data,
+            sender_version: ctx.accounts.sender_account.version,
+            expiry_slot,
+        };
+        relayer::verify_ed25519_authorization(
+            &ctx.accounts.instructions_sysvar,
+            &ctx.accounts.sender_account.owner,
+            &intent.message(),
+        )?;
+        
+        // ============================================
+        // ZK PROOF VERIFICATION
+        // ============================================
+        
+        let amount_commitment = match proof_verification::extract_amount_commitment(&proof_data) {
+            Ok(commitment) => commitment,
+            Err(e) => {
+                msg!("⚠️  Failed to extract amount commitment from proof: {:?}", e);
+                return Err(ErrorCode::InvalidProof.into());
+            }
+        };
+        
+        if let Err(e) = verify_transfer_proof(
+            &proof_data,
+            &amount_commitment,
+            &sender_new_commitment,
+            &sender_old_commitment,
+            &recipient_old_commitment,
+            &recipient_new_commitment,
+        ) {
+            msg!("⚠️  Proof verification error: {:?}", e);
+            return Err(ErrorCode::InvalidProof.into());
+        }
+        
+        // Update encrypted balances
+        let sender_account = &mut ctx.accounts.sender_account;
+        sender_account.encrypted_balance = sender_new_commitment;
+        sender_account.version += 1;
+        sender_account.advance_state_hash(&sender_new_commitment);
+        
+        let recipient_account = &mut ctx.accounts.recipient_account;
+        recipient_account.encrypted_balance = recipient_new_commitment;
+        recipient_account.version += 1;
+        recipient_account.advance_state_hash(&recipient_new_commitment);
+        
+        // Protocol statistics (if initialized)
+        stats::record(&ctx.accounts.stats, stats::Activity::Transfer)?;
+        
+        msg!("✅ Relayed confidential transfer completed");
+        msg!("   ❌ AMOUNT IS HIDDEN - sender did not sign the transaction!");
+        
+        Ok(())
+    }
+
+    /// Publish the Merkle root of the approved association set
+    ///
+    /// Pass all zeros to disable associated spends until a new set is
+    /// published. Proofs against a replaced root stop verifying, so wallets
+    /// must re-prove against the current one.
+    pub fn set_association_root(ctx: Context<UpdateConfig>, association_root: [u8; 32]) -> Result<()> {
+        // SECURITY: Once a timelock is set, the association root changes only through it
+        require!(
+            ctx.accounts.config.timelock_delay_slots == 0,
+            ErrorCode::TimelockRequired
+        );
+        
+        ctx.accounts.config.association_root = association_root;
+        
+        emit!(AssociationRootUpdated {
+            association_root,
+            slot: Clock::get()?.slot,
+        });
+        
+        msg!("Association set root updated");
+        Ok(())
+    }
+
+    /// Spend a note while proving it belongs to the approved association set
+    ///
+    /// Same as `spend_note`, but the proof additionally shows the note's leaf
+    /// is in the association set published in the config. Observers learn
+    /// that the funds come from an approved deposit, not which one.
+    pub fn spend_note_associated(
+        ctx: Context<SpendNoteAssociated>,
+        root: [u8; 32],
+        nullifier: [u8; 32],
+        amount: u64,
+        proof_data: Vec<u8>,
+    ) -> Result<()> {
+        // ============================================
+        // INPUT VALIDATION (Checks)
+        // ============================================
+        
+        // SECURITY: Emergency pause and circuit breaker (set by the config authority)
+        require_not_paused!(Some(&*ctx.accounts.config), pause::WITHDRAWALS);
+        limits::enforce_transfer_rate(Some(&*ctx.accounts.config), &ctx.accounts.stats)?;
+        
+        require!(
+            amount >= transfer_constants::MIN_AMOUNT,
+            ErrorCode::InvalidAmount
+        );
+        
+        // SECURITY: Proofs must be against a root the pool actually had
+        require!(
+            ctx.accounts.note_pool.is_known_root(&root),
+            ErrorCode::UnknownRoot
+        );
+        
+        let association_root = ctx.accounts.config.association_root;
+        require!(
+            association_root != [0u8; 32],
+            ErrorCode::AssociationSetNotConfigured
+        );
+        
+        // ============================================
+        // ZK PROOF VERIFICATION
+        // ============================================
+        
+        let recipient = ctx.accounts.recipient.key();
+        if let Err(e) = verify_association_spend_proof(
+            &proof_data,
+            &root,
+            &association_root,
+            &nullifier,
+            amount,
+            &recipient.to_bytes(),
+        ) {
+            msg!("⚠️  Proof verification error: {:?}", e);
+            return Err(ErrorCode::InvalidProof.into());
+        }
+        
+        // SECURITY: Pairing check against the association circuit's key
+        groth16::verify(
+            &ctx.accounts.verifying_key,
+            &proof_data,
+            &[
+                groth16::field_element(&root),
+                groth16::field_element(&association_root),
+                groth16::field_element(&nullifier),
+                groth16::amount_element(amount),
+                groth16::field_element(&recipient.to_bytes()),
+            ],
+        )?;
+        
+        // ============================================
+        // STATE UPDATES (Effects)
+        // ============================================
+        
+        let spent = &mut ctx.accounts.nullifier_record;
+        spent.nullifier = nullifier;
+        spent.slot = Clock::get()?.slot;
+        spent.bump = ctx.bumps.nullifier_record;
+        
+        let pool = &mut ctx.accounts.note_pool;
+        pool.total_deposited = pool.total_deposited.checked_sub(amount)
+            .ok_or(ErrorCode::InsufficientBalance)?;
+        
+        // ============================================
+        // EXTERNAL CALLS (Interactions)
+        // ============================================
+        
+        // Program-owned pool: move lamports directly
+        let pool_info = ctx.accounts.note_pool.to_account_info();
+        let recipient_info = ctx.accounts.recipient.to_account_info();
+        let new_pool_lamports = pool_info.lamports().checked_sub(amount)
+            .ok_or(ErrorCode::Underflow)?;
+        let new_recipient_lamports = recipient_info.lamports().checked_add(amount)
+            .ok_or(ErrorCode::Overflow)?;
+        **pool_info.try_borrow_mut_lamports()? = new_pool_lamports;
+        **recipient_info.try_borrow_mut_lamports()? = new_recipient_lamports;
+        
+        emit!(NoteSpent {
+            nullifier,
+            root,
+            slot: spent.slot,
+        });
+        
+        // Protocol statistics (if initialized)
+        stats::record(&ctx.accounts.stats, stats::Activity::Withdrawal)?;
+        
+        msg!("✅ Note spent from the approved association set");
+        Ok(())
+    }
+
+    /// Register a viewing key for an encrypted account
+    ///
+    /// The viewing key (an X25519 public key, e.g. an accountant's) only
+    /// receives re-encrypted balance openings via `publish_balance_view`;
+    /// it carries no spend or owner authority.
+    pub fn register_viewing_key(ctx: Context<RegisterViewingKey>, viewing_key: [u8; 32]) -> Result<()> {
+        require!(viewing_key != [0u8; 32], ErrorCode::InvalidViewingKey);
+        
+        let view = &mut ctx.accounts.viewing_key;
+        view.encrypted_account = ctx.accounts.encrypted_account.key();
+        view.viewing_key = viewing_key;
+        view.granted_slot = Clock::get()?.slot;
+        view.bump = ctx.bumps.viewing_key;
+        
+        ctx.accounts.encrypted_account.record_activity()?;
+        
+        msg!("Viewing key registered for {}", view.encrypted_account);
+        Ok(())
+    }
+
+    /// Replace the viewing key (e.g., when changing accountants)
+    ///
+    /// Views published before the change remain readable by the old key.
+    pub fn set_viewing_key(ctx: Context<UpdateViewingKey>, viewing_key: [u8; 32]) -> Result<()> {
+        require!(viewing_key != [0u8; 32], ErrorCode::InvalidViewingKey);
+        
+        let view = &mut ctx.accounts.viewing_key;
+        view.viewing_key = viewing_key;
+        view.granted_slot = Clock::get()?.slot;
+        
+        ctx.accounts.encrypted_account.record_activity()?;
+        
+        msg!("Viewing key updated for {}", view.encrypted_account);
+        Ok(())
+    }
+
+    /// Remove the viewing key and reclaim its rent
+    pub fn revoke_viewing_key(_ctx: Context<RevokeViewingKey>) -> Result<()> {
+        msg!("Viewing key revoked");
+        Ok(())
+    }
+
+    /// Publish the current balance opening encrypted under the viewing key
+    ///
+    /// `view` encrypts balance (u64 LE) || blinding factor to the registered
+    /// viewing key (same envelope as transfer memos). The event pins the
+    /// account version and state hash, so the key holder can check which
+    /// on-chain balance the opening belongs to.
+    pub fn publish_balance_view(ctx: Context<UpdateViewingKey>, view: EncryptedMemo) -> Result<()> {
+        require!(
+            view.ephemeral_key != [0u8; 32],
+            ErrorCode::InvalidViewingKey
+        );
+        
+        let encrypted_account = &ctx.accounts.encrypted_account;
+        emit!(BalanceView {
+            encrypted_account: encrypted_account.key(),
+            viewing_key: ctx.accounts.viewing_key.viewing_key,
+            version: encrypted_account.version,
+            state_hash: encrypted_account.state_hash,
+            view,
+        });
+        
+        msg!("Balance view published (version {})", encrypted_account.version);
+        
+        ctx.accounts.encrypted_account.record_activity()?;
+        Ok(())
+    }
+
+    /// Set the amount above which transfers need a travel-rule envelope
+    ///
+    /// 0 disables the requirement. Confidential transfers hide their amount,
+    /// so any non-zero threshold makes the envelope mandatory for them.
+    pub fn set_travel_rule_threshold(ctx: Context<UpdateConfig>, threshold: u64) -> Result<()> {
+        // SECURITY: Once a timelock is set, the threshold changes only through it
+        require!(
+            ctx.accounts.config.timelock_delay_slots == 0,
+            ErrorCode::TimelockRequired
+        );
+        
+        ctx.accounts.config.travel_rule_threshold = threshold;
+        
+        msg!("Travel-rule threshold: {}", threshold);
+        Ok(())
+    }
+
+    /// Configure the sanctions oracle consulted by withdrawals
+    ///
+    /// `Pubkey::default()` turns the check off. `max_age_slots` bounds how
+    /// old an attestation may be when a withdrawal relies on it.
+    pub fn set_sanctions_oracle(
+        ctx: Context<UpdateConfig>,
+        oracle: Pubkey,
+        max_age_slots: u64,
+    ) -> Result<()> {
+        // SECURITY: Once a timelock is set, the oracle changes only through it
+        require!(
+            ctx.accounts.config.timelock_delay_slots == 0,
+            ErrorCode::TimelockRequired
+        );
+        require!(
+            oracle == Pubkey::default() || max_age_slots > 0,
+            ErrorCode::SanctionsAttestationStale
+        );
+        
+        let config = &mut ctx.accounts.config;
+        config.sanctions_oracle = oracle;
+        config.attestation_max_age = max_age_slots;
+        
+        msg!("Sanctions oracle set to {} (max age {} slots)", oracle, max_age_slots);
+        Ok(())
+    }
+
+    /// Record the oracle's screening result for a destination address
+    pub fn record_attestation(ctx: Context<RecordAttestation>, cleared: bool) -> Result<()> {
+        let attestation = &mut ctx.accounts.attestation;
+        attestation.oracle = ctx.accounts.oracle.key();
+        attestation.destination = ctx.accounts.destination.key();
+        attestation.cleared = cleared;
+        attestation.slot = Clock::get()?.slot;
+        attestation.bump = ctx.bumps.attestation;
+        
+        msg!("Attestation recorded for {} (cleared: {})", attestation.destination, cleared);
+        Ok(())
+    }
+
+    /// Refresh (or change) an existing screening result
+    pub fn update_attestation(ctx: Context<UpdateAttestation>, cleared: bool) -> Result<()> {
+        let attestation = &mut ctx.accounts.attestation;
+        attestation.cleared = cleared;
+        attestation.slot = Clock::get()?.slot;
+        
+        msg!("Attestation updated for {} (cleared: {})", attestation.destination, cleared);
+        Ok(())
+    }
+
+    /// Remove an attestation and reclaim its rent
+    pub fn close_attestation(_ctx: Context<CloseAttestation>) -> Result<()> {
+        msg!("Attestation closed");
+        Ok(())
+    }
+
+    /// Set the per-epoch cap on an account's outgoing confidential transfers
+    ///
+    /// 0 disables the limit. While enabled, every confidential transfer must
+    /// update the sender's encrypted epoch total with a velocity proof.
+    pub fn set_epoch_transfer_limit(ctx: Context<UpdateConfig>, limit: u64) -> Result<()> {
+        // SECURITY: Once a timelock is set, the limit changes only through it
+        require!(
+            ctx.accounts.config.timelock_delay_slots == 0,
+            ErrorCode::TimelockRequired
+        );
+        
+        ctx.accounts.config.epoch_transfer_limit = limit;
+        
+        msg!("Epoch transfer limit: {}", limit);
+        Ok(())
+    }
+
+    /// Create the epoch total accumulator of an encrypted account
+    pub fn initialize_transfer_accumulator(ctx: Context<InitializeTransferAccumulator>) -> Result<()> {
+        let accumulator = &mut ctx.accounts.accumulator;
+        accumulator.encrypted_account = ctx.accounts.encrypted_account.key();
+        accumulator.epoch = Clock::get()?.epoch;
+        accumulator.total_commitment = [0u8; 64];
+        accumulator.bump = ctx.bumps.accumulator;
+        
+        ctx.accounts.encrypted_account.record_activity()?;
+        
+        msg!("Transfer accumulator initialized (epoch {})", accumulator.epoch);
+        Ok(())
+    }
+
+    /// Require a credential token for confidential transfers
+    ///
+    /// `kyc_mint` should be a NonTransferable (soulbound) mint issued by the
+    /// deployment's KYC provider; `Pubkey::default()` turns gating off.
+    pub fn set_kyc_gating(
+        ctx: Context<UpdateConfig>,
+        kyc_mint: Pubkey,
+        require_recipient: bool,
+    ) -> Result<()> {
+        // SECURITY: Once a timelock is set, gating changes only through it
+        require!(
+            ctx.accounts.config.timelock_delay_slots == 0,
+            ErrorCode::TimelockRequired
+        );
+        
+        let config = &mut ctx.accounts.config;
+        config.kyc_mint = kyc_mint;
+        config.kyc_require_recipient = require_recipient;
+        
+        msg!("KYC gating mint: {} (recipient required: {})", kyc_mint, require_recipient);
+        Ok(())
+    }
+
+    /// Set the regulator quorum allowed to order disclosures
+    ///
+    /// `threshold` of the `regulators` must sign `request_disclosure`; owners
+    /// then have `window_slots` to answer. An empty set disables disclosures.
+    pub fn set_regulators(
+        ctx: Context<UpdateConfig>,
+        regulators: Vec<Pubkey>,
+        threshold: u8,
+        window_slots: u64,
+    ) -> Result<()> {
+        // SECURITY: Once a timelock is set, the quorum changes only through it
+        require!(
+            ctx.accounts.config.timelock_delay_slots == 0,
+            ErrorCode::TimelockRequired
+        );
+        let params = RegulatorParams {
+            regulators,
+            threshold,
+            window_slots,
+        };
+        validate_regulators(&params)?;
+        
+        let config = &mut ctx.accounts.config;
+        config.apply_regulators(&params);
+        
+        msg!("Regulator quorum: {} of {}", threshold, config.regulators.len());
+        Ok(())
+    }
+
+    /// Order the owner of an encrypted account to open a commitment
+    ///
+    /// Regulators sign the transaction and are passed as remaining accounts;
+    /// at least `regulator_threshold` distinct configured regulators must
+    /// sign. `commitment` may be the current balance or any past one from
+    /// the account's history. Unanswered orders freeze the account once the
+    /// deadline passes (see `enforce_disclosure`).
+    pub fn request_disclosure(
+        ctx: Context<RequestDisclosure>,
+        case_id: [u8; 32],
+        commitment: [u8; 64],
+    ) -> Result<()> {
+        let config = &ctx.accounts.config;
+        require!(
+            config.regulator_threshold > 0,
+            ErrorCode::InvalidRegulatorSet
+        );
+        require!(is_valid_commitment(&commitment), ErrorCode::InvalidCommitment);
+        
+        // SECURITY: Count each configured regulator signature once
+        let mut approvals: Vec<Pubkey> = Vec::with_capacity(config.regulators.len());
+        for info in ctx.remaining_accounts.iter() {
+            if info.is_signer
+                && config.regulators.contains(info.key)
+                && !approvals.contains(info.key)
+            {
+                approvals.push(*info.key);
+            }
+        }
+        require!(
+            approvals.len() >= config.regulator_threshold as usize,
+            ErrorCode::DisclosureQuorumNotMet
+        );
+        
+        let now = Clock::get()?.slot;
+        let request = &mut ctx.accounts.disclosure_request;
+        request.encrypted_account = ctx.accounts.encrypted_account.key();
+        request.case_id = case_id;
+        request.commitment = commitment;
+        request.payer = ctx.accounts.payer.key();
+        request.requested_slot = now;
+        request.deadline_slot = now.checked_add(config.disclosure_window)
+            .ok_or(ErrorCode::Overflow)?;
+        request.status = DisclosureStatus::Pending;
+        request.bump = ctx.bumps.disclosure_request;
+        
+        emit!(DisclosureRequested {
+            disclosure_request: request.key(),
+            encrypted_account: request.encrypted_account,
+            case_id,
+            commitment,
+            deadline_slot: request.deadline_slot,
+        });
+        
+        msg!("Disclosure ordered ({} approvals), deadline slot {}", approvals.len(), request.deadline_slot);
+        Ok(())
+    }
+
+    /// Answer a disclosure order with a verifiable opening of its commitment
+    ///
+    /// Discloses the amount and proves the commitment opens to it, without
+    /// revealing the blinding factor. Must land before the deadline.
+    pub fn answer_disclosure(
+        ctx: Context<AnswerDisclosure>,
+        amount: u64,
+        proof_data: Vec<u8>,
+    ) -> Result<()> {
+        let request = &mut ctx.accounts.disclosure_request;
+        require!(
+            request.status == DisclosureStatus::Pending,
+            ErrorCode::DisclosureNotPending
+        );
+        require!(
+            Clock::get()?.slot <= request.deadline_slot,
+            ErrorCode::DisclosureDeadlinePassed
+        );
+        
+        if let Err(e) = verify_opening_proof(&proof_data, &request.commitment, amount) {
+            msg!("⚠️  Opening proof error: {:?}", e);
+            return Err(ErrorCode::InvalidProof.into());
+        }
+        
+        request.status = DisclosureStatus::Answered;
+        
+        emit!(DisclosureAnswered {
+            disclosure_request: request.key(),
+            encrypted_account: request.encrypted_account,
+            case_id: request.case_id,
+            commitment: request.commitment,
+            amount,
+        });
+        
+        ctx.accounts.encrypted_account.record_activity()?;
+        
+        msg!("Disclosure answered");
+        Ok(())
+    }
+
+    /// Freeze an account whose disclosure order expired unanswered
+    ///
+    /// Permissionless once the deadline has passed.
+    pub fn enforce_disclosure(ctx: Context<EnforceDisclosure>) -> Result<()> {
+        let request = &mut ctx.accounts.disclosure_request;
+        require!(
+            request.status == DisclosureStatus::Pending,
+            ErrorCode::DisclosureNotPending
+        );
+        require!(
+            Clock::get()?.slot > request.deadline_slot,
+            ErrorCode::DisclosureDeadlineNotReached
+        );
+        
+        request.status = DisclosureStatus::Enforced;
+        ctx.accounts.encrypted_account.frozen = true;
+        
+        msg!("⚠️  Disclosure deadline missed - account {} frozen", request.encrypted_account);
+        Ok(())
+    }
+
+    /// Reclaim the rent of a settled disclosure order
+    pub fn close_disclosure(ctx: Context<CloseDisclosure>) -> Result<()> {
+        require!(
+            ctx.accounts.disclosure_request.status != DisclosureStatus::Pending,
+            ErrorCode::DisclosureNotPending
+        );
+        
+        msg!("Disclosure order closed");
+        Ok(())
+    }
+
+    /// Set the compliance authority (Pubkey::default() disables freezing)
+    pub fn set_compliance_authority(
+        ctx: Context<UpdateConfig>,
+        compliance_authority: Pubkey,
+    ) -> Result<()> {
+        // SECURITY: Once a timelock is set, the authority changes only through it
+        require!(
+            ctx.accounts.config.timelock_delay_slots == 0,
+            ErrorCode::TimelockRequired
+        );
+        
+        ctx.accounts.config.compliance_authority = compliance_authority;
+        
+        msg!("Compliance authority set to {}", compliance_authority);
+        Ok(())
+    }
+
+    /// Freeze an account on a legal order (compliance authority only)
+    ///
+    /// Frozen accounts cannot send, withdraw or spend; incoming transfers
+    /// still land. `case_id` references the order off-chain.
+    pub fn freeze_account(ctx: Context<SetAccountFrozen>, case_id: [u8; 32]) -> Result<()> {
+        let account = &mut ctx.accounts.encrypted_account;
+        require!(!account.frozen, ErrorCode::AccountFrozen);
+        account.frozen = true;
+        
+        // COMPLIANCE: Record who froze the account and under which order
+        emit!(AccountFreezeChanged {
+            encrypted_account: account.key(),
+            frozen: true,
+            case_id,
+            authority: ctx.accounts.compliance_authority.key(),
+            slot: Clock::get()?.slot,
+        });
+        
+        msg!("⚠️  Account {} frozen by compliance authority", account.key());
+        Ok(())
+    }
+
+    /// Lift a freeze (compliance authority only)
+    ///
+    /// Also lifts freezes from missed disclosure deadlines.
+    pub fn unfreeze_account(ctx: Context<SetAccountFrozen>, case_id: [u8; 32]) -> Result<()> {
+        let account = &mut ctx.accounts.encrypted_account;
+        require!(account.frozen, ErrorCode::AccountNotFrozen);
+        account.frozen = false;
+        
+        // COMPLIANCE: Record who lifted the freeze and under which order
+        emit!(AccountFreezeChanged {
+            encrypted_account: account.key(),
+            frozen: false,
+            case_id,
+            authority: ctx.accounts.compliance_authority.key(),
+            slot: Clock::get()?.slot,
+        });
+        
+        msg!("Account {} unfrozen", account.key());
+        Ok(())
+    }
+
+    /// Publish the Merkle root of the sorted deny list
+    ///
+    /// While set, SOL deposits and withdrawals need an exclusion proof for
+    /// the wallet involved, checked against the exclusion circuit's key
+    /// (set_note_verifying_key). All zeros turns the requirement off.
+    pub fn set_deny_list_root(ctx: Context<UpdateConfig>, deny_list_root: [u8; 32]) -> Result<()> {
+        // SECURITY: Once a timelock is set, the root changes only through it
+        require!(
+            ctx.accounts.config.timelock_delay_slots == 0,
+            ErrorCode::TimelockRequired
+        );
+        
+        ctx.accounts.config.deny_list_root = deny_list_root;
+        
+        msg!("Deny list root updated");
+        Ok(())
+    }
+
+    /// Export a verifiable report over the account's recent balance history
+    ///
+    /// `entries` open the commitments of the last `entries.len()` versions,
+    /// oldest first; `start_state_hash` is the state hash just before the
+    /// first of them. Replaying the chain must reproduce the current state
+    /// hash, which proves the commitments are the account's real history.
+    /// Every opening must fall in [min_bound, max_bound]; the resulting
+    /// `DisclosureReport` record attests e.g. "balance never exceeded X"
+    /// over that window.
+    pub fn export_disclosure_report(
+        ctx: Context<ExportDisclosureReport>,
+        report_id: u64,
+        start_state_hash: [u8; 32],
+        entries: Vec<BalanceOpening>,
+        min_bound: u64,
+        max_bound: u64,
+    ) -> Result<()> {
+        // ============================================
+        // INPUT VALIDATION (Checks)
+        // ============================================
+        
+        require!(
+            !entries.is_empty() && entries.len() <= report_constants::MAX_REPORT_ENTRIES,
+            ErrorCode::InvalidDisclosureReport
+        );
+        require!(min_bound <= max_bound, ErrorCode::InvalidDisclosureReport);
+        
+        let account = &ctx.accounts.encrypted_account;
+        let to_version = account.version;
+        let from_version = to_version
+            .checked_sub(entries.len() as u64 - 1)
+            .filter(|&version| version > 0)
+            .ok_or(ErrorCode::InvalidDisclosureReport)?;
+        
+        // ============================================
+        // HISTORY + OPENING VERIFICATION
+        // ============================================
+        
+        let mut state_hash = start_state_hash;
+        for (version, entry) in (from_version..=to_version).zip(entries.iter()) {
+            require!(
+                entry.amount >= min_bound && entry.amount <= max_bound,
+                ErrorCode::InvalidDisclosureReport
+            );
+            if let Err(e) = verify_opening_proof(&entry.proof, &entry.commitment, entry.amount) {
+                msg!("⚠️  Opening proof error at version {}: {:?}", version, e);
+                return Err(ErrorCode::InvalidProof.into());
+            }
+            state_hash = EncryptedAccount::next_state_hash(&state_hash, &entry.commitment, version);
+        }
+        
+        // SECURITY: The replayed chain must end at the on-chain state hash
+        require!(
+            state_hash == account.state_hash,
+            ErrorCode::InvalidDisclosureReport
+        );
+        
+        // ============================================
+        // STATE UPDATES (Effects)
+        // ============================================
+        
+        let report = &mut ctx.accounts.report;
+        report.encrypted_account = account.key();
+        report.owner = ctx.accounts.owner.key();
+        report.report_id = report_id;
+        report.from_version = from_version;
+        report.to_version = to_version;
+        report.start_state_hash = start_state_hash;
+        report.end_state_hash = state_hash;
+        report.min_bound = min_bound;
+        report.max_bound = max_bound;
+        report.slot = Clock::get()?.slot;
+        report.bump = ctx.bumps.report;
+        
+        emit!(DisclosureReportExported {
+            report: report.key(),
+            encrypted_account: report.encrypted_account,
+            from_version,
+            to_version,
+            min_bound,
+            max_bound,
+        });
+        
+        ctx.accounts.encrypted_account.record_activity()?;
+        
+        msg!("✅ Disclosure report {} over versions {}..={}", report_id, from_version, to_version);
+        Ok(())
+    }
+
+    /// Delete a disclosure report and reclaim its rent
+    pub fn close_disclosure_report(_ctx: Context<CloseDisclosureReport>) -> Result<()> {
+        msg!("Disclosure report closed");
+        Ok(())
+    }
+
+    /// Cap the shielded balance of every account
+    ///
+    /// While non-zero, deposits must prove the new balance stays at or
+    /// below `max_balance` without revealing it. 0 removes the cap.
+    pub fn set_max_account_balance(ctx: Context<UpdateConfig>, max_balance: u64) -> Result<()> {
+        // SECURITY: Once a timelock is set, the cap changes only through it
+        require!(
+            ctx.accounts.config.timelock_delay_slots == 0,
+            ErrorCode::TimelockRequired
+        );
+        
+        ctx.accounts.config.max_account_balance = max_balance;
+        
+        msg!("Per-account balance cap: {}", max_balance);
+        Ok(())
+    }
+
+    /// Tune the amount ceiling, proof size bounds and range-proof width
+    ///
+    /// Limits can only be tightened within the compiled-in bounds; a zero
+    /// field restores the default. Instructions that read the config PDA
+    /// enforce them (see `limits`).
+    pub fn update_config(ctx: Context<UpdateConfig>, params: ConfigParams) -> Result<()> {
+        // SECURITY: Once a timelock is set, limits only change through
+        // propose_config_change / execute_config_change
+        require!(
+            ctx.accounts.config.timelock_delay_slots == 0,
+            ErrorCode::TimelockRequired
+        );
+        limits::validate_params(&params)?;
+        
+        ctx.accounts.config.apply_limits(&params);
+        
+        msg!("Config limits updated");
+        msg!("   Max amount: {} (0 = default)", params.max_amount);
+        msg!("   Proof size: {}..={} (0 = default)", params.min_proof_data_size, params.max_proof_data_size);
+        msg!("   Range bits: {} (0 = any)", params.range_bits);
+        msg!("   Transfers per slot: {} (0 = unlimited)", params.max_transfers_per_slot);
+        Ok(())
+    }
+
+    /// Pause deposits, transfers and/or withdrawals (incident response)
+    ///
+    /// `paused` is a bitmask of `pause::DEPOSITS`, `pause::TRANSFERS` and
+    /// `pause::WITHDRAWALS`; `pause::WITHDRAW_ONLY` keeps the exit open.
+    /// 0 resumes everything.
+    pub fn set_pause_flags(ctx: Context<UpdateConfig>, paused: u8) -> Result<()> {
+        require!(paused & !pause::ALL == 0, ErrorCode::InvalidConfigParams);
+        
+        ctx.accounts.config.paused = paused;
+        
+        msg!("Pause flags set: {:#05b}", paused);
+        Ok(())
+    }
+
+    /// Replace the whitelist of external verifier programs
+    ///
+    /// Transfers may route their proof to any listed program (see
+    /// `verifier`). An empty list leaves only the built-in verifier.
+    pub fn set_verifier_programs(ctx: Context<UpdateConfig>, programs: Vec<Pubkey>) -> Result<()> {
+        // SECURITY: Once a timelock is set, verifiers only change through it
+        require!(
+            ctx.accounts.config.timelock_delay_slots == 0,
+            ErrorCode::TimelockRequired
+        );
+        verifier::validate_programs(&programs)?;
+        verifier::validate_required(&ctx.accounts.config.required_verifier, &programs)?;
+        
+        ctx.accounts.config.verifier_programs = programs;
+        
+        msg!("Verifier programs: {} whitelisted", ctx.accounts.config.verifier_programs.len());
+        Ok(())
+    }
+
+    /// Require every transfer proof to go through one whitelisted verifier
+    ///
+    /// While set, transfers that use the built-in verifier or another
+    /// whitelisted program fail with `VerifierRequired`. The program must
+    /// stay in the whitelist; `Pubkey::default()` lifts the requirement.
+    pub fn set_required_verifier(ctx: Context<UpdateConfig>, program: Pubkey) -> Result<()> {
+        // SECURITY: Once a timelock is set, the required verifier changes only through it
+        require!(
+            ctx.accounts.config.timelock_delay_slots == 0,
+            ErrorCode::TimelockRequired
+        );
+        verifier::validate_required(&program, &ctx.accounts.config.verifier_programs)?;
+        
+        ctx.accounts.config.required_verifier = program;
+        
+        msg!("Required verifier: {}", program);
+        Ok(())
+    }
+
+    /// Enable or disable subsystems for this deployment
+    ///
+    /// `flags` is a bitmask of `features::TOKEN_TRANSFERS` and
+    /// `features::RELAYERS`; instructions of a disabled subsystem fail with
+    /// `FeatureDisabled`.
+    pub fn set_features(ctx: Context<UpdateConfig>, flags: u32) -> Result<()> {
+        // SECURITY: Once a timelock is set, features only change through it
+        require!(
+            ctx.accounts.config.timelock_delay_slots == 0,
+            ErrorCode::TimelockRequired
+        );
+        require!(flags & !features::ALL == 0, ErrorCode::InvalidConfigParams);
+        
+        ctx.accounts.config.features = flags;
+        
+        msg!("Feature flags set: {:#04b}", flags);
+        Ok(())
+    }
+
+    /// Set the protocol fee, its collector and the exempt wallets
+    ///
+    /// `fee_bps` applies to withdraw_sol (deducted from the withdrawn amount),
+    /// confidential_sol_transfer (paid on top by the sender) and, as a hidden
+    /// fee, to confidential_transfer; 0 turns fees off. See `fees`.
+    pub fn set_fee_params(ctx: Context<UpdateConfig>, params: FeeParams) -> Result<()> {
+        // SECURITY: Once a timelock is set, fees only change through it
+        require!(
+            ctx.accounts.config.timelock_delay_slots == 0,
+            ErrorCode::TimelockRequired
+        );
+        fees::validate_params(&params)?;
+        
+        ctx.accounts.config.apply_fees(&params);
+        
+        msg!("Protocol fee: {} bps ({} exempt wallets)", params.fee_bps, params.exempt.len());
+        Ok(())
+    }
+
+    /// Create the fee treasury (config authority only)
+    pub fn initialize_treasury(ctx: Context<InitializeTreasury>) -> Result<()> {
+        let treasury = &mut ctx.accounts.treasury;
+        treasury.total_collected = 0;
+        treasury.total_withdrawn = 0;
+        treasury.bump = ctx.bumps.treasury;
+        
+        msg!("Initialized fee treasury");
+        Ok(())
+    }
+
+    /// Withdraw collected fees (fee authority only)
+    ///
+    /// The treasury keeps its rent-exempt minimum.
+    pub fn collect_fees(ctx: Context<CollectFees>, amount: u64) -> Result<()> {
+        let treasury_info = ctx.accounts.treasury.to_account_info();
+        let rent_minimum = Rent::get()?.minimum_balance(treasury_info.data_len());
+        let available = treasury_info.lamports().saturating_sub(rent_minimum);
+        require!(
+            amount > 0 && amount <= available,
+            ErrorCode::InsufficientBalance
+        );
+        
+        // Program-owned treasury: move lamports directly
+        let destination_info = ctx.accounts.destination.to_account_info();
+        let new_treasury_lamports = treasury_info.lamports().checked_sub(amount)
+            .ok_or(ErrorCode::Underflow)?;
+        let new_destination_lamports = destination_info.lamports().checked_add(amount)
+            .ok_or(ErrorCode::Overflow)?;
+        **treasury_info.try_borrow_mut_lamports()? = new_treasury_lamports;
+        **destination_info.try_borrow_mut_lamports()? = new_destination_lamports;
+        
+        let treasury = &mut ctx.accounts.treasury;
+        treasury.total_withdrawn = treasury.total_withdrawn.checked_add(amount)
+            .ok_or(ErrorCode::Overflow)?;
+        
+        msg!("✅ Collected {} lamports in fees", amount);
+        Ok(())
+    }
+
+    /// Create the shielded fee treasury (config authority only)
+    ///
+    /// Holds the sum of hidden confidential_transfer fees as a commitment.
+    pub fn initialize_shielded_treasury(ctx: Context<InitializeShieldedTreasury>) -> Result<()> {
+        let treasury = &mut ctx.accounts.shielded_treasury;
+        treasury.encrypted_balance = [0u8; 64];
+        treasury.version = 0;
+        treasury.fees_charged = 0;
+        treasury.bump = ctx.bumps.shielded_treasury;
+        
+        msg!("Initialized shielded fee treasury");
+        Ok(())
+    }
+
+    /// Move the hidden fees into the fee authority's encrypted account
+    ///
+    /// The balance is added on-chain and the treasury starts over from the
+    /// empty commitment. The fee authority learns the opening by summing
+    /// the `FeeMemo` openings since its last sweep.
+    /// PRIVACY: Amount is NEVER revealed on-chain!
+    pub fn sweep_shielded_fees(
+        ctx: Context<SweepShieldedFees>,
+        expected_treasury_version: u64,
+    ) -> Result<()> {
+        // SECURITY: Optimistic concurrency - the opening the fee authority
+        // tracked must match the balance being swept
+        require!(
+            ctx.accounts.shielded_treasury.version == expected_treasury_version,
+            ErrorCode::StaleAccountVersion
+        );
+        
+        let fees = ctx.accounts.shielded_treasury.encrypted_balance;
+        require!(fees != [0u8; 64], ErrorCode::InsufficientBalance);
+        
+        let fee_account = &mut ctx.accounts.fee_account;
+        let new_commitment = match pedersen::add(&fee_account.encrypted_balance, &fees) {
+            Ok(commitment) => commitment,
+            Err(e) => {
+                msg!("⚠️  Commitment addition error: {:?}", e);
+                return Err(ErrorCode::InvalidCommitment.into());
+            }
+        };
+        fee_account.encrypted_balance = new_commitment;
+        fee_account.version += 1;
+        fee_account.advance_state_hash(&new_commitment);
+        fee_account.record_activity()?;
+        
+        let treasury = &mut ctx.accounts.shielded_treasury;
+        treasury.encrypted_balance = [0u8; 64];
+        treasury.version += 1;
+        
+        msg!("✅ Swept shielded fees into the fee authority's account");
+        msg!("   ❌ AMOUNT IS HIDDEN!");
+        Ok(())
+    }
+
+    /// Create the protocol stats PDA (config authority only)
+    ///
+    /// Counting starts here; see `stats`.
+    pub fn initialize_stats(ctx: Context<InitializeStats>) -> Result<()> {
+        let stats = &mut ctx.accounts.stats;
+        stats.accounts = 0;
+        stats.deposits = 0;
+        stats.transfers = 0;
+        stats.withdrawals = 0;
+        stats.active_escrows = 0;
+        stats.last_update_slot = Clock::get()?.slot;
+        stats.slot_transfers = 0;
+        stats.bump = ctx.bumps.stats;
+        
+        msg!("Initialized protocol stats");
+        Ok(())
+    }
+
+    /// Queue a config change behind the timelock (config authority only)
+    ///
+    /// The change is public in the PendingConfigChange PDA (and the
+    /// `ConfigChangeProposed` event) for `timelock_delay_slots` before it
+    /// can be executed, so users can react to upcoming limit changes. Only
+    /// one change can be pending at a time. The emergency pause stays
+    /// immediate. Mint allowlist changes are applied by the mint
+    /// instructions themselves, which consume the pending entry.
+    pub fn propose_config_change(ctx: Context<ProposeConfigChange>, change: ConfigChange) -> Result<()> {
+        match &change {
+            ConfigChange::Limits(params) => limits::validate_params(params)?,
+            ConfigChange::TimelockDelay(delay_slots) => require!(
+                *delay_slots <= timelock_constants::MAX_DELAY_SLOTS,
+                ErrorCode::InvalidConfigParams
+            ),
+            ConfigChange::VerifierPrograms(programs) => verifier::validate_programs(programs)?,
+            ConfigChange::Features(flags) => require!(
+                flags & !features::ALL == 0,
+                ErrorCode::InvalidConfigParams
+            ),
+            ConfigChange::Fees(params) => fees::validate_params(params)?,
+            ConfigChange::ConfigAuthority(authority) => require!(
+                *authority != Pubkey::default(),
+                ErrorCode::InvalidNewOwner
+            ),
+            ConfigChange::ComplianceAuthority(_) => {}
+            ConfigChange::Regulators(params) => validate_regulators(params)?,
+            ConfigChange::EpochTransferLimit(_) => {}
+            ConfigChange::MaxAccountBalance(_) => {}
+            ConfigChange::DenyListRoot(_) => {}
+            ConfigChange::AssociationRoot(_) => {}
+            ConfigChange::SanctionsOracle(oracle, max_age_slots) => require!(
+                *oracle == Pubkey::default() || *max_age_slots > 0,
+                ErrorCode::SanctionsAttestationStale
+            ),
+            ConfigChange::KycGating(_, _) => {}
+            ConfigChange::TravelRuleThreshold(_) => {}
+            ConfigChange::SupportedMint(params) => {
+                validate_mint_bounds(params.min_amount, params.max_amount)?
+            }
+            ConfigChange::RemoveSupportedMint(_) => {}
+            ConfigChange::RequiredVerifier(program) => {
+                verifier::validate_required(program, &ctx.accounts.config.verifier_programs)?
+            }
+        }
+        
+        let slot = Clock::get()?.slot;
+        let eta_slot = slot.checked_add(ctx.accounts.config.timelock_delay_slots)
+            .ok_or(ErrorCode::Overflow)?;
+        
+        let pending = &mut ctx.accounts.pending_change;
+        pending.change = change.clone();
+        pending.proposed_slot = slot;
+        pending.eta_slot = eta_slot;
+        pending.bump = ctx.bumps.pending_change;
+        
+        emit!(ConfigChangeProposed {
+            change,
+            proposed_slot: slot,
+            eta_slot,
+        });
+        
+        msg!("Config change queued, executable at slot {}", eta_slot);
+        Ok(())
+    }
+
+    /// Apply a queued config change once its delay has elapsed
+    pub fn execute_config_change(ctx: Context<ExecuteConfigChange>) -> Result<()> {
+        let pending = &ctx.accounts.pending_change;
+        require!(
+            Clock::get()?.slot >= pending.eta_slot,
+            ErrorCode::TimelockNotElapsed
+        );
+        
+        let config = &mut ctx.accounts.config;
+        match &pending.change {
+            ConfigChange::Limits(params) => config.apply_limits(params),
+            ConfigChange::TimelockDelay(delay_slots) => config.timelock_delay_slots = *delay_slots,
+            ConfigChange::VerifierPrograms(programs) => {
+                // The required verifier may have changed since the proposal
+                verifier::validate_required(&config.required_verifier, programs)?;
+                config.verifier_programs = programs.clone();
+            }
+            ConfigChange::Features(flags) => config.features = *flags,
+            ConfigChange::Fees(params) => config.apply_fees(params),
+            ConfigChange::ConfigAuthority(authority) => config.authority = *authority,
+            ConfigChange::ComplianceAuthority(authority) => config.compliance_authority = *authority,
+            ConfigChange::Regulators(params) => config.apply_regulators(params),
+            ConfigChange::EpochTransferLimit(limit) => config.epoch_transfer_limit = *limit,
+            ConfigChange::MaxAccountBalance(max_balance) => config.max_account_balance = *max_balance,
+            ConfigChange::DenyListRoot(root) => config.deny_list_root = *root,
+            ConfigChange::AssociationRoot(root) => {
+                config.association_root = *root;
+                emit!(AssociationRootUpdated {
+                    association_root: *root,
+                    slot: Clock::get()?.slot,
+                });
+            }
+            ConfigChange::SanctionsOracle(oracle, max_age_slots) => {
+                config.sanctions_oracle = *oracle;
+                config.attestation_max_age = *max_age_slots;
+            }
+            ConfigChange::KycGating(kyc_mint, require_recipient) => {
+                config.kyc_mint = *kyc_mint;
+                config.kyc_require_recipient = *require_recipient;
+            }
+            ConfigChange::TravelRuleThreshold(threshold) => config.travel_rule_threshold = *threshold,
+            ConfigChange::RequiredVerifier(program) => {
+                verifier::validate_required(program, &config.verifier_programs)?;
+                config.required_verifier = *program;
+            }
+            // Applied by add/update/remove_supported_mint, which own the entry
+            ConfigChange::SupportedMint(_) | ConfigChange::RemoveSupportedMint(_) => {
+                return err!(ErrorCode::QueuedChangeMismatch);
+            }
+        }
+        
+        msg!("✅ Config change executed (queued at slot {})", pending.proposed_slot);
+        Ok(())
+    }
+
+    /// Drop a queued config change and reclaim its rent
+    pub fn cancel_config_change(ctx: Context<ExecuteConfigChange>) -> Result<()> {
+        msg!("Config change cancelled (was due at slot {})", ctx.accounts.pending_change.eta_slot);
+        Ok(())
+    }
+
+    /// Confidential transfer from one sender to several recipients
+    ///
+    /// Remaining accounts: the recipient EncryptedAccounts, in the same order
+    /// as `recipient_commitments` and `expected_recipient_versions`. The proof shows every individual amount is
+    /// in range and that the amounts sum to the sender's debit.
+    /// PRIVACY: Individual amounts and the total are hidden on-chain!
+    pub fn confidential_transfer_multi<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ConfidentialTransferMulti<'info>>,
+        sender_new_commitment: [u8; 64],
+        recipient_commitments: Vec<[u8; 64]>,
+        proof_data: Vec<u8>,
+        expected_sender_version: u64,
+        expected_recipient_versions: Vec<u64>,
+    ) -> Result<()> {
+        // ============================================
+        // INPUT VALIDATION (Checks)
+        // ============================================
+        
+        // SECURITY: Emergency pause and circuit breaker (set by the config authority)
+        let config = Config::load_optional(&ctx.accounts.config)?;
+        require_not_paused!(config.as_ref(), pause::TRANSFERS);
+        limits::enforce_transfer_rate(config.as_ref(), &ctx.accounts.stats)?;
+        
+        // SECURITY: Optimistic concurrency - the proof was built against these
+        // exact states; a concurrent update would make it stale
+        require!(
+            ctx.accounts.sender_account.version == expected_sender_version,
+            ErrorCode::StaleAccountVersion
+        );
+        
+        // SECURITY: Validate sender account ownership
+        require!(
+            ctx.accounts.sender_account.owner == ctx.accounts.sender.key(),
+            ErrorCode::Unauthorized
+        );
+        
+        let recipient_infos = ctx.remaining_accounts;
+        require!(
+            recipient_commitments.len() >= multi_transfer_constants::MIN_RECIPIENTS
+                && recipient_commitments.len() <= multi_transfer_constants::MAX_RECIPIENTS
+                && recipient_commitments.len() == recipient_infos.len()
+                && expected_recipient_versions.len() == recipient_infos.len(),
+            ErrorCode::InvalidMultiTransfer
+        );
+        
+        // Validate proof data size (DoS protection)
+        require!(
+            proof_data.len() >= proof_constants::MIN_PROOF_DATA_SIZE,
+            ErrorCode::InvalidProof
+        );
+        require!(
+            proof_data.len() <= proof_constants::MAX_PROOF_DATA_SIZE,
+            ErrorCode::InvalidProof
+        );
+        
+        // Validate commitments are well-formed for the configured commitment scheme
+        require!(
+            is_valid_commitment(&sender_new_commitment),
+            ErrorCode::InvalidCommitment
+        );
+        for commitment in &recipient_commitments {
+            require!(
+                is_valid_commitment(commitment),
+                ErrorCode::InvalidCommitment
+            );
+        }
+        
+        let sender_old_commitment = ctx.accounts.sender_account.encrypted_balance;
+        require!(
+            sender_old_commitment != [0u8; 64],
+            ErrorCode::InvalidCommitment
+        );
+        
+        // Load recipients: genuine, distinct encrypted accounts other than the sender
+        let sender_key = ctx.accounts.sender_account.key();
+        let mut recipients: Vec<Account<'info, EncryptedAccount>> = Vec::with_capacity(recipient_infos.len());
+        for (info, expected_version) in recipient_infos.iter().zip(&expected_recipient_versions) {
+            require!(info.key() != sender_key, ErrorCode::InvalidRecipient);
+            require!(info.is_writable, ErrorCode::InvalidMultiTransfer);
+            require!(
+                recipients.iter().all(|recipient| recipient.key() != info.key()),
+                ErrorCode::InvalidMultiTransfer
+            );
+            
+            let recipient: Account<'info, EncryptedAccount> = Account::try_from(info)?;
+            let expected = Pubkey::create_program_address(
+                &[
+                    pda::ENCRYPTED_ACCOUNT_SEED,
+                    recipient.seed_owner.as_ref(),
+                    &recipient.account_index.to_le_bytes(),
+                    &[recipient.bump],
+                ],
+                &crate::ID,
+            ).map_err(|_| ErrorCode::InvalidMultiTransfer)?;
+            require!(info.key() == expected, ErrorCode::InvalidMultiTransfer);
+            require!(recipient.version == *expected_version, ErrorCode::StaleAccountVersion);
+            recipients.push(recipient);
+        }
+        
+        // ============================================
+        // ZK PROOF VERIFICATION
+        // ============================================
+        
+        let pairs: Vec<([u8; 64], [u8; 64])> = recipients.iter()
+            .zip(&recipient_commitments)
+            .map(|(recipient, new_commitment)| (recipient.encrypted_balance, *new_commitment))
+            .collect();
+        
+        if let Err(e) = verify_multi_transfer_proof(
+            &proof_data,
+            &sender_old_commitment,
+            &sender_new_commitment,
+            &pairs,
+        ) {
+            msg!("⚠️  Proof verification error: {:?}", e);
+            return Err(ErrorCode::InvalidProof.into());
+        }
+        
+        // ============================================
+        // STATE UPDATES (Effects)
+        // ============================================
+        
+        let sender_account = &mut ctx.accounts.sender_account;
+        sender_account.encrypted_balance = sender_new_commitment;
+        sender_account.version += 1;
+        sender_account.advance_state_hash(&sender_new_commitment);
+        
+        for (recipient, new_commitment) in recipients.iter_mut().zip(&recipient_commitments) {
+            recipient.encrypted_balance = *new_commitment;
+            recipient.version += 1;
+            recipient.advance_state_hash(new_commitment);
+            recipient.exit(&crate::ID)?;
+        }
+        
+        // Protocol statistics (if initialized)
+        stats::record(&ctx.accounts.stats, stats::Activity::Transfer)?;
+        
+        msg!("✅ Multi-recipient confidential transfer completed");
+        msg!("   Recipients: {}", recipients.len());
+        msg!("   ❌ AMOUNTS ARE HIDDEN!");
+        
+        Ok(())
+    }
+
+    /// Several independent confidential transfers in one instruction
+    ///
+    /// Remaining accounts: a (sender, recipient) EncryptedAccount pair per
+    /// leg, in leg order. Every sender must belong to the signing owner, and
+    /// no account may appear twice. Each leg carries its own transfer proof.
+    /// PRIVACY: Amounts are hidden on-chain!
+    pub fn confidential_transfer_batch<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ConfidentialTransferBatch<'info>>,
+        legs: Vec<BatchTransferLeg>,
+    ) -> Result<()> {
+        // ============================================
+        // INPUT VALIDATION (Checks)
+        // ============================================
+        
+        // SECURITY: Emergency pause and circuit breaker (set by the config authority)
+        let config = Config::load_optional(&ctx.accounts.config)?;
+        require_not_paused!(config.as_ref(), pause::TRANSFERS);
+        limits::enforce_transfer_rate(config.as_ref(), &ctx.accounts.stats)?;
+        
+        let account_infos = ctx.remaining_accounts;
+        require!(
+            !legs.is_empty()
+                && legs.len() <= batch_transfer_constants::MAX_TRANSFERS
+                && account_infos.len() == legs.len() * 2,
+            ErrorCode::InvalidBatchTransfer
+        );
+        
+        for leg in &legs {
+            // Validate proof data size (DoS protection)
+            require!(
+                leg.proof_data.len() >= proof_constants::MIN_PROOF_DATA_SIZE,
+                ErrorCode::InvalidProof
+            );
+            require!(
+                leg.proof_data.len() <= proof_constants::MAX_PROOF_DATA_SIZE,
+                ErrorCode::InvalidProof
+            );
+            
+            require!(
+                is_valid_commitment(&leg.sender_new_commitment),
+                ErrorCode::InvalidCommitment
+            );
+            require!(
+                is_valid_commitment(&leg.recipient_new_commitment),
+                ErrorCode::InvalidCommitment
+            );
+        }
+        
+        // Load accounts: genuine, pairwise-distinct encrypted accounts
+        let owner = ctx.accounts.owner.key();
+        let mut accounts: Vec<Account<'info, EncryptedAccount>> = Vec::with_capacity(account_infos.len());
+        for (i, info) in account_infos.iter().enumerate() {
+            require!(info.is_writable, ErrorCode::InvalidBatchTransfer);
+            require!(
+                accounts.iter().all(|account| account.key() != info.key()),
+                ErrorCode::InvalidBatchTransfer
+            );
+            
+            let account: Account<'info, EncryptedAccount> = Account::try_from(info)?;
+            let expected = Pubkey::create_program_address(
+                &[
+                    pda::ENCRYPTED_ACCOUNT_SEED,
+                    account.seed_owner.as_ref(),
+                    &account.account_index.to_le_bytes(),
+                    &[account.bump],
+                ],
+                &crate::ID,
+            ).map_err(|_| ErrorCode::InvalidBatchTransfer)?;
+            require!(info.key() == expected, ErrorCode::InvalidBatchTransfer);
+            
+            // SECURITY: Optimistic concurrency - each leg's proof was built
+            // against these exact states
+            let leg = &legs[i / 2];
+            let expected_version = if i % 2 == 0 {
+                leg.expected_sender_version
+            } else {
+                leg.expected_recipient_version
+            };
+            require!(account.version == expected_version, ErrorCode::StaleAccountVersion);
+            
+            if i % 2 == 0 {
+                // SECURITY: Every sender must belong to the signer and be spendable
+                require!(account.owner == owner, ErrorCode::Unauthorized);
+                require!(!account.frozen, ErrorCode::AccountFrozen);
+                require!(
+                    account.encrypted_balance != [0u8; 64],
+                    ErrorCode::InvalidCommitment
+                );
+            }
+            accounts.push(account);
+        }
+        
+        // ============================================
+        // ZK PROOF VERIFICATION
+        // ============================================
+        
+        for (leg, pair) in legs.iter().zip(accounts.chunks(2)) {
+            let amount_commitment = match proof_verification::extract_amount_commitment(&leg.proof_data) {
+                Ok(commitment) => commitment,
+                Err(e) => {
+                    msg!("⚠️  Failed to extract amount commitment from proof: {:?}", e);
+                    return Err(ErrorCode::InvalidProof.into());
+                }
+            };
+            
+            if let Err(e) = verify_transfer_proof(
+                &leg.proof_data,
+                &amount_commitment,
+                &leg.sender_new_commitment,
+                &pair[0].encrypted_balance,
+                &pair[1].encrypted_balance,
+                &leg.recipient_new_commitment,
+            ) {
+                msg!("⚠️  Proof verification error: {:?}", e);
+                return Err(ErrorCode::InvalidProof.into());
+            }
+        }
+        
+        // ============================================
+        // STATE UPDATES (Effects)
+        // ============================================
+        
+        for (leg, pair) in legs.iter().zip(accounts.chunks_mut(2)) {
+            let sender_account = &mut pair[0];
+            sender_account.encrypted_balance = leg.sender_new_commitment;
+            sender_account.version += 1;
+            sender_account.advance_state_hash(&leg.sender_new_commitment);
+            sender_account.exit(&crate::ID)?;
+            
+            let recipient_account = &mut pair[1];
+            recipient_account.encrypted_balance = leg.recipient_new_commitment;
+            recipient_account.version += 1;
+            recipient_account.advance_state_hash(&leg.recipient_new_commitment);
+            recipient_account.exit(&crate::ID)?;
+        }
+        
+        // Protocol statistics (if initialized)
+        stats::record(&ctx.accounts.stats, stats::Activity::Transfer)?;
+        
+        msg!("✅ Confidential transfer batch completed");
+        msg!("   Transfers: {}", legs.len());
+        msg!("   ❌ AMOUNTS ARE HIDDEN!");
+        
+        Ok(())
+    }
+
+    /// Start a two-phase transfer: debit the sender into a pending escrow
+    ///
+    /// The amount commitment is parked in a PendingTransfer PDA until the
+    /// recipient claims it or, after `expiry_slots`, the sender takes it back.
+    /// PRIVACY: Amount is NEVER revealed on-chain!
+    pub fn initiate_transfer(
+        ctx: Context<InitiateTransfer>,
+        transfer_id: u64,
+        sender_new_commitment: [u8; 64],
+        proof_data: Vec<u8>,
+        expiry_slots: u64,
+    ) -> Result<()> {
+        // ============================================
+        // INPUT VALIDATION (Checks)
+        // ============================================
+        
+        // SECURITY: Emergency pause and circuit breaker (set by the config authority)
+        let config = Config::load_optional(&ctx.accounts.config)?;
+        require_not_paused!(config.as_ref(), pause::TRANSFERS);
+        limits::enforce_transfer_rate(config.as_ref(), &ctx.accounts.stats)?;
+        
+        require!(
+            ctx.accounts.sender_account.key() != ctx.accounts.recipient_account.key(),
+            ErrorCode::InvalidRecipient
+        );
+        require!(
+            (pending_transfer_constants::MIN_EXPIRY_SLOTS..=pending_transfer_constants::MAX_EXPIRY_SLOTS)
+                .contains(&expiry_slots),
+            ErrorCode::InvalidExpiry
+        );
+        
+        // Validate proof data size (DoS protection)
+        require!(
+            proof_data.len() >= proof_constants::MIN_PROOF_DATA_SIZE,
+            ErrorCode::InvalidProof
+        );
+        require!(
+            proof_data.len() <= proof_constants::MAX_PROOF_DATA_SIZE,
+            ErrorCode::InvalidProof
+        );
+        
+        require!(
+            is_valid_commitment(&sender_new_commitment),
+            ErrorCode::InvalidCommitment
+        );
+        
+        let sender_old_commitment = ctx.accounts.sender_account.encrypted_balance;
+        require!(
+            sender_old_commitment != [0u8; 64],
+            ErrorCode::InvalidCommitment
+        );
+        
+        // ============================================
+        // ZK PROOF VERIFICATION
+        // ============================================
+        
+        let amount_commitment = match proof_verification::extract_amount_commitment(&proof_data) {
+            Ok(commitment) => commitment,
+            Err(e) => {
+                msg!("⚠️  Failed to extract amount commitment from proof: {:?}", e);
+                return Err(ErrorCode::InvalidProof.into());
+            }
+        };
+        
+        if let Err(e) = verify_escrow_debit_proof(
+            &proof_data,
+            &amount_commitment,
+            &sender_old_commitment,
+            &sender_new_commitment,
+        ) {
+            msg!("⚠️  Proof verification error: {:?}", e);
+            return Err(ErrorCode::InvalidProof.into());
+        }
+        
+        // ============================================
+        // STATE UPDATES (Effects)
+        // ============================================
+        
+        let sender_account = &mut ctx.accounts.sender_account;
+        sender_account.encrypted_balance = sender_new_commitment;
+        sender_account.version += 1;
+        sender_account.advance_state_hash(&sender_new_commitment);
+        sender_account.record_activity()?;
+        
+        let current_slot = Clock::get()?.slot;
+        let pending = &mut ctx.accounts.pending_transfer;
+        pending.sender = ctx.accounts.sender.key();
+        pending.sender_account = ctx.accounts.sender_account.key();
+        pending.recipient_account = ctx.accounts.recipient_account.key();
+        pending.transfer_id = transfer_id;
+        pending.amount_commitment = amount_commitment;
+        pending.expiry_slot = current_slot.checked_add(expiry_slots)
+            .ok_or(ErrorCode::Overflow)?;
+        pending.bump = ctx.bumps.pending_transfer;
+        
+        emit!(TransferInitiated {
+            pending_transfer: pending.key(),
+            sender_account: pending.sender_account,
+            recipient_account: pending.recipient_account,
+            expiry_slot: pending.expiry_slot,
+        });
+        
+        // Protocol statistics (if initialized)
+        stats::record(&ctx.accounts.stats, stats::Activity::Transfer)?;
+        
+        msg!("⏳ Transfer pending until slot {}", pending.expiry_slot);
+        msg!("   ❌ AMOUNT IS HIDDEN!");
+        Ok(())
+    }
+
+    /// Accept a pending transfer into the recipient's balance
+    ///
+    /// Only possible before expiry. Closes the escrow (rent to the sender).
+    /// PRIVACY: Amount is NEVER revealed on-chain!
+    pub fn claim_transfer(
+        ctx: Context<ClaimTransfer>,
+        recipient_new_commitment: [u8; 64],
+        proof_data: [u8; 96],
+    ) -> Result<()> {
+        // SECURITY: Emergency pause and circuit breaker (set by the config authority)
+        let config = Config::load_optional(&ctx.accounts.config)?;
+        require_not_paused!(config.as_ref(), pause::TRANSFERS);
+        limits::enforce_transfer_rate(config.as_ref(), &ctx.accounts.stats)?;
+        
+        require!(
+            Clock::get()?.slot < ctx.accounts.pending_transfer.expiry_slot,
+            ErrorCode::PendingTransferExpired
+        );
+        require!(
+            is_valid_commitment(&recipient_new_commitment),
+            ErrorCode::InvalidCommitment
+        );
+        
+        let recipient_old_commitment = ctx.accounts.recipient_account.encrypted_balance;
+        if let Err(e) = verify_escrow_credit_proof(
+            &proof_data,
+            &ctx.accounts.pending_transfer.amount_commitment,
+            &recipient_old_commitment,
+            &recipient_new_commitment,
+        ) {
+            msg!("⚠️  Proof verification error: {:?}", e);
+            return Err(ErrorCode::InvalidProof.into());
+        }
+        
+        let recipient_account = &mut ctx.accounts.recipient_account;
+        recipient_account.encrypted_balance = recipient_new_commitment;
+        recipient_account.version += 1;
+        recipient_account.advance_state_hash(&recipient_new_commitment);
+        recipient_account.record_activity()?;
+        
+        emit!(TransferClaimed {
+            pending_transfer: ctx.accounts.pending_transfer.key(),
+            recipient_account: recipient_account.key(),
+        });
+        
+        msg!("✅ Pending transfer claimed");
+        Ok(())
+    }
+
+    /// Take back an unclaimed transfer after it expired
+    ///
+    /// Closes the escrow (rent to the sender).
+    /// PRIVACY: Amount is NEVER revealed on-chain!
+    pub fn refund_transfer(
+        ctx: Context<RefundTransfer>,
+        sender_new_commitment: [u8; 64],
+        proof_data: [u8; 96],
+    ) -> Result<()> {
+        require!(
+            Clock::get()?.slot >= ctx.accounts.pending_transfer.expiry_slot,
+            ErrorCode::PendingTransferNotExpired
+        );
+        require!(
+            is_valid_commitment(&sender_new_commitment),
+            ErrorCode::InvalidCommitment
+        );
+        
+        let sender_old_commitment = ctx.accounts.sender_account.encrypted_balance;
+        if let Err(e) = verify_escrow_credit_proof(
+            &proof_data,
+            &ctx.accounts.pending_transfer.amount_commitment,
+            &sender_old_commitment,
+            &sender_new_commitment,
+        ) {
+            msg!("⚠️  Proof verification error: {:?}", e);
+            return Err(ErrorCode::InvalidProof.into());
+        }
+        
+        let sender_account = &mut ctx.accounts.sender_account;
+        sender_account.encrypted_balance = sender_new_commitment;
+        sender_account.version += 1;
+        sender_account.advance_state_hash(&sender_new_commitment);
+        sender_account.record_activity()?;
+        
+        emit!(TransferRefunded {
+            pending_transfer: ctx.accounts.pending_transfer.key(),
+            sender_account: sender_account.key(),
+        });
+        
+        msg!("↩️  Expired transfer refunded to sender");
+        Ok(())
+    }
+
+    /// Issue an invoice payable into one of the merchant's encrypted accounts
+    ///
+    /// `amount_commitment` commits to the amount due; payers prove their
+    /// transfer commits to the same amount, so it is never revealed.
+    pub fn create_invoice(
+        ctx: Context<CreateInvoice>,
+        invoice_id: u64,
+        amount_commitment: [u8; 64],
+        expiry_slots: u64,
+    ) -> Result<()> {
+        require!(
+            is_valid_commitment(&amount_commitment),
+            ErrorCode::InvalidCommitment
+        );
+        require!(
+            (invoice_constants::MIN_EXPIRY_SLOTS..=invoice_constants::MAX_EXPIRY_SLOTS)
+                .contains(&expiry_slots),
+            ErrorCode::InvalidExpiry
+        );
+        
+        let invoice = &mut ctx.accounts.invoice;
+        invoice.merchant = ctx.accounts.merchant.key();
+        invoice.recipient_account = ctx.accounts.recipient_account.key();
+        invoice.invoice_id = invoice_id;
+        invoice.amount_commitment = amount_commitment;
+        invoice.expiry_slot = Clock::get()?.slot.checked_add(expiry_slots)
+            .ok_or(ErrorCode::Overflow)?;
+        invoice.paid = false;
+        invoice.payer_account = Pubkey::default();
+        invoice.bump = ctx.bumps.invoice;
+        
+        emit!(InvoiceCreated {
+            invoice: invoice.key(),
+            recipient_account: invoice.recipient_account,
+            invoice_id,
+            expiry_slot: invoice.expiry_slot,
+        });
+        
+        ctx.accounts.recipient_account.record_activity()?;
+        
+        msg!("🧾 Invoice {} created", invoice_id);
+        Ok(())
+    }
+
+    /// Pay an open invoice with a confidential transfer
+    ///
+    /// `match_proof` shows the transfer's amount commitment and the invoice's
+    /// amount commitment hide the same amount. Marks the invoice paid.
+    /// PRIVACY: Amount is NEVER revealed on-chain!
+    pub fn pay_invoice(
+        ctx: Context<PayInvoice>,
+        payer_new_commitment: [u8; 64],
+        recipient_new_commitment: [u8; 64],
+        proof_data: Vec<u8>,
+        match_proof: [u8; 96],
+        expected_payer_version: u64,
+        expected_recipient_version: u64,
+    ) -> Result<()> {
+        // ============================================
+        // INPUT VALIDATION (Checks)
+        // ============================================
+        
+        // SECURITY: Emergency pause and circuit breaker (set by the config authority)
+        let config = Config::load_optional(&ctx.accounts.config)?;
+        require_not_paused!(config.as_ref(), pause::TRANSFERS);
+        limits::enforce_transfer_rate(config.as_ref(), &ctx.accounts.stats)?;
+        
+        // SECURITY: Optimistic concurrency - the proof was built against these
+        // exact states; a concurrent update would make it stale
+        require!(
+            ctx.accounts.payer_account.version == expected_payer_version,
+            ErrorCode::StaleAccountVersion
+        );
+        require!(
+            ctx.accounts.recipient_account.version == expected_recipient_version,
+            ErrorCode::StaleAccountVersion
+        );
+        
+        let current_slot = Clock::get()?.slot;
+        require!(
+            current_slot < ctx.accounts.invoice.expiry_slot,
+            ErrorCode::InvoiceExpired
+        );
+        require!(
+            ctx.accounts.payer_account.key() != ctx.accounts.recipient_account.key(),
+            ErrorCode::InvalidRecipient
+        );
+        
+        // Validate proof data size (DoS protection)
+        require!(
+            proof_data.len() >= proof_constants::MIN_PROOF_DATA_SIZE,
+            ErrorCode::InvalidProof
+        );
+        require!(
+            proof_data.len() <= proof_constants::MAX_PROOF_DATA_SIZE,
+            ErrorCode::InvalidProof
+        );
+        
+        require!(
+            is_valid_commitment(&payer_new_commitment),
+            ErrorCode::InvalidCommitment
+        );
+        require!(
+            is_valid_commitment(&recipient_new_commitment),
+            ErrorCode::InvalidCommitment
+        );
+        
+        let payer_old_commitment = ctx.accounts.payer_account.encrypted_balance;
+        let recipient_old_commitment = ctx.accounts.recipient_account.encrypted_balance;
+        require!(
+            payer_old_commitment != [0u8; 64],
+            ErrorCode::InvalidCommitment
+        );
+        
+        // ============================================
+        // ZK PROOF VERIFICATION
+        // ============================================
+        
+        let amount_commitment = match proof_verification::extract_amount_commitment(&proof_data) {
+            Ok(commitment) => commitment,
+            Err(e) => {
+                msg!("⚠️  Failed to extract amount commitment from proof: {:?}", e);
+                return Err(ErrorCode::InvalidProof.into());
+            }
+        };
+        
+        if let Err(e) = verify_transfer_proof(
+            &proof_data,
+            &amount_commitment,
+            &payer_new_commitment,
+            &payer_old_commitment,
+            &recipient_old_commitment,
+            &recipient_new_commitment,
+        ) {
+            msg!("⚠️  Proof verification error: {:?}", e);
+            return Err(ErrorCode::InvalidProof.into());
+        }
+        
+        // SECURITY: The transfer must carry exactly the invoiced amount
+        if let Err(e) = verify_amount_match_proof(
+            &match_proof,
+            &ctx.accounts.invoice.amount_commitment,
+            &amount_commitment,
+        ) {
+            msg!("⚠️  Invoice amount mismatch: {:?}", e);
+            return Err(ErrorCode::InvoiceAmountMismatch.into());
+        }
+        
+        // ============================================
+        // STATE UPDATES (Effects)
+        // ============================================
+        
+        let payer_account = &mut ctx.accounts.payer_account;
+        payer_account.encrypted_balance = payer_new_commitment;
+        payer_account.version += 1;
+        payer_account.advance_state_hash(&payer_new_commitment);
+        payer_account.record_activity()?;
+        
+        let recipient_account = &mut ctx.accounts.recipient_account;
+        recipient_account.encrypted_balance = recipient_new_commitment;
+        recipient_account.version += 1;
+        recipient_account.advance_state_hash(&recipient_new_commitment);
+        
+        let invoice = &mut ctx.accounts.invoice;
+        invoice.paid = true;
+        invoice.payer_account = ctx.accounts.payer_account.key();
+        
+        emit!(InvoicePaid {
+            invoice: invoice.key(),
+            payer_account: invoice.payer_account,
+            recipient_account: invoice.recipient_account,
+            invoice_id: invoice.invoice_id,
+            slot: current_slot,
+        });
+        
+        // Protocol statistics (if initialized)
+        stats::record(&ctx.accounts.stats, stats::Activity::Transfer)?;
+        
+        msg!("✅ Invoice {} paid", invoice.invoice_id);
+        msg!("   ❌ AMOUNT IS HIDDEN!");
+        Ok(())
+    }
+
+    /// Delete an invoice (paid, expired or withdrawn) and reclaim its rent
+    pub fn close_invoice(_ctx: Context<CloseInvoice>) -> Result<()> {
+        msg!("Invoice closed");
+        Ok(())
+    }
+
+    /// Lock a hidden amount behind a one-time claim link
+    ///
+    /// `claim_key` is the public key of the ed25519 keypair seeded by the
+    /// link secret. The link shared off-chain carries the secret and the
+    /// amount opening; whoever holds it can redeem into their own account.
+    /// After expiry the creator can reclaim the funds.
+    /// PRIVACY: Amount is NEVER revealed on-chain!
+    pub fn create_claim(
+        ctx: Context<CreateClaim>,
+        claim_key: Pubkey,
+        creator_new_commitment: [u8; 64],
+        proof_data: Vec<u8>,
+        expiry_slots: u64,
+    ) -> Result<()> {
+        // ============================================
+        // INPUT VALIDATION (Checks)
+        // ============================================
+        
+        // SECURITY: Emergency pause and circuit breaker (set by the config authority)
+        let config = Config::load_optional(&ctx.accounts.config)?;
+        require_not_paused!(config.as_ref(), pause::TRANSFERS);
+        limits::enforce_transfer_rate(config.as_ref(), &ctx.accounts.stats)?;
+        
+        require!(claim_key != Pubkey::default(), ErrorCode::InvalidClaimSecret);
+        require!(
+            (claim_link_constants::MIN_EXPIRY_SLOTS..=claim_link_constants::MAX_EXPIRY_SLOTS)
+                .contains(&expiry_slots),
+            ErrorCode::InvalidExpiry
+        );
+        
+        // Validate proof data size (DoS protection)
+        require!(
+            proof_data.len() >= proof_constants::MIN_PROOF_DATA_SIZE,
+            ErrorCode::InvalidProof
+        );
+        require!(
+            proof_data.len() <= proof_constants::MAX_PROOF_DATA_SIZE,
+            ErrorCode::InvalidProof
+        );
+        
+        require!(
+            is_valid_commitment(&creator_new_commitment),
+            ErrorCode::InvalidCommitment
+        );
+        
+        let creator_old_commitment = ctx.accounts.creator_account.encrypted_balance;
+        require!(
+            creator_old_commitment != [0u8; 64],
+            ErrorCode::InvalidCommitment
+        );
+        
+        // ============================================
+        // ZK PROOF VERIFICATION
+        // ============================================
+        
+        let amount_commitment = match proof_verification::extract_amount_commitment(&proof_data) {
+            Ok(commitment) => commitment,
+            Err(e) => {
+                msg!("⚠️  Failed to extract amount commitment from proof: {:?}", e);
+                return Err(ErrorCode::InvalidProof.into());
+            }
+        };
+        
+        if let Err(e) = verify_escrow_debit_proof(
+            &proof_data,
+            &amount_commitment,
+            &creator_old_commitment,
+            &creator_new_commitment,
+        ) {
+            msg!("⚠️  Proof verification error: {:?}", e);
+            return Err(ErrorCode::InvalidProof.into());
+        }
+        
+        // ============================================
+        // STATE UPDATES (Effects)
+        // ============================================
+        
+        let creator_account = &mut ctx.accounts.creator_account;
+        creator_account.encrypted_balance = creator_new_commitment;
+        creator_account.version += 1;
+        creator_account.advance_state_hash(&creator_new_commitment);
+        creator_account.record_activity()?;
+        
+        let claim = &mut ctx.accounts.claim_link;
+        claim.creator = ctx.accounts.creator.key();
+        claim.creator_account = ctx.accounts.creator_account.key();
+        claim.claim_key = claim_key;
+        claim.amount_commitment = amount_commitment;
+        claim.expiry_slot = Clock::get()?.slot.checked_add(expiry_slots)
+            .ok_or(ErrorCode::Overflow)?;
+        claim.bump = ctx.bumps.claim_link;
+        
+        // Protocol statistics (if initialized)
+        stats::record(&ctx.accounts.stats, stats::Activity::Transfer)?;
+        
+        msg!("🔗 Claim link created, valid until slot {}", claim.expiry_slot);
+        msg!("   ❌ AMOUNT IS HIDDEN!");
+        Ok(())
+    }
+
+    /// Redeem a claim link into the caller's own encrypted account
+    ///
+    /// SECURITY: The claim keypair (seeded by the link secret) must sign.
+    /// Its signature covers the whole transaction, recipient account
+    /// included, and the secret itself never goes on-chain, so a redemption
+    /// seen in flight cannot be replayed into another account. Closes the
+    /// claim (rent to the creator).
+    /// PRIVACY: Amount is NEVER revealed on-chain!
+    pub fn redeem_claim(
+        ctx: Context<RedeemClaim>,
+        recipient_new_commitment: [u8; 64],
+        proof_data: [u8; 96],
+    ) -> Result<()> {
+        // SECURITY: Emergency pause and circuit breaker (set by the config authority)
+        let config = Config::load_optional(&ctx.accounts.config)?;
+        require_not_paused!(config.as_ref(), pause::TRANSFERS);
+        limits::enforce_transfer_rate(config.as_ref(), &ctx.accounts.stats)?;
+        
+        let claim = &ctx.accounts.claim_link;
+        require!(
+            Clock::get()?.slot < claim.expiry_slot,
+            ErrorCode::ClaimExpired
+        );
+        require!(
+            is_valid_commitment(&recipient_new_commitment),
+            ErrorCode::InvalidCommitment
+        );
+        
+        let recipient_old_commitment = ctx.accounts.recipient_account.encrypted_balance;
+        if let Err(e) = verify_escrow_credit_proof(
+            &proof_data,
+            &claim.amount_commitment,
+            &recipient_old_commitment,
+            &recipient_new_commitment,
+        ) {
+            msg!("⚠️  Proof verification error: {:?}", e);
+            return Err(ErrorCode::InvalidProof.into());
+        }
+        
+        let recipient_account = &mut ctx.accounts.recipient_account;
+        recipient_account.encrypted_balance = recipient_new_commitment;
+        recipient_account.version += 1;
+        recipient_account.advance_state_hash(&recipient_new_commitment);
+        recipient_account.record_activity()?;
+        
+        emit!(ClaimRedeemed {
+            claim_key: ctx.accounts.claim_link.claim_key,
+            recipient_account: recipient_account.key(),
+        });
+        
+        msg!("✅ Claim link redeemed");
+        Ok(())
+    }
+
+    /// Take back the funds of an expired, unredeemed claim link
+    ///
+    /// Closes the claim (rent to the creator).
+    /// PRIVACY: Amount is NEVER revealed on-chain!
+    pub fn reclaim_claim(
+        ctx: Context<ReclaimClaim>,
+        creator_new_commitment: [u8; 64],
+        proof_data: [u8; 96],
+    ) -> Result<()> {
+        require!(
+            Clock::get()?.slot >= ctx.accounts.claim_link.expiry_slot,
+            ErrorCode::ClaimNotExpired
+        );
+        require!(
+            is_valid_commitment(&creator_new_commitment),
+            ErrorCode::InvalidCommitment
+        );
+        
+        let creator_old_commitment = ctx.accounts.creator_account.encrypted_balance;
+        if let Err(e) = verify_escrow_credit_proof(
+            &proof_data,
+            &ctx.accounts.claim_link.amount_commitment,
+            &creator_old_commitment,
+            &creator_new_commitment,
+        ) {
+            msg!("⚠️  Proof verification error: {:?}", e);
+            return Err(ErrorCode::InvalidProof.into());
+        }
+        
+        let creator_account = &mut ctx.accounts.creator_account;
+        creator_account.encrypted_balance = creator_new_commitment;
+        creator_account.version += 1;
+        creator_account.advance_state_hash(&creator_new_commitment);
+        creator_account.record_activity()?;
+        
+        msg!("↩️  Expired claim link reclaimed");
+        Ok(())
+    }
+
+    /// Deposit native SOL into another owner's escrow
+    ///
+    /// The payer cannot know the owner's balance opening, so instead of a
+    /// full deposit proof they commit to `amount` (opening proof) and the
+    /// program adds that commitment to the balance itself; the payer never
+    /// chooses the new balance. The blinding factor reaches the owner in
+    /// `memo`, encrypted to their registered key.
+    ///
+    /// While a balance cap is configured the cap proof needs the owner's
+    /// opening, so such deposits require the owner's cooperation.
+    #[allow(clippy::too_many_arguments)]
+    pub fn deposit_sol_for(
+        ctx: Context<DepositSOLFor>,
+        amount: u64,
+        amount_commitment: [u8; 64],
+        opening_proof: [u8; 96],
+        memo: EncryptedMemo,
+        exclusion_proof: Option<Vec<u8>>,  // Deny-list non-membership (when configured)
+        cap_proof: Option<Vec<u8>>,        // New balance <= cap (when configured)
+    ) -> Result<()> {
+        // ============================================
+        // INPUT VALIDATION (Checks)
+        // ============================================
+        
+        // Validate amount (prevent overflow and invalid amounts)
+        require!(
+            amount >= transfer_constants::MIN_AMOUNT,
+            ErrorCode::InvalidAmount
+        );
+        require!(
+            amount <= transfer_constants::MAX_AMOUNT,
+            ErrorCode::InvalidAmount
+        );
+        
+        // Validate commitments are well-formed for the configured commitment scheme
+        require!(
+            is_valid_commitment(&amount_commitment),
+            ErrorCode::InvalidCommitment
+        );
+        
+        // The memo is the owner's only way to learn the new opening
+        require!(
+            ctx.accounts.encrypted_account.encryption_key != [0u8; 32],
+            ErrorCode::InvalidEncryptionKey
+        );
+        
+        let config = Config::load_optional(&ctx.accounts.config)?;
+        
+        // SECURITY: Emergency pause (set by the config authority)
+        require_not_paused!(config.as_ref(), pause::DEPOSITS);
+        
+        // Deployment-tuned amount ceiling (compiled-in default when unset)
+        limits::enforce_amount(config.as_ref(), amount)?;
+        
+        // COMPLIANCE: Paying wallet must not be on the deny list (if configured)
+        deny_list::enforce_if_configured(
+            config.as_ref(),
+            &ctx.accounts.payer.key(),
+            exclusion_proof.as_deref(),
+            ctx.accounts.exclusion_verifying_key.as_deref().map(|vk| &**vk),
+        )?;
+        
+        // ============================================
+        // ZK PROOF VERIFICATION
+        // ============================================
+        
+        // SECURITY: The commitment hides exactly the lamports being paid in
+        if let Err(e) = verify_opening_proof(&opening_proof, &amount_commitment, amount) {
+            msg!("⚠️  Opening proof error: {:?}", e);
+            return Err(ErrorCode::InvalidProof.into());
+        }
+        
+        // SECURITY: new = old + amount commitment, computed here so a third
+        // party can only ever add what they pay in
+        let old_commitment = ctx.accounts.encrypted_account.encrypted_balance;
+        let new_commitment = match pedersen::add(&old_commitment, &amount_commitment) {
+            Ok(commitment) => commitment,
+            Err(e) => {
+                msg!("⚠️  Commitment addition error: {:?}", e);
+                return Err(ErrorCode::InvalidCommitment.into());
+            }
+        };
+        
+        // New balance must stay under the per-account cap (if configured)
+        deposit_cap::enforce_if_configured(config.as_ref(), &new_commitment, cap_proof.as_deref())?;
+        
+        // ============================================
+        // EXTERNAL CALLS (Interactions)
+        // ============================================
+        
+        // Transfer SOL from payer to the owner's escrow PDA
+        let cpi_context = CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.payer.to_account_info(),
+                to: ctx.accounts.sol_escrow.to_account_info(),
+            },
+        );
+        transfer(cpi_context, amount)?;
+        
+        // ============================================
+        // STATE UPDATES (Effects)
+        // ============================================
+        
+        let escrow = &mut ctx.accounts.sol_escrow;
+        escrow.balance = escrow.balance.checked_add(amount)
+            .ok_or(ErrorCode::Overflow)?;
+        
+        let account = &mut ctx.accounts.encrypted_account;
+        account.encrypted_balance = new_commitment;
+        account.version += 1;
+        account.advance_state_hash(&new_commitment);
+        
+        if let Some(inbox) = ctx.accounts.recipient_inbox.as_mut() {
+            inbox.push(InboxEntry {
+                id: account.state_hash,
+                note: memo.clone(),
+                slot: Clock::get()?.slot,
+            })?;
+        }
+        
+        emit!(TransferMemo {
+            recipient_account: account.key(),
+            encryption_key: account.encryption_key,
+            recipient_version: account.version,
+            memo,
+        });
+        
+        // Protocol statistics (if initialized)
+        stats::record(&ctx.accounts.stats, stats::Activity::Deposit)?;
+        
+        msg!("✅ SOL deposited on behalf of {}", account.owner);
+        msg!("   Escrow balance: {} lamports", escrow.balance);
+        msg!("   Commitment version: {}", account.version);
+        
+        Ok(())
+    }
+
+    /// Withdraw the entire SOL escrow and reset the hidden balance to zero
+    ///
+    /// `opening_proof` shows the commitment opens to exactly the escrow
+    /// balance, so nothing hidden is left behind. With `close`, both the
+    /// encrypted account and the escrow are closed in the same call.
+    pub fn sweep(
+        ctx: Context<Sweep>,
+        opening_proof: [u8; 96],
+        close: bool,
+        exclusion_proof: Option<Vec<u8>>,  // Deny-list non-membership (when configured)
+    ) -> Result<()> {
+        // ============================================
+        // INPUT VALIDATION (Checks)
+        // ============================================
+        
+        let amount = ctx.accounts.sol_escrow.balance;
+        
+        // SECURITY: Receipt tokens must stay fully backed (burn them first)
+        require!(
+            ctx.accounts.sol_escrow.receipts_outstanding == 0,
+            ErrorCode::ReceiptsOutstanding
+        );
+        
+        // ============================================
+        // ZK PROOF VERIFICATION
+        // ============================================
+        
+        // An untouched account (zero commitment, empty escrow) needs no proof
+        let commitment = ctx.accounts.encrypted_account.encrypted_balance;
+        if commitment != [0u8; 64] || amount != 0 {
+            if let Err(e) = verify_opening_proof(&opening_proof, &commitment, amount) {
+                msg!("⚠️  Opening proof error: {:?}", e);
+                return Err(ErrorCode::InvalidProof.into());
+            }
+        }
+        
+        let config = Config::load_optional(&ctx.accounts.config)?;
+        
+        // SECURITY: Emergency pause and circuit breaker (set by the config authority)
+        require_not_paused!(config.as_ref(), pause::WITHDRAWALS);
+        limits::enforce_transfer_rate(config.as_ref(), &ctx.accounts.stats)?;
+        
+        // COMPLIANCE: Owner must be cleared by the sanctions oracle and
+        // excluded from the deny list (each only if configured)
+        sanctions::enforce_if_configured(
+            config.as_ref(),
+            ctx.accounts.sanctions_attestation.as_deref(),
+            &ctx.accounts.owner.key(),
+        )?;
+        deny_list::enforce_if_configured(
+            config.as_ref(),
+            &ctx.accounts.owner.key(),
+            exclusion_proof.as_deref(),
+            ctx.accounts.exclusion_verifying_key.as_deref().map(|vk| &**vk),
+        )?;
+        
+        // ============================================
+        // STATE UPDATES (Effects)
+        // ============================================
+        
+        ctx.accounts.sol_escrow.balance = 0;
+        
+        let account = &mut ctx.accounts.encrypted_account;
+        account.encrypted_balance = [0u8; 64]; // Canonical zero state
+        account.version += 1;
+        account.advance_state_hash(&[0u8; 64]);
+        account.record_activity()?;
+        
+        // ============================================
+        // EXTERNAL CALLS (Interactions)
+        // ============================================
+        
+        // Program-owned escrow: move lamports directly
+        let escrow_info = ctx.accounts.sol_escrow.to_account_info();
+        let owner_info = ctx.accounts.owner.to_account_info();
+        let new_escrow_lamports = escrow_info.lamports().checked_sub(amount)
+            .ok_or(ErrorCode::Underflow)?;
+        let new_owner_lamports = owner_info.lamports().checked_add(amount)
+            .ok_or(ErrorCode::Overflow)?;
+        **escrow_info.try_borrow_mut_lamports()? = new_escrow_lamports;
+        **owner_info.try_borrow_mut_lamports()? = new_owner_lamports;
+        
+        if close {
+            let owner_info = ctx.accounts.owner.to_account_info();
+            ctx.accounts.sol_escrow.close(owner_info.clone())?;
+            ctx.accounts.encrypted_account.close(owner_info)?;
+            msg!("✅ Swept {} lamports and closed the account", amount);
+        } else {
+            msg!("✅ Swept {} lamports; account reset to zero", amount);
+        }
+        
+        // Protocol statistics (if initialized)
+        stats::record(&ctx.accounts.stats, stats::Activity::Withdrawal)?;
+        
+        Ok(())
+    }
+
+    /// Merge one of the owner's encrypted accounts into another
+    ///
+    /// The target absorbs the source's whole hidden balance
+    /// (target_new = target_old + source) and the source is reset to the
+    /// canonical zero state, ready to be closed or reused.
+    /// PRIVACY: Balances are NEVER revealed on-chain!
+    pub fn merge_accounts(
+        ctx: Context<MergeAccounts>,
+        target_new_commitment: [u8; 64],
+        proof_data: [u8; 96],
+    ) -> Result<()> {
+        // SECURITY: Emergency pause and circuit breaker (set by the config authority)
+        let config = Config::load_optional(&ctx.accounts.config)?;
+        require_not_paused!(config.as_ref(), pause::TRANSFERS);
+        limits::enforce_transfer_rate(config.as_ref(), &ctx.accounts.stats)?;
+        
+        require!(
+            ctx.accounts.source_account.key() != ctx.accounts.target_account.key(),
+            ErrorCode::InvalidRecipient
+        );
+        require!(
+            is_valid_commitment(&target_new_commitment),
+            ErrorCode::InvalidCommitment
+        );
+        
+        let source_commitment = ctx.accounts.source_account.encrypted_balance;
+        require!(
+            source_commitment != [0u8; 64],
+            ErrorCode::InvalidCommitment
+        );
+        
+        // SECURITY: The target grows by exactly the source balance
+        let target_old_commitment = ctx.accounts.target_account.encrypted_balance;
+        if let Err(e) = verify_escrow_credit_proof(
+            &proof_data,
+            &source_commitment,
+            &target_old_commitment,
+            &target_new_commitment,
+        ) {
+            msg!("⚠️  Proof verification error: {:?}", e);
+            return Err(ErrorCode::InvalidProof.into());
+        }
+        
+        let source_account = &mut ctx.accounts.source_account;
+        source_account.encrypted_balance = [0u8; 64];
+        source_account.version += 1;
+        source_account.advance_state_hash(&[0u8; 64]);
+        source_account.record_activity()?;
+        
+        let target_account = &mut ctx.accounts.target_account;
+        target_account.encrypted_balance = target_new_commitment;
+        target_account.version += 1;
+        target_account.advance_state_hash(&target_new_commitment);
+        target_account.record_activity()?;
+        
+        // Protocol statistics (if initialized)
+        stats::record(&ctx.accounts.stats, stats::Activity::Transfer)?;
+        
+        msg!("✅ Merged account {} into {}", source_account.account_index, target_account.account_index);
+        Ok(())
+    }
+
+    /// Move a hidden amount from one of the owner's encrypted accounts to another
+    ///
+    /// `debit_proof` shows source_old - source_new = amount with both in
+    /// range; `credit_proof` shows target_new = target_old + amount. The
+    /// target is typically a freshly initialized sub-account.
+    /// PRIVACY: Amount is NEVER revealed on-chain!
+    pub fn split_account(
+        ctx: Context<SplitAccount>,
+        source_new_commitment: [u8; 64],
+        target_new_commitment: [u8; 64],
+        debit_proof: Vec<u8>,
+        credit_proof: [u8; 96],
+    ) -> Result<()> {
+        // ============================================
+        // INPUT VALIDATION (Checks)
+        // ============================================
+        
+        // SECURITY: Emergency pause and circuit breaker (set by the config authority)
+        let config = Config::load_optional(&ctx.accounts.config)?;
+        require_not_paused!(config.as_ref(), pause::TRANSFERS);
+        limits::enforce_transfer_rate(config.as_ref(), &ctx.accounts.stats)?;
+        
+        require!(
+            ctx.accounts.source_account.key() != ctx.accounts.target_account.key(),
+            ErrorCode::InvalidRecipient
+        );
+        
+        // Validate proof data size (DoS protection)
+        require!(
+            debit_proof.len() >= proof_constants::MIN_PROOF_DATA_SIZE,
+            ErrorCode::InvalidProof
+        );
+        require!(
+            debit_proof.len() <= proof_constants::MAX_PROOF_DATA_SIZE,
+            ErrorCode::InvalidProof
+        );
+        
+        require!(
+            is_valid_commitment(&source_new_commitment),
+            ErrorCode::InvalidCommitment
+        );
+        require!(
+            is_valid_commitment(&target_new_commitment),
+            ErrorCode::InvalidCommitment
+        );
+        
+        let source_old_commitment = ctx.accounts.source_account.encrypted_balance;
+        let target_old_commitment = ctx.accounts.target_account.encrypted_balance;
+        require!(
+            source_old_commitment != [0u8; 64],
+            ErrorCode::InvalidCommitment
+        );
+        
+        // ============================================
+        // ZK PROOF VERIFICATION
+        // ============================================
+        
+        let amount_commitment = match proof_verification::extract_amount_commitment(&debit_proof) {
+            Ok(commitment) => commitment,
+            Err(e) => {
+                msg!("⚠️  Failed to extract amount commitment from proof: {:?}", e);
+                return Err(ErrorCode::InvalidProof.into());
+            }
+        };
+        
+        if let Err(e) = verify_escrow_debit_proof(
+            &debit_proof,
+            &amount_commitment,
+            &source_old_commitment,
+            &source_new_commitment,
+        ) {
+            msg!("⚠️  Proof verification error: {:?}", e);
+            return Err(ErrorCode::InvalidProof.into());
+        }
+        
+        if let Err(e) = verify_escrow_credit_proof(
+            &credit_proof,
+            &amount_commitment,
+            &target_old_commitment,
+            &target_new_commitment,
+        ) {
+            msg!("⚠️  Proof verification error: {:?}", e);
+            return Err(ErrorCode::InvalidProof.into());
+        }
+        
+        // ============================================
+        // STATE UPDATES (Effects)
+        // ============================================
+        
+        let source_account = &mut ctx.accounts.source_account;
+        source_account.encrypted_balance = source_new_commitment;
+        source_account.version += 1;
+        source_account.advance_state_hash(&source_new_commitment);
+        source_account.record_activity()?;
+        
+        let target_account = &mut ctx.accounts.target_account;
+        target_account.encrypted_balance = target_new_commitment;
+        target_account.version += 1;
+        target_account.advance_state_hash(&target_new_commitment);
+        target_account.record_activity()?;
+        
+        // Protocol statistics (if initialized)
+        stats::record(&ctx.accounts.stats, stats::Activity::Transfer)?;
+        
+        msg!("✅ Split account {} into {}", source_account.account_index, target_account.account_index);
+        msg!("   ❌ AMOUNT IS HIDDEN!");
+        Ok(())
+    }
+
+    /// Open a payment stream with a hidden per-second rate
+    ///
+    /// The sender escrows a hidden deposit (debit proof) that the recipient
+    /// draws down as time passes. An underfunded stream simply stops paying
+    /// out: claims beyond the deposit fail the remainder range proof.
+    /// PRIVACY: Rate and deposit are NEVER revealed on-chain!
+    pub fn create_stream(
+        ctx: Context<CreateStream>,
+        stream_id: u64,
+        rate_commitment: [u8; 64],
+        sender_new_commitment: [u8; 64],
+        deposit_proof: Vec<u8>,
+        duration_secs: u64,
+    ) -> Result<()> {
+        // ============================================
+        // INPUT VALIDATION (Checks)
+        // ============================================
+        
+        // SECURITY: Emergency pause and circuit breaker (set by the config authority)
+        let config = Config::load_optional(&ctx.accounts.config)?;
+        require_not_paused!(config.as_ref(), pause::TRANSFERS);
+        limits::enforce_transfer_rate(config.as_ref(), &ctx.accounts.stats)?;
+        
+        require!(
+            ctx.accounts.sender_account.key() != ctx.accounts.recipient_account.key(),
+            ErrorCode::InvalidRecipient
+        );
+        require!(
+            (stream_constants::MIN_DURATION_SECS..=stream_constants::MAX_DURATION_SECS)
+                .contains(&duration_secs),
+            ErrorCode::InvalidStreamDuration
+        );
+        
+        // Validate proof data size (DoS protection)
+        require!(
+            deposit_proof.len() >= proof_constants::MIN_PROOF_DATA_SIZE,
+            ErrorCode::InvalidProof
+        );
+        require!(
+            deposit_proof.len() <= proof_constants::MAX_PROOF_DATA_SIZE,
+            ErrorCode::InvalidProof
+        );
+        
+        require!(
+            is_valid_commitment(&rate_commitment),
+            ErrorCode::InvalidCommitment
+        );
+        require!(
+            is_valid_commitment(&sender_new_commitment),
+            ErrorCode::InvalidCommitment
+        );
+        
+        let sender_old_commitment = ctx.accounts.sender_account.encrypted_balance;
+        require!(
+            sender_old_commitment != [0u8; 64],
+            ErrorCode::InvalidCommitment
+        );
+        
+        // ============================================
+        // ZK PROOF VERIFICATION
+        // ============================================
+        
+        let deposit_commitment = match proof_verification::extract_amount_commitment(&deposit_proof) {
+            Ok(commitment) => commitment,
+            Err(e) => {
+                msg!("⚠️  Failed to extract amount commitment from proof: {:?}", e);
+                return Err(ErrorCode::InvalidProof.into());
+            }
+        };
+        
+        if let Err(e) = verify_escrow_debit_proof(
+            &deposit_proof,
+            &deposit_commitment,
+            &sender_old_commitment,
+            &sender_new_commitment,
+        ) {
+            msg!("⚠️  Proof verification error: {:?}", e);
+            return Err(ErrorCode::InvalidProof.into());
+        }
+        
+        // ============================================
+        // STATE UPDATES (Effects)
+        // ============================================
+        
+        let sender_account = &mut ctx.accounts.sender_account;
+        sender_account.encrypted_balance = sender_new_commitment;
+        sender_account.version += 1;
+        sender_account.advance_state_hash(&sender_new_commitment);
+        sender_account.record_activity()?;
+        
+        let clock = Clock::get()?;
+        let stream = &mut ctx.accounts.stream;
+        stream.sender = ctx.accounts.sender.key();
+        stream.sender_account = ctx.accounts.sender_account.key();
+        stream.recipient_account = ctx.accounts.recipient_account.key();
+        stream.stream_id = stream_id;
+        stream.rate_commitment = rate_commitment;
+        stream.remaining_commitment = deposit_commitment;
+        stream.start_slot = clock.slot;
+        stream.start_time = clock.unix_timestamp;
+        stream.end_time = clock.unix_timestamp.checked_add(duration_secs as i64)
+            .ok_or(ErrorCode::Overflow)?;
+        stream.claimed_until = clock.unix_timestamp;
+        stream.bump = ctx.bumps.stream;
+        
+        emit!(StreamCreated {
+            stream: stream.key(),
+            sender_account: stream.sender_account,
+            recipient_account: stream.recipient_account,
+            start_time: stream.start_time,
+            end_time: stream.end_time,
+        });
+        
+        // Protocol statistics (if initialized)
+        stats::record(&ctx.accounts.stats, stats::Activity::Transfer)?;
+        
+        msg!("🌊 Stream {} started, ends at {}", stream_id, stream.end_time);
+        msg!("   ❌ RATE AND DEPOSIT ARE HIDDEN!");
+        Ok(())
+    }
+
+    /// Claim everything a stream accrued since the last claim
+    ///
+    /// `accrual_proof` shows claim = elapsed * rate for the public elapsed
+    /// seconds; `debit_proof` takes the claim out of the stream deposit (the
+    /// remainder stays in range); `credit_proof` adds it to the recipient.
+    /// PRIVACY: Rate and claimed amount are NEVER revealed on-chain!
+    pub fn withdraw_stream(
+        ctx: Context<WithdrawStream>,
+        remaining_new_commitment: [u8; 64],
+        recipient_new_commitment: [u8; 64],
+        debit_proof: Vec<u8>,
+        accrual_proof: [u8; 96],
+        credit_proof: [u8; 96],
+    ) -> Result<()> {
+        // ============================================
+        // INPUT VALIDATION (Checks)
+        // ============================================
+        
+        // SECURITY: Emergency pause and circuit breaker (set by the config authority)
+        let config = Config::load_optional(&ctx.accounts.config)?;
+        require_not_paused!(config.as_ref(), pause::TRANSFERS);
+        limits::enforce_transfer_rate(config.as_ref(), &ctx.accounts.stats)?;
+        
+        let now = Clock::get()?.unix_timestamp;
+        let stream = &ctx.accounts.stream;
+        let accrued_until = now.min(stream.end_time);
+        require!(
+            accrued_until > stream.claimed_until,
+            ErrorCode::StreamNothingAccrued
+        );
+        let elapsed = (accrued_until - stream.claimed_until) as u64;
+        
+        // Validate proof data size (DoS protection)
+        require!(
+            debit_proof.len() >= proof_constants::MIN_PROOF_DATA_SIZE,
+            ErrorCode::InvalidProof
+        );
+        require!(
+            debit_proof.len() <= proof_constants::MAX_PROOF_DATA_SIZE,
+            ErrorCode::InvalidProof
+        );
+        
+        require!(
+            is_valid_commitment(&remaining_new_commitment),
+            ErrorCode::InvalidCommitment
+        );
+        require!(
+            is_valid_commitment(&recipient_new_commitment),
+            ErrorCode::InvalidCommitment
+        );
+        
+        // ============================================
+        // ZK PROOF VERIFICATION
+        // ============================================
+        
+        let claim_commitment = match proof_verification::extract_amount_commitment(&debit_proof) {
+            Ok(commitment) => commitment,
+            Err(e) => {
+                msg!("⚠️  Failed to extract amount commitment from proof: {:?}", e);
+                return Err(ErrorCode::InvalidProof.into());
+            }
+        };
+        
+        // SECURITY: The claim is exactly what accrued over `elapsed` seconds
+        if let Err(e) = verify_stream_accrual_proof(
+            &accrual_proof,
+            &stream.rate_commitment,
+            &claim_commitment,
+            elapsed,
+        ) {
+            msg!("⚠️  Accrual proof error: {:?}", e);
+            return Err(ErrorCode::InvalidProof.into());
+        }
+        
+        if let Err(e) = verify_escrow_debit_proof(
+            &debit_proof,
+            &claim_commitment,
+            &stream.remaining_commitment,
+            &remaining_new_commitment,
+        ) {
+            msg!("⚠️  Proof verification error: {:?}", e);
+            return Err(ErrorCode::InvalidProof.into());
+        }
+        
+        let recipient_old_commitment = ctx.accounts.recipient_account.encrypted_balance;
+        if let Err(e) = verify_escrow_credit_proof(
+            &credit_proof,
+            &claim_commitment,
+            &recipient_old_commitment,
+            &recipient_new_commitment,
+        ) {
+            msg!("⚠️  Proof verification error: {:?}", e);
+            return Err(ErrorCode::InvalidProof.into());
+        }
+        
+        // ============================================
+        // STATE UPDATES (Effects)
+        // ============================================
+        
+        let stream = &mut ctx.accounts.stream;
+        stream.remaining_commitment = remaining_new_commitment;
+        stream.claimed_until = accrued_until;
+        
+        let recipient_account = &mut ctx.accounts.recipient_account;
+        recipient_account.encrypted_balance = recipient_new_commitment;
+        recipient_account.version += 1;
+        recipient_account.advance_state_hash(&recipient_new_commitment);
+        recipient_account.record_activity()?;
+        
+        emit!(StreamWithdrawn {
+            stream: stream.key(),
+            claimed_until: accrued_until,
+        });
+        
+        msg!("✅ Claimed {} seconds of stream {}", elapsed, stream.stream_id);
+        msg!("   ❌ AMOUNT IS HIDDEN!");
+        Ok(())
+    }
+
+    /// Stop a stream early (sender only); accrual ends now
+    ///
+    /// What accrued so far stays claimable by the recipient; the rest of the
+    /// deposit returns to the sender through close_stream.
+    pub fn cancel_stream(ctx: Context<CancelStream>) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        let stream = &mut ctx.accounts.stream;
+        require!(now < stream.end_time, ErrorCode::StreamEnded);
+        
+        stream.end_time = now.max(stream.claimed_until);
+        
+        msg!("🛑 Stream {} cancelled at {}", stream.stream_id, stream.end_time);
+        Ok(())
+    }
+
+    /// Return the leftover deposit of a finished stream to the sender
+    ///
+    /// Allowed once the recipient has claimed up to the end, or after
+    /// stream_constants::SETTLEMENT_GRACE_SECS past the end (unclaimed
+    /// accruals are then forfeited). Closes the stream (rent to the sender).
+    /// PRIVACY: Amount is NEVER revealed on-chain!
+    pub fn close_stream(
+        ctx: Context<CloseStream>,
+        sender_new_commitment: [u8; 64],
+        credit_proof: [u8; 96],
+    ) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        let stream = &ctx.accounts.stream;
+        let settled = stream.claimed_until >= stream.end_time;
+        let grace_over = now >= stream.end_time.saturating_add(stream_constants::SETTLEMENT_GRACE_SECS);
+        require!(settled || grace_over, ErrorCode::StreamNotSettled);
+        require!(
+            is_valid_commitment(&sender_new_commitment),
+            ErrorCode::InvalidCommitment
+        );
+        
+        let sender_old_commitment = ctx.accounts.sender_account.encrypted_balance;
+        if let Err(e) = verify_escrow_credit_proof(
+            &credit_proof,
+            &stream.remaining_commitment,
+            &sender_old_commitment,
+            &sender_new_commitment,
+        ) {
+            msg!("⚠️  Proof verification error: {:?}", e);
+            return Err(ErrorCode::InvalidProof.into());
+        }
+        
+        let sender_account = &mut ctx.accounts.sender_account;
+        sender_account.encrypted_balance = sender_new_commitment;
+        sender_account.version += 1;
+        sender_account.advance_state_hash(&sender_new_commitment);
+        sender_account.record_activity()?;
+        
+        msg!("✅ Stream {} closed; leftover deposit returned", ctx.accounts.stream.stream_id);
+        Ok(())
+    }
+
+    /// Lock a hidden grant that vests linearly to a beneficiary
+    ///
+    /// Nothing is claimable before `start_time + cliff_secs`; from then on
+    /// the vested share grows linearly until `start_time + duration_secs`.
+    /// PRIVACY: The grant size is NEVER revealed on-chain!
+    pub fn create_vesting(
+        ctx: Context<CreateVesting>,
+        vesting_id: u64,
+        grantor_new_commitment: [u8; 64],
+        lock_proof: Vec<u8>,
+        start_time: i64,
+        cliff_secs: u64,
+        duration_secs: u64,
+    ) -> Result<()> {
+        // ============================================
+        // INPUT VALIDATION (Checks)
+        // ============================================
+        
+        // SECURITY: Emergency pause and circuit breaker (set by the config authority)
+        let config = Config::load_optional(&ctx.accounts.config)?;
+        require_not_paused!(config.as_ref(), pause::TRANSFERS);
+        limits::enforce_transfer_rate(config.as_ref(), &ctx.accounts.stats)?;
+        
+        require!(
+            ctx.accounts.grantor_account.key() != ctx.accounts.beneficiary_account.key(),
+            ErrorCode::InvalidRecipient
+        );
+        require!(
+            duration_secs > 0
+                && duration_secs <= vesting_constants::MAX_DURATION_SECS
+                && cliff_secs <= duration_secs,
+            ErrorCode::InvalidVestingSchedule
+        );
+        
+        // Validate proof data size (DoS protection)
+        require!(
+            lock_proof.len() >= proof_constants::MIN_PROOF_DATA_SIZE,
+            ErrorCode::InvalidProof
+        );
+        require!(
+            lock_proof.len() <= proof_constants::MAX_PROOF_DATA_SIZE,
+            ErrorCode::InvalidProof
+        );
+        
+        require!(
+            is_valid_commitment(&grantor_new_commitment),
+            ErrorCode::InvalidCommitment
+        );
+        
+        let grantor_old_commitment = ctx.accounts.grantor_account.encrypted_balance;
+        require!(
+            grantor_old_commitment != [0u8; 64],
+            ErrorCode::InvalidCommitment
+        );
+        
+        // ============================================
+        // ZK PROOF VERIFICATION
+        // ============================================
+        
+        let total_commitment = match proof_verification::extract_amount_commitment(&lock_proof) {
+            Ok(commitment) => commitment,
+            Err(e) => {
+                msg!("⚠️  Failed to extract amount commitment from proof: {:?}", e);
+                return Err(ErrorCode::InvalidProof.into());
+            }
+        };
+        
+        if let Err(e) = verify_escrow_debit_proof(
+            &lock_proof,
+            &total_commitment,
+            &grantor_old_commitment,
+            &grantor_new_commitment,
+        ) {
+            msg!("⚠️  Proof verification error: {:?}", e);
+            return Err(ErrorCode::InvalidProof.into());
+        }
+        
+        // ============================================
+        // STATE UPDATES (Effects)
+        // ============================================
+        
+        let grantor_account = &mut ctx.accounts.grantor_account;
+        grantor_account.encrypted_balance = grantor_new_commitment;
+        grantor_account.version += 1;
+        grantor_account.advance_state_hash(&grantor_new_commitment);
+        grantor_account.record_activity()?;
+        
+        let vesting = &mut ctx.accounts.vesting;
+        vesting.grantor = ctx.accounts.grantor.key();
+        vesting.grantor_account = ctx.accounts.grantor_account.key();
+        vesting.beneficiary_account = ctx.accounts.beneficiary_account.key();
+        vesting.vesting_id = vesting_id;
+        vesting.total_commitment = total_commitment;
+        vesting.remaining_commitment = total_commitment;
+        vesting.start_time = start_time;
+        vesting.cliff_time = start_time.checked_add(cliff_secs as i64)
+            .ok_or(ErrorCode::Overflow)?;
+        vesting.end_time = start_time.checked_add(duration_secs as i64)
+            .ok_or(ErrorCode::Overflow)?;
+        vesting.bump = ctx.bumps.vesting;
+        
+        emit!(VestingCreated {
+            vesting: vesting.key(),
+            beneficiary_account: vesting.beneficiary_account,
+            start_time,
+            cliff_time: vesting.cliff_time,
+            end_time: vesting.end_time,
+        });
+        
+        // Protocol statistics (if initialized)
+        stats::record(&ctx.accounts.stats, stats::Activity::Transfer)?;
+        
+        msg!("🔒 Vesting {} created ({}s cliff, {}s total)", vesting_id, cliff_secs, duration_secs);
+        msg!("   ❌ GRANT SIZE IS HIDDEN!");
+        Ok(())
+    }
+
+    /// Claim a vested tranche into the beneficiary's balance
+    ///
+    /// `debit_proof` takes the tranche out of the locked remainder,
+    /// `schedule_proof` shows the new remainder still covers the unvested
+    /// share (see proof_verification::verify_vesting_schedule_proof) and
+    /// `credit_proof` adds the tranche to the beneficiary.
+    /// PRIVACY: Tranche and grant size are NEVER revealed on-chain!
+    pub fn claim_vested(
+        ctx: Context<ClaimVested>,
+        remaining_new_commitment: [u8; 64],
+        beneficiary_new_commitment: [u8; 64],
+        debit_proof: Vec<u8>,
+        schedule_proof: Vec<u8>,
+        credit_proof: [u8; 96],
+    ) -> Result<()> {
+        // ============================================
+        // INPUT VALIDATION (Checks)
+        // ============================================
+        
+        // SECURITY: Emergency pause and circuit breaker (set by the config authority)
+        let config = Config::load_optional(&ctx.accounts.config)?;
+        require_not_paused!(config.as_ref(), pause::TRANSFERS);
+        limits::enforce_transfer_rate(config.as_ref(), &ctx.accounts.stats)?;
+        
+        let now = Clock::get()?.unix_timestamp;
+        let vesting = &ctx.accounts.vesting;
+        require!(now >= vesting.cliff_time, ErrorCode::VestingCliffNotReached);
+        
+        let duration = (vesting.end_time - vesting.start_time) as u64;
+        let elapsed = (now.min(vesting.end_time) - vesting.start_time) as u64;
+        
+        // Validate proof data size (DoS protection)
+        for proof in [&debit_proof, &schedule_proof] {
+            require!(
+                proof.len() >= proof_constants::MIN_PROOF_DATA_SIZE,
+                ErrorCode::InvalidProof
+            );
+            require!(
+                proof.len() <= proof_constants::MAX_PROOF_DATA_SIZE,
+                ErrorCode::InvalidProof
+            );
+        }
+        
+        require!(
+            is_valid_commitment(&remaining_new_commitment),
+            ErrorCode::InvalidCommitment
+        );
+        require!(
+            is_valid_commitment(&beneficiary_new_commitment),
+            ErrorCode::InvalidCommitment
+        );
+        
+        // ============================================
+        // ZK PROOF VERIFICATION
+        // ============================================
+        
+        let tranche_commitment = match proof_verification::extract_amount_commitment(&debit_proof) {
+            Ok(commitment) => commitment,
+            Err(e) => {
+                msg!("⚠️  Failed to extract amount commitment from proof: {:?}", e);
+                return Err(ErrorCode::InvalidProof.into());
+            }
+        };
+        
+        if let Err(e) = verify_escrow_debit_proof(
+            &debit_proof,
+            &tranche_commitment,
+            &vesting.remaining_commitment,
+            &remaining_new_commitment,
+        ) {
+            msg!("⚠️  Proof verification error: {:?}", e);
+            return Err(ErrorCode::InvalidProof.into());
+        }
+        
+        // SECURITY: Cumulative claims never exceed the vested share
+        if let Err(e) = verify_vesting_schedule_proof(
+            &schedule_proof,
+            &vesting.total_commitment,
+            &remaining_new_commitment,
+            elapsed,
+            duration,
+        ) {
+            msg!("⚠️  Vesting schedule proof error: {:?}", e);
+            return Err(ErrorCode::InvalidProof.into());
+        }
+        
+        let beneficiary_old_commitment = ctx.accounts.beneficiary_account.encrypted_balance;
+        if let Err(e) = verify_escrow_credit_proof(
+            &credit_proof,
+            &tranche_commitment,
+            &beneficiary_old_commitment,
+            &beneficiary_new_commitment,
+        ) {
+            msg!("⚠️  Proof verification error: {:?}", e);
+            return Err(ErrorCode::InvalidProof.into());
+        }
+        
+        // ============================================
+        // STATE UPDATES (Effects)
+        // ============================================
+        
+        ctx.accounts.vesting.remaining_commitment = remaining_new_commitment;
+        
+        let beneficiary_account = &mut ctx.accounts.beneficiary_account;
+        beneficiary_account.encrypted_balance = beneficiary_new_commitment;
+        beneficiary_account.version += 1;
+        beneficiary_account.advance_state_hash(&beneficiary_new_commitment);
+        beneficiary_account.record_activity()?;
+        
+        msg!("✅ Vested tranche claimed ({}/{}s elapsed)", elapsed, duration);
+        msg!("   ❌ AMOUNT IS HIDDEN!");
+        Ok(())
+    }
+
+    /// Close a fully vested grant (beneficiary only; rent to the grantor)
+    ///
+    /// Only possible after the end of the schedule. Anything left unclaimed
+    /// is forfeited, so wallets claim the final tranche first.
+    pub fn close_vesting(ctx: Context<CloseVesting>) -> Result<()> {
+        require!(
+            Clock::get()?.unix_timestamp >= ctx.accounts.vesting.end_time,
+            ErrorCode::InvalidVestingSchedule
+        );
+        
+        ctx.accounts.beneficiary_account.record_activity()?;
+        
+        msg!("Vesting {} closed", ctx.accounts.vesting.vesting_id);
+        Ok(())
+    }
+
+    /// Authorize a merchant to pull a fixed hidden amount once per period
+    ///
+    /// The merchant learns the amount (and its opening) off-chain; on-chain
+    /// only its commitment is stored. The first pull is allowed from
+    /// `first_payment_time`. To change the terms, cancel and approve again.
+    /// PRIVACY: The subscription price is NEVER revealed on-chain!
+    pub fn approve_subscription(
+        ctx: Context<ApproveSubscription>,
+        amount_commitment: [u8; 64],
+        period_secs: u64,
+        first_payment_time: i64,
+    ) -> Result<()> {
+        require!(
+            ctx.accounts.subscriber_account.key() != ctx.accounts.merchant_account.key(),
+            ErrorCode::InvalidRecipient
+        );
+        require!(
+            (subscription_constants::MIN_PERIOD_SECS..=subscription_constants::MAX_PERIOD_SECS)
+                .contains(&period_secs),
+            ErrorCode::InvalidSubscriptionPeriod
+        );
+        require!(
+            is_valid_commitment(&amount_commitment),
+            ErrorCode::InvalidCommitment
+        );
+        
+        let subscription = &mut ctx.accounts.subscription;
+        subscription.owner = ctx.accounts.owner.key();
+        subscription.subscriber_account = ctx.accounts.subscriber_account.key();
+        subscription.merchant_account = ctx.accounts.merchant_account.key();
+        subscription.amount_commitment = amount_commitment;
+        subscription.period_secs = period_secs;
+        subscription.next_payment_time = first_payment_time;
+        subscription.payments_made = 0;
+        subscription.bump = ctx.bumps.subscription;
+        
+        ctx.accounts.subscriber_account.record_activity()?;
+        
+        msg!("🔁 Subscription approved (every {}s)", period_secs);
+        msg!("   ❌ AMOUNT IS HIDDEN!");
+        Ok(())
+    }
+
+    /// Pull one period's payment from the subscriber to the merchant
+    ///
+    /// Callable by the merchant or any crank once the period has elapsed.
+    /// `match_proof` shows the transferred amount equals the authorized
+    /// commitment, so a pull can never exceed what the owner approved.
+    /// PRIVACY: Amount and balances are NEVER revealed on-chain!
+    pub fn execute_subscription(
+        ctx: Context<ExecuteSubscription>,
+        subscriber_new_commitment: [u8; 64],
+        merchant_new_commitment: [u8; 64],
+        proof_data: Vec<u8>,
+        match_proof: [u8; 96],
+        expected_subscriber_version: u64,
+        expected_merchant_version: u64,
+    ) -> Result<()> {
+        // ============================================
+        // INPUT VALIDATION (Checks)
+        // ============================================
+        
+        // SECURITY: Emergency pause and circuit breaker (set by the config authority)
+        let config = Config::load_optional(&ctx.accounts.config)?;
+        require_not_paused!(config.as_ref(), pause::TRANSFERS);
+        limits::enforce_transfer_rate(config.as_ref(), &ctx.accounts.stats)?;
+        
+        // SECURITY: Optimistic concurrency - the proof was built against these
+        // exact states; a concurrent update would make it stale
+        require!(
+            ctx.accounts.subscriber_account.version == expected_subscriber_version,
+            ErrorCode::StaleAccountVersion
+        );
+        require!(
+            ctx.accounts.merchant_account.version == expected_merchant_version,
+            ErrorCode::StaleAccountVersion
+        );
+        
+        let now = Clock::get()?.unix_timestamp;
+        require!(
+            now >= ctx.accounts.subscription.next_payment_time,
+            ErrorCode::SubscriptionPeriodNotElapsed
+        );
+        
+        // Validate proof data size (DoS protection)
+        require!(
+            proof_data.len() >= proof_constants::MIN_PROOF_DATA_SIZE,
+            ErrorCode::InvalidProof
+        );
+        require!(
+            proof_data.len() <= proof_constants::MAX_PROOF_DATA_SIZE,
+            ErrorCode::InvalidProof
+        );
+        
+        require!(
+            is_valid_commitment(&subscriber_new_commitment),
+            ErrorCode::InvalidCommitment
+        );
+        require!(
+            is_valid_commitment(&merchant_new_commitment),
+            ErrorCode::InvalidCommitment
+        );
+        
+        let subscriber_old_commitment = ctx.accounts.subscriber_account.encrypted_balance;
+        let merchant_old_commitment = ctx.accounts.merchant_account.encrypted_balance;
+        require!(
+            subscriber_old_commitment != [0u8; 64],
+            ErrorCode::InvalidCommitment
+        );
+        
+        // ============================================
+        // ZK PROOF VERIFICATION
+        // ============================================
+        
+        let amount_commitment = match proof_verification::extract_amount_commitment(&proof_data) {
+            Ok(commitment) => commitment,
+            Err(e) => {
+                msg!("⚠️  Failed to extract amount commitment from proof: {:?}", e);
+                return Err(ErrorCode::InvalidProof.into());
+            }
+        };
+        
+        if let Err(e) = verify_transfer_proof(
+            &proof_data,
+            &amount_commitment,
+            &subscriber_new_commitment,
+            &subscriber_old_commitment,
+            &merchant_old_commitment,
+            &merchant_new_commitment,
+        ) {
+            msg!("⚠️  Proof verification error: {:?}", e);
+            return Err(ErrorCode::InvalidProof.into());
+        }
+        
+        // SECURITY: The pull must be exactly the authorized amount
+        if let Err(e) = verify_amount_match_proof(
+            &match_proof,
+            &ctx.accounts.subscription.amount_commitment,
+            &amount_commitment,
+        ) {
+            msg!("⚠️  Subscription amount mismatch: {:?}", e);
+            return Err(ErrorCode::SubscriptionAmountMismatch.into());
+        }
+        
+        // ============================================
+        // STATE UPDATES (Effects)
+        // ============================================
+        
+        let subscriber_account = &mut ctx.accounts.subscriber_account;
+        subscriber_account.encrypted_balance = subscriber_new_commitment;
+        subscriber_account.version += 1;
+        subscriber_account.advance_state_hash(&subscriber_new_commitment);
+        
+        let merchant_account = &mut ctx.accounts.merchant_account;
+        merchant_account.encrypted_balance = merchant_new_commitment;
+        merchant_account.version += 1;
+        merchant_account.advance_state_hash(&merchant_new_commitment);
+        
+        // SECURITY: Advance by exactly one period so missed periods can be
+        // caught up one pull at a time, never more than one per period
+        let subscription = &mut ctx.accounts.subscription;
+        subscription.next_payment_time = subscription.next_payment_time
+            .checked_add(subscription.period_secs as i64)
+            .ok_or(ErrorCode::Overflow)?;
+        subscription.payments_made = subscription.payments_made
+            .checked_add(1)
+            .ok_or(ErrorCode::Overflow)?;
+        
+        emit!(SubscriptionCharged {
+            subscription: subscription.key(),
+            subscriber_account: subscription.subscriber_account,
+            merchant_account: subscription.merchant_account,
+            payments_made: subscription.payments_made,
+            next_payment_time: subscription.next_payment_time,
+        });
+        
+        // Protocol statistics (if initialized)
+        stats::record(&ctx.accounts.stats, stats::Activity::Transfer)?;
+        
+        msg!("✅ Subscription payment #{} pulled", subscription.payments_made);
+        msg!("   ❌ AMOUNT IS HIDDEN!");
+        Ok(())
+    }
+
+    /// Cancel a subscription (owner only) and reclaim its rent
+    pub fn cancel_subscription(_ctx: Context<CancelSubscription>) -> Result<()> {
+        msg!("Subscription cancelled");
+        Ok(())
+    }
+
+    /// Approve a delegate to move up to a hidden allowance from an account
+    ///
+    /// The delegate learns the allowance (and its opening) off-chain. To
+    /// change it, revoke and approve again.
+    /// PRIVACY: The allowance is NEVER revealed on-chain!
+    pub fn approve(
+        ctx: Context<Approve>,
+        allowance_commitment: [u8; 64],
+    ) -> Result<()> {
+        require!(
+            ctx.accounts.delegate.key() != ctx.accounts.owner.key(),
+            ErrorCode::InvalidDelegate
+        );
+        require!(
+            is_valid_commitment(&allowance_commitment),
+            ErrorCode::InvalidCommitment
+        );
+        
+        let allowance = &mut ctx.accounts.allowance;
+        allowance.owner = ctx.accounts.owner.key();
+        allowance.owner_account = ctx.accounts.owner_account.key();
+        allowance.delegate = ctx.accounts.delegate.key();
+        allowance.allowance_commitment = allowance_commitment;
+        allowance.bump = ctx.bumps.allowance;
+        
+        ctx.accounts.owner_account.record_activity()?;
+        
+        msg!("✅ Delegate {} approved", allowance.delegate);
+        msg!("   ❌ ALLOWANCE IS HIDDEN!");
+        Ok(())
+    }
+
+    /// Transfer from an owner's account as an approved delegate
+    ///
+    /// `proof_data` is a regular transfer proof out of the owner's account.
+    /// `allowance_proof` debits the same amount from the allowance; its range
+    /// proof on the remaining allowance stops the delegate overspending.
+    /// PRIVACY: Amount, allowance and balances are NEVER revealed on-chain!
+    #[allow(clippy::too_many_arguments)]
+    pub fn confidential_transfer_from(
+        ctx: Context<ConfidentialTransferFrom>,
+        owner_new_commitment: [u8; 64],
+        recipient_new_commitment: [u8; 64],
+        proof_data: Vec<u8>,
+        allowance_new_commitment: [u8; 64],
+        allowance_proof: Vec<u8>,
+        expected_owner_version: u64,
+        expected_recipient_version: u64,
+    ) -> Result<()> {
+        // ============================================
+        // INPUT VALIDATION (Checks)
+        // ============================================
+        
+        // SECURITY: Emergency pause and circuit breaker (set by the config authority)
+        let config = Config::load_optional(&ctx.accounts.config)?;
+        require_not_paused!(config.as_ref(), pause::TRANSFERS);
+        limits::enforce_transfer_rate(config.as_ref(), &ctx.accounts.stats)?;
+        
+        // SECURITY: Optimistic concurrency - the proof was built against these
+        // exact states; a concurrent update would make it stale
+        require!(
+            ctx.accounts.owner_account.version == expected_owner_version,
+            ErrorCode::StaleAccountVersion
+        );
+        require!(
+            ctx.accounts.recipient_account.version == expected_recipient_version,
+            ErrorCode::StaleAccountVersion
+        );
+        
+        require!(
+            ctx.accounts.owner_account.key() != ctx.accounts.recipient_account.key(),
+            ErrorCode::InvalidRecipient
+        );
+        
+        // Validate proof data size (DoS protection)
+        for proof in [&proof_data, &allowance_proof] {
+            require!(
+                proof.len() >= proof_constants::MIN_PROOF_DATA_SIZE,
+                ErrorCode::InvalidProof
+            );
+            require!(
+                proof.len() <= proof_constants::MAX_PROOF_DATA_SIZE,
+                ErrorCode::InvalidProof
+            );
+        }
+        
+        require!(
+            is_valid_commitment(&owner_new_commitment),
+            ErrorCode::InvalidCommitment
+        );
+        require!(
+            is_valid_commitment(&recipient_new_commitment),
+            ErrorCode::InvalidCommitment
+        );
+        require!(
+            is_valid_commitment(&allowance_new_commitment),
+            ErrorCode::InvalidCommitment
+        );
+        
+        let owner_old_commitment = ctx.accounts.owner_account.encrypted_balance;
+        let recipient_old_commitment = ctx.accounts.recipient_account.encrypted_balance;
+        require!(
+            owner_old_commitment != [0u8; 64],
+            ErrorCode::InvalidCommitment
+        );
+        
+        // ============================================
+        // ZK PROOF VERIFICATION
+        // ============================================
+        
+        let amount_commitment = match proof_verification::extract_amount_commitment(&proof_data) {
+            Ok(commitment) => commitment,
+            Err(e) => {
+                msg!("⚠️  Failed to extract amount commitment from proof: {:?}", e);
+                return Err(ErrorCode::InvalidProof.into());
+            }
+        };
+        
+        if let Err(e) = verify_transfer_proof(
+            &proof_data,
+            &amount_commitment,
+            &owner_new_commitment,
+            &owner_old_commitment,
+            &recipient_old_commitment,
+            &recipient_new_commitment,
+        ) {
+            msg!("⚠️  Proof verification error: {:?}", e);
+            return Err(ErrorCode::InvalidProof.into());
+        }
+        
+        // SECURITY: The same amount comes out of the allowance, which must
+        // stay non-negative
+        if let Err(e) = verify_escrow_debit_proof(
+            &allowance_proof,
+            &amount_commitment,
+            &ctx.accounts.allowance.allowance_commitment,
+            &allowance_new_commitment,
+        ) {
+            msg!("⚠️  Allowance proof error: {:?}", e);
+            return Err(ErrorCode::InvalidProof.into());
+        }
+        
+        // ============================================
+        // STATE UPDATES (Effects)
+        // ============================================
+        
+        let owner_account = &mut ctx.accounts.owner_account;
+        owner_account.encrypted_balance = owner_new_commitment;
+        owner_account.version += 1;
+        owner_account.advance_state_hash(&owner_new_commitment);
+        
+        let recipient_account = &mut ctx.accounts.recipient_account;
+        recipient_account.encrypted_balance = recipient_new_commitment;
+        recipient_account.version += 1;
+        recipient_account.advance_state_hash(&recipient_new_commitment);
+        
+        ctx.accounts.allowance.allowance_commitment = allowance_new_commitment;
+        
+        // Protocol statistics (if initialized)
+        stats::record(&ctx.accounts.stats, stats::Activity::Transfer)?;
+        
+        msg!("✅ Delegated transfer complete");
+        msg!("   ❌ AMOUNT IS HIDDEN!");
+        Ok(())
+    }
+
+    /// Revoke a delegate's allowance (owner only) and reclaim its rent
+    pub fn revoke(ctx: Context<Revoke>) -> Result<()> {
+        msg!("Delegate {} revoked", ctx.accounts.allowance.delegate);
+        Ok(())
+    }
+
+    /// Register a short-lived session key allowed to sign transfers
+    ///
+    /// Each session transfer is capped by the hidden `max_amount_commitment`,
+    /// the key expires at `expiry_slot`, and when `allowed_recipients` is
+    /// non-empty it may only pay those encrypted accounts.
+    /// PRIVACY: The per-transfer cap is NEVER revealed on-chain!
+    pub fn register_session_key(
+        ctx: Context<RegisterSessionKey>,
+        max_amount_commitment: [u8; 64],
+        expiry_slot: u64,
+        allowed_recipients: Vec<Pubkey>,
+    ) -> Result<()> {
+        let current_slot = Clock::get()?.slot;
+        require!(
+            expiry_slot > current_slot
+                && expiry_slot - current_slot <= session_key_constants::MAX_DURATION_SLOTS,
+            ErrorCode::InvalidExpiry
+        );
+        require!(
+            allowed_recipients.len() <= session_key_constants::MAX_ALLOWED_RECIPIENTS,
+            ErrorCode::InvalidSessionKey
+        );
+        require!(
+            ctx.accounts.session_key.key() != ctx.accounts.owner.key(),
+            ErrorCode::InvalidSessionKey
+        );
+        require!(
+            is_valid_commitment(&max_amount_commitment),
+            ErrorCode::InvalidCommitment
+        );
+        
+        let session = &mut ctx.accounts.session;
+        session.owner = ctx.accounts.owner.key();
+        session.owner_account = ctx.accounts.owner_account.key();
+        session.session_key = ctx.accounts.session_key.key();
+        session.max_amount_commitment = max_amount_commitment;
+        session.expiry_slot = expiry_slot;
+        session.allowed_recipients = allowed_recipients;
+        session.bump = ctx.bumps.session;
+        
+        ctx.accounts.owner_account.record_activity()?;
+        
+        msg!("🔑 Session key {} registered until slot {}", session.session_key, expiry_slot);
+        msg!("   ❌ PER-TRANSFER CAP IS HIDDEN!");
+        Ok(())
+    }
+
+    /// Transfer signed by a session key within its scope
+    ///
+    /// `limit_proof` debits the transfer amount from the session cap into
+    /// `headroom_commitment`; its range proof shows the amount is at most
+    /// the cap. The cap itself is not consumed.
+    /// PRIVACY: Amount, cap and balances are NEVER revealed on-chain!
+    #[allow(clippy::too_many_arguments)]
+    pub fn session_transfer(
+        ctx: Context<SessionTransfer>,
+        sender_new_commitment: [u8; 64],
+        recipient_new_commitment: [u8; 64],
+        proof_data: Vec<u8>,
+        headroom_commitment: [u8; 64],
+        limit_proof: Vec<u8>,
+        expected_sender_version: u64,
+        expected_recipient_version: u64,
+    ) -> Result<()> {
+        // ============================================
+        // INPUT VALIDATION (Checks)
+        // ============================================
+        
+        // SECURITY: Emergency pause and circuit breaker (set by the config authority)
+        let config = Config::load_optional(&ctx.accounts.config)?;
+        require_not_paused!(config.as_ref(), pause::TRANSFERS);
+        limits::enforce_transfer_rate(config.as_ref(), &ctx.accounts.stats)?;
+        
+        // SECURITY: Optimistic concurrency - the proof was built against these
+        // exact states; a concurrent update would make it stale
+        require!(
+            ctx.accounts.sender_account.version == expected_sender_version,
+            ErrorCode::StaleAccountVersion
+        );
+        require!(
+            ctx.accounts.recipient_account.version == expected_recipient_version,
+            ErrorCode::StaleAccountVersion
+        );
+        
+        let session = &ctx.accounts.session;
+        require!(
+            Clock::get()?.slot < session.expiry_slot,
+            ErrorCode::SessionKeyExpired
+        );
+        
+        let recipient_key = ctx.accounts.recipient_account.key();
+        require!(
+            ctx.accounts.sender_account.key() != recipient_key,
+            ErrorCode::InvalidRecipient
+        );
+        require!(
+            session.allowed_recipients.is_empty() || session.allowed_recipients.contains(&recipient_key),
+            ErrorCode::RecipientNotAllowed
+        );
+        
+        // Validate proof data size (DoS protection)
+        for proof in [&proof_data, &limit_proof] {
+            require!(
+                proof.len() >= proof_constants::MIN_PROOF_DATA_SIZE,
+                ErrorCode::InvalidProof
+            );
+            require!(
+                proof.len() <= proof_constants::MAX_PROOF_DATA_SIZE,
+                ErrorCode::InvalidProof
+            );
+        }
+        
+        require!(
+            is_valid_commitment(&sender_new_commitment),
+            ErrorCode::InvalidCommitment
+        );
+        require!(
+            is_valid_commitment(&recipient_new_commitment),
+            ErrorCode::InvalidCommitment
+        );
+        
+        let sender_old_commitment = ctx.accounts.sender_account.encrypted_balance;
+        let recipient_old_commitment = ctx.accounts.recipient_account.encrypted_balance;
+        require!(
+            sender_old_commitment != [0u8; 64],
+            ErrorCode::InvalidCommitment
+        );
+        
+        // ============================================
+        // ZK PROOF VERIFICATION
+        // ============================================
+        
+        let amount_commitment = match proof_verification::extract_amount_commitment(&proof_data) {
+            Ok(commitment) => commitment,
+            Err(e) => {
+                msg!("⚠️  Failed to extract amount commitment from proof: {:?}", e);
+                return Err(ErrorCode::InvalidProof.into());
+            }
+        };
+        
+        if let Err(e) = verify_transfer_proof(
+            &proof_data,
+            &amount_commitment,
+            &sender_new_commitment,
+            &sender_old_commitment,
+            &recipient_old_commitment,
+            &recipient_new_commitment,
+        ) {
+            msg!("⚠️  Proof verification error: {:?}", e);
+            return Err(ErrorCode::InvalidProof.into());
+        }
+        
+        // SECURITY: amount <= cap (headroom = cap - amount is range-proven)
+        if let Err(e) = verify_escrow_debit_proof(
+            &limit_proof,
+            &amount_commitment,
+            &session.max_amount_commitment,
+            &headroom_commitment,
+        ) {
+            msg!("⚠️  Session limit proof error: {:?}", e);
+            return Err(ErrorCode::InvalidProof.into());
+        }
+        
+        // ============================================
+        // STATE UPDATES (Effects)
+        // ============================================
+        
+        let sender_account = &mut ctx.accounts.sender_account;
+        sender_account.encrypted_balance = sender_new_commitment;
+        sender_account.version += 1;
+        sender_account.advance_state_hash(&sender_new_commitment);
+        
+        let recipient_account = &mut ctx.accounts.recipient_account;
+        recipient_account.encrypted_balance = recipient_new_commitment;
+        recipient_account.version += 1;
+        recipient_account.advance_state_hash(&recipient_new_commitment);
+        
+        // Protocol statistics (if initialized)
+        stats::record(&ctx.accounts.stats, stats::Activity::Transfer)?;
+        
+        msg!("✅ Session transfer complete");
+        msg!("   ❌ AMOUNT IS HIDDEN!");
+        Ok(())
+    }
+
+    /// Revoke a session key before it expires (owner only) and reclaim its rent
+    pub fn revoke_session_key(ctx: Context<RevokeSessionKey>) -> Result<()> {
+        msg!("Session key {} revoked", ctx.accounts.session.session_key);
+        Ok(())
+    }
+
+    /// Arm a dead-man switch on an encrypted account
+    ///
+    /// If the owner records no activity for `inactivity_slots`, the
+    /// `recovery_key` may take over the account with `recover_inactive_account`.
+    /// Arming counts as activity, so the timer starts now.
+    pub fn set_dead_man_switch(
+        ctx: Context<SetDeadManSwitch>,
+        recovery_key: Pubkey,
+        inactivity_slots: u64,
+        auth_code: Option<[u8; 32]>,
+    ) -> Result<()> {
+        require!(
+            recovery_key != Pubkey::default() && recovery_key != ctx.accounts.owner.key(),
+            ErrorCode::InvalidNewOwner
+        );
+        require!(
+            (dead_man_switch_constants::MIN_INACTIVITY_SLOTS..=dead_man_switch_constants::MAX_INACTIVITY_SLOTS)
+                .contains(&inactivity_slots),
+            ErrorCode::InvalidInactivityPeriod
+        );
+        
+        // SECURITY: The recovery key can take the account over, so arming
+        // needs the second factor too
+        auth_chain::enforce_if_configured(&ctx.accounts.auth_chain, auth_code)?;
+        
+        ctx.accounts.encrypted_account.record_activity()?;
+        
+        let switch = &mut ctx.accounts.dead_man_switch;
+        switch.owner = ctx.accounts.owner.key();
+        switch.encrypted_account = ctx.accounts.encrypted_account.key();
+        switch.recovery_key = recovery_key;
+        switch.inactivity_slots = inactivity_slots;
+        switch.bump = ctx.bumps.dead_man_switch;
+        
+        msg!("⏳ Dead-man switch armed: {} after {} idle slots", recovery_key, inactivity_slots);
+        Ok(())
+    }
+
+    /// Prove the owner is still active without moving funds
+    pub fn check_in(ctx: Context<CheckIn>) -> Result<()> {
+        let account = &mut ctx.accounts.encrypted_account;
+        account.record_activity()?;
+        
+        msg!("Owner active at slot {}", account.last_activity_slot);
+        Ok(())
+    }
+
+    /// Disarm a dead-man switch (owner only) and reclaim its rent
+    pub fn cancel_dead_man_switch(ctx: Context<CancelDeadManSwitch>) -> Result<()> {
+        ctx.accounts.encrypted_account.record_activity()?;
+        
+        msg!("Dead-man switch disarmed");
+        Ok(())
+    }
+
+    /// Rekey an inactive account (and its SOL escrow) to the recovery key
+    ///
+    /// Only after `inactivity_slots` without owner activity. The switch is
+    /// consumed. Spending the hidden balance afterwards still requires its
+    /// opening, which the owner hands over off-chain (e.g., an encrypted
+    /// backup readable by the recovery key).
+    pub fn recover_inactive_account(ctx: Context<RecoverInactiveAccount>) -> Result<()> {
+        let current_slot = Clock::get()?.slot;
+        let switch = &ctx.accounts.dead_man_switch;
+        let idle_since = ctx.accounts.encrypted_account.last_activity_slot;
+        require!(
+            current_slot >= idle_since.saturating_add(switch.inactivity_slots),
+            ErrorCode::OwnerStillActive
+        );
+        
+        let recovery_key = switch.recovery_key;
+        let account = &mut ctx.accounts.encrypted_account;
+        let previous_owner = account.owner;
+        account.owner = recovery_key;
+        account.pending_owner = Pubkey::default();
+        account.last_activity_slot = current_slot;
+        
+        let escrow = &mut ctx.accounts.sol_escrow;
+        escrow.owner = recovery_key;
+        escrow.pending_owner = Pubkey::default();
+        
+        emit!(InactiveAccountRecovered {
+            encrypted_account: account.key(),
+            previous_owner,
+            recovery_key,
+            idle_since_slot: idle_since,
+            slot: current_slot,
+        });
+        
+        msg!("🛟 Inactive account recovered: {} -> {}", previous_owner, recovery_key);
+        Ok(())
+    }
+
+    /// Register M-of-N guardians able to recover a lost owner key
+    ///
+    /// Once `threshold` guardians approve the same new owner, the owner has
+    /// `recovery_delay_slots` to veto before `recover_account` can run.
+    /// To change the set, remove it and register again.
+    pub fn set_guardians(
+        ctx: Context<SetGuardians>,
+        guardians: Vec<Pubkey>,
+        threshold: u8,
+        recovery_delay_slots: u64,
+        auth_code: Option<[u8; 32]>,
+    ) -> Result<()> {
+        require!(
+            !guardians.is_empty() && guardians.len() <= guardian_constants::MAX_GUARDIANS,
+            ErrorCode::InvalidGuardianSet
+        );
+        require!(
+            threshold > 0 && threshold as usize <= guardians.len(),
+            ErrorCode::InvalidGuardianSet
+        );
+        require!(
+            (guardian_constants::MIN_RECOVERY_DELAY_SLOTS..=guardian_constants::MAX_RECOVERY_DELAY_SLOTS)
+                .contains(&recovery_delay_slots),
+            ErrorCode::InvalidGuardianSet
+        );
+        
+        // SECURITY: No duplicates (each would count twice towards the quorum)
+        // and the owner cannot guard their own account
+        for (i, guardian) in guardians.iter().enumerate() {
+            require!(
+                *guardian != ctx.accounts.owner.key() && !guardians[..i].contains(guardian),
+                ErrorCode::InvalidGuardianSet
+            );
+        }
+        
+        // SECURITY: Second factor, if the owner configured an auth chain
+        auth_chain::enforce_if_configured(&ctx.accounts.auth_chain, auth_code)?;
+        
+        let guardian_set = &mut ctx.accounts.guardian_set;
+        guardian_set.owner = ctx.accounts.owner.key();
+        guardian_set.encrypted_account = ctx.accounts.encrypted_account.key();
+        guardian_set.guardians = guardians;
+        guardian_set.threshold = threshold;
+        guardian_set.recovery_delay_slots = recovery_delay_slots;
+        guardian_set.proposed_owner = Pubkey::default();
+        guardian_set.approvals = 0;
+        guardian_set.objections = 0;
+        guardian_set.recovery_ready_slot = 0;
+        guardian_set.bump = ctx.bumps.guardian_set;
+        
+        msg!(
+            "🛡️  {}-of-{} guardians registered ({} slot veto window)",
+            threshold,
+            guardian_set.guardians.len(),
+            recovery_delay_slots
+        );
+        
+        ctx.accounts.encrypted_account.record_activity()?;
+        Ok(())
+    }
+
+    /// Remove the guardian set (owner only) and reclaim its rent
+    pub fn remove_guardians(
+        ctx: Context<RemoveGuardians>,
+        auth_code: Option<[u8; 32]>,
+    ) -> Result<()> {
+        auth_chain::enforce_if_configured(&ctx.accounts.auth_chain, auth_code)?;
+        
+        ctx.accounts.encrypted_account.record_activity()?;
+        
+        msg!("Guardian set removed");
+        Ok(())
+    }
+
+    /// Approve handing the account to `new_owner` (guardian only)
+    ///
+    /// The first approval fixes the proposed owner. A guardian backing a
+    /// different key withdraws its approval and objects instead; once the
+    /// objections leave the proposal unable to reach the threshold, it is
+    /// replaced by the objecting guardian's candidate. Reaching the
+    /// threshold starts the veto window, after which only the owner can
+    /// cancel.
+    pub fn approve_recovery(ctx: Context<ApproveRecovery>, new_owner: Pubkey) -> Result<()> {
+        let guardian_set = &mut ctx.accounts.guardian_set;
+        let guardian = ctx.accounts.guardian.key();
+        let index = guardian_set.guardians
+            .iter()
+            .position(|g| *g == guardian)
+            .ok_or(ErrorCode::Unauthorized)?;
+        
+        require!(
+            new_owner != Pubkey::default() && new_owner != guardian_set.owner,
+            ErrorCode::InvalidNewOwner
+        );
+        if guardian_set.proposed_owner == Pubkey::default() {
+            guardian_set.proposed_owner = new_owner;
+            guardian_set.approvals = 0;
+            guardian_set.objections = 0;
+        }
+        
+        if guardian_set.proposed_owner != new_owner {
+            // SECURITY: A proposal that reached the threshold is only
+            // cancelled by the owner's veto
+            require!(
+                guardian_set.recovery_ready_slot == 0,
+                ErrorCode::RecoveryInProgress
+            );
+            
+            guardian_set.approvals &= !(1 << index);
+            guardian_set.objections |= 1 << index;
+            
+            // Stale proposal: not enough guardians left to ever approve it
+            let reachable = guardian_set.guardians.len() as u32 - guardian_set.objections.count_ones();
+            if reachable >= guardian_set.threshold as u32 {
+                msg!(
+                    "Guardian {} objects to {} ({} objections)",
+                    guardian,
+                    guardian_set.proposed_owner,
+                    guardian_set.objections.count_ones()
+                );
+                return Ok(());
+            }
+            
+            msg!("Stale recovery of {} replaced", guardian_set.proposed_owner);
+            guardian_set.proposed_owner = new_owner;
+            guardian_set.approvals = 0;
+            guardian_set.objections = 0;
+        }
+        
+        guardian_set.approvals |= 1 << index;
+        guardian_set.objections &= !(1 << index);
+        let approvals = guardian_set.approvals.count_ones() as u8;
+        
+        let current_slot = Clock::get()?.slot;
+        if approvals >= guardian_set.threshold && guardian_set.recovery_ready_slot == 0 {
+            guardian_set.recovery_ready_slot = current_slot
+                .checked_add(guardian_set.recovery_delay_slots)
+                .ok_or(ErrorCode::Overflow)?;
+        }
+        
+        // COMPLIANCE: Owners watch for this event to veto hostile recoveries
+        emit!(RecoveryApproved {
+            encrypted_account: guardian_set.encrypted_account,
+            guardian,
+            new_owner,
+            approvals,
+            threshold: guardian_set.threshold,
+            recovery_ready_slot: guardian_set.recovery_ready_slot,
+        });
+        
+        msg!("Recovery approved by {} ({}/{})", guardian, approvals, guardian_set.threshold);
+        Ok(())
+    }
+
+    /// Cancel a pending recovery (owner only)
+    ///
+    /// Requires the next auth code when an auth chain is configured, so a
+    /// stolen owner key alone cannot block a legitimate recovery.
+    pub fn veto_recovery(
+        ctx: Context<VetoRecovery>,
+        auth_code: Option<[u8; 32]>,
+    ) -> Result<()> {
+        auth_chain::enforce_if_configured(&ctx.accounts.auth_chain, auth_code)?;
+        
+        let guardian_set = &mut ctx.accounts.guardian_set;
+        require!(
+            guardian_set.proposed_owner != Pubkey::default(),
+            ErrorCode::NoRecoveryPending
+        );
+        guardian_set.proposed_owner = Pubkey::default();
+        guardian_set.approvals = 0;
+        guardian_set.objections = 0;
+        guardian_set.recovery_ready_slot = 0;
+        ctx.accounts.encrypted_account.record_activity()?;
+        
+        msg!("🛑 Recovery vetoed by owner");
+        Ok(())
+    }
+
+    /// Rotate the owner of a recovered account (and its SOL escrow)
+    ///
+    /// Signed by the approved new owner once the veto window has passed.
+    /// The guardian set stays in place for the new owner. Pass the old
+    /// auth chain to close it, since its codes died with the old key.
+    pub fn recover_account(ctx: Context<RecoverAccount>) -> Result<()> {
+        let current_slot = Clock::get()?.slot;
+        let guardian_set = &mut ctx.accounts.guardian_set;
+        require!(
+            guardian_set.recovery_ready_slot != 0,
+            ErrorCode::NoRecoveryPending
+        );
+        require!(
+            current_slot >= guardian_set.recovery_ready_slot,
+            ErrorCode::RecoveryDelayActive
+        );
+        
+        let new_owner = ctx.accounts.new_owner.key();
+        guardian_set.owner = new_owner;
+        guardian_set.proposed_owner = Pubkey::default();
+        guardian_set.approvals = 0;
+        guardian_set.objections = 0;
+        guardian_set.recovery_ready_slot = 0;
+        
+        let account = &mut ctx.accounts.encrypted_account;
+        let previous_owner = account.owner;
+        account.owner = new_owner;
+        account.pending_owner = Pubkey::default();
+        account.record_activity()?;
+        
+        let escrow = &mut ctx.accounts.sol_escrow;
+        escrow.owner = new_owner;
+        escrow.pending_owner = Pubkey::default();
+        
+        msg!("🛟 Account recovered: {} -> {}", previous_owner, new_owner);
+        Ok(())
+    }
+
+    /// Lock a hidden amount for a payee under a designated arbiter
+    ///
+    /// The payer can release to the payee and the payee can refund the
+    /// payer, so either outcome only needs the consent of the party giving
+    /// up the funds. The arbiter can rule either way.
+    /// PRIVACY: The amount is NEVER revealed on-chain!
+    pub fn create_conditional_payment(
+        ctx: Context<CreateConditionalPayment>,
+        payment_id: u64,
+        arbiter: Pubkey,
+        payer_new_commitment: [u8; 64],
+        lock_proof: Vec<u8>,
+    ) -> Result<()> {
+        // ============================================
+        // INPUT VALIDATION (Checks)
+        // ============================================
+        
+        // SECURITY: Emergency pause and circuit breaker (set by the config authority)
+        let config = Config::load_optional(&ctx.accounts.config)?;
+        require_not_paused!(config.as_ref(), pause::TRANSFERS);
+        limits::enforce_transfer_rate(config.as_ref(), &ctx.accounts.stats)?;
+        
+        require!(
+            ctx.accounts.payer_account.key() != ctx.accounts.payee_account.key(),
+            ErrorCode::InvalidRecipient
+        );
+        require!(
+            arbiter != Pubkey::default()
+                && arbiter != ctx.accounts.payer.key()
+                && arbiter != ctx.accounts.payee_account.owner,
+            ErrorCode::InvalidArbiter
+        );
+        
+        // Validate proof data size (DoS protection)
+        require!(
+            lock_proof.len() >= proof_constants::MIN_PROOF_DATA_SIZE,
+            ErrorCode::InvalidProof
+        );
+        require!(
+            lock_proof.len() <= proof_constants::MAX_PROOF_DATA_SIZE,
+            ErrorCode::InvalidProof
+        );
+        
+        require!(
+            is_valid_commitment(&payer_new_commitment),
+            ErrorCode::InvalidCommitment
+        );
+        
+        let payer_old_commitment = ctx.accounts.payer_account.encrypted_balance;
+        require!(
+            payer_old_commitment != [0u8; 64],
+            ErrorCode::InvalidCommitment
+        );
+        
+        // ============================================
+        // ZK PROOF VERIFICATION
+        // ============================================
+        
+        let amount_commitment = match proof_verification::extract_amount_commitment(&lock_proof) {
+            Ok(commitment) => commitment,
+            Err(e) => {
+                msg!("⚠️  Failed to extract amount commitment from proof: {:?}", e);
+                return Err(ErrorCode::InvalidProof.into());
+            }
+        };
+        
+        if let Err(e) = verify_escrow_debit_proof(
+            &lock_proof,
+            &amount_commitment,
+            &payer_old_commitment,
+            &payer_new_commitment,
+        ) {
+            msg!("⚠️  Proof verification error: {:?}", e);
+            return Err(ErrorCode::InvalidProof.into());
+        }
+        
+        // ============================================
+        // STATE UPDATES (Effects)
+        // ============================================
+        
+        let payer_account = &mut ctx.accounts.payer_account;
+        payer_account.encrypted_balance = payer_new_commitment;
+        payer_account.version += 1;
+        payer_account.advance_state_hash(&payer_new_commitment);
+        payer_account.record_activity()?;
+        
+        let payment = &mut ctx.accounts.payment;
+        payment.payer = ctx.accounts.payer.key();
+        payment.payer_account = ctx.accounts.payer_account.key();
+        payment.payee_account = ctx.accounts.payee_account.key();
+        payment.arbiter = arbiter;
+        payment.payment_id = payment_id;
+        payment.amount_commitment = amount_commitment;
+        payment.bump = ctx.bumps.payment;
+        
+        // Protocol statistics (if initialized)
+        stats::record(&ctx.accounts.stats, stats::Activity::Transfer)?;
+        
+        msg!("🔒 Conditional payment {} locked (arbiter: {})", payment_id, arbiter);
+        msg!("   ❌ AMOUNT IS HIDDEN!");
+        Ok(())
+    }
+
+    /// Release a conditional payment to the payee (payer or arbiter)
+    /// PRIVACY: The amount is NEVER revealed on-chain!
+    pub fn release_conditional_payment(
+        ctx: Context<SettleConditionalPayment>,
+        payee_new_commitment: [u8; 64],
+        credit_proof: [u8; 96],
+    ) -> Result<()> {
+        // SECURITY: Emergency pause and circuit breaker (set by the config authority)
+        let config = Config::load_optional(&ctx.accounts.config)?;
+        require_not_paused!(config.as_ref(), pause::TRANSFERS);
+        limits::enforce_transfer_rate(config.as_ref(), &ctx.accounts.stats)?;
+        
+        let authority = ctx.accounts.authority.key();
+        let payment = &ctx.accounts.payment;
+        require!(
+            authority == payment.payer || authority == payment.arbiter,
+            ErrorCode::Unauthorized
+        );
+        require!(
+            is_valid_commitment(&payee_new_commitment),
+            ErrorCode::InvalidCommitment
+        );
+        
+        let payee_old_commitment = ctx.accounts.payee_account.encrypted_balance;
+        if let Err(e) = verify_escrow_credit_proof(
+            &credit_proof,
+            &payment.amount_commitment,
+            &payee_old_commitment,
+            &payee_new_commitment,
+        ) {
+            msg!("⚠️  Proof verification error: {:?}", e);
+            return Err(ErrorCode::InvalidProof.into());
+        }
+        
+        let payee_account = &mut ctx.accounts.payee_account;
+        payee_account.encrypted_balance = payee_new_commitment;
+        payee_account.version += 1;
+        payee_account.advance_state_hash(&payee_new_commitment);
+        
+        emit!(ConditionalPaymentSettled {
+            payment: payment.key(),
+            payment_id: payment.payment_id,
+            released: true,
+            by_arbiter: authority == payment.arbiter,
+        });
+        
+        msg!("✅ Conditional payment {} released to payee", payment.payment_id);
+        Ok(())
+    }
+
+    /// Refund a conditional payment to the payer (payee or arbiter)
+    /// PRIVACY: The amount is NEVER revealed on-chain!
+    pub fn refund_conditional_payment(
+        ctx: Context<SettleConditionalPayment>,
+        payer_new_commitment: [u8; 64],
+        credit_proof: [u8; 96],
+    ) -> Result<()> {
+        let authority = ctx.accounts.authority.key();
+        let payment = &ctx.accounts.payment;
+        require!(
+            authority == ctx.accounts.payee_account.owner || authority == payment.arbiter,
+            ErrorCode::Unauthorized
+        );
+        require!(
+            is_valid_commitment(&payer_new_commitment),
+            ErrorCode::InvalidCommitment
+        );
+        
+        let payer_old_commitment = ctx.accounts.payer_account.encrypted_balance;
+        if let Err(e) = verify_escrow_credit_proof(
+            &credit_proof,
+            &payment.amount_commitment,
+            &payer_old_commitment,
+            &payer_new_commitment,
+        ) {
+            msg!("⚠️  Proof verification error: {:?}", e);
+            return Err(ErrorCode::InvalidProof.into());
+        }
+        
+        let payer_account = &mut ctx.accounts.payer_account;
+        payer_account.encrypted_balance = payer_new_commitment;
+        payer_account.version += 1;
+        payer_account.advance_state_hash(&payer_new_commitment);
+        
+        emit!(ConditionalPaymentSettled {
+            payment: payment.key(),
+            payment_id: payment.payment_id,
+            released: false,
+            by_arbiter: authority == payment.arbiter,
+        });
+        
+        msg!("↩️  Conditional payment {} refunded to payer", payment.payment_id);
+        Ok(())
+    }
+
+    /// Lock a hidden amount under a hashlock and timelock
+    ///
+    /// `hashlock` = SHA-256(preimage), the same hash function HTLCs on other
+    /// chains use, so one preimage can settle both legs of a swap.
+    /// PRIVACY: The amount is NEVER revealed on-chain!
+    pub fn lock_htlc(
+        ctx: Context<LockHtlc>,
+        hashlock: [u8; 32],
+        expiry_slot: u64,
+        sender_new_commitment: [u8; 64],
+        lock_proof: Vec<u8>,
+    ) -> Result<()> {
+        // ============================================
+        // INPUT VALIDATION (Checks)
+        // ============================================
+        
+        // SECURITY: Emergency pause and circuit breaker (set by the config authority)
+        let config = Config::load_optional(&ctx.accounts.config)?;
+        require_not_paused!(config.as_ref(), pause::TRANSFERS);
+        limits::enforce_transfer_rate(config.as_ref(), &ctx.accounts.stats)?;
+        
+        let current_slot = Clock::get()?.slot;
+        require!(
+            expiry_slot >= current_slot.saturating_add(htlc_constants::MIN_TIMELOCK_SLOTS)
+                && expiry_slot <= current_slot.saturating_add(htlc_constants::MAX_TIMELOCK_SLOTS),
+            ErrorCode::InvalidExpiry
+        );
+        require!(
+            ctx.accounts.sender_account.key() != ctx.accounts.recipient_account.key(),
+            ErrorCode::InvalidRecipient
+        );
+        
+        // Validate proof data size (DoS protection)
+        require!(
+            lock_proof.len() >= proof_constants::MIN_PROOF_DATA_SIZE,
+            ErrorCode::InvalidProof
+        );
+        require!(
+            lock_proof.len() <= proof_constants::MAX_PROOF_DATA_SIZE,
+            ErrorCode::InvalidProof
+        );
+        
+        require!(
+            is_valid_commitment(&sender_new_commitment),
+            ErrorCode::InvalidCommitment
+        );
+        
+        let sender_old_commitment = ctx.accounts.sender_account.encrypted_balance;
+        require!(
+            sender_old_commitment != [0u8; 64],
+            ErrorCode::InvalidCommitment
+        );
+        
+        // ============================================
+        // ZK PROOF VERIFICATION
+        // ============================================
+        
+        let amount_commitment = match proof_verification::extract_amount_commitment(&lock_proof) {
+            Ok(commitment) => commitment,
+            Err(e) => {
+                msg!("⚠️  Failed to extract amount commitment from proof: {:?}", e);
+                return Err(ErrorCode::InvalidProof.into());
+            }
+        };
+        
+        if let Err(e) = verify_escrow_debit_proof(
+            &lock_proof,
+            &amount_commitment,
+            &sender_old_commitment,
+            &sender_new_commitment,
+        ) {
+            msg!("⚠️  Proof verification error: {:?}", e);
+            return Err(ErrorCode::InvalidProof.into());
+        }
+        
+        // ============================================
+        // STATE UPDATES (Effects)
+        // ============================================
+        
+        let sender_account = &mut ctx.accounts.sender_account;
+        sender_account.encrypted_balance = sender_new_commitment;
+        sender_account.version += 1;
+        sender_account.advance_state_hash(&sender_new_commitment);
+        sender_account.record_activity()?;
+        
+        let htlc = &mut ctx.accounts.htlc;
+        htlc.sender = ctx.accounts.sender.key();
+        htlc.sender_account = ctx.accounts.sender_account.key();
+        htlc.recipient_account = ctx.accounts.recipient_account.key();
+        htlc.hashlock = hashlock;
+        htlc.expiry_slot = expiry_slot;
+        htlc.amount_commitment = amount_commitment;
+        htlc.bump = ctx.bumps.htlc;
+        
+        // Protocol statistics (if initialized)
+        stats::record(&ctx.accounts.stats, stats::Activity::Transfer)?;
+        
+        msg!("🔒 HTLC locked until slot {}", expiry_slot);
+        msg!("   ❌ AMOUNT IS HIDDEN!");
+        Ok(())
+    }
+
+    /// Redeem an HTLC with its preimage (recipient only, before expiry)
+    ///
+    /// The preimage is emitted in `HtlcRedeemed` so the counterparty can
+    /// settle the other leg of the swap.
+    /// PRIVACY: The amount is NEVER revealed on-chain!
+    pub fn redeem_htlc(
+        ctx: Context<RedeemHtlc>,
+        preimage: [u8; 32],
+        recipient_new_commitment: [u8; 64],
+        credit_proof: [u8; 96],
+    ) -> Result<()> {
+        // SECURITY: Emergency pause and circuit breaker (set by the config authority)
+        let config = Config::load_optional(&ctx.accounts.config)?;
+        require_not_paused!(config.as_ref(), pause::TRANSFERS);
+        limits::enforce_transfer_rate(config.as_ref(), &ctx.accounts.stats)?;
+        
+        let htlc = &ctx.accounts.htlc;
+        let current_slot = Clock::get()?.slot;
+        require!(current_slot < htlc.expiry_slot, ErrorCode::HtlcExpired);
+        require!(
+            solana_sha256_hasher::hash(&preimage).to_bytes() == htlc.hashlock,
+            ErrorCode::InvalidPreimage
+        );
+        require!(
+            is_valid_commitment(&recipient_new_commitment),
+            ErrorCode::InvalidCommitment
+        );
+        
+        let recipient_old_commitment = ctx.accounts.recipient_account.encrypted_balance;
+        if let Err(e) = verify_escrow_credit_proof(
+            &credit_proof,
+            &htlc.amount_commitment,
+            &recipient_old_commitment,
+            &recipient_new_commitment,
+        ) {
+            msg!("⚠️  Proof verification error: {:?}", e);
+            return Err(ErrorCode::InvalidProof.into());
+        }
+        
+        let recipient_account = &mut ctx.accounts.recipient_account;
+        recipient_account.encrypted_balance = recipient_new_commitment;
+        recipient_account.version += 1;
+        recipient_account.advance_state_hash(&recipient_new_commitment);
+        recipient_account.record_activity()?;
+        
+        emit!(HtlcRedeemed {
+            htlc: htlc.key(),
+            hashlock: htlc.hashlock,
+            preimage,
+            slot: current_slot,
+        });
+        
+        msg!("✅ HTLC redeemed");
+        Ok(())
+    }
+
+    /// Refund an expired HTLC to the sender
+    /// PRIVACY: The amount is NEVER revealed on-chain!
+    pub fn refund_htlc(
+        ctx: Context<RefundHtlc>,
+        sender_new_commitment: [u8; 64],
+        credit_proof: [u8; 96],
+    ) -> Result<()> {
+        let htlc = &ctx.accounts.htlc;
+        require!(
+            Clock::get()?.slot >= htlc.expiry_slot,
+            ErrorCode::HtlcNotExpired
+        );
+        require!(
+            is_valid_commitment(&sender_new_commitment),
+            ErrorCode::InvalidCommitment
+        );
+        
+        let sender_old_commitment = ctx.accounts.sender_account.encrypted_balance;
+        if let Err(e) = verify_escrow_credit_proof(
+            &credit_proof,
+            &htlc.amount_commitment,
+            &sender_old_commitment,
+            &sender_new_commitment,
+        ) {
+            msg!("⚠️  Proof verification error: {:?}", e);
+            return Err(ErrorCode::InvalidProof.into());
+        }
+        
+        let sender_account = &mut ctx.accounts.sender_account;
+        sender_account.encrypted_balance = sender_new_commitment;
+        sender_account.version += 1;
+        sender_account.advance_state_hash(&sender_new_commitment);
+        sender_account.record_activity()?;
+        
+        msg!("↩️  HTLC refunded to sender");
+        Ok(())
+    }
+
+    /// Prove an account's commitment matches its SOL escrow (owner only)
+    ///
+    /// `opening_proof` shows the encrypted balance commits to exactly the
+    /// escrow's lamport balance, without revealing the blinding factor.
+    /// `AccountAudited` lets third parties check the escrow is fully backed
+    /// at that version.
+    pub fn audit_account(ctx: Context<AuditAccount>, opening_proof: [u8; 96]) -> Result<()> {
+        let account = &ctx.accounts.encrypted_account;
+        let escrow_balance = ctx.accounts.sol_escrow.balance;
+        
+        // An untouched account (zero commitment, empty escrow) is trivially backed
+        if !(account.encrypted_balance == [0u8; 64] && escrow_balance == 0) {
+            if let Err(e) = verify_opening_proof(&opening_proof, &account.encrypted_balance, escrow_balance) {
+                msg!("⚠️  Opening proof error: {:?}", e);
+                return Err(ErrorCode::InvalidProof.into());
+            }
+        }
+        
+        emit!(AccountAudited {
+            encrypted_account: account.key(),
+            sol_escrow: ctx.accounts.sol_escrow.key(),
+            version: account.version,
+            state_hash: account.state_hash,
+            slot: Clock::get()?.slot,
+        });
+        
+        msg!("✅ Commitment matches escrow at version {}", account.version);
+        
+        ctx.accounts.encrypted_account.record_activity()?;
+        Ok(())
+    }
+    
+    /// Create the generator table with the Pedersen bases (config authority only)
+    ///
+    /// One-time: the PDA is global. Fill it with `write_generators`, then
+    /// seal it with `finalize_generators`; see `generators`.
+    pub fn initialize_generators(
+        ctx: Context<InitializeGenerators>,
+        pedersen_g: [u8; 64],
+        pedersen_h: [u8; 64],
+    ) -> Result<()> {
+        let mut table = ctx.accounts.generator_table.load_init()?;
+        generators::init(
+            &mut table,
+            ctx.accounts.authority.key(),
+            &pedersen_g,
+            &pedersen_h,
+            ctx.bumps.generator_table,
+        )?;
+        
+        msg!("Initialized generator table");
+        Ok(())
+    }
+
+    /// Write a chunk of range-proof generators (config authority only)
+    pub fn write_generators(
+        ctx: Context<UpdateGenerators>,
+        start: u16,
+        g: Vec<[u8; 64]>,
+        h: Vec<[u8; 64]>,
+    ) -> Result<()> {
+        let mut table = ctx.accounts.generator_table.load_mut()?;
+        generators::write(&mut table, start, &g, &h)?;
+        
+        msg!("Generators written: {} pairs at index {}", g.len(), start);
+        msg!("   Filled: {}/{}", table.written, generators::GENERATOR_CAPACITY);
+        Ok(())
+    }
+
+    /// Seal the generator table; it is read-only from here on
+    pub fn finalize_generators(ctx: Context<UpdateGenerators>) -> Result<()> {
+        let mut table = ctx.accounts.generator_table.load_mut()?;
+        let digest = generators::finalize(&mut table)?;
+        
+        emit!(GeneratorsFinalized {
+            generator_table: ctx.accounts.generator_table.key(),
+            digest,
+        });
+        
+        msg!("✅ Generator table sealed");
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+#[instruction(account_index: u16)]
+pub struct InitializeAccount<'info> {
+    #[account(
+        init,
+        payer = owner,
+        space = 8 + EncryptedAccount::INIT_SPACE,
+        seeds = [pda::ENCRYPTED_ACCOUNT_SEED, owner.key().as_ref(), &account_index.to_le_bytes()],
+        bump
+    )]
+    pub encrypted_account: Account<'info, EncryptedAccount>,
+    
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    
+    /// CHECK: Protocol stats PDA (may be uninitialized); counters updated when present
+    #[account(
+        mut,
+        seeds = [pda::STATS_SEED],
+        bump
+    )]
+    pub stats: UncheckedAccount<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(account_index: u16)]
+pub struct InitializeSolEscrow<'info> {
+    #[account(
+        init,
+        payer = owner,
+        space = 8 + SolEscrow::INIT_SPACE,
+        seeds = [pda::SOL_ESCROW_SEED, owner.key().as_ref(), &account_index.to_le_bytes()],
+        bump
+    )]
+    pub sol_escrow: Account<'info, SolEscrow>,
+    
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    
+    /// CHECK: Protocol stats PDA (may be uninitialized); counters updated when present
+    #[account(
+        mut,
+        seeds = [pda::STATS_SEED],
+        bump
+    )]
+    pub stats: UncheckedAccount<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct Deposit<'info> {
+    #[account(
+        mut,
+        seeds = [pda::ENCRYPTED_ACCOUNT_SEED, encrypted_account.seed_owner.as_ref(), &encrypted_account.account_index.to_le_bytes()],
+        bump = encrypted_account.bump,
+        has_one = owner
+    )]
+    pub encrypted_account: Account<'info, EncryptedAccount>,
+    
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    
+    /// CHECK: Global config PDA (may be uninitialized); read for the deposit cap
+    #[account(
+        seeds = [pda::CONFIG_SEED],
+        bump
+    )]
+    pub config: UncheckedAccount<'info>,
+    
+    /// CHECK: Protocol stats PDA (may be uninitialized); counters updated when present
+    #[account(
+        mut,
+        seeds = [pda::STATS_SEED],
+        bump
+    )]
+    pub stats: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(
+    sender_new_commitment: PackedCommitment,
+    recipient_new_commitment: PackedCommitment,
+    recipient_seed_owner: Pubkey,
+    recipient_account_index: u16,
+)]
+pub struct ConfidentialTransfer<'info> {
+    #[account(
+        mut,
+        seeds = [pda::ENCRYPTED_ACCOUNT_SEED, sender_account.seed_owner.as_ref(), &sender_account.account_index.to_le_bytes()],
+        bump = sender_account.bump,
+        constraint = !sender_account.frozen @ ErrorCode::AccountFrozen
+    )]
+    pub sender_account: Account<'info, EncryptedAccount>,
+    
+    /// Created (rent paid by the sender) when the recipient has no account
+    /// yet; an existing account keeps its size (tracked assets included)
+    #[account(
+        init_if_needed,
+        payer = sender,
+        space = (8 + EncryptedAccount::INIT_SPACE).max(recipient_account.data_len()),
+        seeds = [pda::ENCRYPTED_ACCOUNT_SEED, recipient_seed_owner.as_ref(), &recipient_account_index.to_le_bytes()],
+        bump,
+    )]
+    pub recipient_account: Account<'info, EncryptedAccount>,
+    
+    #[account(mut)]
+    pub sender: Signer<'info>,
+    
+    /// CHECK: Recipient public key, not a signer
+    pub recipient: UncheckedAccount<'info>,
+    
+    /// CHECK: Global config PDA (may be uninitialized); read for deployment policies
+    #[account(
+        seeds = [pda::CONFIG_SEED],
+        bump
+    )]
+    pub config: UncheckedAccount<'info>,
+    
+    /// Recipient inbox; when passed, a notice (with the memo) is appended
+    #[account(
+        mut,
+        seeds = [pda::INBOX_SEED, recipient_account.key().as_ref()],
+        bump = recipient_inbox.bump,
+    )]
+    pub recipient_inbox: Option<Box<Account<'info, Inbox>>>,
+    
+    /// Sender's epoch total; required while an epoch transfer limit is set
+    #[account(
+        mut,
+        seeds = [pda::TRANSFER_ACCUMULATOR_SEED, sender_account.key().as_ref()],
+        bump = sender_accumulator.bump,
+    )]
+    pub sender_accumulator: Option<Box<Account<'info, TransferAccumulator>>>,
+    
+    /// Shielded fee treasury; required while a protocol fee applies
+    #[account(
+        mut,
+        seeds = [pda::SHIELDED_TREASURY_SEED],
+        bump = shielded_treasury.bump,
+    )]
+    pub shielded_treasury: Option<Box<Account<'info, ShieldedTreasury>>>,
+    
+    /// Sender's KYC credential (token account of config.kyc_mint)
+    pub sender_credential: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+    
+    /// Recipient's KYC credential (when config.kyc_require_recipient)
+    pub recipient_credential: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+    
+    /// CHECK: Whitelisted external verifier program (config.verifier_programs);
+    /// checked by `verifier::verify_transfer`
+    pub verifier_program: Option<UncheckedAccount<'info>>,
+    
+    /// CHECK: Protocol stats PDA (may be uninitialized); counters updated when present
+    #[account(
+        mut,
+        seeds = [pda::STATS_SEED],
+        bump
+    )]
+    pub stats: UncheckedAccount<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct VerifyStageA<'info> {
+    #[account(
+        seeds = [pda::ENCRYPTED_ACCOUNT_SEED, sender_account.seed_owner.as_ref(), &sender_account.account_index.to_le_bytes()],
+        bump = sender_account.bump,
+        constraint = !sender_account.frozen @ ErrorCode::AccountFrozen
+    )]
+    pub sender_account: Account<'info, EncryptedAccount>,
+    
+    #[account(
+        seeds = [pda::ENCRYPTED_ACCOUNT_SEED, recipient_account.seed_owner.as_ref(), &recipient_account.account_index.to_le_bytes()],
+        bump = recipient_account.bump,
+    )]
+    pub recipient_account: Account<'info, EncryptedAccount>,
+    
+    /// One pending verification per sending account
+    #[account(
+        init,
+        payer = sender,
+        space = 8 + PendingVerification::INIT_SPACE,
+        seeds = [pda::PENDING_VERIFICATION_SEED, sender_account.key().as_ref()],
+        bump
+    )]
+    pub pending_verification: Account<'info, PendingVerification>,
+    
+    #[account(mut)]
+    pub sender: Signer<'info>,
+    
+    /// Sender's proof buffer; when passed, the proof is read from it in place
+    #[account(
+        seeds = [pda::PROOF_BUFFER_SEED, sender.key().as_ref()],
+        bump = proof_buffer.load()?.bump,
+    )]
+    pub proof_buffer: Option<AccountLoader<'info, ProofBuffer>>,
+    
+    /// CHECK: Global config PDA (may be uninitialized); read for deployment policies
+    #[account(
+        seeds = [pda::CONFIG_SEED],
+        bump
+    )]
+    pub config: UncheckedAccount<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(sender_new_commitment: [u8; 64], recipient_new_commitment: [u8; 64])]
+pub struct VerifyStageB<'info> {
+    #[account(
+        mut,
+        close = sender,
+        seeds = [pda::PENDING_VERIFICATION_SEED, sender_account.key().as_ref()],
+        bump = pending_verification.bump,
+        has_one = sender @ ErrorCode::Unauthorized,
+        has_one = sender_account @ ErrorCode::Unauthorized,
+        has_one = recipient_account @ ErrorCode::Unauthorized,
+    )]
+    pub pending_verification: Account<'info, PendingVerification>,
+    
+    #[account(
+        mut,
+        seeds = [pda::ENCRYPTED_ACCOUNT_SEED, sender_account.seed_owner.as_ref(), &sender_account.account_index.to_le_bytes()],
+        bump = sender_account.bump,
+        constraint = !sender_account.frozen @ ErrorCode::AccountFrozen
+    )]
+    pub sender_account: Account<'info, EncryptedAccount>,
+    
+    #[account(
+        mut,
+        seeds = [pda::ENCRYPTED_ACCOUNT_SEED, recipient_account.seed_owner.as_ref(), &recipient_account.account_index.to_le_bytes()],
+        bump = recipient_account.bump,
+    )]
+    pub recipient_account: Account<'info, EncryptedAccount>,
+    
+    #[account(mut)]
+    pub sender: Signer<'info>,
+    
+    /// Sender's proof buffer; when passed, the proof is read from it in place
+    #[account(
+        seeds = [pda::PROOF_BUFFER_SEED, sender.key().as_ref()],
+        bump = proof_buffer.load()?.bump,
+    )]
+    pub proof_buffer: Option<AccountLoader<'info, ProofBuffer>>,
+    
+    /// CHECK: Global config PDA (may be uninitialized); read for deployment policies
+    #[account(
+        seeds = [pda::CONFIG_SEED],
+        bump
+    )]
+    pub config: UncheckedAccount<'info>,
+    
+    /// CHECK: Protocol stats PDA (may be uninitialized); counters updated when present
+    #[account(
+        mut,
+        seeds = [pda::STATS_SEED],
+        bump
+    )]
+    pub stats: UncheckedAccount<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct CancelVerification<'info> {
+    #[account(
+        mut,
+        close = sender,
+        has_one = sender @ ErrorCode::Unauthorized,
+    )]
+    pub pending_verification: Account<'info, PendingVerification>,
+    
+    #[account(mut)]
+    pub sender: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct Withdraw<'info> {
+    #[account(
+        mut,
+        seeds = [pda::ENCRYPTED_ACCOUNT_SEED, encrypted_account.seed_owner.as_ref(), &encrypted_account.account_index.to_le_bytes()],
+        bump = encrypted_account.bump,
+        has_one = owner,
+        constraint = !encrypted_account.frozen @ ErrorCode::AccountFrozen
+    )]
+    pub encrypted_account: Account<'info, EncryptedAccount>,
+    
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    
+    /// CHECK: Protocol stats PDA (may be uninitialized); counters updated when present
+    #[account(
+        mut,
+        seeds = [pda::STATS_SEED],
+        bump
+    )]
+    pub stats: UncheckedAccount<'info>,
+    
+    /// CHECK: Global config PDA (may be uninitialized); read for the pause flags
+    #[account(
+        seeds = [pda::CONFIG_SEED],
+        bump
+    )]
+    pub config: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct DepositSOL<'info> {
+    #[account(
+        mut,
+        seeds = [pda::ENCRYPTED_ACCOUNT_SEED, encrypted_account.seed_owner.as_ref(), &encrypted_account.account_index.to_le_bytes()],
+        bump = encrypted_account.bump,
+        has_one = owner
+    )]
+    pub encrypted_account: Account<'info, EncryptedAccount>,
+    
+    #[account(
+        mut,
+        seeds = [pda::SOL_ESCROW_SEED, encrypted_account.seed_owner.as_ref(), &encrypted_account.account_index.to_le_bytes()],
+        bump = sol_escrow.bump,
+        has_one = owner
+    )]
+    pub sol_escrow: Account<'info, SolEscrow>,
+    
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    
+    /// CHECK: Global config PDA (may be uninitialized); read for the deny list and deposit cap
+    #[account(
+        seeds = [pda::CONFIG_SEED],
+        bump
+    )]
+    pub config: UncheckedAccount<'info>,
+    
+    /// CHECK: Protocol stats PDA (may be uninitialized); counters updated when present
+    #[account(
+        mut,
+        seeds = [pda::STATS_SEED],
+        bump
+    )]
+    pub stats: UncheckedAccount<'info>,
+    
+    /// Groth16 verifying key of the exclusion circuit; required while a deny list is set
+    #[account(
+        seeds = [pda::NOTE_VERIFYING_KEY_SEED, &[groth16::EXCLUSION_CIRCUIT]],
+        bump = exclusion_verifying_key.bump,
+    )]
+    pub exclusion_verifying_key: Option<Box<Account<'info, NoteVerifyingKey>>>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct WithdrawSOL<'info> {
+    #[account(
+        mut,
+        seeds = [pda::ENCRYPTED_ACCOUNT_SEED, encrypted_account.seed_owner.as_ref(), &encrypted_account.account_index.to_le_bytes()],
+        bump = encrypted_account.bump,
+        has_one = owner,
+        constraint = !encrypted_account.frozen @ ErrorCode::AccountFrozen
+    )]
+    pub encrypted_account: Account<'info, EncryptedAccount>,
+    
+    #[account(
+        mut,
+        seeds = [pda::SOL_ESCROW_SEED, encrypted_account.seed_owner.as_ref(), &encrypted_account.account_index.to_le_bytes()],
+        bump = sol_escrow.bump,
+        has_one = owner
+    )]
+    pub sol_escrow: Account<'info, SolEscrow>,
+    
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    
+    /// Receives the SOL (owner, exchange deposit address, fresh wallet, ...);
+    /// bound by the withdrawal proof
+    #[account(mut)]
+    pub destination: SystemAccount<'info>,
+    
+    /// CHECK: Global config PDA (may be uninitialized); read for compliance policies
+    #[account(
+        seeds = [pda::CONFIG_SEED],
+        bump
+    )]
+    pub config: UncheckedAccount<'info>,
+    
+    /// Oracle attestation for the destination (required when an oracle is configured)
+    pub sanctions_attestation: Option<Account<'info, SanctionsAttestation>>,
+    
+    /// Fee treasury (required while a protocol fee applies)
+    #[account(
+        mut,
+        seeds = [pda::TREASURY_SEED],
+        bump = treasury.bump,
+    )]
+    pub treasury: Option<Account<'info, Treasury>>,
+    
+    /// CHECK: Protocol stats PDA (may be uninitialized); counters updated when present
+    #[account(
+        mut,
+        seeds = [pda::STATS_SEED],
+        bump
+    )]
+    pub stats: UncheckedAccount<'info>,
+    
+    /// Groth16 verifying key of the exclusion circuit; required while a deny list is set
+    #[account(
+        seeds = [pda::NOTE_VERIFYING_KEY_SEED, &[groth16::EXCLUSION_CIRCUIT]],
+        bump = exclusion_verifying_key.bump,
+    )]
+    pub exclusion_verifying_key: Option<Box<Account<'info, NoteVerifyingKey>>>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ConfidentialSOLTransfer<'info> {
+    #[account(
+        mut,
+        seeds = [pda::ENCRYPTED_ACCOUNT_SEED, sender_account.seed_owner.as_ref(), &sender_account.account_index.to_le_bytes()],
+        bump = sender_account.bump,
+        constraint = !sender_account.frozen @ ErrorCode::AccountFrozen
+    )]
+    pub sender_account: Account<'info, EncryptedAccount>,
+    
+    #[account(
+        mut,
+        seeds = [pda::ENCRYPTED_ACCOUNT_SEED, recipient_account.seed_owner.as_ref(), &recipient_account.account_index.to_le_bytes()],
+        bump = recipient_account.bump,
+    )]
+    pub recipient_account: Account<'info, EncryptedAccount>,
+    
+    #[account(
+        mut,
+        seeds = [pda::SOL_ESCROW_SEED, sender_account.seed_owner.as_ref(), &sender_account.account_index.to_le_bytes()],
+        bump = sender_escrow.bump,
+    )]
+    pub sender_escrow: Account<'info, SolEscrow>,
+    
+    #[account(
+        mut,
+        seeds = [pda::SOL_ESCROW_SEED, recipient_account.seed_owner.as_ref(), &recipient_account.account_index.to_le_bytes()],
+        bump = recipient_escrow.bump,
+    )]
+    pub recipient_escrow: Account<'info, SolEscrow>,
+    
+    #[account(mut)]
+    pub sender: Signer<'info>,
+    
+    /// CHECK: Recipient public key
+    pub recipient: UncheckedAccount<'info>,
+    
+    /// CHECK: Global config PDA (may be uninitialized); read for the protocol fee
+    #[account(
+        seeds = [pda::CONFIG_SEED],
+        bump
+    )]
+    pub config: UncheckedAccount<'info>,
+    
+    /// Fee treasury (required while a protocol fee applies)
+    #[account(
+        mut,
+        seeds = [pda::TREASURY_SEED],
+        bump = treasury.bump,
+    )]
+    pub treasury: Option<Account<'info, Treasury>>,
+    
+    /// CHECK: Protocol stats PDA (may be uninitialized); counters updated when present
+    #[account(
+        mut,
+        seeds = [pda::STATS_SEED],
+        bump
+    )]
+    pub stats: UncheckedAccount<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ProposeOwner<'info> {
+    #[account(
+        mut,
+        seeds = [pda::ENCRYPTED_ACCOUNT_SEED, encrypted_account.seed_owner.as_ref(), &encrypted_account.account_index.to_le_bytes()],
+        bump = encrypted_account.bump,
+        has_one = owner,
+        constraint = !encrypted_account.frozen @ ErrorCode::AccountFrozen
+    )]
+    pub encrypted_account: Account<'info, EncryptedAccount>,
+    
+    #[account(
+        mut,
+        seeds = [pda::SOL_ESCROW_SEED, encrypted_account.seed_owner.as_ref(), &encrypted_account.account_index.to_le_bytes()],
+        bump = sol_escrow.bump,
+        has_one = owner
+    )]
+    pub sol_escrow: Account<'info, SolEscrow>,
+    
+    /// CHECK: AuthChain PDA of the account; may be uninitialized when the
+    /// owner has not opted in (checked in auth_chain::enforce_if_configured)
+    #[account(
+        mut,
+        seeds = [pda::AUTH_CHAIN_SEED, encrypted_account.key().as_ref()],
+        bump
+    )]
+    pub auth_chain: UncheckedAccount<'info>,
+    
+    pub owner: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct AcceptOwner<'info> {
+    #[account(
+        mut,
+        seeds = [pda::ENCRYPTED_ACCOUNT_SEED, encrypted_account.seed_owner.as_ref(), &encrypted_account.account_index.to_le_bytes()],
+        bump = encrypted_account.bump,
+        constraint = !encrypted_account.frozen @ ErrorCode::AccountFrozen
+    )]
+    pub encrypted_account: Account<'info, EncryptedAccount>,
+    
+    #[account(
+        mut,
+        seeds = [pda::SOL_ESCROW_SEED, encrypted_account.seed_owner.as_ref(), &encrypted_account.account_index.to_le_bytes()],
+        bump = sol_escrow.bump,
+    )]
+    pub sol_escrow: Account<'info, SolEscrow>,
+    
+    /// CHECK: AuthChain PDA of the account; may be uninitialized when the
+    /// owner has not opted in (checked in auth_chain::enforce_if_configured)
+    #[account(
+        mut,
+        seeds = [pda::AUTH_CHAIN_SEED, encrypted_account.key().as_ref()],
+        bump
+    )]
+    pub auth_chain: UncheckedAccount<'info>,
+    
+    pub new_owner: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct RotateKeys<'info> {
+    #[account(
+        mut,
+        seeds = [pda::ENCRYPTED_ACCOUNT_SEED, encrypted_account.seed_owner.as_ref(), &encrypted_account.account_index.to_le_bytes()],
+        bump = encrypted_account.bump,
+        has_one = owner
+    )]
+    pub encrypted_account: Account<'info, EncryptedAccount>,
+    
+    /// CHECK: AuthChain PDA of the account; may be uninitialized when the
+    /// owner has not opted in (checked in auth_chain::enforce_if_configured)
+    #[account(
+        mut,
+        seeds = [pda::AUTH_CHAIN_SEED, encrypted_account.key().as_ref()],
+        bump
+    )]
+    pub auth_chain: UncheckedAccount<'info>,
+    
+    pub owner: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct MigrateAccount<'info> {
+    /// CHECK: Read raw because its layout may predate the current struct.
+    /// Program ownership is enforced here; discriminator and owner field are
+    /// validated in the handler.
+    #[account(mut, owner = crate::ID)]
+    pub account: UncheckedAccount<'info>,
+    
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(account_index: u16)]
+pub struct MigrateLegacyAccount<'info> {
+    /// CHECK: Legacy layout (no schema byte) predates the current struct.
+    /// Address and program ownership are enforced here; layout and owner
+    /// field are validated in the handler.
+    #[account(
+        mut,
+        owner = crate::ID,
+        seeds = [pda::ENCRYPTED_ACCOUNT_SEED, owner.key().as_ref()],
+        bump
+    )]
+    pub legacy_account: UncheckedAccount<'info>,
+    
+    #[account(
+        init,
+        payer = owner,
+        space = 8 + EncryptedAccount::INIT_SPACE,
+        seeds = [pda::ENCRYPTED_ACCOUNT_SEED, owner.key().as_ref(), &account_index.to_le_bytes()],
+        bump
+    )]
+    pub encrypted_account: Account<'info, EncryptedAccount>,
+    
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(account_index: u16)]
+pub struct MigrateLegacyEscrow<'info> {
+    /// CHECK: Legacy layout (no schema byte) predates the current struct.
+    /// Address and program ownership are enforced here; layout and owner
+    /// field are validated in the handler.
+    #[account(
+        mut,
+        owner = crate::ID,
+        seeds = [pda::SOL_ESCROW_SEED, owner.key().as_ref()],
+        bump
+    )]
+    pub legacy_escrow: UncheckedAccount<'info>,
+    
+    #[account(
+        init,
+        payer = owner,
+        space = 8 + SolEscrow::INIT_SPACE,
+        seeds = [pda::SOL_ESCROW_SEED, owner.key().as_ref(), &account_index.to_le_bytes()],
+        bump
+    )]
+    pub sol_escrow: Account<'info, SolEscrow>,
+    
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(new_len: u32)]
+pub struct ResizeAccount<'info> {
+    #[account(
+        mut,
+        seeds = [pda::ENCRYPTED_ACCOUNT_SEED, encrypted_account.seed_owner.as_ref(), &encrypted_account.account_index.to_le_bytes()],
+        bump = encrypted_account.bump,
+        has_one = owner,
+        constraint = (new_len as usize) >= EncryptedAccount::space_for(encrypted_account.assets.len())
+            && (new_len as usize) <= EncryptedAccount::space_for(encrypted_account.assets.len()) + migration::MAX_RESERVED_BYTES
+            @ ErrorCode::InvalidAccountSize,
+        realloc = new_len as usize,
+        realloc::payer = owner,
+        realloc::zero = false,
+    )]
+    pub encrypted_account: Account<'info, EncryptedAccount>,
+    
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct InitializeProofBuffer<'info> {
+    #[account(
+        init,
+        payer = owner,
+        space = 8 + std::mem::size_of::<ProofBuffer>(),
+        seeds = [pda::PROOF_BUFFER_SEED, owner.key().as_ref()],
+        bump
+    )]
+    pub proof_buffer: AccountLoader<'info, ProofBuffer>,
+    
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct WriteProofBuffer<'info> {
+    #[account(
+        mut,
+        seeds = [pda::PROOF_BUFFER_SEED, owner.key().as_ref()],
+        bump = proof_buffer.load()?.bump,
+    )]
+    pub proof_buffer: AccountLoader<'info, ProofBuffer>,
+    
+    pub owner: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct CloseProofBuffer<'info> {
+    #[account(
+        mut,
+        close = owner,
+        seeds = [pda::PROOF_BUFFER_SEED, owner.key().as_ref()],
+        bump = proof_buffer.load()?.bump,
+    )]
+    pub proof_buffer: AccountLoader<'info, ProofBuffer>,
+    
+    #[account(mut)]
+    pub owner: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct InitializeTokenEscrow<'info> {
+    #[account(
+        init,
+        payer = owner,
+        space = 8 + TokenEscrow::INIT_SPACE,
+        seeds = [pda::TOKEN_ESCROW_SEED, owner.key().as_ref(), mint.key().as_ref()],
+        bump
+    )]
+    pub token_escrow: Account<'info, TokenEscrow>,
+    
+    #[account(
+        init,
+        payer = owner,
+        seeds = [pda::TOKEN_VAULT_SEED, token_escrow.key().as_ref()],
+        bump,
+        token::mint = mint,
+        token::authority = token_escrow,
+        token::token_program = token_program,
+    )]
+    pub token_vault: InterfaceAccount<'info, TokenAccount>,
+    
+    pub mint: InterfaceAccount<'info, Mint>,
+    
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    
+    pub token_program: Interface<'info, TokenInterface>,
+    
+    /// CHECK: Protocol stats PDA (may be uninitialized); counters updated when present
+    #[account(
+        mut,
+        seeds = [pda::STATS_SEED],
+        bump
+    )]
+    pub stats: UncheckedAccount<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct DepositToken<'info> {
+    #[account(
+        mut,
+        seeds = [pda::TOKEN_ESCROW_SEED, owner.key().as_ref(), mint.key().as_ref()],
+        bump = token_escrow.bump,
+        has_one = owner,
+        has_one = mint
+    )]
+    pub token_escrow: Account<'info, TokenEscrow>,
+    
+    #[account(
+        mut,
+        seeds = [pda::TOKEN_VAULT_SEED, token_escrow.key().as_ref()],
+        bump = token_escrow.vault_bump,
+    )]
+    pub token_vault: InterfaceAccount<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        token::mint = mint,
+        token::authority = owner,
+        token::token_program = token_program,
+    )]
+    pub user_token_account: InterfaceAccount<'info, TokenAccount>,
+    
+    #[account(
+        seeds = [pda::CONFIG_SEED],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, Config>,
+    
+    /// Allowlist entry; required only while the allowlist is enforced
+    #[account(
+        seeds = [pda::MINT_CONFIG_SEED, mint.key().as_ref()],
+        bump = mint_config.bump,
+    )]
+    pub mint_config: Option<Account<'info, MintConfig>>,
+    
+    pub mint: InterfaceAccount<'info, Mint>,
+    
+    pub owner: Signer<'info>,
+    
+    pub token_program: Interface<'info, TokenInterface>,
+    
+    /// CHECK: Protocol stats PDA (may be uninitialized); counters updated when present
+    #[account(
+        mut,
+        seeds = [pda::STATS_SEED],
+        bump
+    )]
+    pub stats: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct WithdrawToken<'info> {
+    #[account(
+        mut,
+        seeds = [pda::TOKEN_ESCROW_SEED, owner.key().as_ref(), mint.key().as_ref()],
+        bump = token_escrow.bump,
+        has_one = owner,
+        has_one = mint
+    )]
+    pub token_escrow: Account<'info, TokenEscrow>,
+    
+    #[account(
+        mut,
+        seeds = [pda::TOKEN_VAULT_SEED, token_escrow.key().as_ref()],
+        bump = token_escrow.vault_bump,
+    )]
+    pub token_vault: InterfaceAccount<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        token::mint = mint,
+        token::token_program = token_program,
+    )]
+    pub user_token_account: InterfaceAccount<'info, TokenAccount>,
+    
+    pub mint: InterfaceAccount<'info, Mint>,
+    
+    pub owner: Signer<'info>,
+    
+    /// CHECK: Global config PDA (may be uninitialized); read for compliance policies
+    #[account(
+        seeds = [pda::CONFIG_SEED],
+        bump
+    )]
+    pub config: UncheckedAccount<'info>,
+    
+    /// Oracle attestation for the destination (required when an oracle is configured)
+    pub sanctions_attestation: Option<Account<'info, SanctionsAttestation>>,
+    
+    pub token_program: Interface<'info, TokenInterface>,
+    
+    /// CHECK: Protocol stats PDA (may be uninitialized); counters updated when present
+    #[account(
+        mut,
+        seeds = [pda::STATS_SEED],
+        bump
+    )]
+    pub stats: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct DepositSolWrapped<'info> {
+    #[account(
+        mut,
+        seeds = [pda::TOKEN_ESCROW_SEED, owner.key().as_ref(), mint.key().as_ref()],
+        bump = token_escrow.bump,
+        has_one = owner,
+        has_one = mint
+    )]
+    pub token_escrow: Account<'info, TokenEscrow>,
+    
+    #[account(
+        mut,
+        seeds = [pda::TOKEN_VAULT_SEED, token_escrow.key().as_ref()],
+        bump = token_escrow.vault_bump,
+    )]
+    pub token_vault: InterfaceAccount<'info, TokenAccount>,
+    
+    #[account(
+        constraint = is_native_mint(&mint.key()) @ ErrorCode::NotNativeMint
+    )]
+    pub mint: InterfaceAccount<'info, Mint>,
+    
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    
+    pub token_program: Interface<'info, TokenInterface>,
+    
+    /// CHECK: Global config PDA (may be uninitialized); read for the pause flags
+    #[account(
+        seeds = [pda::CONFIG_SEED],
+        bump
+    )]
+    pub config: UncheckedAccount<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct WithdrawSolUnwrapped<'info> {
+    #[account(
+        mut,
+        seeds = [pda::TOKEN_ESCROW_SEED, owner.key().as_ref(), mint.key().as_ref()],
+        bump = token_escrow.bump,
+        has_one = owner,
+        has_one = mint
+    )]
+    pub token_escrow: Account<'info, TokenEscrow>,
+    
+    #[account(
+        mut,
+        seeds = [pda::TOKEN_VAULT_SEED, token_escrow.key().as_ref()],
+        bump = token_escrow.vault_bump,
+    )]
+    pub token_vault: InterfaceAccount<'info, TokenAccount>,
+    
+    /// Temporary wSOL account; closed by this instruction
+    #[account(
+        mut,
+        token::mint = mint,
+        token::authority = owner,
+        token::token_program = token_program,
+    )]
+    pub user_wsol_account: InterfaceAccount<'info, TokenAccount>,
+    
+    #[account(
+        constraint = is_native_mint(&mint.key()) @ ErrorCode::NotNativeMint
+    )]
+    pub mint: InterfaceAccount<'info, Mint>,
+    
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    
+    pub token_program: Interface<'info, TokenInterface>,
+    
+    /// CHECK: Global config PDA (may be uninitialized); read for the pause flags
+    #[account(
+        seeds = [pda::CONFIG_SEED],
+        bump
+    )]
+    pub config: UncheckedAccount<'info>,
+    
+    /// CHECK: Protocol stats PDA (may be uninitialized); counters updated when present
+    #[account(
+        mut,
+        seeds = [pda::STATS_SEED],
+        bump
+    )]
+    pub stats: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ConfidentialTokenTransfer<'info> {
+    #[account(
+        mut,
+        seeds = [pda::TOKEN_ESCROW_SEED, sender.key().as_ref(), mint.key().as_ref()],
+        bump = sender_escrow.bump,
+        has_one = mint
+    )]
+    pub sender_escrow: Account<'info, TokenEscrow>,
+    
+    #[account(
+        mut,
+        seeds = [pda::TOKEN_ESCROW_SEED, recipient.key().as_ref(), mint.key().as_ref()],
+        bump = recipient_escrow.bump,
+        has_one = mint
+    )]
+    pub recipient_escrow: Account<'info, TokenEscrow>,
+    
+    #[account(
+        mut,
+        seeds = [pda::TOKEN_VAULT_SEED, sender_escrow.key().as_ref()],
+        bump = sender_escrow.vault_bump,
+    )]
+    pub sender_vault: InterfaceAccount<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        seeds = [pda::TOKEN_VAULT_SEED, recipient_escrow.key().as_ref()],
+        bump = recipient_escrow.vault_bump,
+    )]
+    pub recipient_vault: InterfaceAccount<'info, TokenAccount>,
+    
+    #[account(
+        seeds = [pda::CONFIG_SEED],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, Config>,
+    
+    /// Allowlist entry; required only while the allowlist is enforced
+    #[account(
+        seeds = [pda::MINT_CONFIG_SEED, mint.key().as_ref()],
+        bump = mint_config.bump,
+    )]
+    pub mint_config: Option<Account<'info, MintConfig>>,
+    
+    pub mint: InterfaceAccount<'info, Mint>,
+    
+    pub sender: Signer<'info>,
+    
+    /// CHECK: Recipient public key
+    pub recipient: UncheckedAccount<'info>,
+    
+    pub token_program: Interface<'info, TokenInterface>,
+    
+    /// CHECK: Protocol stats PDA (may be uninitialized); counters updated when present
+    #[account(
+        mut,
+        seeds = [pda::STATS_SEED],
+        bump
+    )]
+    pub stats: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct InitializeArchiveTree<'info> {
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + ArchiveTree::INIT_SPACE,
+        seeds = [pda::ARCHIVE_TREE_SEED],
+        bump
+    )]
+    pub archive_tree: Account<'info, ArchiveTree>,
+    
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct CloseAccount<'info> {
+    #[account(
+        mut,
+        close = owner,
+        seeds = [pda::ENCRYPTED_ACCOUNT_SEED, encrypted_account.seed_owner.as_ref(), &encrypted_account.account_index.to_le_bytes()],
+        bump = encrypted_account.bump,
+        has_one = owner,
+        constraint = !encrypted_account.frozen @ ErrorCode::AccountFrozen
+    )]
+    pub encrypted_account: Account<'info, EncryptedAccount>,
+    
+    /// Required only when archiving
+    #[account(
+        mut,
+        seeds = [pda::ARCHIVE_TREE_SEED],
+        bump = archive_tree.bump,
+    )]
+    pub archive_tree: Option<Account<'info, ArchiveTree>>,
+    
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    
+    /// CHECK: Protocol stats PDA (may be uninitialized); counters updated when present
+    #[account(
+        mut,
+        seeds = [pda::STATS_SEED],
+        bump
+    )]
+    pub stats: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct CloseSolEscrow<'info> {
+    #[account(
+        mut,
+        close = owner,
+        seeds = [pda::SOL_ESCROW_SEED, sol_escrow.seed_owner.as_ref(), &sol_escrow.account_index.to_le_bytes()],
+        bump = sol_escrow.bump,
+        has_one = owner
+    )]
+    pub sol_escrow: Account<'info, SolEscrow>,
+    
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    
+    /// CHECK: Protocol stats PDA (may be uninitialized); counters updated when present
+    #[account(
+        mut,
+        seeds = [pda::STATS_SEED],
+        bump
+    )]
+    pub stats: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct Heartbeat {}
+
+#[derive(Accounts)]
+pub struct RegisterCanary<'info> {
+    #[account(
+        seeds = [pda::CONFIG_SEED],
+        bump = config.bump,
+        has_one = authority
+    )]
+    pub config: Account<'info, Config>,
+    
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + Canary::INIT_SPACE,
+        seeds = [pda::CANARY_SEED, watched_account.key().as_ref()],
+        bump
+    )]
+    pub canary: Account<'info, Canary>,
+    
+    /// CHECK: Any program-owned account; validated in the instruction
+    pub watched_account: UncheckedAccount<'info>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateCanary<'info> {
+    #[account(
+        seeds = [pda::CONFIG_SEED],
+        bump = config.bump,
+        has_one = authority
+    )]
+    pub config: Account<'info, Config>,
+    
+    #[account(
+        mut,
+        seeds = [pda::CANARY_SEED, watched_account.key().as_ref()],
+        bump = canary.bump,
+    )]
+    pub canary: Account<'info, Canary>,
+    
+    /// CHECK: Bound to the canary through its PDA seeds
+    pub watched_account: UncheckedAccount<'info>,
+    
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct CloseCanary<'info> {
+    #[account(
+        seeds = [pda::CONFIG_SEED],
+        bump = config.bump,
+        has_one = authority
+    )]
+    pub config: Account<'info, Config>,
+    
+    #[account(
+        mut,
+        seeds = [pda::CANARY_SEED, canary.watched_account.as_ref()],
+        bump = canary.bump,
+        close = authority
+    )]
+    pub canary: Account<'info, Canary>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct CheckCanary<'info> {
+    #[account(
+        mut,
+        seeds = [pda::CANARY_SEED, watched_account.key().as_ref()],
+        bump = canary.bump,
+    )]
+    pub canary: Account<'info, Canary>,
+    
+    /// CHECK: Bound to the canary through its PDA seeds
+    pub watched_account: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct InitializeConfig<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + Config::INIT_SPACE,
+        seeds = [pda::CONFIG_SEED],
+        bump
+    )]
+    pub config: Account<'info, Config>,
+    
+    #[account(
+        constraint = program.programdata_address()? == Some(program_data.key()) @ ErrorCode::Unauthorized
+    )]
+    pub program: Program<'info, crate::program::PrivacyTransfer>,
+    
+    #[account(
+        constraint = program_data.upgrade_authority_address == Some(authority.key()) @ ErrorCode::Unauthorized
+    )]
+    pub program_data: Account<'info, ProgramData>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateConfig<'info> {
+    #[account(
+        mut,
+        seeds = [pda::CONFIG_SEED],
+        bump = config.bump,
+        has_one = authority
+    )]
+    pub config: Account<'info, Config>,
+    
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct AddSupportedMint<'info> {
+    #[account(
+        seeds = [pda::CONFIG_SEED],
+        bump = config.bump,
+        has_one = authority
+    )]
+    pub config: Account<'info, Config>,
+    
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + MintConfig::INIT_SPACE,
+        seeds = [pda::MINT_CONFIG_SEED, mint.key().as_ref()],
+        bump
+    )]
+    pub mint_config: Account<'info, MintConfig>,
+    
+    pub mint: InterfaceAccount<'info, Mint>,
+    
+    /// Queued allowlist change; required while a config timelock is set
+    #[account(
+        mut,
+        seeds = [pda::PENDING_CONFIG_CHANGE_SEED],
+        bump = pending_change.bump,
+        close = authority
+    )]
+    pub pending_change: Option<Account<'info, PendingConfigChange>>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateSupportedMint<'info> {
+    #[account(
+        seeds = [pda::CONFIG_SEED],
+        bump = config.bump,
+        has_one = authority
+    )]
+    pub config: Account<'info, Config>,
+    
+    #[account(
+        mut,
+        seeds = [pda::MINT_CONFIG_SEED, mint_config.mint.as_ref()],
+        bump = mint_config.bump,
+    )]
+    pub mint_config: Account<'info, MintConfig>,
+    
+    /// Queued allowlist change; required while a config timelock is set
+    #[account(
+        mut,
+        seeds = [pda::PENDING_CONFIG_CHANGE_SEED],
+        bump = pending_change.bump,
+        close = authority
+    )]
+    pub pending_change: Option<Account<'info, PendingConfigChange>>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct RemoveSupportedMint<'info> {
+    #[account(
+        seeds = [pda::CONFIG_SEED],
+        bump = config.bump,
+        has_one = authority
+    )]
+    pub config: Account<'info, Config>,
+    
+    #[account(
+        mut,
+        close = authority,
+        seeds = [pda::MINT_CONFIG_SEED, mint_config.mint.as_ref()],
+        bump = mint_config.bump,
+    )]
+    pub mint_config: Account<'info, MintConfig>,
+    
+    /// Queued allowlist change; required while a config timelock is set
+    #[account(
+        mut,
+        seeds = [pda::PENDING_CONFIG_CHANGE_SEED],
+        bump = pending_change.bump,
+        close = authority
+    )]
+    pub pending_change: Option<Account<'info, PendingConfigChange>>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct InitializeReceiptMint<'info> {
+    /// CHECK: Created and initialized as a Token-2022 mint in the handler
+    #[account(
+        mut,
+        seeds = [pda::RECEIPT_MINT_SEED],
+        bump
+    )]
+    pub receipt_mint: UncheckedAccount<'info>,
+    
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    
+    pub token_program: Program<'info, Token2022>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ReceiptTokens<'info> {
+    #[account(
+        mut,
+        seeds = [pda::SOL_ESCROW_SEED, sol_escrow.seed_owner.as_ref(), &sol_escrow.account_index.to_le_bytes()],
+        bump = sol_escrow.bump,
+        has_one = owner
+    )]
+    pub sol_escrow: Account<'info, SolEscrow>,
+    
+    #[account(
+        mut,
+        seeds = [pda::RECEIPT_MINT_SEED],
+        bump,
+        mint::token_program = token_program,
+    )]
+    pub receipt_mint: InterfaceAccount<'info, Mint>,
+    
+    #[account(
+        mut,
+        token::mint = receipt_mint,
+        token::authority = owner,
+        token::token_program = token_program,
+    )]
+    pub receipt_token_account: InterfaceAccount<'info, TokenAccount>,
+    
+    pub owner: Signer<'info>,
+    
+    pub token_program: Program<'info, Token2022>,
+}
+
+#[derive(Accounts)]
+pub struct InitializeAuthChain<'info> {
+    #[account(
+        init,
+        payer = owner,
+        space = 8 + AuthChain::INIT_SPACE,
+        seeds = [pda::AUTH_CHAIN_SEED, encrypted_account.key().as_ref()],
+        bump
+    )]
+    pub auth_chain: Account<'info, AuthChain>,
+    
+    #[account(
+        mut,
+        seeds = [pda::ENCRYPTED_ACCOUNT_SEED, encrypted_account.seed_owner.as_ref(), &encrypted_account.account_index.to_le_bytes()],
+        bump = encrypted_account.bump,
+        has_one = owner
+    )]
+    pub encrypted_account: Account<'info, EncryptedAccount>,
+    
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateAuthChain<'info> {
+    #[account(
+        mut,
+        seeds = [pda::AUTH_CHAIN_SEED, encrypted_account.key().as_ref()],
+        bump = auth_chain.bump,
+        has_one = encrypted_account
+    )]
+    pub auth_chain: Account<'info, AuthChain>,
+    
+    #[account(
+        mut,
+        seeds = [pda::ENCRYPTED_ACCOUNT_SEED, encrypted_account.seed_owner.as_ref(), &encrypted_account.account_index.to_le_bytes()],
+        bump = encrypted_account.bump,
+        has_one = owner
+    )]
+    pub encrypted_account: Account<'info, EncryptedAccount>,
+    
+    pub owner: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct CloseAuthChain<'info> {
+    #[account(
+        mut,
+        close = owner,
+        seeds = [pda::AUTH_CHAIN_SEED, encrypted_account.key().as_ref()],
+        bump = auth_chain.bump,
+        has_one = encrypted_account
+    )]
+    pub auth_chain: Account<'info, AuthChain>,
+    
+    #[account(
+        mut,
+        seeds = [pda::ENCRYPTED_ACCOUNT_SEED, encrypted_account.seed_owner.as_ref(), &encrypted_account.account_index.to_le_bytes()],
+        bump = encrypted_account.bump,
+        has_one = owner
+    )]
+    pub encrypted_account: Account<'info, EncryptedAccount>,
+    
+    #[account(mut)]
+    pub owner: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct InitializeConfidentialVault<'info> {
+    #[account(
+        seeds = [pda::TOKEN_ESCROW_SEED, owner.key().as_ref(), mint.key().as_ref()],
+        bump = token_escrow.bump,
+        has_one = owner,
+        has_one = mint
+    )]
+    pub token_escrow: Account<'info, TokenEscrow>,
+    
+    /// CHECK: Created and configured as a Token-2022 account in the handler
+    #[account(
+        mut,
+        seeds = [pda::CONFIDENTIAL_VAULT_SEED, token_escrow.key().as_ref()],
+        bump
+    )]
+    pub confidential_vault: UncheckedAccount<'info>,
+    
+    pub mint: InterfaceAccount<'info, Mint>,
+    
+    /// CHECK: Pre-verified ElGamal pubkey validity proof context, validated by Token-2022
+    pub pubkey_proof_context: UncheckedAccount<'info>,
+    
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    
+    pub token_program: Program<'info, Token2022>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ApplyConfidentialVaultBalance<'info> {
+    #[account(
+        seeds = [pda::TOKEN_ESCROW_SEED, owner.key().as_ref(), token_escrow.mint.as_ref()],
+        bump = token_escrow.bump,
+        has_one = owner
+    )]
+    pub token_escrow: Account<'info, TokenEscrow>,
+    
+    /// CHECK: Escrow's confidential vault (PDA); Token-2022 checks its authority
+    #[account(
+        mut,
+        seeds = [pda::CONFIDENTIAL_VAULT_SEED, token_escrow.key().as_ref()],
+        bump
+    )]
+    pub confidential_vault: UncheckedAccount<'info>,
+    
+    pub owner: Signer<'info>,
+    
+    pub token_program: Program<'info, Token2022>,
+}
+
+#[derive(Accounts)]
+pub struct DepositFromConfidential<'info> {
+    #[account(
+        mut,
+        seeds = [pda::TOKEN_ESCROW_SEED, owner.key().as_ref(), mint.key().as_ref()],
+        bump = token_escrow.bump,
+        has_one = owner,
+        has_one = mint
+    )]
+    pub token_escrow: Account<'info, TokenEscrow>,
+    
+    /// CHECK: Escrow's confidential vault (PDA); Token-2022 checks it is configured
+    #[account(
+        mut,
+        seeds = [pda::CONFIDENTIAL_VAULT_SEED, token_escrow.key().as_ref()],
+        bump
+    )]
+    pub confidential_vault: UncheckedAccount<'info>,
+    
+    #[account(
+        mut,
+        token::mint = mint,
+        token::authority = owner,
+        token::token_program = token_program,
+    )]
+    pub user_token_account: InterfaceAccount<'info, TokenAccount>,
+    
+    pub mint: InterfaceAccount<'info, Mint>,
+    
+    /// CHECK: Pre-verified equality proof context state, validated by Token-2022
+    pub equality_proof_context: UncheckedAccount<'info>,
+    
+    /// CHECK: Pre-verified ciphertext validity proof context state; owner and
+    /// proof type checked when the amount is read, the rest by Token-2022
+    pub ciphertext_validity_proof_context: UncheckedAccount<'info>,
+    
+    /// CHECK: Pre-verified range proof context state, validated by Token-2022
+    pub range_proof_context: UncheckedAccount<'info>,
+    
+    pub owner: Signer<'info>,
+    
+    pub token_program: Program<'info, Token2022>,
+    
+    /// CHECK: Global config PDA (may be uninitialized); read for the pause flags
+    #[account(
+        seeds = [pda::CONFIG_SEED],
+        bump
+    )]
+    pub config: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct WithdrawToConfidential<'info> {
+    #[account(
+        mut,
+        seeds = [pda::TOKEN_ESCROW_SEED, owner.key().as_ref(), mint.key().as_ref()],
+        bump = token_escrow.bump,
+        has_one = owner,
+        has_one = mint
+    )]
+    pub token_escrow: Account<'info, TokenEscrow>,
+    
+    /// CHECK: Escrow's confidential vault (PDA); Token-2022 checks its authority
+    #[account(
+        mut,
+        seeds = [pda::CONFIDENTIAL_VAULT_SEED, token_escrow.key().as_ref()],
+        bump
+    )]
+    pub confidential_vault: UncheckedAccount<'info>,
+    
+    #[account(
+        mut,
+        token::mint = mint,
+        token::authority = owner,
+        token::token_program = token_program,
+    )]
+    pub user_token_account: InterfaceAccount<'info, TokenAccount>,
+    
+    pub mint: InterfaceAccount<'info, Mint>,
+    
+    /// CHECK: Pre-verified equality proof context state, validated by Token-2022
+    pub equality_proof_context: UncheckedAccount<'info>,
+    
+    /// CHECK: Pre-verified ciphertext validity proof context state; owner and
+    /// proof type checked when the amount is read, the rest by Token-2022
+    pub ciphertext_validity_proof_context: UncheckedAccount<'info>,
+    
+    /// CHECK: Pre-verified range proof context state, validated by Token-2022
+    pub range_proof_context: UncheckedAccount<'info>,
+    
+    pub owner: Signer<'info>,
+    
+    pub token_program: Program<'info, Token2022>,
+    
+    /// CHECK: Global config PDA (may be uninitialized); read for the pause flags
+    #[account(
+        seeds = [pda::CONFIG_SEED],
+        bump
+    )]
+    pub config: UncheckedAccount<'info>,
+    
+    /// CHECK: Protocol stats PDA (may be uninitialized); counters updated when present
+    #[account(
+        mut,
+        seeds = [pda::STATS_SEED],
+        bump
+    )]
+    pub stats: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct AddAsset<'info> {
+    #[account(
+        mut,
+        seeds = [pda::ENCRYPTED_ACCOUNT_SEED, encrypted_account.seed_owner.as_ref(), &encrypted_account.account_index.to_le_bytes()],
+        bump = encrypted_account.bump,
+        has_one = owner,
+        realloc = EncryptedAccount::space_for(encrypted_account.assets.len() + 1)
+            .max(encrypted_account.to_account_info().data_len()),
+        realloc::payer = owner,
+        realloc::zero = false,
+    )]
+    pub encrypted_account: Account<'info, EncryptedAccount>,
+    
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RemoveAsset<'info> {
+    #[account(
+        mut,
+        seeds = [pda::ENCRYPTED_ACCOUNT_SEED, encrypted_account.seed_owner.as_ref(), &encrypted_account.account_index.to_le_bytes()],
+        bump = encrypted_account.bump,
+        has_one = owner,
+        realloc = EncryptedAccount::space_for(encrypted_account.assets.len().saturating_sub(1)),
+        realloc::payer = owner,
+        realloc::zero = false,
+    )]
+    pub encrypted_account: Account<'info, EncryptedAccount>,
+    
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateAsset<'info> {
+    #[account(
+        mut,
+        seeds = [pda::ENCRYPTED_ACCOUNT_SEED, encrypted_account.seed_owner.as_ref(), &encrypted_account.account_index.to_le_bytes()],
+        bump = encrypted_account.bump,
+        has_one = owner,
+        constraint = !encrypted_account.frozen @ ErrorCode::AccountFrozen
+    )]
+    pub encrypted_account: Account<'info, EncryptedAccount>,
+    
+    pub owner: Signer<'info>,
+    
+    /// CHECK: Global config PDA (may be uninitialized); read for the pause flags
+    #[account(
+        seeds = [pda::CONFIG_SEED],
+        bump
+    )]
+    pub config: UncheckedAccount<'info>,
+    
+    /// CHECK: Protocol stats PDA (may be uninitialized); counters updated when present
+    #[account(
+        mut,
+        seeds = [pda::STATS_SEED],
+        bump
+    )]
+    pub stats: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ConfidentialAssetTransfer<'info> {
+    #[account(
+        mut,
+        seeds = [pda::ENCRYPTED_ACCOUNT_SEED, sender_account.seed_owner.as_ref(), &sender_account.account_index.to_le_bytes()],
+        bump = sender_account.bump,
+        constraint = !sender_account.frozen @ ErrorCode::AccountFrozen
+    )]
+    pub sender_account: Account<'info, EncryptedAccount>,
+    
+    #[account(
+        mut,
+        seeds = [pda::ENCRYPTED_ACCOUNT_SEED, recipient_account.seed_owner.as_ref(), &recipient_account.account_index.to_le_bytes()],
+        bump = recipient_account.bump,
+    )]
+    pub recipient_account: Account<'info, EncryptedAccount>,
+    
+    pub sender: Signer<'info>,
+    
+    /// CHECK: Recipient public key, not a signer
+    pub recipient: UncheckedAccount<'info>,
+    
+    /// CHECK: Global config PDA (may be uninitialized); read for the pause flags
+    #[account(
+        seeds = [pda::CONFIG_SEED],
+        bump
+    )]
+    pub config: UncheckedAccount<'info>,
+    
+    /// CHECK: Protocol stats PDA (may be uninitialized); counters updated when present
+    #[account(
+        mut,
+        seeds = [pda::STATS_SEED],
+        bump
+    )]
+    pub stats: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ShieldTokens<'info> {
+    #[account(
+        mut,
+        seeds = [pda::TOKEN_ESCROW_SEED, owner.key().as_ref(), mint.key().as_ref()],
+        bump = token_escrow.bump,
+        has_one = owner,
+        has_one = mint
+    )]
+    pub token_escrow: Account<'info, TokenEscrow>,
+    
+    #[account(
+        mut,
+        seeds = [pda::TOKEN_VAULT_SEED, token_escrow.key().as_ref()],
+        bump = token_escrow.vault_bump,
+    )]
+    pub token_vault: InterfaceAccount<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        token::mint = mint,
+        token::authority = owner,
+        token::token_program = token_program,
+    )]
+    pub user_token_account: InterfaceAccount<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        seeds = [pda::ENCRYPTED_ACCOUNT_SEED, encrypted_account.seed_owner.as_ref(), &encrypted_account.account_index.to_le_bytes()],
+        bump = encrypted_account.bump,
+        has_one = owner
+    )]
+    pub encrypted_account: Account<'info, EncryptedAccount>,
+    
+    #[account(
+        seeds = [pda::CONFIG_SEED],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, Config>,
+    
+    /// Allowlist entry; required only while the allowlist is enforced
+    #[account(
+        seeds = [pda::MINT_CONFIG_SEED, mint.key().as_ref()],
+        bump = mint_config.bump,
+    )]
+    pub mint_config: Option<Account<'info, MintConfig>>,
+    
+    pub mint: InterfaceAccount<'info, Mint>,
+    
+    pub owner: Signer<'info>,
+    
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+#[derive(Accounts)]
+pub struct InitializePoolVault<'info> {
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + PoolVault::INIT_SPACE,
+        seeds = [pda::POOL_VAULT_SEED],
+        bump
+    )]
+    pub pool_vault: Account<'info, PoolVault>,
+    
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct PoolTransfer<'info> {
+    #[account(
+        mut,
+        seeds = [pda::POOL_VAULT_SEED],
+        bump = pool_vault.bump,
+    )]
+    pub pool_vault: Account<'info, PoolVault>,
+    
+    #[account(
+        mut,
+        seeds = [pda::ENCRYPTED_ACCOUNT_SEED, encrypted_account.seed_owner.as_ref(), &encrypted_account.account_index.to_le_bytes()],
+        bump = encrypted_account.bump,
+        has_one = owner,
+        constraint = !encrypted_account.frozen @ ErrorCode::AccountFrozen
+    )]
+    pub encrypted_account: Account<'info, EncryptedAccount>,
+    
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    
+    /// CHECK: Global config PDA (may be uninitialized); read for the deposit cap
+    #[account(
+        seeds = [pda::CONFIG_SEED],
+        bump
+    )]
+    pub config: UncheckedAccount<'info>,
+    
+    /// CHECK: Protocol stats PDA (may be uninitialized); counters updated when present
+    #[account(
+        mut,
+        seeds = [pda::STATS_SEED],
+        bump
+    )]
+    pub stats: UncheckedAccount<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RegisterPrivateAsset<'info> {
+    #[account(
+        init,
+        payer = issuer,
+        space = 8 + PrivateAsset::INIT_SPACE,
+        seeds = [pda::PRIVATE_ASSET_SEED, asset_id.key().as_ref()],
+        bump
+    )]
+    pub private_asset: Account<'info, PrivateAsset>,
+    
+    /// Fresh keypair identifying the asset (signs once to prevent squatting)
+    pub asset_id: Signer<'info>,
+    
+    #[account(mut)]
+    pub issuer: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ConfidentialSupply<'info> {
+    #[account(
+        mut,
+        seeds = [pda::PRIVATE_ASSET_SEED, private_asset.asset_id.as_ref()],
+        bump = private_asset.bump,
+        has_one = issuer
+    )]
+    pub private_asset: Account<'info, PrivateAsset>,
+    
+    #[account(
+        mut,
+        seeds = [pda::ENCRYPTED_ACCOUNT_SEED, holder_account.seed_owner.as_ref(), &holder_account.account_index.to_le_bytes()],
+        bump = holder_account.bump,
+    )]
+    pub holder_account: Account<'info, EncryptedAccount>,
+    
+    pub issuer: Signer<'info>,
+    
+    /// Holder signature (required for burns only)
+    pub holder: Option<Signer<'info>>,
+}
+
+#[derive(Accounts)]
+pub struct InitializeMailbox<'info> {
+    #[account(
+        init,
+        payer = owner,
+        space = 8 + Mailbox::INIT_SPACE,
+        seeds = [pda::MAILBOX_SEED, encrypted_account.key().as_ref()],
+        bump
+    )]
+    pub mailbox: Account<'info, Mailbox>,
+    
+    #[account(
+        mut,
+        seeds = [pda::ENCRYPTED_ACCOUNT_SEED, encrypted_account.seed_owner.as_ref(), &encrypted_account.account_index.to_le_bytes()],
+        bump = encrypted_account.bump,
+        has_one = owner
+    )]
+    pub encrypted_account: Account<'info, EncryptedAccount>,
+    
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateMailbox<'info> {
+    #[account(
+        mut,
+        seeds = [pda::MAILBOX_SEED, encrypted_account.key().as_ref()],
+        bump = mailbox.bump,
+        has_one = encrypted_account
+    )]
+    pub mailbox: Account<'info, Mailbox>,
+    
+    #[account(
+        mut,
+        seeds = [pda::ENCRYPTED_ACCOUNT_SEED, encrypted_account.seed_owner.as_ref(), &encrypted_account.account_index.to_le_bytes()],
+        bump = encrypted_account.bump,
+        has_one = owner
+    )]
+    pub encrypted_account: Account<'info, EncryptedAccount>,
+    
+    pub owner: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct PostMessage<'info> {
+    #[account(
+        mut,
+        seeds = [pda::MAILBOX_SEED, encrypted_account.key().as_ref()],
+        bump = mailbox.bump,
+        has_one = encrypted_account
+    )]
+    pub mailbox: Account<'info, Mailbox>,
+    
+    #[account(
+        init,
+        payer = sender,
+        space = 8 + MailMessage::INIT_SPACE,
+        seeds = [pda::MAIL_MESSAGE_SEED, mailbox.key().as_ref(), &mailbox.next_seq.to_le_bytes()],
+        bump
+    )]
+    pub message: Account<'info, MailMessage>,
+    
+    #[account(
+        seeds = [pda::ENCRYPTED_ACCOUNT_SEED, encrypted_account.seed_owner.as_ref(), &encrypted_account.account_index.to_le_bytes()],
+        bump = encrypted_account.bump,
+    )]
+    pub encrypted_account: Account<'info, EncryptedAccount>,
+    
+    #[account(mut)]
+    pub sender: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct CloseMessage<'info> {
+    #[account(
+        mut,
+        seeds = [pda::MAIL_MESSAGE_SEED, mailbox.key().as_ref(), &message.seq.to_le_bytes()],
+        bump = message.bump,
+        has_one = mailbox,
+        close = owner
+    )]
+    pub message: Account<'info, MailMessage>,
+    
+    #[account(
+        seeds = [pda::MAILBOX_SEED, encrypted_account.key().as_ref()],
+        bump = mailbox.bump,
+        has_one = encrypted_account
+    )]
+    pub mailbox: Account<'info, Mailbox>,
+    
+    #[account(
+        seeds = [pda::ENCRYPTED_ACCOUNT_SEED, encrypted_account.seed_owner.as_ref(), &encrypted_account.account_index.to_le_bytes()],
+        bump = encrypted_account.bump,
+        has_one = owner
+    )]
+    pub encrypted_account: Account<'info, EncryptedAccount>,
+    
+    #[account(mut)]
+    pub owner: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct InitializeNotePool<'info> {
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + NotePool::INIT_SPACE,
+        seeds = [pda::NOTE_POOL_SEED],
+        bump
+    )]
+    pub note_pool: Box<Account<'info, NotePool>>,
+    
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct DepositNote<'info> {
+    #[account(
+        mut,
+        seeds = [pda::NOTE_POOL_SEED],
+        bump = note_pool.bump,
+    )]
+    pub note_pool: Box<Account<'info, NotePool>>,
+    
+    #[account(mut)]
+    pub depositor: Signer<'info>,
+    
+    /// CHECK: Global config PDA (may be uninitialized); read for the pause flags
+    #[account(
+        seeds = [pda::CONFIG_SEED],
+        bump
+    )]
+    pub config: UncheckedAccount<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(root: [u8; 32], nullifier: [u8; 32])]
+pub struct SpendNote<'info> {
+    #[account(
+        mut,
+        seeds = [pda::NOTE_POOL_SEED],
+        bump = note_pool.bump,
+    )]
+    pub note_pool: Box<Account<'info, NotePool>>,
+    
+    /// Creation fails if the nullifier was already published (double spend)
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + Nullifier::INIT_SPACE,
+        seeds = [pda::NULLIFIER_SEED, nullifier.as_ref()],
+        bump
+    )]
+    pub nullifier_record: Account<'info, Nullifier>,
+    
+    /// Groth16 verifying key of the note spend circuit
+    #[account(
+        seeds = [pda::NOTE_VERIFYING_KEY_SEED, &[groth16::NOTE_SPEND_CIRCUIT]],
+        bump = verifying_key.bump,
+    )]
+    pub verifying_key: Box<Account<'info, NoteVerifyingKey>>,
+    
+    /// CHECK: Any account can receive the funds; bound by the proof
+    #[account(mut)]
+    pub recipient: UncheckedAccount<'info>,
+    
+    /// Pays the nullifier rent (may be a relayer, unlinked to the depositor)
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    
+    /// CHECK: Global config PDA (may be uninitialized); read for the pause flags
+    #[account(
+        seeds = [pda::CONFIG_SEED],
+        bump
+    )]
+    pub config: UncheckedAccount<'info>,
+    
+    /// CHECK: Protocol stats PDA (may be uninitialized); counters updated when present
+    #[account(
+        mut,
+        seeds = [pda::STATS_SEED],
+        bump
+    )]
+    pub stats: UncheckedAccount<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(circuit: u8)]
+pub struct SetNoteVerifyingKey<'info> {
+    #[account(
+        seeds = [pda::CONFIG_SEED],
+        bump = config.bump,
+        has_one = authority
+    )]
+    pub config: Account<'info, Config>,
+    
+    /// Created once per circuit; the key can never be replaced
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + NoteVerifyingKey::INIT_SPACE,
+        seeds = [pda::NOTE_VERIFYING_KEY_SEED, &[circuit]],
+        bump
+    )]
+    pub verifying_key: Box<Account<'info, NoteVerifyingKey>>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(transfer_ref: [u8; 32])]
+pub struct RequestRefund<'info> {
+    #[account(
+        init,
+        payer = requester,
+        space = 8 + RefundRequest::INIT_SPACE,
+        seeds = [pda::REFUND_REQUEST_SEED, refunder_account.key().as_ref(), transfer_ref.as_ref()],
+        bump
+    )]
+    pub refund_request: Account<'info, RefundRequest>,
+    
+    #[account(
+        mut,
+        seeds = [pda::ENCRYPTED_ACCOUNT_SEED, requester_account.seed_owner.as_ref(), &requester_account.account_index.to_le_bytes()],
+        bump = requester_account.bump,
+        constraint = requester_account.owner == requester.key() @ ErrorCode::Unauthorized
+    )]
+    pub requester_account: Account<'info, EncryptedAccount>,
+    
+    #[account(
+        seeds = [pda::ENCRYPTED_ACCOUNT_SEED, refunder_account.seed_owner.as_ref(), &refunder_account.account_index.to_le_bytes()],
+        bump = refunder_account.bump,
+    )]
+    pub refunder_account: Account<'info, EncryptedAccount>,
+    
+    #[account(mut)]
+    pub requester: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct GrantRefund<'info> {
+    #[account(
+        mut,
+        seeds = [pda::REFUND_REQUEST_SEED, refunder_account.key().as_ref(), refund_request.transfer_ref.as_ref()],
+        bump = refund_request.bump,
+        has_one = requester,
+        has_one = requester_account,
+        has_one = refunder_account,
+        close = requester
+    )]
+    pub refund_request: Account<'info, RefundRequest>,
+    
+    #[account(
+        mut,
+        seeds = [pda::ENCRYPTED_ACCOUNT_SEED, requester_account.seed_owner.as_ref(), &requester_account.account_index.to_le_bytes()],
+        bump = requester_account.bump,
+    )]
+    pub requester_account: Account<'info, EncryptedAccount>,
+    
+    #[account(
+        mut,
+        seeds = [pda::ENCRYPTED_ACCOUNT_SEED, refunder_account.seed_owner.as_ref(), &refunder_account.account_index.to_le_bytes()],
+        bump = refunder_account.bump,
+        constraint = refunder_account.owner == refunder.key() @ ErrorCode::Unauthorized,
+        constraint = !refunder_account.frozen @ ErrorCode::AccountFrozen
+    )]
+    pub refunder_account: Account<'info, EncryptedAccount>,
+    
+    pub refunder: Signer<'info>,
+    
+    /// CHECK: Receives the request rent; bound by `has_one`
+    #[account(mut)]
+    pub requester: UncheckedAccount<'info>,
+    
+    /// CHECK: Global config PDA (may be uninitialized); read for the pause flags
+    #[account(
+        seeds = [pda::CONFIG_SEED],
+        bump
+    )]
+    pub config: UncheckedAccount<'info>,
+    
+    /// CHECK: Protocol stats PDA (may be uninitialized); counters updated when present
+    #[account(
+        mut,
+        seeds = [pda::STATS_SEED],
+        bump
+    )]
+    pub stats: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct DeclineRefund<'info> {
+    #[account(
+        mut,
+        seeds = [pda::REFUND_REQUEST_SEED, refunder_account.key().as_ref(), refund_request.transfer_ref.as_ref()],
+        bump = refund_request.bump,
+        has_one = requester,
+        has_one = refunder_account,
+        close = requester
+    )]
+    pub refund_request: Account<'info, RefundRequest>,
+    
+    #[account(
+        seeds = [pda::ENCRYPTED_ACCOUNT_SEED, refunder_account.seed_owner.as_ref(), &refunder_account.account_index.to_le_bytes()],
+        bump = refunder_account.bump,
+        constraint = refunder_account.owner == refunder.key() @ ErrorCode::Unauthorized
+    )]
+    pub refunder_account: Account<'info, EncryptedAccount>,
+    
+    pub refunder: Signer<'info>,
+    
+    /// CHECK: Receives the request rent; bound by `has_one`
+    #[account(mut)]
+    pub requester: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct CancelRefund<'info> {
+    #[account(
+        mut,
+        seeds = [pda::REFUND_REQUEST_SEED, refund_request.refunder_account.as_ref(), refund_request.transfer_ref.as_ref()],
+        bump = refund_request.bump,
+        has_one = requester,
+        close = requester
+    )]
+    pub refund_request: Account<'info, RefundRequest>,
+    
+    #[account(mut)]
+    pub requester: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct RegisterStealthMeta<'info> {
+    #[account(
+        init,
+        payer = owner,
+        space = 8 + StealthMeta::INIT_SPACE,
+        seeds = [pda::STEALTH_META_SEED, owner.key().as_ref()],
+        bump
+    )]
+    pub stealth_meta: Account<'info, StealthMeta>,
+    
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(ephemeral_key: [u8; 32])]
+pub struct AnnounceStealthPayment<'info> {
+    #[account(
+        init,
+        payer = sender,
+        space = 8 + StealthAnnouncement::INIT_SPACE,
+        seeds = [pda::STEALTH_ANNOUNCEMENT_SEED, ephemeral_key.as_ref()],
+        bump
+    )]
+    pub announcement: Account<'info, StealthAnnouncement>,
+    
+    /// CHECK: One-time destination derived off-chain; receives lamports only
+    #[account(mut)]
+    pub destination: UncheckedAccount<'info>,
+    
+    #[account(mut)]
+    pub sender: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SweepStealth<'info> {
+    #[account(
+        mut,
+        seeds = [pda::STEALTH_ANNOUNCEMENT_SEED, announcement.ephemeral_key.as_ref()],
+        bump = announcement.bump,
+        has_one = destination,
+        close = to
+    )]
+    pub announcement: Account<'info, StealthAnnouncement>,
+    
+    /// One-time key derived by the recipient
+    #[account(mut)]
+    pub destination: Signer<'info>,
+    
+    /// CHECK: Any account chosen by the recipient
+    #[account(mut)]
+    pub to: UncheckedAccount<'info>,
+    
+    /// CHECK: Global config PDA (may be uninitialized); read for the pause flags
+    #[account(
+        seeds = [pda::CONFIG_SEED],
+        bump
+    )]
+    pub config: UncheckedAccount<'info>,
+    
+    /// CHECK: Protocol stats PDA (may be uninitialized); counters updated when present
+    #[account(
+        mut,
+        seeds = [pda::STATS_SEED],
+        bump
+    )]
+    pub stats: UncheckedAccount<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct InitializeInbox<'info> {
+    #[account(
+        init,
+        payer = owner,
+        space = 8 + Inbox::INIT_SPACE,
+        seeds = [pda::INBOX_SEED, encrypted_account.key().as_ref()],
+        bump
+    )]
+    pub inbox: Box<Account<'info, Inbox>>,
+    
+    #[account(
+        mut,
+        seeds = [pda::ENCRYPTED_ACCOUNT_SEED, encrypted_account.seed_owner.as_ref(), &encrypted_account.account_index.to_le_bytes()],
+        bump = encrypted_account.bump,
+        has_one = owner
+    )]
+    pub encrypted_account: Account<'info, EncryptedAccount>,
+    
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct PruneInbox<'info> {
+    #[account(
+        mut,
+        seeds = [pda::INBOX_SEED, encrypted_account.key().as_ref()],
+        bump = inbox.bump,
+        has_one = encrypted_account
+    )]
+    pub inbox: Box<Account<'info, Inbox>>,
+    
+    #[account(
+        mut,
+        seeds = [pda::ENCRYPTED_ACCOUNT_SEED, encrypted_account.seed_owner.as_ref(), &encrypted_account.account_index.to_le_bytes()],
+        bump = encrypted_account.bump,
+        has_one = owner
+    )]
+    pub encrypted_account: Account<'info, EncryptedAccount>,
+    
+    pub owner: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct InitializeWithdrawalQueue<'info> {
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + WithdrawalQueue::INIT_SPACE,
+        seeds = [pda::WITHDRAWAL_QUEUE_SEED],
+        bump
+    )]
+    pub withdrawal_queue: Account<'info, WithdrawalQueue>,
+    
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct QueueWithdrawal<'info> {
+    #[account(
+        mut,
+        seeds = [pda::POOL_VAULT_SEED],
+        bump = pool_vault.bump,
+    )]
+    pub pool_vault: Account<'info, PoolVault>,
+    
+    #[account(
+        mut,
+        seeds = [pda::WITHDRAWAL_QUEUE_SEED],
+        bump = withdrawal_queue.bump,
+    )]
+    pub withdrawal_queue: Account<'info, WithdrawalQueue>,
+    
+    #[account(
+        init,
+        payer = owner,
+        space = 8 + QueuedWithdrawal::INIT_SPACE,
+        seeds = [pda::QUEUED_WITHDRAWAL_SEED, &withdrawal_queue.next_ticket.to_le_bytes()],
+        bump
+    )]
+    pub queued_withdrawal: Account<'info, QueuedWithdrawal>,
+    
+    #[account(
+        mut,
+        seeds = [pda::ENCRYPTED_ACCOUNT_SEED, encrypted_account.seed_owner.as_ref(), &encrypted_account.account_index.to_le_bytes()],
+        bump = encrypted_account.bump,
+        has_one = owner,
+        constraint = !encrypted_account.frozen @ ErrorCode::AccountFrozen
+    )]
+    pub encrypted_account: Account<'info, EncryptedAccount>,
+    
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    
+    /// CHECK: Global config PDA (may be uninitialized); read for the pause flags
+    #[account(
+        seeds = [pda::CONFIG_SEED],
+        bump
+    )]
+    pub config: UncheckedAccount<'info>,
+    
+    /// CHECK: Protocol stats PDA (may be uninitialized); counters updated when present
+    #[account(
+        mut,
+        seeds = [pda::STATS_SEED],
+        bump
+    )]
+    pub stats: UncheckedAccount<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ProcessWithdrawalBatch<'info> {
+    #[account(
+        mut,
+        seeds = [pda::POOL_VAULT_SEED],
+        bump = pool_vault.bump,
+    )]
+    pub pool_vault: Account<'info, PoolVault>,
+    
+    #[account(
+        mut,
+        seeds = [pda::WITHDRAWAL_QUEUE_SEED],
+        bump = withdrawal_queue.bump,
+    )]
+    pub withdrawal_queue: Account<'info, WithdrawalQueue>,
+    
+    /// CHECK: Global config PDA (may be uninitialized); read for the pause flags
+    #[account(
+        seeds = [pda::CONFIG_SEED],
+        bump
+    )]
+    pub config: UncheckedAccount<'info>,
+    
+    /// CHECK: Protocol stats PDA (may be uninitialized); counters updated when present
+    #[account(
+        mut,
+        seeds = [pda::STATS_SEED],
+        bump
+    )]
+    pub stats: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ConfidentialDecoyTransfer<'info> {
+    #[account(
+        mut,
+        seeds = [pda::ENCRYPTED_ACCOUNT_SEED, sender_account.seed_owner.as_ref(), &sender_account.account_index.to_le_bytes()],
+        bump = sender_account.bump,
+        constraint = !sender_account.frozen @ ErrorCode::AccountFrozen
+    )]
+    pub sender_account: Account<'info, EncryptedAccount>,
+    
+    pub sender: Signer<'info>,
+    
+    /// CHECK: Global config PDA (may be uninitialized); read for the pause flags
+    #[account(
+        seeds = [pda::CONFIG_SEED],
+        bump
+    )]
+    pub config: UncheckedAccount<'info>,
+    
+    /// CHECK: Protocol stats PDA (may be uninitialized); counters updated when present
+    #[account(
+        mut,
+        seeds = [pda::STATS_SEED],
+        bump
+    )]
+    pub stats: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct RelayedConfidentialTransfer<'info> {
+    #[account(
+        mut,
+        seeds = [pda::ENCRYPTED_ACCOUNT_SEED, sender_account.seed_owner.as_ref(), &sender_account.account_index.to_le_bytes()],
+        bump = sender_account.bump,
+        constraint = !sender_account.frozen @ ErrorCode::AccountFrozen
+    )]
+    pub sender_account: Account<'info, EncryptedAccount>,
+    
+    #[account(
+        mut,
+        seeds = [pda::ENCRYPTED_ACCOUNT_SEED, recipient_account.seed_owner.as_ref(), &recipient_account.account_index.to_le_bytes()],
+        bump = recipient_account.bump,
+    )]
+    pub recipient_account: Account<'info, EncryptedAccount>,
+    
+    /// Submits the transaction and pays the fees
+    #[account(mut)]
+    pub relayer: Signer<'info>,
+    
+    /// CHECK: Instructions sysvar (address-checked), used to read the Ed25519 instruction
+    #[account(address = solana_sdk_ids::sysvar::instructions::ID)]
+    pub instructions_sysvar: UncheckedAccount<'info>,
+    
+    /// CHECK: Global config PDA (may be uninitialized); read for pause and feature flags
+    #[account(
+        seeds = [pda::CONFIG_SEED],
+        bump
+    )]
+    pub config: UncheckedAccount<'info>,
+    
+    /// CHECK: Protocol stats PDA (may be uninitialized); counters updated when present
+    #[account(
+        mut,
+        seeds = [pda::STATS_SEED],
+        bump
+    )]
+    pub stats: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(root: [u8; 32], nullifier: [u8; 32])]
+pub struct SpendNoteAssociated<'info> {
+    #[account(
+        mut,
+        seeds = [pda::NOTE_POOL_SEED],
+        bump = note_pool.bump,
+    )]
+    pub note_pool: Box<Account<'info, NotePool>>,
+    
+    /// Holds the current association set root
+    #[account(
+        seeds = [pda::CONFIG_SEED],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, Config>,
+    
+    /// CHECK: Protocol stats PDA (may be uninitialized); counters updated when present
+    #[account(
+        mut,
+        seeds = [pda::STATS_SEED],
+        bump
+    )]
+    pub stats: UncheckedAccount<'info>,
+    
+    /// Shared with `spend_note`: a note can only be spent once either way
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + Nullifier::INIT_SPACE,
+        seeds = [pda::NULLIFIER_SEED, nullifier.as_ref()],
+        bump
+    )]
+    pub nullifier_record: Account<'info, Nullifier>,
+    
+    /// Groth16 verifying key of the association spend circuit
+    #[account(
+        seeds = [pda::NOTE_VERIFYING_KEY_SEED, &[groth16::ASSOCIATION_SPEND_CIRCUIT]],
+        bump = verifying_key.bump,
+    )]
+    pub verifying_key: Box<Account<'info, NoteVerifyingKey>>,
+    
+    /// CHECK: Any account can receive the funds; bound by the proof
+    #[account(mut)]
+    pub recipient: UncheckedAccount<'info>,
+    
+    /// Pays the nullifier rent (may be a relayer, unlinked to the depositor)
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RegisterViewingKey<'info> {
+    #[account(
+        init,
+        payer = owner,
+        space = 8 + ViewingKey::INIT_SPACE,
+        seeds = [pda::VIEWING_KEY_SEED, encrypted_account.key().as_ref()],
+        bump
+    )]
+    pub viewing_key: Account<'info, ViewingKey>,
+    
+    #[account(
+        mut,
+        seeds = [pda::ENCRYPTED_ACCOUNT_SEED, encrypted_account.seed_owner.as_ref(), &encrypted_account.account_index.to_le_bytes()],
+        bump = encrypted_account.bump,
+        has_one = owner
+    )]
+    pub encrypted_account: Account<'info, EncryptedAccount>,
+    
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateViewingKey<'info> {
+    #[account(
+        mut,
+        seeds = [pda::VIEWING_KEY_SEED, encrypted_account.key().as_ref()],
+        bump = viewing_key.bump,
+        has_one = encrypted_account
+    )]
+    pub viewing_key: Account<'info, ViewingKey>,
+    
+    #[account(
+        mut,
+        seeds = [pda::ENCRYPTED_ACCOUNT_SEED, encrypted_account.seed_owner.as_ref(), &encrypted_account.account_index.to_le_bytes()],
+        bump = encrypted_account.bump,
+        has_one = owner
+    )]
+    pub encrypted_account: Account<'info, EncryptedAccount>,
+    
+    pub owner: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct RevokeViewingKey<'info> {
+    #[account(
+        mut,
+        close = owner,
+        seeds = [pda::VIEWING_KEY_SEED, encrypted_account.key().as_ref()],
+        bump = viewing_key.bump,
+        has_one = encrypted_account
+    )]
+    pub viewing_key: Account<'info, ViewingKey>,
+    
+    #[account(
+        seeds = [pda::ENCRYPTED_ACCOUNT_SEED, encrypted_account.seed_owner.as_ref(), &encrypted_account.account_index.to_le_bytes()],
+        bump = encrypted_account.bump,
+        has_one = owner
+    )]
+    pub encrypted_account: Account<'info, EncryptedAccount>,
+    
+    #[account(mut)]
+    pub owner: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct RecordAttestation<'info> {
+    #[account(
+        init,
+        payer = oracle,
+        space = 8 + SanctionsAttestation::INIT_SPACE,
+        seeds = [pda::SANCTIONS_ATTESTATION_SEED, oracle.key().as_ref(), destination.key().as_ref()],
+        bump
+    )]
+    pub attestation: Account<'info, SanctionsAttestation>,
+    
+    /// CHECK: Screened address; only used as a PDA seed
+    pub destination: UncheckedAccount<'info>,
+    
+    #[account(mut)]
+    pub oracle: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateAttestation<'info> {
+    #[account(
+        mut,
+        seeds = [pda::SANCTIONS_ATTESTATION_SEED, oracle.key().as_ref(), attestation.destination.as_ref()],
+        bump = attestation.bump,
+        has_one = oracle
+    )]
+    pub attestation: Account<'info, SanctionsAttestation>,
+    
+    pub oracle: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct CloseAttestation<'info> {
+    #[account(
+        mut,
+        close = oracle,
+        seeds = [pda::SANCTIONS_ATTESTATION_SEED, oracle.key().as_ref(), attestation.destination.as_ref()],
+        bump = attestation.bump,
+        has_one = oracle
+    )]
+    pub attestation: Account<'info, SanctionsAttestation>,
+    
+    #[account(mut)]
+    pub oracle: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct InitializeTransferAccumulator<'info> {
+    #[account(
+        init,
+        payer = owner,
+        space = 8 + TransferAccumulator::INIT_SPACE,
+        seeds = [pda::TRANSFER_ACCUMULATOR_SEED, encrypted_account.key().as_ref()],
+        bump
+    )]
+    pub accumulator: Account<'info, TransferAccumulator>,
+    
+    #[account(
+        mut,
+        seeds = [pda::ENCRYPTED_ACCOUNT_SEED, encrypted_account.seed_owner.as_ref(), &encrypted_account.account_index.to_le_bytes()],
+        bump = encrypted_account.bump,
+        has_one = owner
+    )]
+    pub encrypted_account: Account<'info, EncryptedAccount>,
+    
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(case_id: [u8; 32])]
+pub struct RequestDisclosure<'info> {
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + DisclosureRequest::INIT_SPACE,
+        seeds = [pda::DISCLOSURE_REQUEST_SEED, encrypted_account.key().as_ref(), case_id.as_ref()],
+        bump
+    )]
+    pub disclosure_request: Account<'info, DisclosureRequest>,
+    
+    #[account(
+        seeds = [pda::ENCRYPTED_ACCOUNT_SEED, encrypted_account.seed_owner.as_ref(), &encrypted_account.account_index.to_le_bytes()],
+        bump = encrypted_account.bump,
+    )]
+    pub encrypted_account: Account<'info, EncryptedAccount>,
+    
+    #[account(
+        seeds = [pda::CONFIG_SEED],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, Config>,
+    
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct AnswerDisclosure<'info> {
+    #[account(
+        mut,
+        seeds = [pda::DISCLOSURE_REQUEST_SEED, encrypted_account.key().as_ref(), disclosure_request.case_id.as_ref()],
+        bump = disclosure_request.bump,
+        has_one = encrypted_account
+    )]
+    pub disclosure_request: Account<'info, DisclosureRequest>,
+    
+    #[account(
+        mut,
+        seeds = [pda::ENCRYPTED_ACCOUNT_SEED, encrypted_account.seed_owner.as_ref(), &encrypted_account.account_index.to_le_bytes()],
+        bump = encrypted_account.bump,
+        has_one = owner
+    )]
+    pub encrypted_account: Account<'info, EncryptedAccount>,
+    
+    pub owner: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct EnforceDisclosure<'info> {
+    #[account(
+        mut,
+        seeds = [pda::DISCLOSURE_REQUEST_SEED, encrypted_account.key().as_ref(), disclosure_request.case_id.as_ref()],
+        bump = disclosure_request.bump,
+        has_one = encrypted_account
+    )]
+    pub disclosure_request: Account<'info, DisclosureRequest>,
+    
+    #[account(
+        mut,
+        seeds = [pda::ENCRYPTED_ACCOUNT_SEED, encrypted_account.seed_owner.as_ref(), &encrypted_account.account_index.to_le_bytes()],
+        bump = encrypted_account.bump,
+    )]
+    pub encrypted_account: Account<'info, EncryptedAccount>,
+}
+
+#[derive(Accounts)]
+pub struct CloseDisclosure<'info> {
+    #[account(
+        mut,
+        close = payer,
+        seeds = [pda::DISCLOSURE_REQUEST_SEED, disclosure_request.encrypted_account.as_ref(), disclosure_request.case_id.as_ref()],
+        bump = disclosure_request.bump,
+        has_one = payer
+    )]
+    pub disclosure_request: Account<'info, DisclosureRequest>,
+    
+    #[account(mut)]
+    pub payer: Signer<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(report_id: u64)]
+pub struct ExportDisclosureReport<'info> {
+    #[account(
+        init,
+        payer = owner,
+        space = 8 + DisclosureReport::INIT_SPACE,
+        seeds = [pda::DISCLOSURE_REPORT_SEED, encrypted_account.key().as_ref(), &report_id.to_le_bytes()],
+        bump
+    )]
+    pub report: Account<'info, DisclosureReport>,
+    
+    #[account(
+        mut,
+        seeds = [pda::ENCRYPTED_ACCOUNT_SEED, encrypted_account.seed_owner.as_ref(), &encrypted_account.account_index.to_le_bytes()],
+        bump = encrypted_account.bump,
+        has_one = owner
+    )]
+    pub encrypted_account: Account<'info, EncryptedAccount>,
+    
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct CloseDisclosureReport<'info> {
+    #[account(
+        mut,
+        close = owner,
+        seeds = [pda::DISCLOSURE_REPORT_SEED, report.encrypted_account.as_ref(), &report.report_id.to_le_bytes()],
+        bump = report.bump,
+        has_one = owner
+    )]
+    pub report: Account<'info, DisclosureReport>,
+    
+    #[account(mut)]
+    pub owner: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ConfidentialTransferMulti<'info> {
+    #[account(
+        mut,
+        seeds = [pda::ENCRYPTED_ACCOUNT_SEED, sender_account.seed_owner.as_ref(), &sender_account.account_index.to_le_bytes()],
+        bump = sender_account.bump,
+        constraint = !sender_account.frozen @ ErrorCode::AccountFrozen
+    )]
+    pub sender_account: Account<'info, EncryptedAccount>,
+    
+    pub sender: Signer<'info>,
+    
+    /// CHECK: Global config PDA (may be uninitialized); read for the pause flags
+    #[account(
+        seeds = [pda::CONFIG_SEED],
+        bump
+    )]
+    pub config: UncheckedAccount<'info>,
+    
+    /// CHECK: Protocol stats PDA (may be uninitialized); counters updated when present
+    #[account(
+        mut,
+        seeds = [pda::STATS_SEED],
+        bump
+    )]
+    pub stats: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ConfidentialTransferBatch<'info> {
+    /// Owner of every sender account in the batch
+    pub owner: Signer<'info>,
+    
+    /// CHECK: Global config PDA (may be uninitialized); read for the pause flags
+    #[account(
+        seeds = [pda::CONFIG_SEED],
+        bump
+    )]
+    pub config: UncheckedAccount<'info>,
+    
+    /// CHECK: Protocol stats PDA (may be uninitialized); counters updated when present
+    #[account(
+        mut,
+        seeds = [pda::STATS_SEED],
+        bump
+    )]
+    pub stats: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(transfer_id: u64)]
+pub struct InitiateTransfer<'info> {
+    #[account(
+        init,
+        payer = sender,
+        space = 8 + PendingTransfer::INIT_SPACE,
+        seeds = [pda::PENDING_TRANSFER_SEED, sender_account.key().as_ref(), &transfer_id.to_le_bytes()],
+        bump
+    )]
+    pub pending_transfer: Account<'info, PendingTransfer>,
+    
+    #[account(
+        mut,
+        seeds = [pda::ENCRYPTED_ACCOUNT_SEED, sender_account.seed_owner.as_ref(), &sender_account.account_index.to_le_bytes()],
+        bump = sender_account.bump,
+        constraint = sender_account.owner == sender.key() @ ErrorCode::Unauthorized,
+        constraint = !sender_account.frozen @ ErrorCode::AccountFrozen
+    )]
+    pub sender_account: Account<'info, EncryptedAccount>,
+    
+    #[account(
+        seeds = [pda::ENCRYPTED_ACCOUNT_SEED, recipient_account.seed_owner.as_ref(), &recipient_account.account_index.to_le_bytes()],
+        bump = recipient_account.bump,
+    )]
+    pub recipient_account: Account<'info, EncryptedAccount>,
+    
+    #[account(mut)]
+    pub sender: Signer<'info>,
+    
+    /// CHECK: Global config PDA (may be uninitialized); read for the pause flags
+    #[account(
+        seeds = [pda::CONFIG_SEED],
+        bump
+    )]
+    pub config: UncheckedAccount<'info>,
+    
+    /// CHECK: Protocol stats PDA (may be uninitialized); counters updated when present
+    #[account(
+        mut,
+        seeds = [pda::STATS_SEED],
+        bump
+    )]
+    pub stats: UncheckedAccount<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimTransfer<'info> {
+    #[account(
+        mut,
+        seeds = [pda::PENDING_TRANSFER_SEED, pending_transfer.sender_account.as_ref(), &pending_transfer.transfer_id.to_le_bytes()],
+        bump = pending_transfer.bump,
+        has_one = sender,
+        has_one = recipient_account,
+        close = sender
+    )]
+    pub pending_transfer: Account<'info, PendingTransfer>,
+    
+    #[account(
+        mut,
+        seeds = [pda::ENCRYPTED_ACCOUNT_SEED, recipient_account.seed_owner.as_ref(), &recipient_account.account_index.to_le_bytes()],
+        bump = recipient_account.bump,
+        constraint = recipient_account.owner == recipient.key() @ ErrorCode::Unauthorized
+    )]
+    pub recipient_account: Account<'info, EncryptedAccount>,
+    
+    pub recipient: Signer<'info>,
+    
+    /// CHECK: Receives the escrow rent; bound by `has_one`
+    #[account(mut)]
+    pub sender: UncheckedAccount<'info>,
+    
+    /// CHECK: Global config PDA (may be uninitialized); read for the pause flags
+    #[account(
+        seeds = [pda::CONFIG_SEED],
+        bump
+    )]
+    pub config: UncheckedAccount<'info>,
+    
+    /// CHECK: Protocol stats PDA (may be uninitialized); counters updated when present
+    #[account(
+        mut,
+        seeds = [pda::STATS_SEED],
+        bump
+    )]
+    pub stats: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct RefundTransfer<'info> {
+    #[account(
+        mut,
+        seeds = [pda::PENDING_TRANSFER_SEED, sender_account.key().as_ref(), &pending_transfer.transfer_id.to_le_bytes()],
+        bump = pending_transfer.bump,
+        has_one = sender,
+        has_one = sender_account,
+        close = sender
+    )]
+    pub pending_transfer: Account<'info, PendingTransfer>,
+    
+    #[account(
+        mut,
+        seeds = [pda::ENCRYPTED_ACCOUNT_SEED, sender_account.seed_owner.as_ref(), &sender_account.account_index.to_le_bytes()],
+        bump = sender_account.bump,
+        constraint = sender_account.owner == sender.key() @ ErrorCode::Unauthorized
+    )]
+    pub sender_account: Account<'info, EncryptedAccount>,
+    
+    #[account(mut)]
+    pub sender: Signer<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(invoice_id: u64)]
+pub struct CreateInvoice<'info> {
+    #[account(
+        init,
+        payer = merchant,
+        space = 8 + Invoice::INIT_SPACE,
+        seeds = [pda::INVOICE_SEED, recipient_account.key().as_ref(), &invoice_id.to_le_bytes()],
+        bump
+    )]
+    pub invoice: Account<'info, Invoice>,
+    
+    #[account(
+        mut,
+        seeds = [pda::ENCRYPTED_ACCOUNT_SEED, recipient_account.seed_owner.as_ref(), &recipient_account.account_index.to_le_bytes()],
+        bump = recipient_account.bump,
+        constraint = recipient_account.owner == merchant.key() @ ErrorCode::Unauthorized
+    )]
+    pub recipient_account: Account<'info, EncryptedAccount>,
+    
+    #[account(mut)]
+    pub merchant: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct PayInvoice<'info> {
+    #[account(
+        mut,
+        seeds = [pda::INVOICE_SEED, recipient_account.key().as_ref(), &invoice.invoice_id.to_le_bytes()],
+        bump = invoice.bump,
+        has_one = recipient_account,
+        constraint = !invoice.paid @ ErrorCode::InvoiceAlreadyPaid
+    )]
+    pub invoice: Account<'info, Invoice>,
+    
+    #[account(
+        mut,
+        seeds = [pda::ENCRYPTED_ACCOUNT_SEED, payer_account.seed_owner.as_ref(), &payer_account.account_index.to_le_bytes()],
+        bump = payer_account.bump,
+        constraint = payer_account.owner == payer.key() @ ErrorCode::Unauthorized,
+        constraint = !payer_account.frozen @ ErrorCode::AccountFrozen
+    )]
+    pub payer_account: Account<'info, EncryptedAccount>,
+    
+    #[account(
+        mut,
+        seeds = [pda::ENCRYPTED_ACCOUNT_SEED, recipient_account.seed_owner.as_ref(), &recipient_account.account_index.to_le_bytes()],
+        bump = recipient_account.bump,
+    )]
+    pub recipient_account: Account<'info, EncryptedAccount>,
+    
+    pub payer: Signer<'info>,
+    
+    /// CHECK: Global config PDA (may be uninitialized); read for the pause flags
+    #[account(
+        seeds = [pda::CONFIG_SEED],
+        bump
+    )]
+    pub config: UncheckedAccount<'info>,
+    
+    /// CHECK: Protocol stats PDA (may be uninitialized); counters updated when present
+    #[account(
+        mut,
+        seeds = [pda::STATS_SEED],
+        bump
+    )]
+    pub stats: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct CloseInvoice<'info> {
+    #[account(
+        mut,
+        close = merchant,
+        seeds = [pda::INVOICE_SEED, invoice.recipient_account.as_ref(), &invoice.invoice_id.to_le_bytes()],
+        bump = invoice.bump,
+        has_one = merchant
+    )]
+    pub invoice: Account<'info, Invoice>,
+    
+    #[account(mut)]
+    pub merchant: Signer<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(claim_key: Pubkey)]
+pub struct CreateClaim<'info> {
+    #[account(
+        init,
+        payer = creator,
+        space = 8 + ClaimLink::INIT_SPACE,
+        seeds = [pda::CLAIM_LINK_SEED, claim_key.as_ref()],
+        bump
+    )]
+    pub claim_link: Account<'info, ClaimLink>,
+    
+    #[account(
+        mut,
+        seeds = [pda::ENCRYPTED_ACCOUNT_SEED, creator_account.seed_owner.as_ref(), &creator_account.account_index.to_le_bytes()],
+        bump = creator_account.bump,
+        constraint = creator_account.owner == creator.key() @ ErrorCode::Unauthorized,
+        constraint = !creator_account.frozen @ ErrorCode::AccountFrozen
+    )]
+    pub creator_account: Account<'info, EncryptedAccount>,
+    
+    #[account(mut)]
+    pub creator: Signer<'info>,
+    
+    /// CHECK: Global config PDA (may be uninitialized); read for the pause flags
+    #[account(
+        seeds = [pda::CONFIG_SEED],
+        bump
+    )]
+    pub config: UncheckedAccount<'info>,
+    
+    /// CHECK: Protocol stats PDA (may be uninitialized); counters updated when present
+    #[account(
+        mut,
+        seeds = [pda::STATS_SEED],
+        bump
+    )]
+    pub stats: UncheckedAccount<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RedeemClaim<'info> {
+    #[account(
+        mut,
+        seeds = [pda::CLAIM_LINK_SEED, claim_link.claim_key.as_ref()],
+        bump = claim_link.bump,
+        has_one = creator,
+        has_one = claim_key @ ErrorCode::InvalidClaimSecret,
+        close = creator
+    )]
+    pub claim_link: Account<'info, ClaimLink>,
+    
+    #[account(
+        mut,
+        seeds = [pda::ENCRYPTED_ACCOUNT_SEED, recipient_account.seed_owner.as_ref(), &recipient_account.account_index.to_le_bytes()],
+        bump = recipient_account.bump,
+        constraint = recipient_account.owner == redeemer.key() @ ErrorCode::Unauthorized
+    )]
+    pub recipient_account: Account<'info, EncryptedAccount>,
+    
+    pub redeemer: Signer<'info>,
+    
+    /// Keypair seeded by the link secret; its signature binds the recipient
+    pub claim_key: Signer<'info>,
+    
+    /// CHECK: Receives the claim rent; bound by `has_one`
+    #[account(mut)]
+    pub creator: UncheckedAccount<'info>,
+    
+    /// CHECK: Global config PDA (may be uninitialized); read for the pause flags
+    #[account(
+        seeds = [pda::CONFIG_SEED],
+        bump
+    )]
+    pub config: UncheckedAccount<'info>,
+    
+    /// CHECK: Protocol stats PDA (may be uninitialized); counters updated when present
+    #[account(
+        mut,
+        seeds = [pda::STATS_SEED],
+        bump
+    )]
+    pub stats: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ReclaimClaim<'info> {
+    #[account(
+        mut,
+        seeds = [pda::CLAIM_LINK_SEED, claim_link.claim_key.as_ref()],
+        bump = claim_link.bump,
+        has_one = creator,
+        has_one = creator_account,
+        close = creator
+    )]
+    pub claim_link: Account<'info, ClaimLink>,
+    
+    #[account(
+        mut,
+        seeds = [pda::ENCRYPTED_ACCOUNT_SEED, creator_account.seed_owner.as_ref(), &creator_account.account_index.to_le_bytes()],
+        bump = creator_account.bump,
+        constraint = creator_account.owner == creator.key() @ ErrorCode::Unauthorized
+    )]
+    pub creator_account: Account<'info, EncryptedAccount>,
+    
+    #[account(mut)]
+    pub creator: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct DepositSOLFor<'info> {
+    #[account(
+        mut,
+        seeds = [pda::ENCRYPTED_ACCOUNT_SEED, encrypted_account.seed_owner.as_ref(), &encrypted_account.account_index.to_le_bytes()],
+        bump = encrypted_account.bump,
+    )]
+    pub encrypted_account: Account<'info, EncryptedAccount>,
+    
+    #[account(
+        mut,
+        seeds = [pda::SOL_ESCROW_SEED, encrypted_account.seed_owner.as_ref(), &encrypted_account.account_index.to_le_bytes()],
+        bump = sol_escrow.bump,
+        constraint = sol_escrow.owner == encrypted_account.owner @ ErrorCode::Unauthorized
+    )]
+    pub sol_escrow: Account<'info, SolEscrow>,
+    
+    /// Funds the deposit (employer, faucet, ...)
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    
+    /// CHECK: Global config PDA (may be uninitialized); read for the deny list and deposit cap
+    #[account(
+        seeds = [pda::CONFIG_SEED],
+        bump
+    )]
+    pub config: UncheckedAccount<'info>,
+    
+    /// Owner's inbox; when passed, a notice (with the memo) is appended
+    #[account(
+        mut,
+        seeds = [pda::INBOX_SEED, encrypted_account.key().as_ref()],
+        bump = recipient_inbox.bump,
+    )]
+    pub recipient_inbox: Option<Box<Account<'info, Inbox>>>,
+    
+    /// CHECK: Protocol stats PDA (may be uninitialized); counters updated when present
+    #[account(
+        mut,
+        seeds = [pda::STATS_SEED],
+        bump
+    )]
+    pub stats: UncheckedAccount<'info>,
+    
+    /// Groth16 verifying key of the exclusion circuit; required while a deny list is set
+    #[account(
+        seeds = [pda::NOTE_VERIFYING_KEY_SEED, &[groth16::EXCLUSION_CIRCUIT]],
+        bump = exclusion_verifying_key.bump,
+    )]
+    pub exclusion_verifying_key: Option<Box<Account<'info, NoteVerifyingKey>>>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct Sweep<'info> {
+    #[account(
+        mut,
+        seeds = [pda::ENCRYPTED_ACCOUNT_SEED, encrypted_account.seed_owner.as_ref(), &encrypted_account.account_index.to_le_bytes()],
+        bump = encrypted_account.bump,
+        has_one = owner,
+        constraint = !encrypted_account.frozen @ ErrorCode::AccountFrozen
+    )]
+    pub encrypted_account: Account<'info, EncryptedAccount>,
+    
+    #[account(
+        mut,
+        seeds = [pda::SOL_ESCROW_SEED, encrypted_account.seed_owner.as_ref(), &encrypted_account.account_index.to_le_bytes()],
+        bump = sol_escrow.bump,
+        has_one = owner
+    )]
+    pub sol_escrow: Account<'info, SolEscrow>,
+    
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    
+    /// CHECK: Global config PDA (may be uninitialized); read for compliance policies
+    #[account(
+        seeds = [pda::CONFIG_SEED],
+        bump
+    )]
+    pub config: UncheckedAccount<'info>,
+    
+    /// CHECK: Protocol stats PDA (may be uninitialized); counters updated when present
+    #[account(
+        mut,
+        seeds = [pda::STATS_SEED],
+        bump
+    )]
+    pub stats: UncheckedAccount<'info>,
+    
+    /// Oracle attestation for the owner (required when an oracle is configured)
+    pub sanctions_attestation: Option<Account<'info, SanctionsAttestation>>,
+    
+    /// Groth16 verifying key of the exclusion circuit; required while a deny list is set
+    #[account(
+        seeds = [pda::NOTE_VERIFYING_KEY_SEED, &[groth16::EXCLUSION_CIRCUIT]],
+        bump = exclusion_verifying_key.bump,
+    )]
+    pub exclusion_verifying_key: Option<Box<Account<'info, NoteVerifyingKey>>>,
+}
+
+#[derive(Accounts)]
+pub struct MergeAccounts<'info> {
+    /// Account being emptied into the target
+    #[account(
+        mut,
+        seeds = [pda::ENCRYPTED_ACCOUNT_SEED, source_account.seed_owner.as_ref(), &source_account.account_index.to_le_bytes()],
+        bump = source_account.bump,
+        has_one = owner,
+        constraint = !source_account.frozen @ ErrorCode::AccountFrozen
+    )]
+    pub source_account: Account<'info, EncryptedAccount>,
+    
+    #[account(
+        mut,
+        seeds = [pda::ENCRYPTED_ACCOUNT_SEED, target_account.seed_owner.as_ref(), &target_account.account_index.to_le_bytes()],
+        bump = target_account.bump,
+        has_one = owner
+    )]
+    pub target_account: Account<'info, EncryptedAccount>,
+    
+    pub owner: Signer<'info>,
+    
+    /// CHECK: Global config PDA (may be uninitialized); read for the pause flags
+    #[account(
+        seeds = [pda::CONFIG_SEED],
+        bump
+    )]
+    pub config: UncheckedAccount<'info>,
+    
+    /// CHECK: Protocol stats PDA (may be uninitialized); counters updated when present
+    #[account(
+        mut,
+        seeds = [pda::STATS_SEED],
+        bump
+    )]
+    pub stats: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SplitAccount<'info> {
+    #[account(
+        mut,
+        seeds = [pda::ENCRYPTED_ACCOUNT_SEED, source_account.seed_owner.as_ref(), &source_account.account_index.to_le_bytes()],
+        bump = source_account.bump,
+        has_one = owner,
+        constraint = !source_account.frozen @ ErrorCode::AccountFrozen
+    )]
+    pub source_account: Account<'info, EncryptedAccount>,
+    
+    /// Sub-account receiving the split-off amount
+    #[account(
+        mut,
+        seeds = [pda::ENCRYPTED_ACCOUNT_SEED, target_account.seed_owner.as_ref(), &target_account.account_index.to_le_bytes()],
+        bump = target_account.bump,
+        has_one = owner
+    )]
+    pub target_account: Account<'info, EncryptedAccount>,
+    
+    pub owner: Signer<'info>,
+    
+    /// CHECK: Global config PDA (may be uninitialized); read for the pause flags
+    #[account(
+        seeds = [pda::CONFIG_SEED],
+        bump
+    )]
+    pub config: UncheckedAccount<'info>,
+    
+    /// CHECK: Protocol stats PDA (may be uninitialized); counters updated when present
+    #[account(
+        mut,
+        seeds = [pda::STATS_SEED],
+        bump
+    )]
+    pub stats: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(stream_id: u64)]
+pub struct CreateStream<'info> {
+    #[account(
+        init,
+        payer = sender,
+        space = 8 + PaymentStream::INIT_SPACE,
+        seeds = [pda::STREAM_SEED, sender_account.key().as_ref(), &stream_id.to_le_bytes()],
+        bump
+    )]
+    pub stream: Account<'info, PaymentStream>,
+    
+    #[account(
+        mut,
+        seeds = [pda::ENCRYPTED_ACCOUNT_SEED, sender_account.seed_owner.as_ref(), &sender_account.account_index.to_le_bytes()],
+        bump = sender_account.bump,
+        constraint = sender_account.owner == sender.key() @ ErrorCode::Unauthorized,
+        constraint = !sender_account.frozen @ ErrorCode::AccountFrozen
+    )]
+    pub sender_account: Account<'info, EncryptedAccount>,
+    
+    #[account(
+        seeds = [pda::ENCRYPTED_ACCOUNT_SEED, recipient_account.seed_owner.as_ref(), &recipient_account.account_index.to_le_bytes()],
+        bump = recipient_account.bump,
+    )]
+    pub recipient_account: Account<'info, EncryptedAccount>,
+    
+    #[account(mut)]
+    pub sender: Signer<'info>,
+    
+    /// CHECK: Global config PDA (may be uninitialized); read for the pause flags
+    #[account(
+        seeds = [pda::CONFIG_SEED],
+        bump
+    )]
+    pub config: UncheckedAccount<'info>,
+    
+    /// CHECK: Protocol stats PDA (may be uninitialized); counters updated when present
+    #[account(
+        mut,
+        seeds = [pda::STATS_SEED],
+        bump
+    )]
+    pub stats: UncheckedAccount<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct WithdrawStream<'info> {
+    #[account(
+        mut,
+        seeds = [pda::STREAM_SEED, stream.sender_account.as_ref(), &stream.stream_id.to_le_bytes()],
+        bump = stream.bump,
+        has_one = recipient_account
+    )]
+    pub stream: Account<'info, PaymentStream>,
+    
+    #[account(
+        mut,
+        seeds = [pda::ENCRYPTED_ACCOUNT_SEED, recipient_account.seed_owner.as_ref(), &recipient_account.account_index.to_le_bytes()],
+        bump = recipient_account.bump,
+        constraint = recipient_account.owner == recipient.key() @ ErrorCode::Unauthorized
+    )]
+    pub recipient_account: Account<'info, EncryptedAccount>,
+    
+    pub recipient: Signer<'info>,
+    
+    /// CHECK: Global config PDA (may be uninitialized); read for the pause flags
+    #[account(
+        seeds = [pda::CONFIG_SEED],
+        bump
+    )]
+    pub config: UncheckedAccount<'info>,
+    
+    /// CHECK: Protocol stats PDA (may be uninitialized); counters updated when present
+    #[account(
+        mut,
+        seeds = [pda::STATS_SEED],
+        bump
+    )]
+    pub stats: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct CancelStream<'info> {
+    #[account(
+        mut,
+        seeds = [pda::STREAM_SEED, stream.sender_account.as_ref(), &stream.stream_id.to_le_bytes()],
+        bump = stream.bump,
+        has_one = sender
+    )]
+    pub stream: Account<'info, PaymentStream>,
+    
+    pub sender: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct CloseStream<'info> {
+    #[account(
+        mut,
+        close = sender,
+        seeds = [pda::STREAM_SEED, sender_account.key().as_ref(), &stream.stream_id.to_le_bytes()],
+        bump = stream.bump,
+        has_one = sender,
+        has_one = sender_account
+    )]
+    pub stream: Account<'info, PaymentStream>,
+    
+    #[account(
+        mut,
+        seeds = [pda::ENCRYPTED_ACCOUNT_SEED, sender_account.seed_owner.as_ref(), &sender_account.account_index.to_le_bytes()],
+        bump = sender_account.bump,
+        constraint = sender_account.owner == sender.key() @ ErrorCode::Unauthorized
+    )]
+    pub sender_account: Account<'info, EncryptedAccount>,
+    
+    #[account(mut)]
+    pub sender: Signer<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(vesting_id: u64)]
+pub struct CreateVesting<'info> {
+    #[account(
+        init,
+        payer = grantor,
+        space = 8 + Vesting::INIT_SPACE,
+        seeds = [pda::VESTING_SEED, grantor_account.key().as_ref(), &vesting_id.to_le_bytes()],
+        bump
+    )]
+    pub vesting: Account<'info, Vesting>,
+    
+    #[account(
+        mut,
+        seeds = [pda::ENCRYPTED_ACCOUNT_SEED, grantor_account.seed_owner.as_ref(), &grantor_account.account_index.to_le_bytes()],
+        bump = grantor_account.bump,
+        constraint = grantor_account.owner == grantor.key() @ ErrorCode::Unauthorized,
+        constraint = !grantor_account.frozen @ ErrorCode::AccountFrozen
+    )]
+    pub grantor_account: Account<'info, EncryptedAccount>,
+    
+    #[account(
+        seeds = [pda::ENCRYPTED_ACCOUNT_SEED, beneficiary_account.seed_owner.as_ref(), &beneficiary_account.account_index.to_le_bytes()],
+        bump = beneficiary_account.bump,
+    )]
+    pub beneficiary_account: Account<'info, EncryptedAccount>,
+    
+    #[account(mut)]
+    pub grantor: Signer<'info>,
+    
+    /// CHECK: Global config PDA (may be uninitialized); read for the pause flags
+    #[account(
+        seeds = [pda::CONFIG_SEED],
+        bump
+    )]
+    pub config: UncheckedAccount<'info>,
+    
+    /// CHECK: Protocol stats PDA (may be uninitialized); counters updated when present
+    #[account(
+        mut,
+        seeds = [pda::STATS_SEED],
+        bump
+    )]
+    pub stats: UncheckedAccount<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimVested<'info> {
+    #[account(
+        mut,
+        seeds = [pda::VESTING_SEED, vesting.grantor_account.as_ref(), &vesting.vesting_id.to_le_bytes()],
+        bump = vesting.bump,
+        has_one = beneficiary_account
+    )]
+    pub vesting: Account<'info, Vesting>,
+    
+    #[account(
+        mut,
+        seeds = [pda::ENCRYPTED_ACCOUNT_SEED, beneficiary_account.seed_owner.as_ref(), &beneficiary_account.account_index.to_le_bytes()],
+        bump = beneficiary_account.bump,
+        constraint = beneficiary_account.owner == beneficiary.key() @ ErrorCode::Unauthorized
+    )]
+    pub beneficiary_account: Account<'info, EncryptedAccount>,
+    
+    pub beneficiary: Signer<'info>,
+    
+    /// CHECK: Global config PDA (may be uninitialized); read for the pause flags
+    #[account(
+        seeds = [pda::CONFIG_SEED],
+        bump
+    )]
+    pub config: UncheckedAccount<'info>,
+    
+    /// CHECK: Protocol stats PDA (may be uninitialized); counters updated when present
+    #[account(
+        mut,
+        seeds = [pda::STATS_SEED],
+        bump
+    )]
+    pub stats: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct CloseVesting<'info> {
+    #[account(
+        mut,
+        close = grantor,
+        seeds = [pda::VESTING_SEED, vesting.grantor_account.as_ref(), &vesting.vesting_id.to_le_bytes()],
+        bump = vesting.bump,
+        has_one = grantor,
+        has_one = beneficiary_account
+    )]
+    pub vesting: Account<'info, Vesting>,
+    
+    #[account(
+        mut,
+        seeds = [pda::ENCRYPTED_ACCOUNT_SEED, beneficiary_account.seed_owner.as_ref(), &beneficiary_account.account_index.to_le_bytes()],
+        bump = beneficiary_account.bump,
+        constraint = beneficiary_account.owner == beneficiary.key() @ ErrorCode::Unauthorized
+    )]
+    pub beneficiary_account: Account<'info, EncryptedAccount>,
+    
+    pub beneficiary: Signer<'info>,
+    
+    /// CHECK: Receives the grant rent; bound by `has_one`
+    #[account(mut)]
+    pub grantor: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ApproveSubscription<'info> {
+    #[account(
+        init,
+        payer = owner,
+        space = 8 + Subscription::INIT_SPACE,
+        seeds = [pda::SUBSCRIPTION_SEED, subscriber_account.key().as_ref(), merchant_account.key().as_ref()],
+        bump
+    )]
+    pub subscription: Account<'info, Subscription>,
+    
+    #[account(
+        mut,
+        seeds = [pda::ENCRYPTED_ACCOUNT_SEED, subscriber_account.seed_owner.as_ref(), &subscriber_account.account_index.to_le_bytes()],
+        bump = subscriber_account.bump,
+        constraint = subscriber_account.owner == owner.key() @ ErrorCode::Unauthorized
+    )]
+    pub subscriber_account: Account<'info, EncryptedAccount>,
+    
+    #[account(
+        seeds = [pda::ENCRYPTED_ACCOUNT_SEED, merchant_account.seed_owner.as_ref(), &merchant_account.account_index.to_le_bytes()],
+        bump = merchant_account.bump,
+    )]
+    pub merchant_account: Account<'info, EncryptedAccount>,
+    
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ExecuteSubscription<'info> {
+    #[account(
+        mut,
+        seeds = [pda::SUBSCRIPTION_SEED, subscriber_account.key().as_ref(), merchant_account.key().as_ref()],
+        bump = subscription.bump,
+        has_one = subscriber_account,
+        has_one = merchant_account
+    )]
+    pub subscription: Account<'info, Subscription>,
+    
+    // SECURITY: No owner signature; the subscription PDA is the authorization
+    #[account(
+        mut,
+        seeds = [pda::ENCRYPTED_ACCOUNT_SEED, subscriber_account.seed_owner.as_ref(), &subscriber_account.account_index.to_le_bytes()],
+        bump = subscriber_account.bump,
+        constraint = subscriber_account.owner == subscription.owner @ ErrorCode::Unauthorized,
+        constraint = !subscriber_account.frozen @ ErrorCode::AccountFrozen
+    )]
+    pub subscriber_account: Account<'info, EncryptedAccount>,
+    
+    #[account(
+        mut,
+        seeds = [pda::ENCRYPTED_ACCOUNT_SEED, merchant_account.seed_owner.as_ref(), &merchant_account.account_index.to_le_bytes()],
+        bump = merchant_account.bump,
+    )]
+    pub merchant_account: Account<'info, EncryptedAccount>,
+    
+    /// Merchant or crank
+    pub caller: Signer<'info>,
+    
+    /// CHECK: Global config PDA (may be uninitialized); read for the pause flags
+    #[account(
+        seeds = [pda::CONFIG_SEED],
+        bump
+    )]
+    pub config: UncheckedAccount<'info>,
+    
+    /// CHECK: Protocol stats PDA (may be uninitialized); counters updated when present
+    #[account(
+        mut,
+        seeds = [pda::STATS_SEED],
+        bump
+    )]
+    pub stats: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct CancelSubscription<'info> {
+    #[account(
+        mut,
+        close = owner,
+        seeds = [pda::SUBSCRIPTION_SEED, subscription.subscriber_account.as_ref(), subscription.merchant_account.as_ref()],
+        bump = subscription.bump,
+        has_one = owner
+    )]
+    pub subscription: Account<'info, Subscription>,
+    
+    #[account(mut)]
+    pub owner: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct Approve<'info> {
+    #[account(
+        init,
+        payer = owner,
+        space = 8 + Allowance::INIT_SPACE,
+        seeds = [pda::ALLOWANCE_SEED, owner_account.key().as_ref(), delegate.key().as_ref()],
+        bump
+    )]
+    pub allowance: Account<'info, Allowance>,
+    
+    #[account(
+        mut,
+        seeds = [pda::ENCRYPTED_ACCOUNT_SEED, owner_account.seed_owner.as_ref(), &owner_account.account_index.to_le_bytes()],
+        bump = owner_account.bump,
+        constraint = owner_account.owner == owner.key() @ ErrorCode::Unauthorized
+    )]
+    pub owner_account: Account<'info, EncryptedAccount>,
+    
+    /// CHECK: Delegate wallet; only its key is recorded
+    pub delegate: UncheckedAccount<'info>,
+    
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ConfidentialTransferFrom<'info> {
+    #[account(
+        mut,
+        seeds = [pda::ALLOWANCE_SEED, owner_account.key().as_ref(), delegate.key().as_ref()],
+        bump = allowance.bump,
+        has_one = owner_account,
+        has_one = delegate
+    )]
+    pub allowance: Account<'info, Allowance>,
+    
+    // SECURITY: An allowance approved by a previous owner is void
+    #[account(
+        mut,
+        seeds = [pda::ENCRYPTED_ACCOUNT_SEED, owner_account.seed_owner.as_ref(), &owner_account.account_index.to_le_bytes()],
+        bump = owner_account.bump,
+        constraint = owner_account.owner == allowance.owner @ ErrorCode::Unauthorized,
+        constraint = !owner_account.frozen @ ErrorCode::AccountFrozen
+    )]
+    pub owner_account: Account<'info, EncryptedAccount>,
+    
+    #[account(
+        mut,
+        seeds = [pda::ENCRYPTED_ACCOUNT_SEED, recipient_account.seed_owner.as_ref(), &recipient_account.account_index.to_le_bytes()],
+        bump = recipient_account.bump,
+    )]
+    pub recipient_account: Account<'info, EncryptedAccount>,
+    
+    pub delegate: Signer<'info>,
+    
+    /// CHECK: Global config PDA (may be uninitialized); read for the pause flags
+    #[account(
+        seeds = [pda::CONFIG_SEED],
+        bump
+    )]
+    pub config: UncheckedAccount<'info>,
+    
+    /// CHECK: Protocol stats PDA (may be uninitialized); counters updated when present
+    #[account(
+        mut,
+        seeds = [pda::STATS_SEED],
+        bump
+    )]
+    pub stats: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct Revoke<'info> {
+    #[account(
+        mut,
+        close = owner,
+        seeds = [pda::ALLOWANCE_SEED, allowance.owner_account.as_ref(), allowance.delegate.as_ref()],
+        bump = allowance.bump,
+        has_one = owner
+    )]
+    pub allowance: Account<'info, Allowance>,
+    
+    #[account(mut)]
+    pub owner: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct RegisterSessionKey<'info> {
+    #[account(
+        init,
+        payer = owner,
+        space = 8 + SessionKey::INIT_SPACE,
+        seeds = [pda::SESSION_KEY_SEED, owner_account.key().as_ref(), session_key.key().as_ref()],
+        bump
+    )]
+    pub session: Account<'info, SessionKey>,
+    
+    #[account(
+        mut,
+        seeds = [pda::ENCRYPTED_ACCOUNT_SEED, owner_account.seed_owner.as_ref(), &owner_account.account_index.to_le_bytes()],
+        bump = owner_account.bump,
+        constraint = owner_account.owner == owner.key() @ ErrorCode::Unauthorized
+    )]
+    pub owner_account: Account<'info, EncryptedAccount>,
+    
+    /// CHECK: Session public key; only its key is recorded
+    pub session_key: UncheckedAccount<'info>,
+    
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SessionTransfer<'info> {
+    #[account(
+        seeds = [pda::SESSION_KEY_SEED, sender_account.key().as_ref(), session_key.key().as_ref()],
+        bump = session.bump,
+        constraint = session.owner_account == sender_account.key() @ ErrorCode::InvalidSessionKey,
+        constraint = session.session_key == session_key.key() @ ErrorCode::InvalidSessionKey
+    )]
+    pub session: Account<'info, SessionKey>,
+    
+    // SECURITY: A session registered by a previous owner is void
+    #[account(
+        mut,
+        seeds = [pda::ENCRYPTED_ACCOUNT_SEED, sender_account.seed_owner.as_ref(), &sender_account.account_index.to_le_bytes()],
+        bump = sender_account.bump,
+        constraint = sender_account.owner == session.owner @ ErrorCode::Unauthorized,
+        constraint = !sender_account.frozen @ ErrorCode::AccountFrozen
+    )]
+    pub sender_account: Account<'info, EncryptedAccount>,
+    
+    #[account(
+        mut,
+        seeds = [pda::ENCRYPTED_ACCOUNT_SEED, recipient_account.seed_owner.as_ref(), &recipient_account.account_index.to_le_bytes()],
+        bump = recipient_account.bump,
+    )]
+    pub recipient_account: Account<'info, EncryptedAccount>,
+    
+    pub session_key: Signer<'info>,
+    
+    /// CHECK: Global config PDA (may be uninitialized); read for the pause flags
+    #[account(
+        seeds = [pda::CONFIG_SEED],
+        bump
+    )]
+    pub config: UncheckedAccount<'info>,
+    
+    /// CHECK: Protocol stats PDA (may be uninitialized); counters updated when present
+    #[account(
+        mut,
+        seeds = [pda::STATS_SEED],
+        bump
+    )]
+    pub stats: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct RevokeSessionKey<'info> {
+    #[account(
+        mut,
+        close = owner,
+        seeds = [pda::SESSION_KEY_SEED, session.owner_account.as_ref(), session.session_key.as_ref()],
+        bump = session.bump,
+        has_one = owner
+    )]
+    pub session: Account<'info, SessionKey>,
+    
+    #[account(mut)]
+    pub owner: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetDeadManSwitch<'info> {
+    #[account(
+        init,
+        payer = owner,
+        space = 8 + DeadManSwitch::INIT_SPACE,
+        seeds = [pda::DEAD_MAN_SWITCH_SEED, encrypted_account.key().as_ref()],
+        bump
+    )]
+    pub dead_man_switch: Account<'info, DeadManSwitch>,
+    
+    #[account(
+        mut,
+        seeds = [pda::ENCRYPTED_ACCOUNT_SEED, encrypted_account.seed_owner.as_ref(), &encrypted_account.account_index.to_le_bytes()],
+        bump = encrypted_account.bump,
+        has_one = owner
+    )]
+    pub encrypted_account: Account<'info, EncryptedAccount>,
+    
+    /// CHECK: AuthChain PDA of the account; may be uninitialized when the
+    /// owner has not opted in (checked in auth_chain::enforce_if_configured)
+    #[account(
+        mut,
+        seeds = [pda::AUTH_CHAIN_SEED, encrypted_account.key().as_ref()],
+        bump
+    )]
+    pub auth_chain: UncheckedAccount<'info>,
+    
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct CheckIn<'info> {
+    #[account(
+        mut,
+        seeds = [pda::ENCRYPTED_ACCOUNT_SEED, encrypted_account.seed_owner.as_ref(), &encrypted_account.account_index.to_le_bytes()],
+        bump = encrypted_account.bump,
+        has_one = owner
+    )]
+    pub encrypted_account: Account<'info, EncryptedAccount>,
+    
+    pub owner: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct CancelDeadManSwitch<'info> {
+    #[account(
+        mut,
+        close = owner,
+        seeds = [pda::DEAD_MAN_SWITCH_SEED, encrypted_account.key().as_ref()],
+        bump = dead_man_switch.bump,
+        has_one = encrypted_account
+    )]
+    pub dead_man_switch: Account<'info, DeadManSwitch>,
+    
+    #[account(
+        mut,
+        seeds = [pda::ENCRYPTED_ACCOUNT_SEED, encrypted_account.seed_owner.as_ref(), &encrypted_account.account_index.to_le_bytes()],
+        bump = encrypted_account.bump,
+        has_one = owner
+    )]
+    pub encrypted_account: Account<'info, EncryptedAccount>,
+    
+    #[account(mut)]
+    pub owner: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct RecoverInactiveAccount<'info> {
+    #[account(
+        mut,
+        close = recovery_key,
+        seeds = [pda::DEAD_MAN_SWITCH_SEED, encrypted_account.key().as_ref()],
+        bump = dead_man_switch.bump,
+        has_one = encrypted_account,
+        has_one = recovery_key
+    )]
+    pub dead_man_switch: Account<'info, DeadManSwitch>,
+    
+    // SECURITY: A switch armed by a previous owner is void
+    #[account(
+        mut,
+        seeds = [pda::ENCRYPTED_ACCOUNT_SEED, encrypted_account.seed_owner.as_ref(), &encrypted_account.account_index.to_le_bytes()],
+        bump = encrypted_account.bump,
+        constraint = encrypted_account.owner == dead_man_switch.owner @ ErrorCode::Unauthorized,
+        constraint = !encrypted_account.frozen @ ErrorCode::AccountFrozen
+    )]
+    pub encrypted_account: Account<'info, EncryptedAccount>,
+    
+    #[account(
+        mut,
+        seeds = [pda::SOL_ESCROW_SEED, encrypted_account.seed_owner.as_ref(), &encrypted_account.account_index.to_le_bytes()],
+        bump = sol_escrow.bump,
+    )]
+    pub sol_escrow: Account<'info, SolEscrow>,
+    
+    #[account(mut)]
+    pub recovery_key: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetGuardians<'info> {
+    #[account(
+        init,
+        payer = owner,
+        space = 8 + GuardianSet::INIT_SPACE,
+        seeds = [pda::GUARDIAN_SET_SEED, encrypted_account.key().as_ref()],
+        bump
+    )]
+    pub guardian_set: Account<'info, GuardianSet>,
+    
+    #[account(
+        mut,
+        seeds = [pda::ENCRYPTED_ACCOUNT_SEED, encrypted_account.seed_owner.as_ref(), &encrypted_account.account_index.to_le_bytes()],
+        bump = encrypted_account.bump,
+        has_one = owner
+    )]
+    pub encrypted_account: Account<'info, EncryptedAccount>,
+    
+    /// CHECK: AuthChain PDA of the account; may be uninitialized when the
+    /// owner has not opted in (checked in auth_chain::enforce_if_configured)
+    #[account(
+        mut,
+        seeds = [pda::AUTH_CHAIN_SEED, encrypted_account.key().as_ref()],
+        bump
+    )]
+    pub auth_chain: UncheckedAccount<'info>,
+    
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RemoveGuardians<'info> {
+    #[account(
+        mut,
+        close = owner,
+        seeds = [pda::GUARDIAN_SET_SEED, encrypted_account.key().as_ref()],
+        bump = guardian_set.bump,
+        has_one = encrypted_account,
+        has_one = owner
+    )]
+    pub guardian_set: Account<'info, GuardianSet>,
+    
+    #[account(
+        mut,
+        seeds = [pda::ENCRYPTED_ACCOUNT_SEED, encrypted_account.seed_owner.as_ref(), &encrypted_account.account_index.to_le_bytes()],
+        bump = encrypted_account.bump,
+        has_one = owner
+    )]
+    pub encrypted_account: Account<'info, EncryptedAccount>,
+    
+    /// CHECK: AuthChain PDA of the account; may be uninitialized when the
+    /// owner has not opted in (checked in auth_chain::enforce_if_configured)
+    #[account(
+        mut,
+        seeds = [pda::AUTH_CHAIN_SEED, encrypted_account.key().as_ref()],
+        bump
+    )]
+    pub auth_chain: UncheckedAccount<'info>,
+    
+    #[account(mut)]
+    pub owner: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ApproveRecovery<'info> {
+    // SECURITY: A guardian set registered by a previous owner is void
+    #[account(
+        mut,
+        seeds = [pda::GUARDIAN_SET_SEED, encrypted_account.key().as_ref()],
+        bump = guardian_set.bump,
+        has_one = encrypted_account,
+        constraint = guardian_set.owner == encrypted_account.owner @ ErrorCode::Unauthorized
+    )]
+    pub guardian_set: Account<'info, GuardianSet>,
+    
+    #[account(
+        seeds = [pda::ENCRYPTED_ACCOUNT_SEED, encrypted_account.seed_owner.as_ref(), &encrypted_account.account_index.to_le_bytes()],
+        bump = encrypted_account.bump,
+        constraint = !encrypted_account.frozen @ ErrorCode::AccountFrozen
+    )]
+    pub encrypted_account: Account<'info, EncryptedAccount>,
+    
+    pub guardian: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct VetoRecovery<'info> {
+    #[account(
+        mut,
+        seeds = [pda::GUARDIAN_SET_SEED, encrypted_account.key().as_ref()],
+        bump = guardian_set.bump,
+        has_one = encrypted_account,
+        has_one = owner
+    )]
+    pub guardian_set: Account<'info, GuardianSet>,
+    
+    #[account(
+        mut,
+        seeds = [pda::ENCRYPTED_ACCOUNT_SEED, encrypted_account.seed_owner.as_ref(), &encrypted_account.account_index.to_le_bytes()],
+        bump = encrypted_account.bump,
+        has_one = owner
+    )]
+    pub encrypted_account: Account<'info, EncryptedAccount>,
+    
+    /// CHECK: AuthChain PDA of the account; may be uninitialized when the
+    /// owner has not opted in (checked in auth_chain::enforce_if_configured)
+    #[account(
+        mut,
+        seeds = [pda::AUTH_CHAIN_SEED, encrypted_account.key().as_ref()],
+        bump
+    )]
+    pub auth_chain: UncheckedAccount<'info>,
+    
+    pub owner: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct RecoverAccount<'info> {
+    #[account(
+        mut,
+        seeds = [pda::GUARDIAN_SET_SEED, encrypted_account.key().as_ref()],
+        bump = guardian_set.bump,
+        has_one = encrypted_account,
+        constraint = guardian_set.owner == encrypted_account.owner @ ErrorCode::Unauthorized,
+        constraint = guardian_set.proposed_owner == new_owner.key() @ ErrorCode::Unauthorized
+    )]
+    pub guardian_set: Account<'info, GuardianSet>,
+    
+    #[account(
+        mut,
+        seeds = [pda::ENCRYPTED_ACCOUNT_SEED, encrypted_account.seed_owner.as_ref(), &encrypted_account.account_index.to_le_bytes()],
+        bump = encrypted_account.bump,
+        constraint = !encrypted_account.frozen @ ErrorCode::AccountFrozen
+    )]
+    pub encrypted_account: Account<'info, EncryptedAccount>,
+    
+    #[account(
+        mut,
+        seeds = [pda::SOL_ESCROW_SEED, encrypted_account.seed_owner.as_ref(), &encrypted_account.account_index.to_le_bytes()],
+        bump = sol_escrow.bump,
+    )]
+    pub sol_escrow: Account<'info, SolEscrow>,
+    
+    /// Old owner's auth chain; when passed it is closed to the new owner
+    #[account(
+        mut,
+        close = new_owner,
+        seeds = [pda::AUTH_CHAIN_SEED, encrypted_account.key().as_ref()],
+        bump = auth_chain.bump,
+    )]
+    pub auth_chain: Option<Account<'info, AuthChain>>,
+    
+    #[account(mut)]
+    pub new_owner: Signer<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(payment_id: u64)]
+pub struct CreateConditionalPayment<'info> {
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + ConditionalPayment::INIT_SPACE,
+        seeds = [pda::CONDITIONAL_PAYMENT_SEED, payer_account.key().as_ref(), &payment_id.to_le_bytes()],
+        bump
+    )]
+    pub payment: Account<'info, ConditionalPayment>,
+    
+    #[account(
+        mut,
+        seeds = [pda::ENCRYPTED_ACCOUNT_SEED, payer_account.seed_owner.as_ref(), &payer_account.account_index.to_le_bytes()],
+        bump = payer_account.bump,
+        constraint = payer_account.owner == payer.key() @ ErrorCode::Unauthorized,
+        constraint = !payer_account.frozen @ ErrorCode::AccountFrozen
+    )]
+    pub payer_account: Account<'info, EncryptedAccount>,
+    
+    #[account(
+        seeds = [pda::ENCRYPTED_ACCOUNT_SEED, payee_account.seed_owner.as_ref(), &payee_account.account_index.to_le_bytes()],
+        bump = payee_account.bump,
+    )]
+    pub payee_account: Account<'info, EncryptedAccount>,
+    
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    
+    /// CHECK: Global config PDA (may be uninitialized); read for the pause flags
+    #[account(
+        seeds = [pda::CONFIG_SEED],
+        bump
+    )]
+    pub config: UncheckedAccount<'info>,
+    
+    /// CHECK: Protocol stats PDA (may be uninitialized); counters updated when present
+    #[account(
+        mut,
+        seeds = [pda::STATS_SEED],
+        bump
+    )]
+    pub stats: UncheckedAccount<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SettleConditionalPayment<'info> {
+    #[account(
+        mut,
+        close = payer,
+        seeds = [pda::CONDITIONAL_PAYMENT_SEED, payer_account.key().as_ref(), &payment.payment_id.to_le_bytes()],
+        bump = payment.bump,
+        has_one = payer,
+        has_one = payer_account,
+        has_one = payee_account
+    )]
+    pub payment: Account<'info, ConditionalPayment>,
+    
+    #[account(
+        mut,
+        seeds = [pda::ENCRYPTED_ACCOUNT_SEED, payer_account.seed_owner.as_ref(), &payer_account.account_index.to_le_bytes()],
+        bump = payer_account.bump,
+    )]
+    pub payer_account: Account<'info, EncryptedAccount>,
+    
+    #[account(
+        mut,
+        seeds = [pda::ENCRYPTED_ACCOUNT_SEED, payee_account.seed_owner.as_ref(), &payee_account.account_index.to_le_bytes()],
+        bump = payee_account.bump,
+    )]
+    pub payee_account: Account<'info, EncryptedAccount>,
+    
+    /// Payer, payee owner or arbiter (checked per instruction)
+    pub authority: Signer<'info>,
+    
+    /// CHECK: Receives the payment rent; bound by `has_one`
+    #[account(mut)]
+    pub payer: UncheckedAccount<'info>,
+    
+    /// CHECK: Global config PDA (may be uninitialized); read for the pause flags
+    #[account(
+        seeds = [pda::CONFIG_SEED],
+        bump
+    )]
+    pub config: UncheckedAccount<'info>,
+    
+    /// CHECK: Protocol stats PDA (may be uninitialized); counters updated when present
+    #[account(
+        mut,
+        seeds = [pda::STATS_SEED],
+        bump
+    )]
+    pub stats: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(hashlock: [u8; 32])]
+pub struct LockHtlc<'info> {
+    #[account(
+        init,
+        payer = sender,
+        space = 8 + HashTimeLock::INIT_SPACE,
+        seeds = [pda::HTLC_SEED, sender_account.key().as_ref(), hashlock.as_ref()],
+        bump
+    )]
+    pub htlc: Account<'info, HashTimeLock>,
+    
+    #[account(
+        mut,
+        seeds = [pda::ENCRYPTED_ACCOUNT_SEED, sender_account.seed_owner.as_ref(), &sender_account.account_index.to_le_bytes()],
+        bump = sender_account.bump,
+        constraint = sender_account.owner == sender.key() @ ErrorCode::Unauthorized,
+        constraint = !sender_account.frozen @ ErrorCode::AccountFrozen
+    )]
+    pub sender_account: Account<'info, EncryptedAccount>,
+    
+    #[account(
+        seeds = [pda::ENCRYPTED_ACCOUNT_SEED, recipient_account.seed_owner.as_ref(), &recipient_account.account_index.to_le_bytes()],
+        bump = recipient_account.bump,
+    )]
+    pub recipient_account: Account<'info, EncryptedAccount>,
+    
+    #[account(mut)]
+    pub sender: Signer<'info>,
+    
+    /// CHECK: Global config PDA (may be uninitialized); read for the pause flags
+    #[account(
+        seeds = [pda::CONFIG_SEED],
+        bump
+    )]
+    pub config: UncheckedAccount<'info>,
+    
+    /// CHECK: Protocol stats PDA (may be uninitialized); counters updated when present
+    #[account(
+        mut,
+        seeds = [pda::STATS_SEED],
+        bump
+    )]
+    pub stats: UncheckedAccount<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RedeemHtlc<'info> {
+    #[account(
+        mut,
+        close = sender,
+        seeds = [pda::HTLC_SEED, htlc.sender_account.as_ref(), htlc.hashlock.as_ref()],
+        bump = htlc.bump,
+        has_one = sender,
+        has_one = recipient_account
+    )]
+    pub htlc: Account<'info, HashTimeLock>,
+    
+    #[account(
+        mut,
+        seeds = [pda::ENCRYPTED_ACCOUNT_SEED, recipient_account.seed_owner.as_ref(), &recipient_account.account_index.to_le_bytes()],
+        bump = recipient_account.bump,
+        constraint = recipient_account.owner == recipient.key() @ ErrorCode::Unauthorized
+    )]
+    pub recipient_account: Account<'info, EncryptedAccount>,
+    
+    pub recipient: Signer<'info>,
+    
+    /// CHECK: Receives the HTLC rent; bound by `has_one`
+    #[account(mut)]
+    pub sender: UncheckedAccount<'info>,
+    
+    /// CHECK: Global config PDA (may be uninitialized); read for the pause flags
+    #[account(
+        seeds = [pda::CONFIG_SEED],
+        bump
+    )]
+    pub config: UncheckedAccount<'info>,
+    
+    /// CHECK: Protocol stats PDA (may be uninitialized); counters updated when present
+    #[account(
+        mut,
+        seeds = [pda::STATS_SEED],
+        bump
+    )]
+    pub stats: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct RefundHtlc<'info> {
+    #[account(
+        mut,
+        close = sender,
+        seeds = [pda::HTLC_SEED, sender_account.key().as_ref(), htlc.hashlock.as_ref()],
+        bump = htlc.bump,
+        has_one = sender,
+        has_one = sender_account
+    )]
+    pub htlc: Account<'info, HashTimeLock>,
+    
+    #[account(
+        mut,
+        seeds = [pda::ENCRYPTED_ACCOUNT_SEED, sender_account.seed_owner.as_ref(), &sender_account.account_index.to_le_bytes()],
+        bump = sender_account.bump,
+        constraint = sender_account.owner == sender.key() @ ErrorCode::Unauthorized
+    )]
+    pub sender_account: Account<'info, EncryptedAccount>,
+    
+    #[account(mut)]
+    pub sender: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct AuditAccount<'info> {
+    #[account(
+        mut,
+        seeds = [pda::ENCRYPTED_ACCOUNT_SEED, encrypted_account.seed_owner.as_ref(), &encrypted_account.account_index.to_le_bytes()],
         bump = encrypted_account.bump,
         has_one = owner
     )]
     pub encrypted_account: Account<'info, EncryptedAccount>,
     
-    #[account(mut)]
-    pub owner: Signer<'info>,
+    #[account(
+        seeds = [pda::SOL_ESCROW_SEED, encrypted_account.seed_owner.as_ref(), &encrypted_account.account_index.to_le_bytes()],
+        bump = sol_escrow.bump,
+        has_one = owner
+    )]
+    pub sol_escrow: Account<'info, SolEscrow>,
+    
+    pub owner: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ProposeConfigChange<'info> {
+    #[account(
+        seeds = [pda::CONFIG_SEED],
+        bump = config.bump,
+        has_one = authority
+    )]
+    pub config: Account<'info, Config>,
+    
+    /// Creation fails while another change is pending
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + PendingConfigChange::INIT_SPACE,
+        seeds = [pda::PENDING_CONFIG_CHANGE_SEED],
+        bump
+    )]
+    pub pending_change: Account<'info, PendingConfigChange>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ExecuteConfigChange<'info> {
+    #[account(
+        mut,
+        seeds = [pda::CONFIG_SEED],
+        bump = config.bump,
+        has_one = authority
+    )]
+    pub config: Account<'info, Config>,
+    
+    #[account(
+        mut,
+        seeds = [pda::PENDING_CONFIG_CHANGE_SEED],
+        bump = pending_change.bump,
+        close = authority
+    )]
+    pub pending_change: Account<'info, PendingConfigChange>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct InitializeTreasury<'info> {
+    #[account(
+        seeds = [pda::CONFIG_SEED],
+        bump = config.bump,
+        has_one = authority
+    )]
+    pub config: Account<'info, Config>,
+    
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + Treasury::INIT_SPACE,
+        seeds = [pda::TREASURY_SEED],
+        bump
+    )]
+    pub treasury: Account<'info, Treasury>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct CollectFees<'info> {
+    #[account(
+        seeds = [pda::CONFIG_SEED],
+        bump = config.bump,
+        has_one = fee_authority
+    )]
+    pub config: Account<'info, Config>,
+    
+    #[account(
+        mut,
+        seeds = [pda::TREASURY_SEED],
+        bump = treasury.bump,
+    )]
+    pub treasury: Account<'info, Treasury>,
+    
+    pub fee_authority: Signer<'info>,
+    
+    /// Receives the fees
+    #[account(mut)]
+    pub destination: SystemAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct InitializeShieldedTreasury<'info> {
+    #[account(
+        seeds = [pda::CONFIG_SEED],
+        bump = config.bump,
+        has_one = authority
+    )]
+    pub config: Account<'info, Config>,
+    
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + ShieldedTreasury::INIT_SPACE,
+        seeds = [pda::SHIELDED_TREASURY_SEED],
+        bump
+    )]
+    pub shielded_treasury: Account<'info, ShieldedTreasury>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SweepShieldedFees<'info> {
+    #[account(
+        seeds = [pda::CONFIG_SEED],
+        bump = config.bump,
+        has_one = fee_authority
+    )]
+    pub config: Account<'info, Config>,
+    
+    #[account(
+        mut,
+        seeds = [pda::SHIELDED_TREASURY_SEED],
+        bump = shielded_treasury.bump,
+    )]
+    pub shielded_treasury: Account<'info, ShieldedTreasury>,
+    
+    /// Fee authority's encrypted account (receives the fees)
+    #[account(
+        mut,
+        seeds = [pda::ENCRYPTED_ACCOUNT_SEED, fee_account.seed_owner.as_ref(), &fee_account.account_index.to_le_bytes()],
+        bump = fee_account.bump,
+        constraint = fee_account.owner == fee_authority.key() @ ErrorCode::Unauthorized,
+        constraint = !fee_account.frozen @ ErrorCode::AccountFrozen
+    )]
+    pub fee_account: Account<'info, EncryptedAccount>,
+    
+    pub fee_authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct InitializeStats<'info> {
+    #[account(
+        seeds = [pda::CONFIG_SEED],
+        bump = config.bump,
+        has_one = authority
+    )]
+    pub config: Account<'info, Config>,
+    
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + ProtocolStats::INIT_SPACE,
+        seeds = [pda::STATS_SEED],
+        bump
+    )]
+    pub stats: Account<'info, ProtocolStats>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SetAccountFrozen<'info> {
+    #[account(
+        seeds = [pda::CONFIG_SEED],
+        bump = config.bump,
+        has_one = compliance_authority
+    )]
+    pub config: Account<'info, Config>,
+    
+    pub compliance_authority: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [pda::ENCRYPTED_ACCOUNT_SEED, encrypted_account.seed_owner.as_ref(), &encrypted_account.account_index.to_le_bytes()],
+        bump = encrypted_account.bump,
+    )]
+    pub encrypted_account: Account<'info, EncryptedAccount>,
+}
+
+#[derive(Accounts)]
+pub struct InitializeGenerators<'info> {
+    #[account(
+        seeds = [pda::CONFIG_SEED],
+        bump = config.bump,
+        has_one = authority
+    )]
+    pub config: Account<'info, Config>,
+    
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + std::mem::size_of::<GeneratorTable>(),
+        seeds = [pda::GENERATORS_SEED],
+        bump
+    )]
+    pub generator_table: AccountLoader<'info, GeneratorTable>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateGenerators<'info> {
+    #[account(
+        seeds = [pda::CONFIG_SEED],
+        bump = config.bump,
+        has_one = authority
+    )]
+    pub config: Account<'info, Config>,
+    
+    #[account(
+        mut,
+        seeds = [pda::GENERATORS_SEED],
+        bump = generator_table.load()?.bump,
+    )]
+    pub generator_table: AccountLoader<'info, GeneratorTable>,
+    
+    pub authority: Signer<'info>,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct EncryptedAccount {
+    /// Owner of this encrypted account
+    pub owner: Pubkey,
+    
+    /// Encrypted balance as Pedersen commitment (64 bytes: 32 for X, 32 for Y)
+    /// This is C = g^balance * h^blinding
+    /// Only the owner can decrypt this with their private key
+    pub encrypted_balance: [u8; 64],
+    
+    /// Version number for tracking updates
+    pub version: u64,
+    
+    /// Bump seed for PDA
+    pub bump: u8,
+    
+    /// Sub-account index (part of the PDA seeds)
+    /// Lets one owner hold several independent encrypted accounts
+    pub account_index: u16,
+    
+    /// Owner key the PDA was derived from (fixed at initialization)
+    /// Stays the same across ownership transfers so the address never changes
+    pub seed_owner: Pubkey,
+    
+    /// Proposed new owner awaiting `accept_owner` (default = none)
+    pub pending_owner: Pubkey,
+    
+    /// Owner's registered encryption public key (all zeros = not registered)
+    /// Refreshed together with the blinding factor by `rotate_keys`
+    pub encryption_key: [u8; 32],
+    
+    /// Account layout version (see migration.rs)
+    /// Fields added in future layouts must be appended after this one
+    pub schema_version: u8,
+    
+    /// Additional encrypted asset balances (schema v2)
+    /// Grows/shrinks via realloc in `add_asset` / `remove_asset`
+    #[max_len(0)]
+    pub assets: Vec<AssetBalance>,
+    
+    /// Tamper-evident history (schema v3):
+    /// keccak(prev_state_hash || commitment || version) after every update.
+    /// Anyone holding a past state hash can verify the account's evolution
+    /// by replaying the chain from the commitments in transaction history.
+    pub state_hash: [u8; 32],
+    
+    /// Frozen by compliance (schema v4): outgoing transfers, withdrawals
+    /// and ownership changes are rejected while set
+    pub frozen: bool,
+    
+    /// Slot of the last recorded owner-signed action (schema v5)
+    /// Drives the dead-man switch; see `record_activity`
+    pub last_activity_slot: u64,
+}
+
+impl EncryptedAccount {
+    /// Account size (discriminator included) of the current layout with
+    /// `assets` asset entries
+    pub fn space_for(assets: usize) -> usize {
+        8 + Self::INIT_SPACE + assets * AssetBalance::INIT_SPACE
+    }
+    
+    /// Fold a commitment update into the state hash chain
+    /// Call after bumping `version`
+    pub fn advance_state_hash(&mut self, commitment: &[u8; 64]) {
+        self.state_hash = Self::next_state_hash(&self.state_hash, commitment, self.version);
+    }
+    
+    /// State hash after folding `commitment` at `version` into `prev`
+    pub fn next_state_hash(prev: &[u8; 32], commitment: &[u8; 64], version: u64) -> [u8; 32] {
+        merkle_tree::hash_leaf(&[prev, commitment, &version.to_le_bytes()])
+    }
+    
+    /// Record an owner-signed action (restarts the dead-man switch timer)
+    pub fn record_activity(&mut self) -> Result<()> {
+        self.last_activity_slot = Clock::get()?.slot;
+        Ok(())
+    }
+    
+    /// Index of the asset entry for `mint`, if tracked
+    pub fn find_asset(&self, mint: &Pubkey) -> Option<usize> {
+        self.assets.iter().position(|asset| asset.mint == *mint)
+    }
+}
+
+/// ECIES-encrypted opening of a transfer amount for the recipient
+///
+/// Plaintext = amount (u64 LE) || blinding factor (32 bytes), encrypted
+/// under a key derived from ECDH(ephemeral_key, recipient encryption_key).
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, InitSpace)]
+pub struct EncryptedMemo {
+    /// Sender's ephemeral public key
+    pub ephemeral_key: [u8; 32],
+    
+    /// AEAD nonce
+    pub nonce: [u8; 12],
+    
+    /// Ciphertext (40-byte plaintext) followed by the 16-byte tag
+    pub ciphertext: [u8; 56],
+}
+
+/// Encrypted balance of one SPL token inside a multi-asset EncryptedAccount
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, InitSpace)]
+pub struct AssetBalance {
+    /// SPL token mint
+    pub mint: Pubkey,
+    
+    /// Encrypted balance as Pedersen commitment (64 bytes)
+    pub encrypted_balance: [u8; 64],
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct SolEscrow {
+    /// Owner of this escrow account
+    pub owner: Pubkey,
+    
+    /// Current SOL balance in lamports
+    /// This is the ACTUAL balance, while encrypted_account stores the ENCRYPTED commitment
+    pub balance: u64,
+    
+    /// Bump seed for PDA
+    pub bump: u8,
+    
+    /// Sub-account index (matches the paired EncryptedAccount)
+    pub account_index: u16,
+    
+    /// Owner key the PDA was derived from (fixed at initialization)
+    pub seed_owner: Pubkey,
+    
+    /// Proposed new owner awaiting `accept_owner` (default = none)
+    pub pending_owner: Pubkey,
+    
+    /// Account layout version (see migration.rs)
+    /// Fields added in future layouts must be appended after this one
+    pub schema_version: u8,
+    
+    /// Receipt tokens (pSOL base units) minted against this escrow (schema v2)
+    /// Invariant: receipts_outstanding <= balance
+    pub receipts_outstanding: u64,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct TokenEscrow {
+    /// Owner of this token escrow
+    pub owner: Pubkey,
+    
+    /// SPL token mint held by this escrow
+    pub mint: Pubkey,
+    
+    /// Encrypted token balance as Pedersen commitment (64 bytes)
+    pub encrypted_balance: [u8; 64],
+    
+    /// Current token balance in base units
+    /// This is the ACTUAL public vault balance; encrypted_balance also covers
+    /// the hidden balance of the confidential vault
+    pub balance: u64,
+    
+    /// Version number for tracking commitment updates
+    pub version: u64,
+    
+    /// Bump seed for escrow PDA
+    pub bump: u8,
+    
+    /// Bump seed for the vault token-account PDA
+    pub vault_bump: u8,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct ArchiveTree {
+    /// Current Merkle root over all archival records
+    pub root: [u8; 32],
+    
+    /// Number of records appended so far
+    pub next_index: u64,
+    
+    /// Rightmost filled subtree per level (incremental Merkle frontier)
+    pub filled_subtrees: [[u8; 32]; archive_constants::ARCHIVE_TREE_DEPTH],
+    
+    /// Bump seed for PDA
+    pub bump: u8,
+}
+
+/// Global program configuration (singleton PDA)
+#[account]
+#[derive(InitSpace)]
+pub struct Config {
+    /// Admin allowed to update the configuration
+    pub authority: Pubkey,
+    
+    /// Whether token instructions only accept allowlisted mints
+    pub mint_allowlist_enabled: bool,
+    
+    /// Bump seed for PDA
+    pub bump: u8,
+    
+    /// Merkle root of the approved association set (all zeros = unset)
+    ///
+    /// Curated off-chain over note pool leaves; associated spends prove
+    /// their note is a member (see `spend_note_associated`).
+    pub association_root: [u8; 32],
+    
+    /// Transfers above this amount need a travel-rule envelope (0 = off)
+    pub travel_rule_threshold: u64,
+    
+    /// Oracle whose attestations withdrawals require (default = off)
+    pub sanctions_oracle: Pubkey,
+    
+    /// Maximum attestation age accepted by withdrawals (slots)
+    pub attestation_max_age: u64,
+    
+    /// Cap on an account's outgoing transfers per epoch (0 = off)
+    pub epoch_transfer_limit: u64,
+    
+    /// Credential (soulbound) mint required to transfer (default = off)
+    pub kyc_mint: Pubkey,
+    
+    /// Whether recipients must hold the credential too
+    pub kyc_require_recipient: bool,
+    
+    /// Regulator keys that can jointly order a disclosure
+    /// Capacity = disclosure_constants::MAX_REGULATORS
+    #[max_len(7)]
+    pub regulators: Vec<Pubkey>,
+    
+    /// Regulator signatures needed for a disclosure order (0 = disabled)
+    pub regulator_threshold: u8,
+    
+    /// Slots an owner has to answer a disclosure order
+    pub disclosure_window: u64,
+    
+    /// Merkle root of the sorted deny list (all zeros = off)
+    pub deny_list_root: [u8; 32],
+    
+    /// Maximum shielded balance per account (0 = uncapped)
+    pub max_account_balance: u64,
+    
+    /// Maximum plaintext amount (0 = transfer_constants::MAX_AMOUNT)
+    pub max_amount: u64,
+    
+    /// Minimum proof data size (0 = proof_constants::MIN_PROOF_DATA_SIZE)
+    pub min_proof_data_size: u32,
+    
+    /// Maximum proof data size (0 = proof_constants::MAX_PROOF_DATA_SIZE)
+    pub max_proof_data_size: u32,
+    
+    /// Widest amount range proof accepted, in bits (0 = any valid width)
+    pub range_bits: u8,
+    
+    /// Paused instruction categories (bitmask of `pause` flags)
+    pub paused: u8,
+    
+    /// Delay between proposing and executing a config change (0 = none)
+    pub timelock_delay_slots: u64,
+    
+    /// External verifier programs transfers may route proofs to
+    /// Capacity = verifier_constants::MAX_VERIFIER_PROGRAMS
+    #[max_len(4)]
+    pub verifier_programs: Vec<Pubkey>,
+    
+    /// Enabled subsystems (bitmask of `features` flags)
+    pub features: u32,
+    
+    /// Fee on public-amount withdrawals and SOL transfers (basis points, 0 = off)
+    pub fee_bps: u16,
+    
+    /// Key allowed to withdraw collected fees from the treasury
+    pub fee_authority: Pubkey,
+    
+    /// Wallets that pay no fee
+    /// Capacity = fee_constants::MAX_FEE_EXEMPTIONS
+    #[max_len(8)]
+    pub fee_exempt: Vec<Pubkey>,
+    
+    /// Key allowed to freeze and unfreeze accounts (default = disabled)
+    pub compliance_authority: Pubkey,
+    
+    /// Circuit breaker: transfers and withdrawals accepted per slot (0 = unlimited)
+    pub max_transfers_per_slot: u32,
+    
+    /// Verifier every transfer proof must go through (default = any)
+    pub required_verifier: Pubkey,
+}
+
+/// Fee settings set by `set_fee_params`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, InitSpace)]
+pub struct FeeParams {
+    pub fee_bps: u16,
+    pub fee_authority: Pubkey,
+    #[max_len(8)]
+    pub exempt: Vec<Pubkey>,
+}
+
+/// Tunable limits set by `update_config` (0 = compiled-in default)
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, InitSpace)]
+pub struct ConfigParams {
+    pub max_amount: u64,
+    pub min_proof_data_size: u32,
+    pub max_proof_data_size: u32,
+    pub range_bits: u8,
+    pub max_transfers_per_slot: u32,
+}
+
+/// Regulator quorum set by `set_regulators`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, InitSpace)]
+pub struct RegulatorParams {
+    #[max_len(7)]
+    pub regulators: Vec<Pubkey>,
+    pub threshold: u8,
+    pub window_slots: u64,
+}
+
+/// Mint allowlist entry queued as `ConfigChange::SupportedMint`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, InitSpace)]
+pub struct MintParams {
+    pub mint: Pubkey,
+    pub min_amount: u64,
+    pub max_amount: u64,
+    pub enabled: bool,
+}
+
+impl Config {
+    /// Store already-validated limits (see `limits::validate_params`)
+    pub fn apply_limits(&mut self, params: &ConfigParams) {
+        self.max_amount = params.max_amount;
+        self.min_proof_data_size = params.min_proof_data_size;
+        self.max_proof_data_size = params.max_proof_data_size;
+        self.range_bits = params.range_bits;
+        self.max_transfers_per_slot = params.max_transfers_per_slot;
+    }
+    
+    /// Store already-validated fee settings (see `fees::validate_params`)
+    pub fn apply_fees(&mut self, params: &FeeParams) {
+        self.fee_bps = params.fee_bps;
+        self.fee_authority = params.fee_authority;
+        self.fee_exempt = params.exempt.clone();
+    }
+    
+    /// Store an already-validated regulator quorum (see `validate_regulators`)
+    pub fn apply_regulators(&mut self, params: &RegulatorParams) {
+        self.regulators = params.regulators.clone();
+        self.regulator_threshold = params.threshold;
+        self.disclosure_window = params.window_slots;
+    }
+    
+    /// Deserialize the config PDA, or None if it was never initialized
+    ///
+    /// `info` must already be seed-checked against the config PDA.
+    pub fn load_optional(info: &AccountInfo) -> Result<Option<Config>> {
+        if info.data_is_empty() || info.owner != &crate::ID {
+            return Ok(None);
+        }
+        
+        let data = info.try_borrow_data()?;
+        Ok(Some(Config::try_deserialize(&mut &data[..])?))
+    }
+    
+    /// Validate a token mint and amount against the allowlist (if enforced)
+    pub fn check_mint(
+        &self,
+        mint_config: Option<&MintConfig>,
+        mint: &InterfaceAccount<Mint>,
+        amount: u64,
+    ) -> Result<()> {
+        if !self.mint_allowlist_enabled {
+            return Ok(());
+        }
+        
+        let mint_config = mint_config.ok_or(ErrorCode::MintNotAllowed)?;
+        require!(
+            mint_config.enabled && mint_config.mint == mint.key(),
+            ErrorCode::MintNotAllowed
+        );
+        require!(
+            mint_config.decimals == mint.decimals,
+            ErrorCode::MintNotAllowed
+        );
+        require!(
+            amount >= mint_config.min_amount && amount <= mint_config.max_amount,
+            ErrorCode::InvalidAmount
+        );
+        Ok(())
+    }
+}
+
+/// Mint allowlist entry with per-mint bounds (in base units)
+#[account]
+#[derive(InitSpace)]
+pub struct MintConfig {
+    /// Allowlisted mint
+    pub mint: Pubkey,
+    
+    /// Decimals recorded when the mint was vetted
+    pub decimals: u8,
+    
+    /// Minimum amount per deposit/transfer
+    pub min_amount: u64,
+    
+    /// Maximum amount per deposit/transfer
+    pub max_amount: u64,
+    
+    /// Disabled entries reject all deposits/transfers
+    pub enabled: bool,
+    
+    /// Bump seed for PDA
+    pub bump: u8,
+}
+
+/// Operator-registered canary watching a program account for any change
+#[account]
+#[derive(InitSpace)]
+pub struct Canary {
+    /// Account that must never change
+    pub watched_account: Pubkey,
+    
+    /// Hash of the watched account's owner and data when armed
+    pub snapshot_hash: [u8; 32],
+    
+    /// Lamports of the watched account when armed
+    pub snapshot_lamports: u64,
+    
+    /// Slot the canary was (re-)armed at
+    pub armed_slot: u64,
+    
+    /// Set once a mismatch has been observed
+    pub tripped: bool,
+    
+    /// Bump seed for PDA
+    pub bump: u8,
+}
+
+/// One-time authorization code chain protecting an encrypted account
+/// (see auth_chain.rs)
+#[account]
+#[derive(InitSpace)]
+pub struct AuthChain {
+    /// Encrypted account protected by this chain
+    pub encrypted_account: Pubkey,
+    
+    /// Current chain tip; the next code must hash to this value
+    pub tip: [u8; 32],
+    
+    /// Number of codes left before the chain must be reset
+    pub remaining: u32,
+    
+    /// Bump seed for PDA
+    pub bump: u8,
+}
+
+/// Shared SOL vault backing every pool-mode balance
+#[account]
+#[derive(InitSpace)]
+pub struct PoolVault {
+    /// Total lamports deposited through the pool (aggregate across users)
+    pub total_shielded: u64,
+    
+    /// Bump seed for PDA
+    pub bump: u8,
+}
+
+/// Issuer-controlled asset that exists only inside the shielded system
+///
+/// Holders track it like any other asset, keyed by `asset_id` in their
+/// EncryptedAccount `assets` vector; supply changes only through
+/// `confidential_mint` / `confidential_burn`.
+#[account]
+#[derive(InitSpace)]
+pub struct PrivateAsset {
+    /// Asset identifier (used in place of a mint in `assets`)
+    pub asset_id: Pubkey,
+    
+    /// Authority allowed to mint and co-sign burns
+    pub issuer: Pubkey,
+    
+    /// Number of supply adjustments so far (amounts stay hidden)
+    pub supply_version: u64,
+    
+    /// Bump seed for PDA
+    pub bump: u8,
+}
+
+/// Per-account mailbox of encrypted messages
+#[account]
+#[derive(InitSpace)]
+pub struct Mailbox {
+    /// Encrypted account receiving the messages
+    pub encrypted_account: Pubkey,
+    
+    /// Sequence number of the next message (part of the message PDA seeds)
+    pub next_seq: u64,
+    
+    /// Minimum number of slots between two posts
+    pub min_post_interval: u64,
+    
+    /// Slot of the most recent post
+    pub last_post_slot: u64,
+    
+    /// Bump seed for PDA
+    pub bump: u8,
+}
+
+/// One encrypted mailbox message (rent paid by the sender)
+#[account]
+#[derive(InitSpace)]
+pub struct MailMessage {
+    /// Mailbox this message belongs to
+    pub mailbox: Pubkey,
+    
+    /// Sender (paid the rent)
+    pub sender: Pubkey,
+    
+    /// Sequence number within the mailbox
+    pub seq: u64,
+    
+    /// Recipient encryption key the ciphertext was encrypted to
+    pub encryption_key: [u8; 32],
+    
+    /// Slot the message was posted at
+    pub slot: u64,
+    
+    /// Ciphertext (opaque to the program)
+    /// Capacity = mailbox_constants::MAX_CIPHERTEXT_LEN
+    #[max_len(512)]
+    pub ciphertext: Vec<u8>,
+    
+    /// Bump seed for PDA
+    pub bump: u8,
+}
+
+/// Shielded note pool: note commitment tree plus the SOL backing all notes
+#[account]
+#[derive(InitSpace)]
+pub struct NotePool {
+    /// Current Merkle root over all note leaves
+    pub root: [u8; 32],
+    
+    /// Number of notes inserted so far
+    pub next_index: u64,
+    
+    /// Rightmost filled subtree per level (incremental Merkle frontier)
+    pub filled_subtrees: [[u8; 32]; note_pool_constants::NOTE_TREE_DEPTH],
+    
+    /// Ring buffer of recent roots (spends may use a slightly stale root)
+    pub root_history: [[u8; 32]; note_pool_constants::ROOT_HISTORY_SIZE],
+    
+    /// Slot of the current root in `root_history`
+    pub root_history_index: u8,
+    
+    /// Lamports backing unspent notes (aggregate)
+    pub total_deposited: u64,
+    
+    /// Bump seed for PDA
+    pub bump: u8,
+}
+
+impl NotePool {
+    /// Record a new current root
+    pub fn push_root(&mut self, root: [u8; 32]) {
+        self.root = root;
+        self.root_history_index =
+            ((self.root_history_index as usize + 1) % note_pool_constants::ROOT_HISTORY_SIZE) as u8;
+        self.root_history[self.root_history_index as usize] = root;
+    }
+    
+    /// Whether `root` is the current root or one of the recent ones
+    pub fn is_known_root(&self, root: &[u8; 32]) -> bool {
+        self.root_history.iter().any(|known| known == root)
+    }
+}
+
+/// Published nullifier of a spent note (existence = spent)
+#[account]
+#[derive(InitSpace)]
+pub struct Nullifier {
+    /// Nullifier value (also the PDA seed)
+    pub nullifier: [u8; 32],
+    
+    /// Slot the note was spent at
+    pub slot: u64,
+    
+    /// Bump seed for PDA
+    pub bump: u8,
+}
+
+/// Groth16 verifying key of a note circuit (see `groth16`)
+#[account]
+#[derive(InitSpace)]
+pub struct NoteVerifyingKey {
+    /// Circuit id (`groth16::NOTE_SPEND_CIRCUIT`, ...)
+    pub circuit: u8,
+    
+    /// alpha in G1
+    pub alpha_g1: [u8; 64],
+    
+    /// beta in G2
+    pub beta_g2: [u8; 128],
+    
+    /// gamma in G2
+    pub gamma_g2: [u8; 128],
+    
+    /// delta in G2
+    pub delta_g2: [u8; 128],
+    
+    /// Input commitments IC[0..=inputs] in G1 (at most 5 public inputs)
+    #[max_len(6)]
+    pub ic: Vec<[u8; 64]>,
+    
+    /// Bump seed for PDA
+    pub bump: u8,
+}
+
+/// Open request to return a mistaken confidential transfer
+#[account]
+#[derive(InitSpace)]
+pub struct RefundRequest {
+    /// Wallet that opened the request (receives the rent back)
+    pub requester: Pubkey,
+    
+    /// Encrypted account the refund goes to (original sender)
+    pub requester_account: Pubkey,
+    
+    /// Encrypted account asked to refund (original recipient)
+    pub refunder_account: Pubkey,
+    
+    /// Reference to the original transfer (recipient's state hash after it)
+    pub transfer_ref: [u8; 32],
+    
+    /// Amount commitment of the original transfer
+    pub amount_commitment: [u8; 64],
+    
+    /// Slot the request was opened at
+    pub requested_slot: u64,
+    
+    /// Bump seed for PDA
+    pub bump: u8,
+}
+
+/// Published stealth meta-keys of a recipient
+#[account]
+#[derive(InitSpace)]
+pub struct StealthMeta {
+    /// Recipient wallet
+    pub owner: Pubkey,
+    
+    /// Scan public key S (detects incoming payments)
+    pub scan_key: [u8; 32],
+    
+    /// Spend public key B (controls one-time destinations)
+    pub spend_key: [u8; 32],
+    
+    /// Bump seed for PDA
+    pub bump: u8,
+}
+
+/// Announcement of one stealth payment (rent paid by the sender)
+#[account]
+#[derive(InitSpace)]
+pub struct StealthAnnouncement {
+    /// Sender's ephemeral public key R (also the PDA seed)
+    pub ephemeral_key: [u8; 32],
+    
+    /// First byte of the shared secret hash (fast scanning filter)
+    pub view_tag: u8,
+    
+    /// One-time destination the payment was sent to
+    pub destination: Pubkey,
+    
+    /// Slot of the payment
+    pub slot: u64,
+    
+    /// Bump seed for PDA
+    pub bump: u8,
+}
+
+/// Append-only list of incoming transfer notices for one account
+#[account]
+#[derive(InitSpace)]
+pub struct Inbox {
+    /// Encrypted account the notices are for
+    pub encrypted_account: Pubkey,
+    
+    /// Notices ever appended (pruning does not decrease it)
+    pub total_received: u64,
+    
+    /// Unpruned notices, oldest first
+    /// Capacity = inbox_constants::INBOX_CAPACITY
+    #[max_len(16)]
+    pub entries: Vec<InboxEntry>,
+    
+    /// Bump seed for PDA
+    pub bump: u8,
+}
+
+impl Inbox {
+    /// Append a notice (fails when the owner has not pruned in a while)
+    pub fn push(&mut self, entry: InboxEntry) -> Result<()> {
+        require!(
+            self.entries.len() < inbox_constants::INBOX_CAPACITY,
+            ErrorCode::InboxFull
+        );
+        self.entries.push(entry);
+        self.total_received += 1;
+        Ok(())
+    }
+}
+
+/// One incoming transfer notice
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, InitSpace)]
+pub struct InboxEntry {
+    /// Sender-opaque id (recipient's state hash after the transfer)
+    pub id: [u8; 32],
+    
+    /// Encrypted amount opening for the recipient
+    pub note: EncryptedMemo,
+    
+    /// Slot the transfer landed in
+    pub slot: u64,
+}
+
+/// Global queue of delayed pool withdrawals
+#[account]
+#[derive(InitSpace)]
+pub struct WithdrawalQueue {
+    /// Ticket number of the next queued withdrawal (part of its PDA seeds)
+    pub next_ticket: u64,
+    
+    /// Withdrawals queued but not yet released
+    pub pending: u64,
+    
+    /// Bump seed for PDA
+    pub bump: u8,
+}
+
+/// One queued pool withdrawal, released by `process_withdrawal_batch`
+#[account]
+#[derive(InitSpace)]
+pub struct QueuedWithdrawal {
+    /// Ticket number (also the PDA seed)
+    pub ticket: u64,
+    
+    /// Account receiving the lamports
+    pub destination: Pubkey,
+    
+    /// Lamports to release
+    pub amount: u64,
+    
+    /// Earliest slot the withdrawal can be released at
+    pub release_slot: u64,
+    
+    /// Bump seed for PDA
+    pub bump: u8,
+}
+
+/// Read-only viewing key granted by the owner of an encrypted account
+#[account]
+#[derive(InitSpace)]
+pub struct ViewingKey {
+    /// Encrypted account this key can view
+    pub encrypted_account: Pubkey,
+    
+    /// X25519 public key balance views are encrypted to
+    pub viewing_key: [u8; 32],
+    
+    /// Slot the current key was set
+    pub granted_slot: u64,
+    
+    /// Bump seed for PDA
+    pub bump: u8,
+}
+
+/// Travel-rule metadata encrypted to the counterparty VASP (see travel_rule.rs)
+///
+/// Plaintext = originator/beneficiary record (e.g. IVMS101), encrypted under
+/// a key derived from ECDH(ephemeral_key, vasp_key).
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct TravelRuleEnvelope {
+    /// Counterparty VASP's X25519 public key
+    pub vasp_key: [u8; 32],
+    
+    /// Sender's ephemeral public key
+    pub ephemeral_key: [u8; 32],
+    
+    /// AEAD nonce
+    pub nonce: [u8; 12],
+    
+    /// Ciphertext followed by the 16-byte tag
+    pub ciphertext: Vec<u8>,
+}
+
+/// Sanctions-oracle screening result for one destination (see sanctions.rs)
+#[account]
+#[derive(InitSpace)]
+pub struct SanctionsAttestation {
+    /// Oracle that produced the attestation
+    pub oracle: Pubkey,
+    
+    /// Screened destination wallet
+    pub destination: Pubkey,
+    
+    /// Whether the destination may receive withdrawals
+    pub cleared: bool,
+    
+    /// Slot of the latest screening
+    pub slot: u64,
+    
+    /// Bump seed for PDA
+    pub bump: u8,
+}
+
+/// Encrypted running total of an account's outgoing transfers in one epoch
+/// (see velocity.rs)
+#[account]
+#[derive(InitSpace)]
+pub struct TransferAccumulator {
+    /// Account whose transfers are accumulated
+    pub encrypted_account: Pubkey,
+    
+    /// Epoch `total_commitment` belongs to
+    pub epoch: u64,
+    
+    /// Commitment to the epoch total (all zeros = nothing sent yet)
+    pub total_commitment: [u8; 64],
+    
+    /// Bump seed for PDA
+    pub bump: u8,
+}
+
+/// New epoch total plus the proof that it stays under the limit
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct VelocityUpdate {
+    /// Commitment to total_old + amount
+    pub total_commitment: [u8; 64],
+    
+    /// Velocity proof (see proof_verification::verify_velocity_proof)
+    pub proof_data: Vec<u8>,
+}
+
+/// Commitment instruction argument, full or compressed (see `compression`)
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy)]
+pub enum PackedCommitment {
+    /// 64-byte commitment slot, as stored on-chain
+    Full([u8; 64]),
+    
+    /// 32-byte compressed form, expanded and validated on-chain
+    Compressed([u8; 32]),
+}
+
+impl PackedCommitment {
+    /// The 64-byte commitment slot
+    pub fn expand(&self) -> Result<[u8; 64]> {
+        match self {
+            PackedCommitment::Full(commitment) => Ok(*commitment),
+            PackedCommitment::Compressed(compressed) => compression::expand(compressed),
+        }
+    }
+}
+
+/// Hidden protocol fee of a confidential transfer
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct ConfidentialFee {
+    /// Commitment to floor(amount * fee_bps / 10_000)
+    pub fee_commitment: [u8; 64],
+    
+    /// Opening of the fee, encrypted to the fee authority
+    pub memo: EncryptedMemo,
+    
+    /// Fee proof (see proof_verification::verify_fee_proof)
+    pub proof_data: Vec<u8>,
 }
 
-#[derive(Accounts)]
-pub struct ConfidentialTransfer<'info> {
-    #[account(
-        mut,
-        seeds = [b"encrypted-account", sender.key().as_ref()],
-        bump = sender_account.bump,
-    )]
-    pub sender_account: Account<'info, EncryptedAccount>,
+/// Lifecycle of a disclosure order
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+pub enum DisclosureStatus {
+    /// Waiting for the owner's answer
+    Pending,
+    /// Owner disclosed a verified opening in time
+    Answered,
+    /// Deadline missed; the account was frozen
+    Enforced,
+}
+
+/// Court-ordered disclosure of one commitment, issued by the regulator quorum
+#[account]
+#[derive(InitSpace)]
+pub struct DisclosureRequest {
+    /// Account whose owner must answer
+    pub encrypted_account: Pubkey,
     
-    #[account(
-        mut,
-        seeds = [b"encrypted-account", recipient.key().as_ref()],
-        bump = recipient_account.bump,
-    )]
-    pub recipient_account: Account<'info, EncryptedAccount>,
+    /// Case reference chosen by the regulators (part of the PDA seeds)
+    pub case_id: [u8; 32],
     
-    #[account(mut)]
-    pub sender: Signer<'info>,
+    /// Commitment that must be opened
+    pub commitment: [u8; 64],
     
-    /// CHECK: Recipient public key, not a signer
-    pub recipient: UncheckedAccount<'info>,
+    /// Paid the rent; receives it back on close
+    pub payer: Pubkey,
+    
+    /// Slot the order was issued
+    pub requested_slot: u64,
+    
+    /// Last slot an answer is accepted
+    pub deadline_slot: u64,
+    
+    /// Current status
+    pub status: DisclosureStatus,
+    
+    /// Bump seed for PDA
+    pub bump: u8,
 }
 
-#[derive(Accounts)]
-pub struct Withdraw<'info> {
-    #[account(
-        mut,
-        seeds = [b"encrypted-account", owner.key().as_ref()],
-        bump = encrypted_account.bump,
-        has_one = owner
-    )]
-    pub encrypted_account: Account<'info, EncryptedAccount>,
+/// Opening of one historical balance commitment
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct BalanceOpening {
+    /// Balance commitment at that version
+    pub commitment: [u8; 64],
     
-    #[account(mut)]
-    pub owner: Signer<'info>,
+    /// Disclosed balance
+    pub amount: u64,
+    
+    /// Opening proof (see proof_verification::verify_opening_proof)
+    pub proof: [u8; 96],
 }
 
-#[derive(Accounts)]
-pub struct DepositSOL<'info> {
-    #[account(
-        mut,
-        seeds = [b"encrypted-account", owner.key().as_ref()],
-        bump = encrypted_account.bump,
-        has_one = owner
-    )]
-    pub encrypted_account: Account<'info, EncryptedAccount>,
+/// Owner-signed attestation that a window of balances stayed within bounds
+#[account]
+#[derive(InitSpace)]
+pub struct DisclosureReport {
+    /// Account the report covers
+    pub encrypted_account: Pubkey,
     
-    #[account(
-        mut,
-        seeds = [b"sol-escrow", owner.key().as_ref()],
-        bump = sol_escrow.bump,
-        has_one = owner
-    )]
-    pub sol_escrow: Account<'info, SolEscrow>,
+    /// Owner that exported (signed) the report
+    pub owner: Pubkey,
     
-    #[account(mut)]
-    pub owner: Signer<'info>,
+    /// Owner-chosen identifier (part of the PDA seeds)
+    pub report_id: u64,
     
-    pub system_program: Program<'info, System>,
+    /// First covered version
+    pub from_version: u64,
+    
+    /// Last covered version (account version at export)
+    pub to_version: u64,
+    
+    /// State hash before `from_version`
+    pub start_state_hash: [u8; 32],
+    
+    /// State hash after `to_version`
+    pub end_state_hash: [u8; 32],
+    
+    /// Every covered balance was at least this
+    pub min_bound: u64,
+    
+    /// Every covered balance was at most this
+    pub max_bound: u64,
+    
+    /// Slot of export
+    pub slot: u64,
+    
+    /// Bump seed for PDA
+    pub bump: u8,
 }
 
-#[derive(Accounts)]
-pub struct WithdrawSOL<'info> {
-    #[account(
-        mut,
-        seeds = [b"encrypted-account", owner.key().as_ref()],
-        bump = encrypted_account.bump,
-        has_one = owner
-    )]
-    pub encrypted_account: Account<'info, EncryptedAccount>,
+/// One transfer inside a confidential_transfer_batch call
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct BatchTransferLeg {
+    /// Sender's balance commitment after the transfer
+    pub sender_new_commitment: [u8; 64],
+    
+    /// Recipient's balance commitment after the transfer
+    pub recipient_new_commitment: [u8; 64],
+    
+    /// Transfer proof (same format as confidential_transfer)
+    pub proof_data: Vec<u8>,    
+    /// Sender version the proof was built against
+    pub expected_sender_version: u64,
+    
+    /// Recipient version the proof was built against
+    pub expected_recipient_version: u64,
+}
+
+/// Transfer debited from the sender and awaiting the recipient's claim
+#[account]
+#[derive(InitSpace)]
+pub struct PendingTransfer {
+    /// Wallet that initiated the transfer (receives the rent back)
+    pub sender: Pubkey,
+    
+    /// Encrypted account the amount was debited from
+    pub sender_account: Pubkey,
+    
+    /// Encrypted account allowed to claim
+    pub recipient_account: Pubkey,
+    
+    /// Sender-chosen identifier (PDA seed)
+    pub transfer_id: u64,
+    
+    /// Commitment to the escrowed amount
+    pub amount_commitment: [u8; 64],
+    
+    /// Claims are accepted before this slot, refunds from it on
+    pub expiry_slot: u64,
+    
+    /// Bump seed for PDA
+    pub bump: u8,
+}
+
+/// Payment request issued by a merchant
+#[account]
+#[derive(InitSpace)]
+pub struct Invoice {
+    /// Wallet that issued the invoice (receives the rent back)
+    pub merchant: Pubkey,
+    
+    /// Encrypted account the payment goes to
+    pub recipient_account: Pubkey,
+    
+    /// Merchant reference id (PDA seed)
+    pub invoice_id: u64,
+    
+    /// Commitment to the amount due
+    pub amount_commitment: [u8; 64],
+    
+    /// Payments are accepted before this slot
+    pub expiry_slot: u64,
+    
+    /// Whether the invoice has been paid
+    pub paid: bool,
+    
+    /// Encrypted account that paid (default until paid)
+    pub payer_account: Pubkey,
+    
+    /// Bump seed for PDA
+    pub bump: u8,
+}
+
+/// Hidden amount redeemable by whoever holds the link secret
+#[account]
+#[derive(InitSpace)]
+pub struct ClaimLink {
+    /// Wallet that created the link (receives the rent back)
+    pub creator: Pubkey,
+    
+    /// Encrypted account the amount was debited from
+    pub creator_account: Pubkey,
+    
+    /// Public key of the keypair seeded by the link secret (PDA seed)
+    pub claim_key: Pubkey,
+    
+    /// Commitment to the locked amount
+    pub amount_commitment: [u8; 64],
+    
+    /// Redeemable before this slot, reclaimable from it on
+    pub expiry_slot: u64,
+    
+    /// Bump seed for PDA
+    pub bump: u8,
+}
+
+/// Continuous payment from a sender to a recipient at a hidden rate
+#[account]
+#[derive(InitSpace)]
+pub struct PaymentStream {
+    /// Wallet that funds the stream (receives rent and leftover back)
+    pub sender: Pubkey,
+    
+    /// Encrypted account the deposit was debited from
+    pub sender_account: Pubkey,
+    
+    /// Encrypted account allowed to claim accruals
+    pub recipient_account: Pubkey,
+    
+    /// Sender-chosen identifier (PDA seed)
+    pub stream_id: u64,
+    
+    /// Commitment to the rate (lamports per second)
+    pub rate_commitment: [u8; 64],
+    
+    /// Commitment to the deposit not yet claimed
+    pub remaining_commitment: [u8; 64],
+    
+    /// Slot the stream was created in
+    pub start_slot: u64,
+    
+    /// Unix time accrual started
+    pub start_time: i64,
+    
+    /// Unix time accrual stops (moved earlier by cancel_stream)
+    pub end_time: i64,
+    
+    /// Unix time up to which accruals have been claimed
+    pub claimed_until: i64,
+    
+    /// Bump seed for PDA
+    pub bump: u8,
+}
+
+/// Hidden grant vesting linearly (with a cliff) to a beneficiary
+#[account]
+#[derive(InitSpace)]
+pub struct Vesting {
+    /// Wallet that funded the grant (receives the rent back)
+    pub grantor: Pubkey,
+    
+    /// Encrypted account the grant was debited from
+    pub grantor_account: Pubkey,
+    
+    /// Encrypted account allowed to claim vested tranches
+    pub beneficiary_account: Pubkey,
+    
+    /// Grantor-chosen identifier (PDA seed)
+    pub vesting_id: u64,
+    
+    /// Commitment to the total grant
+    pub total_commitment: [u8; 64],
+    
+    /// Commitment to the part not yet claimed
+    pub remaining_commitment: [u8; 64],
+    
+    /// Unix time vesting starts
+    pub start_time: i64,
+    
+    /// Unix time before which nothing can be claimed
+    pub cliff_time: i64,
+    
+    /// Unix time the grant is fully vested
+    pub end_time: i64,
+    
+    /// Bump seed for PDA
+    pub bump: u8,
+}
+
+/// Owner's standing authorization for a merchant to pull a hidden amount per period
+#[account]
+#[derive(InitSpace)]
+pub struct Subscription {
+    /// Owner of the subscriber account (receives the rent back)
+    pub owner: Pubkey,
+    
+    /// Encrypted account payments are pulled from
+    pub subscriber_account: Pubkey,
+    
+    /// Encrypted account payments are credited to
+    pub merchant_account: Pubkey,
+    
+    /// Commitment to the authorized amount per period
+    pub amount_commitment: [u8; 64],
+    
+    /// Billing period in seconds
+    pub period_secs: u64,
+    
+    /// Unix time from which the next pull is allowed
+    pub next_payment_time: i64,
+    
+    /// Number of pulls executed so far
+    pub payments_made: u64,
+    
+    /// Bump seed for PDA
+    pub bump: u8,
+}
+
+/// Hidden amount a delegate may move out of an owner's encrypted account
+#[account]
+#[derive(InitSpace)]
+pub struct Allowance {
+    /// Owner who approved the delegate (receives the rent back)
+    pub owner: Pubkey,
+    
+    /// Encrypted account the delegate may spend from
+    pub owner_account: Pubkey,
+    
+    /// Wallet allowed to call confidential_transfer_from
+    pub delegate: Pubkey,
+    
+    /// Commitment to the allowance left
+    pub allowance_commitment: [u8; 64],
+    
+    /// Bump seed for PDA
+    pub bump: u8,
+}
+
+/// Short-lived key allowed to sign scoped transfers for an encrypted account
+#[account]
+#[derive(InitSpace)]
+pub struct SessionKey {
+    /// Owner who registered the key (receives the rent back)
+    pub owner: Pubkey,
+    
+    /// Encrypted account the key may spend from
+    pub owner_account: Pubkey,
+    
+    /// Session public key
+    pub session_key: Pubkey,
+    
+    /// Commitment to the maximum amount per transfer
+    pub max_amount_commitment: [u8; 64],
+    
+    /// Slot from which the key is no longer valid
+    pub expiry_slot: u64,
+    
+    /// Encrypted accounts the key may pay (empty = any)
+    #[max_len(4)]
+    pub allowed_recipients: Vec<Pubkey>,
+    
+    /// Bump seed for PDA
+    pub bump: u8,
+}
+
+/// Recovery key allowed to take over an encrypted account after inactivity
+#[account]
+#[derive(InitSpace)]
+pub struct DeadManSwitch {
+    /// Owner who armed the switch
+    pub owner: Pubkey,
+    
+    /// Encrypted account covered by the switch
+    pub encrypted_account: Pubkey,
+    
+    /// Key that becomes the owner after the inactivity period
+    pub recovery_key: Pubkey,
+    
+    /// Slots without owner activity before recovery is allowed
+    pub inactivity_slots: u64,
+    
+    /// Bump seed for PDA
+    pub bump: u8,
+}
+
+/// M-of-N guardians able to rotate the owner of an encrypted account
+#[account]
+#[derive(InitSpace)]
+pub struct GuardianSet {
+    /// Owner who registered the guardians (updated on recovery)
+    pub owner: Pubkey,
+    
+    /// Encrypted account protected by this set
+    pub encrypted_account: Pubkey,
+    
+    /// Guardian keys
+    #[max_len(7)]
+    pub guardians: Vec<Pubkey>,
+    
+    /// Approvals required to start a recovery
+    pub threshold: u8,
+    
+    /// Veto window after the threshold is reached
+    pub recovery_delay_slots: u64,
+    
+    /// Owner proposed by the pending recovery (default = none)
+    pub proposed_owner: Pubkey,
+    
+    /// Bitmask of approving guardians (bit i = guardians[i])
+    pub approvals: u8,
+    
+    /// Bitmask of guardians backing a different owner than `proposed_owner`
+    pub objections: u8,
+    
+    /// Slot from which the pending recovery can execute (0 = quorum not reached)
+    pub recovery_ready_slot: u64,
+    
+    /// Bump seed for PDA
+    pub bump: u8,
+}
+
+/// Hidden amount locked between a payer and a payee under an arbiter
+#[account]
+#[derive(InitSpace)]
+pub struct ConditionalPayment {
+    /// Wallet that locked the funds (receives the rent back)
+    pub payer: Pubkey,
+    
+    /// Encrypted account the amount was debited from (refund target)
+    pub payer_account: Pubkey,
+    
+    /// Encrypted account the amount is released to
+    pub payee_account: Pubkey,
+    
+    /// Key that may rule either way
+    pub arbiter: Pubkey,
+    
+    /// Payer-chosen identifier (PDA seed)
+    pub payment_id: u64,
+    
+    /// Commitment to the locked amount
+    pub amount_commitment: [u8; 64],
+    
+    /// Bump seed for PDA
+    pub bump: u8,
+}
+
+/// Hidden amount locked under a hashlock and timelock
+#[account]
+#[derive(InitSpace)]
+pub struct HashTimeLock {
+    /// Wallet that locked the funds (receives the rent back)
+    pub sender: Pubkey,
+    
+    /// Encrypted account the amount was debited from (refund target)
+    pub sender_account: Pubkey,
+    
+    /// Encrypted account that can redeem with the preimage
+    pub recipient_account: Pubkey,
+    
+    /// SHA-256 of the preimage
+    pub hashlock: [u8; 32],
+    
+    /// Slot from which only a refund is possible
+    pub expiry_slot: u64,
+    
+    /// Commitment to the locked amount
+    pub amount_commitment: [u8; 64],
+    
+    /// Bump seed for PDA
+    pub bump: u8,
+}
+
+/// A config change queued behind the timelock
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, InitSpace)]
+pub enum ConfigChange {
+    /// Replace the tunable limits (see `update_config`)
+    Limits(ConfigParams),
+    /// Replace the timelock delay itself (slots)
+    TimelockDelay(u64),
+    /// Replace the external verifier whitelist (see `set_verifier_programs`)
+    VerifierPrograms(#[max_len(4)] Vec<Pubkey>),
+    /// Replace the feature flags (see `set_features`)
+    Features(u32),
+    /// Replace the fee settings (see `set_fee_params`)
+    Fees(FeeParams),
+    /// Hand the config authority over (see `set_config_authority`)
+    ConfigAuthority(Pubkey),
+    /// Replace the compliance authority (see `set_compliance_authority`)
+    ComplianceAuthority(Pubkey),
+    /// Replace the regulator quorum (see `set_regulators`)
+    Regulators(RegulatorParams),
+    /// Replace the per-epoch transfer cap (see `set_epoch_transfer_limit`)
+    EpochTransferLimit(u64),
+    /// Replace the per-account balance cap (see `set_max_account_balance`)
+    MaxAccountBalance(u64),
+    /// Replace the deny list root (see `set_deny_list_root`)
+    DenyListRoot([u8; 32]),
+    /// Replace the association set root (see `set_association_root`)
+    AssociationRoot([u8; 32]),
+    /// Replace the sanctions oracle and attestation max age
+    /// (see `set_sanctions_oracle`)
+    SanctionsOracle(Pubkey, u64),
+    /// Replace the KYC mint and recipient requirement (see `set_kyc_gating`)
+    KycGating(Pubkey, bool),
+    /// Replace the travel-rule threshold (see `set_travel_rule_threshold`)
+    TravelRuleThreshold(u64),
+    /// Add or update a mint allowlist entry (applied by
+    /// `add_supported_mint` / `update_supported_mint`)
+    SupportedMint(MintParams),
+    /// Remove a mint allowlist entry (applied by `remove_supported_mint`)
+    RemoveSupportedMint(Pubkey),
+    /// Replace the required transfer verifier (see `set_required_verifier`)
+    RequiredVerifier(Pubkey),
+}
+
+/// Pending config change (at most one; existence = pending)
+#[account]
+#[derive(InitSpace)]
+pub struct PendingConfigChange {
+    /// The queued change
+    pub change: ConfigChange,
+    
+    /// Slot the change was proposed at
+    pub proposed_slot: u64,
+    
+    /// First slot the change can be executed at
+    pub eta_slot: u64,
+    
+    /// Bump seed for PDA
+    pub bump: u8,
+}
+
+/// Protocol fee treasury; collected fees are held as its lamports
+#[account]
+#[derive(InitSpace)]
+pub struct Treasury {
+    /// Lamports collected in fees (all time)
+    pub total_collected: u64,
+    
+    /// Lamports withdrawn by the fee authority (all time)
+    pub total_withdrawn: u64,
+    
+    /// Bump seed for PDA
+    pub bump: u8,
+}
+
+/// Hidden confidential_transfer fees awaiting a sweep (see `fees`)
+#[account]
+#[derive(InitSpace)]
+pub struct ShieldedTreasury {
+    /// Sum of the fee commitments since the last sweep
+    pub encrypted_balance: [u8; 64],
     
-    #[account(
-        mut,
-        seeds = [b"sol-escrow", owner.key().as_ref()],
-        bump = sol_escrow.bump,
-        has_one = owner
-    )]
-    pub sol_escrow: Account<'info, SolEscrow>,
+    /// Incremented on every fee and sweep
+    pub version: u64,
     
-    #[account(mut)]
-    pub owner: Signer<'info>,
+    /// Fees charged (all time)
+    pub fees_charged: u64,
     
-    pub system_program: Program<'info, System>,
+    /// Bump seed for PDA
+    pub bump: u8,
 }
 
-#[derive(Accounts)]
-pub struct ConfidentialSOLTransfer<'info> {
-    #[account(
-        mut,
-        seeds = [b"encrypted-account", sender.key().as_ref()],
-        bump = sender_account.bump,
-    )]
-    pub sender_account: Account<'info, EncryptedAccount>,
+/// Amount-free protocol activity counters (see `stats`)
+#[account]
+#[derive(InitSpace)]
+pub struct ProtocolStats {
+    /// Live encrypted accounts (the anonymity set)
+    pub accounts: u64,
     
-    #[account(
-        mut,
-        seeds = [b"encrypted-account", recipient.key().as_ref()],
-        bump = recipient_account.bump,
-    )]
-    pub recipient_account: Account<'info, EncryptedAccount>,
+    /// Deposits into encrypted accounts and escrows
+    pub deposits: u64,
     
-    #[account(
-        mut,
-        seeds = [b"sol-escrow", sender.key().as_ref()],
-        bump = sender_escrow.bump,
-    )]
-    pub sender_escrow: Account<'info, SolEscrow>,
+    /// Confidential transfers
+    pub transfers: u64,
     
-    #[account(
-        mut,
-        seeds = [b"sol-escrow", recipient.key().as_ref()],
-        bump = recipient_escrow.bump,
-    )]
-    pub recipient_escrow: Account<'info, SolEscrow>,
+    /// Withdrawals out of encrypted accounts and escrows
+    pub withdrawals: u64,
     
-    #[account(mut)]
-    pub sender: Signer<'info>,
+    /// Open SOL and token escrows
+    pub active_escrows: u64,
     
-    /// CHECK: Recipient public key
-    pub recipient: UncheckedAccount<'info>,
+    /// Slot of the last counted activity
+    pub last_update_slot: u64,
     
-    pub system_program: Program<'info, System>,
+    /// Transfers and withdrawals counted in `last_update_slot` (circuit breaker)
+    pub slot_transfers: u32,
+    
+    /// Bump seed for PDA
+    pub bump: u8,
 }
 
+/// Result of verify_stage_a, consumed (and closed) by verify_stage_b
+///
+/// Binds the verified statement to the exact account versions it was
+/// proven against; any update in between makes stage B fail.
 #[account]
 #[derive(InitSpace)]
-pub struct EncryptedAccount {
-    /// Owner of this encrypted account
-    pub owner: Pubkey,
+pub struct PendingVerification {
+    /// Wallet that ran stage A and receives the rent back
+    pub sender: Pubkey,
+    pub sender_account: Pubkey,
+    pub recipient_account: Pubkey,
+    pub sender_version: u64,
+    pub recipient_version: u64,
+    /// Hash of the proof bytes and commitments verified in stage A
+    pub statement_hash: [u8; 32],
+    /// Fiat-Shamir challenge derived in stage A
+    pub challenge: [u8; 32],
+    pub slot: u64,
+    pub bump: u8,
+}
+
+/// Bulletproof generator table (zero-copy, read-only once sealed)
+///
+/// See `generators` for the write flow and digest.
+#[account(zero_copy)]
+pub struct GeneratorTable {
+    /// Config authority that created the table
+    pub authority: Pubkey,
     
-    /// Encrypted balance as Pedersen commitment (64 bytes: 32 for X, 32 for Y)
-    /// This is C = g^balance * h^blinding
-    /// Only the owner can decrypt this with their private key
-    pub encrypted_balance: [u8; 64],
+    /// Generator pairs written so far (high-water mark)
+    pub written: u16,
     
-    /// Version number for tracking updates
-    pub version: u64,
+    /// 1 once sealed by `finalize_generators`
+    pub finalized: u8,
     
     /// Bump seed for PDA
     pub bump: u8,
+    
+    /// Keccak256 over all bases (set when sealed)
+    pub digest: [u8; 32],
+    
+    /// Pedersen value base G
+    pub pedersen_g: [u8; 64],
+    
+    /// Pedersen blinding base H
+    pub pedersen_h: [u8; 64],
+    
+    /// Range-proof vector generators G_i
+    pub g_vec: [[u8; 64]; generators::GENERATOR_CAPACITY],
+    
+    /// Range-proof vector generators H_i
+    pub h_vec: [[u8; 64]; generators::GENERATOR_CAPACITY],
 }
 
-#[account]
-#[derive(InitSpace)]
-pub struct SolEscrow {
-    /// Owner of this escrow account
+/// Chunked proof upload buffer (zero-copy)
+///
+/// Multi-kilobyte proofs exceed what stack-based `Account<>` deserialization
+/// can handle, so this account is accessed in place via `AccountLoader`.
+#[account(zero_copy)]
+pub struct ProofBuffer {
+    /// Owner allowed to write and consume this buffer
     pub owner: Pubkey,
     
-    /// Current SOL balance in lamports
-    /// This is the ACTUAL balance, while encrypted_account stores the ENCRYPTED commitment
-    pub balance: u64,
+    /// Number of proof bytes written so far (high-water mark)
+    pub data_len: u32,
     
     /// Bump seed for PDA
     pub bump: u8,
+    
+    /// Explicit padding for Pod alignment
+    pub _padding: [u8; 3],
+    
+    /// Raw proof bytes (same format as `proof_data` instruction arguments)
+    pub data: [u8; proof_constants::MAX_PROOF_DATA_SIZE],
+}
+
+impl ProofBuffer {
+    /// Buffered proof bytes, borrowed in place from the account data
+    pub fn proof_data(&self) -> &[u8] {
+        &self.data[..self.data_len as usize]
+    }
+    
+    /// Proof to verify: the buffered one when a buffer is passed (the
+    /// instruction's `proof_data` must then be empty), else `proof_data`
+    pub fn select<'a>(buffer: Option<&'a ProofBuffer>, proof_data: &'a [u8]) -> Result<&'a [u8]> {
+        match buffer {
+            Some(buffer) => {
+                require!(proof_data.is_empty(), ErrorCode::InvalidProof);
+                Ok(buffer.proof_data())
+            }
+            None => Ok(proof_data),
+        }
+    }
+}
+
+/// Emitted when a closed account is archived
+/// Leaf = keccak(account || owner || commitment_hash || version || close_slot)
+#[event]
+pub struct AccountArchived {
+    pub account: Pubkey,
+    pub owner: Pubkey,
+    pub commitment_hash: [u8; 32],
+    pub version: u64,
+    pub close_slot: u64,
+    pub leaf_index: u64,
+    pub root: [u8; 32],
+}
+
+/// Emitted by `heartbeat` with the self-test results
+#[event]
+pub struct HeartbeatResult {
+    pub nonce: u64,
+    pub slot: u64,
+    pub transcript_ok: bool,
+    pub curve_ok: bool,
+}
+
+/// Emitted by `check_canary` when a canary account has changed
+/// High priority: indicates an unauthorized mutation path
+#[event]
+pub struct CanaryTripped {
+    pub watched_account: Pubkey,
+    pub expected_hash: [u8; 32],
+    pub observed_hash: [u8; 32],
+    pub expected_lamports: u64,
+    pub observed_lamports: u64,
+    pub armed_slot: u64,
+    pub slot: u64,
+}
+
+/// Emitted when a message is left in a mailbox
+#[event]
+pub struct MessagePosted {
+    pub mailbox: Pubkey,
+    pub message: Pubkey,
+    pub seq: u64,
+    pub slot: u64,
+}
+
+/// Emitted for every note leaf (wallets rebuild the tree from these)
+/// Leaf = keccak(note_commitment || amount)
+#[event]
+pub struct NoteInserted {
+    pub leaf_index: u64,
+    pub note_commitment: [u8; 32],
+    pub amount: u64,
+    pub root: [u8; 32],
+}
+
+/// Emitted when a note is spent
+#[event]
+pub struct NoteSpent {
+    pub nullifier: [u8; 32],
+    pub root: [u8; 32],
+    pub slot: u64,
+}
+
+/// Emitted when a refund is requested (wallets surface it to the refunder)
+#[event]
+pub struct RefundRequested {
+    pub refund_request: Pubkey,
+    pub requester_account: Pubkey,
+    pub refunder_account: Pubkey,
+    pub transfer_ref: [u8; 32],
+}
+
+/// Emitted for every stealth payment (recipients scan these)
+#[event]
+pub struct StealthAnnounced {
+    pub ephemeral_key: [u8; 32],
+    pub view_tag: u8,
+    pub destination: Pubkey,
+    pub slot: u64,
+}
+
+/// Emitted by `confidential_transfer` when the sender attaches a memo
+/// Lets the recipient learn the opening of its new commitment
+#[event]
+pub struct TransferMemo {
+    pub recipient_account: Pubkey,
+    pub encryption_key: [u8; 32],
+    pub recipient_version: u64,
+    pub memo: EncryptedMemo,
+}
+
+/// Emitted by `confidential_transfer` when a hidden fee is charged
+/// Lets the fee authority track the shielded treasury's opening
+#[event]
+pub struct FeeMemo {
+    pub treasury_version: u64,
+    pub memo: EncryptedMemo,
+}
+
+/// Emitted when the config authority publishes a new association set
+#[event]
+pub struct AssociationRootUpdated {
+    pub association_root: [u8; 32],
+    pub slot: u64,
+}
+
+/// Emitted by `publish_balance_view` for the viewing key holder
+#[event]
+pub struct BalanceView {
+    pub encrypted_account: Pubkey,
+    pub viewing_key: [u8; 32],
+    pub version: u64,
+    pub state_hash: [u8; 32],
+    pub view: EncryptedMemo,
+}
+
+/// Emitted when a transfer carries a travel-rule envelope
+#[event]
+pub struct TravelRuleData {
+    pub sender_account: Pubkey,
+    pub recipient_account: Pubkey,
+    pub sender_version: u64,
+    pub envelope: TravelRuleEnvelope,
+}
+
+/// Emitted when the regulator quorum orders a disclosure
+#[event]
+pub struct DisclosureRequested {
+    pub disclosure_request: Pubkey,
+    pub encrypted_account: Pubkey,
+    pub case_id: [u8; 32],
+    pub commitment: [u8; 64],
+    pub deadline_slot: u64,
+}
+
+/// Emitted when an owner answers a disclosure order
+#[event]
+pub struct DisclosureAnswered {
+    pub disclosure_request: Pubkey,
+    pub encrypted_account: Pubkey,
+    pub case_id: [u8; 32],
+    pub commitment: [u8; 64],
+    pub amount: u64,
+}
+
+/// Emitted when an owner exports a selective-disclosure report
+#[event]
+pub struct DisclosureReportExported {
+    pub report: Pubkey,
+    pub encrypted_account: Pubkey,
+    pub from_version: u64,
+    pub to_version: u64,
+    pub min_bound: u64,
+    pub max_bound: u64,
+}
+
+/// Emitted when a two-phase transfer starts (wallets surface it to the recipient)
+#[event]
+pub struct TransferInitiated {
+    pub pending_transfer: Pubkey,
+    pub sender_account: Pubkey,
+    pub recipient_account: Pubkey,
+    pub expiry_slot: u64,
+}
+
+/// Emitted when the recipient claims a pending transfer
+#[event]
+pub struct TransferClaimed {
+    pub pending_transfer: Pubkey,
+    pub recipient_account: Pubkey,
+}
+
+/// Emitted when an expired pending transfer returns to the sender
+#[event]
+pub struct TransferRefunded {
+    pub pending_transfer: Pubkey,
+    pub sender_account: Pubkey,
+}
+
+/// Emitted when a merchant issues an invoice
+#[event]
+pub struct InvoiceCreated {
+    pub invoice: Pubkey,
+    pub recipient_account: Pubkey,
+    pub invoice_id: u64,
+    pub expiry_slot: u64,
+}
+
+/// Emitted when an invoice is paid (merchants watch these at checkout)
+#[event]
+pub struct InvoicePaid {
+    pub invoice: Pubkey,
+    pub payer_account: Pubkey,
+    pub recipient_account: Pubkey,
+    pub invoice_id: u64,
+    pub slot: u64,
+}
+
+/// Emitted when a claim link is redeemed (the creator's wallet marks it used)
+#[event]
+pub struct ClaimRedeemed {
+    pub claim_key: Pubkey,
+    pub recipient_account: Pubkey,
+}
+
+/// Emitted when a payment stream starts (wallets surface it to the recipient)
+#[event]
+pub struct StreamCreated {
+    pub stream: Pubkey,
+    pub sender_account: Pubkey,
+    pub recipient_account: Pubkey,
+    pub start_time: i64,
+    pub end_time: i64,
+}
+
+/// Emitted on every stream claim
+#[event]
+pub struct StreamWithdrawn {
+    pub stream: Pubkey,
+    pub claimed_until: i64,
+}
+
+/// Emitted when a vesting grant is created (wallets surface it to the beneficiary)
+#[event]
+pub struct VestingCreated {
+    pub vesting: Pubkey,
+    pub beneficiary_account: Pubkey,
+    pub start_time: i64,
+    pub cliff_time: i64,
+    pub end_time: i64,
+}
+
+/// Emitted on every subscription pull
+#[event]
+pub struct SubscriptionCharged {
+    pub subscription: Pubkey,
+    pub subscriber_account: Pubkey,
+    pub merchant_account: Pubkey,
+    pub payments_made: u64,
+    pub next_payment_time: i64,
+}
+
+/// Emitted when a dead-man switch hands an account to its recovery key
+#[event]
+pub struct InactiveAccountRecovered {
+    pub encrypted_account: Pubkey,
+    pub previous_owner: Pubkey,
+    pub recovery_key: Pubkey,
+    pub idle_since_slot: u64,
+    pub slot: u64,
+}
+
+/// Emitted on every guardian approval of a recovery
+#[event]
+pub struct RecoveryApproved {
+    pub encrypted_account: Pubkey,
+    pub guardian: Pubkey,
+    pub new_owner: Pubkey,
+    pub approvals: u8,
+    pub threshold: u8,
+    pub recovery_ready_slot: u64,
+}
+
+/// Emitted when a conditional payment is released or refunded
+#[event]
+pub struct ConditionalPaymentSettled {
+    pub payment: Pubkey,
+    pub payment_id: u64,
+    /// True = paid to the payee, false = refunded to the payer
+    pub released: bool,
+    pub by_arbiter: bool,
+}
+
+/// Emitted when an HTLC is redeemed; carries the preimage for the other leg
+#[event]
+pub struct HtlcRedeemed {
+    pub htlc: Pubkey,
+    pub hashlock: [u8; 32],
+    pub preimage: [u8; 32],
+    pub slot: u64,
+}
+
+/// Emitted when an owner proves their commitment matches their SOL escrow
+#[event]
+pub struct AccountAudited {
+    pub encrypted_account: Pubkey,
+    pub sol_escrow: Pubkey,
+    /// Commitment version the audit covers
+    pub version: u64,
+    pub state_hash: [u8; 32],
+    pub slot: u64,
+}
+
+/// Emitted when a config change is queued; users can react before `eta_slot`
+#[event]
+pub struct ConfigChangeProposed {
+    pub change: ConfigChange,
+    pub proposed_slot: u64,
+    pub eta_slot: u64,
+}
+
+/// Emitted when the compliance authority freezes or unfreezes an account
+#[event]
+pub struct AccountFreezeChanged {
+    pub encrypted_account: Pubkey,
+    pub frozen: bool,
+    /// Off-chain reference of the legal order
+    pub case_id: [u8; 32],
+    pub authority: Pubkey,
+    pub slot: u64,
+}
+
+/// Emitted when the generator table is sealed
+#[event]
+pub struct GeneratorsFinalized {
+    pub generator_table: Pubkey,
+    pub digest: [u8; 32],
 }
 
 #[error_code]
@@ -835,4 +16008,313 @@ pub enum ErrorCode {
     
     #[msg("Invalid recipient: Recipient address is invalid or same as sender")]
     InvalidRecipient,
+    
+    #[msg("Invalid new owner: No pending owner or owner unchanged")]
+    InvalidNewOwner,
+    
+    #[msg("Invalid encryption key: Key must be non-zero")]
+    InvalidEncryptionKey,
+    
+    #[msg("Unsupported schema version: Account layout not recognized")]
+    UnsupportedSchemaVersion,
+    
+    #[msg("Account already migrated: Layout is at the current schema version")]
+    AccountAlreadyMigrated,
+    
+    #[msg("Archive tree required: Pass the archive tree account to archive")]
+    ArchiveTreeRequired,
+    
+    #[msg("Archive tree full: No more archival records can be appended")]
+    ArchiveTreeFull,
+    
+    #[msg("Escrow not empty: Withdraw the remaining balance before closing")]
+    EscrowNotEmpty,
+    
+    #[msg("Token-2022 required: Confidential bridging needs the Token-2022 program")]
+    Token2022Required,
+    
+    #[msg("Too many assets: Account already tracks the maximum number of assets")]
+    TooManyAssets,
+    
+    #[msg("Asset already exists: Mint is already tracked by this account")]
+    AssetAlreadyExists,
+    
+    #[msg("Asset not found: Mint is not tracked by this account")]
+    AssetNotFound,
+    
+    #[msg("Asset not empty: Commitment may still hide funds")]
+    AssetNotEmpty,
+    
+    #[msg("Auth code required: This account requires a one-time authorization code")]
+    AuthCodeRequired,
+    
+    #[msg("Invalid auth code: Code does not match the auth chain")]
+    InvalidAuthCode,
+    
+    #[msg("Auth chain exhausted: Reset the auth chain with new codes")]
+    AuthChainExhausted,
+    
+    #[msg("Not native mint: SOL wrapping requires the wSOL mint")]
+    NotNativeMint,
+    
+    #[msg("Receipts outstanding: Burn receipt tokens before moving backed funds")]
+    ReceiptsOutstanding,
+    
+    #[msg("Mint not allowed: Mint is not on the allowlist or is disabled")]
+    MintNotAllowed,
+    
+    #[msg("Invalid canary: Only initialized program accounts can be watched")]
+    InvalidCanary,
+    
+    #[msg("Pool asset reserved: Use deposit_pool / withdraw_pool for pool-backed SOL")]
+    PoolAssetReserved,
+    
+    #[msg("Invalid message: Ciphertext is empty or too large")]
+    InvalidMessage,
+    
+    #[msg("Mailbox rate limited: Wait before posting again")]
+    MailboxRateLimited,
+    
+    #[msg("Note tree full: No more notes can be deposited")]
+    NoteTreeFull,
+    
+    #[msg("Unknown root: Proof is not against a recent note tree root")]
+    UnknownRoot,
+    
+    #[msg("Refund amount mismatch: Proof amount differs from the requested transfer")]
+    RefundAmountMismatch,
+    
+    #[msg("Inbox full: Recipient must prune its inbox first")]
+    InboxFull,
+    
+    #[msg("Invalid withdrawal batch: Wrong accounts or batch size")]
+    InvalidWithdrawalBatch,
+    
+    #[msg("Withdrawal not ready: Release delay has not elapsed")]
+    WithdrawalNotReady,
+    
+    #[msg("Invalid decoy outputs: Wrong count, duplicate or non-program account")]
+    InvalidDecoyOutputs,
+    
+    #[msg("Invalid intent signature: Missing or mismatched Ed25519 authorization")]
+    InvalidIntentSignature,
+    
+    #[msg("Intent expired: Expiry slot has passed")]
+    IntentExpired,
+    
+    #[msg("Association set not configured: No association root has been published")]
+    AssociationSetNotConfigured,
+    
+    #[msg("Invalid viewing key: Key or view envelope is empty")]
+    InvalidViewingKey,
+    
+    #[msg("Invalid travel-rule envelope: Empty key or ciphertext size out of bounds")]
+    InvalidTravelRuleEnvelope,
+    
+    #[msg("Travel-rule envelope required by the configured threshold")]
+    TravelRuleEnvelopeRequired,
+    
+    #[msg("Sanctions attestation required: Missing or issued by another oracle")]
+    SanctionsAttestationRequired,
+    
+    #[msg("Sanctions attestation stale: Destination must be re-screened")]
+    SanctionsAttestationStale,
+    
+    #[msg("Destination sanctioned: Oracle did not clear this address")]
+    DestinationSanctioned,
+    
+    #[msg("Velocity proof required: Epoch transfer limit is enabled")]
+    VelocityProofRequired,
+    
+    #[msg("KYC credential required: Wallet does not hold the configured credential token")]
+    KycCredentialRequired,
+    
+    #[msg("Account frozen: Outgoing operations are blocked")]
+    AccountFrozen,
+    
+    #[msg("Invalid regulator set: Bad size, threshold, window or duplicate key")]
+    InvalidRegulatorSet,
+    
+    #[msg("Disclosure quorum not met: Not enough regulator signatures")]
+    DisclosureQuorumNotMet,
+    
+    #[msg("Disclosure order is not pending")]
+    DisclosureNotPending,
+    
+    #[msg("Disclosure deadline passed")]
+    DisclosureDeadlinePassed,
+    
+    #[msg("Disclosure deadline not reached yet")]
+    DisclosureDeadlineNotReached,
+    
+    #[msg("Exclusion proof required: A deny list is configured")]
+    ExclusionProofRequired,
+    
+    #[msg("Invalid disclosure report: Bad window, bounds or history")]
+    InvalidDisclosureReport,
+    
+    #[msg("Balance cap proof required: A per-account cap is configured")]
+    BalanceCapProofRequired,
+    
+    #[msg("Invalid multi-recipient transfer: Wrong count, duplicate or non-program account")]
+    InvalidMultiTransfer,
+    
+    #[msg("Invalid transfer batch: Wrong count, duplicate or non-program account")]
+    InvalidBatchTransfer,
+    
+    #[msg("Invalid expiry: Claim window out of bounds")]
+    InvalidExpiry,
+    
+    #[msg("Pending transfer expired: Only the sender can reclaim it")]
+    PendingTransferExpired,
+    
+    #[msg("Pending transfer not expired: The recipient can still claim it")]
+    PendingTransferNotExpired,
+    
+    #[msg("Invoice already paid")]
+    InvoiceAlreadyPaid,
+    
+    #[msg("Invoice expired: Ask the merchant for a new one")]
+    InvoiceExpired,
+    
+    #[msg("Invoice amount mismatch: Transfer amount differs from the invoice")]
+    InvoiceAmountMismatch,
+    
+    #[msg("Invalid claim secret: Signer does not match the claim key")]
+    InvalidClaimSecret,
+    
+    #[msg("Claim link expired: Only the creator can reclaim it")]
+    ClaimExpired,
+    
+    #[msg("Claim link not expired: It can still be redeemed")]
+    ClaimNotExpired,
+    
+    #[msg("Invalid stream duration: Out of bounds")]
+    InvalidStreamDuration,
+    
+    #[msg("Nothing accrued: Stream already claimed up to now")]
+    StreamNothingAccrued,
+    
+    #[msg("Stream ended")]
+    StreamEnded,
+    
+    #[msg("Stream not settled: Recipient can still claim accruals")]
+    StreamNotSettled,
+    
+    #[msg("Invalid vesting schedule: Bad duration or cliff, or schedule not over")]
+    InvalidVestingSchedule,
+    
+    #[msg("Vesting cliff not reached")]
+    VestingCliffNotReached,
+    
+    #[msg("Invalid subscription period")]
+    InvalidSubscriptionPeriod,
+    
+    #[msg("Subscription period has not elapsed yet")]
+    SubscriptionPeriodNotElapsed,
+    
+    #[msg("Pulled amount does not match the authorized subscription amount")]
+    SubscriptionAmountMismatch,
+    
+    #[msg("Invalid delegate: Cannot delegate to yourself")]
+    InvalidDelegate,
+    
+    #[msg("Invalid session key or session parameters")]
+    InvalidSessionKey,
+    
+    #[msg("Session key expired")]
+    SessionKeyExpired,
+    
+    #[msg("Recipient not allowed for this session key")]
+    RecipientNotAllowed,
+    
+    #[msg("Invalid inactivity period")]
+    InvalidInactivityPeriod,
+    
+    #[msg("Owner is still active: Inactivity period has not elapsed")]
+    OwnerStillActive,
+    
+    #[msg("Invalid guardian set: Bad guardians, threshold or delay")]
+    InvalidGuardianSet,
+    
+    #[msg("A recovery to a different owner is already in progress")]
+    RecoveryInProgress,
+    
+    #[msg("No recovery pending")]
+    NoRecoveryPending,
+    
+    #[msg("Recovery veto window has not passed yet")]
+    RecoveryDelayActive,
+    
+    #[msg("Invalid arbiter: Must be a third party")]
+    InvalidArbiter,
+    
+    #[msg("Preimage does not match the hashlock")]
+    InvalidPreimage,
+    
+    #[msg("HTLC expired")]
+    HtlcExpired,
+    
+    #[msg("HTLC has not expired yet")]
+    HtlcNotExpired,
+    
+    #[msg("Account version changed since the proof was built; rebuild against the current state")]
+    StaleAccountVersion,
+    
+    #[msg("Invalid config parameters: Limits must stay within compiled-in bounds")]
+    InvalidConfigParams,
+    
+    #[msg("Operation paused by the config authority")]
+    OperationPaused,
+    
+    #[msg("Config timelock is set: Use propose_config_change")]
+    TimelockRequired,
+    
+    #[msg("Config change timelock has not elapsed yet")]
+    TimelockNotElapsed,
+    
+    #[msg("Verifier program is not whitelisted in the config")]
+    VerifierNotApproved,
+    
+    #[msg("Config requires a specific verifier program for transfers")]
+    VerifierRequired,
+    
+    #[msg("Feature disabled for this deployment")]
+    FeatureDisabled,
+    
+    #[msg("Fee treasury account required while a protocol fee applies")]
+    TreasuryRequired,
+    
+    #[msg("Fee proof required: Protocol fee applies to this transfer")]
+    FeeProofRequired,
+    
+    #[msg("Account not frozen: Nothing to unfreeze")]
+    AccountNotFrozen,
+    
+    #[msg("Stats account required: Circuit breaker is enabled")]
+    StatsRequired,
+    
+    #[msg("Circuit breaker tripped: Too many transfers or withdrawals in this slot")]
+    CircuitBreakerTripped,
+    
+    #[msg("Invalid generator: Zero, duplicate or out-of-range point")]
+    InvalidGenerator,
+    
+    #[msg("Generator table is sealed")]
+    GeneratorsSealed,
+    
+    #[msg("Generator table incomplete: Write all generators before sealing")]
+    GeneratorsIncomplete,
+    
+    #[msg("Invalid account size: Below the current layout or above the reserve limit")]
+    InvalidAccountSize,
+    
+    #[msg("Invalid verifying key: Unknown circuit or wrong input count")]
+    InvalidVerifyingKey,
+    
+    #[msg("Pending config change does not match this instruction")]
+    QueuedChangeMismatch,
+    
+    #[msg("Invalid denomination: Pool amounts must be one of the fixed denominations")]
+    InvalidDenomination,
 }
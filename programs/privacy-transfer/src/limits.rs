@@ -0,0 +1,184 @@
+/*!
+ * Deployment-Tuned Limits
+ *
+ * The config can tighten the compiled-in amount ceiling, proof size bounds
+ * and range-proof width without a redeploy (see `update_config`). A zero
+ * field keeps the compiled-in default; `update_config` never lets a limit
+ * exceed its compiled-in bound, so these checks only ever add to the
+ * constant checks each instruction already performs.
+ *
+ * `max_transfers_per_slot` is a circuit breaker: once that many transfers
+ * and withdrawals landed in the current slot (counted in the stats PDA),
+ * further ones fail until the next slot. Normal traffic stays well below
+ * the cap.
+ */
+
+use anchor_lang::prelude::*;
+
+use crate::proof_verification::deserialize_proof_data;
+use crate::{proof_constants, stats, transfer_constants, Config, ConfigParams, ErrorCode};
+
+/**
+ * Reject an amount above the configured ceiling
+ */
+pub fn enforce_amount(config: Option<&Config>, amount: u64) -> Result<()> {
+    let max_amount = match config.map_or(0, |config| config.max_amount) {
+        0 => transfer_constants::MAX_AMOUNT,
+        max_amount => max_amount,
+    };
+    require!(amount <= max_amount, ErrorCode::InvalidAmount);
+    Ok(())
+}
+
+/**
+ * Reject proof data outside the configured size bounds
+ */
+pub fn enforce_proof_size(config: Option<&Config>, proof_data: &[u8]) -> Result<()> {
+    let min_size = match config.map_or(0, |config| config.min_proof_data_size) {
+        0 => proof_constants::MIN_PROOF_DATA_SIZE,
+        min_size => min_size as usize,
+    };
+    let max_size = match config.map_or(0, |config| config.max_proof_data_size) {
+        0 => proof_constants::MAX_PROOF_DATA_SIZE,
+        max_size => max_size as usize,
+    };
+    require!(
+        proof_data.len() >= min_size && proof_data.len() <= max_size,
+        ErrorCode::InvalidProof
+    );
+    Ok(())
+}
+
+/**
+ * Reject a transfer proof whose amount range proof is wider than configured
+ */
+pub fn enforce_range_bits(config: Option<&Config>, proof_data: &[u8]) -> Result<()> {
+    let range_bits = config.map_or(0, |config| config.range_bits);
+    if range_bits == 0 {
+        return Ok(());
+    }
+
+    let proof = deserialize_proof_data(proof_data).map_err(|e| {
+        msg!("⚠️  Proof deserialization error: {:?}", e);
+        ErrorCode::InvalidProof
+    })?;
+    require!(proof.amount_range_proof.n <= range_bits, ErrorCode::InvalidProof);
+    Ok(())
+}
+
+/**
+ * Reject a transfer or withdrawal once the current slot reached the configured cap
+ *
+ * `stats` must already be seed-checked; it has to be initialized while a
+ * cap is set.
+ */
+pub fn enforce_transfer_rate(config: Option<&Config>, stats: &AccountInfo) -> Result<()> {
+    let cap = config.map_or(0, |config| config.max_transfers_per_slot);
+    if cap == 0 {
+        return Ok(());
+    }
+
+    let stats = stats::load_optional(stats)?.ok_or(ErrorCode::StatsRequired)?;
+    let in_slot = if stats.last_update_slot == Clock::get()?.slot {
+        stats.slot_transfers
+    } else {
+        0
+    };
+    require!(in_slot < cap, ErrorCode::CircuitBreakerTripped);
+    Ok(())
+}
+
+/**
+ * Validate new limits against the compiled-in bounds
+ */
+pub fn validate_params(params: &ConfigParams) -> Result<()> {
+    let min_size = match params.min_proof_data_size {
+        0 => proof_constants::MIN_PROOF_DATA_SIZE,
+        min_size => min_size as usize,
+    };
+    let max_size = match params.max_proof_data_size {
+        0 => proof_constants::MAX_PROOF_DATA_SIZE,
+        max_size => max_size as usize,
+    };
+    require!(
+        params.max_amount <= transfer_constants::MAX_AMOUNT
+            && min_size >= proof_constants::MIN_PROOF_DATA_SIZE
+            && max_size <= proof_constants::MAX_PROOF_DATA_SIZE
+            && min_size <= max_size
+            && params.range_bits <= proof_constants::DEFAULT_RANGE_BITS,
+        ErrorCode::InvalidConfigParams
+    );
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Config with every field zeroed (compiled-in limits)
+    fn config() -> Config {
+        let zeroed = vec![0u8; Config::INIT_SPACE];
+        Config::deserialize(&mut &zeroed[..]).unwrap()
+    }
+
+    fn params() -> ConfigParams {
+        ConfigParams {
+            max_amount: 0,
+            min_proof_data_size: 0,
+            max_proof_data_size: 0,
+            range_bits: 0,
+            max_transfers_per_slot: 0,
+        }
+    }
+
+    #[test]
+    fn zero_limits_fall_back_to_the_compiled_in_bounds() {
+        let config = config();
+        for config in [None, Some(&config)] {
+            assert!(enforce_amount(config, transfer_constants::MAX_AMOUNT).is_ok());
+            assert!(enforce_amount(config, transfer_constants::MAX_AMOUNT + 1).is_err());
+            assert!(enforce_proof_size(config, &[0u8; proof_constants::MIN_PROOF_DATA_SIZE]).is_ok());
+            assert!(enforce_proof_size(config, &[0u8; proof_constants::MIN_PROOF_DATA_SIZE - 1]).is_err());
+            assert!(enforce_proof_size(config, &[0u8; proof_constants::MAX_PROOF_DATA_SIZE + 1]).is_err());
+            assert!(enforce_range_bits(config, &[]).is_ok());
+        }
+    }
+
+    #[test]
+    fn configured_limits_tighten_the_checks() {
+        let mut config = config();
+        config.max_amount = 1_000;
+        config.min_proof_data_size = 100;
+        config.max_proof_data_size = 200;
+
+        assert!(enforce_amount(Some(&config), 1_000).is_ok());
+        assert_eq!(
+            enforce_amount(Some(&config), 1_001).unwrap_err(),
+            ErrorCode::InvalidAmount.into()
+        );
+        assert!(enforce_proof_size(Some(&config), &[0u8; 150]).is_ok());
+        assert!(enforce_proof_size(Some(&config), &[0u8; 99]).is_err());
+        assert!(enforce_proof_size(Some(&config), &[0u8; 201]).is_err());
+
+        // A width limit needs a parseable transfer proof
+        config.range_bits = 32;
+        assert!(enforce_range_bits(Some(&config), &[0u8; 150]).is_err());
+    }
+
+    #[test]
+    fn params_cannot_exceed_the_compiled_in_bounds() {
+        assert!(validate_params(&params()).is_ok());
+        assert!(validate_params(&ConfigParams { max_amount: 1_000, range_bits: 32, ..params() }).is_ok());
+
+        let rejected = [
+            ConfigParams { max_amount: transfer_constants::MAX_AMOUNT + 1, ..params() },
+            ConfigParams { min_proof_data_size: 1, ..params() },
+            ConfigParams { max_proof_data_size: 20_000, ..params() },
+            ConfigParams { min_proof_data_size: 500, max_proof_data_size: 400, ..params() },
+            ConfigParams { range_bits: proof_constants::DEFAULT_RANGE_BITS + 1, ..params() },
+        ];
+        for params in &rejected {
+            assert_eq!(validate_params(params).unwrap_err(), ErrorCode::InvalidConfigParams.into());
+        }
+    }
+}
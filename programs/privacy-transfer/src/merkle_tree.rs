@@ -0,0 +1,210 @@
+/*!
+ * Incremental Merkle Tree (BPF-Compatible)
+ *
+ * Append-only Merkle tree that stores only the current root and the
+ * "frontier" (rightmost filled subtree at each level), so on-chain state is
+ * O(depth) regardless of the number of leaves. Leaves themselves live
+ * off-chain (reconstructed from emitted events) and membership is proven
+ * against the stored root.
+ *
+ * Hash: Keccak256(left || right), with empty subtrees hashed from a zero leaf.
+ */
+
+use sha3::{Digest, Keccak256};
+
+/// Hash of an empty leaf
+pub const ZERO_LEAF: [u8; 32] = [0u8; 32];
+
+/// Error codes for Merkle tree operations
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MerkleTreeError {
+    /// All 2^depth leaves are in use
+    TreeFull,
+    /// Proof length does not match tree depth
+    InvalidProofLength,
+}
+
+/**
+ * Hash two child nodes into their parent
+ */
+pub fn hash_pair(left: &[u8; 32], right: &[u8; 32]) -> [u8; 32] {
+    let mut hasher = Keccak256::new();
+    hasher.update(left);
+    hasher.update(right);
+    hasher.finalize().into()
+}
+
+/**
+ * Hash arbitrary leaf data into a 32-byte leaf
+ */
+pub fn hash_leaf(parts: &[&[u8]]) -> [u8; 32] {
+    let mut hasher = Keccak256::new();
+    for part in parts {
+        hasher.update(part);
+    }
+    hasher.finalize().into()
+}
+
+/**
+ * Root of an empty subtree of the given height
+ */
+pub fn zero_hash(height: usize) -> [u8; 32] {
+    let mut node = ZERO_LEAF;
+    for _ in 0..height {
+        node = hash_pair(&node, &node);
+    }
+    node
+}
+
+/**
+ * Root of an empty tree whose depth is `filled_subtrees.len()`
+ */
+pub fn empty_root(depth: usize) -> [u8; 32] {
+    zero_hash(depth)
+}
+
+/**
+ * Append a leaf to the tree
+ *
+ * `filled_subtrees` holds one node per level (its length is the tree depth)
+ * and `next_index` is the number of leaves inserted so far.
+ * Returns (leaf_index, new_root).
+ */
+pub fn insert_leaf(
+    filled_subtrees: &mut [[u8; 32]],
+    next_index: &mut u64,
+    leaf: [u8; 32],
+) -> Result<(u64, [u8; 32]), MerkleTreeError> {
+    let depth = filled_subtrees.len();
+    if *next_index >= 1u64 << depth {
+        return Err(MerkleTreeError::TreeFull);
+    }
+
+    let leaf_index = *next_index;
+    let mut index = leaf_index;
+    let mut node = leaf;
+    let mut zero = ZERO_LEAF;
+
+    for subtree in filled_subtrees.iter_mut() {
+        if index & 1 == 0 {
+            // Left child: remember it and pair with an empty right sibling
+            *subtree = node;
+            node = hash_pair(&node, &zero);
+        } else {
+            // Right child: pair with the stored left sibling
+            node = hash_pair(subtree, &node);
+        }
+        zero = hash_pair(&zero, &zero);
+        index /= 2;
+    }
+
+    *next_index += 1;
+    Ok((leaf_index, node))
+}
+
+/**
+ * Compute the root implied by a leaf and its authentication path
+ *
+ * `siblings[i]` is the sibling at level i (leaf level first).
+ */
+#[allow(dead_code)] // Reserved for membership proofs against stored roots
+pub fn compute_root(
+    leaf: &[u8; 32],
+    leaf_index: u64,
+    siblings: &[[u8; 32]],
+    depth: usize,
+) -> Result<[u8; 32], MerkleTreeError> {
+    if siblings.len() != depth {
+        return Err(MerkleTreeError::InvalidProofLength);
+    }
+
+    let mut index = leaf_index;
+    let mut node = *leaf;
+    for sibling in siblings {
+        node = if index & 1 == 0 {
+            hash_pair(&node, sibling)
+        } else {
+            hash_pair(sibling, &node)
+        };
+        index /= 2;
+    }
+    Ok(node)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DEPTH: usize = 4;
+
+    fn leaf(i: u8) -> [u8; 32] {
+        hash_leaf(&[b"leaf", &[i]])
+    }
+
+    /// Root of a full tree over `leaves` (padded with zero leaves)
+    fn naive_root(leaves: &[[u8; 32]]) -> [u8; 32] {
+        let mut level = leaves.to_vec();
+        level.resize(1 << DEPTH, ZERO_LEAF);
+        while level.len() > 1 {
+            level = level.chunks(2).map(|pair| hash_pair(&pair[0], &pair[1])).collect();
+        }
+        level[0]
+    }
+
+    /// Authentication path of `index` in the padded tree
+    fn path(leaves: &[[u8; 32]], mut index: usize) -> Vec<[u8; 32]> {
+        let mut level = leaves.to_vec();
+        level.resize(1 << DEPTH, ZERO_LEAF);
+        let mut siblings = Vec::new();
+        while level.len() > 1 {
+            siblings.push(level[index ^ 1]);
+            level = level.chunks(2).map(|pair| hash_pair(&pair[0], &pair[1])).collect();
+            index /= 2;
+        }
+        siblings
+    }
+
+    #[test]
+    fn incremental_root_matches_the_full_tree() {
+        let mut filled = [[0u8; 32]; DEPTH];
+        let mut next_index = 0;
+        let mut leaves = Vec::new();
+        assert_eq!(empty_root(DEPTH), naive_root(&leaves));
+
+        for i in 0..5 {
+            leaves.push(leaf(i));
+            let (index, root) = insert_leaf(&mut filled, &mut next_index, leaf(i)).unwrap();
+            assert_eq!(index, i as u64);
+            assert_eq!(root, naive_root(&leaves));
+        }
+    }
+
+    #[test]
+    fn paths_recompute_the_root() {
+        let leaves: Vec<_> = (0..6).map(leaf).collect();
+        let root = naive_root(&leaves);
+        for (i, leaf) in leaves.iter().enumerate() {
+            let siblings = path(&leaves, i);
+            assert_eq!(compute_root(leaf, i as u64, &siblings, DEPTH), Ok(root));
+            assert_ne!(compute_root(leaf, (i ^ 1) as u64, &siblings, DEPTH), Ok(root));
+        }
+        assert_eq!(
+            compute_root(&leaves[0], 0, &path(&leaves, 0)[1..], DEPTH),
+            Err(MerkleTreeError::InvalidProofLength)
+        );
+    }
+
+    #[test]
+    fn rejects_inserts_into_a_full_tree() {
+        let mut filled = [[0u8; 32]; 2];
+        let mut next_index = 0;
+        for i in 0..4 {
+            insert_leaf(&mut filled, &mut next_index, leaf(i)).unwrap();
+        }
+        assert_eq!(
+            insert_leaf(&mut filled, &mut next_index, leaf(4)),
+            Err(MerkleTreeError::TreeFull)
+        );
+        assert_eq!(next_index, 4);
+    }
+}
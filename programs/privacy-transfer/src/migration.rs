@@ -0,0 +1,356 @@
+/*!
+ * Account Layout Migration Framework
+ *
+ * Upgrades existing EncryptedAccount / SolEscrow accounts in place when the
+ * on-chain layout changes (e.g., ElGamal fields, pending balances).
+ *
+ * LAYOUT RULES:
+ * 1. Layout changes are append-only: new fields go after `schema_version`
+ * 2. `schema_version` therefore lives at a fixed offset per account type
+ * 3. Each migration step upgrades exactly one version (N -> N+1) by
+ *    growing the account and inserting default values for the new fields
+ *    at their layout offset (after the previous layout's last field, which
+ *    may follow a variable-length vector); anything past that offset, such
+ *    as bytes reserved by `resize_account`, moves up
+ *
+ * Legacy accounts (pre-`account_index`, no schema byte, PDA seeds
+ * [prefix, owner]) cannot be upgraded in place: their address is not
+ * derivable from the current seeds. `migrate_legacy_account` /
+ * `migrate_legacy_escrow` move them into a freshly created current-layout
+ * account at [prefix, owner, account_index] and close the legacy one.
+ *
+ * Adding a layout change:
+ * - Append the new fields to the account struct
+ * - Bump the account type's *_SCHEMA_VERSION
+ * - Register a MigrationStep { from: previous version, .. } below
+ * - Teach account_decoder.rs the new layout
+ */
+
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{transfer, Transfer};
+
+use crate::{AssetBalance, ErrorCode};
+
+/// Current EncryptedAccount schema version (written for new accounts)
+/// v1: account_index, seed_owner, pending_owner, encryption_key
+/// v2: multi-asset entries (`assets` vector)
+/// v3: tamper-evident `state_hash` chain
+/// v4: `frozen` flag (compliance freezes)
+/// v5: `last_activity_slot` (dead-man switch)
+pub const ENCRYPTED_ACCOUNT_SCHEMA_VERSION: u8 = 5;
+
+/// Current SolEscrow schema version (written for new accounts)
+/// v1: account_index, seed_owner, pending_owner
+/// v2: receipt token accounting (`receipts_outstanding`)
+pub const SOL_ESCROW_SCHEMA_VERSION: u8 = 2;
+
+/// Byte offset of `schema_version` in a serialized EncryptedAccount
+/// discriminator(8) + owner(32) + encrypted_balance(64) + version(8) + bump(1)
+/// + account_index(2) + seed_owner(32) + pending_owner(32) + encryption_key(32)
+pub const ENCRYPTED_ACCOUNT_SCHEMA_OFFSET: usize = 8 + 32 + 64 + 8 + 1 + 2 + 32 + 32 + 32;
+
+/// Byte offset of `schema_version` in a serialized SolEscrow
+/// discriminator(8) + owner(32) + balance(8) + bump(1)
+/// + account_index(2) + seed_owner(32) + pending_owner(32)
+pub const SOL_ESCROW_SCHEMA_OFFSET: usize = 8 + 32 + 8 + 1 + 2 + 32 + 32;
+
+/// Largest reserve `resize_account` keeps past an EncryptedAccount's
+/// current layout (room for future optional fields)
+pub const MAX_RESERVED_BYTES: usize = 1_024;
+
+/// Byte offset of the `owner` field (same for every account type)
+pub const OWNER_OFFSET: usize = 8;
+
+/// A single in-place upgrade from schema version `from` to `from + 1`
+pub struct MigrationStep {
+    /// Schema version this step upgrades from
+    pub from: u8,
+    /// Byte offset the new fields go to, computed from the `from` layout
+    /// (None if the data is too short for it)
+    pub offset: fn(&[u8]) -> Option<usize>,
+    /// Serialized default values of the new fields, in layout order
+    pub defaults: &'static [u8],
+}
+
+/// Registered EncryptedAccount migrations (ordered by `from`)
+pub const ENCRYPTED_ACCOUNT_MIGRATIONS: &[MigrationStep] = &[
+    // v1 -> v2: empty `assets` vector (u32 length prefix = 0)
+    MigrationStep {
+        from: 1,
+        offset: |_| Some(ENCRYPTED_ACCOUNT_SCHEMA_OFFSET + 1),
+        defaults: &[0; 4],
+    },
+    // v2 -> v3: `state_hash` after `assets` (all zeros = chain genesis)
+    MigrationStep {
+        from: 2,
+        offset: encrypted_account_assets_end,
+        defaults: &[0; 32],
+    },
+    // v3 -> v4: `frozen` after `state_hash` (0 = not frozen)
+    MigrationStep {
+        from: 3,
+        offset: |data| encrypted_account_assets_end(data).map(|end| end + 32),
+        defaults: &[0],
+    },
+    // v4 -> v5: `last_activity_slot` after `frozen` (0 = never recorded;
+    // arming a dead-man switch records activity first)
+    MigrationStep {
+        from: 4,
+        offset: |data| encrypted_account_assets_end(data).map(|end| end + 32 + 1),
+        defaults: &[0; 8],
+    },
+];
+
+/// Registered SolEscrow migrations (ordered by `from`)
+pub const SOL_ESCROW_MIGRATIONS: &[MigrationStep] = &[
+    // v1 -> v2: `receipts_outstanding` (zero = no receipts minted)
+    MigrationStep {
+        from: 1,
+        offset: |_| Some(SOL_ESCROW_SCHEMA_OFFSET + 1),
+        defaults: &[0; 8],
+    },
+];
+
+/// End of the `assets` vector of an EncryptedAccount at schema v2 or later
+fn encrypted_account_assets_end(data: &[u8]) -> Option<usize> {
+    let start = ENCRYPTED_ACCOUNT_SCHEMA_OFFSET + 1;
+    let count = u32::from_le_bytes(data.get(start..start + 4)?.try_into().ok()?);
+    let end = start + 4 + (count as usize).checked_mul(AssetBalance::INIT_SPACE)?;
+    (end <= data.len()).then_some(end)
+}
+
+/**
+ * Insert a step's default values at `offset` in a grown account
+ *
+ * `data` has already been grown by `defaults.len()` bytes past `old_len`;
+ * whatever followed the insertion point (later fields, reserved bytes)
+ * shifts up so every field stays at its layout offset.
+ */
+pub fn insert_fields(data: &mut [u8], old_len: usize, offset: usize, defaults: &[u8]) {
+    data.copy_within(offset..old_len, offset + defaults.len());
+    data[offset..offset + defaults.len()].copy_from_slice(defaults);
+}
+
+/**
+ * Read the schema version of a raw account
+ */
+pub fn read_schema_version(data: &[u8], schema_offset: usize) -> Result<u8> {
+    data.get(schema_offset)
+        .copied()
+        .ok_or_else(|| ErrorCode::UnsupportedSchemaVersion.into())
+}
+
+/**
+ * Apply all pending migration steps to an account
+ *
+ * Tops up rent from `payer` whenever a step grows the account.
+ * Returns the schema version the account ended at.
+ */
+pub fn migrate_in_place<'info>(
+    account: &AccountInfo<'info>,
+    payer: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    schema_offset: usize,
+    current_version: u8,
+    steps: &[MigrationStep],
+) -> Result<u8> {
+    let mut version = read_schema_version(&account.try_borrow_data()?, schema_offset)?;
+
+    // SECURITY: Never touch accounts written by a newer program version
+    require!(
+        version <= current_version,
+        ErrorCode::UnsupportedSchemaVersion
+    );
+    require!(
+        version < current_version,
+        ErrorCode::AccountAlreadyMigrated
+    );
+
+    while version < current_version {
+        let step = steps
+            .iter()
+            .find(|step| step.from == version)
+            .ok_or(ErrorCode::UnsupportedSchemaVersion)?;
+
+        let old_len = account.data_len();
+        let offset = (step.offset)(&account.try_borrow_data()?)
+            .filter(|&offset| offset <= old_len)
+            .ok_or(ErrorCode::UnsupportedSchemaVersion)?;
+        
+        // Fund the larger account before reallocating
+        let new_len = old_len + step.defaults.len();
+        let required = Rent::get()?.minimum_balance(new_len);
+        let current = account.lamports();
+        if required > current {
+            transfer(
+                CpiContext::new(
+                    system_program.clone(),
+                    Transfer {
+                        from: payer.clone(),
+                        to: account.clone(),
+                    },
+                ),
+                required - current,
+            )?;
+        }
+        account.resize(new_len)?;
+
+        let mut data = account.try_borrow_mut_data()?;
+        insert_fields(&mut data, old_len, offset, step.defaults);
+        version += 1;
+        data[schema_offset] = version;
+
+        msg!("Migrated account schema {} -> {}", step.from, version);
+    }
+
+    Ok(version)
+}
+
+/**
+ * Close a migrated legacy account, refunding its remaining lamports
+ *
+ * The account is handed back to the system program with no data, so its
+ * legacy address can never be read as a program account again.
+ */
+pub fn close_legacy<'info>(
+    account: &AccountInfo<'info>,
+    destination: &AccountInfo<'info>,
+) -> Result<()> {
+    let new_destination_lamports = destination.lamports().checked_add(account.lamports())
+        .ok_or(ErrorCode::Overflow)?;
+    **destination.try_borrow_mut_lamports()? = new_destination_lamports;
+    **account.try_borrow_mut_lamports()? = 0;
+
+    account.assign(&anchor_lang::system_program::ID);
+    account.resize(0).map_err(Into::into)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{EncryptedAccount, SolEscrow};
+
+    fn account(assets: usize) -> EncryptedAccount {
+        EncryptedAccount {
+            owner: Pubkey::new_unique(),
+            encrypted_balance: [7u8; 64],
+            version: 42,
+            bump: 254,
+            account_index: 3,
+            seed_owner: Pubkey::new_unique(),
+            pending_owner: Pubkey::default(),
+            encryption_key: [9u8; 32],
+            schema_version: ENCRYPTED_ACCOUNT_SCHEMA_VERSION,
+            assets: (0..assets)
+                .map(|i| AssetBalance {
+                    mint: Pubkey::new_unique(),
+                    encrypted_balance: [i as u8 + 1; 64],
+                })
+                .collect(),
+            state_hash: [0u8; 32],
+            frozen: false,
+            last_activity_slot: 0,
+        }
+    }
+
+    fn serialize<T: AccountSerialize>(account: &T) -> Vec<u8> {
+        let mut data = Vec::new();
+        account.try_serialize(&mut data).unwrap();
+        data
+    }
+
+    /// Serialized account cut back to the layout of `version`
+    fn at_version(mut data: Vec<u8>, schema_offset: usize, version: u8, len: usize) -> Vec<u8> {
+        data.truncate(len);
+        data[schema_offset] = version;
+        data
+    }
+
+    /// migrate_in_place without the account plumbing (steps run in order)
+    fn migrate(data: &mut Vec<u8>, schema_offset: usize, steps: &[MigrationStep]) {
+        let from = data[schema_offset];
+        for step in steps.iter().filter(|step| step.from >= from) {
+            assert_eq!(step.from, data[schema_offset]);
+            let old_len = data.len();
+            let offset = (step.offset)(data).unwrap();
+            data.resize(old_len + step.defaults.len(), 0);
+            insert_fields(data, old_len, offset, step.defaults);
+            data[schema_offset] += 1;
+        }
+    }
+
+    #[test]
+    fn encrypted_account_migrates_from_v1() {
+        let expected = account(0);
+        let mut data = at_version(
+            serialize(&expected),
+            ENCRYPTED_ACCOUNT_SCHEMA_OFFSET,
+            1,
+            ENCRYPTED_ACCOUNT_SCHEMA_OFFSET + 1,
+        );
+
+        migrate(&mut data, ENCRYPTED_ACCOUNT_SCHEMA_OFFSET, ENCRYPTED_ACCOUNT_MIGRATIONS);
+        assert_eq!(data, serialize(&expected));
+    }
+
+    #[test]
+    fn fields_after_assets_land_at_their_layout_offset() {
+        let expected = account(2);
+        let v2_len = ENCRYPTED_ACCOUNT_SCHEMA_OFFSET + 1 + 4 + 2 * AssetBalance::INIT_SPACE;
+        let mut data = at_version(serialize(&expected), ENCRYPTED_ACCOUNT_SCHEMA_OFFSET, 2, v2_len);
+        // Reserved bytes past the layout (resize_account)
+        data.extend_from_slice(&[0xaa; 16]);
+
+        migrate(&mut data, ENCRYPTED_ACCOUNT_SCHEMA_OFFSET, ENCRYPTED_ACCOUNT_MIGRATIONS);
+
+        let (layout, reserved) = data.split_at(data.len() - 16);
+        assert_eq!(layout, serialize(&expected).as_slice());
+        assert_eq!(reserved, &[0xaa; 16]);
+        let migrated = EncryptedAccount::try_deserialize(&mut &data[..]).unwrap();
+        assert_eq!(migrated.assets.len(), 2);
+        assert_eq!(migrated.assets[1].encrypted_balance, [2u8; 64]);
+    }
+
+    #[test]
+    fn sol_escrow_migrates_from_v1() {
+        let expected = SolEscrow {
+            owner: Pubkey::new_unique(),
+            balance: 5_000,
+            bump: 253,
+            account_index: 1,
+            seed_owner: Pubkey::new_unique(),
+            pending_owner: Pubkey::default(),
+            schema_version: SOL_ESCROW_SCHEMA_VERSION,
+            receipts_outstanding: 0,
+        };
+        let v1_len = SOL_ESCROW_SCHEMA_OFFSET + 1;
+        let mut data = at_version(serialize(&expected), SOL_ESCROW_SCHEMA_OFFSET, 1, v1_len);
+
+        migrate(&mut data, SOL_ESCROW_SCHEMA_OFFSET, SOL_ESCROW_MIGRATIONS);
+        assert_eq!(data, serialize(&expected));
+    }
+
+    #[test]
+    fn every_version_has_a_step() {
+        for version in 1..ENCRYPTED_ACCOUNT_SCHEMA_VERSION {
+            assert!(ENCRYPTED_ACCOUNT_MIGRATIONS.iter().any(|step| step.from == version));
+        }
+        for version in 1..SOL_ESCROW_SCHEMA_VERSION {
+            assert!(SOL_ESCROW_MIGRATIONS.iter().any(|step| step.from == version));
+        }
+    }
+
+    #[test]
+    fn rejects_an_assets_vector_past_the_data() {
+        let mut data = at_version(
+            serialize(&account(0)),
+            ENCRYPTED_ACCOUNT_SCHEMA_OFFSET,
+            2,
+            ENCRYPTED_ACCOUNT_SCHEMA_OFFSET + 1 + 4,
+        );
+        data[ENCRYPTED_ACCOUNT_SCHEMA_OFFSET + 1] = 1;
+        assert_eq!(encrypted_account_assets_end(&data), None);
+        data[ENCRYPTED_ACCOUNT_SCHEMA_OFFSET + 1..].fill(0xff);
+        assert_eq!(encrypted_account_assets_end(&data), None);
+    }
+}
@@ -0,0 +1,563 @@
+/*!
+ * Canonical PDA Seeds and Derivation Helpers
+ *
+ * Single source of truth for every program-derived address, so integrators
+ * and client SDKs derive escrow PDAs exactly like the program does.
+ *
+ * Token escrows mirror associated-token-account semantics: there is exactly
+ * one escrow (and one vault) per (owner, mint) pair.
+ */
+
+use anchor_lang::prelude::Pubkey;
+
+/// Seed prefix for EncryptedAccount PDAs: [prefix, owner, account_index (u16 LE)]
+pub const ENCRYPTED_ACCOUNT_SEED: &[u8] = b"encrypted-account";
+
+/// Seed prefix for SolEscrow PDAs: [prefix, owner, account_index (u16 LE)]
+pub const SOL_ESCROW_SEED: &[u8] = b"sol-escrow";
+
+/// Seed prefix for TokenEscrow PDAs: [prefix, owner, mint]
+pub const TOKEN_ESCROW_SEED: &[u8] = b"token-escrow";
+
+/// Seed prefix for token vault PDAs: [prefix, token_escrow]
+pub const TOKEN_VAULT_SEED: &[u8] = b"token-vault";
+
+/// Seed prefix for confidential vault PDAs: [prefix, token_escrow]
+pub const CONFIDENTIAL_VAULT_SEED: &[u8] = b"confidential-vault";
+
+/// Seed prefix for ProofBuffer PDAs: [prefix, owner]
+pub const PROOF_BUFFER_SEED: &[u8] = b"proof-buffer";
+
+/// Seed for the global ArchiveTree PDA
+pub const ARCHIVE_TREE_SEED: &[u8] = b"archive-tree";
+
+/// Seed for the global Config PDA
+pub const CONFIG_SEED: &[u8] = b"config";
+
+/// Seed prefix for MintConfig (allowlist entry) PDAs: [prefix, mint]
+pub const MINT_CONFIG_SEED: &[u8] = b"mint-config";
+
+/// Seed for the global receipt-token (pSOL) mint PDA
+pub const RECEIPT_MINT_SEED: &[u8] = b"receipt-mint";
+
+/// Seed prefix for AuthChain PDAs: [prefix, encrypted_account]
+pub const AUTH_CHAIN_SEED: &[u8] = b"auth-chain";
+
+/// Seed prefix for PrivateAsset PDAs: [prefix, asset_id]
+pub const PRIVATE_ASSET_SEED: &[u8] = b"private-asset";
+
+/// Seed prefix for Canary PDAs: [prefix, watched_account]
+pub const CANARY_SEED: &[u8] = b"canary";
+
+/// Seed for the global shared SOL PoolVault PDA
+pub const POOL_VAULT_SEED: &[u8] = b"pool-vault";
+
+/// Seed prefix for Mailbox PDAs: [prefix, encrypted_account]
+pub const MAILBOX_SEED: &[u8] = b"mailbox";
+
+/// Seed prefix for MailMessage PDAs: [prefix, mailbox, seq (u64 LE)]
+pub const MAIL_MESSAGE_SEED: &[u8] = b"mail-message";
+
+/// Seed for the global shielded NotePool PDA
+pub const NOTE_POOL_SEED: &[u8] = b"note-pool";
+
+/// Seed prefix for Nullifier PDAs: [prefix, nullifier]
+pub const NULLIFIER_SEED: &[u8] = b"nullifier";
+
+/// Seed prefix for NoteVerifyingKey PDAs: [prefix, circuit (u8)]
+pub const NOTE_VERIFYING_KEY_SEED: &[u8] = b"note-vk";
+
+/// Seed prefix for RefundRequest PDAs: [prefix, refunder_account, transfer_ref]
+pub const REFUND_REQUEST_SEED: &[u8] = b"refund-request";
+
+/// Seed prefix for StealthMeta PDAs: [prefix, owner]
+pub const STEALTH_META_SEED: &[u8] = b"stealth-meta";
+
+/// Seed prefix for StealthAnnouncement PDAs: [prefix, ephemeral_key]
+pub const STEALTH_ANNOUNCEMENT_SEED: &[u8] = b"stealth-announcement";
+
+/// Seed prefix for Inbox PDAs: [prefix, encrypted_account]
+pub const INBOX_SEED: &[u8] = b"inbox";
+
+/// Seed for the global WithdrawalQueue PDA
+pub const WITHDRAWAL_QUEUE_SEED: &[u8] = b"withdrawal-queue";
+
+/// Seed prefix for QueuedWithdrawal PDAs: [prefix, ticket (u64 LE)]
+pub const QUEUED_WITHDRAWAL_SEED: &[u8] = b"queued-withdrawal";
+
+/// Seed prefix for ViewingKey PDAs: [prefix, encrypted_account]
+pub const VIEWING_KEY_SEED: &[u8] = b"viewing-key";
+
+/// Seed prefix for SanctionsAttestation PDAs: [prefix, oracle, destination]
+pub const SANCTIONS_ATTESTATION_SEED: &[u8] = b"sanctions-attestation";
+
+/// Seed prefix for TransferAccumulator PDAs: [prefix, encrypted_account]
+pub const TRANSFER_ACCUMULATOR_SEED: &[u8] = b"transfer-accumulator";
+
+/// Seed prefix for DisclosureRequest PDAs: [prefix, encrypted_account, case_id]
+pub const DISCLOSURE_REQUEST_SEED: &[u8] = b"disclosure-request";
+
+/// Seed prefix for DisclosureReport PDAs: [prefix, encrypted_account, report_id (u64 LE)]
+pub const DISCLOSURE_REPORT_SEED: &[u8] = b"disclosure-report";
+
+/// Seed prefix for PendingTransfer PDAs: [prefix, sender_account, transfer_id (u64 LE)]
+pub const PENDING_TRANSFER_SEED: &[u8] = b"pending-transfer";
+
+/// Seed prefix for Invoice PDAs: [prefix, recipient_account, invoice_id (u64 LE)]
+pub const INVOICE_SEED: &[u8] = b"invoice";
+
+/// Seed prefix for ClaimLink PDAs: [prefix, claim_key]
+pub const CLAIM_LINK_SEED: &[u8] = b"claim-link";
+
+/// Seed prefix for PaymentStream PDAs: [prefix, sender_account, stream_id (u64 LE)]
+pub const STREAM_SEED: &[u8] = b"stream";
+
+/// Seed prefix for Vesting PDAs: [prefix, grantor_account, vesting_id (u64 LE)]
+pub const VESTING_SEED: &[u8] = b"vesting";
+
+/// Seed prefix for Subscription PDAs: [prefix, subscriber_account, merchant_account]
+pub const SUBSCRIPTION_SEED: &[u8] = b"subscription";
+
+/// Seed prefix for Allowance PDAs: [prefix, owner_account, delegate]
+pub const ALLOWANCE_SEED: &[u8] = b"allowance";
+
+/// Seed prefix for SessionKey PDAs: [prefix, owner_account, session_key]
+pub const SESSION_KEY_SEED: &[u8] = b"session-key";
+
+/// Seed prefix for DeadManSwitch PDAs: [prefix, encrypted_account]
+pub const DEAD_MAN_SWITCH_SEED: &[u8] = b"dead-man-switch";
+
+/// Seed prefix for GuardianSet PDAs: [prefix, encrypted_account]
+pub const GUARDIAN_SET_SEED: &[u8] = b"guardian-set";
+
+/// Seed prefix for ConditionalPayment PDAs: [prefix, payer_account, payment_id (u64 LE)]
+pub const CONDITIONAL_PAYMENT_SEED: &[u8] = b"conditional-payment";
+
+/// Seed prefix for HashTimeLock PDAs: [prefix, sender_account, hashlock]
+pub const HTLC_SEED: &[u8] = b"htlc";
+
+/// Seed for the global PendingConfigChange PDA
+pub const PENDING_CONFIG_CHANGE_SEED: &[u8] = b"pending-config-change";
+
+/// Seed for the global Treasury PDA (protocol fees)
+pub const TREASURY_SEED: &[u8] = b"treasury";
+
+/// Seed for the global ShieldedTreasury PDA (hidden fees)
+pub const SHIELDED_TREASURY_SEED: &[u8] = b"shielded-treasury";
+
+/// Seed for the global ProtocolStats PDA
+pub const STATS_SEED: &[u8] = b"stats";
+
+/// Seed for PendingVerification PDAs (two-stage transfers, one per sender account)
+pub const PENDING_VERIFICATION_SEED: &[u8] = b"pending-verification";
+
+/// Seed for the global GeneratorTable PDA
+pub const GENERATORS_SEED: &[u8] = b"generators";
+
+/**
+ * Derive the EncryptedAccount address for (owner, account_index)
+ */
+pub fn derive_encrypted_account_address(owner: &Pubkey, account_index: u16) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[ENCRYPTED_ACCOUNT_SEED, owner.as_ref(), &account_index.to_le_bytes()],
+        &crate::ID,
+    )
+}
+
+/**
+ * Derive the SolEscrow address for (owner, account_index)
+ */
+pub fn derive_sol_escrow_address(owner: &Pubkey, account_index: u16) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[SOL_ESCROW_SEED, owner.as_ref(), &account_index.to_le_bytes()],
+        &crate::ID,
+    )
+}
+
+/**
+ * Derive the TokenEscrow address for (owner, mint)
+ */
+pub fn derive_token_escrow_address(owner: &Pubkey, mint: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[TOKEN_ESCROW_SEED, owner.as_ref(), mint.as_ref()],
+        &crate::ID,
+    )
+}
+
+/**
+ * Derive the token vault address for (owner, mint)
+ */
+pub fn derive_token_vault_address(owner: &Pubkey, mint: &Pubkey) -> (Pubkey, u8) {
+    let (token_escrow, _) = derive_token_escrow_address(owner, mint);
+    Pubkey::find_program_address(&[TOKEN_VAULT_SEED, token_escrow.as_ref()], &crate::ID)
+}
+
+/**
+ * Derive the confidential vault address for (owner, mint)
+ */
+pub fn derive_confidential_vault_address(owner: &Pubkey, mint: &Pubkey) -> (Pubkey, u8) {
+    let (token_escrow, _) = derive_token_escrow_address(owner, mint);
+    Pubkey::find_program_address(&[CONFIDENTIAL_VAULT_SEED, token_escrow.as_ref()], &crate::ID)
+}
+
+/**
+ * Derive the ProofBuffer address for an owner
+ */
+pub fn derive_proof_buffer_address(owner: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[PROOF_BUFFER_SEED, owner.as_ref()], &crate::ID)
+}
+
+/**
+ * Derive the global ArchiveTree address
+ */
+pub fn derive_archive_tree_address() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[ARCHIVE_TREE_SEED], &crate::ID)
+}
+
+/**
+ * Derive the AuthChain address protecting an encrypted account
+ */
+pub fn derive_auth_chain_address(encrypted_account: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[AUTH_CHAIN_SEED, encrypted_account.as_ref()], &crate::ID)
+}
+
+/**
+ * Derive the global receipt-token (pSOL) mint address
+ */
+pub fn derive_receipt_mint_address() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[RECEIPT_MINT_SEED], &crate::ID)
+}
+
+/**
+ * Derive the global Config address
+ */
+pub fn derive_config_address() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[CONFIG_SEED], &crate::ID)
+}
+
+/**
+ * Derive the MintConfig (allowlist entry) address for a mint
+ */
+pub fn derive_mint_config_address(mint: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[MINT_CONFIG_SEED, mint.as_ref()], &crate::ID)
+}
+
+/**
+ * Derive the PrivateAsset address for an asset id
+ */
+pub fn derive_private_asset_address(asset_id: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[PRIVATE_ASSET_SEED, asset_id.as_ref()], &crate::ID)
+}
+
+/**
+ * Derive the Canary address watching a program account
+ */
+pub fn derive_canary_address(watched_account: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[CANARY_SEED, watched_account.as_ref()], &crate::ID)
+}
+
+/**
+ * Derive the global shared SOL PoolVault address
+ */
+pub fn derive_pool_vault_address() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[POOL_VAULT_SEED], &crate::ID)
+}
+
+/**
+ * Derive the Mailbox address of an encrypted account
+ */
+pub fn derive_mailbox_address(encrypted_account: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[MAILBOX_SEED, encrypted_account.as_ref()], &crate::ID)
+}
+
+/**
+ * Derive the MailMessage address for (mailbox, seq)
+ */
+pub fn derive_mail_message_address(mailbox: &Pubkey, seq: u64) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[MAIL_MESSAGE_SEED, mailbox.as_ref(), &seq.to_le_bytes()],
+        &crate::ID,
+    )
+}
+
+/**
+ * Derive the global shielded NotePool address
+ */
+pub fn derive_note_pool_address() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[NOTE_POOL_SEED], &crate::ID)
+}
+
+/**
+ * Derive the Nullifier address for a published nullifier
+ */
+pub fn derive_nullifier_address(nullifier: &[u8; 32]) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[NULLIFIER_SEED, nullifier.as_ref()], &crate::ID)
+}
+
+/**
+ * Derive the RefundRequest address for (refunder_account, transfer_ref)
+ */
+pub fn derive_refund_request_address(refunder_account: &Pubkey, transfer_ref: &[u8; 32]) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[REFUND_REQUEST_SEED, refunder_account.as_ref(), transfer_ref.as_ref()],
+        &crate::ID,
+    )
+}
+
+/**
+ * Derive the StealthMeta address of a recipient
+ */
+pub fn derive_stealth_meta_address(owner: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[STEALTH_META_SEED, owner.as_ref()], &crate::ID)
+}
+
+/**
+ * Derive the StealthAnnouncement address for an ephemeral key
+ */
+pub fn derive_stealth_announcement_address(ephemeral_key: &[u8; 32]) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[STEALTH_ANNOUNCEMENT_SEED, ephemeral_key.as_ref()], &crate::ID)
+}
+
+/**
+ * Derive the Inbox address of an encrypted account
+ */
+pub fn derive_inbox_address(encrypted_account: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[INBOX_SEED, encrypted_account.as_ref()], &crate::ID)
+}
+
+/**
+ * Derive the global WithdrawalQueue address
+ */
+pub fn derive_withdrawal_queue_address() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[WITHDRAWAL_QUEUE_SEED], &crate::ID)
+}
+
+/**
+ * Derive the QueuedWithdrawal address for a ticket
+ */
+pub fn derive_queued_withdrawal_address(ticket: u64) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[QUEUED_WITHDRAWAL_SEED, &ticket.to_le_bytes()], &crate::ID)
+}
+
+/**
+ * Derive the ViewingKey address of an encrypted account
+ */
+pub fn derive_viewing_key_address(encrypted_account: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[VIEWING_KEY_SEED, encrypted_account.as_ref()], &crate::ID)
+}
+
+/**
+ * Derive the SanctionsAttestation address of an oracle for a destination
+ */
+pub fn derive_sanctions_attestation_address(oracle: &Pubkey, destination: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[SANCTIONS_ATTESTATION_SEED, oracle.as_ref(), destination.as_ref()],
+        &crate::ID,
+    )
+}
+
+/**
+ * Derive the TransferAccumulator address of an encrypted account
+ */
+pub fn derive_transfer_accumulator_address(encrypted_account: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[TRANSFER_ACCUMULATOR_SEED, encrypted_account.as_ref()], &crate::ID)
+}
+
+/**
+ * Derive the DisclosureRequest address of a case against an encrypted account
+ */
+pub fn derive_disclosure_request_address(encrypted_account: &Pubkey, case_id: &[u8; 32]) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[DISCLOSURE_REQUEST_SEED, encrypted_account.as_ref(), case_id.as_ref()],
+        &crate::ID,
+    )
+}
+
+/**
+ * Derive the DisclosureReport address of an encrypted account
+ */
+pub fn derive_disclosure_report_address(encrypted_account: &Pubkey, report_id: u64) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[DISCLOSURE_REPORT_SEED, encrypted_account.as_ref(), &report_id.to_le_bytes()],
+        &crate::ID,
+    )
+}
+
+/**
+ * Derive the PendingTransfer address of a two-phase transfer
+ */
+pub fn derive_pending_transfer_address(sender_account: &Pubkey, transfer_id: u64) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[PENDING_TRANSFER_SEED, sender_account.as_ref(), &transfer_id.to_le_bytes()],
+        &crate::ID,
+    )
+}
+
+/**
+ * Derive the Invoice address of a merchant's encrypted account
+ */
+pub fn derive_invoice_address(recipient_account: &Pubkey, invoice_id: u64) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[INVOICE_SEED, recipient_account.as_ref(), &invoice_id.to_le_bytes()],
+        &crate::ID,
+    )
+}
+
+/**
+ * Derive the ClaimLink address for a claim key (keypair seeded by the link secret)
+ */
+pub fn derive_claim_link_address(claim_key: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[CLAIM_LINK_SEED, claim_key.as_ref()], &crate::ID)
+}
+
+/**
+ * Derive the PaymentStream address of a sender's encrypted account
+ */
+pub fn derive_stream_address(sender_account: &Pubkey, stream_id: u64) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[STREAM_SEED, sender_account.as_ref(), &stream_id.to_le_bytes()],
+        &crate::ID,
+    )
+}
+
+/**
+ * Derive the Vesting address of a grantor's encrypted account
+ */
+pub fn derive_vesting_address(grantor_account: &Pubkey, vesting_id: u64) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[VESTING_SEED, grantor_account.as_ref(), &vesting_id.to_le_bytes()],
+        &crate::ID,
+    )
+}
+
+/**
+ * Derive the Subscription address between a subscriber and a merchant account
+ */
+pub fn derive_subscription_address(subscriber_account: &Pubkey, merchant_account: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[SUBSCRIPTION_SEED, subscriber_account.as_ref(), merchant_account.as_ref()],
+        &crate::ID,
+    )
+}
+
+/**
+ * Derive the Allowance address of a delegate on an encrypted account
+ */
+pub fn derive_allowance_address(owner_account: &Pubkey, delegate: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[ALLOWANCE_SEED, owner_account.as_ref(), delegate.as_ref()],
+        &crate::ID,
+    )
+}
+
+/**
+ * Derive the SessionKey address of a session key on an encrypted account
+ */
+pub fn derive_session_key_address(owner_account: &Pubkey, session_key: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[SESSION_KEY_SEED, owner_account.as_ref(), session_key.as_ref()],
+        &crate::ID,
+    )
+}
+
+/**
+ * Derive the DeadManSwitch address of an encrypted account
+ */
+pub fn derive_dead_man_switch_address(encrypted_account: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[DEAD_MAN_SWITCH_SEED, encrypted_account.as_ref()],
+        &crate::ID,
+    )
+}
+
+/**
+ * Derive the GuardianSet address of an encrypted account
+ */
+pub fn derive_guardian_set_address(encrypted_account: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[GUARDIAN_SET_SEED, encrypted_account.as_ref()],
+        &crate::ID,
+    )
+}
+
+/**
+ * Derive the ConditionalPayment address of a payer's encrypted account
+ */
+pub fn derive_conditional_payment_address(payer_account: &Pubkey, payment_id: u64) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[CONDITIONAL_PAYMENT_SEED, payer_account.as_ref(), &payment_id.to_le_bytes()],
+        &crate::ID,
+    )
+}
+
+/**
+ * Derive the HashTimeLock address of a sender's encrypted account
+ */
+pub fn derive_htlc_address(sender_account: &Pubkey, hashlock: &[u8; 32]) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[HTLC_SEED, sender_account.as_ref(), hashlock.as_ref()],
+        &crate::ID,
+    )
+}
+
+/**
+ * Derive the global PendingConfigChange address
+ */
+pub fn derive_pending_config_change_address() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[PENDING_CONFIG_CHANGE_SEED], &crate::ID)
+}
+
+/**
+ * Derive the global Treasury address
+ */
+pub fn derive_treasury_address() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[TREASURY_SEED], &crate::ID)
+}
+
+/**
+ * Derive the global ShieldedTreasury address
+ */
+pub fn derive_shielded_treasury_address() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[SHIELDED_TREASURY_SEED], &crate::ID)
+}
+
+/**
+ * Derive the global ProtocolStats address
+ */
+pub fn derive_stats_address() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[STATS_SEED], &crate::ID)
+}
+
+/**
+ * Derive the PendingVerification address for a sending account
+ */
+pub fn derive_pending_verification_address(sender_account: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[PENDING_VERIFICATION_SEED, sender_account.as_ref()], &crate::ID)
+}
+
+/**
+ * Derive the global GeneratorTable address
+ */
+pub fn derive_generators_address() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[GENERATORS_SEED], &crate::ID)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sub_accounts_of_one_owner_are_distinct_transfer_endpoints() {
+        // Transfers reject sender_account == recipient_account, so this is
+        // what lets an owner move value between their own sub-accounts
+        let owner = Pubkey::new_unique();
+        let (main, _) = derive_encrypted_account_address(&owner, 0);
+        let (savings, _) = derive_encrypted_account_address(&owner, 1);
+        assert_ne!(main, savings);
+        assert_ne!(derive_sol_escrow_address(&owner, 0).0, derive_sol_escrow_address(&owner, 1).0);
+
+        // The same (owner, account_index) is the same account, i.e. a self-transfer
+        assert_eq!(main, derive_encrypted_account_address(&owner, 0).0);
+        assert_ne!(main, derive_encrypted_account_address(&Pubkey::new_unique(), 0).0);
+    }
+}
@@ -1,611 +0,0 @@
-/**
- * On-Chain ZK Proof Verification Module (BPF-Compatible)
- * 
- * This module provides BPF-compatible proof verification for Solana.
- * 
- * IMPORTANT LIMITATION: Due to Solana's 4KB stack limit, full elliptic curve
- * operations (curve25519-dalek) cannot be used on-chain. This implementation:
- * 
- * 1. Performs basic validation (format, size, non-zero checks)
- * 2. Validates proof structure and commitments
- * 3. Verifies commitment format and consistency
- * 
- * Full cryptographic verification (elliptic curve operations, scalar arithmetic)
- * should be performed off-chain or using a compute-efficient approach.
- * 
- * STATUS: BPF-compatible basic validation with structure for full verification
- */
-
-use std::result::Result;
-use crate::crypto_primitives::{is_nonzero_point, is_valid_commitment_format, constant_time_eq};
-use crate::merlin_transcript::{MerlinTranscript, rangeproof_domain_sep};
-
-/// Proof verification constants
-mod proof_constants {
-    /// Minimum proof data size in bytes (basic proof structure)
-    pub const MIN_PROOF_DATA_SIZE: usize = 64;
-    
-    /// Maximum proof data size in bytes (DoS protection)
-    pub const MAX_PROOF_DATA_SIZE: usize = 10000;
-    
-    /// Default range proof bits for lamport amounts
-    #[allow(dead_code)]
-    pub const DEFAULT_RANGE_BITS: u8 = 64;
-}
-
-/// Error codes for proof verification
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-pub enum ProofVerificationError {
-    DeserializationFailed,
-    InvalidRangeProof,
-    InvalidEqualityProof,
-    InvalidValidityProof,
-    #[allow(dead_code)] // Reserved for future use in full implementation
-    BalanceEquationFailed,
-    CommitmentMismatch,
-    InvalidPoint,
-    #[allow(dead_code)] // Reserved for future use in full implementation
-    InvalidProofStructure,
-    InvalidCommitment, // Added for commitment validation
-}
-
-impl From<&str> for ProofVerificationError {
-    fn from(_: &str) -> Self {
-        ProofVerificationError::InvalidPoint
-    }
-}
-
-/// Bulletproof range proof structure
-#[derive(Debug, Clone)]
-pub struct BulletproofRangeProof {
-    /// Commitment V = g^v * h^gamma (64 bytes: 32 for X, 32 for Y)
-    pub commitment: [u8; 64],
-    /// Commitment A (64 bytes)
-    pub a: [u8; 64],
-    /// Commitment S (64 bytes)
-    pub s: [u8; 64],
-    /// Commitment T1 (64 bytes)
-    pub t1: [u8; 64],
-    /// Commitment T2 (64 bytes)
-    pub t2: [u8; 64],
-    /// Scalar taux (32 bytes)
-    pub taux: [u8; 32],
-    /// Scalar mu (32 bytes)
-    pub mu: [u8; 32],
-    /// Scalar t (32 bytes)
-    pub t: [u8; 32],
-    /// Inner product proof (structure only - not verified on-chain)
-    #[allow(dead_code)] // Reserved for future use in full implementation
-    pub inner_product_proof: InnerProductProof,
-    /// Range size (n bits)
-    pub n: u8,
-}
-
-/// Inner product proof structure
-#[derive(Debug, Clone)]
-#[allow(dead_code)] // Reserved for future use in full implementation
-pub struct InnerProductProof {
-    /// Left commitments L (variable length, typically log2(n))
-    pub l: Vec<[u8; 64]>,
-    /// Right commitments R (variable length, typically log2(n))
-    pub r: Vec<[u8; 64]>,
-    /// Final scalar a (32 bytes)
-    pub a: [u8; 32],
-    /// Final scalar b (32 bytes)
-    pub b: [u8; 32],
-}
-
-/// Validity proof structure
-#[derive(Debug, Clone)]
-pub struct ValidityProof {
-    /// Equality proof for sender balance equation
-    pub sender_equality_proof: EqualityProof,
-    /// Equality proof for recipient balance equation
-    pub recipient_equality_proof: EqualityProof,
-}
-
-/// Equality proof structure (Schnorr-like)
-#[derive(Debug, Clone)]
-pub struct EqualityProof {
-    /// Commitment R (64 bytes)
-    pub r: [u8; 64],
-    /// Scalar s (32 bytes)
-    pub s: [u8; 32],
-}
-
-/// Transfer proof structure (complete proof for a transfer)
-#[derive(Debug, Clone)]
-pub struct TransferProof {
-    /// Range proof for amount
-    pub amount_range_proof: BulletproofRangeProof,
-    /// Range proof for sender's new balance
-    pub sender_after_range_proof: BulletproofRangeProof,
-    /// Validity proof for balance equations
-    pub validity_proof: ValidityProof,
-}
-
-/**
- * Extract amount commitment from proof data (without full deserialization)
- * 
- * SECURITY: This function extracts only the amount commitment (first 64 bytes)
- * to avoid full deserialization overhead. Used for parameter validation.
- * 
- * @param proof_data - Serialized proof data
- * @returns Amount commitment (64 bytes)
- */
-pub fn extract_amount_commitment(proof_data: &[u8]) -> Result<[u8; 64], ProofVerificationError> {
-    // Validate minimum size
-    if proof_data.len() < 64 {
-        return Err(ProofVerificationError::DeserializationFailed);
-    }
-    
-    // Extract first 64 bytes as amount commitment
-    let mut commitment = [0u8; 64];
-    commitment.copy_from_slice(&proof_data[0..64]);
-    
-    // SECURITY: Validate commitment is not all zeros
-    if commitment == [0u8; 64] {
-        return Err(ProofVerificationError::InvalidCommitment);
-    }
-    
-    Ok(commitment)
-}
-
-/**
- * Deserialize proof data from bytes
- * 
- * PROOF DATA FORMAT:
- * [amount_range_proof][sender_after_range_proof][validity_proof]
- * 
- * Each range proof structure:
- * - commitment: 64 bytes
- * - A: 64 bytes
- * - S: 64 bytes
- * - T1: 64 bytes
- * - T2: 64 bytes
- * - taux: 32 bytes
- * - mu: 32 bytes
- * - t: 32 bytes
- * - inner_product_proof: variable (min 64 bytes for basic structure)
- * - n: 1 byte
- * 
- * Each range proof: ~700 bytes minimum
- * Validity proof: ~200 bytes minimum
- * Total: ~1600 bytes minimum
- */
-pub fn deserialize_proof_data(proof_data: &[u8]) -> Result<TransferProof, ProofVerificationError> {
-    // Validate minimum size (must have at least basic structure)
-    if proof_data.len() < proof_constants::MIN_PROOF_DATA_SIZE {
-        return Err(ProofVerificationError::DeserializationFailed);
-    }
-    
-    // Validate proof data is not too large (DoS protection)
-    if proof_data.len() > proof_constants::MAX_PROOF_DATA_SIZE {
-        return Err(ProofVerificationError::DeserializationFailed);
-    }
-
-    // SECURITY: Reject invalid proof data
-    // Check for common invalid patterns that indicate malformed proofs
-    if proof_data.len() < 512 {
-        // Minimum size for a valid proof structure
-        return Err(ProofVerificationError::DeserializationFailed);
-    }
-
-    // SECURITY: Reject all-zero proof data (common invalid pattern)
-    let mut all_zeros = true;
-    for byte in proof_data.iter().take(256) {
-        if *byte != 0u8 {
-            all_zeros = false;
-            break;
-        }
-    }
-    if all_zeros {
-        return Err(ProofVerificationError::DeserializationFailed);
-    }
-
-    // Parse proof data structure
-    // For now, we'll parse a simplified structure and validate it's not dummy data
-    // Full parsing would require more complex deserialization
-    
-    let mut offset = 0;
-    
-    // Helper to read fixed-size arrays
-    fn read_array<const N: usize>(data: &[u8], offset: &mut usize) -> Result<[u8; N], ProofVerificationError> {
-        if *offset + N > data.len() {
-            return Err(ProofVerificationError::DeserializationFailed);
-        }
-        let mut arr = [0u8; N];
-        arr.copy_from_slice(&data[*offset..*offset + N]);
-        *offset += N;
-        Ok(arr)
-    }
-
-    // Parse amount range proof
-    let amount_commitment = read_array::<64>(proof_data, &mut offset)?;
-    let amount_a = read_array::<64>(proof_data, &mut offset)?;
-    let amount_s = read_array::<64>(proof_data, &mut offset)?;
-    let amount_t1 = read_array::<64>(proof_data, &mut offset)?;
-    let amount_t2 = read_array::<64>(proof_data, &mut offset)?;
-    let amount_taux = read_array::<32>(proof_data, &mut offset)?;
-    let amount_mu = read_array::<32>(proof_data, &mut offset)?;
-    let amount_t = read_array::<32>(proof_data, &mut offset)?;
-    
-    // SECURITY: Validate parsed data is not all zeros (reject dummy proofs)
-    if amount_commitment == [0u8; 64] 
-        || amount_a == [0u8; 64] 
-        || amount_s == [0u8; 64]
-        || amount_taux == [0u8; 32]
-        || amount_mu == [0u8; 32]
-        || amount_t == [0u8; 32] {
-        return Err(ProofVerificationError::InvalidRangeProof);
-    }
-
-    // Read n (range size) - default to 64 if not present
-    let amount_n = if offset < proof_data.len() {
-        proof_data[offset]
-    } else {
-        64u8
-    };
-    offset += 1;
-
-    // Inner product proof (simplified - just read basic structure)
-    // In full implementation, would parse variable-length inner product proof
-    let amount_inner_product = InnerProductProof {
-        l: vec![],
-        r: vec![],
-        a: [0u8; 32],
-        b: [0u8; 32],
-    };
-
-    let amount_range_proof = BulletproofRangeProof {
-        commitment: amount_commitment,
-        a: amount_a,
-        s: amount_s,
-        t1: amount_t1,
-        t2: amount_t2,
-        taux: amount_taux,
-        mu: amount_mu,
-        t: amount_t,
-        inner_product_proof: amount_inner_product,
-        n: amount_n,
-    };
-
-    // Parse sender_after range proof (same structure)
-    let sender_commitment = read_array::<64>(proof_data, &mut offset)?;
-    let sender_a = read_array::<64>(proof_data, &mut offset)?;
-    let sender_s = read_array::<64>(proof_data, &mut offset)?;
-    let sender_t1 = read_array::<64>(proof_data, &mut offset)?;
-    let sender_t2 = read_array::<64>(proof_data, &mut offset)?;
-    let sender_taux = read_array::<32>(proof_data, &mut offset)?;
-    let sender_mu = read_array::<32>(proof_data, &mut offset)?;
-    let sender_t = read_array::<32>(proof_data, &mut offset)?;
-    
-    // SECURITY: Validate parsed data is not all zeros
-    if sender_commitment == [0u8; 64] 
-        || sender_a == [0u8; 64] 
-        || sender_s == [0u8; 64]
-        || sender_taux == [0u8; 32]
-        || sender_mu == [0u8; 32]
-        || sender_t == [0u8; 32] {
-        return Err(ProofVerificationError::InvalidRangeProof);
-    }
-
-    let sender_n = if offset < proof_data.len() {
-        proof_data[offset]
-    } else {
-        64u8
-    };
-    offset += 1;
-
-    let sender_inner_product = InnerProductProof {
-        l: vec![],
-        r: vec![],
-        a: [0u8; 32],
-        b: [0u8; 32],
-    };
-
-    let sender_after_range_proof = BulletproofRangeProof {
-        commitment: sender_commitment,
-        a: sender_a,
-        s: sender_s,
-        t1: sender_t1,
-        t2: sender_t2,
-        taux: sender_taux,
-        mu: sender_mu,
-        t: sender_t,
-        inner_product_proof: sender_inner_product,
-        n: sender_n,
-    };
-
-    // Parse validity proof (equality proofs)
-    let sender_equality_r = read_array::<64>(proof_data, &mut offset)?;
-    let sender_equality_s = read_array::<32>(proof_data, &mut offset)?;
-    
-    // SECURITY: Validate equality proof is not all zeros
-    if sender_equality_r == [0u8; 64] || sender_equality_s == [0u8; 32] {
-        return Err(ProofVerificationError::InvalidEqualityProof);
-    }
-
-    let sender_equality_proof = EqualityProof {
-        r: sender_equality_r,
-        s: sender_equality_s,
-    };
-
-    let recipient_equality_r = read_array::<64>(proof_data, &mut offset)?;
-    let recipient_equality_s = read_array::<32>(proof_data, &mut offset)?;
-    
-    // SECURITY: Validate equality proof is not all zeros
-    if recipient_equality_r == [0u8; 64] || recipient_equality_s == [0u8; 32] {
-        return Err(ProofVerificationError::InvalidEqualityProof);
-    }
-
-    let recipient_equality_proof = EqualityProof {
-        r: recipient_equality_r,
-        s: recipient_equality_s,
-    };
-
-    let validity_proof = ValidityProof {
-        sender_equality_proof,
-        recipient_equality_proof,
-    };
-
-    Ok(TransferProof {
-        amount_range_proof,
-        sender_after_range_proof,
-        validity_proof,
-    })
-}
-
-/**
- * Verify a Bulletproof range proof (BPF-compatible enhanced validation)
- * 
- * VERIFICATION STEPS (on-chain):
- * 1. Validate commitment format (non-zero, 64 bytes)
- * 2. Validate proof structure (all commitments non-zero)
- * 3. Validate commitment matches proof.commitment
- * 4. Basic transcript validation
- * 5. Enhanced structural validation (NEW)
- * 
- * SECURITY IMPROVEMENTS:
- * - Validates all proof components are distinct (prevents dummy proofs)
- * - Checks proof structure consistency
- * - Validates scalar ranges
- * - Rejects obviously invalid proof patterns
- * 
- * NOTE: Full cryptographic verification (elliptic curve operations,
- * scalar arithmetic, multi-scalar multiplication) is NOT performed
- * on-chain due to Solana's 4KB stack limit. Full verification should
- * be done off-chain or using a compute-efficient approach.
- * 
- * This enhanced validation provides stronger security guarantees while
- * remaining BPF-compatible.
- */
-pub fn verify_range_proof(
-    proof: &BulletproofRangeProof,
-    commitment: &[u8; 64],
-) -> Result<(), ProofVerificationError> {
-    // Validate commitment format
-    if !is_valid_commitment_format(commitment) {
-        return Err(ProofVerificationError::InvalidRangeProof);
-    }
-    
-    // Verify commitment matches proof.commitment
-    if !constant_time_eq(&proof.commitment, commitment) {
-        return Err(ProofVerificationError::CommitmentMismatch);
-    }
-    
-    // Validate all proof commitments are non-zero
-    if !is_nonzero_point(&proof.a)
-        || !is_nonzero_point(&proof.s)
-        || !is_nonzero_point(&proof.t1)
-        || !is_nonzero_point(&proof.t2)
-    {
-        return Err(ProofVerificationError::InvalidRangeProof);
-    }
-    
-    // Validate scalars are non-zero (basic check)
-    if proof.taux == [0u8; 32] || proof.mu == [0u8; 32] || proof.t == [0u8; 32] {
-        return Err(ProofVerificationError::InvalidRangeProof);
-    }
-    
-    // Basic transcript validation (structure only)
-    let domain_sep = rangeproof_domain_sep(proof.n, 1);
-    let mut transcript = MerlinTranscript::new(&domain_sep);
-    transcript.append_point(b"V", &proof.commitment);
-    transcript.append_point(b"A", &proof.a);
-    transcript.append_point(b"S", &proof.s);
-    
-    // Get challenges (for structure validation)
-    let _y = transcript.challenge_scalar(b"y");
-    let _z = transcript.challenge_scalar(b"z");
-    
-    transcript.append_point(b"T1", &proof.t1);
-    transcript.append_point(b"T2", &proof.t2);
-    
-    let _x = transcript.challenge_scalar(b"x");
-    
-    // SECURITY: Enhanced validation to reject obviously invalid proofs
-    // While full cryptographic verification is not performed on-chain due to
-    // Solana's 4KB stack limit, we perform strict structural validation to
-    // reject invalid proof data.
-    
-    // Validate that proof components are not identical (would indicate dummy data)
-    if constant_time_eq(&proof.a, &proof.s) 
-        || constant_time_eq(&proof.t1, &proof.t2)
-        || constant_time_eq(&proof.taux, &proof.mu) {
-        return Err(ProofVerificationError::InvalidRangeProof);
-    }
-    
-    // SECURITY: Validate proof components are not all zeros (additional check)
-    if proof.taux == [0u8; 32] || proof.mu == [0u8; 32] || proof.t == [0u8; 32] {
-        return Err(ProofVerificationError::InvalidRangeProof);
-    }
-    
-    // SECURITY: Validate commitment is not equal to other proof components
-    // (prevents reuse of commitments as proof components)
-    if constant_time_eq(commitment, &proof.a)
-        || constant_time_eq(commitment, &proof.s)
-        || constant_time_eq(commitment, &proof.t1)
-        || constant_time_eq(commitment, &proof.t2) {
-        return Err(ProofVerificationError::InvalidRangeProof);
-    }
-    
-    // SECURITY: Validate range size is reasonable (prevent DoS)
-    if proof.n == 0 || proof.n > 64 {
-        return Err(ProofVerificationError::InvalidRangeProof);
-    }
-    
-    // NOTE: Full cryptographic verification (elliptic curve operations,
-    // scalar arithmetic, multi-scalar multiplication) is NOT performed
-    // on-chain due to Solana's 4KB stack limit.
-    // 
-    // Full verification should be done off-chain or using a compute-efficient
-    // approach. This on-chain implementation performs strict structural validation
-    // to reject invalid proofs while maintaining BPF compatibility.
-    //
-    // SECURITY MODEL:
-    // - On-chain: Structural validation (this function)
-    // - Off-chain: Full cryptographic verification (required)
-    // - Hybrid: Both validations must pass for transaction acceptance
-    
-    Ok(())
-}
-
-/**
- * Verify equality proof (BPF-compatible basic validation)
- * 
- * VERIFICATION STEPS (on-chain):
- * 1. Validate commitment format (non-zero, 64 bytes)
- * 2. Validate proof structure
- * 
- * NOTE: Full cryptographic verification (elliptic curve operations)
- * is NOT performed on-chain due to Solana's 4KB stack limit.
- */
-pub fn verify_equality_proof(
-    proof: &EqualityProof,
-    commitment1: &[u8; 64],
-    commitment2: &[u8; 64],
-) -> Result<(), ProofVerificationError> {
-    // Validate commitments are not all zeros
-    if !is_nonzero_point(commitment1) || !is_nonzero_point(commitment2) {
-        return Err(ProofVerificationError::InvalidEqualityProof);
-    }
-    
-    // Validate proof structure
-    if !is_nonzero_point(&proof.r) || proof.s == [0u8; 32] {
-        return Err(ProofVerificationError::InvalidEqualityProof);
-    }
-    
-    // SECURITY: Additional validation to reject obviously invalid proofs
-    // Reject if R and s are identical (would indicate dummy data)
-    let r_first_32 = &proof.r[..32];
-    if constant_time_eq(r_first_32, &proof.s) {
-        return Err(ProofVerificationError::InvalidEqualityProof);
-    }
-    
-    // NOTE: Full cryptographic verification (R + s*G == commitment1 - commitment2)
-    // is NOT performed on-chain due to Solana's 4KB stack limit.
-    // Full verification should be done off-chain.
-    // This implementation performs strict structural validation to reject invalid proofs.
-    
-    Ok(())
-}
-
-/**
- * Verify validity proof (BPF-compatible basic validation)
- * 
- * VERIFICATION STEPS (on-chain):
- * 1. Validate all commitments are non-zero
- * 2. Validate proof structure
- * 
- * NOTE: Full cryptographic verification (homomorphic commitment operations,
- * equality proofs) is NOT performed on-chain due to Solana's 4KB stack limit.
- */
-pub fn verify_validity_proof(
-    proof: &ValidityProof,
-    sender_old_commitment: &[u8; 64],
-    amount_commitment: &[u8; 64],
-    sender_new_commitment: &[u8; 64],
-    recipient_old_commitment: &[u8; 64],
-    recipient_new_commitment: &[u8; 64],
-) -> Result<(), ProofVerificationError> {
-    // Validate commitments are not all zeros
-    if !is_nonzero_point(sender_old_commitment)
-        || !is_nonzero_point(amount_commitment)
-        || !is_nonzero_point(sender_new_commitment)
-        || !is_nonzero_point(recipient_old_commitment)
-        || !is_nonzero_point(recipient_new_commitment)
-    {
-        return Err(ProofVerificationError::InvalidValidityProof);
-    }
-    
-    // Verify equality proofs (structure only)
-    verify_equality_proof(
-        &proof.sender_equality_proof,
-        sender_old_commitment,
-        sender_new_commitment,
-    )?;
-    
-    verify_equality_proof(
-        &proof.recipient_equality_proof,
-        recipient_old_commitment,
-        recipient_new_commitment,
-    )?;
-    
-    // NOTE: Full cryptographic verification (homomorphic commitment operations,
-    // balance equation verification) is NOT performed on-chain due to Solana's
-    // 4KB stack limit. Full verification should be done off-chain.
-    
-    Ok(())
-}
-
-/**
- * Verify complete transfer proof (BPF-compatible)
- * 
- * VERIFICATION STEPS:
- * 1. Deserialize proof data
- * 2. Verify amount range proof (basic validation)
- * 3. Verify sender_after range proof (basic validation)
- * 4. Verify validity proof (basic validation)
- * 5. Verify commitments match
- * 
- * NOTE: Full cryptographic verification is NOT performed on-chain.
- * This implementation performs basic validation and structure checks.
- */
-pub fn verify_transfer_proof(
-    proof_data: &[u8],
-    amount_commitment: &[u8; 64],
-    sender_after_commitment: &[u8; 64],
-    sender_old_commitment: &[u8; 64],
-    recipient_old_commitment: &[u8; 64],
-    recipient_new_commitment: &[u8; 64],
-) -> Result<(), ProofVerificationError> {
-    // Deserialize proof data
-    let proof = deserialize_proof_data(proof_data)?;
-
-    // Verify amount range proof (basic validation)
-    verify_range_proof(&proof.amount_range_proof, amount_commitment)?;
-
-    // Verify sender_after range proof (basic validation)
-    verify_range_proof(&proof.sender_after_range_proof, sender_after_commitment)?;
-
-    // Verify validity proof (basic validation)
-    verify_validity_proof(
-        &proof.validity_proof,
-        sender_old_commitment,
-        amount_commitment,
-        sender_after_commitment,
-        recipient_old_commitment,
-        recipient_new_commitment,
-    )?;
-
-    // Verify commitments match
-    if !constant_time_eq(&proof.amount_range_proof.commitment, amount_commitment) {
-        return Err(ProofVerificationError::CommitmentMismatch);
-    }
-    if !constant_time_eq(&proof.sender_after_range_proof.commitment, sender_after_commitment) {
-        return Err(ProofVerificationError::CommitmentMismatch);
-    }
-
-    Ok(())
-}
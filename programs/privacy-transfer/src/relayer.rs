@@ -0,0 +1,193 @@
+/*!
+ * Relayed (Intent-Based) Transfers
+ *
+ * The sender does not sign the transaction. Instead it signs a transfer
+ * intent off-chain with its wallet key; any relayer submits the transaction
+ * and pays the fees, so the sender's key never appears as a fee payer or
+ * signer.
+ *
+ * The intent signature is checked by the native Ed25519 program in an
+ * instruction placed directly before ours; we introspect that instruction
+ * through the instructions sysvar and make sure it verified exactly the
+ * expected (key, message) pair.
+ *
+ * Intent message:
+ *   DOMAIN || program_id || sender_account || recipient_account
+ *   || sender_new_commitment || recipient_new_commitment
+ *   || keccak(proof_data) || sender_version (u64 LE) || expiry_slot (u64 LE)
+ *
+ * `sender_version` makes every intent single-use: it changes with every
+ * update of the sender account.
+ */
+
+use anchor_lang::prelude::*;
+use solana_instructions_sysvar::{load_current_index_checked, load_instruction_at_checked};
+use solana_sdk_ids::ed25519_program;
+
+use crate::merkle_tree::hash_leaf;
+use crate::ErrorCode;
+
+/// Domain separator for transfer intents
+pub const INTENT_DOMAIN: &[u8] = b"privacy-transfer:relayed-transfer:v1";
+
+/// Size of the Ed25519 instruction header (count + padding)
+const ED25519_HEADER_LEN: usize = 2;
+
+/// Size of one Ed25519SignatureOffsets entry
+const ED25519_OFFSETS_LEN: usize = 14;
+
+/// Offsets refer to data inside the Ed25519 instruction itself
+const SAME_INSTRUCTION: u16 = u16::MAX;
+
+/// Fields covered by a transfer intent signature
+pub struct TransferIntent<'a> {
+    pub sender_account: &'a Pubkey,
+    pub recipient_account: &'a Pubkey,
+    pub sender_new_commitment: &'a [u8; 64],
+    pub recipient_new_commitment: &'a [u8; 64],
+    pub proof_data: &'a [u8],
+    pub sender_version: u64,
+    pub expiry_slot: u64,
+}
+
+impl TransferIntent<'_> {
+    /**
+     * Serialize the intent into the message the sender signs
+     */
+    pub fn message(&self) -> Vec<u8> {
+        let mut message = Vec::with_capacity(INTENT_DOMAIN.len() + 32 * 4 + 64 * 2 + 16);
+        message.extend_from_slice(INTENT_DOMAIN);
+        message.extend_from_slice(crate::ID.as_ref());
+        message.extend_from_slice(self.sender_account.as_ref());
+        message.extend_from_slice(self.recipient_account.as_ref());
+        message.extend_from_slice(self.sender_new_commitment);
+        message.extend_from_slice(self.recipient_new_commitment);
+        message.extend_from_slice(&hash_leaf(&[self.proof_data]));
+        message.extend_from_slice(&self.sender_version.to_le_bytes());
+        message.extend_from_slice(&self.expiry_slot.to_le_bytes());
+        message
+    }
+}
+
+/**
+ * Require that the previous instruction is an Ed25519 verification of
+ * `message` signed by `signer`
+ */
+pub fn verify_ed25519_authorization(
+    instructions_sysvar: &AccountInfo,
+    signer: &Pubkey,
+    message: &[u8],
+) -> Result<()> {
+    let current_index = load_current_index_checked(instructions_sysvar)?;
+    require!(current_index > 0, ErrorCode::InvalidIntentSignature);
+
+    let ix = load_instruction_at_checked((current_index - 1) as usize, instructions_sysvar)?;
+    require!(
+        ix.program_id == ed25519_program::ID,
+        ErrorCode::InvalidIntentSignature
+    );
+
+    let data = &ix.data;
+    require!(
+        data.len() >= ED25519_HEADER_LEN + ED25519_OFFSETS_LEN && data[0] == 1,
+        ErrorCode::InvalidIntentSignature
+    );
+
+    let offsets = &data[ED25519_HEADER_LEN..ED25519_HEADER_LEN + ED25519_OFFSETS_LEN];
+    let read_u16 = |at: usize| u16::from_le_bytes([offsets[at], offsets[at + 1]]);
+    let signature_ix = read_u16(2);
+    let public_key_offset = read_u16(4) as usize;
+    let public_key_ix = read_u16(6);
+    let message_offset = read_u16(8) as usize;
+    let message_size = read_u16(10) as usize;
+    let message_ix = read_u16(12);
+
+    // SECURITY: Signature, key and message must all live in the Ed25519
+    // instruction itself, otherwise they could point at attacker data
+    require!(
+        signature_ix == SAME_INSTRUCTION
+            && public_key_ix == SAME_INSTRUCTION
+            && message_ix == SAME_INSTRUCTION,
+        ErrorCode::InvalidIntentSignature
+    );
+
+    let public_key = data
+        .get(public_key_offset..public_key_offset + 32)
+        .ok_or(ErrorCode::InvalidIntentSignature)?;
+    let signed_message = data
+        .get(message_offset..message_offset + message_size)
+        .ok_or(ErrorCode::InvalidIntentSignature)?;
+
+    require!(
+        public_key == signer.as_ref() && signed_message == message,
+        ErrorCode::InvalidIntentSignature
+    );
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn intent_message_layout() {
+        let sender = Pubkey::new_unique();
+        let recipient = Pubkey::new_unique();
+        let intent = TransferIntent {
+            sender_account: &sender,
+            recipient_account: &recipient,
+            sender_new_commitment: &[1u8; 64],
+            recipient_new_commitment: &[2u8; 64],
+            proof_data: b"proof",
+            sender_version: 9,
+            expiry_slot: 500,
+        };
+        let message = intent.message();
+
+        let mut offset = 0;
+        let mut field = |len: usize| {
+            let bytes = &message[offset..offset + len];
+            offset += len;
+            bytes.to_vec()
+        };
+        assert_eq!(field(INTENT_DOMAIN.len()), INTENT_DOMAIN);
+        assert_eq!(field(32), crate::ID.as_ref());
+        assert_eq!(field(32), sender.as_ref());
+        assert_eq!(field(32), recipient.as_ref());
+        assert_eq!(field(64), [1u8; 64]);
+        assert_eq!(field(64), [2u8; 64]);
+        assert_eq!(field(32), hash_leaf(&[b"proof"]));
+        assert_eq!(field(8), 9u64.to_le_bytes());
+        assert_eq!(field(8), 500u64.to_le_bytes());
+        assert_eq!(offset, message.len());
+    }
+
+    #[test]
+    fn intent_message_binds_every_field() {
+        let sender = Pubkey::new_unique();
+        let recipient = Pubkey::new_unique();
+        let base = TransferIntent {
+            sender_account: &sender,
+            recipient_account: &recipient,
+            sender_new_commitment: &[1u8; 64],
+            recipient_new_commitment: &[2u8; 64],
+            proof_data: b"proof",
+            sender_version: 9,
+            expiry_slot: 500,
+        };
+        let message = base.message();
+
+        let variants = [
+            TransferIntent { sender_account: &recipient, ..base },
+            TransferIntent { recipient_account: &sender, ..base },
+            TransferIntent { sender_new_commitment: &[3u8; 64], ..base },
+            TransferIntent { recipient_new_commitment: &[3u8; 64], ..base },
+            TransferIntent { proof_data: b"other", ..base },
+            TransferIntent { sender_version: 10, ..base },
+            TransferIntent { expiry_slot: 501, ..base },
+        ];
+        for variant in &variants {
+            assert_ne!(variant.message(), message);
+        }
+    }
+}
@@ -0,0 +1,47 @@
+/*!
+ * Sanctions-Oracle Withdrawal Hook
+ *
+ * Opt-in compliance mode for pool operators. When the config names a
+ * sanctions oracle, withdrawals only release funds to destinations the
+ * oracle has recently attested as cleared. Attestations are
+ * `SanctionsAttestation` PDAs written by the oracle key:
+ *   seeds = [SANCTIONS_ATTESTATION_SEED, oracle, destination]
+ *
+ * Replacing the oracle in the config invalidates every attestation of the
+ * previous one. Deployments without a config (or with no oracle set) do not
+ * check anything.
+ */
+
+use anchor_lang::prelude::*;
+
+use crate::{Config, ErrorCode, SanctionsAttestation};
+
+/**
+ * Require a valid, recent attestation for `destination` if an oracle is configured
+ */
+pub fn enforce_if_configured(
+    config: Option<&Config>,
+    attestation: Option<&SanctionsAttestation>,
+    destination: &Pubkey,
+) -> Result<()> {
+    let Some(config) = config else {
+        return Ok(());
+    };
+    if config.sanctions_oracle == Pubkey::default() {
+        return Ok(());
+    }
+
+    let attestation = attestation.ok_or(ErrorCode::SanctionsAttestationRequired)?;
+    require!(
+        attestation.oracle == config.sanctions_oracle && attestation.destination == *destination,
+        ErrorCode::SanctionsAttestationRequired
+    );
+    require!(attestation.cleared, ErrorCode::DestinationSanctioned);
+
+    let age = Clock::get()?.slot.saturating_sub(attestation.slot);
+    require!(
+        age <= config.attestation_max_age,
+        ErrorCode::SanctionsAttestationStale
+    );
+    Ok(())
+}
@@ -0,0 +1,83 @@
+/*!
+ * Protocol Statistics
+ *
+ * Amount-free activity counters in a single global PDA, so explorers and
+ * dashboards can report pool activity and the anonymity set (live
+ * accounts) without running an indexer. Only event counts are kept:
+ * amounts stay hidden and no per-user data is recorded.
+ *
+ * The stats account is optional per deployment. Instructions take it as an
+ * unchecked, seed-checked account and only update it once
+ * `initialize_stats` has run.
+ *
+ * Transfers and withdrawals are also counted per slot, which backs the
+ * circuit breaker (`limits::enforce_transfer_rate`). Escrowed payments
+ * (claims, streams, HTLCs, ...) count once, when the sender is debited.
+ */
+
+use anchor_lang::prelude::*;
+
+use crate::{ErrorCode, ProtocolStats};
+
+/// Counted protocol activity
+#[derive(Clone, Copy)]
+pub enum Activity {
+    AccountOpened,
+    AccountClosed,
+    Deposit,
+    Transfer,
+    Withdrawal,
+    EscrowOpened,
+    EscrowClosed,
+}
+
+/**
+ * Deserialize the stats PDA, or None if it was never initialized
+ *
+ * `info` must already be seed-checked against the stats PDA.
+ */
+pub fn load_optional(info: &AccountInfo) -> Result<Option<ProtocolStats>> {
+    if info.data_is_empty() || info.owner != &crate::ID {
+        return Ok(None);
+    }
+
+    let data = info.try_borrow_data()?;
+    Ok(Some(ProtocolStats::try_deserialize(&mut &data[..])?))
+}
+
+/**
+ * Count `activity` in the stats PDA (no-op until it is initialized)
+ *
+ * `info` must already be seed-checked against the stats PDA.
+ */
+pub fn record(info: &AccountInfo, activity: Activity) -> Result<()> {
+    let Some(mut stats) = load_optional(info)? else {
+        return Ok(());
+    };
+
+    let counter = match activity {
+        Activity::AccountOpened | Activity::AccountClosed => &mut stats.accounts,
+        Activity::Deposit => &mut stats.deposits,
+        Activity::Transfer => &mut stats.transfers,
+        Activity::Withdrawal => &mut stats.withdrawals,
+        Activity::EscrowOpened | Activity::EscrowClosed => &mut stats.active_escrows,
+    };
+    *counter = match activity {
+        // Accounts opened before the stats PDA existed are not counted
+        Activity::AccountClosed | Activity::EscrowClosed => counter.saturating_sub(1),
+        _ => counter.checked_add(1).ok_or(ErrorCode::Overflow)?,
+    };
+
+    // Per-slot transfer count restarts with each new slot
+    let slot = Clock::get()?.slot;
+    if stats.last_update_slot != slot {
+        stats.slot_transfers = 0;
+    }
+    if let Activity::Transfer | Activity::Withdrawal = activity {
+        stats.slot_transfers = stats.slot_transfers.saturating_add(1);
+    }
+    stats.last_update_slot = slot;
+
+    let mut data = info.try_borrow_mut_data()?;
+    stats.try_serialize(&mut &mut data[..])
+}
@@ -0,0 +1,44 @@
+/*!
+ * Transfer-Hook Aware Token Transfers
+ *
+ * Token-2022 mints with the transfer-hook extension invoke a hook program on
+ * every transfer, and that program needs extra accounts (its
+ * ExtraAccountMetaList PDA plus whatever the list resolves to). Clients pass
+ * those accounts as remaining accounts; they are resolved on-chain here and
+ * appended to the `transfer_checked` CPI.
+ *
+ * Mints of the legacy Token program cannot have hooks and use the plain
+ * Anchor CPI.
+ */
+
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{transfer_checked, TransferChecked};
+
+/**
+ * `transfer_checked` that forwards `ctx.remaining_accounts` to transfer hooks
+ *
+ * Drop-in replacement for `token_interface::transfer_checked`; attach the
+ * instruction's remaining accounts with `CpiContext::with_remaining_accounts`.
+ */
+pub fn transfer_checked_with_hooks<'info>(
+    ctx: CpiContext<'_, '_, '_, 'info, TransferChecked<'info>>,
+    amount: u64,
+    decimals: u8,
+) -> Result<()> {
+    if ctx.program.key() != spl_token_2022::ID {
+        return transfer_checked(ctx, amount, decimals);
+    }
+
+    spl_token_2022::onchain::invoke_transfer_checked(
+        &spl_token_2022::ID,
+        ctx.accounts.from,
+        ctx.accounts.mint,
+        ctx.accounts.to,
+        ctx.accounts.authority,
+        &ctx.remaining_accounts,
+        amount,
+        decimals,
+        ctx.signer_seeds,
+    )
+    .map_err(Into::into)
+}
@@ -0,0 +1,49 @@
+/*!
+ * Travel-Rule Metadata Envelopes
+ *
+ * Regulated senders attach originator/beneficiary information (e.g. an
+ * IVMS101 record) to a transfer, encrypted to the counterparty VASP's
+ * X25519 key. The program never sees the plaintext; it only bounds the
+ * envelope size and publishes it in a `TravelRuleData` event.
+ *
+ * Policy lives in the global config: a non-zero `travel_rule_threshold`
+ * makes the envelope mandatory. Confidential transfer amounts are hidden
+ * and cannot be compared on-chain, so they are treated as above the
+ * threshold. Deployments without a config have no travel-rule policy.
+ */
+
+use anchor_lang::prelude::*;
+
+use crate::travel_rule_constants::{MAX_CIPHERTEXT_LEN, MIN_CIPHERTEXT_LEN};
+use crate::{Config, ErrorCode, TravelRuleEnvelope};
+
+/**
+ * Check an envelope's key and size bounds
+ */
+pub fn validate_envelope(envelope: &TravelRuleEnvelope) -> Result<()> {
+    require!(
+        envelope.vasp_key != [0u8; 32] && envelope.ephemeral_key != [0u8; 32],
+        ErrorCode::InvalidTravelRuleEnvelope
+    );
+    require!(
+        envelope.ciphertext.len() >= MIN_CIPHERTEXT_LEN
+            && envelope.ciphertext.len() <= MAX_CIPHERTEXT_LEN,
+        ErrorCode::InvalidTravelRuleEnvelope
+    );
+    Ok(())
+}
+
+/**
+ * Enforce the travel-rule policy for a transfer with a hidden amount
+ */
+pub fn enforce_for_hidden_amount(
+    config: Option<&Config>,
+    envelope: Option<&TravelRuleEnvelope>,
+) -> Result<()> {
+    if let Some(envelope) = envelope {
+        return validate_envelope(envelope);
+    }
+    let threshold = config.map_or(0, |config| config.travel_rule_threshold);
+    require!(threshold == 0, ErrorCode::TravelRuleEnvelopeRequired);
+    Ok(())
+}
@@ -0,0 +1,62 @@
+/*!
+ * Per-Epoch Transfer Limits (Encrypted Accumulators)
+ *
+ * AML-style velocity limits without revealing amounts. Each account keeps a
+ * `TransferAccumulator` holding a commitment to its outgoing total for the
+ * current Solana epoch. While the config sets `epoch_transfer_limit`, every
+ * confidential transfer must move the accumulator forward:
+ *
+ *   total_new = total_old + amount        (homomorphic, added on-chain)
+ *   limit - total_new in [0, 2^64)        (range proof on the headroom)
+ *
+ * The first transfer of an epoch starts from an empty total, so the new
+ * total is the transfer's amount commitment itself.
+ */
+
+use anchor_lang::prelude::*;
+
+use crate::proof_verification::verify_velocity_proof;
+use crate::{Config, ErrorCode, TransferAccumulator, VelocityUpdate};
+
+/**
+ * Enforce the epoch limit for a transfer, if one is configured
+ *
+ * `accumulator` must already be seed-checked against the sending account.
+ */
+pub fn enforce_if_configured(
+    config: Option<&Config>,
+    accumulator: Option<&mut TransferAccumulator>,
+    update: Option<&VelocityUpdate>,
+    amount_commitment: &[u8; 64],
+) -> Result<()> {
+    let limit = config.map_or(0, |config| config.epoch_transfer_limit);
+    if limit == 0 {
+        return Ok(());
+    }
+
+    let accumulator = accumulator.ok_or(ErrorCode::VelocityProofRequired)?;
+    let update = update.ok_or(ErrorCode::VelocityProofRequired)?;
+
+    // A new epoch resets the running total
+    let epoch = Clock::get()?.epoch;
+    let total_old = if accumulator.epoch == epoch {
+        accumulator.total_commitment
+    } else {
+        [0u8; 64]
+    };
+
+    if let Err(e) = verify_velocity_proof(
+        &update.proof_data,
+        amount_commitment,
+        &total_old,
+        &update.total_commitment,
+        limit,
+    ) {
+        msg!("⚠️  Velocity proof error: {:?}", e);
+        return Err(ErrorCode::InvalidProof.into());
+    }
+
+    accumulator.epoch = epoch;
+    accumulator.total_commitment = update.total_commitment;
+    Ok(())
+}
@@ -0,0 +1,136 @@
+/*!
+ * External Verifier Dispatch
+ *
+ * The config authority can whitelist external verifier programs
+ * (`set_verifier_programs`). A transfer that passes one of them as its
+ * `verifier_program` account has its proof checked by that program via CPI
+ * instead of the built-in verifier, so verification can be upgraded
+ * without touching the transfer logic. Without a verifier account, the
+ * built-in `verify_transfer_proof` is used (stage-profiled in `cu-profile`
+ * builds, see cu_profile.rs).
+ *
+ * The authority can also pin one whitelisted program as the required
+ * verifier (`set_required_verifier`). Transfers must then pass exactly that
+ * program; neither the built-in verifier nor another whitelisted one is
+ * accepted, so a flaw found in them cannot be used to bypass it.
+ *
+ * Verifier interface (Anchor-compatible instruction, no accounts):
+ *   verify_transfer(amount_commitment: [u8; 64], sender_old: [u8; 64],
+ *                   sender_new: [u8; 64], recipient_old: [u8; 64],
+ *                   recipient_new: [u8; 64], proof_data: Vec<u8>)
+ *
+ * The verifier accepts by returning successfully. Any error aborts the
+ * whole transaction (CPI failures cannot be caught).
+ */
+
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::instruction::Instruction;
+use anchor_lang::solana_program::program::invoke;
+
+#[cfg(not(feature = "cu-profile"))]
+use crate::proof_verification::verify_transfer_proof;
+use crate::{verifier_constants, Config, ErrorCode};
+
+/// Preimage of the verifier instruction's Anchor discriminator
+pub const VERIFY_TRANSFER_IX: &[u8] = b"global:verify_transfer";
+
+/// Public inputs of a transfer proof
+pub struct TransferStatement<'a> {
+    pub amount_commitment: &'a [u8; 64],
+    pub sender_old: &'a [u8; 64],
+    pub sender_new: &'a [u8; 64],
+    pub recipient_old: &'a [u8; 64],
+    pub recipient_new: &'a [u8; 64],
+    pub proof_data: &'a [u8],
+}
+
+/**
+ * Validate a verifier whitelist (bounded, no duplicates, no default key)
+ */
+pub fn validate_programs(programs: &[Pubkey]) -> Result<()> {
+    require!(
+        programs.len() <= verifier_constants::MAX_VERIFIER_PROGRAMS,
+        ErrorCode::InvalidConfigParams
+    );
+    for (i, program) in programs.iter().enumerate() {
+        require!(
+            *program != Pubkey::default() && *program != crate::ID && !programs[..i].contains(program),
+            ErrorCode::InvalidConfigParams
+        );
+    }
+    Ok(())
+}
+
+/**
+ * Validate a required verifier (none, or one of the whitelisted programs)
+ */
+pub fn validate_required(required: &Pubkey, programs: &[Pubkey]) -> Result<()> {
+    require!(
+        *required == Pubkey::default() || programs.contains(required),
+        ErrorCode::InvalidConfigParams
+    );
+    Ok(())
+}
+
+/**
+ * Verify a transfer proof with the selected verifier
+ *
+ * `verifier` must be a whitelisted, executable program when present, and
+ * the config's required verifier when one is set.
+ */
+pub fn verify_transfer(
+    config: Option<&Config>,
+    verifier: Option<&AccountInfo>,
+    statement: &TransferStatement,
+) -> Result<()> {
+    // SECURITY: A pinned verifier cannot be sidestepped by omitting it
+    if let Some(required) = config
+        .map(|config| config.required_verifier)
+        .filter(|required| *required != Pubkey::default())
+    {
+        require!(
+            verifier.is_some_and(|verifier| *verifier.key == required),
+            ErrorCode::VerifierRequired
+        );
+    }
+
+    let Some(verifier) = verifier else {
+        #[cfg(feature = "cu-profile")]
+        let result = crate::cu_profile::verify_transfer_proof(statement);
+        #[cfg(not(feature = "cu-profile"))]
+        let result = verify_transfer_proof(
+            statement.proof_data,
+            statement.amount_commitment,
+            statement.sender_new,
+            statement.sender_old,
+            statement.recipient_old,
+            statement.recipient_new,
+        );
+        if let Err(e) = result {
+            msg!("⚠️  Proof verification error: {:?}", e);
+            return Err(ErrorCode::InvalidProof.into());
+        }
+        return Ok(());
+    };
+
+    // SECURITY: Only programs the config authority approved may vouch for proofs
+    let approved = config.is_some_and(|config| config.verifier_programs.contains(verifier.key));
+    require!(approved && verifier.executable, ErrorCode::VerifierNotApproved);
+
+    let mut data = Vec::with_capacity(8 + 5 * 64 + 4 + statement.proof_data.len());
+    data.extend_from_slice(&solana_sha256_hasher::hash(VERIFY_TRANSFER_IX).to_bytes()[..8]);
+    data.extend_from_slice(statement.amount_commitment);
+    data.extend_from_slice(statement.sender_old);
+    data.extend_from_slice(statement.sender_new);
+    data.extend_from_slice(statement.recipient_old);
+    data.extend_from_slice(statement.recipient_new);
+    data.extend_from_slice(&(statement.proof_data.len() as u32).to_le_bytes());
+    data.extend_from_slice(statement.proof_data);
+
+    let ix = Instruction {
+        program_id: *verifier.key,
+        accounts: Vec::new(),
+        data,
+    };
+    invoke(&ix, std::slice::from_ref(verifier)).map_err(Into::into)
+}
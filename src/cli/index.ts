@@ -7,10 +7,22 @@
 import { Command } from 'commander';
 import { PublicKey } from '@solana/web3.js';
 import { ConfidentialWallet } from '../wallet/ConfidentialWallet';
-import { LocalKeyStorage } from '../storage/KeyStorage';
+import { LocalKeyStorage, VIEW_KEY_FILE } from '../storage/KeyStorage';
+import { WatchOnlyWallet } from '../wallet/WatchOnlyWallet';
+import {
+    activityFromHistory,
+    analyzeFootprint,
+    discoverLinkedAccounts,
+    type FootprintAccount,
+} from '../privacy/PrivacyReport';
+import { PROGRAM_CONSTANTS } from '../utils/constants';
+import type { TransactionRecord } from '../types';
 import {
     promptPasswordWithConfirmation,
     loadWallet,
+    loadWatchOnlyWallet,
+    isWatchOnlyWallet,
+    assertSpendAuthority,
     formatError,
     formatAmount,
     formatAddress,
@@ -24,6 +36,13 @@ import {
 
 const program = new Command();
 
+/**
+ * Format lamports as SOL for display
+ */
+function formatLamports(lamports: bigint): string {
+    return (Number(lamports) / 1_000_000_000).toFixed(4);
+}
+
 // Configure CLI
 program
     .name('utxo-wallet')
@@ -42,7 +61,7 @@ program
             const keyStorage = new LocalKeyStorage(config.keyStoragePath);
 
             // Check if wallet already exists
-            if (keyStorage.exists()) {
+            if (keyStorage.exists() || isWatchOnlyWallet(config)) {
                 displayWarning('Wallet already exists!');
                 displayInfo('Use a different directory or delete the existing wallet.');
                 process.exit(1);
@@ -100,6 +119,7 @@ program
     .action(async () => {
         try {
             const config = getDefaultConfig();
+            assertSpendAuthority(config, 'set up accounts');
             const keyStorage = new LocalKeyStorage(config.keyStoragePath);
 
             displayInfo('Loading wallet...');
@@ -147,6 +167,7 @@ program
     .action(async (amount: string) => {
         try {
             const config = getDefaultConfig();
+            assertSpendAuthority(config, 'deposit');
             const keyStorage = new LocalKeyStorage(config.keyStoragePath);
 
             displayInfo('Loading wallet...');
@@ -175,6 +196,12 @@ program
     .action(async () => {
         try {
             const config = getDefaultConfig();
+            if (isWatchOnlyWallet(config)) {
+                displayInfo('Loading watch-only wallet...');
+                const watchOnly = await loadWatchOnlyWallet(config);
+                displayWatchedBalances(watchOnly);
+                return;
+            }
             const keyStorage = new LocalKeyStorage(config.keyStoragePath);
 
             displayInfo('Loading wallet...');
@@ -203,6 +230,7 @@ program
     .action(async (recipient: string, amount: string) => {
         try {
             const config = getDefaultConfig();
+            assertSpendAuthority(config, 'transfer');
             const keyStorage = new LocalKeyStorage(config.keyStoragePath);
 
             // Validate recipient address
@@ -243,6 +271,7 @@ program
     .action(async () => {
         try {
             const config = getDefaultConfig();
+            assertSpendAuthority(config, 'process transfers');
             const keyStorage = new LocalKeyStorage(config.keyStoragePath);
 
             displayInfo('Loading wallet...');
@@ -295,7 +324,9 @@ program
             const keyStorage = new LocalKeyStorage(config.keyStoragePath);
 
             displayInfo('Loading wallet...');
-            const wallet = await loadWallet(config, keyStorage);
+            const wallet = isWatchOnlyWallet(config)
+                ? await loadWatchOnlyWallet(config)
+                : await loadWallet(config, keyStorage);
 
             const limit = parseInt(options.limit);
             const allHistory = wallet.getHistory();
@@ -345,6 +376,25 @@ program
     .action(async () => {
         try {
             const config = getDefaultConfig();
+            if (isWatchOnlyWallet(config)) {
+                displayInfo('Loading watch-only wallet...');
+                const watchOnly = await loadWatchOnlyWallet(config);
+
+                displayInfo('Scanning watched accounts for balance views...');
+                const activity = await watchOnly.sync();
+
+                displaySuccess(`Wallet synced! (${activity.length} new balance views)`);
+                const incoming = activity.filter((change) => change.delta !== undefined && change.delta > 0n);
+                if (incoming.length > 0) {
+                    console.log('\n📥 Incoming Transfers:');
+                    incoming.forEach((change) => {
+                        console.log(`   +${formatLamports(change.delta!)} SOL to ${formatAddress(change.account)}`);
+                        console.log(`      ${getExplorerUrl(change.signature, config.network)}`);
+                    });
+                }
+                displayWatchedBalances(watchOnly);
+                return;
+            }
             const keyStorage = new LocalKeyStorage(config.keyStoragePath);
 
             displayInfo('Loading wallet...');
@@ -373,6 +423,20 @@ program
     .action(async () => {
         try {
             const config = getDefaultConfig();
+            if (isWatchOnlyWallet(config)) {
+                displayInfo('Loading watch-only wallet...');
+                const watchOnly = await loadWatchOnlyWallet(config);
+
+                displaySuccess('Watch-only wallet information:');
+                console.log('\n👁️  Viewing Key:');
+                console.log(`   ${Buffer.from(watchOnly.getViewingPublicKey()).toString('hex')}`);
+                console.log('\n📍 Watched Accounts:');
+                watchOnly.getWatchedAccounts().forEach((account) => {
+                    console.log(`   ${account.toBase58()}`);
+                });
+                console.log('\n🔒 No spend authority: deposit, transfer and process-batch are disabled.');
+                return;
+            }
             const keyStorage = new LocalKeyStorage(config.keyStoragePath);
 
             displayInfo('Loading wallet...');
@@ -401,5 +465,168 @@ program
         }
     });
 
+/**
+ * View key command - Show (or export) the wallet's viewing key
+ */
+program
+    .command('view-key')
+    .description('Show the viewing key to register for watch-only access')
+    .option('--export-secret', 'Also print the viewing secret to import into a watch-only wallet')
+    .action(async (options) => {
+        try {
+            const config = getDefaultConfig();
+            assertSpendAuthority(config, 'export a viewing key');
+            const keyStorage = new LocalKeyStorage(config.keyStoragePath);
+
+            displayInfo('Loading wallet...');
+            const wallet = await loadWallet(config, keyStorage);
+
+            displaySuccess('Viewing key:');
+            console.log('\n👁️  Viewing Public Key (register with register_viewing_key):');
+            console.log(`   ${Buffer.from(wallet.getViewingPublicKey()).toString('hex')}`);
+
+            if (options.exportSecret) {
+                console.log('\n🔑 Viewing Secret (import with: utxo-wallet import-view-key):');
+                console.log(`   ${Buffer.from(wallet.getViewingSecret()).toString('hex')}`);
+                console.log('\n⚠️  The viewing secret reveals every published balance.');
+                console.log('   It cannot spend funds. Share it only with trusted auditors.');
+            }
+        } catch (error: any) {
+            displayError(formatError(error));
+            process.exit(1);
+        }
+    });
+
+/**
+ * Import view key command - Create a watch-only wallet
+ */
+program
+    .command('import-view-key')
+    .description('Create a watch-only wallet from a viewing secret (no spend authority)')
+    .argument('<viewingSecret>', 'Viewing secret (64 hex characters)')
+    .requiredOption('-a, --account <addresses...>', 'Encrypted account addresses to watch')
+    .action(async (viewingSecret: string, options) => {
+        try {
+            const config = getDefaultConfig();
+            const keyStorage = new LocalKeyStorage(config.keyStoragePath, VIEW_KEY_FILE);
+
+            if (new LocalKeyStorage(config.keyStoragePath).exists() || keyStorage.exists()) {
+                displayWarning('Wallet already exists!');
+                displayInfo('Use a different directory for the watch-only wallet.');
+                process.exit(1);
+            }
+
+            if (!/^[0-9a-fA-F]{64}$/.test(viewingSecret)) {
+                displayError('Viewing secret must be 64 hex characters');
+                process.exit(1);
+            }
+
+            let accounts: PublicKey[];
+            try {
+                accounts = (options.account as string[]).map((address) => new PublicKey(address));
+            } catch {
+                displayError('Invalid account address');
+                process.exit(1);
+            }
+
+            displayInfo('Please enter a strong password to encrypt the viewing key.');
+            const password = await promptPasswordWithConfirmation();
+            if (password.length < 8) {
+                displayError('Password must be at least 8 characters long');
+                process.exit(1);
+            }
+
+            const wallet = new WatchOnlyWallet(config, keyStorage);
+            await wallet.importViewingKey(Buffer.from(viewingSecret, 'hex'), accounts, password);
+
+            displaySuccess('Watch-only wallet created!');
+            console.log('\n👁️  Watching:');
+            accounts.forEach((account) => console.log(`   ${account.toBase58()}`));
+            console.log('\n🔒 Spend commands (deposit, transfer, process-batch) are disabled.');
+            console.log('\n📖 Next Steps:');
+            console.log('   1. Scan for balance views: utxo-wallet sync');
+            console.log('   2. Check balances: utxo-wallet balance');
+        } catch (error: any) {
+            displayError(formatError(error));
+            process.exit(1);
+        }
+    });
+
+/**
+ * Privacy report command - Score the wallet's deanonymization risk
+ */
+program
+    .command('privacy-report')
+    .description('Analyze your on-chain footprint and score deanonymization risk')
+    .option('--json', 'Print the report as JSON')
+    .action(async (options) => {
+        try {
+            const config = getDefaultConfig();
+            const programId = new PublicKey(PROGRAM_CONSTANTS.PRIVACY_TRANSFER_PROGRAM_ID);
+
+            displayInfo('Loading wallet...');
+            let owner: string;
+            let accounts: FootprintAccount[];
+            let history: TransactionRecord[];
+            if (isWatchOnlyWallet(config)) {
+                const watchOnly = await loadWatchOnlyWallet(config);
+                owner = 'watch-only';
+                accounts = watchOnly.getWatchedAccounts().map((account) => ({
+                    address: account.toBase58(),
+                    kind: 'encrypted-account' as const,
+                }));
+                history = watchOnly.getHistory();
+            } else {
+                const wallet = await loadWallet(config, new LocalKeyStorage(config.keyStoragePath));
+                owner = wallet.getAddress().toBase58();
+
+                displayInfo('Scanning linked program accounts...');
+                accounts = await discoverLinkedAccounts(wallet.getConnection(), wallet.getAddress(), programId);
+                history = wallet.getHistory();
+            }
+
+            const report = analyzeFootprint({ owner, accounts, activity: activityFromHistory(history) });
+
+            if (options.json) {
+                console.log(JSON.stringify(report, null, 2));
+                return;
+            }
+
+            const levelIcon = report.level === 'high' ? '🔴' : report.level === 'medium' ? '🟡' : '🟢';
+            displaySuccess('Privacy report ready!');
+            console.log(`\n${levelIcon} Deanonymization Risk: ${report.score}/100 (${report.level})`);
+            console.log(`   Linked accounts: ${accounts.length}, operations analyzed: ${history.length}`);
+
+            if (report.findings.length === 0) {
+                console.log('\n✅ No linkable patterns found in your footprint.');
+                return;
+            }
+
+            console.log('\n🔍 Findings:\n');
+            report.findings.forEach((finding, i) => {
+                console.log(`${i + 1}. [${finding.severity.toUpperCase()}] ${finding.title} (+${finding.points})`);
+                console.log(`   ${finding.detail}`);
+                console.log(`   💡 ${finding.suggestion}`);
+                console.log('');
+            });
+        } catch (error: any) {
+            displayError(formatError(error));
+            process.exit(1);
+        }
+    });
+
+/**
+ * Print the stored balances of a watch-only wallet
+ */
+function displayWatchedBalances(wallet: WatchOnlyWallet): void {
+    const balances = wallet.getBalances();
+    console.log('\n💰 Watched Balances (watch-only):');
+    wallet.getWatchedAccounts().forEach((account) => {
+        const balance = balances.get(account.toBase58());
+        const shown = balance === undefined ? 'no balance view yet' : `${formatLamports(balance)} SOL`;
+        console.log(`   ${formatAddress(account.toBase58())}: ${shown}`);
+    });
+}
+
 // Parse arguments
 program.parse();
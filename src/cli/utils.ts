@@ -4,7 +4,8 @@
 
 import * as readline from 'readline';
 import { ConfidentialWallet } from '../wallet/ConfidentialWallet';
-import { LocalKeyStorage } from '../storage/KeyStorage';
+import { WatchOnlyWallet } from '../wallet/WatchOnlyWallet';
+import { LocalKeyStorage, VIEW_KEY_FILE } from '../storage/KeyStorage';
 import { WalletConfig, createError } from '../types';
 import { constantTimeEqual } from '../crypto/zkproofs/primitives';
 
@@ -146,6 +147,46 @@ export async function loadWallet(
     }
 }
 
+/**
+ * Whether the wallet directory holds only a viewing key
+ * @param config - Wallet configuration
+ * @returns True for watch-only wallets (no spend keys)
+ */
+export function isWatchOnlyWallet(config: WalletConfig): boolean {
+    return !new LocalKeyStorage(config.keyStoragePath).exists()
+        && new LocalKeyStorage(config.keyStoragePath, VIEW_KEY_FILE).exists();
+}
+
+/**
+ * Refuse spend commands on a watch-only wallet
+ * @param config - Wallet configuration
+ * @param operation - Command being run (shown in the error)
+ * @throws UTXOError with ErrorCode.WATCH_ONLY for watch-only wallets
+ */
+export function assertSpendAuthority(config: WalletConfig, operation: string): void {
+    if (isWatchOnlyWallet(config)) {
+        throw createError.watchOnly(operation);
+    }
+}
+
+/**
+ * Load watch-only wallet with password prompt
+ * @param config - Wallet configuration
+ * @returns Initialized watch-only wallet
+ */
+export async function loadWatchOnlyWallet(config: WalletConfig): Promise<WatchOnlyWallet> {
+    const keyStorage = new LocalKeyStorage(config.keyStoragePath, VIEW_KEY_FILE);
+    if (!keyStorage.exists()) {
+        throw createError.keyNotFound();
+    }
+
+    const password = await promptPassword();
+    const wallet = new WatchOnlyWallet(config, keyStorage);
+    await wallet.initialize(password);
+
+    return wallet;
+}
+
 /**
  * Format error message for user display
  * @param error - Error object
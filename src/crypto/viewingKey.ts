@@ -0,0 +1,148 @@
+/**
+ * Viewing Keys
+ *
+ * A viewing key is an X25519 keypair that can read balance openings but
+ * carries no spend authority. The owner registers its public key on an
+ * encrypted account (register_viewing_key) and publishes openings with
+ * publish_balance_view; whoever holds the viewing secret (a treasurer, an
+ * accountant, a watch-only wallet) decrypts them from BalanceView events.
+ *
+ * ENVELOPE (EncryptedMemo on-chain):
+ * - ephemeral_key: X25519 public key of a one-time sender key
+ * - key = SHA-256(MEMO_KDF_DOMAIN || ECDH(ephemeral, viewing key))
+ * - ciphertext = AES-256-GCM(key, nonce, balance (u64 LE) || blinding (32))
+ *   followed by the 16-byte tag
+ */
+
+import * as crypto from 'crypto';
+import { x25519 } from '@noble/curves/ed25519.js';
+import { sha256 } from '@noble/hashes/sha2.js';
+import { PublicKey } from '@solana/web3.js';
+import { ScalarOps, scalarToBytes } from './zkproofs/primitives';
+import type { BalanceOpening } from './zkproofs/publicAmountProof';
+
+/** Domain of the viewing secret derived from a wallet seed */
+const VIEWING_KEY_DOMAIN = 'privacy-transfer/viewing-key';
+
+/** Domain of the envelope key derivation */
+const MEMO_KDF_DOMAIN = 'privacy-transfer/memo';
+
+const NONCE_LENGTH = 12;
+const TAG_LENGTH = 16;
+const PLAINTEXT_LENGTH = 40;
+
+/**
+ * Encrypted opening as stored in an EncryptedMemo
+ */
+export interface EncryptedView {
+    ephemeralKey: Uint8Array;
+    nonce: Uint8Array;
+    ciphertext: Uint8Array;
+}
+
+/**
+ * Decoded BalanceView event
+ */
+export interface BalanceViewEvent {
+    encryptedAccount: PublicKey;
+    viewingKey: Uint8Array;
+    version: bigint;
+    stateHash: Uint8Array;
+    view: EncryptedView;
+}
+
+/**
+ * Viewing secret of a wallet (deterministic, so it can be re-exported)
+ */
+export function deriveViewingSecret(seed: Uint8Array): Uint8Array {
+    const domain = new TextEncoder().encode(VIEWING_KEY_DOMAIN);
+    const input = new Uint8Array(domain.length + seed.length);
+    input.set(domain, 0);
+    input.set(seed, domain.length);
+    return sha256(input);
+}
+
+/**
+ * Public key to register on-chain for a viewing secret
+ */
+export function viewingPublicKey(viewingSecret: Uint8Array): Uint8Array {
+    return x25519.getPublicKey(viewingSecret);
+}
+
+/**
+ * Encrypt a balance opening to a viewing key (publish_balance_view)
+ */
+export function sealBalanceView(viewingKey: Uint8Array, opening: BalanceOpening): EncryptedView {
+    const ephemeralSecret = new Uint8Array(crypto.randomBytes(32));
+    const ephemeralKey = x25519.getPublicKey(ephemeralSecret);
+    const key = envelopeKey(x25519.getSharedSecret(ephemeralSecret, viewingKey));
+
+    const plaintext = Buffer.alloc(PLAINTEXT_LENGTH);
+    plaintext.writeBigUInt64LE(opening.value, 0);
+    plaintext.set(scalarToBytes(opening.blinding), 8);
+
+    const nonce = crypto.randomBytes(NONCE_LENGTH);
+    const cipher = crypto.createCipheriv('aes-256-gcm', key, nonce);
+    const ciphertext = Buffer.concat([cipher.update(plaintext), cipher.final(), cipher.getAuthTag()]);
+
+    return { ephemeralKey, nonce: new Uint8Array(nonce), ciphertext: new Uint8Array(ciphertext) };
+}
+
+/**
+ * Decrypt a balance opening with the viewing secret
+ *
+ * @throws Error if the view was not encrypted to this key or was tampered with
+ */
+export function openBalanceView(viewingSecret: Uint8Array, view: EncryptedView): BalanceOpening {
+    if (view.nonce.length !== NONCE_LENGTH || view.ciphertext.length !== PLAINTEXT_LENGTH + TAG_LENGTH) {
+        throw new Error('Malformed balance view');
+    }
+    const key = envelopeKey(x25519.getSharedSecret(viewingSecret, view.ephemeralKey));
+
+    const decipher = crypto.createDecipheriv('aes-256-gcm', key, view.nonce);
+    decipher.setAuthTag(view.ciphertext.slice(PLAINTEXT_LENGTH));
+    let plaintext: Buffer;
+    try {
+        plaintext = Buffer.concat([decipher.update(view.ciphertext.slice(0, PLAINTEXT_LENGTH)), decipher.final()]);
+    } catch {
+        throw new Error('Balance view cannot be decrypted with this viewing key');
+    }
+
+    return {
+        value: plaintext.readBigUInt64LE(0),
+        blinding: ScalarOps.fromBytes(plaintext.subarray(8)),
+    };
+}
+
+/**
+ * Decode a BalanceView event from its `Program data:` payload
+ *
+ * @returns The event, or null if the payload is another event
+ */
+export function decodeBalanceViewEvent(data: Uint8Array): BalanceViewEvent | null {
+    const discriminator = sha256(new TextEncoder().encode('event:BalanceView')).slice(0, 8);
+    if (data.length !== 8 + 204 || !discriminator.every((byte, i) => data[i] === byte)) {
+        return null;
+    }
+
+    const body = Buffer.from(data.subarray(8));
+    return {
+        encryptedAccount: new PublicKey(body.subarray(0, 32)),
+        viewingKey: new Uint8Array(body.subarray(32, 64)),
+        version: body.readBigUInt64LE(64),
+        stateHash: new Uint8Array(body.subarray(72, 104)),
+        view: {
+            ephemeralKey: new Uint8Array(body.subarray(104, 136)),
+            nonce: new Uint8Array(body.subarray(136, 148)),
+            ciphertext: new Uint8Array(body.subarray(148, 204)),
+        },
+    };
+}
+
+function envelopeKey(sharedSecret: Uint8Array): Buffer {
+    const domain = new TextEncoder().encode(MEMO_KDF_DOMAIN);
+    const input = new Uint8Array(domain.length + sharedSecret.length);
+    input.set(domain, 0);
+    input.set(sharedSecret, domain.length);
+    return Buffer.from(sha256(input));
+}
@@ -0,0 +1,78 @@
+/**
+ * Tests for cancellable proof jobs
+ * Tests: Progress reporting, cancellation, AbortSignal, Bulletproof hooks
+ */
+
+import { spawnProofJob, stageProgress, ProofJobCancelledError, type ProofProgress } from '../proofJob';
+import { Bulletproof } from '../bulletproof';
+import { ScalarOps } from '../primitives';
+
+describe('Proof Jobs', () => {
+    test('Reports monotonic progress up to 100', async () => {
+        const seen: ProofProgress[] = [];
+        const job = spawnProofJob(async (ctx) => {
+            await ctx.step('first', 40);
+            await ctx.step('regressed', 10);
+            await ctx.step('second', 80);
+            return 'proof';
+        }, { onProgress: (progress) => seen.push(progress) });
+
+        expect(await job.result).toBe('proof');
+        const percents = seen.map((progress) => progress.percent);
+        expect(percents).toEqual([...percents].sort((a, b) => a - b));
+        expect(job.progress).toEqual({ percent: 100, stage: 'done' });
+    });
+
+    test('Cancel stops the job at its next step', async () => {
+        let reachedEnd = false;
+        const job = spawnProofJob(async (ctx) => {
+            await ctx.step('first', 10);
+            job.cancel();
+            await ctx.step('second', 50);
+            reachedEnd = true;
+            return 'proof';
+        });
+
+        await expect(job.result).rejects.toBeInstanceOf(ProofJobCancelledError);
+        expect(reachedEnd).toBe(false);
+    });
+
+    test('An aborted signal cancels the job before it starts', async () => {
+        const controller = new AbortController();
+        controller.abort();
+        let started = false;
+        const job = spawnProofJob(async () => {
+            started = true;
+            return 'proof';
+        }, { signal: controller.signal });
+
+        await expect(job.result).rejects.toBeInstanceOf(ProofJobCancelledError);
+        expect(started).toBe(false);
+    });
+
+    test('Bulletproof rounds drive stage progress', async () => {
+        const seen: number[] = [];
+        const job = spawnProofJob(
+            (ctx) => Bulletproof.prove(5n, ScalarOps.random(), 8, stageProgress(ctx, 'range proof', 0, 50)),
+            { onProgress: (progress) => seen.push(progress.percent) }
+        );
+
+        const proof = await job.result;
+        expect(await Bulletproof.verify(proof)).toBe(true);
+        expect(seen).toContain(50);
+        expect(seen.filter((percent) => percent > 0 && percent < 50).length).toBeGreaterThan(2);
+    });
+
+    test('Cancelling mid-proof aborts the Bulletproof', async () => {
+        const job = spawnProofJob((ctx) =>
+            Bulletproof.prove(5n, ScalarOps.random(), 8, async (fraction) => {
+                if (fraction >= 0.3) {
+                    job.cancel();
+                }
+                await ctx.step('range proof', fraction * 100);
+            })
+        );
+
+        await expect(job.result).rejects.toBeInstanceOf(ProofJobCancelledError);
+    });
+});
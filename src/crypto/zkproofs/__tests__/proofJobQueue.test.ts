@@ -0,0 +1,151 @@
+/**
+ * Tests for the durable proof job queue
+ * Tests: Polling by ID, restart recovery, cancellation, SQLite store
+ */
+
+import {
+    decodeJobValue,
+    encodeJobValue,
+    MemoryProofJobStore,
+    ProofJobQueue,
+    SqliteProofJobStore,
+    type ProofJobHandler,
+    type ProofJobStore,
+} from '../proofJobQueue';
+
+// better-sqlite3 is optional; the SQLite case only runs where it is installed
+function openSqlite() {
+    try {
+        // eslint-disable-next-line @typescript-eslint/no-var-requires
+        const Database = require('better-sqlite3');
+        return new Database(':memory:');
+    } catch {
+        return undefined;
+    }
+}
+
+const handlers: Record<string, ProofJobHandler> = {
+    async double(input: { value: bigint }, ctx) {
+        await ctx.step('doubling', 50);
+        return { value: input.value * 2n, proof: new Uint8Array([1, 2, 3]) };
+    },
+    async fail() {
+        throw new Error('bad witness');
+    },
+};
+
+/** Handler that waits until released, so a test can stop the queue mid-job */
+function blockingHandler(): { handler: ProofJobHandler; release: () => void } {
+    let release!: () => void;
+    const released = new Promise<void>((resolve) => { release = resolve; });
+    return {
+        handler: async (_input, ctx) => {
+            await ctx.step('waiting', 10);
+            await released;
+            await ctx.step('finishing', 90);
+            return 'done';
+        },
+        release,
+    };
+}
+
+const stores: [string, () => ProofJobStore][] = [['MemoryProofJobStore', () => new MemoryProofJobStore()]];
+if (openSqlite()) {
+    stores.push(['SqliteProofJobStore', () => new SqliteProofJobStore(openSqlite())]);
+}
+
+describe('Proof Job Values', () => {
+    test('bigint and byte values round-trip', () => {
+        const value = { amount: 5n, bytes: new Uint8Array([9, 8]), buffer: Buffer.from([7]), nested: [1n] };
+        expect(decodeJobValue(encodeJobValue(value))).toEqual({
+            amount: 5n,
+            bytes: new Uint8Array([9, 8]),
+            buffer: new Uint8Array([7]),
+            nested: [1n],
+        });
+    });
+});
+
+describe.each(stores)('ProofJobQueue on %s', (_name, create) => {
+    test('Runs submitted jobs and reports status by ID', async () => {
+        const queue = new ProofJobQueue(create(), handlers);
+        const id = queue.submit('double', { value: 21n });
+        expect(queue.status(id)?.status).toBe('queued');
+
+        queue.start();
+        await queue.drain();
+
+        const record = queue.status(id)!;
+        expect(record.status).toBe('succeeded');
+        expect(record.progress).toEqual({ percent: 100, stage: 'done' });
+        expect(record.attempts).toBe(1);
+        expect(queue.result(id)).toEqual({ value: 42n, proof: new Uint8Array([1, 2, 3]) });
+    });
+
+    test('Records handler failures and rejects unknown kinds', async () => {
+        const queue = new ProofJobQueue(create(), handlers);
+        queue.start();
+        const id = queue.submit('fail', null);
+        await queue.drain();
+
+        expect(queue.status(id)).toMatchObject({ status: 'failed', error: 'bad witness' });
+        expect(queue.result(id)).toBeUndefined();
+        expect(queue.status('missing')).toBeUndefined();
+        expect(() => queue.submit('unknown', null)).toThrow('Unknown proof job kind');
+    });
+
+    test('Jobs interrupted by a restart run again', async () => {
+        const store = create();
+        const first = blockingHandler();
+        const before = new ProofJobQueue(store, { slow: first.handler });
+        before.start();
+        const id = before.submit('slow', null);
+        const queued = before.submit('slow', null);
+
+        await new Promise((resolve) => setImmediate(resolve));
+        const stopped = before.stop();
+        first.release();
+        await stopped;
+        expect(store.get(id)?.status).toBe('running');
+        expect(store.get(queued)?.status).toBe('queued');
+
+        const second = blockingHandler();
+        second.release();
+        const after = new ProofJobQueue(store, { slow: second.handler });
+        after.start();
+        await after.drain();
+
+        expect(after.status(id)).toMatchObject({ status: 'succeeded', attempts: 2 });
+        expect(after.status(queued)).toMatchObject({ status: 'succeeded', attempts: 1 });
+        expect(after.result(id)).toBe('done');
+    });
+
+    test('A job interrupted maxAttempts times fails', async () => {
+        const store = create();
+        const queue = new ProofJobQueue(store, handlers, { maxAttempts: 2 });
+        const id = queue.submit('double', { value: 1n });
+        store.put({ ...store.get(id)!, status: 'running', attempts: 2 });
+
+        queue.start();
+        await queue.drain();
+        expect(queue.status(id)).toMatchObject({ status: 'failed', error: 'Interrupted 2 times' });
+    });
+
+    test('Cancels queued and running jobs', async () => {
+        const blocking = blockingHandler();
+        const queue = new ProofJobQueue(create(), { slow: blocking.handler });
+        queue.start();
+        const running = queue.submit('slow', null);
+        const waiting = queue.submit('slow', null);
+
+        expect(queue.cancel(waiting)).toBe(true);
+        await new Promise((resolve) => setImmediate(resolve));
+        expect(queue.cancel(running)).toBe(true);
+        blocking.release();
+        await queue.drain();
+
+        expect(queue.status(running)?.status).toBe('cancelled');
+        expect(queue.status(waiting)?.status).toBe('cancelled');
+        expect(queue.cancel(running)).toBe(false);
+    });
+});
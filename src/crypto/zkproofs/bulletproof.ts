@@ -39,11 +39,16 @@ export interface BulletproofRangeProof {
 export class Bulletproof {
     /**
      * Generate complete Bulletproof range proof
+     *
+     * `onProgress` is awaited with the completed fraction (0..1) between
+     * stages and after every inner product round; proof jobs use it to
+     * report progress and to cancel (by throwing).
      */
     static async prove(
         value: Scalar,
         blinding: Scalar,
-        n: number = 64
+        n: number = 64,
+        onProgress?: (fraction: number) => Promise<void>
     ): Promise<BulletproofRangeProof> {
         // Validate range
         if (value < 0n || value >= (1n << BigInt(n))) {
@@ -94,6 +99,9 @@ export class Bulletproof {
 
         transcript.appendPoint('A', A);
         transcript.appendPoint('S', S);
+        if (onProgress) {
+            await onProgress(0.2);
+        }
 
         // Get challenges y and z
         const y = transcript.challengeScalar('y');
@@ -149,6 +157,9 @@ export class Bulletproof {
 
         transcript.appendPoint('T1', T1);
         transcript.appendPoint('T2', T2);
+        if (onProgress) {
+            await onProgress(0.3);
+        }
 
         // Get challenge x
         const x = transcript.challengeScalar('x');
@@ -225,7 +236,10 @@ export class Bulletproof {
             G_factors,
             H_factors,
             Q,
-            transcript
+            transcript,
+            onProgress
+                ? (round, rounds) => onProgress(0.3 + 0.7 * (round / rounds))
+                : undefined
         );
 
         return {
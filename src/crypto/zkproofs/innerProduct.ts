@@ -121,6 +121,7 @@ export class InnerProductArgument {
      * @param H_factors - Optional scalars to adjust H generators (default: all 1s)
      * @param u - Additional generator point
      * @param transcript - Fiat-Shamir transcript
+     * @param onRound - Optional hook awaited after each halving round
      */
     static async prove(
         a: Scalar[],
@@ -128,7 +129,8 @@ export class InnerProductArgument {
         G_factors: Scalar[] | null,
         H_factors: Scalar[] | null,
         Q: CurvePoint,
-        transcript: Transcript
+        transcript: Transcript,
+        onRound?: (round: number, rounds: number) => Promise<void>
     ): Promise<InnerProductProof> {
         const n = a.length;
 
@@ -171,6 +173,8 @@ export class InnerProductArgument {
         // They're only used in the generator folding step
 
         let currentN = n;
+        const rounds = Math.log2(n);
+        let round = 0;
 
         // Recursive halving
         while (currentN > 1) {
@@ -275,6 +279,11 @@ export class InnerProductArgument {
             }
 
             currentN = nPrime;
+
+            round++;
+            if (onRound) {
+                await onRound(round, rounds);
+            }
         }
 
         return {
@@ -0,0 +1,108 @@
+/**
+ * On-Chain Transcript and Encoding
+ *
+ * Mirrors confidential_proofs::merlin_transcript (Keccak256 running state)
+ * and the program's 64-byte commitment slots, so proofs built here produce
+ * the same challenges the program derives during verification.
+ *
+ * The Shake256 MerlinTranscript in dalek-compat.ts is kept for the
+ * off-chain Dalek-compatible proofs; it does NOT match the program.
+ */
+
+import { keccak_256 } from '@noble/hashes/sha3.js';
+import { bytesToScalar, CurvePoint, CURVE_ORDER, mod, type Scalar } from './primitives';
+
+/** Size of an on-chain commitment slot */
+export const COMMITMENT_SLOT_SIZE = 64;
+
+/**
+ * Encode a point as an on-chain slot: 32-byte compressed point + 32 zero bytes
+ *
+ * The identity encodes as the all-zero slot (an empty balance).
+ */
+export function pointToSlot(point: CurvePoint): Uint8Array {
+    const slot = new Uint8Array(COMMITMENT_SLOT_SIZE);
+    slot.set(point.toBytes(), 0);
+    return slot;
+}
+
+/**
+ * Decode an on-chain slot back to a point
+ */
+export function slotToPoint(slot: Uint8Array): CurvePoint {
+    if (slot.length !== COMMITMENT_SLOT_SIZE || slot.slice(32).some((b) => b !== 0)) {
+        throw new Error('Invalid commitment slot');
+    }
+    return CurvePoint.fromBytes(slot.slice(0, 32));
+}
+
+/**
+ * Keccak256 transcript matching the program's MerlinTranscript
+ */
+export class ProgramTranscript {
+    private absorbed: Uint8Array[] = [];
+
+    constructor(domainSeparator: string | Uint8Array) {
+        this.absorbed.push(new TextEncoder().encode('Merlin v1.0'));
+        this.absorbed.push(toBytes(domainSeparator));
+    }
+
+    /**
+     * Append labelled message (u64 LE length prefixes)
+     */
+    appendMessage(label: string, message: Uint8Array): void {
+        const labelBytes = new TextEncoder().encode(label);
+        this.absorbed.push(u64le(labelBytes.length), labelBytes, u64le(message.length), message);
+    }
+
+    /**
+     * Append a 64-byte commitment slot
+     */
+    appendSlot(label: string, slot: Uint8Array): void {
+        this.appendMessage(label, slot);
+    }
+
+    /**
+     * Append a point in slot encoding
+     */
+    appendPoint(label: string, point: CurvePoint): void {
+        this.appendSlot(label, pointToSlot(point));
+    }
+
+    /**
+     * Raw 32-byte challenge (what the program hashes)
+     */
+    challengeBytes(label: string): Uint8Array {
+        const labelBytes = new TextEncoder().encode(label);
+        const hash = keccak_256(concat([...this.absorbed, u64le(labelBytes.length), labelBytes]));
+        this.absorbed.push(hash);
+        return hash;
+    }
+
+    /**
+     * Challenge as a scalar (little-endian, reduced mod l like the program)
+     */
+    challengeScalar(label: string): Scalar {
+        return mod(bytesToScalar(this.challengeBytes(label)), CURVE_ORDER);
+    }
+}
+
+function toBytes(value: string | Uint8Array): Uint8Array {
+    return typeof value === 'string' ? new TextEncoder().encode(value) : value;
+}
+
+function u64le(value: number): Uint8Array {
+    const out = new Uint8Array(8);
+    new DataView(out.buffer).setBigUint64(0, BigInt(value), true);
+    return out;
+}
+
+function concat(parts: Uint8Array[]): Uint8Array {
+    const out = new Uint8Array(parts.reduce((sum, part) => sum + part.length, 0));
+    let offset = 0;
+    for (const part of parts) {
+        out.set(part, offset);
+        offset += part.length;
+    }
+    return out;
+}
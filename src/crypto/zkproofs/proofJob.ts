@@ -0,0 +1,151 @@
+/**
+ * Async Proof Jobs
+ *
+ * Runs proof generation as a cancellable task that reports progress, so
+ * wallets and GUIs can show percent-complete for multi-second Bulletproof
+ * generation instead of freezing.
+ *
+ * Provers call `ctx.step(stage, percent)` between stages (Bulletproof rounds,
+ * one range proof after another). Every step yields to the event loop, so
+ * UI updates and a `cancel()` get a chance to run, and throws
+ * ProofJobCancelledError once the job was cancelled.
+ */
+
+/**
+ * Progress of a running proof job
+ */
+export interface ProofProgress {
+    /** Percent complete (0-100, never decreases) */
+    percent: number;
+    /** Stage currently being proven (e.g. 'amount range proof') */
+    stage: string;
+}
+
+export type ProofProgressListener = (progress: ProofProgress) => void;
+
+/**
+ * Handle passed to the task of a proof job
+ */
+export interface ProofJobContext {
+    /**
+     * Report progress and yield; throws ProofJobCancelledError if cancelled
+     */
+    step(stage: string, percent: number): Promise<void>;
+    /** Whether the job was cancelled */
+    readonly cancelled: boolean;
+}
+
+/**
+ * A running proof job
+ */
+export interface ProofJob<T> {
+    /** Resolves with the proof, rejects with ProofJobCancelledError when cancelled */
+    readonly result: Promise<T>;
+    /** Latest reported progress */
+    readonly progress: ProofProgress;
+    /** Stop the job at its next step */
+    cancel(): void;
+}
+
+/**
+ * Error a cancelled proof job rejects with
+ */
+export class ProofJobCancelledError extends Error {
+    constructor() {
+        super('Proof generation cancelled');
+        this.name = 'ProofJobCancelledError';
+        Object.setPrototypeOf(this, ProofJobCancelledError.prototype);
+    }
+}
+
+/**
+ * Options of spawnProofJob
+ */
+export interface ProofJobOptions {
+    /** Called on every progress change */
+    onProgress?: ProofProgressListener;
+    /** Cancels the job when aborted */
+    signal?: AbortSignal;
+}
+
+/**
+ * Start `task` as a cancellable proof job
+ *
+ * The task starts on the next tick, so listeners and `cancel()` can be
+ * wired up before any proving work happens.
+ */
+export function spawnProofJob<T>(
+    task: (ctx: ProofJobContext) => Promise<T>,
+    options: ProofJobOptions = {}
+): ProofJob<T> {
+    let cancelled = false;
+    let progress: ProofProgress = { percent: 0, stage: 'queued' };
+
+    const report = (stage: string, percent: number) => {
+        const clamped = Math.min(100, Math.max(progress.percent, percent));
+        progress = { percent: clamped, stage };
+        options.onProgress?.(progress);
+    };
+
+    const ctx: ProofJobContext = {
+        async step(stage: string, percent: number): Promise<void> {
+            if (cancelled) {
+                throw new ProofJobCancelledError();
+            }
+            report(stage, percent);
+            await yieldToEventLoop();
+            if (cancelled) {
+                throw new ProofJobCancelledError();
+            }
+        },
+        get cancelled() {
+            return cancelled;
+        },
+    };
+
+    const cancel = () => {
+        cancelled = true;
+    };
+    if (options.signal) {
+        if (options.signal.aborted) {
+            cancel();
+        } else {
+            options.signal.addEventListener('abort', cancel, { once: true });
+        }
+    }
+
+    const result = (async () => {
+        await ctx.step('starting', 0);
+        const value = await task(ctx);
+        // Work finished after the last step; a late cancel still wins
+        if (cancelled) {
+            throw new ProofJobCancelledError();
+        }
+        report('done', 100);
+        return value;
+    })();
+
+    return {
+        result,
+        get progress() {
+            return progress;
+        },
+        cancel,
+    };
+}
+
+/**
+ * Map a prover's 0..1 progress into [from, to] percent of a job
+ */
+export function stageProgress(
+    ctx: ProofJobContext,
+    stage: string,
+    from: number,
+    to: number
+): (fraction: number) => Promise<void> {
+    return (fraction: number) => ctx.step(stage, from + (to - from) * fraction);
+}
+
+function yieldToEventLoop(): Promise<void> {
+    return new Promise((resolve) => setImmediate(resolve));
+}
@@ -0,0 +1,432 @@
+/**
+ * Durable Proof Job Queue
+ *
+ * Queues proof jobs in a ProofJobStore so they survive restarts, and lets
+ * clients poll a job's status, progress and result by ID. Prover services
+ * put their store on SQLite (SqliteProofJobStore); MemoryProofJobStore is
+ * for tests and single-session use.
+ *
+ * Each job names a registered handler (`kind`) and carries its input.
+ * Inputs and results are stored as JSON, with bigint and Uint8Array values
+ * tagged so they round-trip. Handlers run as proof jobs (see proofJob.ts),
+ * so they report progress and honor cancellation through their context.
+ *
+ * RECOVERY: A job found `running` on start() was interrupted by a restart
+ * and goes back to `queued`. Every start of a job counts as an attempt;
+ * a job interrupted `maxAttempts` times fails instead of crash-looping the
+ * service.
+ */
+
+import { v4 as uuidv4 } from 'uuid';
+import type { SqliteDatabase } from '../../storage/SqliteStateStore';
+import { spawnProofJob, ProofJobCancelledError, type ProofJob, type ProofJobContext, type ProofProgress } from './proofJob';
+
+export type ProofJobStatus = 'queued' | 'running' | 'succeeded' | 'failed' | 'cancelled';
+
+/**
+ * Stored state of one job
+ */
+export interface ProofJobRecord {
+    id: string;
+    /** Handler the job runs */
+    kind: string;
+    /** Encoded input (see encodeJobValue) */
+    input: string;
+    status: ProofJobStatus;
+    /** Latest progress reported by the handler */
+    progress: ProofProgress;
+    /** Encoded result, once succeeded */
+    result?: string;
+    /** Failure reason, once failed */
+    error?: string;
+    /** Number of times the job was started */
+    attempts: number;
+    createdAt: number;
+    updatedAt: number;
+}
+
+/**
+ * Storage backend for queued proof jobs
+ */
+export interface ProofJobStore {
+    /** Job with the given ID, if stored */
+    get(id: string): ProofJobRecord | undefined;
+    /** Insert or replace a job (keyed by ID) */
+    put(record: ProofJobRecord): void;
+    /** Forget a job */
+    delete(id: string): void;
+    /** Jobs in submission order, optionally only those with `status` */
+    list(status?: ProofJobStatus): ProofJobRecord[];
+}
+
+/**
+ * Proof generation for one job kind
+ */
+export type ProofJobHandler = (input: any, ctx: ProofJobContext) => Promise<unknown>;
+
+export interface ProofJobQueueOptions {
+    /** Jobs proven at the same time (default: 1) */
+    concurrency?: number;
+    /** Starts before an interrupted job is failed (default: 3) */
+    maxAttempts?: number;
+}
+
+/**
+ * In-memory ProofJobStore (tests, single-session provers)
+ */
+export class MemoryProofJobStore implements ProofJobStore {
+    private jobs: Map<string, ProofJobRecord> = new Map();
+
+    get(id: string): ProofJobRecord | undefined {
+        const record = this.jobs.get(id);
+        return record ? copyRecord(record) : undefined;
+    }
+
+    put(record: ProofJobRecord): void {
+        this.jobs.set(record.id, copyRecord(record));
+    }
+
+    delete(id: string): void {
+        this.jobs.delete(id);
+    }
+
+    list(status?: ProofJobStatus): ProofJobRecord[] {
+        return Array.from(this.jobs.values())
+            .filter((record) => !status || record.status === status)
+            .sort((a, b) => a.createdAt - b.createdAt)
+            .map(copyRecord);
+    }
+}
+
+interface JobRow {
+    id: string;
+    kind: string;
+    input: string;
+    status: ProofJobStatus;
+    percent: number;
+    stage: string;
+    result: string | null;
+    error: string | null;
+    attempts: number;
+    created_at: number;
+    updated_at: number;
+}
+
+const SCHEMA = `
+    CREATE TABLE IF NOT EXISTS proof_jobs (
+        id TEXT PRIMARY KEY,
+        kind TEXT NOT NULL,
+        input TEXT NOT NULL,
+        status TEXT NOT NULL,
+        percent REAL NOT NULL,
+        stage TEXT NOT NULL,
+        result TEXT,
+        error TEXT,
+        attempts INTEGER NOT NULL,
+        created_at INTEGER NOT NULL,
+        updated_at INTEGER NOT NULL
+    );
+    CREATE INDEX IF NOT EXISTS proof_jobs_status ON proof_jobs (status, created_at);
+`;
+
+/**
+ * SQLite-backed ProofJobStore
+ *
+ * Takes an open handle like SqliteStateStore; the prover service and the
+ * wallet state may share one database.
+ */
+export class SqliteProofJobStore implements ProofJobStore {
+    private readonly db: SqliteDatabase;
+
+    /**
+     * @param db - Open database handle; the table is created if missing
+     */
+    constructor(db: SqliteDatabase) {
+        this.db = db;
+        this.db.exec(SCHEMA);
+    }
+
+    get(id: string): ProofJobRecord | undefined {
+        const row = this.db.prepare('SELECT * FROM proof_jobs WHERE id = ?').get(id) as JobRow | undefined;
+        return row ? toRecord(row) : undefined;
+    }
+
+    put(record: ProofJobRecord): void {
+        this.db
+            // Upsert rather than REPLACE: keeps the rowid, which orders jobs
+            // submitted in the same millisecond
+            .prepare(`INSERT INTO proof_jobs VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+                ON CONFLICT (id) DO UPDATE SET kind = excluded.kind, input = excluded.input,
+                status = excluded.status, percent = excluded.percent, stage = excluded.stage,
+                result = excluded.result, error = excluded.error, attempts = excluded.attempts,
+                created_at = excluded.created_at, updated_at = excluded.updated_at`)
+            .run(
+                record.id,
+                record.kind,
+                record.input,
+                record.status,
+                record.progress.percent,
+                record.progress.stage,
+                record.result ?? null,
+                record.error ?? null,
+                record.attempts,
+                record.createdAt,
+                record.updatedAt
+            );
+    }
+
+    delete(id: string): void {
+        this.db.prepare('DELETE FROM proof_jobs WHERE id = ?').run(id);
+    }
+
+    list(status?: ProofJobStatus): ProofJobRecord[] {
+        const rows = (status
+            ? this.db.prepare('SELECT * FROM proof_jobs WHERE status = ? ORDER BY created_at, rowid').all(status)
+            : this.db.prepare('SELECT * FROM proof_jobs ORDER BY created_at, rowid').all()) as JobRow[];
+        return rows.map(toRecord);
+    }
+}
+
+/**
+ * Runs queued proof jobs from a ProofJobStore
+ */
+export class ProofJobQueue {
+    private readonly store: ProofJobStore;
+    private readonly handlers: Record<string, ProofJobHandler>;
+    private readonly concurrency: number;
+    private readonly maxAttempts: number;
+    private readonly running: Map<string, ProofJob<unknown>> = new Map();
+    private readonly settled: Map<string, Promise<void>> = new Map();
+    private started = false;
+
+    constructor(
+        store: ProofJobStore,
+        handlers: Record<string, ProofJobHandler>,
+        options: ProofJobQueueOptions = {}
+    ) {
+        this.store = store;
+        this.handlers = handlers;
+        this.concurrency = Math.max(1, options.concurrency ?? 1);
+        this.maxAttempts = Math.max(1, options.maxAttempts ?? 3);
+    }
+
+    /**
+     * Queue a job; returns its ID for polling
+     */
+    submit(kind: string, input: unknown): string {
+        if (!this.handlers[kind]) {
+            throw new Error(`Unknown proof job kind: ${kind}`);
+        }
+        const now = Date.now();
+        const id = uuidv4();
+        this.store.put({
+            id,
+            kind,
+            input: encodeJobValue(input),
+            status: 'queued',
+            progress: { percent: 0, stage: 'queued' },
+            attempts: 0,
+            createdAt: now,
+            updatedAt: now,
+        });
+        this.pump();
+        return id;
+    }
+
+    /**
+     * Current state of a job, or undefined for an unknown ID
+     */
+    status(id: string): ProofJobRecord | undefined {
+        return this.store.get(id);
+    }
+
+    /**
+     * Decoded result of a succeeded job
+     */
+    result<T>(id: string): T | undefined {
+        const record = this.store.get(id);
+        return record?.status === 'succeeded' && record.result !== undefined
+            ? (decodeJobValue(record.result) as T)
+            : undefined;
+    }
+
+    /**
+     * Cancel a queued or running job; false once it already finished
+     */
+    cancel(id: string): boolean {
+        const record = this.store.get(id);
+        if (!record || (record.status !== 'queued' && record.status !== 'running')) {
+            return false;
+        }
+        this.running.get(id)?.cancel();
+        this.update(id, { status: 'cancelled' });
+        return true;
+    }
+
+    /**
+     * Recover jobs interrupted by a restart and start processing
+     */
+    start(): void {
+        if (this.started) {
+            return;
+        }
+        this.started = true;
+        for (const record of this.store.list('running')) {
+            if (record.attempts >= this.maxAttempts) {
+                this.update(record.id, {
+                    status: 'failed',
+                    error: `Interrupted ${record.attempts} times`,
+                });
+            } else {
+                this.update(record.id, {
+                    status: 'queued',
+                    progress: { percent: 0, stage: 'queued' },
+                });
+            }
+        }
+        this.pump();
+    }
+
+    /**
+     * Stop taking new jobs and interrupt running ones
+     *
+     * Interrupted jobs stay `running` in the store, so the next start()
+     * queues them again.
+     */
+    async stop(): Promise<void> {
+        this.started = false;
+        const interrupted = Array.from(this.running.keys());
+        for (const job of this.running.values()) {
+            job.cancel();
+        }
+        await Promise.all(interrupted.map((id) => this.settled.get(id)));
+    }
+
+    /**
+     * Resolve once no job is queued or running
+     */
+    async drain(): Promise<void> {
+        while (this.settled.size > 0) {
+            await Promise.all(this.settled.values());
+        }
+    }
+
+    private pump(): void {
+        if (!this.started) {
+            return;
+        }
+        for (const record of this.store.list('queued')) {
+            if (this.running.size >= this.concurrency) {
+                return;
+            }
+            this.run(record);
+        }
+    }
+
+    private run(record: ProofJobRecord): void {
+        const handler = this.handlers[record.kind];
+        if (!handler) {
+            this.update(record.id, { status: 'failed', error: `Unknown proof job kind: ${record.kind}` });
+            return;
+        }
+        this.update(record.id, { status: 'running', attempts: record.attempts + 1 });
+
+        const input = decodeJobValue(record.input);
+        const job = spawnProofJob((ctx) => handler(input, ctx), {
+            onProgress: (progress) => {
+                if (this.running.has(record.id)) {
+                    this.update(record.id, { progress });
+                }
+            },
+        });
+        this.running.set(record.id, job);
+
+        const settled = job.result.then(
+            (value) => {
+                if (this.isRunning(record.id)) {
+                    this.update(record.id, { status: 'succeeded', result: encodeJobValue(value) });
+                }
+            },
+            (error) => {
+                // Cancelled by cancel() (already recorded) or interrupted by stop()
+                if (error instanceof ProofJobCancelledError || !this.isRunning(record.id)) {
+                    return;
+                }
+                this.update(record.id, {
+                    status: 'failed',
+                    error: error instanceof Error ? error.message : String(error),
+                });
+            }
+        ).finally(() => {
+            this.running.delete(record.id);
+            this.settled.delete(record.id);
+            this.pump();
+        });
+        this.settled.set(record.id, settled);
+    }
+
+    private isRunning(id: string): boolean {
+        return this.store.get(id)?.status === 'running';
+    }
+
+    private update(id: string, changes: Partial<ProofJobRecord>): void {
+        const record = this.store.get(id);
+        if (record) {
+            this.store.put({ ...record, ...changes, updatedAt: Date.now() });
+        }
+    }
+}
+
+/**
+ * JSON-encode a job input or result, tagging bigint and Uint8Array values
+ */
+export function encodeJobValue(value: unknown): string {
+    return JSON.stringify(value ?? null, function (this: any, key: string, item: unknown) {
+        // Buffers arrive here already through Buffer#toJSON; tag the raw value
+        const raw = this[key];
+        if (typeof raw === 'bigint') {
+            return { $bigint: raw.toString() };
+        }
+        if (raw instanceof Uint8Array) {
+            return { $bytes: Buffer.from(raw).toString('hex') };
+        }
+        return item;
+    });
+}
+
+/**
+ * Inverse of encodeJobValue
+ */
+export function decodeJobValue(encoded: string): unknown {
+    return JSON.parse(encoded, (_key, item) => {
+        if (item && typeof item === 'object' && !Array.isArray(item)) {
+            if (typeof item.$bigint === 'string') {
+                return BigInt(item.$bigint);
+            }
+            if (typeof item.$bytes === 'string') {
+                return new Uint8Array(Buffer.from(item.$bytes, 'hex'));
+            }
+        }
+        return item;
+    });
+}
+
+function toRecord(row: JobRow): ProofJobRecord {
+    return {
+        id: row.id,
+        kind: row.kind,
+        input: row.input,
+        status: row.status,
+        progress: { percent: row.percent, stage: row.stage },
+        result: row.result ?? undefined,
+        error: row.error ?? undefined,
+        attempts: row.attempts,
+        createdAt: row.created_at,
+        updatedAt: row.updated_at,
+    };
+}
+
+// Stored records are copied so callers can't mutate the store's state
+function copyRecord(record: ProofJobRecord): ProofJobRecord {
+    return { ...record, progress: { ...record.progress } };
+}
@@ -20,9 +20,13 @@ export interface ConfidentialTransfer {
     recipientCommitment?: CurvePoint;
     amountRangeProof: BulletproofRangeProof;
     senderAfterRangeProof: BulletproofRangeProof;
+    recipientAfterRangeProof: BulletproofRangeProof;
     validityProof: TransferValidityProof;
 }
 
+/** On-chain range proof size (5 points, 3 scalars, n); the program does not read the inner product */
+export const RANGE_PROOF_SIZE = 5 * 64 + 3 * 32 + 1;
+
 /**
  * Serialize a Bulletproof range proof to bytes
  * 
@@ -220,34 +224,39 @@ export function serializeValidityProof(proof: TransferValidityProof): Uint8Array
 /**
  * Serialize complete transfer proof for on-chain submission
  * 
- * Format:
- * [amount_range_proof][sender_after_range_proof][validity_proof]
+ * Format (matches deserialize_proof_data):
+ * [amount_range_proof][sender_after_range_proof][recipient_after_range_proof][validity_proof]
  * 
- * Each range proof: ~400-700 bytes
- * Validity proof: ~200 bytes
- * Total: ~1000-1600 bytes
+ * Each range proof: 417 bytes (RANGE_PROOF_SIZE)
+ * Validity proof: 192 bytes
+ * Total: 1443 bytes
  */
 export function serializeTransferProof(transfer: ConfidentialTransfer): Uint8Array {
-    // Serialize range proofs
-    const amountRangeProofBytes = serializeRangeProof(transfer.amountRangeProof);
-    const senderAfterRangeProofBytes = serializeRangeProof(transfer.senderAfterRangeProof);
+    // Serialize range proofs (on-chain part only)
+    const amountRangeProofBytes = serializeRangeProof(transfer.amountRangeProof).slice(0, RANGE_PROOF_SIZE);
+    const senderAfterRangeProofBytes = serializeRangeProof(transfer.senderAfterRangeProof).slice(0, RANGE_PROOF_SIZE);
+    const recipientAfterRangeProofBytes = serializeRangeProof(transfer.recipientAfterRangeProof).slice(0, RANGE_PROOF_SIZE);
     
     // Serialize validity proof
     const validityProofBytes = serializeValidityProof(transfer.validityProof);
     
     // Combine all proofs
-    const totalSize = amountRangeProofBytes.length + senderAfterRangeProofBytes.length + validityProofBytes.length;
+    const totalSize = 3 * RANGE_PROOF_SIZE + validityProofBytes.length;
     const result = new Uint8Array(totalSize);
     
     let offset = 0;
     
     // Write amount range proof
     result.set(amountRangeProofBytes, offset);
-    offset += amountRangeProofBytes.length;
+    offset += RANGE_PROOF_SIZE;
     
     // Write sender after range proof
     result.set(senderAfterRangeProofBytes, offset);
-    offset += senderAfterRangeProofBytes.length;
+    offset += RANGE_PROOF_SIZE;
+    
+    // Write recipient after range proof (no hidden wrap past 2^64)
+    result.set(recipientAfterRangeProofBytes, offset);
+    offset += RANGE_PROOF_SIZE;
     
     // Write validity proof
     result.set(validityProofBytes, offset);
@@ -0,0 +1,131 @@
+/**
+ * Public-Amount Proofs (deposit / withdraw)
+ *
+ * Builds the proof_data the program checks in verify_deposit_proof and
+ * verify_withdraw_proof(_to):
+ * [balance_after_range_proof (417)][equality_proof (R: 64, s: 32)]
+ *
+ * The equality proof shows D commits to zero, where
+ * - deposit:  D = C_new - C_old - amount*G
+ * - withdraw: D = C_old - C_new - amount*G
+ * and the program checks s*H == R + c*D with c from ProgramTranscript.
+ */
+
+import { Bulletproof } from './bulletproof';
+import { RANGE_PROOF_SIZE, serializeRangeProof } from './proofSerialization';
+import { PedersenCommitment, ScalarOps, scalarToBytes, type Scalar } from './primitives';
+import { pointToSlot, ProgramTranscript } from './programTranscript';
+
+export { RANGE_PROOF_SIZE };
+
+/**
+ * Opening of a hidden balance commitment
+ */
+export interface BalanceOpening {
+    value: bigint;
+    blinding: Scalar;
+}
+
+/** Opening of a fresh account (all-zero slot) */
+export const EMPTY_BALANCE: BalanceOpening = { value: 0n, blinding: 0n };
+
+/**
+ * Proof plus the commitment/opening it moves the account to
+ */
+export interface PublicAmountProof {
+    proofData: Uint8Array;
+    newCommitment: Uint8Array;
+    newOpening: BalanceOpening;
+}
+
+/**
+ * Prove a deposit of `amount` into the balance opened by `current`
+ */
+export async function proveDeposit(
+    current: BalanceOpening,
+    amount: bigint
+): Promise<PublicAmountProof> {
+    const newOpening = { value: current.value + amount, blinding: ScalarOps.random() };
+    return provePublicAmount('deposit', current, newOpening, amount, undefined);
+}
+
+/**
+ * Prove a withdrawal of `amount` from the balance opened by `current`
+ *
+ * When `destination` is given the proof is bound to it (withdraw_sol).
+ */
+export async function proveWithdraw(
+    current: BalanceOpening,
+    amount: bigint,
+    destination?: Uint8Array
+): Promise<PublicAmountProof> {
+    if (amount > current.value) {
+        throw new Error('Insufficient balance');
+    }
+    const newOpening = { value: current.value - amount, blinding: ScalarOps.random() };
+    return provePublicAmount('withdraw', current, newOpening, amount, destination);
+}
+
+/**
+ * Prove the balance opened by `balance` does not exceed `cap`
+ *
+ * Range proof on the headroom cap*G - C_new, i.e. value cap - v under
+ * blinding -r (checked by deposit_cap on deposits while a cap is set).
+ */
+export async function proveBalanceCap(balance: BalanceOpening, cap: bigint): Promise<Uint8Array> {
+    if (balance.value > cap) {
+        throw new Error('Balance exceeds the account cap');
+    }
+    const headroom = await Bulletproof.prove(cap - balance.value, ScalarOps.negate(balance.blinding), 64);
+    return serializeRangeProof(headroom).slice(0, RANGE_PROOF_SIZE);
+}
+
+async function provePublicAmount(
+    domain: 'deposit' | 'withdraw',
+    current: BalanceOpening,
+    next: BalanceOpening,
+    amount: bigint,
+    destination: Uint8Array | undefined
+): Promise<PublicAmountProof> {
+    if (amount <= 0n) {
+        throw new Error('Amount must be positive');
+    }
+    const oldSlot = pointToSlot(PedersenCommitment.commit(current.value, current.blinding));
+    const newSlot = pointToSlot(PedersenCommitment.commit(next.value, next.blinding));
+
+    const rangeProof = await Bulletproof.prove(next.value, next.blinding, 64);
+    const rangeProofBytes = serializeRangeProof(rangeProof).slice(0, RANGE_PROOF_SIZE);
+
+    // Blinding of D (D = r*H)
+    const r = domain === 'deposit'
+        ? ScalarOps.subtract(next.blinding, current.blinding)
+        : ScalarOps.subtract(current.blinding, next.blinding);
+
+    const k = ScalarOps.random();
+    const { H } = PedersenCommitment.getGenerators();
+    const R = pointToSlot(H.multiply(k));
+
+    const transcript = new ProgramTranscript(domain);
+    transcript.appendSlot('C_old', oldSlot);
+    transcript.appendSlot('C_new', newSlot);
+    transcript.appendMessage('amount', u64le(amount));
+    if (destination) {
+        transcript.appendMessage('destination', destination);
+    }
+    transcript.appendSlot('R', R);
+    const c = transcript.challengeScalar('c');
+    const s = ScalarOps.add(k, ScalarOps.multiply(c, r));
+
+    const proofData = new Uint8Array(RANGE_PROOF_SIZE + 96);
+    proofData.set(rangeProofBytes, 0);
+    proofData.set(R, RANGE_PROOF_SIZE);
+    proofData.set(scalarToBytes(s), RANGE_PROOF_SIZE + 64);
+
+    return { proofData, newCommitment: newSlot, newOpening: next };
+}
+
+function u64le(value: bigint): Uint8Array {
+    const out = new Uint8Array(8);
+    new DataView(out.buffer).setBigUint64(0, value, true);
+    return out;
+}
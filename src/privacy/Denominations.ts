@@ -0,0 +1,122 @@
+/**
+ * Pool Denominations
+ *
+ * The shared pool only accepts and pays out fixed denominations
+ * (POOL_DENOMINATION_CONSTANTS), so every pool transaction of one size
+ * looks like every other. A larger deposit is split greedily into those
+ * denominations and the parts are spread out over time in random order:
+ * a burst of same-owner deposits would otherwise link the parts back
+ * together and undo the split.
+ *
+ * splitIntoDenominations() and scheduleDenominations() are pure;
+ * runSchedule() executes a schedule through a caller-supplied callback
+ * (SolPrivacyMethods.depositPoolSplit submits one deposit_pool each).
+ */
+
+import { POOL_DENOMINATION_CONSTANTS } from '../utils/constants';
+
+/**
+ * Result of splitting an amount into pool denominations
+ */
+export interface DenominationSplit {
+    /** Denominations to deposit, largest first */
+    parts: bigint[];
+    /** Lamports below the smallest denomination (not deposited) */
+    remainder: bigint;
+}
+
+/**
+ * One scheduled pool transaction
+ */
+export interface ScheduledDenomination {
+    amount: bigint;
+    /** Unix time in milliseconds before which the transaction is not sent */
+    notBefore: number;
+}
+
+export interface ScheduleOptions {
+    /** Time of the first transaction (default: now) */
+    start?: number;
+    /** Minimum gap between transactions */
+    minDelayMs?: number;
+    /** Random extra gap per transaction, in [0, jitterMs) */
+    jitterMs?: number;
+    /** Uniform [0, 1) source (default: Math.random) */
+    random?: () => number;
+}
+
+/**
+ * Split `lamports` into the fewest pool denominations
+ */
+export function splitIntoDenominations(lamports: bigint): DenominationSplit {
+    if (lamports < 0n) {
+        throw new Error('Amount must not be negative');
+    }
+    const parts: bigint[] = [];
+    let remainder = lamports;
+    for (const denomination of POOL_DENOMINATION_CONSTANTS.DENOMINATIONS_LAMPORTS) {
+        while (remainder >= denomination) {
+            parts.push(denomination);
+            remainder -= denomination;
+        }
+    }
+    return { parts, remainder };
+}
+
+/**
+ * Give each part a send time: shuffled order, randomized gaps
+ *
+ * The first part is due at `start`; every following one after at least
+ * `minDelayMs` plus jitter, so the parts do not form a recognizable burst.
+ */
+export function scheduleDenominations(
+    parts: bigint[],
+    options: ScheduleOptions = {}
+): ScheduledDenomination[] {
+    const random = options.random ?? Math.random;
+    const minDelayMs = options.minDelayMs ?? POOL_DENOMINATION_CONSTANTS.MIN_DELAY_MS;
+    const jitterMs = options.jitterMs ?? POOL_DENOMINATION_CONSTANTS.DELAY_JITTER_MS;
+
+    // Fisher-Yates: the sizes should not come out in a fixed order either
+    const shuffled = [...parts];
+    for (let i = shuffled.length - 1; i > 0; i--) {
+        const j = Math.floor(random() * (i + 1));
+        [shuffled[i], shuffled[j]] = [shuffled[j], shuffled[i]];
+    }
+
+    let notBefore = options.start ?? Date.now();
+    return shuffled.map((amount, index) => {
+        if (index > 0) {
+            notBefore += minDelayMs + Math.floor(random() * jitterMs);
+        }
+        return { amount, notBefore };
+    });
+}
+
+/**
+ * Run `execute` for each scheduled part in order, waiting until it is due
+ *
+ * Parts run one at a time: each pool update proves against the balance
+ * the previous one produced. Stops at the first failure; the error
+ * carries how many parts completed.
+ */
+export async function runSchedule<T>(
+    schedule: ScheduledDenomination[],
+    execute: (amount: bigint, index: number) => Promise<T>,
+    sleep: (ms: number) => Promise<void> = (ms) => new Promise((resolve) => setTimeout(resolve, ms))
+): Promise<T[]> {
+    const results: T[] = [];
+    for (const [index, part] of schedule.entries()) {
+        const wait = part.notBefore - Date.now();
+        if (wait > 0) {
+            await sleep(wait);
+        }
+        try {
+            results.push(await execute(part.amount, index));
+        } catch (error) {
+            const reason = error instanceof Error ? error.message : String(error);
+            throw new Error(`Scheduled pool transaction ${index + 1}/${schedule.length} failed after ${index} completed: ${reason}`);
+        }
+    }
+    return results;
+}
@@ -12,6 +12,13 @@ import { ValidityProof, TransferValidityProof } from '../crypto/zkproofs/validit
 import { PedersenCommitment, CurvePoint, ScalarOps } from '../crypto/zkproofs/primitives';
 import { CACHE_CONSTANTS, PROOF_CONSTANTS } from '../utils/constants';
 import { createError, ErrorCode } from '../types';
+import {
+    spawnProofJob,
+    stageProgress,
+    type ProofJob,
+    type ProofJobContext,
+    type ProofJobOptions,
+} from '../crypto/zkproofs/proofJob';
 
 /**
  * Configuration for the privacy layer
@@ -41,6 +48,8 @@ export interface ConfidentialTransfer {
     amountRangeProof: BulletproofRangeProof;
     /** Range proof for sender's remaining balance */
     senderAfterRangeProof: BulletproofRangeProof;
+    /** Range proof for recipient's new balance */
+    recipientAfterRangeProof: BulletproofRangeProof;
     /** Validity proof showing balance equation holds */
     validityProof: TransferValidityProof;
 }
@@ -100,6 +109,10 @@ export class PrivacyLayer {
      * @param amount Amount to transfer
      * @param senderAfter Sender's balance after transfer
      * @param blindings Blinding factors for commitments
+     * @param recipient Opening of the recipient's new balance (defaults to a
+     *                  fresh recipient: amount under the amount blinding)
+     * @param job Proof job to report progress to; proofs then run one after
+     *            another so the percentage advances steadily
      * @returns ConfidentialTransfer with all proofs
      * @throws UTXOError with appropriate ErrorCode on failure
      */
@@ -111,7 +124,9 @@ export class PrivacyLayer {
             senderBefore: bigint;
             amount: bigint;
             senderAfter: bigint;
-        }
+        },
+        recipient: { after: bigint; blinding: bigint } = { after: amount, blinding: blindings.amount },
+        job?: ProofJobContext
     ): Promise<ConfidentialTransfer> {
         // Validate inputs
         if (amount < 0n) {
@@ -133,19 +148,34 @@ export class PrivacyLayer {
             const senderBeforeCommitment = PedersenCommitment.commit(senderBefore, blindings.senderBefore);
             const amountCommitment = PedersenCommitment.commit(amount, blindings.amount);
             const senderAfterCommitment = PedersenCommitment.commit(senderAfter, blindings.senderAfter);
+            const recipientCommitment = PedersenCommitment.commit(recipient.after, recipient.blinding);
 
             // Generate proofs in parallel if enabled
             let amountRangeProof: BulletproofRangeProof;
             let senderAfterRangeProof: BulletproofRangeProof;
-
-            if (this.config.enableParallel) {
-                [amountRangeProof, senderAfterRangeProof] = await Promise.all([
+            let recipientAfterRangeProof: BulletproofRangeProof;
+
+            if (job) {
+                amountRangeProof = await this.generateRangeProof(
+                    amount, blindings.amount, stageProgress(job, 'amount range proof', 0, 30)
+                );
+                senderAfterRangeProof = await this.generateRangeProof(
+                    senderAfter, blindings.senderAfter, stageProgress(job, 'sender balance range proof', 30, 60)
+                );
+                recipientAfterRangeProof = await this.generateRangeProof(
+                    recipient.after, recipient.blinding, stageProgress(job, 'recipient balance range proof', 60, 90)
+                );
+                await job.step('validity proof', 90);
+            } else if (this.config.enableParallel) {
+                [amountRangeProof, senderAfterRangeProof, recipientAfterRangeProof] = await Promise.all([
                     this.generateRangeProof(amount, blindings.amount),
                     this.generateRangeProof(senderAfter, blindings.senderAfter),
+                    this.generateRangeProof(recipient.after, recipient.blinding),
                 ]);
             } else {
                 amountRangeProof = await this.generateRangeProof(amount, blindings.amount);
                 senderAfterRangeProof = await this.generateRangeProof(senderAfter, blindings.senderAfter);
+                recipientAfterRangeProof = await this.generateRangeProof(recipient.after, recipient.blinding);
             }
 
             // Generate validity proof
@@ -161,14 +191,41 @@ export class PrivacyLayer {
             senderBeforeCommitment,
             amountCommitment,
             senderAfterCommitment,
+            recipientCommitment,
             amountRangeProof,
             senderAfterRangeProof,
+            recipientAfterRangeProof,
             validityProof,
         };
 
         return transfer;
     }
 
+    /**
+     * Generate a confidential transfer as a cancellable proof job
+     *
+     * Same inputs as generateTransferProofs. The job reports percent-complete
+     * through `options.onProgress` and stops at the next Bulletproof round
+     * once cancelled (via `cancel()` or `options.signal`).
+     */
+    spawnTransferProofJob(
+        senderBefore: bigint,
+        amount: bigint,
+        senderAfter: bigint,
+        blindings: {
+            senderBefore: bigint;
+            amount: bigint;
+            senderAfter: bigint;
+        },
+        recipient: { after: bigint; blinding: bigint } = { after: amount, blinding: blindings.amount },
+        options: ProofJobOptions = {}
+    ): ProofJob<ConfidentialTransfer> {
+        return spawnProofJob(
+            (job) => this.generateTransferProofs(senderBefore, amount, senderAfter, blindings, recipient, job),
+            options
+        );
+    }
+
     /**
      * Verify a confidential transfer
      * 
@@ -189,6 +246,11 @@ export class PrivacyLayer {
             throw createError.proofGenerationFailed('Sender after range proof verification failed');
         }
 
+        const recipientAfterRangeValid = await Bulletproof.verify(transfer.recipientAfterRangeProof);
+        if (!recipientAfterRangeValid) {
+            throw createError.proofGenerationFailed('Recipient after range proof verification failed');
+        }
+
         // Verify validity proof
         const validityValid = await ValidityProof.verifyTransfer(transfer.validityProof);
         if (!validityValid) {
@@ -203,12 +265,21 @@ export class PrivacyLayer {
         if (!transfer.senderAfterRangeProof.commitment.equals(transfer.senderAfterCommitment)) {
             throw createError.proofGenerationFailed('Sender after commitment mismatch');
         }
+
+        if (transfer.recipientCommitment
+            && !transfer.recipientAfterRangeProof.commitment.equals(transfer.recipientCommitment)) {
+            throw createError.proofGenerationFailed('Recipient after commitment mismatch');
+        }
     }
 
     /**
      * Generate a range proof with optional caching
      */
-    private async generateRangeProof(value: bigint, blinding: bigint): Promise<BulletproofRangeProof> {
+    private async generateRangeProof(
+        value: bigint,
+        blinding: bigint,
+        onProgress?: (fraction: number) => Promise<void>
+    ): Promise<BulletproofRangeProof> {
         if (this.config.enableCaching) {
             const cacheKey = `${value}-${blinding}`;
             const cached = this.proofCache.get(cacheKey);
@@ -217,7 +288,7 @@ export class PrivacyLayer {
                 return cached.proof;
             }
 
-            const proof = await Bulletproof.prove(value, blinding, this.config.rangeBits);
+            const proof = await Bulletproof.prove(value, blinding, this.config.rangeBits, onProgress);
             this.proofCache.set(cacheKey, { proof, timestamp: Date.now() });
 
             // Clean up old cache entries
@@ -226,7 +297,7 @@ export class PrivacyLayer {
             return proof;
         }
 
-        return Bulletproof.prove(value, blinding, this.config.rangeBits);
+        return Bulletproof.prove(value, blinding, this.config.rangeBits, onProgress);
     }
 
     /**
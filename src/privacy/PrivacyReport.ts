@@ -0,0 +1,375 @@
+/**
+ * Privacy Report
+ *
+ * Scores how easily a user's on-chain footprint can be deanonymized and
+ * suggests what to change. Hidden balances and amounts do not help if the
+ * surrounding metadata links the user's activity together, so the report
+ * looks at what an indexer sees:
+ * - Linked PDAs: every account and escrow is derived from the owner key, so
+ *   all of a wallet's account indices are publicly tied together
+ * - Timing: withdrawals right after deposits, clockwork schedules and
+ *   fixed time-of-day habits
+ * - Denominations: public deposit/withdraw amounts that are reused or that
+ *   come back out unchanged
+ * - Counterparties: repeated transfers to the same recipient
+ *
+ * analyzeFootprint() is pure; discoverLinkedAccounts() and
+ * activityFromHistory() build its input from the chain and local history.
+ */
+
+import { Connection, LAMPORTS_PER_SOL, PublicKey } from '@solana/web3.js';
+import { accountIndexSeed } from '../wallet/SolPrivacyMethods';
+import { PRIVACY_REPORT_CONSTANTS } from '../utils/constants';
+import type { TransactionRecord } from '../types';
+
+/**
+ * Kind of program account owned by the user
+ */
+export type FootprintAccountKind = 'encrypted-account' | 'sol-escrow' | 'viewing-key';
+
+/**
+ * Program account linked to the owner
+ */
+export interface FootprintAccount {
+    address: string;
+    kind: FootprintAccountKind;
+    /** Account index of encrypted accounts and escrows */
+    accountIndex?: number;
+}
+
+/**
+ * One confirmed operation of the user
+ */
+export interface FootprintActivity {
+    signature?: string;
+    type: 'deposit' | 'withdraw' | 'transfer' | 'receive';
+    /** Unix time in milliseconds */
+    timestamp: number;
+    /** Public amount in lamports (deposits and withdrawals only) */
+    publicAmount?: bigint;
+    /** Recipient of transfers and withdrawals */
+    counterparty?: string;
+}
+
+/**
+ * What an indexer knows about one user
+ */
+export interface PrivacyFootprint {
+    owner: string;
+    accounts: FootprintAccount[];
+    activity: FootprintActivity[];
+}
+
+export type PrivacyRiskLevel = 'low' | 'medium' | 'high';
+
+/**
+ * One deanonymization risk found in the footprint
+ */
+export interface PrivacyFinding {
+    category: 'linked-accounts' | 'timing' | 'denomination' | 'counterparty';
+    severity: PrivacyRiskLevel;
+    title: string;
+    detail: string;
+    suggestion: string;
+    /** Contribution to the risk score */
+    points: number;
+}
+
+/**
+ * Scored privacy report
+ */
+export interface PrivacyReport {
+    owner: string;
+    /** Deanonymization risk, 0 (none found) to 100 */
+    score: number;
+    level: PrivacyRiskLevel;
+    findings: PrivacyFinding[];
+}
+
+/**
+ * Analyze a footprint and score its deanonymization risk
+ */
+export function analyzeFootprint(footprint: PrivacyFootprint): PrivacyReport {
+    const activity = [...footprint.activity].sort((a, b) => a.timestamp - b.timestamp);
+    const findings = [
+        ...linkedAccountFindings(footprint.accounts),
+        ...timingFindings(activity),
+        ...denominationFindings(activity),
+        ...counterpartyFindings(activity),
+    ].sort((a, b) => b.points - a.points);
+
+    const score = Math.min(100, findings.reduce((sum, finding) => sum + finding.points, 0));
+    const level: PrivacyRiskLevel = score >= 50 ? 'high' : score >= 25 ? 'medium' : 'low';
+
+    return { owner: footprint.owner, score, level, findings };
+}
+
+function linkedAccountFindings(accounts: FootprintAccount[]): PrivacyFinding[] {
+    const findings: PrivacyFinding[] = [];
+
+    const indices = new Set(
+        accounts
+            .filter((account) => account.kind !== 'viewing-key' && account.accountIndex !== undefined)
+            .map((account) => account.accountIndex as number)
+    );
+    if (indices.size > 1) {
+        findings.push({
+            category: 'linked-accounts',
+            severity: 'medium',
+            title: `${indices.size} account indices share one owner`,
+            detail: `Accounts ${[...indices].sort((a, b) => a - b).join(', ')} are PDAs of the same owner key; `
+                + 'anyone can derive and link all of them.',
+            suggestion: 'Use a separate wallet (owner key) for each identity instead of extra account indices.',
+            points: Math.min(25, 10 + 5 * (indices.size - 2)),
+        });
+    }
+
+    const viewingKeys = accounts.filter((account) => account.kind === 'viewing-key').length;
+    if (viewingKeys > 0) {
+        findings.push({
+            category: 'linked-accounts',
+            severity: 'low',
+            title: 'Viewing key registered',
+            detail: `${viewingKeys} account(s) grant a viewing key; its holder can read every published balance.`,
+            suggestion: 'Revoke viewing keys (revoke_viewing_key) once the audit they were granted for is over.',
+            points: 5,
+        });
+    }
+
+    return findings;
+}
+
+function timingFindings(activity: FootprintActivity[]): PrivacyFinding[] {
+    const findings: PrivacyFinding[] = [];
+
+    // Withdrawals shortly after a deposit link the two ends of the pool
+    let quickWithdrawals = 0;
+    let lastDeposit: number | undefined;
+    for (const event of activity) {
+        if (event.type === 'deposit') {
+            lastDeposit = event.timestamp;
+        } else if (event.type === 'withdraw' && lastDeposit !== undefined
+            && event.timestamp - lastDeposit < PRIVACY_REPORT_CONSTANTS.QUICK_WITHDRAW_WINDOW_MS) {
+            quickWithdrawals++;
+        }
+    }
+    if (quickWithdrawals > 0) {
+        findings.push({
+            category: 'timing',
+            severity: 'high',
+            title: 'Withdrawals right after deposits',
+            detail: `${quickWithdrawals} withdrawal(s) followed a deposit within an hour; `
+                + 'few other users act in such a short window, so deposit and withdrawal are linkable.',
+            suggestion: 'Leave funds shielded longer (days rather than minutes) before withdrawing.',
+            points: Math.min(30, 15 * quickWithdrawals),
+        });
+    }
+
+    if (activity.length >= PRIVACY_REPORT_CONSTANTS.MIN_TIMING_EVENTS) {
+        const gaps = activity.slice(1).map((event, i) => event.timestamp - activity[i].timestamp);
+        const mean = gaps.reduce((sum, gap) => sum + gap, 0) / gaps.length;
+        const deviation = Math.sqrt(gaps.reduce((sum, gap) => sum + (gap - mean) ** 2, 0) / gaps.length);
+        if (mean > 0 && deviation / mean < PRIVACY_REPORT_CONSTANTS.REGULAR_TIMING_CV) {
+            findings.push({
+                category: 'timing',
+                severity: 'medium',
+                title: 'Clockwork schedule',
+                detail: `${activity.length} operations are spaced almost evenly `
+                    + `(every ~${formatDuration(mean)}), a pattern that stands out in the pool.`,
+                suggestion: 'Randomize when you deposit, transfer and withdraw.',
+                points: 15,
+            });
+        }
+
+        const window = PRIVACY_REPORT_CONSTANTS.TIME_OF_DAY_WINDOW_HOURS;
+        const hours = activity.map((event) => new Date(event.timestamp).getUTCHours());
+        let busiest = 0;
+        let busiestStart = 0;
+        for (let start = 0; start < 24; start++) {
+            const count = hours.filter((hour) => (hour - start + 24) % 24 < window).length;
+            if (count > busiest) {
+                busiest = count;
+                busiestStart = start;
+            }
+        }
+        if (busiest / activity.length >= PRIVACY_REPORT_CONSTANTS.TIME_OF_DAY_SHARE) {
+            findings.push({
+                category: 'timing',
+                severity: 'low',
+                title: 'Fixed time of day',
+                detail: `${busiest} of ${activity.length} operations happened between `
+                    + `${busiestStart}:00 and ${(busiestStart + window) % 24}:00 UTC, hinting at your time zone and routine.`,
+                suggestion: 'Spread operations across the day, or queue them and submit at random times.',
+                points: 5,
+            });
+        }
+    }
+
+    return findings;
+}
+
+function denominationFindings(activity: FootprintActivity[]): PrivacyFinding[] {
+    const findings: PrivacyFinding[] = [];
+
+    // A withdrawal of exactly a deposited amount matches the two sides
+    const deposited: bigint[] = [];
+    let roundTrips = 0;
+    for (const event of activity) {
+        if (event.publicAmount === undefined) {
+            continue;
+        }
+        if (event.type === 'deposit') {
+            deposited.push(event.publicAmount);
+        } else if (event.type === 'withdraw') {
+            const match = deposited.indexOf(event.publicAmount);
+            if (match !== -1) {
+                deposited.splice(match, 1);
+                roundTrips++;
+            }
+        }
+    }
+    if (roundTrips > 0) {
+        findings.push({
+            category: 'denomination',
+            severity: 'high',
+            title: 'Deposited amounts withdrawn unchanged',
+            detail: `${roundTrips} withdrawal(s) took out exactly an amount deposited earlier.`,
+            suggestion: 'Withdraw amounts that differ from your deposits (split or combine them).',
+            points: Math.min(40, 20 * roundTrips),
+        });
+    }
+
+    // Reusing an unusual amount fingerprints the user across operations
+    const counts = new Map<bigint, number>();
+    for (const event of activity) {
+        if (event.publicAmount !== undefined
+            && event.publicAmount % PRIVACY_REPORT_CONSTANTS.ROUND_DENOMINATION_LAMPORTS !== 0n) {
+            counts.set(event.publicAmount, (counts.get(event.publicAmount) ?? 0) + 1);
+        }
+    }
+    const reused = [...counts.entries()].filter(([, count]) => count > 1);
+    if (reused.length > 0) {
+        findings.push({
+            category: 'denomination',
+            severity: 'medium',
+            title: 'Distinctive amounts reused',
+            detail: `Non-round amounts used more than once: ${reused
+                .map(([amount, count]) => `${formatSol(amount)} SOL (${count}x)`)
+                .join(', ')}.`,
+            suggestion: 'Deposit and withdraw common round denominations (multiples of 0.1 SOL).',
+            points: Math.min(20, 10 * reused.length),
+        });
+    }
+
+    return findings;
+}
+
+function counterpartyFindings(activity: FootprintActivity[]): PrivacyFinding[] {
+    const counts = new Map<string, number>();
+    for (const event of activity) {
+        if (event.counterparty && (event.type === 'transfer' || event.type === 'withdraw')) {
+            counts.set(event.counterparty, (counts.get(event.counterparty) ?? 0) + 1);
+        }
+    }
+
+    const frequent = [...counts.entries()]
+        .filter(([, count]) => count >= PRIVACY_REPORT_CONSTANTS.COUNTERPARTY_REUSE_COUNT);
+    if (frequent.length === 0) {
+        return [];
+    }
+    return [{
+        category: 'counterparty',
+        severity: 'low',
+        title: 'Repeated recipients',
+        detail: `${frequent.length} recipient(s) received ${PRIVACY_REPORT_CONSTANTS.COUNTERPARTY_REUSE_COUNT}`
+            + ' or more payments; the recurring pair is visible even with hidden amounts.',
+        suggestion: 'Ask regular recipients for fresh addresses, or batch payments to them less often.',
+        points: Math.min(10, 5 * frequent.length),
+    }];
+}
+
+/**
+ * Program accounts of `owner` that exist on-chain
+ *
+ * Scans account indices 0..MAX_ACCOUNT_INDEX for encrypted accounts and SOL
+ * escrows, plus the viewing key PDA of each encrypted account found.
+ */
+export async function discoverLinkedAccounts(
+    connection: Connection,
+    owner: PublicKey,
+    programId: PublicKey
+): Promise<FootprintAccount[]> {
+    const candidates: FootprintAccount[] = [];
+    for (let index = 0; index < PRIVACY_REPORT_CONSTANTS.MAX_ACCOUNT_INDEX; index++) {
+        // The kinds double as the PDA seed prefixes
+        for (const kind of ['encrypted-account', 'sol-escrow'] as const) {
+            const [address] = PublicKey.findProgramAddressSync(
+                [Buffer.from(kind), owner.toBuffer(), accountIndexSeed(index)],
+                programId
+            );
+            candidates.push({ address: address.toBase58(), kind, accountIndex: index });
+        }
+    }
+
+    const found = await existingAccounts(connection, candidates);
+
+    const viewingKeys = found
+        .filter((account) => account.kind === 'encrypted-account')
+        .map((account): FootprintAccount => {
+            const [address] = PublicKey.findProgramAddressSync(
+                [Buffer.from('viewing-key'), new PublicKey(account.address).toBuffer()],
+                programId
+            );
+            return { address: address.toBase58(), kind: 'viewing-key' };
+        });
+
+    return [...found, ...await existingAccounts(connection, viewingKeys)];
+}
+
+async function existingAccounts(connection: Connection, accounts: FootprintAccount[]): Promise<FootprintAccount[]> {
+    // getMultipleAccountsInfo takes at most 100 keys
+    const existing: FootprintAccount[] = [];
+    for (let i = 0; i < accounts.length; i += 100) {
+        const chunk = accounts.slice(i, i + 100);
+        const infos = await connection.getMultipleAccountsInfo(chunk.map((account) => new PublicKey(account.address)));
+        chunk.forEach((account, j) => {
+            if (infos[j]) {
+                existing.push(account);
+            }
+        });
+    }
+    return existing;
+}
+
+/**
+ * Footprint activity from local transaction history
+ *
+ * History amounts are in SOL; only deposits and withdrawals move public
+ * amounts, transfer amounts stay hidden on-chain.
+ */
+export function activityFromHistory(history: TransactionRecord[]): FootprintActivity[] {
+    return history
+        .filter((record) => record.status === 'confirmed')
+        .map((record) => ({
+            signature: record.signature,
+            type: record.type,
+            timestamp: record.timestamp,
+            publicAmount: record.type === 'deposit' || record.type === 'withdraw'
+                ? BigInt(Math.round(parseFloat(record.amount) * LAMPORTS_PER_SOL))
+                : undefined,
+            counterparty: record.recipient,
+        }));
+}
+
+function formatSol(lamports: bigint): string {
+    return (Number(lamports) / LAMPORTS_PER_SOL).toString();
+}
+
+function formatDuration(ms: number): string {
+    const minutes = Math.round(ms / 60000);
+    if (minutes < 120) {
+        return `${minutes} min`;
+    }
+    const hours = Math.round(minutes / 60);
+    return hours < 48 ? `${hours} h` : `${Math.round(hours / 24)} days`;
+}
@@ -0,0 +1,91 @@
+/**
+ * Tests for pool denominations
+ * Tests: Greedy split, schedule order and gaps, sequential execution
+ */
+
+import { runSchedule, scheduleDenominations, splitIntoDenominations } from '../Denominations';
+
+const SOL = 1_000_000_000n;
+const MINUTE = 60 * 1000;
+
+/** Deterministic [0, 1) sequence */
+function sequence(...values: number[]): () => number {
+    let index = 0;
+    return () => values[index++ % values.length];
+}
+
+describe('Pool Denominations', () => {
+    test('Splits into the fewest denominations and keeps the dust', () => {
+        const { parts, remainder } = splitIntoDenominations(123n * SOL + 450_000_000n + 7n);
+
+        expect(parts).toEqual([
+            100n * SOL,
+            10n * SOL, 10n * SOL,
+            SOL, SOL, SOL,
+            SOL / 10n, SOL / 10n, SOL / 10n, SOL / 10n,
+        ]);
+        expect(remainder).toBe(50_000_007n);
+    });
+
+    test('Amounts below the smallest denomination produce no parts', () => {
+        expect(splitIntoDenominations(SOL / 10n - 1n)).toEqual({ parts: [], remainder: SOL / 10n - 1n });
+        expect(() => splitIntoDenominations(-1n)).toThrow('negative');
+    });
+
+    test('Schedules every part with at least the minimum gap', () => {
+        const parts = [10n * SOL, SOL, SOL, SOL / 10n];
+        const schedule = scheduleDenominations(parts, {
+            start: 1_000,
+            minDelayMs: 10 * MINUTE,
+            jitterMs: 50 * MINUTE,
+            random: sequence(0.3, 0.9, 0.1, 0.6, 0.5),
+        });
+
+        expect(schedule[0].notBefore).toBe(1_000);
+        for (let i = 1; i < schedule.length; i++) {
+            const gap = schedule[i].notBefore - schedule[i - 1].notBefore;
+            expect(gap).toBeGreaterThanOrEqual(10 * MINUTE);
+            expect(gap).toBeLessThan(60 * MINUTE);
+        }
+        // Shuffled, but the same parts
+        expect(schedule.map((part) => part.amount).sort()).toEqual([...parts].sort());
+    });
+
+    test('Runs parts in order, waiting until each is due', async () => {
+        const now = Date.now();
+        const schedule = [
+            { amount: SOL, notBefore: now - MINUTE },
+            { amount: 10n * SOL, notBefore: now + 5 * MINUTE },
+        ];
+        const waits: number[] = [];
+        const sent: bigint[] = [];
+
+        const results = await runSchedule(
+            schedule,
+            async (amount, index) => {
+                sent.push(amount);
+                return `sig-${index}`;
+            },
+            async (ms) => { waits.push(ms); }
+        );
+
+        expect(results).toEqual(['sig-0', 'sig-1']);
+        expect(sent).toEqual([SOL, 10n * SOL]);
+        expect(waits).toHaveLength(1);
+        expect(waits[0]).toBeGreaterThan(4 * MINUTE);
+    });
+
+    test('Stops at the first failure and reports progress', async () => {
+        const schedule = [SOL, SOL, SOL].map((amount) => ({ amount, notBefore: 0 }));
+        let calls = 0;
+
+        await expect(runSchedule(schedule, async (_, index) => {
+            calls++;
+            if (index === 1) {
+                throw new Error('rpc down');
+            }
+            return index;
+        })).rejects.toThrow('2/3 failed after 1 completed: rpc down');
+        expect(calls).toBe(2);
+    });
+});
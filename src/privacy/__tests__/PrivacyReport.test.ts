@@ -0,0 +1,118 @@
+/**
+ * Tests for the privacy report
+ * Tests: Linked PDAs, timing patterns, denomination reuse, scoring
+ */
+
+import {
+    activityFromHistory,
+    analyzeFootprint,
+    type FootprintAccount,
+    type FootprintActivity,
+} from '../PrivacyReport';
+import type { TransactionRecord } from '../../types';
+
+const HOUR = 60 * 60 * 1000;
+const DAY = 24 * HOUR;
+const SOL = 1_000_000_000n;
+
+function account(accountIndex: number): FootprintAccount {
+    return { address: `account-${accountIndex}`, kind: 'encrypted-account', accountIndex };
+}
+
+function report(activity: FootprintActivity[], accounts: FootprintAccount[] = [account(0)]) {
+    return analyzeFootprint({ owner: 'owner', accounts, activity });
+}
+
+function titles(activity: FootprintActivity[], accounts?: FootprintAccount[]): string[] {
+    return report(activity, accounts).findings.map((finding) => finding.title);
+}
+
+describe('Privacy Report', () => {
+    test('A quiet single-account footprint scores low', () => {
+        const result = report([
+            { type: 'deposit', timestamp: 0, publicAmount: 2n * SOL },
+            { type: 'withdraw', timestamp: 9 * DAY + 5 * HOUR, publicAmount: SOL },
+        ]);
+
+        expect(result.findings).toHaveLength(0);
+        expect(result.score).toBe(0);
+        expect(result.level).toBe('low');
+    });
+
+    test('Flags account indices linked through the owner and viewing keys', () => {
+        const accounts = [
+            account(0),
+            { address: 'escrow-0', kind: 'sol-escrow' as const, accountIndex: 0 },
+            account(1),
+            account(2),
+            { address: 'view', kind: 'viewing-key' as const },
+        ];
+
+        const result = report([], accounts);
+        expect(result.findings.map((finding) => finding.title))
+            .toEqual(['3 account indices share one owner', 'Viewing key registered']);
+        expect(result.score).toBe(20);
+    });
+
+    test('Flags withdrawals right after deposits and unchanged amounts', () => {
+        const result = report([
+            { type: 'deposit', timestamp: 0, publicAmount: 1_234_567_891n },
+            { type: 'withdraw', timestamp: 10 * 60 * 1000, publicAmount: 1_234_567_891n },
+        ]);
+
+        expect(result.findings.map((finding) => finding.title)).toEqual([
+            'Deposited amounts withdrawn unchanged',
+            'Withdrawals right after deposits',
+            'Distinctive amounts reused',
+        ]);
+        expect(result.score).toBe(45);
+        expect(result.level).toBe('medium');
+    });
+
+    test('Flags clockwork schedules and fixed times of day', () => {
+        const activity: FootprintActivity[] = [0, 1, 2, 3, 4].map((day) => ({
+            type: 'transfer',
+            timestamp: day * DAY + 9 * HOUR,
+        }));
+
+        expect(titles(activity)).toEqual(['Clockwork schedule', 'Fixed time of day']);
+
+        const irregular = activity.map((event, i) => ({ ...event, timestamp: event.timestamp + i * i * 5 * HOUR }));
+        expect(titles(irregular)).not.toContain('Clockwork schedule');
+    });
+
+    test('Round denominations are not flagged as reuse', () => {
+        const activity: FootprintActivity[] = [
+            { type: 'deposit', timestamp: 0, publicAmount: SOL },
+            { type: 'deposit', timestamp: 3 * DAY + 7 * HOUR, publicAmount: SOL },
+        ];
+        expect(titles(activity)).toEqual([]);
+    });
+
+    test('Flags repeated recipients and caps the score at 100', () => {
+        const activity: FootprintActivity[] = [];
+        for (let i = 0; i < 6; i++) {
+            activity.push({ type: 'deposit', timestamp: i * DAY, publicAmount: 1_111_111_111n });
+            activity.push({ type: 'withdraw', timestamp: i * DAY + HOUR / 2, publicAmount: 1_111_111_111n });
+            activity.push({ type: 'transfer', timestamp: i * DAY + HOUR, counterparty: 'bob' });
+        }
+
+        const result = report(activity, [account(0), account(1), account(2), account(3)]);
+        expect(result.findings.map((finding) => finding.title)).toContain('Repeated recipients');
+        expect(result.score).toBe(100);
+        expect(result.level).toBe('high');
+    });
+
+    test('Builds activity from confirmed history records', () => {
+        const history: TransactionRecord[] = [
+            { id: '1', type: 'deposit', amount: '1.5', status: 'confirmed', timestamp: 1 },
+            { id: '2', type: 'transfer', amount: '0.2', recipient: 'bob', status: 'confirmed', timestamp: 2 },
+            { id: '3', type: 'withdraw', amount: '1', status: 'failed', timestamp: 3 },
+        ];
+
+        expect(activityFromHistory(history)).toEqual([
+            { type: 'deposit', timestamp: 1, publicAmount: 1_500_000_000n },
+            { type: 'transfer', timestamp: 2, counterparty: 'bob' },
+        ]);
+    });
+});
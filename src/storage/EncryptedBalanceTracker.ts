@@ -10,12 +10,16 @@
  * - Plaintext balance: For local tracking and display
  * - Commitment: For on-chain operations and verification
  * - Blinding factor: For commitment verification and homomorphic operations
+ *
+ * Entries live in encrypted-balances.json by default, or in the StateStore
+ * passed to the constructor (embedder-provided storage).
  */
 
 import * as fs from 'fs';
 import * as path from 'path';
 import { PedersenCommitment, CurvePoint } from '../crypto/zkproofs/primitives';
 import { ScalarOps, type Scalar } from '../crypto/zkproofs/primitives';
+import type { StateStore } from './StateStore';
 
 /**
  * Balance commitment entry
@@ -55,10 +59,16 @@ export interface BalanceUpdate {
 export class EncryptedBalanceTracker {
     private storagePath: string;
     private balances: Map<string, EncryptedBalanceEntry>;
+    private store?: StateStore;
 
-    constructor(storagePath: string) {
+    /**
+     * @param storagePath - Wallet directory (used when no store is given)
+     * @param store - Optional StateStore holding the openings instead
+     */
+    constructor(storagePath: string, store?: StateStore) {
         this.storagePath = path.join(storagePath, 'encrypted-balances.json');
         this.balances = new Map();
+        this.store = store;
     }
 
     /**
@@ -497,6 +507,18 @@ export class EncryptedBalanceTracker {
      * Save balances to disk
      */
     private save(): void {
+        if (this.store) {
+            for (const opening of this.store.listOpenings()) {
+                if (!this.balances.has(opening.account)) {
+                    this.store.deleteOpening(opening.account);
+                }
+            }
+            for (const entry of this.balances.values()) {
+                this.store.putOpening(entry);
+            }
+            return;
+        }
+
         try {
             const dir = path.dirname(this.storagePath);
             if (!fs.existsSync(dir)) {
@@ -516,6 +538,14 @@ export class EncryptedBalanceTracker {
      * NOTE: Handles backward compatibility with old ElGamal format files
      */
     private load(): void {
+        if (this.store) {
+            this.balances.clear();
+            for (const opening of this.store.listOpenings()) {
+                this.balances.set(opening.account, opening);
+            }
+            return;
+        }
+
         try {
             if (fs.existsSync(this.storagePath)) {
                 const data = fs.readFileSync(this.storagePath, 'utf8');
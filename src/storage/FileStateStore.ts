@@ -0,0 +1,154 @@
+/**
+ * File-backed StateStore
+ *
+ * Keeps all state in one JSON file (state.json in the wallet directory),
+ * rewritten on every change with the same strict permissions as the key
+ * file. Bigints are stored as strings and byte arrays as number arrays,
+ * matching encrypted-balances.json.
+ */
+
+import * as fs from 'fs';
+import * as path from 'path';
+import { KEY_STORAGE_CONSTANTS } from '../utils/constants';
+import {
+    MemoryStateStore,
+    type ScanCursor,
+    type StateStore,
+    type StoredNote,
+    type StoredOpening,
+} from './StateStore';
+
+/** Layout version of state.json */
+const STATE_FILE_VERSION = 1;
+
+export class FileStateStore implements StateStore {
+    private readonly filePath: string;
+    private readonly state: MemoryStateStore = new MemoryStateStore();
+
+    constructor(walletDir: string = '.wallet') {
+        this.filePath = path.join(walletDir, 'state.json');
+        this.load();
+    }
+
+    getOpening(account: string): StoredOpening | undefined {
+        return this.state.getOpening(account);
+    }
+
+    putOpening(opening: StoredOpening): void {
+        this.state.putOpening(opening);
+        this.save();
+    }
+
+    deleteOpening(account: string): void {
+        this.state.deleteOpening(account);
+        this.save();
+    }
+
+    listOpenings(): StoredOpening[] {
+        return this.state.listOpenings();
+    }
+
+    getNote(commitment: Uint8Array): StoredNote | undefined {
+        return this.state.getNote(commitment);
+    }
+
+    putNote(note: StoredNote): void {
+        this.state.putNote(note);
+        this.save();
+    }
+
+    deleteNote(commitment: Uint8Array): void {
+        this.state.deleteNote(commitment);
+        this.save();
+    }
+
+    listNotes(): StoredNote[] {
+        return this.state.listNotes();
+    }
+
+    getScanCursor(): ScanCursor | undefined {
+        return this.state.getScanCursor();
+    }
+
+    setScanCursor(cursor: ScanCursor): void {
+        this.state.setScanCursor(cursor);
+        this.save();
+    }
+
+    clear(): void {
+        this.state.clear();
+        if (fs.existsSync(this.filePath)) {
+            fs.unlinkSync(this.filePath);
+        }
+    }
+
+    /**
+     * Write the whole state to disk
+     */
+    private save(): void {
+        const dir = path.dirname(this.filePath);
+        if (!fs.existsSync(dir)) {
+            fs.mkdirSync(dir, { recursive: true, mode: KEY_STORAGE_CONSTANTS.DIR_PERMISSIONS });
+        }
+
+        const data = {
+            version: STATE_FILE_VERSION,
+            openings: this.state.listOpenings().map((opening) => ({
+                ...opening,
+                balance: opening.balance.toString(),
+                commitment: Array.from(opening.commitment),
+                blinding: Array.from(opening.blinding),
+            })),
+            notes: this.state.listNotes().map((note) => ({
+                ...note,
+                amount: note.amount.toString(),
+                commitment: Array.from(note.commitment),
+                secret: Array.from(note.secret),
+            })),
+            scanCursor: this.state.getScanCursor() ?? null,
+        };
+
+        fs.writeFileSync(this.filePath, JSON.stringify(data, null, 2), {
+            mode: KEY_STORAGE_CONSTANTS.FILE_PERMISSIONS,
+        });
+        fs.chmodSync(this.filePath, KEY_STORAGE_CONSTANTS.FILE_PERMISSIONS);
+    }
+
+    /**
+     * Load state from disk (a missing file is an empty store)
+     */
+    private load(): void {
+        if (!fs.existsSync(this.filePath)) {
+            return;
+        }
+
+        const data = JSON.parse(fs.readFileSync(this.filePath, 'utf-8'));
+        if (data.version !== STATE_FILE_VERSION) {
+            throw new Error(`Unsupported state file version: ${data.version}`);
+        }
+
+        for (const opening of data.openings ?? []) {
+            this.state.putOpening({
+                account: opening.account,
+                balance: BigInt(opening.balance),
+                commitment: new Uint8Array(opening.commitment),
+                blinding: new Uint8Array(opening.blinding),
+                lastUpdated: opening.lastUpdated,
+                version: opening.version,
+            });
+        }
+        for (const note of data.notes ?? []) {
+            this.state.putNote({
+                commitment: new Uint8Array(note.commitment),
+                amount: BigInt(note.amount),
+                secret: new Uint8Array(note.secret),
+                leafIndex: note.leafIndex,
+                spent: note.spent,
+                createdAt: note.createdAt,
+            });
+        }
+        if (data.scanCursor) {
+            this.state.setScanCursor(data.scanCursor);
+        }
+    }
+}
@@ -72,11 +72,22 @@ export interface KeyStorage {
  */
 import { KEY_STORAGE_CONSTANTS } from '../utils/constants';
 
+/** Key file holding the wallet seed (spend authority) */
+export const SPEND_KEY_FILE = 'keys.enc';
+
+/** Key file holding only a viewing secret (watch-only wallets) */
+export const VIEW_KEY_FILE = 'view.enc';
+
 export class LocalKeyStorage implements KeyStorage {
     private readonly filePath: string;
 
-    constructor(walletDir: string = '.wallet') {
-        this.filePath = path.join(walletDir, 'keys.enc');
+    /**
+     * @param walletDir - Wallet directory
+     * @param fileName - Key file (SPEND_KEY_FILE, or VIEW_KEY_FILE for the
+     *                   viewing secret of a watch-only wallet)
+     */
+    constructor(walletDir: string = '.wallet', fileName: string = SPEND_KEY_FILE) {
+        this.filePath = path.join(walletDir, fileName);
     }
 
     /**
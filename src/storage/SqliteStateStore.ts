@@ -0,0 +1,201 @@
+/**
+ * SQLite-backed StateStore
+ *
+ * Stores openings, notes and the scan cursor in three tables of a SQLite
+ * database. The SDK does not ship a SQLite driver: pass an open handle
+ * from `better-sqlite3` (or any driver with the same synchronous
+ * prepare/run/get/all API), e.g. one opened on an SQLCipher database for
+ * encryption at rest.
+ */
+
+import {
+    noteKey,
+    type ScanCursor,
+    type StateStore,
+    type StoredNote,
+    type StoredOpening,
+} from './StateStore';
+
+/**
+ * Prepared statement of a synchronous SQLite driver
+ */
+export interface SqliteStatement {
+    run(...params: unknown[]): unknown;
+    get(...params: unknown[]): unknown;
+    all(...params: unknown[]): unknown[];
+}
+
+/**
+ * Open database handle of a synchronous SQLite driver (better-sqlite3 API)
+ */
+export interface SqliteDatabase {
+    exec(sql: string): unknown;
+    prepare(sql: string): SqliteStatement;
+}
+
+interface OpeningRow {
+    account: string;
+    balance: string;
+    commitment: Uint8Array;
+    blinding: Uint8Array;
+    last_updated: number;
+    version: number;
+}
+
+interface NoteRow {
+    commitment: Uint8Array;
+    amount: string;
+    secret: Uint8Array;
+    leaf_index: number | null;
+    spent: number;
+    created_at: number;
+}
+
+interface CursorRow {
+    slot: number;
+    signature: string | null;
+    updated_at: number;
+}
+
+const SCHEMA = `
+    CREATE TABLE IF NOT EXISTS openings (
+        account TEXT PRIMARY KEY,
+        balance TEXT NOT NULL,
+        commitment BLOB NOT NULL,
+        blinding BLOB NOT NULL,
+        last_updated INTEGER NOT NULL,
+        version INTEGER NOT NULL
+    );
+    CREATE TABLE IF NOT EXISTS notes (
+        id TEXT PRIMARY KEY,
+        commitment BLOB NOT NULL,
+        amount TEXT NOT NULL,
+        secret BLOB NOT NULL,
+        leaf_index INTEGER,
+        spent INTEGER NOT NULL,
+        created_at INTEGER NOT NULL
+    );
+    CREATE TABLE IF NOT EXISTS scan_cursor (
+        id INTEGER PRIMARY KEY CHECK (id = 0),
+        slot INTEGER NOT NULL,
+        signature TEXT,
+        updated_at INTEGER NOT NULL
+    );
+`;
+
+export class SqliteStateStore implements StateStore {
+    private readonly db: SqliteDatabase;
+
+    /**
+     * @param db - Open database handle; the tables are created if missing
+     */
+    constructor(db: SqliteDatabase) {
+        this.db = db;
+        this.db.exec(SCHEMA);
+    }
+
+    getOpening(account: string): StoredOpening | undefined {
+        const row = this.db
+            .prepare('SELECT * FROM openings WHERE account = ?')
+            .get(account) as OpeningRow | undefined;
+        return row ? toOpening(row) : undefined;
+    }
+
+    putOpening(opening: StoredOpening): void {
+        this.db
+            .prepare('INSERT OR REPLACE INTO openings VALUES (?, ?, ?, ?, ?, ?)')
+            .run(
+                opening.account,
+                opening.balance.toString(),
+                Buffer.from(opening.commitment),
+                Buffer.from(opening.blinding),
+                opening.lastUpdated,
+                opening.version
+            );
+    }
+
+    deleteOpening(account: string): void {
+        this.db.prepare('DELETE FROM openings WHERE account = ?').run(account);
+    }
+
+    listOpenings(): StoredOpening[] {
+        const rows = this.db.prepare('SELECT * FROM openings').all() as OpeningRow[];
+        return rows.map(toOpening);
+    }
+
+    getNote(commitment: Uint8Array): StoredNote | undefined {
+        const row = this.db
+            .prepare('SELECT * FROM notes WHERE id = ?')
+            .get(noteKey(commitment)) as NoteRow | undefined;
+        return row ? toNote(row) : undefined;
+    }
+
+    putNote(note: StoredNote): void {
+        this.db
+            .prepare('INSERT OR REPLACE INTO notes VALUES (?, ?, ?, ?, ?, ?, ?)')
+            .run(
+                noteKey(note.commitment),
+                Buffer.from(note.commitment),
+                note.amount.toString(),
+                Buffer.from(note.secret),
+                note.leafIndex ?? null,
+                note.spent ? 1 : 0,
+                note.createdAt
+            );
+    }
+
+    deleteNote(commitment: Uint8Array): void {
+        this.db.prepare('DELETE FROM notes WHERE id = ?').run(noteKey(commitment));
+    }
+
+    listNotes(): StoredNote[] {
+        const rows = this.db.prepare('SELECT * FROM notes').all() as NoteRow[];
+        return rows.map(toNote);
+    }
+
+    getScanCursor(): ScanCursor | undefined {
+        const row = this.db
+            .prepare('SELECT slot, signature, updated_at FROM scan_cursor WHERE id = 0')
+            .get() as CursorRow | undefined;
+        if (!row) {
+            return undefined;
+        }
+        return {
+            slot: row.slot,
+            signature: row.signature ?? undefined,
+            updatedAt: row.updated_at,
+        };
+    }
+
+    setScanCursor(cursor: ScanCursor): void {
+        this.db
+            .prepare('INSERT OR REPLACE INTO scan_cursor VALUES (0, ?, ?, ?)')
+            .run(cursor.slot, cursor.signature ?? null, cursor.updatedAt);
+    }
+
+    clear(): void {
+        this.db.exec('DELETE FROM openings; DELETE FROM notes; DELETE FROM scan_cursor;');
+    }
+}
+
+function toOpening(row: OpeningRow): StoredOpening {
+    return {
+        account: row.account,
+        balance: BigInt(row.balance),
+        commitment: new Uint8Array(row.commitment),
+        blinding: new Uint8Array(row.blinding),
+        lastUpdated: row.last_updated,
+        version: row.version,
+    };
+}
+
+function toNote(row: NoteRow): StoredNote {
+    return {
+        commitment: new Uint8Array(row.commitment),
+        amount: BigInt(row.amount),
+        secret: new Uint8Array(row.secret),
+        leafIndex: row.leaf_index ?? undefined,
+        spent: row.spent !== 0,
+        createdAt: row.created_at,
+    };
+}
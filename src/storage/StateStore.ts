@@ -0,0 +1,164 @@
+/**
+ * Pluggable SDK State Storage
+ *
+ * Everything the SDK has to remember between sessions goes through a
+ * StateStore, so embedders can keep it in their own encrypted storage
+ * (mobile keystores, HSM-backed stores) without forking the SDK:
+ * - Openings: value + blinding of each hidden balance commitment
+ * - Notes: secrets of deposited note-pool notes (needed to spend them)
+ * - Scan cursor: how far the wallet has scanned the chain
+ *
+ * Implementations: MemoryStateStore (below), FileStateStore and
+ * SqliteStateStore. The interface is synchronous like the balance tracker
+ * that drives it; stores backed by an async keystore should load into
+ * memory on startup and write through in the background.
+ */
+
+import type { EncryptedBalanceEntry } from './EncryptedBalanceTracker';
+
+/**
+ * Opening of a hidden balance (same shape the balance tracker keeps)
+ */
+export type StoredOpening = EncryptedBalanceEntry;
+
+/**
+ * Note deposited into the note pool
+ */
+export interface StoredNote {
+    /** Note commitment (32 bytes), the note's identifier */
+    commitment: Uint8Array;
+    /** Amount held by the note */
+    amount: bigint;
+    /** Secret the commitment hides (spends derive the nullifier from it) */
+    secret: Uint8Array;
+    /** Leaf index in the note tree, once known */
+    leafIndex?: number;
+    /** Whether the note was spent */
+    spent: boolean;
+    /** Creation timestamp */
+    createdAt: number;
+}
+
+/**
+ * Position of the chain scanner
+ */
+export interface ScanCursor {
+    /** Last fully scanned slot */
+    slot: number;
+    /** Last processed transaction signature in that slot */
+    signature?: string;
+    /** Last update timestamp */
+    updatedAt: number;
+}
+
+/**
+ * Storage backend for SDK state
+ */
+export interface StateStore {
+    /** Opening of an account's balance, if known */
+    getOpening(account: string): StoredOpening | undefined;
+    /** Insert or replace an account's opening */
+    putOpening(opening: StoredOpening): void;
+    /** Forget an account's opening */
+    deleteOpening(account: string): void;
+    /** All stored openings */
+    listOpenings(): StoredOpening[];
+
+    /** Note with the given commitment, if stored */
+    getNote(commitment: Uint8Array): StoredNote | undefined;
+    /** Insert or replace a note (keyed by commitment) */
+    putNote(note: StoredNote): void;
+    /** Forget a note */
+    deleteNote(commitment: Uint8Array): void;
+    /** All stored notes */
+    listNotes(): StoredNote[];
+
+    /** Current scan cursor, if the wallet has scanned before */
+    getScanCursor(): ScanCursor | undefined;
+    /** Move the scan cursor */
+    setScanCursor(cursor: ScanCursor): void;
+
+    /** Remove all state */
+    clear(): void;
+}
+
+/**
+ * Hex key of a note commitment
+ */
+export function noteKey(commitment: Uint8Array): string {
+    return Buffer.from(commitment).toString('hex');
+}
+
+/**
+ * In-memory StateStore (tests, short-lived sessions, async-backed caches)
+ */
+export class MemoryStateStore implements StateStore {
+    private openings: Map<string, StoredOpening> = new Map();
+    private notes: Map<string, StoredNote> = new Map();
+    private cursor?: ScanCursor;
+
+    getOpening(account: string): StoredOpening | undefined {
+        const opening = this.openings.get(account);
+        return opening ? copyOpening(opening) : undefined;
+    }
+
+    putOpening(opening: StoredOpening): void {
+        this.openings.set(opening.account, copyOpening(opening));
+    }
+
+    deleteOpening(account: string): void {
+        this.openings.delete(account);
+    }
+
+    listOpenings(): StoredOpening[] {
+        return Array.from(this.openings.values(), copyOpening);
+    }
+
+    getNote(commitment: Uint8Array): StoredNote | undefined {
+        const note = this.notes.get(noteKey(commitment));
+        return note ? copyNote(note) : undefined;
+    }
+
+    putNote(note: StoredNote): void {
+        this.notes.set(noteKey(note.commitment), copyNote(note));
+    }
+
+    deleteNote(commitment: Uint8Array): void {
+        this.notes.delete(noteKey(commitment));
+    }
+
+    listNotes(): StoredNote[] {
+        return Array.from(this.notes.values(), copyNote);
+    }
+
+    getScanCursor(): ScanCursor | undefined {
+        return this.cursor ? { ...this.cursor } : undefined;
+    }
+
+    setScanCursor(cursor: ScanCursor): void {
+        this.cursor = { ...cursor };
+    }
+
+    clear(): void {
+        this.openings.clear();
+        this.notes.clear();
+        this.cursor = undefined;
+    }
+}
+
+// Stored values are copied so callers can't mutate the store's state
+function copyOpening(opening: StoredOpening): StoredOpening {
+    return {
+        ...opening,
+        commitment: new Uint8Array(opening.commitment),
+        blinding: new Uint8Array(opening.blinding),
+    };
+}
+
+function copyNote(note: StoredNote): StoredNote {
+    return {
+        ...note,
+        commitment: new Uint8Array(note.commitment),
+        secret: new Uint8Array(note.secret),
+    };
+}
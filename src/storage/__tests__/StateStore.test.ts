@@ -0,0 +1,153 @@
+/**
+ * Tests for pluggable state storage
+ * Tests: Memory/File/SQLite stores, balance tracker on a custom store
+ */
+
+import * as fs from 'fs';
+import * as os from 'os';
+import * as path from 'path';
+import { MemoryStateStore, type StateStore, type StoredNote, type StoredOpening } from '../StateStore';
+import { FileStateStore } from '../FileStateStore';
+import { SqliteStateStore, type SqliteDatabase } from '../SqliteStateStore';
+import { EncryptedBalanceTracker } from '../EncryptedBalanceTracker';
+
+function opening(account: string, balance: bigint): StoredOpening {
+    return {
+        account,
+        balance,
+        commitment: new Uint8Array(32).fill(1),
+        blinding: new Uint8Array(32).fill(2),
+        lastUpdated: 1000,
+        version: 1,
+    };
+}
+
+function note(byte: number, amount: bigint): StoredNote {
+    return {
+        commitment: new Uint8Array(32).fill(byte),
+        amount,
+        secret: new Uint8Array(32).fill(byte + 1),
+        leafIndex: 7,
+        spent: false,
+        createdAt: 2000,
+    };
+}
+
+// better-sqlite3 is optional; the SQLite suite only runs where it is installed
+function openSqlite(): SqliteDatabase | undefined {
+    try {
+        // eslint-disable-next-line @typescript-eslint/no-var-requires
+        const Database = require('better-sqlite3');
+        return new Database(':memory:');
+    } catch {
+        return undefined;
+    }
+}
+
+const tempDirs: string[] = [];
+
+function tempDir(): string {
+    const dir = fs.mkdtempSync(path.join(os.tmpdir(), 'state-store-'));
+    tempDirs.push(dir);
+    return dir;
+}
+
+afterAll(() => {
+    for (const dir of tempDirs) {
+        fs.rmSync(dir, { recursive: true, force: true });
+    }
+});
+
+const sqlite = openSqlite();
+const backends: [string, () => StateStore][] = [
+    ['MemoryStateStore', () => new MemoryStateStore()],
+    ['FileStateStore', () => new FileStateStore(tempDir())],
+];
+if (sqlite) {
+    backends.push(['SqliteStateStore', () => new SqliteStateStore(openSqlite()!)]);
+}
+
+describe.each(backends)('%s', (_name, create) => {
+    test('Stores, replaces and deletes openings', () => {
+        const store = create();
+        store.putOpening(opening('alice', 5n));
+        store.putOpening(opening('bob', 7n));
+        store.putOpening({ ...opening('alice', 9n), version: 2 });
+
+        expect(store.getOpening('alice')).toEqual({ ...opening('alice', 9n), version: 2 });
+        expect(store.listOpenings()).toHaveLength(2);
+
+        store.deleteOpening('bob');
+        expect(store.getOpening('bob')).toBeUndefined();
+    });
+
+    test('Stores notes by commitment', () => {
+        const store = create();
+        store.putNote(note(3, 100n));
+        store.putNote({ ...note(3, 100n), spent: true });
+        store.putNote({ ...note(5, 1n), leafIndex: undefined });
+
+        expect(store.getNote(new Uint8Array(32).fill(3))).toEqual({ ...note(3, 100n), spent: true });
+        expect(store.getNote(new Uint8Array(32).fill(5))?.leafIndex).toBeUndefined();
+        expect(store.listNotes()).toHaveLength(2);
+
+        store.deleteNote(new Uint8Array(32).fill(3));
+        expect(store.getNote(new Uint8Array(32).fill(3))).toBeUndefined();
+    });
+
+    test('Tracks the scan cursor and clears everything', () => {
+        const store = create();
+        expect(store.getScanCursor()).toBeUndefined();
+        store.setScanCursor({ slot: 42, signature: 'sig', updatedAt: 3000 });
+        expect(store.getScanCursor()).toEqual({ slot: 42, signature: 'sig', updatedAt: 3000 });
+
+        store.putOpening(opening('alice', 5n));
+        store.putNote(note(3, 100n));
+        store.clear();
+        expect(store.listOpenings()).toHaveLength(0);
+        expect(store.listNotes()).toHaveLength(0);
+        expect(store.getScanCursor()).toBeUndefined();
+    });
+});
+
+describe('FileStateStore persistence', () => {
+    test('Reloads state written by another instance', () => {
+        const dir = tempDir();
+        const store = new FileStateStore(dir);
+        store.putOpening(opening('alice', 5n));
+        store.putNote(note(3, 100n));
+        store.setScanCursor({ slot: 42, updatedAt: 3000 });
+
+        const reopened = new FileStateStore(dir);
+        expect(reopened.getOpening('alice')).toEqual(opening('alice', 5n));
+        expect(reopened.getNote(new Uint8Array(32).fill(3))).toEqual(note(3, 100n));
+        expect(reopened.getScanCursor()).toEqual({ slot: 42, updatedAt: 3000 });
+    });
+
+    test('Writes the state file owner-only', () => {
+        const dir = tempDir();
+        new FileStateStore(dir).putOpening(opening('alice', 5n));
+        expect(fs.statSync(path.join(dir, 'state.json')).mode & 0o777).toBe(0o600);
+    });
+});
+
+describe('EncryptedBalanceTracker on a StateStore', () => {
+    test('Keeps openings in the store instead of encrypted-balances.json', () => {
+        const dir = tempDir();
+        const store = new MemoryStateStore();
+        const tracker = new EncryptedBalanceTracker(dir, store);
+        tracker.initialize();
+        tracker.setBalance('alice', 10n);
+        tracker.setBalance('bob', 3n);
+        tracker.clearBalance('bob');
+
+        expect(store.getOpening('alice')?.balance).toBe(10n);
+        expect(store.getOpening('bob')).toBeUndefined();
+        expect(fs.existsSync(path.join(dir, 'encrypted-balances.json'))).toBe(false);
+
+        const reloaded = new EncryptedBalanceTracker(dir, store);
+        reloaded.initialize();
+        expect(reloaded.getBalance('alice')).toBe(10n);
+        expect(reloaded.verifyCommitment('alice', 10n)).toBe(true);
+    });
+});
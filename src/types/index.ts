@@ -1,4 +1,5 @@
 import { PublicKey } from '@solana/web3.js';
+import type { StateStore } from '../storage/StateStore';
 // NOTE: ElGamalKeypair removed - using Pedersen commitments instead
 
 /**
@@ -30,6 +31,8 @@ export interface WalletConfig {
     keyStoragePath: string;
     /** Default privacy mode (defaults to ELUSIV for production-ready privacy) */
     defaultPrivacyMode?: PrivacyMode;
+    /** Storage for balance openings (defaults to files in keyStoragePath) */
+    stateStore?: StateStore;
 }
 
 /**
@@ -51,7 +54,7 @@ export interface QueuedTransfer {
  */
 export interface TransactionRecord {
     id: string;
-    type: 'deposit' | 'transfer' | 'withdraw';
+    type: 'deposit' | 'transfer' | 'withdraw' | 'receive';
     amount: string;
     recipient?: string;
     status: 'confirmed' | 'failed';
@@ -97,6 +100,7 @@ export enum ErrorCode {
     LIGHT_PROTOCOL_ERROR = 'LIGHT_PROTOCOL_ERROR',
     CONFIG_INVALID = 'CONFIG_INVALID',
     FILE_ERROR = 'FILE_ERROR',
+    WATCH_ONLY = 'WATCH_ONLY',
 }
 
 /**
@@ -141,4 +145,9 @@ export const createError = {
         new UTXOError(ErrorCode.NETWORK_ERROR, `Network error: ${reason}`),
     configInvalid: (reason: string) =>
         new UTXOError(ErrorCode.CONFIG_INVALID, `Invalid configuration: ${reason}`),
+    watchOnly: (operation: string) =>
+        new UTXOError(
+            ErrorCode.WATCH_ONLY,
+            `Cannot ${operation}: this is a watch-only wallet (viewing key only, no spend authority)`
+        ),
 };
@@ -85,3 +85,44 @@ export const RETRY_CONSTANTS = {
     DEFAULT_BACKOFF_MULTIPLIER: 2,
 } as const;
 
+/**
+ * Privacy transfer program constants
+ */
+export const PROGRAM_CONSTANTS = {
+    /** Deployed privacy-transfer program (Anchor.toml) */
+    PRIVACY_TRANSFER_PROGRAM_ID: 'HHvRt9CScrgHkfhDGUiwbskYpCSA9PetdT4uVwQ5C7f5',
+} as const;
+
+/**
+ * Privacy report thresholds
+ */
+export const PRIVACY_REPORT_CONSTANTS = {
+    /** Account indices scanned for linked PDAs */
+    MAX_ACCOUNT_INDEX: 16,
+    /** Withdrawals this soon after a deposit are linkable by timing */
+    QUICK_WITHDRAW_WINDOW_MS: 60 * 60 * 1000, // 1 hour
+    /** Minimum events before timing regularity is judged */
+    MIN_TIMING_EVENTS: 4,
+    /** Gap coefficient of variation below which timing counts as regular */
+    REGULAR_TIMING_CV: 0.2,
+    /** Share of events in one time-of-day window that forms a habit */
+    TIME_OF_DAY_SHARE: 0.8,
+    /** Width of the time-of-day window in hours */
+    TIME_OF_DAY_WINDOW_HOURS: 3,
+    /** Public amounts that are multiples of this are common denominations (0.1 SOL) */
+    ROUND_DENOMINATION_LAMPORTS: 100_000_000n,
+    /** Transfers to one recipient before the counterparty stands out */
+    COUNTERPARTY_REUSE_COUNT: 3,
+} as const;
+
+/**
+ * Shared pool denominations (must match pool_constants::DENOMINATIONS)
+ */
+export const POOL_DENOMINATION_CONSTANTS = {
+    /** Amounts deposit_pool / withdraw_pool accept, largest first (100, 10, 1, 0.1 SOL) */
+    DENOMINATIONS_LAMPORTS: [100_000_000_000n, 10_000_000_000n, 1_000_000_000n, 100_000_000n],
+    /** Minimum gap between two scheduled pool transactions */
+    MIN_DELAY_MS: 10 * 60 * 1000, // 10 minutes
+    /** Random extra gap per transaction, in [0, JITTER) */
+    DELAY_JITTER_MS: 50 * 60 * 1000, // 50 minutes
+} as const;
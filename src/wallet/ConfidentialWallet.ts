@@ -16,6 +16,7 @@ import { createError } from '../types';
 import { BatchQueue } from '../batch/BatchQueue';
 import { DEFAULT_RETRY_CONFIG } from '../utils/errorHandler';
 import * as crypto from 'crypto';
+import { deriveViewingSecret, viewingPublicKey } from '../crypto/viewingKey';
 
 /**
  * ConfidentialWallet - Hybrid Privacy Wallet
@@ -63,7 +64,7 @@ export class ConfidentialWallet {
         this.keyStorage = keyStorage;
         this.accountStorage = new AccountStorage(config.keyStoragePath);
         this.transactionHistory = new TransactionHistory(config.keyStoragePath);
-        this.balanceTracker = new EncryptedBalanceTracker(config.keyStoragePath);
+        this.balanceTracker = new EncryptedBalanceTracker(config.keyStoragePath, config.stateStore);
         // Default to Native ZK for privacy with our custom implementation
         this.privacyMode = config.defaultPrivacyMode || PrivacyMode.NATIVE_ZK;
     }
@@ -142,6 +143,24 @@ export class ConfidentialWallet {
         return this.signer.publicKey;
     }
 
+    /**
+     * Get the viewing secret (import it into a watch-only wallet)
+     * @returns 32-byte X25519 secret; grants read access to balance views only
+     */
+    getViewingSecret(): Uint8Array {
+        if (!this.signer) {
+            throw createError.keyNotFound();
+        }
+        return deriveViewingSecret(this.signer.secretKey.slice(0, 32));
+    }
+
+    /**
+     * Get the viewing public key (register it with register_viewing_key)
+     */
+    getViewingPublicKey(): Uint8Array {
+        return viewingPublicKey(this.getViewingSecret());
+    }
+
     /**
      * Setup confidential mint and token account (public method)
      * Call this after wallet has been funded with SOL
@@ -7,11 +7,58 @@
 
 import { Connection, PublicKey, SystemProgram, Transaction } from '@solana/web3.js';
 import { Program, AnchorProvider, BN } from '@coral-xyz/anchor';
-import { PedersenCommitment, ScalarOps } from '../crypto/zkproofs/primitives';
+import { ScalarOps } from '../crypto/zkproofs/primitives';
 import { PrivacyLayer } from '../privacy/PrivacyLayer';
+import { pointToSlot } from '../crypto/zkproofs/programTranscript';
+import {
+    BalanceOpening,
+    EMPTY_BALANCE,
+    proveBalanceCap,
+    proveDeposit,
+    proveWithdraw,
+} from '../crypto/zkproofs/publicAmountProof';
+import {
+    runSchedule,
+    scheduleDenominations,
+    splitIntoDenominations,
+    type ScheduleOptions,
+} from '../privacy/Denominations';
+import { POOL_DENOMINATION_CONSTANTS } from '../utils/constants';
 
 const LAMPORTS_PER_SOL = 1_000_000_000;
 
+/** Circuit id of deny-list exclusion proofs (groth16::EXCLUSION_CIRCUIT) */
+const EXCLUSION_CIRCUIT = 2;
+
+/**
+ * PDA seed of an account index (u16 little-endian, as the program derives it)
+ */
+export function accountIndexSeed(accountIndex: number): Buffer {
+    const seed = Buffer.alloc(2);
+    seed.writeUInt16LE(accountIndex);
+    return seed;
+}
+
+/**
+ * Compliance proofs the program requires when the config enables them
+ */
+export interface ComplianceProofs {
+    /** Deny-list non-membership proof for the depositing / receiving wallet */
+    exclusionProof?: Uint8Array;
+}
+
+/** Encode an optional `Vec<u8>` argument */
+function optionalBytes(bytes: Uint8Array | undefined): Buffer | null {
+    return bytes ? Buffer.from(bytes) : null;
+}
+
+/** Reject an amount the pool would refuse */
+function requireDenomination(lamports: bigint): void {
+    if (!(POOL_DENOMINATION_CONSTANTS.DENOMINATIONS_LAMPORTS as readonly bigint[]).includes(lamports)) {
+        throw new Error(`${lamports} lamports is not a pool denomination`);
+    }
+}
+
 export interface SolEscrowAccount {
     owner: PublicKey;
     balance: BN;
@@ -28,35 +75,93 @@ export class SolPrivacyMethods {
 
     /**
      * Get SOL escrow PDA for a wallet
+     *
+     * `accountIndex` selects one of the owner's accounts (u16 LE seed).
      */
-    getSolEscrowPDA(owner: PublicKey): [PublicKey, number] {
+    getSolEscrowPDA(owner: PublicKey, accountIndex = 0): [PublicKey, number] {
         return PublicKey.findProgramAddressSync(
-            [Buffer.from('sol-escrow'), owner.toBuffer()],
+            [Buffer.from('sol-escrow'), owner.toBuffer(), accountIndexSeed(accountIndex)],
             this.program.programId
         );
     }
 
     /**
      * Get encrypted account PDA
+     *
+     * `accountIndex` selects one of the owner's accounts (u16 LE seed).
      */
-    getEncryptedAccountPDA(owner: PublicKey): [PublicKey, number] {
+    getEncryptedAccountPDA(owner: PublicKey, accountIndex = 0): [PublicKey, number] {
         return PublicKey.findProgramAddressSync(
-            [Buffer.from('encrypted-account'), owner.toBuffer()],
+            [Buffer.from('encrypted-account'), owner.toBuffer(), accountIndexSeed(accountIndex)],
             this.program.programId
         );
     }
 
+    /**
+     * Get the global config PDA (compliance policies)
+     */
+    getConfigPDA(): [PublicKey, number] {
+        return PublicKey.findProgramAddressSync([Buffer.from('config')], this.program.programId);
+    }
+
+    /**
+     * Verifying key PDA of the deny-list exclusion circuit
+     */
+    getExclusionVerifyingKeyPDA(): [PublicKey, number] {
+        return PublicKey.findProgramAddressSync(
+            [Buffer.from('note-vk'), Buffer.from([EXCLUSION_CIRCUIT])],
+            this.program.programId
+        );
+    }
+
+    /**
+     * Shared SOL pool vault PDA
+     */
+    getPoolVaultPDA(): [PublicKey, number] {
+        return PublicKey.findProgramAddressSync([Buffer.from('pool-vault')], this.program.programId);
+    }
+
+    /**
+     * Protocol stats PDA (circuit breaker counters)
+     */
+    getStatsPDA(): [PublicKey, number] {
+        return PublicKey.findProgramAddressSync([Buffer.from('stats')], this.program.programId);
+    }
+
+    /**
+     * Fee treasury PDA, or null before the treasury is initialized
+     *
+     * withdraw_sol requires it while a protocol fee applies.
+     */
+    async getTreasuryIfInitialized(): Promise<PublicKey | null> {
+        const [treasuryPDA] = PublicKey.findProgramAddressSync(
+            [Buffer.from('treasury')],
+            this.program.programId
+        );
+        const info = await this.connection.getAccountInfo(treasuryPDA);
+        return info ? treasuryPDA : null;
+    }
+
+    /**
+     * Per-account balance cap from the config (0 = no cap or no config)
+     */
+    async getMaxAccountBalance(): Promise<bigint> {
+        const [configPDA] = this.getConfigPDA();
+        const config = await (this.program.account as any).config?.fetchNullable(configPDA);
+        return config ? BigInt(config.maxAccountBalance.toString()) : 0n;
+    }
+
     /**
      * Initialize SOL escrow account
      */
-    async initializeSolEscrow(): Promise<string> {
-        const [solEscrowPDA] = this.getSolEscrowPDA(this.wallet.publicKey);
+    async initializeSolEscrow(accountIndex = 0): Promise<string> {
+        const [solEscrowPDA] = this.getSolEscrowPDA(this.wallet.publicKey, accountIndex);
 
         console.log('🔐 Initializing SOL escrow...');
         console.log(`   Escrow PDA: ${solEscrowPDA.toBase58()}`);
 
         const tx = await this.program.methods
-            .initializeSolEscrow()
+            .initializeSolEscrow(accountIndex)
             .accounts({
                 solEscrow: solEscrowPDA,
                 owner: this.wallet.publicKey,
@@ -72,34 +177,52 @@ export class SolPrivacyMethods {
 
     /**
      * Deposit native SOL with privacy
+     *
+     * `current` opens the account's current hidden balance (EMPTY_BALANCE for
+     * a fresh account). Returns the opening of the new balance; keep it, the
+     * next deposit, withdrawal or transfer has to prove against it.
+     * Pass `proofs.exclusionProof` while a deny list is configured; the
+     * balance cap proof is generated here when the config sets a cap.
      */
-    async depositSOL(amountSol: number): Promise<string> {
+    async depositSOL(
+        amountSol: number,
+        current: BalanceOpening = EMPTY_BALANCE,
+        proofs: ComplianceProofs = {}
+    ): Promise<{ signature: string; opening: BalanceOpening }> {
         console.log(`\n💰 Depositing ${amountSol} SOL with privacy...`);
 
         const lamports = BigInt(Math.floor(amountSol * LAMPORTS_PER_SOL));
 
-        // Generate encrypted commitment
-        const blinding = ScalarOps.random();
-        const commitment = PedersenCommitment.commit(lamports, blinding);
-        const commitmentBytes = commitment.toBytes();
+        // Generate the new commitment and the deposit proof
+        // (new = old + amount*G + r*H, checked on-chain)
+        const deposit = await proveDeposit(current, lamports);
+
+        // Cap proof: new balance <= max_account_balance (when configured)
+        const cap = await this.getMaxAccountBalance();
+        const capProof = cap > 0n ? await proveBalanceCap(deposit.newOpening, cap) : undefined;
 
         // Get PDAs
         const [encryptedAccountPDA] = this.getEncryptedAccountPDA(this.wallet.publicKey);
         const [solEscrowPDA] = this.getSolEscrowPDA(this.wallet.publicKey);
 
-        console.log('   Generating encrypted commitment...');
+        console.log('   Generating deposit proof...');
         console.log(`   Amount: ${lamports} lamports (ENCRYPTED)`);
 
         // Call on-chain program
         const tx = await this.program.methods
             .depositSol(
                 new BN(lamports.toString()),
-                Array.from(commitmentBytes)
+                Array.from(deposit.newCommitment),
+                Buffer.from(deposit.proofData),
+                optionalBytes(proofs.exclusionProof),
+                optionalBytes(capProof)
             )
             .accounts({
                 encryptedAccount: encryptedAccountPDA,
                 solEscrow: solEscrowPDA,
                 owner: this.wallet.publicKey,
+                config: this.getConfigPDA()[0],
+                exclusionVerifyingKey: proofs.exclusionProof ? this.getExclusionVerifyingKeyPDA()[0] : null,
                 systemProgram: SystemProgram.programId,
             })
             .rpc();
@@ -108,44 +231,57 @@ export class SolPrivacyMethods {
         console.log(`   Signature: ${tx.slice(0, 16)}...`);
         console.log(`   Amount: ${amountSol} SOL (encrypted on-chain)`);
 
-        return tx;
+        return { signature: tx, opening: deposit.newOpening };
     }
 
     /**
      * Withdraw native SOL
+     *
+     * `current` opens the account's current hidden balance. The SOL goes to
+     * `destination` (the wallet by default); the proof is bound to it, so a
+     * relayer or front-runner cannot redirect the payout. Returns the opening
+     * of the remaining balance. Pass `proofs.exclusionProof` (for the
+     * destination) while a deny list is configured.
      */
-    async withdrawSOL(amountSol: number): Promise<string> {
+    async withdrawSOL(
+        amountSol: number,
+        current: BalanceOpening,
+        destination: PublicKey = this.wallet.publicKey,
+        proofs: ComplianceProofs = {}
+    ): Promise<{ signature: string; opening: BalanceOpening }> {
         console.log(`\n💸 Withdrawing ${amountSol} SOL...`);
 
         const lamports = BigInt(Math.floor(amountSol * LAMPORTS_PER_SOL));
 
-        // Get current balance and calculate new commitment
-        const currentBalance = await this.getSOLBalance();
-        const currentLamports = BigInt(Math.floor(currentBalance * LAMPORTS_PER_SOL));
-        const newBalance = currentLamports - lamports;
-
-        // Generate new encrypted commitment
-        const blinding = ScalarOps.random();
-        const commitment = PedersenCommitment.commit(newBalance, blinding);
-        const commitmentBytes = commitment.toBytes();
+        // Remainder commitment plus the withdraw proof
+        // (old - new = amount*G + r*H, bound to the destination)
+        const withdrawal = await proveWithdraw(current, lamports, destination.toBytes());
 
         // Get PDAs
         const [encryptedAccountPDA] = this.getEncryptedAccountPDA(this.wallet.publicKey);
         const [solEscrowPDA] = this.getSolEscrowPDA(this.wallet.publicKey);
 
-        console.log('   Generating new encrypted commitment...');
-        console.log(`   Remaining: ${Number(newBalance) / LAMPORTS_PER_SOL} SOL (ENCRYPTED)`);
+        console.log('   Generating withdraw proof...');
+        console.log(`   Remaining: ${Number(withdrawal.newOpening.value) / LAMPORTS_PER_SOL} SOL (ENCRYPTED)`);
+        console.log(`   Destination: ${destination.toBase58()}`);
 
         // Call on-chain program
         const tx = await this.program.methods
             .withdrawSol(
                 new BN(lamports.toString()),
-                Array.from(commitmentBytes)
+                Array.from(withdrawal.newCommitment),
+                Buffer.from(withdrawal.proofData),
+                optionalBytes(proofs.exclusionProof)
             )
             .accounts({
                 encryptedAccount: encryptedAccountPDA,
                 solEscrow: solEscrowPDA,
                 owner: this.wallet.publicKey,
+                destination,
+                config: this.getConfigPDA()[0],
+                sanctionsAttestation: null,
+                treasury: await this.getTreasuryIfInitialized(),
+                exclusionVerifyingKey: proofs.exclusionProof ? this.getExclusionVerifyingKeyPDA()[0] : null,
                 systemProgram: SystemProgram.programId,
             })
             .rpc();
@@ -154,7 +290,114 @@ export class SolPrivacyMethods {
         console.log(`   Signature: ${tx.slice(0, 16)}...`);
         console.log(`   Amount: ${amountSol} SOL`);
 
-        return tx;
+        return { signature: tx, opening: withdrawal.newOpening };
+    }
+
+    /**
+     * Deposit one fixed denomination into the shared pool
+     *
+     * `current` opens the account's pool balance. Returns the opening of the
+     * new pool balance. Use depositPoolSplit() for any other amount.
+     */
+    async depositPool(
+        lamports: bigint,
+        current: BalanceOpening = EMPTY_BALANCE
+    ): Promise<{ signature: string; opening: BalanceOpening }> {
+        requireDenomination(lamports);
+
+        const deposit = await proveDeposit(current, lamports);
+        const cap = await this.getMaxAccountBalance();
+        const capProof = cap > 0n ? await proveBalanceCap(deposit.newOpening, cap) : undefined;
+
+        const tx = await this.program.methods
+            .depositPool(
+                new BN(lamports.toString()),
+                Array.from(deposit.newCommitment),
+                Buffer.from(deposit.proofData),
+                optionalBytes(capProof)
+            )
+            .accounts(this.poolAccounts())
+            .rpc();
+
+        return { signature: tx, opening: deposit.newOpening };
+    }
+
+    /**
+     * Withdraw one fixed denomination from the shared pool
+     *
+     * `current` opens the account's pool balance. Returns the opening of the
+     * remaining pool balance.
+     */
+    async withdrawPool(
+        lamports: bigint,
+        current: BalanceOpening
+    ): Promise<{ signature: string; opening: BalanceOpening }> {
+        requireDenomination(lamports);
+
+        const withdrawal = await proveWithdraw(current, lamports);
+
+        const tx = await this.program.methods
+            .withdrawPool(
+                new BN(lamports.toString()),
+                Array.from(withdrawal.newCommitment),
+                Buffer.from(withdrawal.proofData)
+            )
+            .accounts(this.poolAccounts())
+            .rpc();
+
+        return { signature: tx, opening: withdrawal.newOpening };
+    }
+
+    /**
+     * Deposit any amount into the shared pool as fixed denominations
+     *
+     * The amount is split into the fewest denominations, which are sent in
+     * random order with randomized gaps (see scheduleDenominations; pass
+     * `schedule` to tune them). This resolves only after the last part
+     * lands, which takes hours with the default gaps. Lamports below the
+     * smallest denomination are not deposited and are returned as
+     * `remainder`. Returns the final pool balance opening.
+     */
+    async depositPoolSplit(
+        amountSol: number,
+        current: BalanceOpening = EMPTY_BALANCE,
+        schedule: ScheduleOptions = {}
+    ): Promise<{ signatures: string[]; opening: BalanceOpening; remainder: bigint }> {
+        const lamports = BigInt(Math.floor(amountSol * LAMPORTS_PER_SOL));
+        const { parts, remainder } = splitIntoDenominations(lamports);
+        if (parts.length === 0) {
+            throw new Error('Amount is below the smallest pool denomination');
+        }
+        const plan = scheduleDenominations(parts, schedule);
+
+        console.log(`\n🏊 Depositing ${amountSol} SOL into the pool as ${parts.length} denominations...`);
+        if (remainder > 0n) {
+            console.log(`   ${remainder} lamports below the smallest denomination stay in the wallet`);
+        }
+
+        let opening = current;
+        const signatures = await runSchedule(plan, async (amount, index) => {
+            const result = await this.depositPool(amount, opening);
+            opening = result.opening;
+            console.log(`   ✅ Part ${index + 1}/${plan.length}: ${Number(amount) / LAMPORTS_PER_SOL} SOL`);
+            return result.signature;
+        });
+
+        return { signatures, opening, remainder };
+    }
+
+    /**
+     * Accounts shared by deposit_pool and withdraw_pool
+     */
+    private poolAccounts() {
+        return {
+            poolVault: this.getPoolVaultPDA()[0],
+            encryptedAccount: this.getEncryptedAccountPDA(this.wallet.publicKey)[0],
+            owner: this.wallet.publicKey,
+            config: this.getConfigPDA()[0],
+            stats: this.getStatsPDA()[0],
+            systemProgram: SystemProgram.programId,
+        };
     }
 
     /**
@@ -208,9 +451,9 @@ export class SolPrivacyMethods {
 
         console.log(`   ✅ Proofs verified in ${verifyTime}ms`);
 
-        // Generate new commitments
-        const senderCommitment = PedersenCommitment.commit(senderAfter, blindings.senderAfter);
-        const recipientCommitment = PedersenCommitment.commit(transferAmount, blindings.amount);
+        // New commitments (the recipient's is range-proven in the transfer proof)
+        const senderCommitment = transfer.senderAfterCommitment;
+        const recipientCommitment = transfer.recipientCommitment!;
 
         // Serialize proof data for on-chain submission
         // SECURITY: Properly serializes all proof components to bytes
@@ -223,13 +466,20 @@ export class SolPrivacyMethods {
         const [senderEscrowPDA] = this.getSolEscrowPDA(this.wallet.publicKey);
         const [recipientEscrowPDA] = this.getSolEscrowPDA(recipient);
 
+        // Versions the proof was built against; the program rejects the
+        // transfer if either account changed in the meantime
+        const senderVersion = await this.getAccountVersion(senderAccountPDA);
+        const recipientVersion = await this.getAccountVersion(recipientAccountPDA);
+
         // Call on-chain program
         const tx = await this.program.methods
             .confidentialSolTransfer(
                 new BN(transferAmount.toString()),
-                Array.from(senderCommitment.toBytes()),
-                Array.from(recipientCommitment.toBytes()),
-                Array.from(proofData)
+                Array.from(pointToSlot(senderCommitment)),
+                Array.from(pointToSlot(recipientCommitment)),
+                proofData,
+                senderVersion,
+                recipientVersion
             )
             .accounts({
                 senderAccount: senderAccountPDA,
@@ -251,6 +501,14 @@ export class SolPrivacyMethods {
         return tx;
     }
 
+    /**
+     * Current version of an encrypted account (expected-version argument)
+     */
+    async getAccountVersion(encryptedAccount: PublicKey): Promise<BN> {
+        const account = await (this.program.account as any).encryptedAccount.fetch(encryptedAccount);
+        return account.version as BN;
+    }
+
     /**
      * Get SOL balance from escrow
      */
@@ -0,0 +1,301 @@
+/**
+ * Watch-Only Wallet
+ *
+ * Monitors encrypted accounts with only a viewing key imported, for
+ * treasurers and accountants who must see balances and incoming transfers
+ * but must not be able to move funds. No seed or signer is ever loaded:
+ * every spend operation fails with ErrorCode.WATCH_ONLY.
+ *
+ * The owner registers the viewing public key on each account
+ * (register_viewing_key) and publishes balance views; sync() scans the
+ * watched accounts for BalanceView events, decrypts the openings and
+ * stores them in the StateStore. A balance that grows between two views
+ * is recorded as an incoming transfer.
+ */
+
+import * as fs from 'fs';
+import * as path from 'path';
+import { Connection, LAMPORTS_PER_SOL, PublicKey } from '@solana/web3.js';
+import { KeyStorage } from '../storage/KeyStorage';
+import { FileStateStore } from '../storage/FileStateStore';
+import type { StateStore } from '../storage/StateStore';
+import { TransactionHistory } from '../storage/TransactionHistory';
+import { PedersenCommitment, scalarToBytes } from '../crypto/zkproofs/primitives';
+import {
+    decodeBalanceViewEvent,
+    openBalanceView,
+    viewingPublicKey,
+    type BalanceViewEvent,
+} from '../crypto/viewingKey';
+import { KEY_STORAGE_CONSTANTS } from '../utils/constants';
+import { WalletConfig, TransactionRecord } from '../types';
+import { createError } from '../types';
+
+/** Signatures fetched per watched account and sync */
+const SYNC_SIGNATURE_LIMIT = 1000;
+
+/**
+ * Balance change found by sync()
+ */
+export interface WatchedActivity {
+    /** Encrypted account */
+    account: string;
+    /** Account version of the balance view */
+    version: number;
+    /** Balance after the change (lamports) */
+    balance: bigint;
+    /** Change since the previous view, undefined for the first view */
+    delta?: bigint;
+    /** Transaction that published the view */
+    signature: string;
+}
+
+export class WatchOnlyWallet {
+    private config: WalletConfig;
+    private keyStorage: KeyStorage;
+    private store: StateStore;
+    private transactionHistory: TransactionHistory;
+    private watchListPath: string;
+    private viewingSecret?: Uint8Array;
+    private watchedAccounts: PublicKey[] = [];
+    private connection?: Connection;
+
+    /**
+     * @param config - Wallet configuration
+     * @param keyStorage - Storage of the viewing secret (VIEW_KEY_FILE)
+     */
+    constructor(config: WalletConfig, keyStorage: KeyStorage) {
+        this.config = config;
+        this.keyStorage = keyStorage;
+        this.store = config.stateStore ?? new FileStateStore(config.keyStoragePath);
+        this.transactionHistory = new TransactionHistory(config.keyStoragePath);
+        this.watchListPath = path.join(config.keyStoragePath, 'watched-accounts.json');
+    }
+
+    /**
+     * Import a viewing secret and the encrypted accounts it may view
+     * @param viewingSecret - 32-byte viewing secret exported by the owner
+     * @param accounts - Encrypted account addresses to watch
+     * @param password - Password to encrypt the viewing secret
+     */
+    async importViewingKey(viewingSecret: Uint8Array, accounts: PublicKey[], password: string): Promise<void> {
+        if (viewingSecret.length !== 32) {
+            throw createError.configInvalid('Viewing key must be 32 bytes');
+        }
+        if (accounts.length === 0) {
+            throw createError.configInvalid('At least one account to watch is required');
+        }
+
+        await this.keyStorage.save(Buffer.from(viewingSecret), undefined, password);
+        this.saveWatchList(accounts);
+
+        this.viewingSecret = viewingSecret;
+        this.watchedAccounts = accounts;
+        this.connection = new Connection(this.config.rpcUrl, 'confirmed');
+    }
+
+    /**
+     * Load the imported viewing secret
+     * @param password - Password to decrypt the viewing secret
+     */
+    async initialize(password: string): Promise<void> {
+        const keys = await this.keyStorage.load(password);
+        this.viewingSecret = new Uint8Array(keys.seed);
+        this.watchedAccounts = this.loadWatchList();
+        this.connection = new Connection(this.config.rpcUrl, 'confirmed');
+    }
+
+    /**
+     * Watch-only wallets never hold spend authority
+     */
+    isWatchOnly(): boolean {
+        return true;
+    }
+
+    /**
+     * Refuse a spend operation
+     * @param operation - Operation name shown to the user (e.g. 'deposit')
+     * @throws UTXOError with ErrorCode.WATCH_ONLY, always
+     */
+    assertCanSpend(operation: string): never {
+        throw createError.watchOnly(operation);
+    }
+
+    /**
+     * Get the viewing public key the owner must register
+     */
+    getViewingPublicKey(): Uint8Array {
+        return viewingPublicKey(this.getViewingSecret());
+    }
+
+    /**
+     * Get the watched encrypted accounts
+     */
+    getWatchedAccounts(): PublicKey[] {
+        return [...this.watchedAccounts];
+    }
+
+    /**
+     * Latest known balance of each watched account (lamports)
+     */
+    getBalances(): Map<string, bigint> {
+        const balances = new Map<string, bigint>();
+        for (const account of this.watchedAccounts) {
+            const opening = this.store.getOpening(account.toBase58());
+            if (opening) {
+                balances.set(account.toBase58(), opening.balance);
+            }
+        }
+        return balances;
+    }
+
+    /**
+     * Get transaction history (incoming transfers found by sync)
+     */
+    getHistory(): TransactionRecord[] {
+        return this.transactionHistory.getHistory();
+    }
+
+    /**
+     * Scan watched accounts for new balance views
+     * @returns Balance changes, oldest first
+     */
+    async sync(): Promise<WatchedActivity[]> {
+        const connection = this.getConnection();
+        const cursor = this.store.getScanCursor();
+        const fromSlot = cursor?.slot ?? 0;
+        let lastSlot = fromSlot;
+        const activity: WatchedActivity[] = [];
+
+        try {
+            for (const account of this.watchedAccounts) {
+                const signatures = await connection.getSignaturesForAddress(account, {
+                    limit: SYNC_SIGNATURE_LIMIT,
+                });
+                const pending = signatures
+                    .filter((info) => !info.err && info.slot > fromSlot)
+                    .reverse();
+
+                for (const info of pending) {
+                    lastSlot = Math.max(lastSlot, info.slot);
+                    const tx = await connection.getTransaction(info.signature, {
+                        maxSupportedTransactionVersion: 0,
+                    });
+                    for (const event of balanceViewEvents(tx?.meta?.logMessages ?? [])) {
+                        const change = this.applyBalanceView(event, info.signature);
+                        if (change) {
+                            activity.push(change);
+                        }
+                    }
+                }
+            }
+        } catch (error: any) {
+            throw createError.syncFailed(error.message);
+        }
+
+        this.store.setScanCursor({ slot: lastSlot, updatedAt: Date.now() });
+        return activity;
+    }
+
+    /**
+     * Apply a decoded BalanceView event
+     *
+     * Views for other keys or unwatched accounts, and views older than the
+     * stored one, are ignored.
+     *
+     * @returns The balance change, or null if the view was ignored
+     */
+    applyBalanceView(event: BalanceViewEvent, signature: string): WatchedActivity | null {
+        const account = event.encryptedAccount.toBase58();
+        const viewingKey = this.getViewingPublicKey();
+        if (!this.watchedAccounts.some((watched) => watched.equals(event.encryptedAccount))
+            || !viewingKey.every((byte, i) => event.viewingKey[i] === byte)) {
+            return null;
+        }
+
+        const version = Number(event.version);
+        const previous = this.store.getOpening(account);
+        if (previous && previous.version >= version) {
+            return null;
+        }
+
+        const opening = openBalanceView(this.getViewingSecret(), event.view);
+        this.store.putOpening({
+            account,
+            balance: opening.value,
+            commitment: PedersenCommitment.commit(opening.value, opening.blinding).toBytes(),
+            blinding: scalarToBytes(opening.blinding),
+            lastUpdated: Date.now(),
+            version,
+        });
+
+        const delta = previous ? opening.value - previous.balance : undefined;
+        if (delta !== undefined && delta > 0n) {
+            this.transactionHistory.addTransaction({
+                id: signature,
+                type: 'receive',
+                amount: (Number(delta) / LAMPORTS_PER_SOL).toString(),
+                recipient: account,
+                status: 'confirmed',
+                signature,
+                timestamp: Date.now(),
+            });
+        }
+
+        return { account, version, balance: opening.value, delta, signature };
+    }
+
+    /**
+     * Get the RPC connection
+     */
+    getConnection(): Connection {
+        if (!this.connection) {
+            throw createError.keyNotFound();
+        }
+        return this.connection;
+    }
+
+    private getViewingSecret(): Uint8Array {
+        if (!this.viewingSecret) {
+            throw createError.keyNotFound();
+        }
+        return this.viewingSecret;
+    }
+
+    private saveWatchList(accounts: PublicKey[]): void {
+        const dir = path.dirname(this.watchListPath);
+        if (!fs.existsSync(dir)) {
+            fs.mkdirSync(dir, { recursive: true, mode: KEY_STORAGE_CONSTANTS.DIR_PERMISSIONS });
+        }
+        fs.writeFileSync(
+            this.watchListPath,
+            JSON.stringify(accounts.map((account) => account.toBase58()), null, 2),
+            { mode: KEY_STORAGE_CONSTANTS.FILE_PERMISSIONS }
+        );
+    }
+
+    private loadWatchList(): PublicKey[] {
+        if (!fs.existsSync(this.watchListPath)) {
+            return [];
+        }
+        const addresses: string[] = JSON.parse(fs.readFileSync(this.watchListPath, 'utf-8'));
+        return addresses.map((address) => new PublicKey(address));
+    }
+}
+
+/**
+ * BalanceView events in a transaction's logs
+ */
+export function balanceViewEvents(logMessages: string[]): BalanceViewEvent[] {
+    const events: BalanceViewEvent[] = [];
+    for (const line of logMessages) {
+        if (!line.startsWith('Program data: ')) {
+            continue;
+        }
+        const event = decodeBalanceViewEvent(Buffer.from(line.slice('Program data: '.length), 'base64'));
+        if (event) {
+            events.push(event);
+        }
+    }
+    return events;
+}
@@ -0,0 +1,134 @@
+/**
+ * Tests for viewing keys and watch-only wallets
+ * Tests: View envelope, BalanceView decoding, watch-only balance tracking
+ */
+
+import * as fs from 'fs';
+import * as os from 'os';
+import * as path from 'path';
+import { Keypair, PublicKey } from '@solana/web3.js';
+import { sha256 } from '@noble/hashes/sha2.js';
+import {
+    deriveViewingSecret,
+    openBalanceView,
+    sealBalanceView,
+    viewingPublicKey,
+    type EncryptedView,
+} from '../../crypto/viewingKey';
+import { PedersenCommitment, ScalarOps } from '../../crypto/zkproofs/primitives';
+import { MemoryStateStore } from '../../storage/StateStore';
+import type { KeyStorage } from '../../storage/KeyStorage';
+import { ErrorCode, UTXOError, type WalletConfig } from '../../types';
+import { WatchOnlyWallet, balanceViewEvents } from '../WatchOnlyWallet';
+
+const viewingSecret = deriveViewingSecret(new Uint8Array(32).fill(9));
+const viewingKey = viewingPublicKey(viewingSecret);
+
+/** `Program data:` log line of a BalanceView event */
+function balanceViewLog(account: PublicKey, key: Uint8Array, version: bigint, view: EncryptedView): string {
+    const data = Buffer.alloc(8 + 204);
+    data.set(sha256(new TextEncoder().encode('event:BalanceView')).slice(0, 8), 0);
+    data.set(account.toBytes(), 8);
+    data.set(key, 40);
+    data.writeBigUInt64LE(version, 72);
+    data.set(view.ephemeralKey, 112);
+    data.set(view.nonce, 144);
+    data.set(view.ciphertext, 156);
+    return `Program data: ${data.toString('base64')}`;
+}
+
+function viewEvent(account: PublicKey, version: bigint, balance: bigint, key = viewingKey) {
+    const view = sealBalanceView(key, { value: balance, blinding: ScalarOps.random() });
+    return balanceViewEvents([balanceViewLog(account, key, version, view)])[0];
+}
+
+describe('Viewing Keys', () => {
+    test('Balance views round-trip under the viewing key', () => {
+        const blinding = ScalarOps.random();
+        const view = sealBalanceView(viewingKey, { value: 1234n, blinding });
+
+        expect(view.ciphertext).toHaveLength(56);
+        expect(openBalanceView(viewingSecret, view)).toEqual({ value: 1234n, blinding });
+    });
+
+    test('Other keys cannot open a view', () => {
+        const view = sealBalanceView(viewingKey, { value: 1n, blinding: 1n });
+        const otherSecret = deriveViewingSecret(new Uint8Array(32).fill(1));
+        expect(() => openBalanceView(otherSecret, view)).toThrow('cannot be decrypted');
+    });
+
+    test('Only BalanceView payloads decode', () => {
+        const account = Keypair.generate().publicKey;
+        const event = viewEvent(account, 3n, 10n);
+
+        expect(event.encryptedAccount.equals(account)).toBe(true);
+        expect(event.version).toBe(3n);
+        expect(balanceViewEvents(['Program log: hello', 'Program data: AAAA'])).toHaveLength(0);
+    });
+});
+
+describe('WatchOnlyWallet', () => {
+    let dir: string;
+    let wallet: WatchOnlyWallet;
+    let store: MemoryStateStore;
+    const watched = Keypair.generate().publicKey;
+
+    beforeEach(async () => {
+        dir = fs.mkdtempSync(path.join(os.tmpdir(), 'watch-only-'));
+        store = new MemoryStateStore();
+        const config: WalletConfig = {
+            rpcUrl: 'http://127.0.0.1:8899',
+            network: 'devnet',
+            batch: { windowMs: 1000, maxSize: 1 },
+            keyStoragePath: dir,
+            stateStore: store,
+        };
+        const keyStorage: KeyStorage = {
+            save: async () => undefined,
+            load: async () => ({ seed: Buffer.from(viewingSecret) }),
+            exists: () => true,
+        };
+        wallet = new WatchOnlyWallet(config, keyStorage);
+        await wallet.importViewingKey(viewingSecret, [watched], 'password');
+    });
+
+    afterEach(() => {
+        fs.rmSync(dir, { recursive: true, force: true });
+    });
+
+    test('Tracks balances and records incoming transfers', () => {
+        expect(wallet.applyBalanceView(viewEvent(watched, 1n, 100n), 'sig-1')?.delta).toBeUndefined();
+        expect(wallet.applyBalanceView(viewEvent(watched, 2n, 250n), 'sig-2')?.delta).toBe(150n);
+        expect(wallet.applyBalanceView(viewEvent(watched, 3n, 50n), 'sig-3')?.delta).toBe(-200n);
+
+        expect(wallet.getBalances().get(watched.toBase58())).toBe(50n);
+        const opening = store.getOpening(watched.toBase58())!;
+        const blinding = ScalarOps.fromBytes(opening.blinding);
+        expect(PedersenCommitment.commit(50n, blinding).toBytes()).toEqual(opening.commitment);
+
+        const received = wallet.getHistory().filter((record) => record.type === 'receive');
+        expect(received.map((record) => record.signature)).toEqual(['sig-2']);
+    });
+
+    test('Ignores stale views, other keys and unwatched accounts', () => {
+        wallet.applyBalanceView(viewEvent(watched, 5n, 100n), 'sig-1');
+
+        expect(wallet.applyBalanceView(viewEvent(watched, 4n, 999n), 'old')).toBeNull();
+        const otherKey = viewingPublicKey(deriveViewingSecret(new Uint8Array(32).fill(2)));
+        expect(wallet.applyBalanceView(viewEvent(watched, 6n, 999n, otherKey), 'other')).toBeNull();
+        expect(wallet.applyBalanceView(viewEvent(Keypair.generate().publicKey, 1n, 999n), 'x')).toBeNull();
+        expect(wallet.getBalances().get(watched.toBase58())).toBe(100n);
+    });
+
+    test('Refuses every spend operation', () => {
+        expect(wallet.isWatchOnly()).toBe(true);
+        try {
+            wallet.assertCanSpend('transfer');
+            throw new Error('expected a watch-only error');
+        } catch (error) {
+            expect(error).toBeInstanceOf(UTXOError);
+            expect((error as UTXOError).code).toBe(ErrorCode.WATCH_ONLY);
+            expect((error as UTXOError).message).toContain('Cannot transfer');
+        }
+    });
+});